@@ -439,6 +439,22 @@ pub struct Config {
     )]
     pub semantic_depth: usize,
 
+    /// Language pack directories to load for semantic analysis
+    #[arg(
+        long = "language-pack",
+        value_name = "DIR",
+        help = "Load a tree-sitter language pack, or a directory of packs, for semantic analysis"
+    )]
+    pub language_packs: Vec<PathBuf>,
+
+    /// Extra C and C++ include directories for semantic analysis
+    #[arg(
+        long = "include-dir",
+        value_name = "DIR",
+        help = "Search DIR for C and C++ #include targets, after the compilation database paths"
+    )]
+    pub include_dirs: Vec<PathBuf>,
+
     /// Start MCP server mode
     #[arg(long, help = "Start MCP server mode")]
     pub mcp: bool,
@@ -505,6 +521,8 @@ impl Default for Config {
             include_callers: false,
             include_types: false,
             semantic_depth: 5,
+            language_packs: vec![],
+            include_dirs: vec![],
             mcp: false,
             mcp_port: 9090,
             rmcp: false,
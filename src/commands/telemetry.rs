@@ -246,6 +246,7 @@ pub fn run_telemetry(config: Config) -> Result<()> {
         trace_imports: false,
         include_types: false,
         include_functions: true,
+        include_dirs: config.include_dirs.clone(),
     };
 
     // Get file paths for analysis
@@ -28,6 +28,10 @@ pub struct ConfigFile {
     #[serde(default)]
     pub include: Vec<String>,
 
+    /// Extra C and C++ include directories for semantic analysis
+    #[serde(default)]
+    pub include_dirs: Vec<PathBuf>,
+
     /// Token limits for different LLM tools
     #[serde(default)]
     pub tokens: TokenLimits,
@@ -204,6 +208,11 @@ impl ConfigFile {
         if cli_config.include.is_none() && !self.include.is_empty() {
             cli_config.include = Some(self.include.clone());
         }
+
+        // Apply include directories from config file if none were given on the CLI
+        if cli_config.include_dirs.is_empty() {
+            cli_config.include_dirs = self.include_dirs.clone();
+        }
     }
 }
 
@@ -258,6 +267,7 @@ pub fn create_example_config() -> String {
             ".env".to_string(),
         ],
         include: vec!["!important/**".to_string()],
+        include_dirs: vec![],
     };
 
     toml::to_string_pretty(&example)
@@ -342,6 +352,7 @@ progress = true
             priorities: vec![],
             ignore: vec![],
             include: vec![],
+            include_dirs: vec![],
         };
 
         let mut cli_config = CliConfig {
@@ -442,6 +453,7 @@ max_tokens = 200000
             priorities: vec![],
             ignore: vec![],
             include: vec![],
+            include_dirs: vec![],
         };
 
         let mut cli_config = CliConfig {
@@ -678,6 +678,9 @@ pub fn file_type_display(file_type: &FileType) -> &'static str {
         FileType::Julia => "Julia",
         FileType::Elixir => "Elixir",
         FileType::Elm => "Elm",
+        FileType::Vue => "Vue",
+        FileType::Svelte => "Svelte",
+        FileType::Astro => "Astro",
         FileType::Markdown => "Markdown",
         FileType::Json => "JSON",
         FileType::Yaml => "YAML",
@@ -714,6 +717,9 @@ pub fn get_language_hint(file_type: &FileType) -> &'static str {
         FileType::Julia => "julia",
         FileType::Elixir => "elixir",
         FileType::Elm => "elm",
+        FileType::Vue => "vue",
+        FileType::Svelte => "svelte",
+        FileType::Astro => "astro",
         FileType::Markdown => "markdown",
         FileType::Json => "json",
         FileType::Yaml => "yaml",
@@ -750,6 +756,9 @@ fn file_type_priority(file_type: &FileType) -> u8 {
         FileType::Julia => 18,
         FileType::Elixir => 19,
         FileType::Elm => 20,
+        FileType::Vue => 3,
+        FileType::Svelte => 3,
+        FileType::Astro => 3,
         FileType::Markdown => 21,
         FileType::Json => 22,
         FileType::Yaml => 23,
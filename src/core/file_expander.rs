@@ -5,10 +5,14 @@
 
 use crate::cli::Config;
 use crate::core::cache::FileCache;
+pub use crate::core::project_root::detect_project_root;
+use crate::core::semantic::analyzer::{Import, SemanticContext, SymbolKind};
 use crate::core::semantic::function_call_index::FunctionCallIndex;
+use crate::core::semantic::languages::c::c_implementation_files;
 use crate::core::semantic::path_validator::validate_import_path;
+use crate::core::semantic::symbol_graph::{SymbolGraphBuilder, SymbolLink};
 use crate::core::semantic::type_resolver::{ResolutionLimits, TypeResolver};
-use crate::core::walker::{walk_directory, FileInfo};
+use crate::core::walker::{walk_ci_workflow_files, walk_directory, FileInfo};
 use crate::utils::error::ContextCreatorError;
 use ignore::gitignore::{Gitignore, GitignoreBuilder};
 use std::collections::{HashMap, HashSet, VecDeque};
@@ -35,67 +39,6 @@ fn build_ignore_matcher(
     builder.build().ok()
 }
 
-/// Detect the project root directory using git root or fallback methods
-pub fn detect_project_root(start_path: &Path) -> PathBuf {
-    // If start_path is a file, start from its parent directory
-    let start_dir = if start_path.is_file() {
-        start_path.parent().unwrap_or(start_path)
-    } else {
-        start_path
-    };
-
-    // First try to find git root
-    let mut current = start_dir;
-    loop {
-        if current.join(".git").exists() {
-            return absolute_project_root(current);
-        }
-        if let Some(parent) = current.parent() {
-            current = parent;
-        } else {
-            break;
-        }
-    }
-
-    // Fallback: Look for common project markers
-    current = start_dir;
-    loop {
-        // Check for Rust project markers
-        if current.join("Cargo.toml").exists() {
-            return absolute_project_root(current);
-        }
-        // Check for Node.js project markers
-        if current.join("package.json").exists() {
-            return absolute_project_root(current);
-        }
-        // Check for Python project markers
-        if current.join("pyproject.toml").exists() || current.join("setup.py").exists() {
-            return absolute_project_root(current);
-        }
-        // Check for Go project markers
-        if current.join("go.mod").exists() {
-            return absolute_project_root(current);
-        }
-        // Check for Swift Package Manager project markers
-        if current.join("Package.swift").exists() {
-            return absolute_project_root(current);
-        }
-        // Check for generic project markers
-        if current.join("README.md").exists() || current.join("readme.md").exists() {
-            return absolute_project_root(current);
-        }
-
-        if let Some(parent) = current.parent() {
-            current = parent;
-        } else {
-            break;
-        }
-    }
-
-    // Ultimate fallback: use the start directory
-    absolute_project_root(start_dir)
-}
-
 fn configured_input_root(config: &Config) -> Option<&Path> {
     config
         .directories
@@ -120,18 +63,6 @@ fn current_dir_project_root_for_files(files_map: &HashMap<PathBuf, FileInfo>) ->
     all_files_under_current_project.then_some(project_root)
 }
 
-fn absolute_project_root(path: &Path) -> PathBuf {
-    path.canonicalize().unwrap_or_else(|_| {
-        if path.is_absolute() {
-            path.to_path_buf()
-        } else {
-            std::env::current_dir()
-                .map(|current_dir| current_dir.join(path))
-                .unwrap_or_else(|_| path.to_path_buf())
-        }
-    })
-}
-
 fn find_importers_of_files(
     project_files: &[FileInfo],
     target_files: &[PathBuf],
@@ -202,6 +133,7 @@ pub fn expand_file_list_with_context(
         walk_options,
         Some(all_files_context),
     )
+    .map(|(files_map, _)| files_map)
 }
 
 /// Expand file list based on semantic relationships
@@ -215,6 +147,237 @@ pub fn expand_file_list(
     walk_options: &crate::core::walker::WalkOptions,
 ) -> Result<HashMap<PathBuf, FileInfo>, ContextCreatorError> {
     expand_file_list_internal(files_map, config, cache, walk_options, None)
+        .map(|(files_map, _)| files_map)
+}
+
+/// Expand file list based on semantic relationships, recording why each file was added
+///
+/// Returns the expanded file list together with one [`FileInclusion`] per added file, sorted by
+/// path. `all_files_context` is the analyzed project, as for [`expand_file_list_with_context`].
+pub fn expand_file_list_with_inclusions(
+    files_map: HashMap<PathBuf, FileInfo>,
+    config: &Config,
+    cache: &Arc<FileCache>,
+    walk_options: &crate::core::walker::WalkOptions,
+    all_files_context: Option<&HashMap<PathBuf, FileInfo>>,
+) -> Result<(HashMap<PathBuf, FileInfo>, Vec<FileInclusion>), ContextCreatorError> {
+    expand_file_list_internal(files_map, config, cache, walk_options, all_files_context)
+}
+
+/// How an added file relates to the file it was included for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InclusionKind {
+    /// The source file imports it
+    Import,
+    /// It defines a type the source file uses
+    Type,
+    /// It calls a function of the source file, one of the initial files
+    Caller,
+    /// It declares the Rust module of the source file
+    ModuleDeclaration,
+    /// It belongs to the Go package or Swift module of the source file, or implements the
+    /// C or C++ header
+    Companion,
+}
+
+/// Why semantic expansion added a file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileInclusion {
+    /// File added by the expansion
+    pub path: PathBuf,
+    /// File it was included for: the file using it, or the initial file a caller calls
+    pub source: PathBuf,
+    /// How the two files relate
+    pub kind: InclusionKind,
+    /// Symbol behind the relation: the imported items, the used type, or the called function
+    pub symbol: Option<String>,
+    /// Line of the import, type use, or call in the file using the other
+    pub line: Option<usize>,
+    /// Symbol edges from the using file to the used one, filled in by [`explain_inclusions`]
+    /// with those to `symbol` first
+    pub links: Vec<SymbolLink>,
+}
+
+impl FileInclusion {
+    /// One-line explanation naming the linking symbols, or the source file and the recorded
+    /// symbol when there is no symbol-level link
+    pub fn reason(&self) -> String {
+        match self.links.as_slice() {
+            [] => {}
+            [link] => return link.to_string(),
+            [link, rest @ ..] => return format!("{link} (+{} more)", rest.len()),
+        }
+
+        let source = self
+            .source
+            .file_name()
+            .unwrap_or(self.source.as_os_str())
+            .to_string_lossy();
+        let line = self
+            .line
+            .map(|line| format!(" (line {line})"))
+            .unwrap_or_default();
+        match (self.kind, &self.symbol) {
+            (InclusionKind::Import, Some(symbol)) => {
+                format!("imported by {source} for `{symbol}`{line}")
+            }
+            (InclusionKind::Import, None) => format!("imported by {source}{line}"),
+            (InclusionKind::Type, Some(symbol)) => {
+                format!("defines type `{symbol}` used by {source}{line}")
+            }
+            (InclusionKind::Type, None) => format!("defines a type used by {source}{line}"),
+            (InclusionKind::Caller, Some(symbol)) => {
+                format!("calls `{symbol}` from {source}{line}")
+            }
+            (InclusionKind::Caller, None) => format!("depends on {source}"),
+            (InclusionKind::ModuleDeclaration, _) => {
+                format!("declares the module of {source}")
+            }
+            (InclusionKind::Companion, _) => format!("compiled with {source}"),
+        }
+    }
+}
+
+/// Link the recorded inclusions to the symbols behind them
+///
+/// The files on either side of an inclusion form a symbol graph, from which each inclusion
+/// gets the calls and type references of its using file into its used file: the source into
+/// the added file, or a caller into the initial file it calls. Module declarations and
+/// companion files are included for the file they belong to and get no links.
+pub fn explain_inclusions(
+    inclusions: &mut [FileInclusion],
+    files_map: &HashMap<PathBuf, FileInfo>,
+    cache: &Arc<FileCache>,
+) {
+    let linked_files = |inclusion: &FileInclusion| match inclusion.kind {
+        InclusionKind::Import | InclusionKind::Type => {
+            Some((inclusion.source.clone(), inclusion.path.clone()))
+        }
+        InclusionKind::Caller => Some((inclusion.path.clone(), inclusion.source.clone())),
+        InclusionKind::ModuleDeclaration | InclusionKind::Companion => None,
+    };
+
+    let mut paths: Vec<PathBuf> = inclusions
+        .iter()
+        .filter_map(linked_files)
+        .flat_map(|(from, to)| [from, to])
+        .collect();
+    paths.sort();
+    paths.dedup();
+
+    let mut builder = SymbolGraphBuilder::new();
+    for path in &paths {
+        let imports = files_map
+            .get(path)
+            .map(|file| file.imports.as_slice())
+            .unwrap_or_default();
+        if let Ok(content) = cache.get_or_load(path) {
+            if let Err(e) = builder.add_file(path, &content, imports) {
+                tracing::debug!("Skipping symbols of {}: {}", path.display(), e);
+            }
+        }
+    }
+    let graph = builder.build();
+
+    for inclusion in inclusions.iter_mut() {
+        let Some((from, to)) = linked_files(inclusion) else {
+            continue;
+        };
+        let mut links = graph.links_between(&from, &to);
+        if let Some(symbol) = &inclusion.symbol {
+            // Imported items are recorded as `a, b`; links from definitions stay ahead of those
+            // from top-level code such as the import itself
+            let names: Vec<&str> = symbol.split(", ").collect();
+            links.sort_by_key(|link| {
+                (
+                    link.from.kind == SymbolKind::Module,
+                    !names.contains(&link.to.name.as_str()),
+                )
+            });
+        }
+        inclusion.links = links;
+    }
+}
+
+/// Where a file imports another: the imported items, if named, and the line
+#[derive(Debug, Clone)]
+struct ImportSite {
+    symbol: Option<String>,
+    line: usize,
+}
+
+/// Import sites keyed by the importing file and the resolved imported file
+type ImportSites = HashMap<(PathBuf, PathBuf), ImportSite>;
+
+/// The context for analyzing a file, carrying the configured include directories
+fn semantic_context(path: &Path, project_root: &Path, config: &Config) -> SemanticContext {
+    SemanticContext::new(
+        path.to_path_buf(),
+        project_root.to_path_buf(),
+        config.semantic_depth,
+    )
+    .with_include_dirs(config.include_dirs.clone())
+}
+
+/// Resolve the imports of an analyzed file to paths, remembering where each was imported
+fn resolve_imports(
+    imports: &[Import],
+    context: &SemanticContext,
+    import_sites: &mut ImportSites,
+) -> Vec<PathBuf> {
+    imports
+        .iter()
+        .filter_map(|imp| {
+            let resolved = resolve_import_to_path(&imp.module, context)?;
+            import_sites
+                .entry((context.current_file.clone(), resolved.clone()))
+                .or_insert_with(|| ImportSite {
+                    symbol: imported_symbol(imp, &resolved),
+                    line: imp.line,
+                });
+            Some(resolved)
+        })
+        .collect()
+}
+
+/// The items an import names, or the item at the end of a Rust path such as
+/// `crate::db::save` that resolved to the file of its module
+fn imported_symbol(import: &Import, resolved: &Path) -> Option<String> {
+    if !import.items.is_empty() {
+        return Some(import.items.join(", "));
+    }
+    let (_, item) = import.module.rsplit_once("::")?;
+    let module_name = match resolved.file_stem()?.to_str()? {
+        "mod" => resolved.parent()?.file_name()?.to_str()?,
+        stem => stem,
+    };
+    (item != module_name && item != "*").then(|| item.to_string())
+}
+
+/// The first call in `caller`, by line, of a function exported by one of `targets`
+fn called_export(
+    caller_path: &Path,
+    caller: &FileInfo,
+    targets: &[(&PathBuf, &FileInfo)],
+) -> Option<FileInclusion> {
+    let mut calls: Vec<_> = caller.function_calls.iter().collect();
+    calls.sort_by_key(|call| call.line);
+    calls.into_iter().find_map(|call| {
+        targets.iter().find_map(|(path, target)| {
+            target
+                .exported_functions
+                .iter()
+                .any(|function| function.is_exported && function.name == call.name)
+                .then(|| FileInclusion {
+                    path: caller_path.to_path_buf(),
+                    source: (*path).clone(),
+                    kind: InclusionKind::Caller,
+                    symbol: Some(call.name.clone()),
+                    line: Some(call.line),
+                    links: Vec::new(),
+                })
+        })
+    })
 }
 
 /// Internal implementation of expand_file_list with optional context
@@ -224,10 +387,10 @@ fn expand_file_list_internal(
     cache: &Arc<FileCache>,
     walk_options: &crate::core::walker::WalkOptions,
     all_files_context: Option<&HashMap<PathBuf, FileInfo>>,
-) -> Result<HashMap<PathBuf, FileInfo>, ContextCreatorError> {
+) -> Result<(HashMap<PathBuf, FileInfo>, Vec<FileInclusion>), ContextCreatorError> {
     // If no semantic features are enabled, return as-is
     if !config.trace_imports && !config.include_callers && !config.include_types {
-        return Ok(files_map);
+        return Ok((files_map, Vec::new()));
     }
 
     // Detect the project root for secure path validation
@@ -247,10 +410,10 @@ fn expand_file_list_internal(
         cache,
         all_files_context,
     };
+    let mut import_sites = ImportSites::new();
 
     // First, perform semantic analysis on the initial files if needed
     if config.trace_imports || config.include_types {
-        use crate::core::semantic::analyzer::SemanticContext;
         use crate::core::semantic::get_analyzer_for_file;
 
         for (path, file_info) in files_map.iter_mut() {
@@ -262,13 +425,7 @@ fn expand_file_list_internal(
             // Read file content and analyze
             if let Ok(content) = cache.get_or_load(path) {
                 if let Ok(Some(analyzer)) = get_analyzer_for_file(path) {
-                    let context = SemanticContext {
-                        current_file: path.clone(),
-                        base_dir: project_root.clone(),
-                        current_depth: 0,
-                        max_depth: config.semantic_depth,
-                        visited_files: HashSet::new(),
-                    };
+                    let context = semantic_context(path, &project_root, config);
 
                     if let Ok(analysis) = analyzer.analyze_requested(
                         path,
@@ -280,14 +437,16 @@ fn expand_file_list_internal(
                         config.include_callers,
                     ) {
                         // Convert imports to resolved file paths
-                        file_info.imports = analysis
-                            .imports
-                            .iter()
-                            .filter_map(|imp| {
-                                // Try to resolve import to file path
-                                resolve_import_to_path(&imp.module, path, &project_root)
-                            })
-                            .collect();
+                        file_info.imports =
+                            resolve_imports(&analysis.imports, &context, &mut import_sites);
+                        if config.trace_imports {
+                            add_schema_references(
+                                &mut file_info.imports,
+                                path,
+                                &content,
+                                &project_root,
+                            );
+                        }
                         file_info.function_calls = analysis.function_calls;
                         file_info.type_references = analysis.type_references;
 
@@ -309,8 +468,11 @@ fn expand_file_list_internal(
     let mut visited_paths = HashSet::new();
     let mut files_to_add = Vec::new();
 
-    // Initialize with files that have semantic relationships
-    for (path, file_info) in &files_map {
+    // Initialize with files that have semantic relationships, in path order so the
+    // expansion and its recorded reasons don't depend on hash order
+    let mut initial_files: Vec<(&PathBuf, &FileInfo)> = files_map.iter().collect();
+    initial_files.sort_by_key(|(path, _)| *path);
+    for &(path, file_info) in &initial_files {
         visited_paths.insert(path.clone());
 
         // Queue files based on enabled features (depth 0 for initial files)
@@ -338,8 +500,12 @@ fn expand_file_list_internal(
             let mut project_walk_options = walk_options.clone();
             project_walk_options.include_patterns.clear(); // Search entire project
 
-            let mut all_project_files = walk_directory(&project_root, project_walk_options)
+            let mut all_project_files = walk_directory(&project_root, project_walk_options.clone())
                 .map_err(|e| ContextCreatorError::ContextGenerationError(e.to_string()))?;
+            all_project_files.extend(
+                walk_ci_workflow_files(&project_root, &project_walk_options)
+                    .map_err(|e| ContextCreatorError::ContextGenerationError(e.to_string()))?,
+            );
 
             // Perform semantic analysis on project files
             crate::core::semantic_graph::perform_semantic_analysis_graph_with_root(
@@ -357,9 +523,14 @@ fn expand_file_list_internal(
         let function_call_index = FunctionCallIndex::build(&project_files);
 
         // Find all callers of functions exported by our initial files
-        let initial_files: Vec<PathBuf> = files_map.keys().cloned().collect();
-        let mut caller_paths = function_call_index.find_callers_of_files(&initial_files);
-        caller_paths.extend(find_importers_of_files(&project_files, &initial_files));
+        let initial_paths: Vec<PathBuf> = initial_files
+            .iter()
+            .map(|(path, _)| (*path).clone())
+            .collect();
+        let mut caller_paths = function_call_index.find_callers_of_files(&initial_paths);
+        caller_paths.extend(find_importers_of_files(&project_files, &initial_paths));
+        let mut caller_paths: Vec<PathBuf> = caller_paths.into_iter().collect();
+        caller_paths.sort();
 
         // Add caller files while respecting security boundaries
         for caller_path in caller_paths {
@@ -386,8 +557,39 @@ fn expand_file_list_internal(
                     };
 
                     if let Some(caller_info) = caller_info {
+                        let inclusion = called_export(&caller_path, &caller_info, &initial_files)
+                            .unwrap_or_else(|| FileInclusion {
+                                path: caller_path.clone(),
+                                source: initial_paths
+                                    .iter()
+                                    .find(|initial| caller_info.imports.contains(initial))
+                                    .unwrap_or(&initial_paths[0])
+                                    .clone(),
+                                kind: InclusionKind::Caller,
+                                symbol: None,
+                                line: None,
+                                links: Vec::new(),
+                            });
                         visited_paths.insert(caller_path.clone());
-                        files_to_add.push((caller_path, caller_info));
+                        add_companion_files(
+                            &caller_path,
+                            &caller_path,
+                            CompanionExpansionContext {
+                                project_root: &project_root,
+                                cache,
+                                config,
+                                all_files_context,
+                                depth: 0,
+                                whole_package: false,
+                            },
+                            ExpansionQueues {
+                                visited_paths: &mut visited_paths,
+                                files_to_add: &mut files_to_add,
+                                work_queue: &mut work_queue,
+                                import_sites: &mut import_sites,
+                            },
+                        )?;
+                        files_to_add.push((caller_info, inclusion));
                     }
                 }
             }
@@ -497,20 +699,37 @@ fn expand_file_list_internal(
                                         &mut visited_paths,
                                         &mut files_to_add,
                                     )?;
+                                    add_companion_files(
+                                        &validated_path,
+                                        &source_path,
+                                        CompanionExpansionContext {
+                                            project_root: &project_root,
+                                            cache,
+                                            config,
+                                            all_files_context,
+                                            depth,
+                                            whole_package: false,
+                                        },
+                                        ExpansionQueues {
+                                            visited_paths: &mut visited_paths,
+                                            files_to_add: &mut files_to_add,
+                                            work_queue: &mut work_queue,
+                                            import_sites: &mut import_sites,
+                                        },
+                                    )?;
 
                                     // Perform semantic analysis on the newly found file to get its type references
                                     if depth + 1 < config.semantic_depth {
                                         if let Ok(content) = cache.get_or_load(&validated_path) {
-                                            use crate::core::semantic::analyzer::SemanticContext;
                                             use crate::core::semantic::get_analyzer_for_file;
 
                                             if let Ok(Some(analyzer)) =
                                                 get_analyzer_for_file(&validated_path)
                                             {
-                                                let context = SemanticContext::new(
-                                                    validated_path.clone(),
-                                                    project_root.clone(),
-                                                    config.semantic_depth,
+                                                let context = semantic_context(
+                                                    &validated_path,
+                                                    &project_root,
+                                                    config,
                                                 );
 
                                                 if let Ok(analysis) = analyzer.analyze_requested(
@@ -540,7 +759,17 @@ fn expand_file_list_internal(
                                         }
                                     }
 
-                                    files_to_add.push((validated_path.clone(), file_info));
+                                    files_to_add.push((
+                                        file_info,
+                                        FileInclusion {
+                                            path: validated_path.clone(),
+                                            source: source_path.clone(),
+                                            kind: InclusionKind::Type,
+                                            symbol: Some(type_ref.name.clone()),
+                                            line: Some(type_ref.line),
+                                            links: Vec::new(),
+                                        },
+                                    ));
                                 }
                                 Err(_) => {
                                     // Path validation failed, skip this file
@@ -599,21 +828,38 @@ fn expand_file_list_internal(
                                             &mut visited_paths,
                                             &mut files_to_add,
                                         )?;
+                                        add_companion_files(
+                                            &validated_path,
+                                            &source_path,
+                                            CompanionExpansionContext {
+                                                project_root: &project_root,
+                                                cache,
+                                                config,
+                                                all_files_context,
+                                                depth,
+                                                whole_package: false,
+                                            },
+                                            ExpansionQueues {
+                                                visited_paths: &mut visited_paths,
+                                                files_to_add: &mut files_to_add,
+                                                work_queue: &mut work_queue,
+                                                import_sites: &mut import_sites,
+                                            },
+                                        )?;
 
                                         // Perform semantic analysis on the newly found file to get its type references
                                         if depth + 1 < config.semantic_depth {
                                             if let Ok(content) = cache.get_or_load(&validated_path)
                                             {
-                                                use crate::core::semantic::analyzer::SemanticContext;
                                                 use crate::core::semantic::get_analyzer_for_file;
 
                                                 if let Ok(Some(analyzer)) =
                                                     get_analyzer_for_file(&validated_path)
                                                 {
-                                                    let context = SemanticContext::new(
-                                                        validated_path.clone(),
-                                                        project_root.clone(),
-                                                        config.semantic_depth,
+                                                    let context = semantic_context(
+                                                        &validated_path,
+                                                        &project_root,
+                                                        config,
                                                     );
 
                                                     if let Ok(analysis) = analyzer
@@ -645,7 +891,17 @@ fn expand_file_list_internal(
                                             }
                                         }
 
-                                        files_to_add.push((validated_path, file_info));
+                                        files_to_add.push((
+                                            file_info,
+                                            FileInclusion {
+                                                path: validated_path,
+                                                source: source_path.clone(),
+                                                kind: InclusionKind::Type,
+                                                symbol: Some(type_ref.name.clone()),
+                                                line: Some(type_ref.line),
+                                                links: Vec::new(),
+                                            },
+                                        ));
                                     }
                                     Err(_) => {
                                         // Path validation failed, skip this file
@@ -695,36 +951,22 @@ fn expand_file_list_internal(
                                 &mut visited_paths,
                                 &mut files_to_add,
                             )?;
-                            add_go_package_files(
+                            add_companion_files(
                                 &validated_path,
                                 &source_path,
-                                GoPackageExpansionContext {
-                                    project_root: &project_root,
-                                    cache,
-                                    config,
-                                    all_files_context,
-                                    depth,
-                                },
-                                ExpansionQueues {
-                                    visited_paths: &mut visited_paths,
-                                    files_to_add: &mut files_to_add,
-                                    work_queue: &mut work_queue,
-                                },
-                            )?;
-                            add_swift_module_files(
-                                &validated_path,
-                                &source_path,
-                                SwiftModuleExpansionContext {
+                                CompanionExpansionContext {
                                     project_root: &project_root,
                                     cache,
                                     config,
                                     all_files_context,
                                     depth,
+                                    whole_package: true,
                                 },
                                 ExpansionQueues {
                                     visited_paths: &mut visited_paths,
                                     files_to_add: &mut files_to_add,
                                     work_queue: &mut work_queue,
+                                    import_sites: &mut import_sites,
                                 },
                             )?;
 
@@ -794,16 +1036,15 @@ fn expand_file_list_internal(
                                     }
                                 } else if let Ok(content) = cache.get_or_load(&validated_path) {
                                     // Perform semantic analysis on the imported file
-                                    use crate::core::semantic::analyzer::SemanticContext;
                                     use crate::core::semantic::get_analyzer_for_file;
 
                                     if let Ok(Some(analyzer)) =
                                         get_analyzer_for_file(&validated_path)
                                     {
-                                        let context = SemanticContext::new(
-                                            validated_path.clone(),
-                                            project_root.clone(),
-                                            config.semantic_depth,
+                                        let context = semantic_context(
+                                            &validated_path,
+                                            &project_root,
+                                            config,
                                         );
 
                                         if let Ok(analysis) = analyzer.analyze_requested(
@@ -816,18 +1057,19 @@ fn expand_file_list_internal(
                                             config.include_callers,
                                         ) {
                                             // Update file info with semantic data
-                                            file_info.imports = analysis
-                                                .imports
-                                                .iter()
-                                                .filter_map(|imp| {
-                                                    // Try to resolve import to file path
-                                                    resolve_import_to_path(
-                                                        &imp.module,
-                                                        &validated_path,
-                                                        &project_root,
-                                                    )
-                                                })
-                                                .collect();
+                                            file_info.imports = resolve_imports(
+                                                &analysis.imports,
+                                                &context,
+                                                &mut import_sites,
+                                            );
+                                            if config.trace_imports {
+                                                add_schema_references(
+                                                    &mut file_info.imports,
+                                                    &validated_path,
+                                                    &content,
+                                                    &project_root,
+                                                );
+                                            }
                                             file_info.function_calls = analysis.function_calls;
                                             file_info.type_references = analysis.type_references;
 
@@ -845,7 +1087,19 @@ fn expand_file_list_internal(
                                 }
                             }
 
-                            files_to_add.push((validated_path, file_info));
+                            let site =
+                                import_sites.get(&(source_path.clone(), import_path.clone()));
+                            files_to_add.push((
+                                file_info,
+                                FileInclusion {
+                                    path: validated_path,
+                                    source: source_path.clone(),
+                                    kind: InclusionKind::Import,
+                                    symbol: site.and_then(|site| site.symbol.clone()),
+                                    line: site.map(|site| site.line),
+                                    links: Vec::new(),
+                                },
+                            ));
                         }
                         Err(_) => {
                             // Path validation failed, skip this import
@@ -863,8 +1117,10 @@ fn expand_file_list_internal(
     }
 
     // Add new files to the map
-    for (path, file_info) in files_to_add {
-        files_map.insert(path, file_info);
+    let mut inclusions = Vec::with_capacity(files_to_add.len());
+    for (file_info, inclusion) in files_to_add {
+        files_map.insert(inclusion.path.clone(), file_info);
+        inclusions.push(inclusion);
     }
 
     // Update imported_by relationships for proper prioritization
@@ -888,7 +1144,10 @@ fn expand_file_list_internal(
         }
     }
 
-    Ok(files_map)
+    inclusions.retain(|inclusion| files_map.contains_key(&inclusion.path));
+    inclusions.sort_by(|left, right| left.path.cmp(&right.path));
+
+    Ok((files_map, inclusions))
 }
 
 fn normalize_initial_file_paths(
@@ -936,114 +1195,59 @@ fn merge_unique_paths(target: &mut Vec<PathBuf>, source: &[PathBuf]) {
     }
 }
 
-struct GoPackageExpansionContext<'a> {
-    project_root: &'a Path,
-    cache: &'a Arc<FileCache>,
-    config: &'a Config,
-    all_files_context: Option<&'a HashMap<PathBuf, FileInfo>>,
-    depth: usize,
+/// Add the Protobuf, GraphQL, OpenAPI, and SQL schemas a source file uses to its imports
+fn add_schema_references(
+    imports: &mut Vec<PathBuf>,
+    path: &Path,
+    content: &str,
+    project_root: &Path,
+) {
+    let schemas: Vec<PathBuf> =
+        crate::core::semantic::languages::schema::schema_references(path, content, project_root)
+            .into_iter()
+            .map(|(schema, _)| schema)
+            .collect();
+    merge_unique_paths(imports, &schemas);
 }
 
-struct SwiftModuleExpansionContext<'a> {
+struct CompanionExpansionContext<'a> {
     project_root: &'a Path,
     cache: &'a Arc<FileCache>,
     config: &'a Config,
     all_files_context: Option<&'a HashMap<PathBuf, FileInfo>>,
     depth: usize,
+    /// Whether the rest of a Go package or Swift module is added; an imported package is
+    /// used as a whole, while a type or a caller is used through its own file
+    whole_package: bool,
 }
 
 struct ExpansionQueues<'a> {
     visited_paths: &'a mut HashSet<PathBuf>,
-    files_to_add: &'a mut Vec<(PathBuf, FileInfo)>,
+    files_to_add: &'a mut Vec<(FileInfo, FileInclusion)>,
     work_queue: &'a mut VecDeque<(PathBuf, FileInfo, ExpansionReason, usize)>,
+    import_sites: &'a mut ImportSites,
 }
 
-fn add_go_package_files(
-    go_file: &Path,
-    source_path: &Path,
-    context: GoPackageExpansionContext<'_>,
-    queues: ExpansionQueues<'_>,
-) -> Result<(), ContextCreatorError> {
-    if go_file.extension() != Some(std::ffi::OsStr::new("go")) {
-        return Ok(());
+/// Files that only make sense together with an expanded file: the rest of its Go package
+/// or Swift module, or the `.c`/`.cpp` file implementing an included header, so the
+/// function bodies behind its declarations are in context.
+fn companion_files(path: &Path, project_root: &Path, whole_package: bool) -> Vec<PathBuf> {
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some("go") if whole_package => go_package_files(path),
+        Some("swift") if whole_package => swift_module_files(path, project_root),
+        Some("go" | "swift") => Vec::new(),
+        _ => c_implementation_files(path, project_root),
     }
-
-    for package_file in go_package_files(go_file) {
-        let validated_path = match validate_import_path(context.project_root, &package_file) {
-            Ok(path) => path,
-            Err(_) => continue,
-        };
-
-        let canonical = validated_path
-            .canonicalize()
-            .unwrap_or_else(|_| validated_path.clone());
-        if queues.visited_paths.contains(&validated_path)
-            || queues.visited_paths.contains(&canonical)
-        {
-            continue;
-        }
-
-        queues.visited_paths.insert(validated_path.clone());
-        queues.visited_paths.insert(canonical);
-
-        let mut file_info = file_info_for_expanded_path(
-            &validated_path,
-            source_path,
-            context.all_files_context,
-            context.project_root,
-        )?;
-
-        if context.depth + 1 < context.config.semantic_depth {
-            if file_info.imports.is_empty()
-                && file_info.type_references.is_empty()
-                && file_info.function_calls.is_empty()
-            {
-                analyze_expanded_file_semantics(
-                    &validated_path,
-                    &mut file_info,
-                    context.project_root,
-                    context.cache,
-                    context.config,
-                )?;
-            }
-
-            if !file_info.imports.is_empty() {
-                queues.work_queue.push_back((
-                    validated_path.clone(),
-                    file_info.clone(),
-                    ExpansionReason::Imports,
-                    context.depth + 1,
-                ));
-            }
-
-            if context.config.include_types && !file_info.type_references.is_empty() {
-                queues.work_queue.push_back((
-                    validated_path.clone(),
-                    file_info.clone(),
-                    ExpansionReason::Types,
-                    context.depth + 1,
-                ));
-            }
-        }
-
-        queues.files_to_add.push((validated_path, file_info));
-    }
-
-    Ok(())
 }
 
-fn add_swift_module_files(
-    swift_file: &Path,
+fn add_companion_files(
+    path: &Path,
     source_path: &Path,
-    context: SwiftModuleExpansionContext<'_>,
+    context: CompanionExpansionContext<'_>,
     queues: ExpansionQueues<'_>,
 ) -> Result<(), ContextCreatorError> {
-    if swift_file.extension() != Some(std::ffi::OsStr::new("swift")) {
-        return Ok(());
-    }
-
-    for module_file in swift_module_files(swift_file, context.project_root) {
-        let validated_path = match validate_import_path(context.project_root, &module_file) {
+    for companion_file in companion_files(path, context.project_root, context.whole_package) {
+        let validated_path = match validate_import_path(context.project_root, &companion_file) {
             Ok(path) => path,
             Err(_) => continue,
         };
@@ -1053,6 +1257,7 @@ fn add_swift_module_files(
             .unwrap_or_else(|_| validated_path.clone());
         if queues.visited_paths.contains(&validated_path)
             || queues.visited_paths.contains(&canonical)
+            || paths_equivalent(&validated_path, source_path)
         {
             continue;
         }
@@ -1075,9 +1280,8 @@ fn add_swift_module_files(
                 analyze_expanded_file_semantics(
                     &validated_path,
                     &mut file_info,
-                    context.project_root,
-                    context.cache,
-                    context.config,
+                    &context,
+                    queues.import_sites,
                 )?;
             }
 
@@ -1100,7 +1304,17 @@ fn add_swift_module_files(
             }
         }
 
-        queues.files_to_add.push((validated_path, file_info));
+        queues.files_to_add.push((
+            file_info,
+            FileInclusion {
+                path: validated_path,
+                source: path.to_path_buf(),
+                kind: InclusionKind::Companion,
+                symbol: None,
+                line: None,
+                links: Vec::new(),
+            },
+        ));
     }
 
     Ok(())
@@ -1140,6 +1354,13 @@ fn swift_module_files(swift_file: &Path, project_root: &Path) -> Vec<PathBuf> {
 
     let mut files = Vec::new();
     collect_swift_source_files(&module_dir, &mut files);
+    files.retain(|file| {
+        crate::core::semantic::languages::swift::swift_target_includes_file(
+            &module_dir,
+            file,
+            project_root,
+        )
+    });
     files.sort();
     files
 }
@@ -1183,10 +1404,10 @@ fn is_swift_test_file(path: &Path) -> bool {
 fn analyze_expanded_file_semantics(
     path: &Path,
     file_info: &mut FileInfo,
-    project_root: &Path,
-    cache: &Arc<FileCache>,
-    config: &Config,
+    expansion: &CompanionExpansionContext<'_>,
+    import_sites: &mut ImportSites,
 ) -> Result<(), ContextCreatorError> {
+    let (project_root, cache, config) = (expansion.project_root, expansion.cache, expansion.config);
     let Ok(content) = cache.get_or_load(path) else {
         return Ok(());
     };
@@ -1195,11 +1416,7 @@ fn analyze_expanded_file_semantics(
         return Ok(());
     };
 
-    let context = crate::core::semantic::analyzer::SemanticContext::new(
-        path.to_path_buf(),
-        project_root.to_path_buf(),
-        config.semantic_depth,
-    );
+    let context = semantic_context(path, project_root, config);
 
     let Ok(analysis) = analyzer.analyze_requested(
         path,
@@ -1213,11 +1430,10 @@ fn analyze_expanded_file_semantics(
         return Ok(());
     };
 
-    file_info.imports = analysis
-        .imports
-        .iter()
-        .filter_map(|imp| resolve_import_to_path(&imp.module, path, project_root))
-        .collect();
+    file_info.imports = resolve_imports(&analysis.imports, &context, import_sites);
+    if config.trace_imports {
+        add_schema_references(&mut file_info.imports, path, &content, project_root);
+    }
     file_info.function_calls = analysis.function_calls;
     file_info.type_references = analysis.type_references;
     file_info.exported_functions = analysis.exported_functions;
@@ -1305,7 +1521,7 @@ fn add_rust_module_declaration_files(
     source_path: &Path,
     context: &RustModuleDeclarationContext<'_>,
     visited_paths: &mut HashSet<PathBuf>,
-    files_to_add: &mut Vec<(PathBuf, FileInfo)>,
+    files_to_add: &mut Vec<(FileInfo, FileInclusion)>,
 ) -> Result<(), ContextCreatorError> {
     if rust_file.extension() != Some(std::ffi::OsStr::new("rs")) {
         return Ok(());
@@ -1335,7 +1551,17 @@ fn add_rust_module_declaration_files(
             context.all_files_context,
             context.project_root,
         )?;
-        files_to_add.push((validated_path, file_info));
+        files_to_add.push((
+            file_info,
+            FileInclusion {
+                path: validated_path,
+                source: rust_file.to_path_buf(),
+                kind: InclusionKind::ModuleDeclaration,
+                symbol: None,
+                line: None,
+                links: Vec::new(),
+            },
+        ));
     }
 
     Ok(())
@@ -1758,14 +1984,13 @@ fn find_type_definition_file(
 }
 
 /// Resolve an import module name to a file path
-fn resolve_import_to_path(
-    module_name: &str,
-    importing_file: &Path,
-    project_root: &Path,
-) -> Option<PathBuf> {
+fn resolve_import_to_path(module_name: &str, context: &SemanticContext) -> Option<PathBuf> {
     // Use the semantic module resolver system
     use crate::core::semantic::get_module_resolver_for_file;
 
+    let (importing_file, project_root) =
+        (context.current_file.as_path(), context.base_dir.as_path());
+
     // Get the appropriate resolver for this file type
     let resolver = match get_module_resolver_for_file(importing_file) {
         Ok(Some(r)) => r,
@@ -1796,7 +2021,7 @@ fn resolve_import_to_path(
     };
 
     // Resolve the import
-    match resolver.resolve_import(module_name, importing_file, project_root) {
+    match resolver.resolve_import_in_context(module_name, importing_file, context) {
         Ok(resolved) => {
             if resolved.is_external {
                 // Skip external modules
@@ -2275,6 +2500,31 @@ mod tests {
     use super::*;
     use crate::utils::file_ext::FileType;
 
+    #[test]
+    fn test_inclusion_reason_without_symbol_links() {
+        let inclusion = |kind, symbol: Option<&str>, line| FileInclusion {
+            path: PathBuf::from("src/api.rs"),
+            source: PathBuf::from("src/db.rs"),
+            kind,
+            symbol: symbol.map(str::to_string),
+            line,
+            links: Vec::new(),
+        };
+
+        assert_eq!(
+            inclusion(InclusionKind::Caller, Some("save"), Some(6)).reason(),
+            "calls `save` from db.rs (line 6)"
+        );
+        assert_eq!(
+            inclusion(InclusionKind::Caller, None, None).reason(),
+            "depends on db.rs"
+        );
+        assert_eq!(
+            inclusion(InclusionKind::Import, Some("User"), Some(2)).reason(),
+            "imported by db.rs for `User` (line 2)"
+        );
+    }
+
     #[test]
     fn test_no_expansion_when_disabled() {
         let mut files_map = HashMap::new();
@@ -2318,6 +2568,55 @@ mod tests {
         assert_eq!(result.len(), 1);
     }
 
+    #[test]
+    fn test_expansion_searches_include_dirs_of_config() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path();
+        std::fs::create_dir(root.join(".git")).unwrap();
+        std::fs::create_dir_all(root.join("hal/include")).unwrap();
+        std::fs::create_dir_all(root.join("app")).unwrap();
+        std::fs::write(root.join("hal/include/hal.h"), "void hal_init(void);\n").unwrap();
+        let main_path = root.join("app/main.c");
+        std::fs::write(
+            &main_path,
+            "#include \"hal.h\"\n\nint main(void) { return 0; }\n",
+        )
+        .unwrap();
+
+        let expand = |include_dirs: Vec<PathBuf>| {
+            let files_map = HashMap::from([(
+                main_path.clone(),
+                FileInfo {
+                    path: main_path.clone(),
+                    relative_path: PathBuf::from("app/main.c"),
+                    size: 0,
+                    file_type: FileType::C,
+                    priority: 1.0,
+                    imports: Vec::new(),
+                    imported_by: Vec::new(),
+                    function_calls: Vec::new(),
+                    type_references: Vec::new(),
+                    exported_functions: Vec::new(),
+                },
+            )]);
+            let config = Config {
+                paths: Some(vec![root.to_path_buf()]),
+                trace_imports: true,
+                include_dirs,
+                ..Default::default()
+            };
+            let walk_options = crate::core::walker::WalkOptions::from_config(&config).unwrap();
+            let cache = Arc::new(FileCache::new());
+            expand_file_list(files_map, &config, &cache, &walk_options)
+                .unwrap()
+                .into_values()
+                .any(|file| file.relative_path == Path::new("hal/include/hal.h"))
+        };
+
+        assert!(expand(vec![PathBuf::from("hal/include")]));
+        assert!(!expand(Vec::new()));
+    }
+
     #[test]
     fn test_common_ancestor() {
         #[cfg(windows)]
@@ -5,6 +5,7 @@ pub mod context_builder;
 pub mod file_expander;
 pub mod prioritizer;
 pub mod project_analyzer;
+pub mod project_root;
 pub mod search;
 pub mod semantic;
 pub mod semantic_cache;
@@ -5,7 +5,7 @@
 
 use crate::cli::Config;
 use crate::core::cache::FileCache;
-use crate::core::walker::{walk_directory, FileInfo, WalkOptions};
+use crate::core::walker::{walk_ci_workflow_files, walk_directory, FileInfo, WalkOptions};
 use crate::utils::error::ContextCreatorError;
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
@@ -47,9 +47,15 @@ impl ProjectAnalysis {
             info!("Analyzing project from: {}", project_root.display());
         }
 
-        let mut all_files = walk_directory(&project_root, project_walk_options)
+        let mut all_files = walk_directory(&project_root, project_walk_options.clone())
             .map_err(|e| ContextCreatorError::ContextGenerationError(e.to_string()))?;
 
+        // Workflows are hidden but call the project's scripts
+        all_files.extend(
+            walk_ci_workflow_files(&project_root, &project_walk_options)
+                .map_err(|e| ContextCreatorError::ContextGenerationError(e.to_string()))?,
+        );
+
         // Perform semantic analysis once
         if config.trace_imports || config.include_callers || config.include_types {
             super::semantic_graph::perform_semantic_analysis_graph_with_root(
@@ -0,0 +1,194 @@
+//! Project root detection shared by semantic expansion and analysis
+//!
+//! The root is the closest ancestor holding `.git`, else the closest ancestor holding a
+//! project marker: a build, package, or workspace file of one of the supported languages.
+
+use std::collections::HashSet;
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+
+/// Files marking the root of a project
+const PROJECT_MARKER_FILES: &[&str] = &[
+    // Rust
+    "Cargo.toml",
+    // Node.js
+    "package.json",
+    // Python
+    "pyproject.toml",
+    "setup.py",
+    // Go; a module of a go.work workspace belongs to it
+    "go.mod",
+    "go.work",
+    // Swift Package Manager
+    "Package.swift",
+    // CMake and compilation databases
+    "CMakeLists.txt",
+    "compile_commands.json",
+    // Maven and Gradle
+    "pom.xml",
+    "build.gradle",
+    "build.gradle.kts",
+    "settings.gradle",
+    "settings.gradle.kts",
+    // sbt
+    "build.sbt",
+    // Dart and Flutter
+    "pubspec.yaml",
+    // Mix
+    "mix.exs",
+    // lua-language-server
+    ".luarc.json",
+    // Cabal, Stack, and hpack
+    "cabal.project",
+    "stack.yaml",
+    "package.yaml",
+    // R packages and renv
+    "DESCRIPTION",
+    "renv.lock",
+    // Julia
+    "Project.toml",
+    "JuliaProject.toml",
+    // Elm
+    "elm.json",
+    // Bundler
+    "Gemfile",
+    // Composer
+    "composer.json",
+    // Generic
+    "README.md",
+    "readme.md",
+];
+
+/// Extensions of files marking the root of a project: LuaRocks specifications, Cabal
+/// package descriptions, RStudio projects, and Visual Studio solutions
+const PROJECT_MARKER_EXTENSIONS: &[&str] = &["rockspec", "cabal", "Rproj", "sln"];
+
+/// Detect the project root directory using git root or fallback methods
+pub fn detect_project_root(start_path: &Path) -> PathBuf {
+    // If start_path is a file, start from its parent directory
+    let start_dir = if start_path.is_file() {
+        start_path.parent().unwrap_or(start_path)
+    } else {
+        start_path
+    };
+
+    // First try to find git root
+    if let Some(git_root) = start_dir.ancestors().find(|dir| dir.join(".git").exists()) {
+        return absolute_project_root(git_root);
+    }
+
+    // Fallback: Look for project markers
+    for dir in start_dir.ancestors() {
+        let entries = DirEntries::read(dir);
+        if entries.has_file_named("go.mod") || entries.has_file_named("go.work") {
+            return absolute_project_root(enclosing_go_workspace(dir).unwrap_or(dir));
+        }
+        if PROJECT_MARKER_FILES
+            .iter()
+            .any(|marker| entries.has_file_named(marker))
+            || PROJECT_MARKER_EXTENSIONS
+                .iter()
+                .any(|extension| entries.has_file_with_extension(extension))
+        {
+            return absolute_project_root(dir);
+        }
+    }
+
+    // Ultimate fallback: use the start directory
+    absolute_project_root(start_dir)
+}
+
+/// The closest directory at or above `dir` holding a `go.work` file.
+fn enclosing_go_workspace(dir: &Path) -> Option<&Path> {
+    dir.ancestors()
+        .find(|ancestor| ancestor.join("go.work").is_file())
+}
+
+/// Make a project root absolute, resolving symlinks when it exists
+fn absolute_project_root(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| {
+        if path.is_absolute() {
+            path.to_path_buf()
+        } else {
+            std::env::current_dir()
+                .map(|current_dir| current_dir.join(path))
+                .unwrap_or_else(|_| path.to_path_buf())
+        }
+    })
+}
+
+/// The entry names of a directory, read once to check every marker
+struct DirEntries {
+    names: HashSet<OsString>,
+    file_extensions: HashSet<OsString>,
+}
+
+impl DirEntries {
+    fn read(dir: &Path) -> Self {
+        let mut names = HashSet::new();
+        let mut file_extensions = HashSet::new();
+        for entry in std::fs::read_dir(dir).into_iter().flatten().flatten() {
+            let path = entry.path();
+            if let Some(extension) = path.extension() {
+                if entry.file_type().is_ok_and(|file_type| file_type.is_file()) {
+                    file_extensions.insert(extension.to_os_string());
+                }
+            }
+            names.insert(entry.file_name());
+        }
+        Self {
+            names,
+            file_extensions,
+        }
+    }
+
+    fn has_file_named(&self, name: &str) -> bool {
+        self.names.contains(std::ffi::OsStr::new(name))
+    }
+
+    fn has_file_with_extension(&self, extension: &str) -> bool {
+        self.file_extensions
+            .contains(std::ffi::OsStr::new(extension))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_markers_pick_the_closest_project_directory() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path().canonicalize().unwrap();
+        fs::create_dir_all(root.join("app/src")).unwrap();
+        fs::create_dir_all(root.join("tools/lua")).unwrap();
+        fs::write(root.join("Cargo.toml"), "").unwrap();
+        fs::write(root.join("app/Widget.sln"), "").unwrap();
+        fs::write(root.join("app/src/main.cs"), "").unwrap();
+        fs::write(root.join("tools/lua/init.lua"), "").unwrap();
+
+        assert_eq!(
+            detect_project_root(&root.join("app/src/main.cs")),
+            root.join("app")
+        );
+        assert_eq!(detect_project_root(&root.join("tools/lua/init.lua")), root);
+    }
+
+    #[test]
+    fn test_go_modules_belong_to_their_workspace() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path().canonicalize().unwrap();
+        fs::create_dir_all(root.join("services/api")).unwrap();
+        fs::write(root.join("go.work"), "go 1.22\n\nuse ./services/api\n").unwrap();
+        fs::write(root.join("services/api/go.mod"), "module example.com/api\n").unwrap();
+        fs::write(root.join("services/api/package.json"), "{}").unwrap();
+        fs::write(root.join("services/api/main.go"), "package main\n").unwrap();
+
+        assert_eq!(
+            detect_project_root(&root.join("services/api/main.go")),
+            root
+        );
+    }
+}
@@ -1,7 +1,9 @@
 //! Base trait and types for language-specific semantic analyzers
 
+use crate::core::semantic::query_engine::QueryEngine;
 use crate::utils::error::ContextCreatorError;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
 use std::path::{Path, PathBuf};
 
 /// Result type for semantic analysis operations
@@ -20,6 +22,8 @@ pub struct SemanticContext {
     pub max_depth: usize,
     /// Files already visited (for cycle detection)
     pub visited_files: HashSet<PathBuf>,
+    /// Extra C and C++ include directories, relative to the base directory
+    pub include_dirs: Vec<PathBuf>,
 }
 
 impl SemanticContext {
@@ -31,9 +35,16 @@ impl SemanticContext {
             current_depth: 0,
             max_depth,
             visited_files: HashSet::new(),
+            include_dirs: Vec::new(),
         }
     }
 
+    /// Search these include directories when resolving C and C++ includes
+    pub fn with_include_dirs(mut self, include_dirs: Vec<PathBuf>) -> Self {
+        self.include_dirs = include_dirs;
+        self
+    }
+
     /// Check if we've reached maximum depth
     pub fn at_max_depth(&self) -> bool {
         self.current_depth >= self.max_depth
@@ -105,6 +116,80 @@ pub struct TypeReference {
     pub external_package: Option<String>,
 }
 
+/// Kind of a symbol defined in a file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SymbolKind {
+    /// Free function
+    Function,
+    /// Function defined inside a type
+    Method,
+    /// Class, struct, enum, interface, trait, or type alias
+    Type,
+    /// Top-level code of a file outside any definition
+    Module,
+}
+
+impl std::fmt::Display for SymbolKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            SymbolKind::Function => "function",
+            SymbolKind::Method => "method",
+            SymbolKind::Type => "type",
+            SymbolKind::Module => "module",
+        })
+    }
+}
+
+/// A function, method, or type defined in a file
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SymbolDefinition {
+    /// Name of the symbol
+    pub name: String,
+    /// Kind of the symbol
+    pub kind: SymbolKind,
+    /// Byte range of the whole definition
+    pub byte_range: Range<usize>,
+    /// Line number where the definition starts
+    pub line: usize,
+    /// Whether the symbol is exported/public (types always are)
+    pub is_exported: bool,
+    /// Index of the enclosing definition, such as the type of a method
+    pub container: Option<usize>,
+}
+
+/// Kind of a reference from one symbol to another
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SymbolReferenceKind {
+    /// Function or method call
+    Call,
+    /// Use of a type
+    Type,
+}
+
+/// A call or type reference at a position in a file
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SymbolReference {
+    /// Name of the called function or referenced type
+    pub name: String,
+    /// Module or type qualifying the name (if any)
+    pub module: Option<String>,
+    /// Kind of the reference
+    pub kind: SymbolReferenceKind,
+    /// Byte offset of the referenced name
+    pub byte_offset: usize,
+    /// Line number where the reference appears
+    pub line: usize,
+}
+
+/// Symbols defined in a file and the references made from it
+#[derive(Debug, Default, Clone)]
+pub struct FileSymbols {
+    /// Definitions, in source order
+    pub definitions: Vec<SymbolDefinition>,
+    /// References, in source order
+    pub references: Vec<SymbolReference>,
+}
+
 /// Results from semantic analysis
 #[derive(Debug, Default, Clone)]
 pub struct AnalysisResult {
@@ -120,6 +205,54 @@ pub struct AnalysisResult {
     pub errors: Vec<String>,
 }
 
+impl AnalysisResult {
+    /// Drop the function calls `keep` rejects, and keep one call per name and line,
+    /// preferring the one qualified by a module when several patterns capture it
+    pub fn dedupe_function_calls(&mut self, keep: impl FnMut(&FunctionCall) -> bool) {
+        let calls = std::mem::take(&mut self.function_calls);
+        self.function_calls =
+            dedupe_by_name_and_line(calls, keep, |call| (&call.name, call.line, &call.module));
+    }
+
+    /// Drop the type references `keep` rejects, and keep one reference per name and line,
+    /// preferring the one qualified by a module when several patterns capture it
+    pub fn dedupe_type_references(&mut self, keep: impl FnMut(&TypeReference) -> bool) {
+        let type_refs = std::mem::take(&mut self.type_references);
+        self.type_references = dedupe_by_name_and_line(type_refs, keep, |type_ref| {
+            (&type_ref.name, type_ref.line, &type_ref.module)
+        });
+    }
+}
+
+fn dedupe_by_name_and_line<T>(
+    items: Vec<T>,
+    mut keep: impl FnMut(&T) -> bool,
+    key: impl Fn(&T) -> (&String, usize, &Option<String>),
+) -> Vec<T> {
+    let mut deduped: Vec<T> = Vec::new();
+    let mut index_by_name_line: HashMap<(String, usize), usize> = HashMap::new();
+
+    for item in items {
+        if !keep(&item) {
+            continue;
+        }
+
+        let (name, line, module) = key(&item);
+        let name_line = (name.clone(), line);
+        if let Some(existing_index) = index_by_name_line.get(&name_line).copied() {
+            let existing = &mut deduped[existing_index];
+            if key(existing).2.is_none() && module.is_some() {
+                *existing = item;
+            }
+        } else {
+            index_by_name_line.insert(name_line, deduped.len());
+            deduped.push(item);
+        }
+    }
+
+    deduped
+}
+
 /// Base trait for language-specific analyzers
 pub trait LanguageAnalyzer: Send + Sync {
     /// Get the language name
@@ -217,4 +350,10 @@ pub trait LanguageAnalyzer: Send + Sync {
         // Languages should override this to provide type resolution
         None
     }
+    /// Query engine whose captures describe the symbols of this language
+    ///
+    /// Used to build the symbol graph; analyzers without tree-sitter queries return None.
+    fn query_engine(&self) -> Option<&QueryEngine> {
+        None
+    }
 }
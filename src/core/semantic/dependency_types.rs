@@ -71,3 +71,37 @@ pub struct FileAnalysisResult {
     /// Error if analysis failed
     pub error: Option<String>,
 }
+
+/// Edge types for the symbol graph
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum SymbolEdgeType {
+    /// Symbol calls a function or method
+    Calls {
+        /// Line of the call site
+        line: usize,
+    },
+    /// Symbol uses a type
+    References {
+        /// Line of the reference
+        line: usize,
+    },
+    /// Type contains a method, or a definition a nested one
+    Contains,
+}
+
+/// Node metadata for the symbol graph
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SymbolNode {
+    /// Name of the symbol
+    pub name: String,
+    /// Kind of the symbol
+    pub kind: crate::core::semantic::analyzer::SymbolKind,
+    /// Path of the file defining the symbol
+    pub path: PathBuf,
+    /// Byte range of the definition in the file
+    pub byte_range: std::ops::Range<usize>,
+    /// Line number where the definition starts
+    pub line: usize,
+    /// Whether the symbol is exported/public
+    pub is_exported: bool,
+}
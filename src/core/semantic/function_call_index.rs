@@ -3,6 +3,7 @@
 //! This module provides an index that maps function names to the files that call them,
 //! enabling O(1) lookup for finding callers instead of O(n) file scanning.
 
+use crate::core::semantic::languages::r::{r_package_name, r_package_root};
 use crate::core::walker::FileInfo;
 use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
@@ -163,6 +164,25 @@ impl FunctionCallIndex {
         match target_path.extension().and_then(|ext| ext.to_str()) {
             Some("go") => {}
             Some("swift") => return self.swift_call_site_matches_target(call_site, target_path),
+            Some("java" | "kt" | "kts" | "scala" | "sc") => {
+                return self.java_call_site_matches_target(call_site, target_path)
+            }
+            Some("cs") => return self.csharp_call_site_matches_target(call_site, target_path),
+            Some("rb") => return self.ruby_call_site_matches_target(call_site, target_path),
+            Some("php") => return self.php_call_site_matches_target(call_site, target_path),
+            Some("dart") => return self.dart_call_site_matches_target(call_site, target_path),
+            Some("ex" | "exs") => {
+                return self.elixir_call_site_matches_target(call_site, target_path)
+            }
+            Some("lua") => return self.lua_call_site_matches_target(call_site, target_path),
+            Some("hs" | "elm") => {
+                return self.haskell_call_site_matches_target(call_site, target_path)
+            }
+            Some("r" | "R") => return self.r_call_site_matches_target(call_site, target_path),
+            Some("jl") => return self.julia_call_site_matches_target(call_site, target_path),
+            Some("c" | "h" | "cc" | "cpp" | "cxx" | "hh" | "hpp" | "hxx") => {
+                return self.c_call_site_matches_target(call_site, target_path)
+            }
             _ => return true,
         }
 
@@ -197,6 +217,304 @@ impl FunctionCallIndex {
         self.file_imports_swift_target_module(&call_site.file, target_path)
     }
 
+    fn java_call_site_matches_target(
+        &self,
+        call_site: &FunctionCallSite,
+        target_path: &Path,
+    ) -> bool {
+        if same_java_package(&call_site.file, target_path) {
+            return true;
+        }
+
+        if let Some(module) = call_site.module.as_deref() {
+            if target_path.file_stem().and_then(|stem| stem.to_str()) == Some(module) {
+                return true;
+            }
+        }
+
+        self.file_to_imports
+            .get(&call_site.file)
+            .is_some_and(|imports| {
+                imports
+                    .iter()
+                    .any(|import_path| paths_equivalent(import_path, target_path))
+            })
+    }
+
+    /// C# callers usually hold a service through its interface, so importing
+    /// `IOrderService` counts as a dependency on `OrderService`.
+    fn csharp_call_site_matches_target(
+        &self,
+        call_site: &FunctionCallSite,
+        target_path: &Path,
+    ) -> bool {
+        if same_directory(&call_site.file, target_path) {
+            return true;
+        }
+
+        let target_stem = target_path.file_stem().and_then(|stem| stem.to_str());
+        if call_site.module.is_some() && call_site.module.as_deref() == target_stem {
+            return true;
+        }
+
+        let interface_stem = target_stem.map(|stem| format!("I{stem}"));
+        self.file_to_imports
+            .get(&call_site.file)
+            .is_some_and(|imports| {
+                imports.iter().any(|import_path| {
+                    paths_equivalent(import_path, target_path)
+                        || (interface_stem.is_some()
+                            && import_path.file_stem().and_then(|stem| stem.to_str())
+                                == interface_stem.as_deref())
+                })
+            })
+    }
+
+    /// Ruby methods are called on autoloaded constants (`Billing::InvoiceMailer.notify`)
+    /// or on objects of classes the caller references, which become its imports.
+    fn ruby_call_site_matches_target(
+        &self,
+        call_site: &FunctionCallSite,
+        target_path: &Path,
+    ) -> bool {
+        if paths_equivalent(&call_site.file, target_path) {
+            return true;
+        }
+
+        if let Some(module) = call_site.module.as_deref() {
+            let constant = module.rsplit("::").next().unwrap_or(module);
+            let target_stem = target_path.file_stem().and_then(|stem| stem.to_str());
+            if target_stem.is_some_and(|stem| stem.replace('_', "") == constant.to_lowercase()) {
+                return true;
+            }
+        }
+
+        self.file_to_imports
+            .get(&call_site.file)
+            .is_some_and(|imports| {
+                imports
+                    .iter()
+                    .any(|import_path| paths_equivalent(import_path, target_path))
+            })
+    }
+
+    /// PHP classes are autoloaded one per file, so a static call names the target file
+    /// and instance calls go through an imported class or its `...Interface`.
+    fn php_call_site_matches_target(
+        &self,
+        call_site: &FunctionCallSite,
+        target_path: &Path,
+    ) -> bool {
+        if same_directory(&call_site.file, target_path) {
+            return true;
+        }
+
+        let target_stem = target_path.file_stem().and_then(|stem| stem.to_str());
+        if call_site.module.is_some() && call_site.module.as_deref() == target_stem {
+            return true;
+        }
+
+        let interface_stem = target_stem.map(|stem| format!("{stem}Interface"));
+        self.file_to_imports
+            .get(&call_site.file)
+            .is_some_and(|imports| {
+                imports.iter().any(|import_path| {
+                    paths_equivalent(import_path, target_path)
+                        || (interface_stem.is_some()
+                            && import_path.file_stem().and_then(|stem| stem.to_str())
+                                == interface_stem.as_deref())
+                })
+            })
+    }
+
+    /// Dart names are visible through imports only; barrel re-exports are already
+    /// expanded to the declaring file by the analyzer.
+    fn dart_call_site_matches_target(
+        &self,
+        call_site: &FunctionCallSite,
+        target_path: &Path,
+    ) -> bool {
+        if paths_equivalent(&call_site.file, target_path) {
+            return true;
+        }
+
+        self.file_to_imports
+            .get(&call_site.file)
+            .is_some_and(|imports| {
+                imports
+                    .iter()
+                    .any(|import_path| paths_equivalent(import_path, target_path))
+            })
+    }
+
+    /// Remote calls name the module, which Mix convention puts in a file named after its
+    /// last segment (`MyApp.LineItems` in `line_items.ex`). Local calls reach functions
+    /// of the same module or of an `import`ed one.
+    fn elixir_call_site_matches_target(
+        &self,
+        call_site: &FunctionCallSite,
+        target_path: &Path,
+    ) -> bool {
+        if let Some(module) = call_site.module.as_deref() {
+            let module_name = module.rsplit('.').next().unwrap_or(module);
+            return target_path
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .is_some_and(|stem| camelize(stem) == module_name);
+        }
+
+        if paths_equivalent(&call_site.file, target_path) {
+            return true;
+        }
+
+        self.file_to_imports
+            .get(&call_site.file)
+            .is_some_and(|imports| {
+                imports
+                    .iter()
+                    .any(|import_path| paths_equivalent(import_path, target_path))
+            })
+    }
+
+    /// Lua functions reach other files through the table a module returns, so callers
+    /// must `require` the target; calls on a required module must also name it.
+    fn lua_call_site_matches_target(
+        &self,
+        call_site: &FunctionCallSite,
+        target_path: &Path,
+    ) -> bool {
+        if paths_equivalent(&call_site.file, target_path) {
+            return true;
+        }
+
+        let imports_target = self
+            .file_to_imports
+            .get(&call_site.file)
+            .is_some_and(|imports| {
+                imports
+                    .iter()
+                    .any(|import_path| paths_equivalent(import_path, target_path))
+            });
+
+        match call_site.module.as_deref() {
+            Some(module) => imports_target && lua_module_matches_target(module, target_path),
+            None => imports_target,
+        }
+    }
+
+    /// Haskell and Elm callers import the module defining a function; qualified calls
+    /// name it, and the module name is the file's path under its source directory.
+    fn haskell_call_site_matches_target(
+        &self,
+        call_site: &FunctionCallSite,
+        target_path: &Path,
+    ) -> bool {
+        if let Some(module) = call_site.module.as_deref() {
+            let extension = target_path.extension().unwrap_or_default();
+            let module_file = PathBuf::from(module.replace('.', "/")).with_extension(extension);
+            return target_path.ends_with(module_file);
+        }
+
+        if paths_equivalent(&call_site.file, target_path) {
+            return true;
+        }
+
+        self.file_to_imports
+            .get(&call_site.file)
+            .is_some_and(|imports| {
+                imports
+                    .iter()
+                    .any(|import_path| paths_equivalent(import_path, target_path))
+            })
+    }
+
+    /// Files in an R package's `R/` directory share one namespace. Other code reaches
+    /// them with `pkg::fn`, by attaching the package with `library()`, or by `source()`.
+    fn r_call_site_matches_target(&self, call_site: &FunctionCallSite, target_path: &Path) -> bool {
+        let target_package = r_package_root(target_path);
+
+        if let Some(package) = call_site.module.as_deref() {
+            return target_package
+                .as_deref()
+                .and_then(r_package_name)
+                .is_some_and(|name| name == package);
+        }
+
+        if paths_equivalent(&call_site.file, target_path) {
+            return true;
+        }
+        if let Some(target_package) = &target_package {
+            if r_package_root(&call_site.file).as_ref() == Some(target_package) {
+                return true;
+            }
+        }
+
+        self.file_to_imports
+            .get(&call_site.file)
+            .is_some_and(|imports| {
+                imports.iter().any(|import_path| {
+                    paths_equivalent(import_path, target_path)
+                        || target_package.as_ref().is_some_and(|target_package| {
+                            r_package_root(import_path).as_ref() == Some(target_package)
+                        })
+                })
+            })
+    }
+
+    /// Julia files included into one module share its namespace, so a caller sees the
+    /// target when it uses a module whose files include it, or when both are included
+    /// by the same module file.
+    fn julia_call_site_matches_target(
+        &self,
+        call_site: &FunctionCallSite,
+        target_path: &Path,
+    ) -> bool {
+        if paths_equivalent(&call_site.file, target_path) {
+            return true;
+        }
+
+        let mut pending = vec![call_site.file.clone()];
+        let mut visited = HashSet::new();
+        while let Some(file) = pending.pop() {
+            if !visited.insert(file.clone()) || visited.len() > 64 {
+                continue;
+            }
+            for import_path in self.file_to_imports.get(&file).into_iter().flatten() {
+                if paths_equivalent(import_path, target_path) {
+                    return true;
+                }
+                pending.push(import_path.clone());
+            }
+        }
+
+        self.file_to_imports.values().any(|imports| {
+            imports
+                .iter()
+                .any(|import_path| paths_equivalent(import_path, &call_site.file))
+                && imports
+                    .iter()
+                    .any(|import_path| paths_equivalent(import_path, target_path))
+        })
+    }
+
+    /// C and C++ callers see a function through the header that declares it, which
+    /// usually shares its name with the implementation file.
+    fn c_call_site_matches_target(&self, call_site: &FunctionCallSite, target_path: &Path) -> bool {
+        if same_directory(&call_site.file, target_path) {
+            return true;
+        }
+
+        self.file_to_imports
+            .get(&call_site.file)
+            .is_some_and(|imports| {
+                imports.iter().any(|include_path| {
+                    paths_equivalent(include_path, target_path)
+                        || (include_path.file_stem().is_some()
+                            && include_path.file_stem() == target_path.file_stem())
+                })
+            })
+    }
+
     fn file_imports_go_target_package(&self, caller_path: &Path, target_path: &Path) -> bool {
         self.file_to_imports
             .get(caller_path)
@@ -253,6 +571,30 @@ fn paths_equivalent(left: &std::path::Path, right: &std::path::Path) -> bool {
     }
 }
 
+/// `line_items` to `LineItems`, the way Mix names modules after their files.
+fn camelize(stem: &str) -> String {
+    stem.split('_')
+        .map(|part| {
+            let mut chars = part.chars();
+            chars
+                .next()
+                .map(|first| first.to_uppercase().chain(chars).collect::<String>())
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+/// Whether `require("a.b")` could have loaded the file, as `a/b.lua` or `a/b/init.lua`.
+fn lua_module_matches_target(module: &str, target_path: &Path) -> bool {
+    let module_path = PathBuf::from(module.replace('.', "/"));
+    let target = target_path.with_extension("");
+    target.ends_with(&module_path)
+        || (target.file_name().is_some_and(|name| name == "init")
+            && target
+                .parent()
+                .is_some_and(|dir| dir.ends_with(&module_path)))
+}
+
 fn go_module_matches_target(module: &str, target_path: &Path) -> bool {
     let Some(package_name) = target_path
         .parent()
@@ -299,6 +641,33 @@ fn swift_target_dir(path: &Path) -> Option<PathBuf> {
     None
 }
 
+/// Classes in the same package see each other without imports, even when `src/main/java`
+/// and `src/test/java` keep them in different directories.
+fn same_java_package(left: &Path, right: &Path) -> bool {
+    if same_directory(left, right) {
+        return true;
+    }
+
+    match (java_package_dir(left), java_package_dir(right)) {
+        (Some(left_package), Some(right_package)) => left_package == right_package,
+        _ => false,
+    }
+}
+
+fn java_package_dir(path: &Path) -> Option<PathBuf> {
+    let components: Vec<_> = path.parent()?.components().collect();
+
+    (2..components.len()).rev().find_map(|index| {
+        // src/<source set>/<language>, e.g. src/main/java, src/jvmMain/kotlin, src/main/scala-2.13
+        let is_source_root = components[index]
+            .as_os_str()
+            .to_str()
+            .is_some_and(|dir| matches!(dir, "java" | "kotlin") || dir.starts_with("scala"))
+            && components[index - 2].as_os_str() == "src";
+        is_source_root.then(|| components[index + 1..].iter().collect())
+    })
+}
+
 fn swift_module_matches_target(module: &str, target_path: &Path) -> bool {
     let Some(target_name) = swift_target_dir(target_path)
         .as_deref()
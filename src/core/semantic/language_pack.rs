@@ -0,0 +1,527 @@
+//! Language packs loaded at runtime
+//!
+//! A language pack adds semantic analysis for a language without a built-in analyzer. It is
+//! a directory holding a `language.toml` manifest, a compiled tree-sitter grammar, and the
+//! `imports.scm`, `calls.scm`, `definitions.scm`, and `types.scm` queries, which use the
+//! capture names of the built-in queries:
+//!
+//! - imports: `@module_path`, `@import_name`, and `@wildcard`
+//! - calls: `@fn_name` or `@method_name`, with `@module_name` for qualified calls
+//! - definitions: `@fn_name` or `@method_name`, and `@private` for hidden definitions
+//! - types: `@type_name`, with `@module_name` for qualified types
+//!
+//! ```toml
+//! name = "widget"
+//! extensions = ["wdg"]
+//! # Defaults to `<name>.so` (`.dylib` on macOS, `.dll` on Windows)
+//! grammar = "widget.so"
+//! # Defaults to `tree_sitter_<name>`
+//! symbol = "tree_sitter_widget"
+//!
+//! [resolve]
+//! # Module paths are split on the separator and joined with `/` for `{module}`
+//! separator = "."
+//! # Files tried for a module, relative to the project root, or to the importing file when
+//! # they start with `./` or `../`. Defaults to `./{module}.<ext>` and `{module}.<ext>`.
+//! paths = ["src/{module}.wdg", "{module}/index.wdg"]
+//! # Modules with these prefixes belong to the standard library or installed packages
+//! external = ["std"]
+//! ```
+
+use crate::core::semantic::analyzer::{
+    AnalysisResult, LanguageAnalyzer, SemanticContext, SemanticResult,
+};
+use crate::core::semantic::path_validator::{validate_import_path, validate_module_name};
+use crate::core::semantic::query_engine::{QueryEngine, QuerySources};
+use crate::core::semantic::resolver::{ModuleResolver, ResolvedPath};
+use crate::utils::error::ContextCreatorError;
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::path::{Component, Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock, RwLock};
+use tracing::warn;
+use tree_sitter::{Language, Parser};
+
+/// Manifest file of a language pack.
+pub const LANGUAGE_PACK_MANIFEST: &str = "language.toml";
+
+/// Directory of language packs in the home directory.
+pub const LANGUAGE_PACK_DIR: &str = ".context-creator/languages";
+
+/// The `language.toml` of a language pack.
+#[derive(Debug, Clone, Deserialize)]
+pub struct LanguagePackManifest {
+    pub name: String,
+    pub extensions: Vec<String>,
+    #[serde(default)]
+    pub grammar: Option<PathBuf>,
+    #[serde(default)]
+    pub symbol: Option<String>,
+    #[serde(default)]
+    pub resolve: ResolveRules,
+}
+
+/// How module paths of a language pack map to files.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ResolveRules {
+    #[serde(default)]
+    pub separator: Option<String>,
+    #[serde(default)]
+    pub paths: Vec<String>,
+    #[serde(default)]
+    pub external: Vec<String>,
+}
+
+/// A language loaded from a language pack.
+pub struct LanguagePack {
+    name: &'static str,
+    extensions: Vec<&'static str>,
+    resolve: ResolveRules,
+    language: Language,
+    query_engine: QueryEngine,
+    // Declared last so the grammar outlives everything using it
+    _library: Option<libloading::Library>,
+}
+
+impl std::fmt::Debug for LanguagePack {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LanguagePack")
+            .field("name", &self.name)
+            .field("extensions", &self.extensions)
+            .finish()
+    }
+}
+
+impl LanguagePack {
+    /// Load the language pack in `dir`.
+    pub fn load(dir: &Path) -> Result<Self, ContextCreatorError> {
+        let manifest = read_manifest(dir)?;
+        let grammar = dir.join(manifest.grammar.clone().unwrap_or_else(|| {
+            PathBuf::from(format!(
+                "{}.{}",
+                manifest.name,
+                std::env::consts::DLL_EXTENSION
+            ))
+        }));
+        let symbol = manifest
+            .symbol
+            .clone()
+            .unwrap_or_else(|| format!("tree_sitter_{}", manifest.name.replace('-', "_")));
+
+        // SAFETY: the grammar is a tree-sitter parser library the user installed as a language
+        // pack; its language function takes no arguments and returns a `TSLanguage` pointer,
+        // which `Language` wraps transparently. The library is kept loaded as long as the pack.
+        let (library, language) = unsafe {
+            let library = libloading::Library::new(&grammar).map_err(|e| {
+                ContextCreatorError::ConfigError(format!(
+                    "Failed to load grammar {}: {e}",
+                    grammar.display()
+                ))
+            })?;
+            let language_fn = library
+                .get::<unsafe extern "C" fn() -> Language>(symbol.as_bytes())
+                .map_err(|e| {
+                    ContextCreatorError::ConfigError(format!(
+                        "Grammar {} has no symbol {symbol}: {e}",
+                        grammar.display()
+                    ))
+                })?;
+            let language = language_fn();
+            (library, language)
+        };
+
+        let mut pack = Self::with_language(dir, manifest, language)?;
+        pack._library = Some(library);
+        Ok(pack)
+    }
+
+    /// Create the language pack in `dir` for a grammar that is already loaded.
+    pub fn with_language(
+        dir: &Path,
+        manifest: LanguagePackManifest,
+        language: Language,
+    ) -> Result<Self, ContextCreatorError> {
+        let version = language.version();
+        if !(tree_sitter::MIN_COMPATIBLE_LANGUAGE_VERSION..=tree_sitter::LANGUAGE_VERSION)
+            .contains(&version)
+        {
+            return Err(ContextCreatorError::ConfigError(format!(
+                "Grammar of language pack {} has ABI version {version}, expected {} to {}",
+                manifest.name,
+                tree_sitter::MIN_COMPATIBLE_LANGUAGE_VERSION,
+                tree_sitter::LANGUAGE_VERSION
+            )));
+        }
+        if manifest.extensions.is_empty() {
+            return Err(ContextCreatorError::ConfigError(format!(
+                "Language pack {} declares no extensions",
+                manifest.name
+            )));
+        }
+
+        let sources = QuerySources {
+            imports: read_query(dir, "imports.scm")?,
+            calls: read_query(dir, "calls.scm")?,
+            definitions: read_query(dir, "definitions.scm")?,
+            types: read_query(dir, "types.scm")?,
+        };
+        let query_engine = QueryEngine::from_sources(language, &manifest.name, &sources)?;
+
+        let name = intern(&manifest.name);
+        let extensions = manifest
+            .extensions
+            .iter()
+            .map(|extension| intern(extension.trim_start_matches('.')))
+            .collect();
+
+        Ok(Self {
+            name,
+            extensions,
+            resolve: manifest.resolve,
+            language,
+            query_engine,
+            _library: None,
+        })
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    pub fn extensions(&self) -> &[&'static str] {
+        &self.extensions
+    }
+
+    /// Files tried for a module path, before validation.
+    fn candidate_files(
+        &self,
+        module_path: &str,
+        from_file: &Path,
+        base_dir: &Path,
+    ) -> Vec<PathBuf> {
+        let importing_dir = from_file.parent().unwrap_or(base_dir);
+
+        // Relative module paths name a file next to the importing file
+        if module_path.starts_with("./") || module_path.starts_with("../") {
+            let path = importing_dir.join(module_path);
+            let mut candidates = vec![path.clone()];
+            candidates.extend(
+                self.extensions
+                    .iter()
+                    .map(|extension| PathBuf::from(format!("{}.{extension}", path.display()))),
+            );
+            return candidates;
+        }
+
+        let module = match &self.resolve.separator {
+            Some(separator) if !separator.is_empty() => {
+                module_path.replace(separator.as_str(), "/")
+            }
+            _ => module_path.to_string(),
+        };
+        let templates: Vec<String> = if self.resolve.paths.is_empty() {
+            let relative = self
+                .extensions
+                .iter()
+                .map(|extension| format!("./{{module}}.{extension}"));
+            let rooted = self
+                .extensions
+                .iter()
+                .map(|extension| format!("{{module}}.{extension}"));
+            relative.chain(rooted).collect()
+        } else {
+            self.resolve.paths.clone()
+        };
+
+        templates
+            .iter()
+            .map(|template| {
+                let path = template.replace("{module}", &module);
+                if template.starts_with("./") || template.starts_with("../") {
+                    importing_dir.join(path)
+                } else {
+                    base_dir.join(path)
+                }
+            })
+            .collect()
+    }
+}
+
+fn read_manifest(dir: &Path) -> Result<LanguagePackManifest, ContextCreatorError> {
+    let path = dir.join(LANGUAGE_PACK_MANIFEST);
+    let content = std::fs::read_to_string(&path).map_err(|e| {
+        ContextCreatorError::ConfigError(format!("Failed to read {}: {e}", path.display()))
+    })?;
+    toml::from_str(&content).map_err(|e| {
+        ContextCreatorError::ConfigError(format!("Failed to parse {}: {e}", path.display()))
+    })
+}
+
+/// The query in `file_name`, or no patterns when the pack leaves it out.
+fn read_query(dir: &Path, file_name: &str) -> Result<String, ContextCreatorError> {
+    let path = dir.join(file_name);
+    if !path.exists() {
+        return Ok(String::new());
+    }
+    std::fs::read_to_string(&path).map_err(|e| {
+        ContextCreatorError::ConfigError(format!("Failed to read {}: {e}", path.display()))
+    })
+}
+
+/// The `'static` copy of a pack name or extension. Packs stay registered until the process
+/// exits, and loading the same pack again reuses the strings leaked the first time.
+fn intern(value: &str) -> &'static str {
+    static STRINGS: OnceLock<Mutex<HashSet<&'static str>>> = OnceLock::new();
+    let mut strings = STRINGS
+        .get_or_init(|| Mutex::new(HashSet::new()))
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    if let Some(&interned) = strings.get(value) {
+        return interned;
+    }
+    let interned: &'static str = Box::leak(value.to_string().into_boxed_str());
+    strings.insert(interned);
+    interned
+}
+
+fn language_packs() -> &'static RwLock<Vec<Arc<LanguagePack>>> {
+    static PACKS: OnceLock<RwLock<Vec<Arc<LanguagePack>>>> = OnceLock::new();
+    PACKS.get_or_init(|| RwLock::new(Vec::new()))
+}
+
+/// Register a language pack, replacing a registered pack of the same name.
+pub fn register_language_pack(pack: LanguagePack) -> Arc<LanguagePack> {
+    let pack = Arc::new(pack);
+    if let Ok(mut packs) = language_packs().write() {
+        packs.retain(|registered| registered.name != pack.name);
+        packs.push(pack.clone());
+    }
+    pack
+}
+
+/// The registered language pack handling files with `extension`.
+pub fn language_pack_for_extension(extension: &str) -> Option<Arc<LanguagePack>> {
+    let packs = language_packs().read().ok()?;
+    packs
+        .iter()
+        .rev()
+        .find(|pack| pack.extensions.contains(&extension))
+        .cloned()
+}
+
+/// The pack directories in `dir`: `dir` itself when it is a pack, else its subdirectories
+/// holding a manifest. A directory that doesn't exist holds no packs.
+fn pack_dirs(dir: &Path) -> Vec<PathBuf> {
+    if dir.join(LANGUAGE_PACK_MANIFEST).is_file() {
+        return vec![dir.to_path_buf()];
+    }
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut pack_dirs: Vec<PathBuf> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.join(LANGUAGE_PACK_MANIFEST).is_file())
+        .collect();
+    pack_dirs.sort();
+    pack_dirs
+}
+
+/// Load and register the language packs in `dirs`. Each directory is either a pack or holds
+/// packs in its subdirectories, and must exist and hold at least one pack.
+pub fn load_language_packs(
+    dirs: &[PathBuf],
+) -> Result<Vec<Arc<LanguagePack>>, ContextCreatorError> {
+    let mut loaded = Vec::new();
+    for dir in dirs {
+        if !dir.is_dir() {
+            return Err(ContextCreatorError::ConfigError(format!(
+                "Language pack directory {} does not exist",
+                dir.display()
+            )));
+        }
+        let pack_dirs = pack_dirs(dir);
+        if pack_dirs.is_empty() {
+            return Err(ContextCreatorError::ConfigError(format!(
+                "No language pack in {}: expected a {LANGUAGE_PACK_MANIFEST} in it or in its subdirectories",
+                dir.display()
+            )));
+        }
+        for pack_dir in pack_dirs {
+            loaded.push(register_language_pack(LanguagePack::load(&pack_dir)?));
+        }
+    }
+    Ok(loaded)
+}
+
+/// Load the language packs given with `--language-pack` and, when semantic analysis may run,
+/// those installed in `~/.context-creator/languages`. Command-line packs take precedence and
+/// must load, while an installed pack that fails to load is skipped with a warning. The MCP
+/// server always loads installed packs, since each request chooses its own analysis. Packs
+/// inside the processed project are not loaded, since that would run code from the repository.
+pub fn load_configured_language_packs(
+    config: &crate::cli::Config,
+) -> Result<Vec<Arc<LanguagePack>>, ContextCreatorError> {
+    let mut loaded = Vec::new();
+    let semantic_analysis = config.mcp
+        || config.rmcp
+        || crate::core::semantic::SemanticOptions::from_config(config).is_enabled();
+    if let Some(home) = dirs::home_dir().filter(|_| semantic_analysis) {
+        for pack_dir in pack_dirs(&home.join(LANGUAGE_PACK_DIR)) {
+            match LanguagePack::load(&pack_dir) {
+                Ok(pack) => loaded.push(register_language_pack(pack)),
+                Err(e) => warn!("Skipping language pack {}: {e}", pack_dir.display()),
+            }
+        }
+    }
+    loaded.extend(load_language_packs(&config.language_packs)?);
+    Ok(loaded)
+}
+
+/// Semantic analyzer for a language pack
+pub struct LanguagePackAnalyzer {
+    pack: Arc<LanguagePack>,
+}
+
+impl LanguagePackAnalyzer {
+    pub fn new(pack: Arc<LanguagePack>) -> Self {
+        Self { pack }
+    }
+}
+
+impl LanguageAnalyzer for LanguagePackAnalyzer {
+    fn language_name(&self) -> &'static str {
+        self.pack.name
+    }
+
+    fn analyze_file(
+        &self,
+        path: &Path,
+        content: &str,
+        context: &SemanticContext,
+    ) -> SemanticResult<AnalysisResult> {
+        let mut parser = Parser::new();
+        parser
+            .set_language(self.pack.language)
+            .map_err(|e| ContextCreatorError::ParseError(format!("Failed to set language: {e}")))?;
+
+        let mut result = self
+            .pack
+            .query_engine
+            .analyze_with_parser(&mut parser, content)?;
+
+        // Types are defined in the module they are qualified with or imported from
+        let resolver = LanguagePackResolver::new(self.pack.clone());
+        for type_ref in &mut result.type_references {
+            let module = type_ref.module.clone().or_else(|| {
+                result
+                    .imports
+                    .iter()
+                    .find(|import| import.items.contains(&type_ref.name))
+                    .map(|import| import.module.clone())
+            });
+            let Some(module) = module else {
+                continue;
+            };
+            if let Ok(resolved) = resolver.resolve_import(&module, path, &context.base_dir) {
+                type_ref.is_external = resolved.is_external;
+                if resolved.is_external {
+                    type_ref.external_package = Some(module);
+                } else {
+                    type_ref.definition_path = Some(resolved.path);
+                }
+            }
+        }
+
+        Ok(result)
+    }
+
+    fn can_handle_extension(&self, extension: &str) -> bool {
+        self.pack.extensions.contains(&extension)
+    }
+
+    fn supported_extensions(&self) -> Vec<&'static str> {
+        self.pack.extensions.clone()
+    }
+
+    fn query_engine(&self) -> Option<&QueryEngine> {
+        Some(&self.pack.query_engine)
+    }
+}
+
+/// Module resolver following the `[resolve]` rules of a language pack
+pub struct LanguagePackResolver {
+    pack: Arc<LanguagePack>,
+}
+
+impl LanguagePackResolver {
+    pub fn new(pack: Arc<LanguagePack>) -> Self {
+        Self { pack }
+    }
+}
+
+impl ModuleResolver for LanguagePackResolver {
+    fn resolve_import(
+        &self,
+        module_path: &str,
+        from_file: &Path,
+        base_dir: &Path,
+    ) -> Result<ResolvedPath, ContextCreatorError> {
+        let is_relative = module_path.starts_with("./") || module_path.starts_with("../");
+        if is_relative {
+            // Relative paths may climb, containment is checked on the resolved file
+            let path = Path::new(module_path);
+            if module_path.len() > 255
+                || module_path.contains('\0')
+                || path
+                    .components()
+                    .any(|component| matches!(component, Component::Prefix(_) | Component::RootDir))
+            {
+                return Err(ContextCreatorError::SecurityError(format!(
+                    "Invalid relative module path: {module_path}"
+                )));
+            }
+        } else {
+            validate_module_name(module_path)?;
+        }
+
+        if !is_relative && self.is_external_module(module_path) {
+            return Ok(ResolvedPath {
+                path: base_dir.to_path_buf(),
+                is_external: true,
+                confidence: 1.0,
+            });
+        }
+
+        for candidate in self.pack.candidate_files(module_path, from_file, base_dir) {
+            if candidate.is_file() {
+                let validated_path = validate_import_path(base_dir, &candidate)?;
+                return Ok(ResolvedPath {
+                    path: validated_path,
+                    is_external: false,
+                    confidence: 0.9,
+                });
+            }
+        }
+
+        Ok(ResolvedPath {
+            path: base_dir.to_path_buf(),
+            is_external: true,
+            confidence: 0.5,
+        })
+    }
+
+    fn get_file_extensions(&self) -> Vec<&'static str> {
+        self.pack.extensions.clone()
+    }
+
+    fn is_external_module(&self, module_path: &str) -> bool {
+        let separator = self.pack.resolve.separator.as_deref().unwrap_or("/");
+        self.pack.resolve.external.iter().any(|prefix| {
+            module_path == prefix
+                || module_path
+                    .strip_prefix(prefix.as_str())
+                    .is_some_and(|rest| rest.starts_with(separator) || rest.starts_with('/'))
+        })
+    }
+}
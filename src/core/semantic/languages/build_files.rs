@@ -0,0 +1,728 @@
+//! Dependency edges from build, CI, and container files to the files they run
+//!
+//! Dockerfiles, GitHub Actions workflows, Makefiles, and `package.json` scripts name the
+//! scripts and sources they use by path: `COPY package.json ./`, `RUN ./scripts/build.sh`,
+//! a workflow step running `python tools/release.py`, or a make recipe calling
+//! `./scripts/test.sh`. Every word of those commands that looks like a relative path becomes an
+//! import, as does the Dockerfile a `docker build` reads and the Makefile a `make` runs, and the
+//! resolver keeps the ones naming a file of the project, so the files a pipeline runs join the
+//! dependency graph.
+
+use crate::core::semantic::{
+    analyzer::{AnalysisResult, Import, LanguageAnalyzer, SemanticContext, SemanticResult},
+    path_validator::validate_import_path,
+    resolver::{ModuleResolver, ResolvedPath},
+};
+use crate::utils::error::ContextCreatorError;
+use std::path::{Component, Path};
+
+/// Files a local action directory used by a workflow step may define the action in.
+const ACTION_FILES: &[&str] = &["action.yml", "action.yaml"];
+
+/// Most files a Dockerfile `COPY` glob adds edges to.
+const MAX_GLOB_MATCHES: usize = 32;
+
+/// Build files commonly named without an extension, which commands and action inputs name bare.
+const EXTENSIONLESS_BUILD_FILES: &[&str] = &[
+    "Dockerfile",
+    "Containerfile",
+    "Makefile",
+    "makefile",
+    "GNUmakefile",
+    "Justfile",
+    "justfile",
+    "Jenkinsfile",
+    "Rakefile",
+    "Gemfile",
+    "Procfile",
+    "Vagrantfile",
+    "Brewfile",
+];
+
+/// Options of `docker build` and `make` that take no value.
+const FLAGS_WITHOUT_VALUE: &[&str] = &[
+    "--no-cache",
+    "--pull",
+    "--push",
+    "--load",
+    "--rm",
+    "--force-rm",
+    "--squash",
+    "-q",
+    "--quiet",
+    "-B",
+    "--always-make",
+    "-i",
+    "--ignore-errors",
+    "-k",
+    "--keep-going",
+    "-n",
+    "--dry-run",
+    "-s",
+    "--silent",
+];
+
+/// The kind of build or CI file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BuildFileKind {
+    Dockerfile,
+    Workflow,
+    Makefile,
+    PackageJson,
+}
+
+impl BuildFileKind {
+    /// The kind of build file `path` is, from its name: `Dockerfile`, `api.Dockerfile`,
+    /// `Containerfile`, `.github/workflows/*.yml`, `.github/actions/*/action.yml`, `Makefile`,
+    /// `*.mk`, or `package.json`.
+    pub fn for_path(path: &Path) -> Option<Self> {
+        let name = path.file_name()?.to_str()?;
+        let lower = name.to_ascii_lowercase();
+
+        if lower == "dockerfile"
+            || lower == "containerfile"
+            || lower.starts_with("dockerfile.")
+            || lower.ends_with(".dockerfile")
+        {
+            return Some(BuildFileKind::Dockerfile);
+        }
+        if matches!(name, "Makefile" | "makefile" | "GNUmakefile") || lower.ends_with(".mk") {
+            return Some(BuildFileKind::Makefile);
+        }
+        if name == "package.json" {
+            return Some(BuildFileKind::PackageJson);
+        }
+        if lower.ends_with(".yml") || lower.ends_with(".yaml") {
+            let components: Vec<&str> = path
+                .components()
+                .filter_map(|component| component.as_os_str().to_str())
+                .collect();
+            let in_github_dir = |dir: &str| {
+                components
+                    .windows(2)
+                    .any(|pair| pair[0] == ".github" && pair[1] == dir)
+            };
+            if in_github_dir("workflows")
+                || (in_github_dir("actions") && lower.starts_with("action."))
+            {
+                return Some(BuildFileKind::Workflow);
+            }
+        }
+        None
+    }
+}
+
+pub struct BuildFileAnalyzer {
+    kind: BuildFileKind,
+}
+
+impl BuildFileAnalyzer {
+    pub fn new(kind: BuildFileKind) -> Self {
+        Self { kind }
+    }
+}
+
+impl LanguageAnalyzer for BuildFileAnalyzer {
+    fn language_name(&self) -> &'static str {
+        match self.kind {
+            BuildFileKind::Dockerfile => "Dockerfile",
+            BuildFileKind::Workflow => "GitHub Actions",
+            BuildFileKind::Makefile => "Makefile",
+            BuildFileKind::PackageJson => "package.json",
+        }
+    }
+
+    fn analyze_file(
+        &self,
+        path: &Path,
+        content: &str,
+        context: &SemanticContext,
+    ) -> SemanticResult<AnalysisResult> {
+        let references = match self.kind {
+            BuildFileKind::Dockerfile => dockerfile_references(path, content, &context.base_dir),
+            BuildFileKind::Workflow => workflow_references(content),
+            BuildFileKind::Makefile => makefile_references(content),
+            BuildFileKind::PackageJson => package_script_references(content),
+        };
+
+        let mut result = AnalysisResult::default();
+        for (module, line) in references {
+            if !result.imports.iter().any(|import| import.module == module) {
+                result.imports.push(Import {
+                    module,
+                    items: Vec::new(),
+                    is_relative: true,
+                    line,
+                });
+            }
+        }
+        Ok(result)
+    }
+
+    fn can_handle_extension(&self, extension: &str) -> bool {
+        self.supported_extensions().contains(&extension)
+    }
+
+    fn supported_extensions(&self) -> Vec<&'static str> {
+        // Build files are recognized by name rather than by extension
+        match self.kind {
+            BuildFileKind::Dockerfile => vec!["dockerfile"],
+            BuildFileKind::Workflow => vec!["yml", "yaml"],
+            BuildFileKind::Makefile => vec!["mk"],
+            BuildFileKind::PackageJson => vec!["json"],
+        }
+    }
+}
+
+/// Resolves paths named by build files against the directory of the build file and its
+/// ancestors, since a Docker build context or a workflow's working directory is usually one
+/// of them
+pub struct BuildFileResolver;
+
+impl ModuleResolver for BuildFileResolver {
+    fn resolve_import(
+        &self,
+        module_path: &str,
+        from_file: &Path,
+        base_dir: &Path,
+    ) -> Result<ResolvedPath, ContextCreatorError> {
+        let path = Path::new(module_path);
+        if module_path.is_empty()
+            || module_path.len() > 255
+            || module_path.contains('\0')
+            || path
+                .components()
+                .any(|component| matches!(component, Component::Prefix(_) | Component::RootDir))
+        {
+            return Err(ContextCreatorError::SecurityError(format!(
+                "Invalid build file reference: {module_path}"
+            )));
+        }
+
+        if !self.is_external_module(module_path) {
+            let importing_dir = from_file.parent().unwrap_or(base_dir);
+            let search_dirs = importing_dir
+                .ancestors()
+                .take_while(|dir| dir.starts_with(base_dir))
+                .chain(std::iter::once(base_dir));
+            for dir in search_dirs {
+                let candidate = dir.join(path);
+                let file = if candidate.is_dir() {
+                    ACTION_FILES
+                        .iter()
+                        .map(|name| candidate.join(name))
+                        .find(|action| action.is_file())
+                } else {
+                    Some(candidate).filter(|candidate| candidate.is_file())
+                };
+                if let Some(file) = file {
+                    return Ok(ResolvedPath {
+                        path: validate_import_path(base_dir, &file)?,
+                        is_external: false,
+                        confidence: 0.8,
+                    });
+                }
+            }
+        }
+
+        // Commands, directories, and paths inside the container are not project files
+        Ok(ResolvedPath {
+            path: base_dir.to_path_buf(),
+            is_external: true,
+            confidence: 0.5,
+        })
+    }
+
+    fn get_file_extensions(&self) -> Vec<&'static str> {
+        vec!["dockerfile", "yml", "yaml", "mk", "json"]
+    }
+
+    fn is_external_module(&self, module_path: &str) -> bool {
+        module_path.contains("://")
+    }
+}
+
+/// Words of a shell command that may be relative paths to project files, keeping track of
+/// `cd` so `cd web && node build.js` names `web/build.js`, along with the Dockerfile of a
+/// `docker build` and the Makefile of a `make`.
+fn command_paths(command: &str) -> Vec<String> {
+    let mut paths = Vec::new();
+    let mut working_dir: Option<String> = None;
+    let mut after_cd = false;
+    let relative_to_working_dir = |working_dir: &Option<String>, path: &str| match working_dir {
+        Some(dir) => join_relative(dir, path),
+        None => path.trim_start_matches("./").to_string(),
+    };
+
+    for simple_command in command.split([';', '|', '&', '(', ')']) {
+        let words: Vec<&str> = simple_command
+            .split(|c: char| c.is_whitespace() || "<>\"'`[],".contains(c))
+            .filter(|word| !word.is_empty())
+            .collect();
+        for &word in &words {
+            if after_cd {
+                after_cd = false;
+                working_dir =
+                    is_path_like(word, true).then(|| relative_to_working_dir(&working_dir, word));
+                continue;
+            }
+            if word == "cd" || word == "pushd" {
+                after_cd = true;
+                continue;
+            }
+
+            // `--config=tools/lint.json`, `ENV=ci`
+            let word = word.rsplit('=').next().unwrap_or(word);
+            if is_path_like(word, false) {
+                paths.push(relative_to_working_dir(&working_dir, word));
+            }
+        }
+        paths.extend(
+            build_tool_files(&words)
+                .iter()
+                .map(|path| relative_to_working_dir(&working_dir, path)),
+        );
+    }
+    paths
+}
+
+/// The build file a `docker build` or `make` command reads: the file given with `-f`, else
+/// the `Dockerfile` of the build context or the `Makefile` of the `-C` directory.
+fn build_tool_files(words: &[&str]) -> Vec<String> {
+    // Skip `sudo` and environment assignments before the program
+    let mut words = words
+        .iter()
+        .copied()
+        .skip_while(|word| *word == "sudo" || (word.contains('=') && !word.starts_with('-')));
+    let Some(program) = words.next() else {
+        return Vec::new();
+    };
+    let arguments: Vec<&str> = words.collect();
+
+    match program.rsplit('/').next().unwrap_or(program) {
+        "docker" | "podman" | "buildah" => {
+            let arguments = match arguments.as_slice() {
+                ["build" | "bud", rest @ ..]
+                | ["buildx" | "image" | "builder", "build", rest @ ..] => rest,
+                _ => return Vec::new(),
+            };
+            let (files, positional) = split_build_tool_arguments(arguments, &["-f", "--file"]);
+            if !files.is_empty() {
+                return files;
+            }
+            let context = positional.first().map_or(".", String::as_str);
+            if context != "." && !is_path_like(context, true) {
+                return Vec::new();
+            }
+            let default_files: &[&str] = if program.ends_with("docker") {
+                &["Dockerfile"]
+            } else {
+                &["Containerfile", "Dockerfile"]
+            };
+            default_files
+                .iter()
+                .map(|file| join_relative(context, file))
+                .collect()
+        }
+        "make" | "gmake" => {
+            let (files, _) =
+                split_build_tool_arguments(&arguments, &["-f", "--file", "--makefile"]);
+            let (directories, _) = split_build_tool_arguments(&arguments, &["-C", "--directory"]);
+            let directory = directories.last().map_or(".", String::as_str);
+            if directory != "." && !is_path_like(directory, true) {
+                return Vec::new();
+            }
+            if files.is_empty() {
+                vec![join_relative(directory, "Makefile")]
+            } else {
+                files
+                    .iter()
+                    .map(|file| join_relative(directory, file))
+                    .collect()
+            }
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// The values of the `options` among a command's arguments, in all of the `-f file`,
+/// `-ffile`, and `--file=file` forms, and its positional arguments.
+fn split_build_tool_arguments(arguments: &[&str], options: &[&str]) -> (Vec<String>, Vec<String>) {
+    let mut values = Vec::new();
+    let mut positional = Vec::new();
+    let mut arguments = arguments.iter();
+    while let Some(&argument) = arguments.next() {
+        if options.contains(&argument) {
+            if let Some(value) = arguments.next() {
+                values.push(value.to_string());
+            }
+        } else if let Some(value) = options.iter().find_map(|option| {
+            argument
+                .strip_prefix(option)
+                .and_then(|rest| {
+                    if option.starts_with("--") {
+                        rest.strip_prefix('=')
+                    } else {
+                        Some(rest)
+                    }
+                })
+                .filter(|value| !value.is_empty())
+        }) {
+            values.push(value.to_string());
+        } else if argument.starts_with('-') {
+            // Options other than the wanted ones take the next argument unless they are flags
+            if !argument.contains('=') && !FLAGS_WITHOUT_VALUE.contains(&argument) {
+                arguments.next();
+            }
+        } else {
+            positional.push(argument.to_string());
+        }
+    }
+    (values, positional)
+}
+
+/// Whether a word looks like a relative path: it has a directory or an extension, or is a
+/// build file such as `Dockerfile`, and no variables, globs, flags, or scheme. Directories
+/// given to `cd` need neither.
+fn is_path_like(word: &str, is_directory: bool) -> bool {
+    let word = word.trim_end_matches('/');
+    if word.is_empty()
+        || word.len() > 255
+        || word.starts_with(['-', '/', '@', '~', '$', '%'])
+        || word.contains(['$', '*', '?', '{', '}', ':', '\\'])
+        || word.chars().any(|c| c.is_control())
+    {
+        return false;
+    }
+    if is_directory {
+        return word != "." && word != "-";
+    }
+
+    let name = word.rsplit('/').next().unwrap_or(word);
+    let has_extension = name.rsplit_once('.').is_some_and(|(stem, extension)| {
+        !stem.is_empty() && !extension.is_empty() && !extension.chars().all(|c| c.is_ascii_digit())
+    });
+    (word.contains('/') && !name.is_empty() && name != "." && name != "..")
+        || has_extension
+        || EXTENSIONLESS_BUILD_FILES.contains(&name)
+}
+
+/// `dir` joined with a relative path, without its leading `./`.
+fn join_relative(dir: &str, path: &str) -> String {
+    let dir = dir.trim_start_matches("./").trim_end_matches('/');
+    let path = path.trim_start_matches("./");
+    if dir.is_empty() || dir == "." {
+        path.to_string()
+    } else {
+        format!("{dir}/{path}")
+    }
+}
+
+/// Lines of `content` with `\` continuations joined, with the line each one starts on.
+fn logical_lines(content: &str) -> Vec<(String, usize)> {
+    let mut lines = Vec::new();
+    let mut current: Option<(String, usize)> = None;
+    for (index, line) in content.lines().enumerate() {
+        let (text, start) = current.take().unwrap_or_else(|| (String::new(), index + 1));
+        let mut text = text;
+        match line.strip_suffix('\\') {
+            Some(continued) => {
+                text.push_str(continued);
+                text.push(' ');
+                current = Some((text, start));
+            }
+            None => {
+                text.push_str(line);
+                lines.push((text, start));
+            }
+        }
+    }
+    if let Some(last) = current {
+        lines.push(last);
+    }
+    lines
+}
+
+fn dockerfile_references(path: &Path, content: &str, base_dir: &Path) -> Vec<(String, usize)> {
+    let mut references = Vec::new();
+    for (line, line_number) in logical_lines(content) {
+        let line = line.trim();
+        if line.starts_with('#') {
+            continue;
+        }
+        let Some((instruction, arguments)) = line.split_once(char::is_whitespace) else {
+            continue;
+        };
+        match instruction.to_ascii_uppercase().as_str() {
+            "COPY" | "ADD" => {
+                // Files copied from another build stage or image are not in the build context
+                if arguments.contains("--from=") {
+                    continue;
+                }
+                let mut sources: Vec<String> = if arguments.trim_start().starts_with('[') {
+                    serde_json::from_str(arguments.trim()).unwrap_or_default()
+                } else {
+                    arguments
+                        .split_whitespace()
+                        .filter(|argument| !argument.starts_with("--"))
+                        .map(str::to_string)
+                        .collect()
+                };
+                // The last argument is the destination
+                sources.pop();
+                for source in sources {
+                    if source.contains('*') || source.contains('?') {
+                        references.extend(
+                            expand_copy_glob(path, &source, base_dir)
+                                .into_iter()
+                                .map(|matched| (matched, line_number)),
+                        );
+                    } else if is_path_like(&source, false) {
+                        references.push((source.trim_start_matches("./").to_string(), line_number));
+                    }
+                }
+            }
+            "RUN" | "CMD" | "ENTRYPOINT" => references.extend(
+                command_paths(arguments)
+                    .into_iter()
+                    .map(|reference| (reference, line_number)),
+            ),
+            _ => {}
+        }
+    }
+    references
+}
+
+/// Files matching a `COPY` glob such as `package*.json`, relative to the directory of the
+/// Dockerfile, or to the project root when nothing matches there.
+fn expand_copy_glob(dockerfile: &Path, pattern: &str, base_dir: &Path) -> Vec<String> {
+    let pattern = pattern.trim_start_matches("./");
+    if pattern.starts_with('/') || pattern.split('/').any(|part| part == "..") {
+        return Vec::new();
+    }
+    let context_dirs = dockerfile
+        .parent()
+        .into_iter()
+        .chain(std::iter::once(base_dir));
+    for context_dir in context_dirs {
+        let Some(full_pattern) = context_dir.join(pattern).to_str().map(str::to_string) else {
+            continue;
+        };
+        let Ok(paths) = glob::glob(&full_pattern) else {
+            return Vec::new();
+        };
+        let matches: Vec<String> = paths
+            .flatten()
+            .filter(|matched| matched.is_file())
+            .filter_map(|matched| {
+                matched
+                    .strip_prefix(context_dir)
+                    .ok()
+                    .and_then(|relative| relative.to_str())
+                    .map(str::to_string)
+            })
+            .take(MAX_GLOB_MATCHES)
+            .collect();
+        if !matches.is_empty() {
+            return matches;
+        }
+    }
+    Vec::new()
+}
+
+/// A list item of a workflow, such as a step, with the commands it runs.
+#[derive(Debug, Default)]
+struct WorkflowItem {
+    indent: usize,
+    commands: Vec<(String, usize)>,
+    working_dir: Option<String>,
+}
+
+fn workflow_references(content: &str) -> Vec<(String, usize)> {
+    let mut references = Vec::new();
+    let mut default_working_dir: Option<String> = None;
+    let mut item: Option<WorkflowItem> = None;
+    // Key and indentation of the block scalar (`run: |`) being read
+    let mut block: Option<(String, usize)> = None;
+
+    let flush = |item: WorkflowItem,
+                 default_working_dir: &Option<String>,
+                 references: &mut Vec<(String, usize)>| {
+        let working_dir = item.working_dir.as_ref().or(default_working_dir.as_ref());
+        for (command, line) in item.commands {
+            for path in command_paths(&command) {
+                let path = match working_dir {
+                    Some(dir) => join_relative(dir, &path),
+                    None => path,
+                };
+                references.push((path, line));
+            }
+        }
+    };
+
+    for (index, raw_line) in content.lines().enumerate() {
+        let line_number = index + 1;
+        let trimmed = raw_line.trim_start();
+        if trimmed.is_empty() {
+            continue;
+        }
+        let indent = raw_line.len() - trimmed.len();
+
+        if let Some((key, key_indent)) = &block {
+            if indent > *key_indent {
+                if key == "run" {
+                    match item.as_mut() {
+                        Some(item) => item.commands.push((trimmed.to_string(), line_number)),
+                        None => references.extend(
+                            command_paths(trimmed)
+                                .into_iter()
+                                .map(|path| (path, line_number)),
+                        ),
+                    }
+                }
+                continue;
+            }
+            block = None;
+        }
+        if trimmed.starts_with('#') {
+            continue;
+        }
+
+        let is_list_item = trimmed.starts_with("- ");
+        if item.as_ref().is_some_and(|current| {
+            indent < current.indent || (indent == current.indent && is_list_item)
+        }) {
+            if let Some(finished) = item.take() {
+                flush(finished, &default_working_dir, &mut references);
+            }
+        }
+        if is_list_item && item.is_none() {
+            item = Some(WorkflowItem {
+                indent,
+                ..WorkflowItem::default()
+            });
+        }
+
+        let entry = trimmed.trim_start_matches("- ");
+        let key_indent = indent + (trimmed.len() - entry.len());
+        let Some((key, value)) = entry.split_once(':') else {
+            continue;
+        };
+        let key = key.trim();
+        let value = value.trim();
+        if matches!(value, "|" | "|-" | "|+" | ">" | ">-" | ">+") {
+            block = Some((key.to_string(), key_indent));
+            continue;
+        }
+        let value = value
+            .split(" #")
+            .next()
+            .unwrap_or("")
+            .trim()
+            .trim_matches(|c| c == '"' || c == '\'');
+        if value.is_empty() {
+            continue;
+        }
+
+        match key {
+            "run" => match item.as_mut() {
+                Some(item) => item.commands.push((value.to_string(), line_number)),
+                None => references.extend(
+                    command_paths(value)
+                        .into_iter()
+                        .map(|path| (path, line_number)),
+                ),
+            },
+            "working-directory" => match item.as_mut() {
+                Some(item) => item.working_dir = Some(value.to_string()),
+                None => default_working_dir = Some(value.to_string()),
+            },
+            // Local actions and reusable workflows: `uses: ./.github/actions/setup`
+            "uses" if value.starts_with("./") => {
+                references.push((value.trim_start_matches("./").to_string(), line_number));
+            }
+            // Inputs of actions such as docker/build-push-action naming a file
+            "file" | "dockerfile" | "script-path" if is_path_like(value, false) => {
+                references.push((value.trim_start_matches("./").to_string(), line_number));
+            }
+            _ => {}
+        }
+    }
+    if let Some(finished) = item {
+        flush(finished, &default_working_dir, &mut references);
+    }
+    references
+}
+
+fn makefile_references(content: &str) -> Vec<(String, usize)> {
+    let mut references = Vec::new();
+    for (line, line_number) in logical_lines(content) {
+        let line = line.split('#').next().unwrap_or("");
+        let paths = if let Some(recipe) = line.strip_prefix('\t') {
+            command_paths(recipe.trim_start_matches(['@', '-', '+']))
+        } else if let Some(included) = ["include ", "-include ", "sinclude "]
+            .iter()
+            .find_map(|directive| line.strip_prefix(directive))
+        {
+            included
+                .split_whitespace()
+                .filter(|path| is_path_like(path, false))
+                .map(|path| path.trim_start_matches("./").to_string())
+                .collect()
+        } else if let Some((_, prerequisites)) = line
+            .split_once(':')
+            .filter(|(targets, rest)| !rest.starts_with('=') && !targets.contains('='))
+        {
+            // Prerequisites of a rule, before any order-only `|` or inline `;` recipe
+            let (prerequisites, inline_recipe) =
+                prerequisites.split_once(';').unwrap_or((prerequisites, ""));
+            let prerequisites = prerequisites.split('|').next().unwrap_or("");
+            prerequisites
+                .split_whitespace()
+                .filter(|path| is_path_like(path, false))
+                .map(|path| path.trim_start_matches("./").to_string())
+                .chain(command_paths(inline_recipe))
+                .collect()
+        } else {
+            Vec::new()
+        };
+        references.extend(paths.into_iter().map(|path| (path, line_number)));
+    }
+    references
+}
+
+fn package_script_references(content: &str) -> Vec<(String, usize)> {
+    let Ok(package) = serde_json::from_str::<serde_json::Value>(content) else {
+        return Vec::new();
+    };
+    let Some(scripts) = package
+        .get("scripts")
+        .and_then(|scripts| scripts.as_object())
+    else {
+        return Vec::new();
+    };
+
+    let scripts_line = content
+        .lines()
+        .position(|line| line.contains("\"scripts\""))
+        .unwrap_or(0);
+    let mut references = Vec::new();
+    for (name, command) in scripts {
+        let Some(command) = command.as_str() else {
+            continue;
+        };
+        let key = format!("\"{name}\"");
+        let line_number = content
+            .lines()
+            .enumerate()
+            .skip(scripts_line)
+            .find(|(_, line)| line.contains(&key))
+            .map_or(scripts_line + 1, |(index, _)| index + 1);
+        references.extend(
+            command_paths(command)
+                .into_iter()
+                .map(|path| (path, line_number)),
+        );
+    }
+    references
+}
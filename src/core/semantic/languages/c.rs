@@ -1,23 +1,43 @@
-//! Semantic analyzer for C
+//! Semantic analyzer for C and include resolution shared with C++
 
-use crate::core::semantic::analyzer::{
-    AnalysisResult, LanguageAnalyzer, SemanticContext, SemanticResult,
+use crate::core::semantic::{
+    analyzer::{AnalysisResult, LanguageAnalyzer, SemanticContext, SemanticResult},
+    path_validator::validate_import_path,
+    query_engine::QueryEngine,
+    resolver::{ModuleResolver, ResolvedPath},
 };
-use std::path::Path;
-use tree_sitter::Parser;
+use crate::utils::error::ContextCreatorError;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+use tree_sitter::{Language, Parser, Query, QueryCursor};
+
+/// Header extensions that may have a separate implementation file.
+const C_HEADER_EXTENSIONS: &[&str] = &["h", "hh", "hpp", "hxx"];
+
+/// Implementation extensions, in the order they are preferred for a header.
+const C_SOURCE_EXTENSIONS: &[&str] = &["c", "cc", "cpp", "cxx"];
+
+/// Compilation databases are usually written to the project root or a build directory.
+const COMPILE_COMMANDS_LOCATIONS: &[&str] = &[
+    "compile_commands.json",
+    "build/compile_commands.json",
+    "out/compile_commands.json",
+    "cmake-build-debug/compile_commands.json",
+];
 
 #[allow(clippy::new_without_default)]
 pub struct CAnalyzer {
-    #[allow(dead_code)]
-    parser: Parser,
+    query_engine: QueryEngine,
 }
 
 impl CAnalyzer {
     pub fn new() -> Self {
-        let parser = Parser::new();
-        // Note: tree_sitter_c::language() would be used here when the crate is added
-        // parser.set_language(tree_sitter_c::language()).unwrap();
-        Self { parser }
+        let language = tree_sitter_c::language();
+        let query_engine =
+            QueryEngine::new(language, "c").expect("Failed to create C query engine");
+        Self { query_engine }
     }
 }
 
@@ -28,19 +48,676 @@ impl LanguageAnalyzer for CAnalyzer {
 
     fn analyze_file(
         &self,
-        _path: &Path,
-        _content: &str,
-        _context: &SemanticContext,
+        path: &Path,
+        content: &str,
+        context: &SemanticContext,
     ) -> SemanticResult<AnalysisResult> {
-        // TODO: Implement C analysis
-        Ok(AnalysisResult::default())
+        analyze_c_family_file(
+            &self.query_engine,
+            tree_sitter_c::language(),
+            path,
+            content,
+            context,
+        )
     }
 
     fn can_handle_extension(&self, extension: &str) -> bool {
-        extension == "c" || extension == "h"
+        extension == "c"
     }
 
     fn supported_extensions(&self) -> Vec<&'static str> {
-        vec!["c", "h"]
+        vec!["c"]
+    }
+
+    fn query_engine(&self) -> Option<&QueryEngine> {
+        Some(&self.query_engine)
+    }
+}
+
+/// Analysis shared by the C and C++ analyzers: run the language queries, then link type
+/// references to the headers that define them.
+pub(crate) fn analyze_c_family_file(
+    query_engine: &QueryEngine,
+    language: Language,
+    path: &Path,
+    content: &str,
+    context: &SemanticContext,
+) -> SemanticResult<AnalysisResult> {
+    let mut parser = Parser::new();
+    parser
+        .set_language(language)
+        .map_err(|e| ContextCreatorError::ParseError(format!("Failed to set language: {e}")))?;
+
+    let mut result = query_engine.analyze_with_parser(&mut parser, content)?;
+
+    result.dedupe_function_calls(|_| true);
+    // Drop template parameters, and mark standard library typedefs as external
+    result.dedupe_type_references(|type_ref| {
+        !(type_ref.name.len() == 1 && type_ref.name.starts_with(|c: char| c.is_uppercase()))
+    });
+    for type_ref in &mut result.type_references {
+        if type_ref.module.as_deref() == Some("std") || is_c_standard_type(&type_ref.name) {
+            type_ref.is_external = true;
+            type_ref.external_package = Some("std".to_string());
+        }
+    }
+
+    resolve_c_type_definitions(&mut result, path, context);
+    query_engine.resolve_type_definitions(&mut result.type_references, path, &context.base_dir)?;
+
+    Ok(result)
+}
+
+/// Types in C and C++ are visible through the include graph, so search the headers this
+/// file includes (and the headers they include) for the defining declaration.
+fn resolve_c_type_definitions(
+    result: &mut AnalysisResult,
+    current_file: &Path,
+    context: &SemanticContext,
+) {
+    let resolver = CModuleResolver;
+    let mut headers = Vec::new();
+    let mut seen = HashSet::new();
+    let mut frontier: Vec<(String, PathBuf)> = result
+        .imports
+        .iter()
+        .map(|import| (import.module.clone(), current_file.to_path_buf()))
+        .collect();
+
+    for _ in 0..2 {
+        let mut next = Vec::new();
+        for (include, from_file) in frontier {
+            let Ok(resolved) = resolver.resolve_import_in_context(&include, &from_file, context)
+            else {
+                continue;
+            };
+            if resolved.is_external || !seen.insert(resolved.path.clone()) {
+                continue;
+            }
+            if let Ok(content) = fs::read_to_string(&resolved.path) {
+                next.extend(
+                    c_include_paths(&content)
+                        .into_iter()
+                        .map(|include| (include, resolved.path.clone())),
+                );
+                headers.push((resolved.path, content));
+            }
+        }
+        frontier = next;
+    }
+
+    let mut definitions_by_header: Vec<(PathBuf, HashSet<String>)> = Vec::new();
+    for (header, content) in &headers {
+        definitions_by_header.push((header.clone(), c_type_definitions(header, content)));
+    }
+
+    for type_ref in &mut result.type_references {
+        if type_ref.definition_path.is_some() || type_ref.is_external {
+            continue;
+        }
+
+        if let Some((header, _)) = definitions_by_header
+            .iter()
+            .find(|(_, names)| names.contains(&type_ref.name))
+        {
+            if let Ok(validated) = validate_import_path(&context.base_dir, header) {
+                type_ref.definition_path = Some(validated);
+            }
+        }
+    }
+}
+
+/// Names of the structs, unions, enums, classes, and typedefs a header defines.
+fn c_type_definitions(path: &Path, content: &str) -> HashSet<String> {
+    let is_c = path.extension().and_then(|ext| ext.to_str()) == Some("c");
+    let (language, query_text) = if is_c {
+        (
+            tree_sitter_c::language(),
+            r#"
+            [
+              (struct_specifier name: (type_identifier) @name body: (field_declaration_list))
+              (union_specifier name: (type_identifier) @name body: (field_declaration_list))
+              (enum_specifier name: (type_identifier) @name body: (enumerator_list))
+              (type_definition declarator: (type_identifier) @name)
+            ]
+            "#,
+        )
+    } else {
+        (
+            tree_sitter_cpp::language(),
+            r#"
+            [
+              (class_specifier name: (type_identifier) @name body: (field_declaration_list))
+              (struct_specifier name: (type_identifier) @name body: (field_declaration_list))
+              (union_specifier name: (type_identifier) @name body: (field_declaration_list))
+              (enum_specifier name: (type_identifier) @name body: (enumerator_list))
+              (type_definition declarator: (type_identifier) @name)
+              (alias_declaration name: (type_identifier) @name)
+            ]
+            "#,
+        )
+    };
+
+    let mut names = HashSet::new();
+    let mut parser = Parser::new();
+    if parser.set_language(language).is_err() {
+        return names;
+    }
+    let (Some(tree), Ok(query)) = (
+        parser.parse(content, None),
+        Query::new(language, query_text),
+    ) else {
+        return names;
+    };
+
+    let mut cursor = QueryCursor::new();
+    for query_match in cursor.matches(&query, tree.root_node(), content.as_bytes()) {
+        for capture in query_match.captures {
+            if let Ok(name) = capture.node.utf8_text(content.as_bytes()) {
+                names.insert(name.to_string());
+            }
+        }
+    }
+
+    names
+}
+
+/// Include targets of `#include "..."` and `#include <...>` lines.
+fn c_include_paths(content: &str) -> Vec<String> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let directive = line.trim_start().strip_prefix('#')?.trim_start();
+            let target = directive.strip_prefix("include")?.trim();
+            let target = target
+                .strip_prefix('"')
+                .and_then(|rest| rest.split('"').next())
+                .or_else(|| {
+                    target
+                        .strip_prefix('<')
+                        .and_then(|rest| rest.split('>').next())
+                })?;
+            (!target.is_empty()).then(|| target.to_string())
+        })
+        .collect()
+}
+
+/// Resolves `#include` targets for C and C++ using the include search path of the
+/// including file's translation unit.
+pub struct CModuleResolver;
+
+impl CModuleResolver {
+    fn resolve_include(
+        &self,
+        module_path: &str,
+        from_file: &Path,
+        base_dir: &Path,
+        include_dirs: &[PathBuf],
+    ) -> Result<ResolvedPath, ContextCreatorError> {
+        validate_include_path(module_path)?;
+
+        let external = |confidence| ResolvedPath {
+            path: base_dir.join("compile_commands.json"),
+            is_external: true,
+            confidence,
+        };
+
+        for include_dir in c_include_search_dirs(from_file, base_dir, include_dirs) {
+            let candidate = include_dir.join(module_path);
+            if candidate.is_file() {
+                return match validate_import_path(base_dir, &candidate) {
+                    Ok(path) => Ok(ResolvedPath {
+                        path,
+                        is_external: false,
+                        confidence: 0.9,
+                    }),
+                    // Found through an include directory outside the project, like /usr/include
+                    Err(_) if is_outside_project(&candidate, base_dir) => Ok(external(1.0)),
+                    Err(e) => Err(e),
+                };
+            }
+        }
+
+        Ok(external(if self.is_external_module(module_path) {
+            1.0
+        } else {
+            0.5
+        }))
+    }
+}
+
+impl ModuleResolver for CModuleResolver {
+    fn resolve_import(
+        &self,
+        module_path: &str,
+        from_file: &Path,
+        base_dir: &Path,
+    ) -> Result<ResolvedPath, ContextCreatorError> {
+        self.resolve_include(module_path, from_file, base_dir, &[])
+    }
+
+    fn resolve_import_in_context(
+        &self,
+        module_path: &str,
+        from_file: &Path,
+        context: &SemanticContext,
+    ) -> Result<ResolvedPath, ContextCreatorError> {
+        self.resolve_include(
+            module_path,
+            from_file,
+            &context.base_dir,
+            &context.include_dirs,
+        )
+    }
+
+    fn get_file_extensions(&self) -> Vec<&'static str> {
+        vec!["h", "c"]
+    }
+
+    fn is_external_module(&self, module_path: &str) -> bool {
+        // C++ standard headers have no extension: <vector>, <memory>, <cstdio>
+        if Path::new(module_path).extension().is_none() {
+            return true;
+        }
+
+        [
+            "sys/", "linux/", "asm/", "arpa/", "netinet/", "bits/", "mach/",
+        ]
+        .iter()
+        .any(|prefix| module_path.starts_with(prefix))
+            || matches!(
+                module_path,
+                "assert.h"
+                    | "complex.h"
+                    | "ctype.h"
+                    | "errno.h"
+                    | "fenv.h"
+                    | "float.h"
+                    | "inttypes.h"
+                    | "limits.h"
+                    | "locale.h"
+                    | "math.h"
+                    | "setjmp.h"
+                    | "signal.h"
+                    | "stdalign.h"
+                    | "stdarg.h"
+                    | "stdatomic.h"
+                    | "stdbool.h"
+                    | "stddef.h"
+                    | "stdint.h"
+                    | "stdio.h"
+                    | "stdlib.h"
+                    | "string.h"
+                    | "time.h"
+                    | "threads.h"
+                    | "uchar.h"
+                    | "wchar.h"
+                    | "wctype.h"
+                    | "unistd.h"
+                    | "fcntl.h"
+                    | "pthread.h"
+                    | "dirent.h"
+                    | "windows.h"
+            )
+    }
+}
+
+fn is_outside_project(path: &Path, base_dir: &Path) -> bool {
+    let (Ok(path), Ok(base_dir)) = (path.canonicalize(), base_dir.canonicalize()) else {
+        return false;
+    };
+    !path.starts_with(base_dir)
+}
+
+/// Include paths may legitimately climb directories (`../common/log.h`); containment is
+/// enforced on the resolved file by `validate_import_path` instead of on the name.
+fn validate_include_path(include: &str) -> Result<(), ContextCreatorError> {
+    let path = Path::new(include);
+    if include.is_empty()
+        || include.len() > 255
+        || include.contains('\0')
+        || path.is_absolute()
+        || path
+            .components()
+            .any(|component| matches!(component, Component::Prefix(_) | Component::RootDir))
+    {
+        return Err(ContextCreatorError::SecurityError(format!(
+            "Invalid include path: {include}"
+        )));
     }
+
+    Ok(())
+}
+
+/// Directories searched for an include, in the order a compiler would: the including
+/// file's directory, then `-iquote`/`-I`/`-isystem` paths from `compile_commands.json`
+/// or `compile_flags.txt`, then the configured include directories (relative to the
+/// project root) and the conventional `include/` directory.
+pub(crate) fn c_include_search_dirs(
+    from_file: &Path,
+    base_dir: &Path,
+    include_dirs: &[PathBuf],
+) -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+    if let Some(parent) = from_file.parent() {
+        dirs.push(parent.to_path_buf());
+    }
+
+    let database = compile_database(base_dir);
+    let canonical_file = from_file
+        .canonicalize()
+        .unwrap_or_else(|_| from_file.to_path_buf());
+    match database
+        .command_for(&canonical_file)
+        .or_else(|| database.command_for(from_file))
+    {
+        Some(command) => dirs.extend(command.include_dirs.iter().cloned()),
+        // Headers have no entry of their own; any translation unit's search path may apply.
+        None => dirs.extend(database.all_include_dirs.iter().cloned()),
+    }
+
+    dirs.extend(database.flag_include_dirs.iter().cloned());
+    dirs.extend(include_dirs.iter().map(|dir| base_dir.join(dir)));
+    dirs.push(base_dir.join("include"));
+    dirs.push(base_dir.to_path_buf());
+
+    let mut seen = HashSet::new();
+    dirs.into_iter()
+        .filter(|dir| seen.insert(dir.clone()) && dir.is_dir())
+        .collect()
+}
+
+/// Implementation files for a header: same-named sources next to it, in the mirrored
+/// `src/` directory of an `include/` tree, or compiled under the same name in the database.
+pub(crate) fn c_implementation_files(header: &Path, base_dir: &Path) -> Vec<PathBuf> {
+    let Some(extension) = header.extension().and_then(|ext| ext.to_str()) else {
+        return Vec::new();
+    };
+    if !C_HEADER_EXTENSIONS.contains(&extension) {
+        return Vec::new();
+    }
+    let (Some(stem), Some(header_dir)) = (
+        header.file_stem().and_then(|stem| stem.to_str()),
+        header.parent(),
+    ) else {
+        return Vec::new();
+    };
+
+    let mut candidate_dirs = vec![header_dir.to_path_buf()];
+    if let Ok(relative_dir) = header_dir.strip_prefix(base_dir) {
+        let components: Vec<_> = relative_dir.components().collect();
+        if let Some(include_index) = components
+            .iter()
+            .position(|component| component.as_os_str() == "include")
+        {
+            let prefix: PathBuf = components[..include_index].iter().collect();
+            let rest: PathBuf = components[include_index + 1..].iter().collect();
+            let source_root = base_dir.join(prefix).join("src");
+            candidate_dirs.push(source_root.join(&rest));
+            candidate_dirs.push(
+                rest.components()
+                    .skip(1)
+                    .fold(source_root.clone(), |path, component| path.join(component)),
+            );
+            candidate_dirs.push(source_root);
+        }
+    }
+
+    let mut files = Vec::new();
+    for dir in candidate_dirs {
+        for source_extension in C_SOURCE_EXTENSIONS {
+            let candidate = dir.join(format!("{stem}.{source_extension}"));
+            if candidate.is_file() && !files.contains(&candidate) {
+                files.push(candidate);
+            }
+        }
+        if !files.is_empty() {
+            return files;
+        }
+    }
+
+    compile_database(base_dir)
+        .commands
+        .iter()
+        .filter(|command| {
+            command
+                .file
+                .file_stem()
+                .and_then(|file_stem| file_stem.to_str())
+                == Some(stem)
+        })
+        .map(|command| command.file.clone())
+        .filter(|file| file.is_file())
+        .collect()
+}
+
+#[derive(Debug, Clone)]
+struct CompileCommand {
+    file: PathBuf,
+    include_dirs: Vec<PathBuf>,
+}
+
+/// The compilation database and `compile_flags.txt` of a project, read once.
+#[derive(Debug, Default)]
+struct CompileDatabase {
+    commands: Vec<CompileCommand>,
+    command_by_file: HashMap<PathBuf, usize>,
+    /// Existing include directories of every translation unit, for headers without an entry
+    all_include_dirs: Vec<PathBuf>,
+    flag_include_dirs: Vec<PathBuf>,
+}
+
+impl CompileDatabase {
+    fn load(base_dir: &Path) -> Self {
+        let commands: Vec<CompileCommand> = COMPILE_COMMANDS_LOCATIONS
+            .iter()
+            .map(|location| base_dir.join(location))
+            .find(|path| path.is_file())
+            .and_then(|path| fs::read_to_string(path).ok())
+            .map(|content| parse_compile_commands(&content, base_dir))
+            .unwrap_or_default();
+
+        let command_by_file = commands
+            .iter()
+            .enumerate()
+            .map(|(index, command)| (command.file.clone(), index))
+            .collect();
+        let mut seen = HashSet::new();
+        let all_include_dirs = commands
+            .iter()
+            .flat_map(|command| command.include_dirs.iter())
+            .filter(|dir| seen.insert(*dir) && dir.is_dir())
+            .cloned()
+            .collect();
+
+        Self {
+            commands,
+            command_by_file,
+            all_include_dirs,
+            flag_include_dirs: compile_flags_include_dirs(base_dir),
+        }
+    }
+
+    fn command_for(&self, file: &Path) -> Option<&CompileCommand> {
+        self.command_by_file
+            .get(file)
+            .map(|&index| &self.commands[index])
+    }
+}
+
+fn compile_database_cache() -> &'static Mutex<HashMap<PathBuf, Arc<CompileDatabase>>> {
+    static CACHE: OnceLock<Mutex<HashMap<PathBuf, Arc<CompileDatabase>>>> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn compile_database(base_dir: &Path) -> Arc<CompileDatabase> {
+    if let Some(database) = compile_database_cache()
+        .lock()
+        .ok()
+        .and_then(|cache| cache.get(base_dir).cloned())
+    {
+        return database;
+    }
+
+    let database = Arc::new(CompileDatabase::load(base_dir));
+
+    if let Ok(mut cache) = compile_database_cache().lock() {
+        cache.insert(base_dir.to_path_buf(), database.clone());
+    }
+
+    database
+}
+
+fn parse_compile_commands(content: &str, base_dir: &Path) -> Vec<CompileCommand> {
+    let Ok(serde_json::Value::Array(entries)) = serde_json::from_str::<serde_json::Value>(content)
+    else {
+        return Vec::new();
+    };
+
+    entries
+        .iter()
+        .filter_map(|entry| {
+            let directory = entry
+                .get("directory")
+                .and_then(|value| value.as_str())
+                .map(|directory| base_dir.join(directory))
+                .unwrap_or_else(|| base_dir.to_path_buf());
+            let file = directory.join(entry.get("file")?.as_str()?);
+            let arguments: Vec<String> = match entry.get("arguments") {
+                Some(serde_json::Value::Array(arguments)) => arguments
+                    .iter()
+                    .filter_map(|argument| argument.as_str().map(ToOwned::to_owned))
+                    .collect(),
+                _ => split_command_line(entry.get("command")?.as_str()?),
+            };
+
+            Some(CompileCommand {
+                file: file.canonicalize().unwrap_or(file),
+                include_dirs: include_dirs_from_flags(&arguments, &directory),
+            })
+        })
+        .collect()
+}
+
+/// Split a `command` string into arguments the way a POSIX shell would: whitespace separates
+/// arguments, quotes group them, and a backslash escapes the next character.
+fn split_command_line(command: &str) -> Vec<String> {
+    let mut arguments = Vec::new();
+    let mut current = String::new();
+    let mut in_argument = false;
+    let mut chars = command.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' => {
+                in_argument = true;
+                current.extend(chars.by_ref().take_while(|&c| c != '\''));
+            }
+            '"' => {
+                in_argument = true;
+                while let Some(c) = chars.next() {
+                    match c {
+                        '"' => break,
+                        '\\' => {
+                            if let Some(escaped) = chars.next() {
+                                if !matches!(escaped, '"' | '\\' | '$' | '`') {
+                                    current.push('\\');
+                                }
+                                current.push(escaped);
+                            }
+                        }
+                        c => current.push(c),
+                    }
+                }
+            }
+            '\\' => {
+                in_argument = true;
+                if let Some(escaped) = chars.next() {
+                    current.push(escaped);
+                }
+            }
+            c if c.is_whitespace() => {
+                if in_argument {
+                    arguments.push(std::mem::take(&mut current));
+                    in_argument = false;
+                }
+            }
+            c => {
+                in_argument = true;
+                current.push(c);
+            }
+        }
+    }
+    if in_argument {
+        arguments.push(current);
+    }
+
+    arguments
+}
+
+/// `compile_flags.txt` holds one compiler flag per line and applies to every file.
+fn compile_flags_include_dirs(base_dir: &Path) -> Vec<PathBuf> {
+    fs::read_to_string(base_dir.join("compile_flags.txt"))
+        .map(|content| {
+            let flags: Vec<String> = content
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty())
+                .map(ToOwned::to_owned)
+                .collect();
+            include_dirs_from_flags(&flags, base_dir)
+        })
+        .unwrap_or_default()
+}
+
+fn include_dirs_from_flags(flags: &[String], directory: &Path) -> Vec<PathBuf> {
+    const INCLUDE_FLAGS: &[&str] = &["-iquote", "-isystem", "-idirafter", "-I", "/I"];
+
+    let mut dirs = Vec::new();
+    let mut flags = flags.iter();
+    while let Some(flag) = flags.next() {
+        let Some(include_flag) = INCLUDE_FLAGS
+            .iter()
+            .find(|prefix| flag.starts_with(*prefix))
+        else {
+            continue;
+        };
+        let value = match &flag[include_flag.len()..] {
+            "" => match flags.next() {
+                Some(value) => value.as_str(),
+                None => break,
+            },
+            attached => attached,
+        };
+        dirs.push(directory.join(value));
+    }
+
+    dirs
+}
+
+/// Standard library typedefs that never have a project-local definition.
+fn is_c_standard_type(type_name: &str) -> bool {
+    matches!(
+        type_name,
+        "size_t"
+            | "ssize_t"
+            | "ptrdiff_t"
+            | "intptr_t"
+            | "uintptr_t"
+            | "int8_t"
+            | "int16_t"
+            | "int32_t"
+            | "int64_t"
+            | "uint8_t"
+            | "uint16_t"
+            | "uint32_t"
+            | "uint64_t"
+            | "off_t"
+            | "time_t"
+            | "pid_t"
+            | "bool"
+            | "FILE"
+            | "va_list"
+            | "wchar_t"
+    )
 }
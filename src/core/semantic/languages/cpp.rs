@@ -1,23 +1,23 @@
 //! Semantic analyzer for Cpp
 
-use crate::core::semantic::analyzer::{
-    AnalysisResult, LanguageAnalyzer, SemanticContext, SemanticResult,
+use crate::core::semantic::{
+    analyzer::{AnalysisResult, LanguageAnalyzer, SemanticContext, SemanticResult},
+    languages::c::analyze_c_family_file,
+    query_engine::QueryEngine,
 };
 use std::path::Path;
-use tree_sitter::Parser;
 
 #[allow(clippy::new_without_default)]
 pub struct CppAnalyzer {
-    #[allow(dead_code)]
-    parser: Parser,
+    query_engine: QueryEngine,
 }
 
 impl CppAnalyzer {
     pub fn new() -> Self {
-        let parser = Parser::new();
-        // Note: tree_sitter_cpp::language() would be used here when the crate is added
-        // parser.set_language(tree_sitter_cpp::language()).unwrap();
-        Self { parser }
+        let language = tree_sitter_cpp::language();
+        let query_engine =
+            QueryEngine::new(language, "cpp").expect("Failed to create C++ query engine");
+        Self { query_engine }
     }
 }
 
@@ -28,19 +28,31 @@ impl LanguageAnalyzer for CppAnalyzer {
 
     fn analyze_file(
         &self,
-        _path: &Path,
-        _content: &str,
-        _context: &SemanticContext,
+        path: &Path,
+        content: &str,
+        context: &SemanticContext,
     ) -> SemanticResult<AnalysisResult> {
-        // TODO: Implement Cpp analysis
-        Ok(AnalysisResult::default())
+        analyze_c_family_file(
+            &self.query_engine,
+            tree_sitter_cpp::language(),
+            path,
+            content,
+            context,
+        )
     }
 
     fn can_handle_extension(&self, extension: &str) -> bool {
-        matches!(extension, "cpp" | "cc" | "cxx" | "hpp" | "h")
+        matches!(extension, "cpp" | "cc" | "cxx" | "hpp" | "hh" | "hxx" | "h")
     }
 
     fn supported_extensions(&self) -> Vec<&'static str> {
-        vec!["cpp", "cc", "cxx", "hpp", "h"]
+        vec!["cpp", "cc", "cxx", "hpp", "hh", "hxx", "h"]
+    }
+
+    fn query_engine(&self) -> Option<&QueryEngine> {
+        Some(&self.query_engine)
     }
 }
+
+/// C++ includes follow the same search rules as C.
+pub use crate::core::semantic::languages::c::CModuleResolver as CppModuleResolver;
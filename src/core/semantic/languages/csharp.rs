@@ -1,24 +1,33 @@
-//! Semantic analyzer for CSharp
+//! Semantic analyzer for C# and .csproj/.sln project layouts
 
-use crate::core::semantic::analyzer::{
-    AnalysisResult, LanguageAnalyzer, SemanticContext, SemanticResult,
+use crate::core::semantic::{
+    analyzer::{AnalysisResult, Import, LanguageAnalyzer, SemanticContext, SemanticResult},
+    languages::is_capitalized_type_name,
+    path_validator::{validate_import_path, validate_module_name},
+    query_engine::QueryEngine,
+    resolver::{ModuleResolver, ResolvedPath},
 };
-use std::path::Path;
-use tree_sitter::Parser;
+use crate::utils::error::ContextCreatorError;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+use tree_sitter::{Node, Parser};
+
+/// Directories that hold build output or tooling state rather than project sources.
+const CSHARP_SKIPPED_DIRS: &[&str] = &["bin", "obj", "node_modules", "packages", "TestResults"];
 
 #[allow(clippy::new_without_default)]
 pub struct CSharpAnalyzer {
-    #[allow(dead_code)]
-    #[allow(dead_code)]
-    parser: Parser,
+    query_engine: QueryEngine,
 }
 
 impl CSharpAnalyzer {
     pub fn new() -> Self {
-        let parser = Parser::new();
-        // Note: tree_sitter_c_sharp::language() would be used here when the crate is added
-        // parser.set_language(tree_sitter_c_sharp::language()).unwrap();
-        Self { parser }
+        let language = tree_sitter_c_sharp::language();
+        let query_engine =
+            QueryEngine::new(language, "csharp").expect("Failed to create C# query engine");
+        Self { query_engine }
     }
 }
 
@@ -29,19 +38,637 @@ impl LanguageAnalyzer for CSharpAnalyzer {
 
     fn analyze_file(
         &self,
-        _path: &Path,
-        _content: &str,
-        _context: &SemanticContext,
+        path: &Path,
+        content: &str,
+        context: &SemanticContext,
     ) -> SemanticResult<AnalysisResult> {
-        // TODO: Implement CSharp analysis
-        Ok(AnalysisResult::default())
+        let mut parser = Parser::new();
+        parser
+            .set_language(tree_sitter_c_sharp::language())
+            .map_err(|e| ContextCreatorError::ParseError(format!("Failed to set language: {e}")))?;
+
+        let mut result = self
+            .query_engine
+            .analyze_with_parser(&mut parser, content)?;
+
+        // Receiver calls match both the member and the qualified call pattern
+        result.dedupe_function_calls(|_| true);
+        result.dedupe_type_references(|type_ref| is_capitalized_type_name(&type_ref.name));
+
+        let index = csharp_project_index(&context.base_dir);
+        let scope = CSharpScope::new(path, content, &result.imports, &index);
+        let resolved_namespaces =
+            self.resolve_csharp_type_definitions(&mut result, &context.base_dir, &scope, &index);
+        self.replace_namespace_usings(&mut result, &resolved_namespaces, &index);
+
+        Ok(result)
     }
 
     fn can_handle_extension(&self, extension: &str) -> bool {
-        matches!(extension, "cs")
+        extension == "cs"
     }
 
     fn supported_extensions(&self) -> Vec<&'static str> {
         vec!["cs"]
     }
+
+    fn query_engine(&self) -> Option<&QueryEngine> {
+        Some(&self.query_engine)
+    }
+}
+
+impl CSharpAnalyzer {
+    /// Resolve each type through the namespaces visible at its use site. Returns the
+    /// `namespace.Type` paths that resolved to project files, for import tracing.
+    fn resolve_csharp_type_definitions(
+        &self,
+        result: &mut AnalysisResult,
+        project_root: &Path,
+        scope: &CSharpScope,
+        index: &CSharpProjectIndex,
+    ) -> Vec<String> {
+        let mut resolved_types = Vec::new();
+
+        for type_ref in &mut result.type_references {
+            if type_ref.definition_path.is_some() || type_ref.is_external {
+                continue;
+            }
+
+            let Some((type_path, file)) =
+                scope.resolve_type(&type_ref.name, type_ref.module.as_deref(), index)
+            else {
+                if type_ref.module.is_none() && scope.has_only_external_candidates(index) {
+                    type_ref.is_external = true;
+                }
+                continue;
+            };
+            if file == scope.current_file {
+                continue;
+            }
+
+            match validate_import_path(project_root, &file) {
+                Ok(validated) => {
+                    type_ref.definition_path = Some(validated);
+                    if !resolved_types.contains(&type_path) {
+                        resolved_types.push(type_path);
+                    }
+                }
+                Err(_) => type_ref.is_external = true,
+            }
+        }
+
+        resolved_types
+    }
+
+    /// A `using Acme.Services;` names a whole namespace, but the file only depends on the
+    /// types it uses from it. Trace those types instead of an arbitrary namespace member,
+    /// including types reached through the enclosing namespaces without any using.
+    fn replace_namespace_usings(
+        &self,
+        result: &mut AnalysisResult,
+        resolved_types: &[String],
+        index: &CSharpProjectIndex,
+    ) {
+        let line = result.imports.first().map_or(1, |import| import.line);
+        let mut imports: Vec<Import> = result
+            .imports
+            .drain(..)
+            .filter(|import| !(import.items.is_empty() && index.has_namespace(&import.module)))
+            .collect();
+
+        for type_path in resolved_types {
+            if imports.iter().any(|import| &import.module == type_path) {
+                continue;
+            }
+            let type_name = type_path.rsplit('.').next().unwrap_or(type_path);
+            imports.push(Import {
+                module: type_path.clone(),
+                items: vec![type_name.to_string()],
+                is_relative: false,
+                line,
+            });
+        }
+
+        result.imports = imports;
+    }
+}
+
+/// Names visible from one C# file: its namespace, its usings, and the projects it can see.
+struct CSharpScope {
+    current_file: PathBuf,
+    /// Innermost namespace first, ending with the global namespace.
+    enclosing_namespaces: Vec<String>,
+    using_namespaces: Vec<String>,
+    static_using_types: Vec<String>,
+    aliases: HashMap<String, String>,
+    visible_projects: Option<HashSet<PathBuf>>,
+}
+
+impl CSharpScope {
+    fn new(
+        current_file: &Path,
+        content: &str,
+        imports: &[Import],
+        index: &CSharpProjectIndex,
+    ) -> Self {
+        let namespace = csharp_namespace(content).unwrap_or_default();
+        let mut enclosing_namespaces = Vec::new();
+        let mut prefix = namespace.as_str();
+        while !prefix.is_empty() {
+            enclosing_namespaces.push(prefix.to_string());
+            prefix = prefix.rsplit_once('.').map_or("", |(parent, _)| parent);
+        }
+        enclosing_namespaces.push(String::new());
+
+        let mut using_namespaces = Vec::new();
+        let mut static_using_types = Vec::new();
+        let mut aliases = HashMap::new();
+        for import in imports {
+            match import.items.first().map(String::as_str) {
+                None => using_namespaces.push(import.module.clone()),
+                Some("*") => static_using_types.push(import.module.clone()),
+                Some(item) => {
+                    if let Some(alias) = item.strip_prefix("as ") {
+                        aliases.insert(alias.to_string(), import.module.clone());
+                    }
+                }
+            }
+        }
+
+        Self {
+            current_file: current_file
+                .canonicalize()
+                .unwrap_or_else(|_| current_file.to_path_buf()),
+            enclosing_namespaces,
+            using_namespaces,
+            static_using_types,
+            aliases,
+            visible_projects: index.visible_projects(current_file),
+        }
+    }
+
+    /// Resolve a type name to its `namespace.Type` path and defining file.
+    fn resolve_type(
+        &self,
+        name: &str,
+        qualifier: Option<&str>,
+        index: &CSharpProjectIndex,
+    ) -> Option<(String, PathBuf)> {
+        let mut candidates = Vec::new();
+
+        match qualifier {
+            Some(qualifier) => {
+                let qualifier = self
+                    .aliases
+                    .get(qualifier)
+                    .map(String::as_str)
+                    .unwrap_or(qualifier);
+                candidates.push(format!("{qualifier}.{name}"));
+                for namespace in &self.enclosing_namespaces {
+                    if !namespace.is_empty() {
+                        candidates.push(format!("{namespace}.{qualifier}.{name}"));
+                    }
+                }
+            }
+            None => {
+                if let Some(target) = self.aliases.get(name) {
+                    candidates.push(target.clone());
+                }
+                for namespace in self
+                    .enclosing_namespaces
+                    .iter()
+                    .chain(&self.using_namespaces)
+                {
+                    candidates.push(qualify(namespace, name));
+                    // [Authorize] refers to AuthorizeAttribute
+                    candidates.push(qualify(namespace, &format!("{name}Attribute")));
+                }
+                // Nested types of statically imported classes
+                for type_path in &self.static_using_types {
+                    candidates.push(format!("{type_path}.{name}"));
+                }
+            }
+        }
+
+        candidates.into_iter().find_map(|type_path| {
+            let file = index.type_file(&type_path, self.visible_projects.as_ref())?;
+            Some((type_path, file))
+        })
+    }
+
+    /// True when every namespace a bare type name could come from is outside the project.
+    fn has_only_external_candidates(&self, index: &CSharpProjectIndex) -> bool {
+        !self.using_namespaces.is_empty()
+            && self
+                .using_namespaces
+                .iter()
+                .all(|namespace| !index.has_namespace(namespace))
+            && self
+                .enclosing_namespaces
+                .iter()
+                .all(|namespace| namespace.is_empty() || !index.has_namespace(namespace))
+            && self.static_using_types.is_empty()
+            && self.aliases.is_empty()
+    }
+}
+
+fn qualify(namespace: &str, name: &str) -> String {
+    if namespace.is_empty() {
+        name.to_string()
+    } else {
+        format!("{namespace}.{name}")
+    }
+}
+
+pub struct CSharpModuleResolver;
+
+impl ModuleResolver for CSharpModuleResolver {
+    fn resolve_import(
+        &self,
+        module_path: &str,
+        from_file: &Path,
+        base_dir: &Path,
+    ) -> Result<ResolvedPath, ContextCreatorError> {
+        let module_path = module_path.trim();
+        validate_module_name(module_path)?;
+
+        let index = csharp_project_index(base_dir);
+        let visible_projects = index.visible_projects(from_file);
+
+        if !self.is_external_module(module_path) {
+            if let Some(file) = index.type_file(module_path, visible_projects.as_ref()) {
+                return resolved_csharp_path(file, base_dir, 0.95);
+            }
+            if let Some(file) = index.namespace_file(module_path, visible_projects.as_ref()) {
+                return resolved_csharp_path(file, base_dir, 0.7);
+            }
+        }
+
+        Ok(ResolvedPath {
+            path: index
+                .owning_project(from_file)
+                .map(|project| project.file.clone())
+                .unwrap_or_else(|| base_dir.to_path_buf()),
+            is_external: true,
+            confidence: if self.is_external_module(module_path) {
+                1.0
+            } else {
+                0.5
+            },
+        })
+    }
+
+    fn get_file_extensions(&self) -> Vec<&'static str> {
+        vec!["cs"]
+    }
+
+    fn is_external_module(&self, module_path: &str) -> bool {
+        let root = module_path.split('.').next().unwrap_or(module_path);
+        matches!(
+            root,
+            "System"
+                | "Microsoft"
+                | "Windows"
+                | "Newtonsoft"
+                | "Xunit"
+                | "NUnit"
+                | "Moq"
+                | "NSubstitute"
+                | "FluentAssertions"
+                | "AutoMapper"
+                | "MediatR"
+                | "Serilog"
+                | "Polly"
+        )
+    }
+}
+
+fn resolved_csharp_path(
+    path: PathBuf,
+    base_dir: &Path,
+    confidence: f32,
+) -> Result<ResolvedPath, ContextCreatorError> {
+    Ok(ResolvedPath {
+        path: validate_import_path(base_dir, &path)?,
+        is_external: false,
+        confidence,
+    })
+}
+
+/// A `.csproj` project: SDK-style projects compile every `.cs` file under their directory.
+#[derive(Debug, Clone)]
+struct CSharpProject {
+    file: PathBuf,
+    dir: PathBuf,
+    references: Vec<PathBuf>,
+}
+
+/// Namespace and type declarations of every C# file in the project, with the
+/// `.csproj` graph used to decide which of them a file can see.
+#[derive(Debug, Default)]
+struct CSharpProjectIndex {
+    /// `Namespace.Type` to the files declaring it (several for partial types).
+    types: HashMap<String, Vec<PathBuf>>,
+    /// Namespace to the files declaring types in it.
+    namespaces: HashMap<String, Vec<PathBuf>>,
+    projects: Vec<CSharpProject>,
+}
+
+impl CSharpProjectIndex {
+    fn build(base_dir: &Path) -> Self {
+        let mut index = Self::default();
+        let mut project_files = Vec::new();
+        let mut source_files = Vec::new();
+        collect_csharp_files(base_dir, 0, &mut project_files, &mut source_files);
+
+        // Solutions can list projects that live outside the scanned directories.
+        for solution in project_files
+            .iter()
+            .filter(|path| path.extension().and_then(|ext| ext.to_str()) == Some("sln"))
+            .cloned()
+            .collect::<Vec<_>>()
+        {
+            project_files.extend(solution_projects(&solution));
+        }
+
+        let mut seen = HashSet::new();
+        for project_file in project_files
+            .into_iter()
+            .filter(|path| path.extension().and_then(|ext| ext.to_str()) == Some("csproj"))
+        {
+            let project_file = project_file.canonicalize().unwrap_or(project_file);
+            if !project_file.is_file() || !seen.insert(project_file.clone()) {
+                continue;
+            }
+            let Some(dir) = project_file.parent().map(Path::to_path_buf) else {
+                continue;
+            };
+            let references = fs::read_to_string(&project_file)
+                .map(|content| project_references(&content, &dir))
+                .unwrap_or_default();
+            index.projects.push(CSharpProject {
+                file: project_file,
+                dir,
+                references,
+            });
+        }
+
+        let mut parser = Parser::new();
+        if parser
+            .set_language(tree_sitter_c_sharp::language())
+            .is_err()
+        {
+            return index;
+        }
+        source_files.sort();
+        for source_file in source_files {
+            let Ok(content) = fs::read_to_string(&source_file) else {
+                continue;
+            };
+            let Some(tree) = parser.parse(&content, None) else {
+                continue;
+            };
+            let source_file = source_file.canonicalize().unwrap_or(source_file);
+            let mut declarations = Vec::new();
+            collect_type_declarations(tree.root_node(), &content, "", &mut declarations);
+            for (namespace, type_name) in declarations {
+                index
+                    .types
+                    .entry(qualify(&namespace, &type_name))
+                    .or_default()
+                    .push(source_file.clone());
+                let files = index.namespaces.entry(namespace).or_default();
+                if !files.contains(&source_file) {
+                    files.push(source_file.clone());
+                }
+            }
+        }
+
+        index
+    }
+
+    fn has_namespace(&self, namespace: &str) -> bool {
+        self.namespaces.contains_key(namespace)
+    }
+
+    fn type_file(
+        &self,
+        type_path: &str,
+        visible_projects: Option<&HashSet<PathBuf>>,
+    ) -> Option<PathBuf> {
+        self.first_visible(self.types.get(type_path)?, visible_projects)
+    }
+
+    fn namespace_file(
+        &self,
+        namespace: &str,
+        visible_projects: Option<&HashSet<PathBuf>>,
+    ) -> Option<PathBuf> {
+        self.first_visible(self.namespaces.get(namespace)?, visible_projects)
+    }
+
+    fn first_visible(
+        &self,
+        files: &[PathBuf],
+        visible_projects: Option<&HashSet<PathBuf>>,
+    ) -> Option<PathBuf> {
+        files
+            .iter()
+            .find(|file| match (visible_projects, self.owning_project(file)) {
+                (Some(visible), Some(project)) => visible.contains(&project.file),
+                _ => true,
+            })
+            .cloned()
+    }
+
+    /// The project whose directory most closely contains the file.
+    fn owning_project(&self, file: &Path) -> Option<&CSharpProject> {
+        let file = file.canonicalize().unwrap_or_else(|_| file.to_path_buf());
+        self.projects
+            .iter()
+            .filter(|project| file.starts_with(&project.dir))
+            .max_by_key(|project| project.dir.components().count())
+    }
+
+    /// The file's own project plus everything it references, transitively. `None` when the
+    /// file is not part of any project, in which case every project is visible.
+    fn visible_projects(&self, file: &Path) -> Option<HashSet<PathBuf>> {
+        let project = self.owning_project(file)?;
+        let mut visible = HashSet::new();
+        let mut pending = vec![project.file.clone()];
+        while let Some(project_file) = pending.pop() {
+            if !visible.insert(project_file.clone()) {
+                continue;
+            }
+            if let Some(project) = self
+                .projects
+                .iter()
+                .find(|project| project.file == project_file)
+            {
+                pending.extend(project.references.iter().cloned());
+            }
+        }
+        Some(visible)
+    }
+}
+
+fn csharp_index_cache() -> &'static Mutex<HashMap<PathBuf, Arc<CSharpProjectIndex>>> {
+    static CACHE: OnceLock<Mutex<HashMap<PathBuf, Arc<CSharpProjectIndex>>>> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn csharp_project_index(base_dir: &Path) -> Arc<CSharpProjectIndex> {
+    if let Some(index) = csharp_index_cache()
+        .lock()
+        .ok()
+        .and_then(|cache| cache.get(base_dir).cloned())
+    {
+        return index;
+    }
+
+    let index = Arc::new(CSharpProjectIndex::build(base_dir));
+    if let Ok(mut cache) = csharp_index_cache().lock() {
+        cache.insert(base_dir.to_path_buf(), index.clone());
+    }
+    index
+}
+
+fn collect_csharp_files(
+    dir: &Path,
+    depth: usize,
+    project_files: &mut Vec<PathBuf>,
+    source_files: &mut Vec<PathBuf>,
+) {
+    if depth > 12 {
+        return;
+    }
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+            continue;
+        };
+        if name.starts_with('.') {
+            continue;
+        }
+
+        if path.is_dir() {
+            if !CSHARP_SKIPPED_DIRS.contains(&name) {
+                collect_csharp_files(&path, depth + 1, project_files, source_files);
+            }
+            continue;
+        }
+
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("cs") => source_files.push(path),
+            Some("csproj" | "sln") => project_files.push(path),
+            _ => {}
+        }
+    }
+}
+
+/// Top-level type declarations with their namespace, for block-scoped, nested, and
+/// file-scoped namespaces.
+fn collect_type_declarations(
+    node: Node<'_>,
+    content: &str,
+    namespace: &str,
+    declarations: &mut Vec<(String, String)>,
+) {
+    let mut cursor = node.walk();
+    for child in node.named_children(&mut cursor) {
+        match child.kind() {
+            "namespace_declaration" | "file_scoped_namespace_declaration" => {
+                let Some(name) = child
+                    .child_by_field_name("name")
+                    .and_then(|name| name.utf8_text(content.as_bytes()).ok())
+                else {
+                    continue;
+                };
+                let nested = qualify(namespace, &name.split_whitespace().collect::<String>());
+                let body = child.child_by_field_name("body").unwrap_or(child);
+                collect_type_declarations(body, content, &nested, declarations);
+            }
+            "class_declaration"
+            | "interface_declaration"
+            | "struct_declaration"
+            | "record_declaration"
+            | "record_struct_declaration"
+            | "enum_declaration"
+            | "delegate_declaration" => {
+                if let Some(name) = child
+                    .child_by_field_name("name")
+                    .and_then(|name| name.utf8_text(content.as_bytes()).ok())
+                {
+                    declarations.push((namespace.to_string(), name.to_string()));
+                }
+            }
+            "declaration_list" => {
+                collect_type_declarations(child, content, namespace, declarations)
+            }
+            _ => {}
+        }
+    }
+}
+
+/// The first namespace declared in a file.
+fn csharp_namespace(content: &str) -> Option<String> {
+    content.lines().find_map(|line| {
+        let declaration = line.trim().strip_prefix("namespace ")?;
+        let namespace = declaration.split([';', '{']).next()?.trim();
+        (!namespace.is_empty()).then(|| namespace.to_string())
+    })
+}
+
+/// `<ProjectReference Include="..\Shop.Core\Shop.Core.csproj" />` entries of a project.
+fn project_references(content: &str, project_dir: &Path) -> Vec<PathBuf> {
+    content
+        .split("<ProjectReference")
+        .skip(1)
+        .filter_map(|element| xml_attribute(element, "Include"))
+        .map(|include| normalize_windows_path(project_dir, &include))
+        .collect()
+}
+
+/// Project entries of a solution file:
+/// `Project("{FAE04EC0-...}") = "Shop.Api", "src\Shop.Api\Shop.Api.csproj", "{...}"`
+fn solution_projects(solution: &Path) -> Vec<PathBuf> {
+    let (Ok(content), Some(solution_dir)) = (fs::read_to_string(solution), solution.parent())
+    else {
+        return Vec::new();
+    };
+
+    content
+        .lines()
+        .filter(|line| line.trim_start().starts_with("Project("))
+        .filter_map(|line| {
+            line.split('"')
+                .find(|part| part.ends_with(".csproj"))
+                .map(|project| normalize_windows_path(solution_dir, project))
+        })
+        .collect()
+}
+
+fn xml_attribute(element: &str, name: &str) -> Option<String> {
+    let start = element.find(&format!("{name}=\""))? + name.len() + 2;
+    let end = element[start..].find('"')? + start;
+    Some(element[start..end].to_string())
+}
+
+/// Join a project-relative path written with Windows separators, resolving `..` lexically.
+fn normalize_windows_path(base: &Path, relative: &str) -> PathBuf {
+    let mut path = base.to_path_buf();
+    for component in Path::new(&relative.replace('\\', "/")).components() {
+        match component {
+            Component::ParentDir => {
+                path.pop();
+            }
+            Component::Normal(segment) => path.push(segment),
+            _ => {}
+        }
+    }
+    path.canonicalize().unwrap_or(path)
 }
@@ -1,24 +1,36 @@
-//! Semantic analyzer for Dart
+//! Semantic analyzer for Dart and Flutter packages
 
-use crate::core::semantic::analyzer::{
-    AnalysisResult, LanguageAnalyzer, SemanticContext, SemanticResult,
+use crate::core::semantic::{
+    analyzer::{AnalysisResult, Import, LanguageAnalyzer, SemanticContext, SemanticResult},
+    languages::is_capitalized_type_name,
+    path_validator::{validate_import_path, validate_module_name},
+    query_engine::QueryEngine,
+    resolver::{ModuleResolver, ResolvedPath},
 };
-use std::path::Path;
+use crate::utils::error::ContextCreatorError;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
 use tree_sitter::Parser;
 
+/// Directories holding build output, platform runners, or tooling state rather than packages.
+const DART_SKIPPED_DIRS: &[&str] = &["build", "node_modules", "ios", "android", "macos"];
+
+/// How many `export` hops to follow from an imported library.
+const MAX_EXPORT_DEPTH: usize = 8;
+
 #[allow(clippy::new_without_default)]
 pub struct DartAnalyzer {
-    #[allow(dead_code)]
-    #[allow(dead_code)]
-    parser: Parser,
+    query_engine: QueryEngine,
 }
 
 impl DartAnalyzer {
     pub fn new() -> Self {
-        let parser = Parser::new();
-        // Note: tree_sitter_dart::language() would be used here when the crate is added
-        // parser.set_language(tree_sitter_dart::language()).unwrap();
-        Self { parser }
+        let language = tree_sitter_dart::language();
+        let query_engine =
+            QueryEngine::new(language, "dart").expect("Failed to create Dart query engine");
+        Self { query_engine }
     }
 }
 
@@ -29,19 +41,539 @@ impl LanguageAnalyzer for DartAnalyzer {
 
     fn analyze_file(
         &self,
-        _path: &Path,
-        _content: &str,
-        _context: &SemanticContext,
+        path: &Path,
+        content: &str,
+        context: &SemanticContext,
     ) -> SemanticResult<AnalysisResult> {
-        // TODO: Implement Dart analysis
-        Ok(AnalysisResult::default())
+        let mut parser = Parser::new();
+        parser
+            .set_language(tree_sitter_dart::language())
+            .map_err(|e| ContextCreatorError::ParseError(format!("Failed to set language: {e}")))?;
+
+        let mut result = self
+            .query_engine
+            .analyze_with_parser(&mut parser, content)?;
+
+        // Receiver calls match both the named-receiver and the generic selector pattern, and
+        // constructor calls and receivers are captured as candidate types
+        result.dedupe_function_calls(|_| true);
+        result.dedupe_type_references(|type_ref| is_capitalized_type_name(&type_ref.name));
+        self.follow_library_exports(&mut result, path, content, &context.base_dir);
+
+        self.query_engine.resolve_type_definitions(
+            &mut result.type_references,
+            path,
+            &context.base_dir,
+        )?;
+
+        Ok(result)
     }
 
     fn can_handle_extension(&self, extension: &str) -> bool {
-        matches!(extension, "dart")
+        extension == "dart"
     }
 
     fn supported_extensions(&self) -> Vec<&'static str> {
         vec!["dart"]
     }
+
+    fn query_engine(&self) -> Option<&QueryEngine> {
+        Some(&self.query_engine)
+    }
+}
+
+impl DartAnalyzer {
+    /// Look names up in the libraries this file imports, including what they re-export.
+    /// Flutter packages import a barrel (`package:shop/shop.dart`) that exports `src/`
+    /// files, so the declaring file is added as an import and set as the type definition.
+    /// A `part` file sees the imports of the library it belongs to.
+    fn follow_library_exports(
+        &self,
+        result: &mut AnalysisResult,
+        path: &Path,
+        content: &str,
+        base_dir: &Path,
+    ) {
+        let resolver = DartModuleResolver;
+        let mut libraries: Vec<(PathBuf, Vec<String>)> = Vec::new();
+        let mut add_library = |uri: &str, show: Vec<String>, from_file: &Path| {
+            if let Ok(resolved) = resolver.resolve_import(uri, from_file, base_dir) {
+                if !resolved.is_external {
+                    libraries.push((resolved.path, show));
+                }
+            }
+        };
+
+        for import in &result.imports {
+            let show = import
+                .items
+                .iter()
+                .filter(|item| !item.starts_with("as "))
+                .cloned()
+                .collect();
+            add_library(&import.module, show, path);
+        }
+        for directive in dart_directives(content) {
+            if directive.kind != DartDirectiveKind::PartOf {
+                continue;
+            }
+            let Ok(resolved) = resolver.resolve_import(&directive.uri, path, base_dir) else {
+                continue;
+            };
+            if resolved.is_external {
+                continue;
+            }
+            let Ok(library_content) = fs::read_to_string(&resolved.path) else {
+                continue;
+            };
+            for directive in dart_directives(&library_content) {
+                if directive.kind == DartDirectiveKind::Import {
+                    add_library(&directive.uri, directive.show, &resolved.path);
+                }
+            }
+        }
+
+        let mut visible: HashMap<String, PathBuf> = HashMap::new();
+        for (library, show) in &libraries {
+            for (name, file) in dart_exported_declarations(library, base_dir) {
+                if show.is_empty() || show.contains(&name) {
+                    visible.entry(name).or_insert(file);
+                }
+            }
+        }
+        if visible.is_empty() {
+            return;
+        }
+
+        let current_file = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+        let imported_files: HashSet<&PathBuf> = libraries.iter().map(|(file, _)| file).collect();
+        let mut added = HashSet::new();
+        let mut extra_imports = Vec::new();
+        let referenced = result
+            .type_references
+            .iter()
+            .map(|type_ref| (type_ref.name.as_str(), type_ref.line))
+            .chain(
+                result
+                    .function_calls
+                    .iter()
+                    .filter(|call| call.module.is_none())
+                    .map(|call| (call.name.as_str(), call.line)),
+            );
+        for (name, line) in referenced {
+            let Some(file) = visible.get(name) else {
+                continue;
+            };
+            if file == &current_file || imported_files.contains(file) || !added.insert(file) {
+                continue;
+            }
+            if let Some(uri) = relative_dart_uri(&current_file, file) {
+                extra_imports.push(Import {
+                    module: uri,
+                    items: vec![name.to_string()],
+                    is_relative: true,
+                    line,
+                });
+            }
+        }
+        result.imports.extend(extra_imports);
+
+        for type_ref in &mut result.type_references {
+            if type_ref.definition_path.is_some() || type_ref.is_external {
+                continue;
+            }
+            if let Some(file) = visible.get(&type_ref.name) {
+                if let Ok(validated) = validate_import_path(base_dir, file) {
+                    type_ref.definition_path = Some(validated);
+                }
+            }
+        }
+    }
+}
+
+pub struct DartModuleResolver;
+
+impl ModuleResolver for DartModuleResolver {
+    fn resolve_import(
+        &self,
+        module_path: &str,
+        from_file: &Path,
+        base_dir: &Path,
+    ) -> Result<ResolvedPath, ContextCreatorError> {
+        if self.is_external_module(module_path) {
+            return Ok(external_dart_path(from_file, base_dir, 1.0));
+        }
+
+        if let Some(package_path) = module_path.strip_prefix("package:") {
+            validate_module_name(package_path)?;
+            let Some((package, library)) = package_path.split_once('/') else {
+                return Ok(external_dart_path(from_file, base_dir, 0.5));
+            };
+
+            return match dart_package_map(base_dir).lib_dir(package, from_file) {
+                Some(lib_dir) if lib_dir.join(library).is_file() => Ok(ResolvedPath {
+                    path: validate_import_path(base_dir, &lib_dir.join(library))?,
+                    is_external: false,
+                    confidence: 0.95,
+                }),
+                // Hosted and SDK packages live outside the project
+                _ => Ok(external_dart_path(from_file, base_dir, 0.9)),
+            };
+        }
+
+        validate_relative_uri(module_path)?;
+        let candidate = from_file.parent().unwrap_or(base_dir).join(module_path);
+        if candidate.is_file() {
+            return Ok(ResolvedPath {
+                path: validate_import_path(base_dir, &candidate)?,
+                is_external: false,
+                confidence: 0.95,
+            });
+        }
+
+        Ok(external_dart_path(from_file, base_dir, 0.5))
+    }
+
+    fn get_file_extensions(&self) -> Vec<&'static str> {
+        vec!["dart"]
+    }
+
+    fn is_external_module(&self, module_path: &str) -> bool {
+        module_path.starts_with("dart:")
+    }
+}
+
+/// Unresolved URIs name SDK libraries or hosted packages, which are declared in the
+/// importing package's `pubspec.yaml`.
+fn external_dart_path(from_file: &Path, base_dir: &Path, confidence: f32) -> ResolvedPath {
+    let pubspec = from_file
+        .ancestors()
+        .skip(1)
+        .take_while(|dir| dir.starts_with(base_dir))
+        .map(|dir| dir.join("pubspec.yaml"))
+        .find(|path| path.is_file())
+        .unwrap_or_else(|| base_dir.join("pubspec.yaml"));
+
+    ResolvedPath {
+        path: pubspec,
+        is_external: true,
+        confidence,
+    }
+}
+
+/// Relative URIs may climb with `../`; containment is checked after resolution.
+fn validate_relative_uri(uri: &str) -> Result<(), ContextCreatorError> {
+    let path = Path::new(uri);
+    if uri.is_empty()
+        || uri.len() > 255
+        || uri.contains('\0')
+        || uri.contains(':')
+        || path.is_absolute()
+        || path
+            .components()
+            .any(|component| matches!(component, Component::Prefix(_) | Component::RootDir))
+    {
+        return Err(ContextCreatorError::SecurityError(format!(
+            "Invalid Dart URI: {uri}"
+        )));
+    }
+
+    Ok(())
+}
+
+/// The relative URI from one Dart file to another, as written in an `import`.
+fn relative_dart_uri(from_file: &Path, to_file: &Path) -> Option<String> {
+    let from_dir: Vec<_> = from_file.parent()?.components().collect();
+    let to: Vec<_> = to_file.components().collect();
+    let shared = from_dir
+        .iter()
+        .zip(&to)
+        .take_while(|(left, right)| left == right)
+        .count();
+    if shared == 0 {
+        return None;
+    }
+
+    let segments: Vec<String> = std::iter::repeat("..".to_string())
+        .take(from_dir.len() - shared)
+        .chain(
+            to[shared..]
+                .iter()
+                .map(|component| component.as_os_str().to_string_lossy().into_owned()),
+        )
+        .collect();
+    Some(segments.join("/"))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DartDirectiveKind {
+    Import,
+    Export,
+    Part,
+    PartOf,
+}
+
+#[derive(Debug)]
+struct DartDirective {
+    kind: DartDirectiveKind,
+    uri: String,
+    /// Names listed by a `show` combinator; empty means everything.
+    show: Vec<String>,
+}
+
+/// The directives at the top of a Dart file. Directives must precede all declarations,
+/// so scanning stops at the first statement that is not one.
+fn dart_directives(content: &str) -> Vec<DartDirective> {
+    let code: String = content
+        .lines()
+        .map(|line| line.split("//").next().unwrap_or_default())
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let mut directives = Vec::new();
+    for statement in code.split(';') {
+        let statement = statement.trim();
+        let statement = statement
+            .lines()
+            .skip_while(|line| line.trim().starts_with('@') || line.trim().is_empty())
+            .collect::<Vec<_>>()
+            .join(" ");
+        let (kind, rest) = if let Some(rest) = statement.strip_prefix("import ") {
+            (DartDirectiveKind::Import, rest)
+        } else if let Some(rest) = statement.strip_prefix("export ") {
+            (DartDirectiveKind::Export, rest)
+        } else if let Some(rest) = statement.strip_prefix("part of ") {
+            (DartDirectiveKind::PartOf, rest)
+        } else if let Some(rest) = statement.strip_prefix("part ") {
+            (DartDirectiveKind::Part, rest)
+        } else if statement.starts_with("library") || statement.is_empty() {
+            continue;
+        } else {
+            break;
+        };
+
+        let mut quoted = rest.split(['\'', '"']);
+        let (Some(_), Some(uri), Some(combinators)) = (quoted.next(), quoted.next(), quoted.next())
+        else {
+            continue;
+        };
+        let show = combinators
+            .split_once("show ")
+            .map(|(_, names)| {
+                names
+                    .split(" hide ")
+                    .next()
+                    .unwrap_or_default()
+                    .split(',')
+                    .map(|name| name.trim().to_string())
+                    .filter(|name| !name.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        directives.push(DartDirective {
+            kind,
+            uri: uri.to_string(),
+            show,
+        });
+    }
+
+    directives
+}
+
+/// Public top-level names a library makes visible to importers, mapped to the file
+/// declaring them: its own declarations, its `part` files, and its `export`s.
+fn dart_exported_declarations(library: &Path, base_dir: &Path) -> Vec<(String, PathBuf)> {
+    let resolver = DartModuleResolver;
+    let mut parser = Parser::new();
+    if parser.set_language(tree_sitter_dart::language()).is_err() {
+        return Vec::new();
+    }
+
+    let mut declarations = Vec::new();
+    let mut visited = HashSet::new();
+    let mut pending = vec![(library.to_path_buf(), Vec::<String>::new(), 0)];
+    while let Some((file, show, depth)) = pending.pop() {
+        if depth > MAX_EXPORT_DEPTH || !visited.insert(file.clone()) {
+            continue;
+        }
+        let Ok(content) = fs::read_to_string(&file) else {
+            continue;
+        };
+
+        if let Some(tree) = parser.parse(&content, None) {
+            let mut cursor = tree.root_node().walk();
+            for node in tree.root_node().named_children(&mut cursor) {
+                let name = match node.kind() {
+                    "class_definition" | "enum_declaration" | "extension_declaration" => {
+                        node.child_by_field_name("name")
+                    }
+                    "mixin_declaration" => node.named_child(0),
+                    "type_alias" => node.named_child(0),
+                    // Top-level functions parse as a signature followed by a body
+                    "function_signature" => node.child_by_field_name("name"),
+                    "lambda_expression" => node
+                        .child_by_field_name("parameters")
+                        .and_then(|signature| signature.child_by_field_name("name")),
+                    _ => None,
+                };
+                let Some(name) = name.and_then(|name| name.utf8_text(content.as_bytes()).ok())
+                else {
+                    continue;
+                };
+                if !name.starts_with('_') && (show.is_empty() || show.iter().any(|s| s == name)) {
+                    declarations.push((name.to_string(), file.clone()));
+                }
+            }
+        }
+
+        for directive in dart_directives(&content) {
+            let show = match directive.kind {
+                DartDirectiveKind::Export if !directive.show.is_empty() => directive.show,
+                DartDirectiveKind::Export | DartDirectiveKind::Part => show.clone(),
+                _ => continue,
+            };
+            if let Ok(resolved) = resolver.resolve_import(&directive.uri, &file, base_dir) {
+                if !resolved.is_external {
+                    pending.push((resolved.path, show, depth + 1));
+                }
+            }
+        }
+    }
+
+    declarations
+}
+
+/// Package names mapped to their `lib/` directories, from every `pubspec.yaml` in the
+/// project and the `.dart_tool/package_config.json` written by `pub get`.
+#[derive(Debug, Default)]
+struct DartPackageMap {
+    /// Package name to `lib/` for every package in the project.
+    packages: HashMap<String, PathBuf>,
+    /// Package root to the path dependencies its `package_config.json` resolved, which
+    /// take precedence for files of that package.
+    configs: Vec<(PathBuf, HashMap<String, PathBuf>)>,
+}
+
+impl DartPackageMap {
+    fn build(base_dir: &Path) -> Self {
+        let mut map = Self::default();
+        let canonical_base = base_dir
+            .canonicalize()
+            .unwrap_or_else(|_| base_dir.to_path_buf());
+
+        let walker = walkdir::WalkDir::new(base_dir)
+            .max_depth(8)
+            .sort_by_file_name()
+            .into_iter()
+            .filter_entry(|entry| {
+                entry.depth() == 0
+                    || !entry.file_name().to_str().is_some_and(|name| {
+                        name.starts_with('.') || DART_SKIPPED_DIRS.contains(&name)
+                    })
+            });
+
+        for entry in walker.flatten() {
+            if entry.file_name() != "pubspec.yaml" {
+                continue;
+            }
+            let Some(package_root) = entry.path().parent() else {
+                continue;
+            };
+            let package_root = package_root
+                .canonicalize()
+                .unwrap_or_else(|_| package_root.to_path_buf());
+            if let Some(name) = fs::read_to_string(entry.path())
+                .ok()
+                .and_then(|pubspec| pubspec_name(&pubspec))
+            {
+                map.packages
+                    .entry(name)
+                    .or_insert_with(|| package_root.join("lib"));
+            }
+
+            let config = package_config(&package_root, &canonical_base);
+            if !config.is_empty() {
+                map.configs.push((package_root, config));
+            }
+        }
+
+        map
+    }
+
+    fn lib_dir(&self, package: &str, from_file: &Path) -> Option<PathBuf> {
+        let from_file = from_file
+            .canonicalize()
+            .unwrap_or_else(|_| from_file.to_path_buf());
+        self.configs
+            .iter()
+            .filter(|(root, _)| from_file.starts_with(root))
+            .max_by_key(|(root, _)| root.components().count())
+            .and_then(|(_, config)| config.get(package))
+            .or_else(|| self.packages.get(package))
+            .cloned()
+    }
+}
+
+/// The top-level `name:` of a pubspec.
+fn pubspec_name(pubspec: &str) -> Option<String> {
+    pubspec.lines().find_map(|line| {
+        let name = line.strip_prefix("name:")?;
+        let name = name.split('#').next()?.trim().trim_matches(['\'', '"']);
+        (!name.is_empty()).then(|| name.to_string())
+    })
+}
+
+/// Packages from `.dart_tool/package_config.json` whose sources are inside the project.
+/// `rootUri` is relative to the `.dart_tool` directory unless it is a `file://` URI.
+fn package_config(package_root: &Path, canonical_base: &Path) -> HashMap<String, PathBuf> {
+    let config_dir = package_root.join(".dart_tool");
+    let Some(config) = fs::read_to_string(config_dir.join("package_config.json"))
+        .ok()
+        .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+    else {
+        return HashMap::new();
+    };
+
+    let mut packages = HashMap::new();
+    for package in config["packages"].as_array().into_iter().flatten() {
+        let (Some(name), Some(root_uri)) = (package["name"].as_str(), package["rootUri"].as_str())
+        else {
+            continue;
+        };
+        let package_uri = package["packageUri"].as_str().unwrap_or("lib/");
+        let root = match root_uri.strip_prefix("file://") {
+            Some(absolute) => PathBuf::from(absolute),
+            None => config_dir.join(root_uri),
+        };
+        let Ok(lib_dir) = root.join(package_uri).canonicalize() else {
+            continue;
+        };
+        if lib_dir.starts_with(canonical_base) {
+            packages.insert(name.to_string(), lib_dir);
+        }
+    }
+
+    packages
+}
+
+fn dart_package_cache() -> &'static Mutex<HashMap<PathBuf, Arc<DartPackageMap>>> {
+    static CACHE: OnceLock<Mutex<HashMap<PathBuf, Arc<DartPackageMap>>>> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn dart_package_map(base_dir: &Path) -> Arc<DartPackageMap> {
+    if let Some(map) = dart_package_cache()
+        .lock()
+        .ok()
+        .and_then(|cache| cache.get(base_dir).cloned())
+    {
+        return map;
+    }
+
+    let map = Arc::new(DartPackageMap::build(base_dir));
+    if let Ok(mut cache) = dart_package_cache().lock() {
+        cache.insert(base_dir.to_path_buf(), map.clone());
+    }
+    map
 }
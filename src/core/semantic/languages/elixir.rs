@@ -1,23 +1,65 @@
-//! Semantic analyzer for Elixir
+//! Semantic analyzer for Elixir and Mix projects, including umbrella apps
 
-use crate::core::semantic::analyzer::{
-    AnalysisResult, LanguageAnalyzer, SemanticContext, SemanticResult,
+use crate::core::semantic::{
+    analyzer::{AnalysisResult, Import, LanguageAnalyzer, SemanticContext, SemanticResult},
+    path_validator::{validate_import_path, validate_module_name},
+    query_engine::QueryEngine,
+    resolver::{ModuleResolver, ResolvedPath},
 };
-use std::path::Path;
-use tree_sitter::Parser;
+use crate::utils::error::ContextCreatorError;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+use tree_sitter::{Node, Parser};
+
+/// Directories holding dependencies, build output, or front-end assets.
+const ELIXIR_SKIPPED_DIRS: &[&str] = &["_build", "deps", "node_modules", "assets", "cover"];
+
+/// Kernel macros and special forms that parse as local calls but are not function calls.
+const ELIXIR_KERNEL_FORMS: &[&str] = &[
+    "def",
+    "defp",
+    "defmacro",
+    "defmacrop",
+    "defguard",
+    "defguardp",
+    "defdelegate",
+    "defmodule",
+    "defprotocol",
+    "defimpl",
+    "defstruct",
+    "defexception",
+    "defoverridable",
+    "alias",
+    "import",
+    "require",
+    "use",
+    "if",
+    "unless",
+    "case",
+    "cond",
+    "with",
+    "for",
+    "try",
+    "receive",
+    "quote",
+    "unquote",
+    "raise",
+    "fn",
+];
 
 #[allow(clippy::new_without_default)]
 pub struct ElixirAnalyzer {
-    #[allow(dead_code)]
-    parser: Parser,
+    query_engine: QueryEngine,
 }
 
 impl ElixirAnalyzer {
     pub fn new() -> Self {
-        let parser = Parser::new();
-        // Note: tree_sitter_elixir::language() would be used here when the crate is added
-        // parser.set_language(tree_sitter_elixir::language()).unwrap();
-        Self { parser }
+        let language = tree_sitter_elixir::language();
+        let query_engine =
+            QueryEngine::new(language, "elixir").expect("Failed to create Elixir query engine");
+        Self { query_engine }
     }
 }
 
@@ -28,12 +70,33 @@ impl LanguageAnalyzer for ElixirAnalyzer {
 
     fn analyze_file(
         &self,
-        _path: &Path,
-        _content: &str,
-        _context: &SemanticContext,
+        path: &Path,
+        content: &str,
+        context: &SemanticContext,
     ) -> SemanticResult<AnalysisResult> {
-        // TODO: Implement Elixir analysis
-        Ok(AnalysisResult::default())
+        let mut parser = Parser::new();
+        parser
+            .set_language(tree_sitter_elixir::language())
+            .map_err(|e| ContextCreatorError::ParseError(format!("Failed to set language: {e}")))?;
+
+        let tree = parser
+            .parse(content, None)
+            .ok_or_else(|| ContextCreatorError::ParseError("Failed to parse file".to_string()))?;
+        let mut defined_modules = Vec::new();
+        collect_elixir_modules(tree.root_node(), content, "", &mut defined_modules);
+
+        let mut result = self
+            .query_engine
+            .analyze_with_parser(&mut parser, content)?;
+
+        result.function_calls.retain(|call| {
+            call.module.is_some() || !ELIXIR_KERNEL_FORMS.contains(&call.name.as_str())
+        });
+
+        let aliases = self.expand_directives(&mut result, defined_modules.first());
+        self.resolve_module_references(&mut result, path, &aliases, &context.base_dir);
+
+        Ok(result)
     }
 
     fn can_handle_extension(&self, extension: &str) -> bool {
@@ -43,4 +106,347 @@ impl LanguageAnalyzer for ElixirAnalyzer {
     fn supported_extensions(&self) -> Vec<&'static str> {
         vec!["ex", "exs"]
     }
+
+    fn query_engine(&self) -> Option<&QueryEngine> {
+        Some(&self.query_engine)
+    }
+}
+
+impl ElixirAnalyzer {
+    /// Split multi-alias groups into one import per module, expand `__MODULE__`, and
+    /// return the alias table (bound name to full module name).
+    fn expand_directives(
+        &self,
+        result: &mut AnalysisResult,
+        current_module: Option<&String>,
+    ) -> HashMap<String, String> {
+        let mut aliases = HashMap::new();
+        let mut imports = Vec::new();
+
+        for import in result.imports.drain(..) {
+            let prefix = match (import.module.strip_prefix("__MODULE__"), current_module) {
+                (Some(rest), Some(current)) => format!("{current}{rest}"),
+                _ => import.module.clone(),
+            };
+            let is_group = import
+                .items
+                .iter()
+                .any(|item| !item.starts_with("as ") && prefix.rsplit('.').next() != Some(item));
+
+            if !is_group {
+                for item in &import.items {
+                    let name = item.strip_prefix("as ").unwrap_or(item);
+                    aliases.insert(name.to_string(), prefix.clone());
+                }
+                imports.push(Import {
+                    module: prefix,
+                    ..import
+                });
+                continue;
+            }
+
+            for item in &import.items {
+                let module = format!("{prefix}.{item}");
+                let name = item.rsplit('.').next().unwrap_or(item).to_string();
+                aliases.insert(name.clone(), module.clone());
+                imports.push(Import {
+                    module,
+                    items: vec![name],
+                    is_relative: false,
+                    line: import.line,
+                });
+            }
+        }
+
+        result.imports = imports;
+        aliases
+    }
+
+    /// Expand aliased module names in remote calls and type references to full module
+    /// names. Modules called by their full name need no directive in Elixir, so any
+    /// project module referenced that way is added as an import.
+    fn resolve_module_references(
+        &self,
+        result: &mut AnalysisResult,
+        path: &Path,
+        aliases: &HashMap<String, String>,
+        project_root: &Path,
+    ) {
+        let expand = |module: &str| {
+            let (first, rest) = module.split_once('.').unwrap_or((module, ""));
+            match aliases.get(first) {
+                Some(full) if rest.is_empty() => full.clone(),
+                Some(full) => format!("{full}.{rest}"),
+                None => module.to_string(),
+            }
+        };
+        let index = elixir_module_index(project_root);
+        let current_file = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+
+        let mut imported: HashSet<String> = result
+            .imports
+            .iter()
+            .map(|import| import.module.clone())
+            .collect();
+        let mut referenced = Vec::new();
+
+        for call in &mut result.function_calls {
+            if let Some(module) = call.module.as_deref() {
+                let module = expand(module);
+                referenced.push((module.clone(), call.line));
+                call.module = Some(module);
+            }
+        }
+
+        for type_ref in &mut result.type_references {
+            let module = expand(&type_ref.name);
+            referenced.push((module.clone(), type_ref.line));
+
+            if type_ref.definition_path.is_some() || type_ref.is_external {
+                continue;
+            }
+            match index.module_file(&module) {
+                Some(file) if file != current_file => {
+                    if let Ok(validated) = validate_import_path(project_root, &file) {
+                        type_ref.definition_path = Some(validated);
+                    }
+                }
+                Some(_) => {}
+                None if ElixirModuleResolver.is_external_module(&module) => {
+                    type_ref.is_external = true;
+                    type_ref.external_package = Some(module);
+                }
+                None => {}
+            }
+        }
+
+        for (module, line) in referenced {
+            let is_project_module = index
+                .module_file(&module)
+                .is_some_and(|file| file != current_file);
+            if is_project_module && imported.insert(module.clone()) {
+                result.imports.push(Import {
+                    module,
+                    items: Vec::new(),
+                    is_relative: false,
+                    line,
+                });
+            }
+        }
+    }
+}
+
+pub struct ElixirModuleResolver;
+
+impl ModuleResolver for ElixirModuleResolver {
+    fn resolve_import(
+        &self,
+        module_path: &str,
+        from_file: &Path,
+        base_dir: &Path,
+    ) -> Result<ResolvedPath, ContextCreatorError> {
+        validate_module_name(module_path)?;
+
+        if let Some(file) = elixir_module_index(base_dir).module_file(module_path) {
+            return Ok(ResolvedPath {
+                path: validate_import_path(base_dir, &file)?,
+                is_external: false,
+                confidence: 0.95,
+            });
+        }
+
+        Ok(ResolvedPath {
+            path: mix_file(from_file, base_dir),
+            is_external: true,
+            confidence: if self.is_external_module(module_path) {
+                0.9
+            } else {
+                0.5
+            },
+        })
+    }
+
+    fn get_file_extensions(&self) -> Vec<&'static str> {
+        vec!["ex", "exs"]
+    }
+
+    fn is_external_module(&self, module_path: &str) -> bool {
+        let root = module_path.split('.').next().unwrap_or(module_path);
+        matches!(
+            root,
+            "Kernel"
+                | "Enum"
+                | "Stream"
+                | "List"
+                | "Map"
+                | "MapSet"
+                | "Keyword"
+                | "String"
+                | "Integer"
+                | "Float"
+                | "Tuple"
+                | "Atom"
+                | "Process"
+                | "Agent"
+                | "Task"
+                | "GenServer"
+                | "Supervisor"
+                | "DynamicSupervisor"
+                | "Registry"
+                | "Application"
+                | "Logger"
+                | "File"
+                | "Path"
+                | "IO"
+                | "System"
+                | "DateTime"
+                | "NaiveDateTime"
+                | "Date"
+                | "Time"
+                | "Regex"
+                | "URI"
+                | "Access"
+                | "Macro"
+                | "Code"
+                | "ExUnit"
+                | "Mix"
+                | "Ecto"
+                | "Phoenix"
+                | "Plug"
+                | "Jason"
+                | "Oban"
+                | "Absinthe"
+        )
+    }
+}
+
+/// Unresolved modules come from dependencies, which are declared in the nearest `mix.exs`.
+fn mix_file(from_file: &Path, base_dir: &Path) -> PathBuf {
+    from_file
+        .ancestors()
+        .skip(1)
+        .take_while(|dir| dir.starts_with(base_dir))
+        .map(|dir| dir.join("mix.exs"))
+        .find(|path| path.is_file())
+        .unwrap_or_else(|| base_dir.join("mix.exs"))
+}
+
+/// Full names of the modules and protocols a file defines, including nested
+/// `defmodule`s, which are prefixed with their parent.
+fn collect_elixir_modules(node: Node<'_>, content: &str, parent: &str, modules: &mut Vec<String>) {
+    let mut cursor = node.walk();
+    for child in node.named_children(&mut cursor) {
+        if child.kind() != "call" {
+            collect_elixir_modules(child, content, parent, modules);
+            continue;
+        }
+
+        let keyword = child
+            .child_by_field_name("target")
+            .and_then(|target| target.utf8_text(content.as_bytes()).ok());
+        let mut child_cursor = child.walk();
+        let name = child
+            .named_children(&mut child_cursor)
+            .find(|part| part.kind() == "arguments")
+            .and_then(|arguments| arguments.named_child(0))
+            .filter(|argument| argument.kind() == "alias")
+            .and_then(|alias| alias.utf8_text(content.as_bytes()).ok());
+
+        match (keyword, name) {
+            (Some("defmodule" | "defprotocol"), Some(name)) => {
+                let module = if parent.is_empty() {
+                    name.to_string()
+                } else {
+                    format!("{parent}.{name}")
+                };
+                modules.push(module.clone());
+                collect_elixir_modules(child, content, &module, modules);
+            }
+            _ => collect_elixir_modules(child, content, parent, modules),
+        }
+    }
+}
+
+/// Modules defined anywhere in the Mix project, including every umbrella app under
+/// `apps/`. Files under `lib/` win over scripts and test support defining the same module.
+#[derive(Debug, Default)]
+struct ElixirModuleIndex {
+    modules: HashMap<String, Vec<PathBuf>>,
+}
+
+impl ElixirModuleIndex {
+    fn build(base_dir: &Path) -> Self {
+        let mut index = Self::default();
+        let mut parser = Parser::new();
+        if parser.set_language(tree_sitter_elixir::language()).is_err() {
+            return index;
+        }
+
+        let walker = walkdir::WalkDir::new(base_dir)
+            .max_depth(16)
+            .sort_by_file_name()
+            .into_iter()
+            .filter_entry(|entry| {
+                entry.depth() == 0
+                    || !entry.file_name().to_str().is_some_and(|name| {
+                        name.starts_with('.') || ELIXIR_SKIPPED_DIRS.contains(&name)
+                    })
+            });
+
+        for entry in walker.flatten() {
+            let path = entry.path();
+            if !entry.file_type().is_file()
+                || !matches!(
+                    path.extension().and_then(|ext| ext.to_str()),
+                    Some("ex" | "exs")
+                )
+            {
+                continue;
+            }
+            let Ok(content) = fs::read_to_string(path) else {
+                continue;
+            };
+            let Some(tree) = parser.parse(&content, None) else {
+                continue;
+            };
+
+            let file = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+            let mut modules = Vec::new();
+            collect_elixir_modules(tree.root_node(), &content, "", &mut modules);
+            for module in modules {
+                index.modules.entry(module).or_default().push(file.clone());
+            }
+        }
+
+        for files in index.modules.values_mut() {
+            files.sort_by_key(|file| !file.components().any(|part| part.as_os_str() == "lib"));
+        }
+
+        index
+    }
+
+    fn module_file(&self, module: &str) -> Option<PathBuf> {
+        self.modules.get(module)?.first().cloned()
+    }
+}
+
+fn elixir_index_cache() -> &'static Mutex<HashMap<PathBuf, Arc<ElixirModuleIndex>>> {
+    static CACHE: OnceLock<Mutex<HashMap<PathBuf, Arc<ElixirModuleIndex>>>> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn elixir_module_index(base_dir: &Path) -> Arc<ElixirModuleIndex> {
+    if let Some(index) = elixir_index_cache()
+        .lock()
+        .ok()
+        .and_then(|cache| cache.get(base_dir).cloned())
+    {
+        return index;
+    }
+
+    let index = Arc::new(ElixirModuleIndex::build(base_dir));
+    if let Ok(mut cache) = elixir_index_cache().lock() {
+        cache.insert(base_dir.to_path_buf(), index.clone());
+    }
+    index
 }
@@ -1,24 +1,39 @@
-//! Semantic analyzer for Elm
+//! Semantic analyzer for Elm, resolving modules through the `source-directories` of
+//! `elm.json`
 
-use crate::core::semantic::analyzer::{
-    AnalysisResult, LanguageAnalyzer, SemanticContext, SemanticResult,
+use crate::core::semantic::{
+    analyzer::{AnalysisResult, Import, LanguageAnalyzer, SemanticContext, SemanticResult},
+    path_validator::{validate_import_path, validate_module_name},
+    query_engine::QueryEngine,
+    resolver::{ModuleResolver, ResolvedPath},
 };
-use std::path::Path;
-use tree_sitter::Parser;
+use crate::utils::error::ContextCreatorError;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+use tree_sitter::{Node, Parser};
+
+/// Directories holding build output or installed dependencies.
+const ELM_SKIPPED_DIRS: &[&str] = &["elm-stuff", "node_modules"];
+
+/// Types every Elm module sees through the default imports.
+const ELM_DEFAULT_TYPES: &[&str] = &[
+    "Int", "Float", "Bool", "String", "Char", "List", "Maybe", "Result", "Cmd", "Sub", "Never",
+    "Order", "Program",
+];
 
 #[allow(clippy::new_without_default)]
 pub struct ElmAnalyzer {
-    #[allow(dead_code)]
-    #[allow(dead_code)]
-    parser: Parser,
+    query_engine: QueryEngine,
 }
 
 impl ElmAnalyzer {
     pub fn new() -> Self {
-        let parser = Parser::new();
-        // Note: tree_sitter_elm::language() would be used here when the crate is added
-        // parser.set_language(tree_sitter_elm::language()).unwrap();
-        Self { parser }
+        let language = tree_sitter_elm::language();
+        let query_engine =
+            QueryEngine::new(language, "elm").expect("Failed to create Elm query engine");
+        Self { query_engine }
     }
 }
 
@@ -29,19 +44,422 @@ impl LanguageAnalyzer for ElmAnalyzer {
 
     fn analyze_file(
         &self,
-        _path: &Path,
-        _content: &str,
-        _context: &SemanticContext,
+        path: &Path,
+        content: &str,
+        context: &SemanticContext,
     ) -> SemanticResult<AnalysisResult> {
-        // TODO: Implement Elm analysis
-        Ok(AnalysisResult::default())
+        let mut parser = Parser::new();
+        parser
+            .set_language(tree_sitter_elm::language())
+            .map_err(|e| ContextCreatorError::ParseError(format!("Failed to set language: {e}")))?;
+
+        let tree = parser
+            .parse(content, None)
+            .ok_or_else(|| ContextCreatorError::ParseError("Failed to parse file".to_string()))?;
+        let exposed = elm_exposing_list(tree.root_node(), content);
+        let declared_types = elm_declared_types(tree.root_node(), content);
+
+        let mut result = self
+            .query_engine
+            .analyze_with_parser(&mut parser, content)?;
+
+        if let Some(exposed) = &exposed {
+            for function in &mut result.exported_functions {
+                function.is_exported = exposed.contains(&function.name);
+            }
+        }
+
+        // Default imports are shadowed by names an import exposes explicitly
+        let imports = &result.imports;
+        result.type_references.retain(|type_ref| {
+            type_ref.module.is_some()
+                || !(declared_types.contains(&type_ref.name)
+                    || (ELM_DEFAULT_TYPES.contains(&type_ref.name.as_str())
+                        && !imports
+                            .iter()
+                            .any(|import| import.items.contains(&type_ref.name))))
+        });
+
+        self.resolve_qualified_names(&mut result, path, &context.base_dir);
+
+        Ok(result)
     }
 
     fn can_handle_extension(&self, extension: &str) -> bool {
-        matches!(extension, "elm")
+        extension == "elm"
     }
 
     fn supported_extensions(&self) -> Vec<&'static str> {
         vec!["elm"]
     }
+
+    fn query_engine(&self) -> Option<&QueryEngine> {
+        Some(&self.query_engine)
+    }
+}
+
+impl ElmAnalyzer {
+    /// Expand `as` aliases in qualified calls and types to the imported module, and
+    /// point type references at the project module that declares them.
+    fn resolve_qualified_names(
+        &self,
+        result: &mut AnalysisResult,
+        path: &Path,
+        project_root: &Path,
+    ) {
+        // An aliased module is only reachable through its alias
+        let mut qualifiers: HashMap<String, String> = HashMap::new();
+        for import in &result.imports {
+            let alias = import
+                .items
+                .iter()
+                .find_map(|item| item.strip_prefix("as "))
+                .unwrap_or(&import.module);
+            qualifiers.insert(alias.to_string(), import.module.clone());
+        }
+
+        for call in &mut result.function_calls {
+            if let Some(module) = call.module.as_deref().and_then(|name| qualifiers.get(name)) {
+                call.module = Some(module.clone());
+            }
+        }
+
+        let resolver = ElmModuleResolver;
+        let mut module_types: HashMap<String, Option<(PathBuf, HashSet<String>)>> = HashMap::new();
+        let mut declarations = |module: &str| {
+            module_types
+                .entry(module.to_string())
+                .or_insert_with(|| {
+                    let resolved = resolver.resolve_import(module, path, project_root).ok()?;
+                    if resolved.is_external {
+                        return None;
+                    }
+                    let types = elm_file_declared_types(&resolved.path);
+                    Some((resolved.path, types))
+                })
+                .clone()
+        };
+
+        for type_ref in &mut result.type_references {
+            if let Some(qualifier) = type_ref.module.clone() {
+                let module = qualifiers.get(&qualifier).cloned().unwrap_or(qualifier);
+                type_ref.module = Some(module.clone());
+                match declarations(&module) {
+                    Some((file, _)) => type_ref.definition_path = Some(file),
+                    None => {
+                        type_ref.is_external = true;
+                        type_ref.external_package = Some(module);
+                    }
+                }
+                continue;
+            }
+
+            // Unqualified names come from an import exposing them by name, or else from
+            // an `exposing (..)` import whose module declares them
+            let listed = result
+                .imports
+                .iter()
+                .find(|import| import.items.contains(&type_ref.name));
+            let candidates = listed.into_iter().chain(
+                result
+                    .imports
+                    .iter()
+                    .filter(|import| import_exposes_all(import)),
+            );
+            for import in candidates {
+                if let Some((file, types)) = declarations(&import.module) {
+                    if types.contains(&type_ref.name) {
+                        type_ref.module = Some(import.module.clone());
+                        type_ref.definition_path = Some(file);
+                        break;
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn import_exposes_all(import: &Import) -> bool {
+    import.items.iter().any(|item| item == "..")
+}
+
+/// Names in the module's `exposing` list, or `None` when it exposes everything.
+fn elm_exposing_list(root: Node<'_>, content: &str) -> Option<HashSet<String>> {
+    let exposing = root
+        .child_by_field_name("moduleDeclaration")?
+        .child_by_field_name("exposing")?;
+
+    let mut names = HashSet::new();
+    let mut cursor = exposing.walk();
+    for exposed in exposing.named_children(&mut cursor) {
+        match exposed.kind() {
+            "double_dot" => return None,
+            "exposed_value" => {
+                if let Ok(name) = exposed.utf8_text(content.as_bytes()) {
+                    names.insert(name.to_string());
+                }
+            }
+            _ => {}
+        }
+    }
+    Some(names)
+}
+
+/// Custom types and type aliases declared in the module.
+fn elm_declared_types(root: Node<'_>, content: &str) -> HashSet<String> {
+    let mut types = HashSet::new();
+    let mut cursor = root.walk();
+    for child in root.named_children(&mut cursor) {
+        if !matches!(child.kind(), "type_declaration" | "type_alias_declaration") {
+            continue;
+        }
+        if let Some(name) = child
+            .child_by_field_name("name")
+            .and_then(|name| name.utf8_text(content.as_bytes()).ok())
+        {
+            types.insert(name.to_string());
+        }
+    }
+    types
+}
+
+fn elm_file_declared_types(path: &Path) -> HashSet<String> {
+    let mut parser = Parser::new();
+    if parser.set_language(tree_sitter_elm::language()).is_err() {
+        return HashSet::new();
+    }
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|content| {
+            let tree = parser.parse(&content, None)?;
+            Some(elm_declared_types(tree.root_node(), &content))
+        })
+        .unwrap_or_default()
+}
+
+pub struct ElmModuleResolver;
+
+impl ModuleResolver for ElmModuleResolver {
+    fn resolve_import(
+        &self,
+        module_path: &str,
+        from_file: &Path,
+        base_dir: &Path,
+    ) -> Result<ResolvedPath, ContextCreatorError> {
+        validate_module_name(module_path)?;
+        let projects = elm_projects(base_dir);
+        let module_file = format!("{}.elm", module_path.replace('.', "/"));
+
+        if let Some(file) = projects.find_module(&module_file, from_file) {
+            return Ok(ResolvedPath {
+                path: validate_import_path(base_dir, &file)?,
+                is_external: false,
+                confidence: 0.95,
+            });
+        }
+
+        Ok(ResolvedPath {
+            path: projects
+                .manifest_for(from_file)
+                .unwrap_or_else(|| base_dir.to_path_buf()),
+            is_external: true,
+            confidence: if self.is_external_module(module_path) {
+                0.9
+            } else {
+                0.5
+            },
+        })
+    }
+
+    fn get_file_extensions(&self) -> Vec<&'static str> {
+        vec!["elm"]
+    }
+
+    fn is_external_module(&self, module_path: &str) -> bool {
+        let root = module_path.split('.').next().unwrap_or(module_path);
+        matches!(
+            root,
+            "Basics"
+                | "List"
+                | "Maybe"
+                | "Result"
+                | "String"
+                | "Char"
+                | "Tuple"
+                | "Debug"
+                | "Platform"
+                | "Dict"
+                | "Set"
+                | "Array"
+                | "Task"
+                | "Process"
+                | "Bitwise"
+                | "Html"
+                | "Browser"
+                | "Json"
+                | "Http"
+                | "Url"
+                | "Time"
+                | "Random"
+                | "Svg"
+                | "Bytes"
+                | "File"
+                | "Parser"
+                | "Markdown"
+                | "Element"
+        )
+    }
+}
+
+/// An Elm application or package and the directories its modules live in.
+#[derive(Debug)]
+struct ElmProject {
+    root: PathBuf,
+    manifest: PathBuf,
+    source_dirs: Vec<PathBuf>,
+}
+
+/// Every `elm.json` in the repository, so a frontend split into several Elm projects
+/// sharing source directories resolves across them.
+#[derive(Debug, Default)]
+struct ElmProjects {
+    projects: Vec<ElmProject>,
+}
+
+impl ElmProjects {
+    fn build(base_dir: &Path) -> Self {
+        let walker = walkdir::WalkDir::new(base_dir)
+            .max_depth(8)
+            .sort_by_file_name()
+            .into_iter()
+            .filter_entry(|entry| {
+                entry.depth() == 0
+                    || !entry.file_name().to_str().is_some_and(|name| {
+                        name.starts_with('.') || ELM_SKIPPED_DIRS.contains(&name)
+                    })
+            });
+
+        let mut projects = Vec::new();
+        for entry in walker.flatten() {
+            if !entry.file_type().is_file() || entry.file_name() != "elm.json" {
+                continue;
+            }
+            let (Some(root), Ok(content)) =
+                (entry.path().parent(), fs::read_to_string(entry.path()))
+            else {
+                continue;
+            };
+            projects.push(ElmProject {
+                source_dirs: elm_source_dirs(&content)
+                    .into_iter()
+                    .map(|dir| {
+                        let dir = root.join(dir);
+                        dir.canonicalize().unwrap_or(dir)
+                    })
+                    .collect(),
+                root: root.to_path_buf(),
+                manifest: entry.path().to_path_buf(),
+            });
+        }
+
+        if projects.is_empty() {
+            projects.push(ElmProject {
+                root: base_dir.to_path_buf(),
+                manifest: base_dir.to_path_buf(),
+                source_dirs: vec![base_dir.join("src"), base_dir.to_path_buf()],
+            });
+        }
+
+        Self { projects }
+    }
+
+    /// Source directories of the project owning the importing file come first, then
+    /// those of the other projects in the repository.
+    fn find_module(&self, module_file: &str, from_file: &Path) -> Option<PathBuf> {
+        let owner = self.owning_project(from_file);
+        owner
+            .into_iter()
+            .chain(
+                self.projects
+                    .iter()
+                    .filter(|project| owner.map_or(true, |owner| owner.root != project.root)),
+            )
+            .flat_map(|project| &project.source_dirs)
+            .map(|dir| dir.join(module_file))
+            .find(|candidate| candidate.is_file())
+    }
+
+    /// The project whose source directories contain the file, or else the nearest
+    /// `elm.json` above it.
+    fn owning_project(&self, file: &Path) -> Option<&ElmProject> {
+        self.projects
+            .iter()
+            .find(|project| project.source_dirs.iter().any(|dir| file.starts_with(dir)))
+            .or_else(|| {
+                self.projects
+                    .iter()
+                    .filter(|project| file.starts_with(&project.root))
+                    .max_by_key(|project| project.root.components().count())
+            })
+    }
+
+    /// Unresolved modules come from dependencies declared in `elm.json`.
+    fn manifest_for(&self, from_file: &Path) -> Option<PathBuf> {
+        self.owning_project(from_file)
+            .map(|project| project.manifest.clone())
+    }
+}
+
+/// `source-directories` of an application's `elm.json`; packages always use `src`.
+/// Directories may climb out of the project (`../shared/src`), but not to the root.
+fn elm_source_dirs(elm_json: &str) -> Vec<String> {
+    let Ok(manifest) = serde_json::from_str::<serde_json::Value>(elm_json) else {
+        return vec!["src".to_string()];
+    };
+
+    match manifest
+        .get("source-directories")
+        .and_then(|dirs| dirs.as_array())
+    {
+        Some(dirs) => dirs
+            .iter()
+            .filter_map(|dir| dir.as_str())
+            .filter(|dir| is_relative_source_dir(dir))
+            .map(str::to_string)
+            .collect(),
+        None => vec!["src".to_string()],
+    }
+}
+
+fn is_relative_source_dir(dir: &str) -> bool {
+    !dir.is_empty()
+        && dir.len() <= 255
+        && !dir.contains('\0')
+        && Path::new(dir).components().all(|component| {
+            matches!(
+                component,
+                Component::Normal(_) | Component::CurDir | Component::ParentDir
+            )
+        })
+}
+
+fn elm_project_cache() -> &'static Mutex<HashMap<PathBuf, Arc<ElmProjects>>> {
+    static CACHE: OnceLock<Mutex<HashMap<PathBuf, Arc<ElmProjects>>>> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn elm_projects(base_dir: &Path) -> Arc<ElmProjects> {
+    if let Some(projects) = elm_project_cache()
+        .lock()
+        .ok()
+        .and_then(|cache| cache.get(base_dir).cloned())
+    {
+        return projects;
+    }
+
+    let projects = Arc::new(ElmProjects::build(base_dir));
+    if let Ok(mut cache) = elm_project_cache().lock() {
+        cache.insert(base_dir.to_path_buf(), projects.clone());
+    }
+    projects
 }
@@ -11,9 +11,13 @@ use crate::core::semantic::{
 use crate::utils::error::ContextCreatorError;
 use std::collections::{HashMap, HashSet};
 use std::fs;
-use std::path::{Path, PathBuf};
+use std::path::{Component, Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
 use tree_sitter::{Parser, Query, QueryCursor};
 
+/// Directories never holding modules of the repository itself.
+const GO_SKIPPED_DIRS: &[&str] = &["vendor", "testdata", "node_modules"];
+
 #[allow(clippy::new_without_default)]
 pub struct GoAnalyzer {
     query_engine: QueryEngine,
@@ -50,7 +54,7 @@ impl LanguageAnalyzer for GoAnalyzer {
 
         self.merge_duplicate_imports(&mut result);
         self.correlate_qualified_symbols_with_imports(&mut result);
-        self.dedupe_type_references(&mut result);
+        result.dedupe_type_references(|_| true);
 
         self.query_engine.resolve_type_definitions(
             &mut result.type_references,
@@ -69,6 +73,10 @@ impl LanguageAnalyzer for GoAnalyzer {
     fn supported_extensions(&self) -> Vec<&'static str> {
         vec!["go"]
     }
+
+    fn query_engine(&self) -> Option<&QueryEngine> {
+        Some(&self.query_engine)
+    }
 }
 
 impl GoAnalyzer {
@@ -113,26 +121,6 @@ impl GoAnalyzer {
         }
     }
 
-    fn dedupe_type_references(&self, result: &mut AnalysisResult) {
-        let mut deduped: Vec<TypeReference> = Vec::new();
-        let mut index_by_name_line: HashMap<(String, usize), usize> = HashMap::new();
-
-        for type_ref in result.type_references.drain(..) {
-            let key = (type_ref.name.clone(), type_ref.line);
-            if let Some(existing_index) = index_by_name_line.get(&key).copied() {
-                let existing = &mut deduped[existing_index];
-                if existing.module.is_none() && type_ref.module.is_some() {
-                    *existing = type_ref;
-                }
-            } else {
-                index_by_name_line.insert(key, deduped.len());
-                deduped.push(type_ref);
-            }
-        }
-
-        result.type_references = deduped;
-    }
-
     fn resolve_go_type_definitions(
         &self,
         type_refs: &mut [TypeReference],
@@ -196,6 +184,16 @@ impl ModuleResolver for GoModuleResolver {
             }
         }
 
+        // Modules of the repository, `replace` directives with local paths, `go.work`
+        // workspaces, and `vendor/` directories
+        if let Some(package_dir) = go_modules(base_dir).package_dir(&module_path, from_file) {
+            if let Some(path) = find_go_package_file(&package_dir) {
+                if let Ok(resolved) = resolved_go_path(path, base_dir, false, 0.95) {
+                    return Ok(resolved);
+                }
+            }
+        }
+
         if let Some(go_module) = read_go_module_path(base_dir) {
             if module_path == go_module {
                 if let Some(path) = find_go_package_file(base_dir) {
@@ -277,6 +275,275 @@ fn read_go_module_path(base_dir: &Path) -> Option<String> {
     })
 }
 
+/// A `replace` directive whose replacement is a local directory.
+#[derive(Debug)]
+struct GoReplace {
+    module: String,
+    dir: PathBuf,
+}
+
+/// A module of the repository, found by its `go.mod`.
+#[derive(Debug)]
+struct GoModule {
+    path: String,
+    dir: PathBuf,
+    replaces: Vec<GoReplace>,
+    /// Packages listed in `vendor/modules.txt`
+    vendored: HashSet<String>,
+}
+
+/// A `go.work` file and the directories of the modules it uses.
+#[derive(Debug)]
+struct GoWorkspace {
+    dir: PathBuf,
+    uses: Vec<PathBuf>,
+    replaces: Vec<GoReplace>,
+}
+
+/// Every Go module and workspace of the repository.
+#[derive(Debug, Default)]
+struct GoModules {
+    modules: Vec<GoModule>,
+    workspaces: Vec<GoWorkspace>,
+}
+
+impl GoModules {
+    fn build(base_dir: &Path) -> Self {
+        let walker = walkdir::WalkDir::new(base_dir)
+            .max_depth(8)
+            .sort_by_file_name()
+            .into_iter()
+            .filter_entry(|entry| {
+                entry.depth() == 0
+                    || !entry.file_name().to_str().is_some_and(|name| {
+                        name.starts_with('.') || GO_SKIPPED_DIRS.contains(&name)
+                    })
+            });
+
+        let mut index = Self::default();
+        for entry in walker.flatten() {
+            let is_go_mod = entry.file_name() == "go.mod";
+            if !entry.file_type().is_file() || !(is_go_mod || entry.file_name() == "go.work") {
+                continue;
+            }
+            let path = entry.path();
+            let (Some(dir), Ok(content)) = (path.parent(), fs::read_to_string(path)) else {
+                continue;
+            };
+            let dir = dir.canonicalize().unwrap_or_else(|_| dir.to_path_buf());
+            let directives = go_directives(&content);
+
+            if is_go_mod {
+                let Some(module_path) = directives
+                    .iter()
+                    .find(|(verb, _)| verb == "module")
+                    .map(|(_, args)| args.trim_matches('"').to_string())
+                else {
+                    continue;
+                };
+                index.modules.push(GoModule {
+                    path: module_path,
+                    replaces: go_local_replaces(&directives, &dir),
+                    vendored: read_vendored_packages(&dir.join("vendor/modules.txt")),
+                    dir,
+                });
+            } else {
+                let uses = directives
+                    .iter()
+                    .filter(|(verb, _)| verb == "use")
+                    .map(|(_, args)| normalize_go_dir(&dir, args.trim_matches('"')))
+                    .collect();
+                index.workspaces.push(GoWorkspace {
+                    replaces: go_local_replaces(&directives, &dir),
+                    uses,
+                    dir,
+                });
+            }
+        }
+
+        index
+    }
+
+    /// The directory of the package `import_path` as seen from `from_file`.
+    fn package_dir(&self, import_path: &str, from_file: &Path) -> Option<PathBuf> {
+        let from_file = from_file
+            .canonicalize()
+            .unwrap_or_else(|_| from_file.to_path_buf());
+        let owner = self
+            .modules
+            .iter()
+            .filter(|module| from_file.starts_with(&module.dir))
+            .max_by_key(|module| module.dir.components().count());
+        let workspace = self
+            .workspaces
+            .iter()
+            .filter(|workspace| from_file.starts_with(&workspace.dir))
+            .max_by_key(|workspace| workspace.dir.components().count());
+
+        // `replace` in go.work overrides those of the modules
+        if let Some(dir) =
+            workspace.and_then(|workspace| replaced_dir(&workspace.replaces, import_path))
+        {
+            return Some(dir);
+        }
+
+        // The main modules: those used by the workspace, or else the owning module
+        let main_modules: Vec<&GoModule> = match workspace {
+            Some(workspace) => self
+                .modules
+                .iter()
+                .filter(|module| workspace.uses.contains(&module.dir))
+                .collect(),
+            None => owner.into_iter().collect(),
+        };
+        if let Some(dir) = main_modules
+            .iter()
+            .filter_map(|module| {
+                let rest = go_module_subpath(&module.path, import_path)?;
+                Some((module.path.len(), join_go_import_path(&module.dir, rest)))
+            })
+            .max_by_key(|(length, _)| *length)
+            .map(|(_, dir)| dir)
+        {
+            return Some(dir);
+        }
+
+        let owner = owner?;
+        if let Some(dir) = replaced_dir(&owner.replaces, import_path) {
+            return Some(dir);
+        }
+        if owner.vendored.contains(import_path) {
+            return Some(join_go_import_path(&owner.dir.join("vendor"), import_path));
+        }
+        None
+    }
+}
+
+/// The package directory inside the longest replaced module that contains `import_path`.
+fn replaced_dir(replaces: &[GoReplace], import_path: &str) -> Option<PathBuf> {
+    replaces
+        .iter()
+        .filter_map(|replace| {
+            let rest = go_module_subpath(&replace.module, import_path)?;
+            Some((
+                replace.module.len(),
+                join_go_import_path(&replace.dir, rest),
+            ))
+        })
+        .max_by_key(|(length, _)| *length)
+        .map(|(_, dir)| dir)
+}
+
+/// The package path inside a module: `""` for the module itself, `pkg/x` for `module/pkg/x`.
+fn go_module_subpath<'a>(module: &str, import_path: &'a str) -> Option<&'a str> {
+    if import_path == module {
+        return Some("");
+    }
+    import_path.strip_prefix(module)?.strip_prefix('/')
+}
+
+/// The directives of a `go.mod` or `go.work` file as `(verb, arguments)`, with the
+/// entries of `verb ( ... )` blocks listed one by one.
+fn go_directives(content: &str) -> Vec<(String, String)> {
+    let mut directives = Vec::new();
+    let mut block: Option<String> = None;
+    for line in content.lines() {
+        let line = line.split("//").next().unwrap_or_default().trim();
+        if line.is_empty() {
+            continue;
+        }
+        if let Some(verb) = &block {
+            if line == ")" {
+                block = None;
+            } else {
+                directives.push((verb.clone(), line.to_string()));
+            }
+            continue;
+        }
+
+        let (verb, args) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+        let args = args.trim();
+        if args == "(" {
+            block = Some(verb.to_string());
+        } else {
+            directives.push((verb.to_string(), args.to_string()));
+        }
+    }
+    directives
+}
+
+/// `replace example.com/lib [v1.2.0] => ../lib` directives pointing at local directories.
+fn go_local_replaces(directives: &[(String, String)], dir: &Path) -> Vec<GoReplace> {
+    directives
+        .iter()
+        .filter(|(verb, _)| verb == "replace")
+        .filter_map(|(_, args)| {
+            let (old, new) = args.split_once("=>")?;
+            let module = old.split_whitespace().next()?.trim_matches('"');
+            let target = new.split_whitespace().next()?.trim_matches('"');
+            let is_local = target.starts_with("./")
+                || target.starts_with("../")
+                || target.starts_with('/')
+                || matches!(target, "." | "..");
+            is_local.then(|| GoReplace {
+                module: module.to_string(),
+                dir: normalize_go_dir(dir, target),
+            })
+        })
+        .collect()
+}
+
+/// Package paths listed in `vendor/modules.txt`; the `#` lines name modules.
+fn read_vendored_packages(modules_txt: &Path) -> HashSet<String> {
+    fs::read_to_string(modules_txt)
+        .map(|content| {
+            content
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Join a directory from `go.mod` or `go.work` onto its file's directory, resolving `..`.
+fn normalize_go_dir(dir: &Path, relative: &str) -> PathBuf {
+    let mut path = dir.to_path_buf();
+    for component in Path::new(relative).components() {
+        match component {
+            Component::ParentDir => {
+                path.pop();
+            }
+            Component::Normal(segment) => path.push(segment),
+            Component::RootDir => path = PathBuf::from("/"),
+            _ => {}
+        }
+    }
+    path.canonicalize().unwrap_or(path)
+}
+
+fn go_modules_cache() -> &'static Mutex<HashMap<PathBuf, Arc<GoModules>>> {
+    static CACHE: OnceLock<Mutex<HashMap<PathBuf, Arc<GoModules>>>> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn go_modules(base_dir: &Path) -> Arc<GoModules> {
+    if let Some(modules) = go_modules_cache()
+        .lock()
+        .ok()
+        .and_then(|cache| cache.get(base_dir).cloned())
+    {
+        return modules;
+    }
+
+    let modules = Arc::new(GoModules::build(base_dir));
+    if let Ok(mut cache) = go_modules_cache().lock() {
+        cache.insert(base_dir.to_path_buf(), modules.clone());
+    }
+    modules
+}
+
 fn join_go_import_path(base: &Path, module_path: &str) -> PathBuf {
     module_path
         .split('/')
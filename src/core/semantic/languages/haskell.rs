@@ -0,0 +1,536 @@
+//! Semantic analyzer for Haskell, resolving modules through the `hs-source-dirs` of
+//! Cabal packages and the `source-dirs` of hpack `package.yaml` files
+
+use crate::core::semantic::{
+    analyzer::{AnalysisResult, Import, LanguageAnalyzer, SemanticContext, SemanticResult},
+    path_validator::{validate_import_path, validate_module_name},
+    query_engine::QueryEngine,
+    resolver::{ModuleResolver, ResolvedPath},
+};
+use crate::utils::error::ContextCreatorError;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+use tree_sitter::{Node, Parser};
+
+/// Directories holding build output or installed dependencies.
+const HASKELL_SKIPPED_DIRS: &[&str] = &["dist", "dist-newstyle", "node_modules"];
+
+/// Source directories tried when a package declares none.
+const DEFAULT_SOURCE_DIRS: &[&str] = &["src", "app", "lib", "."];
+
+#[allow(clippy::new_without_default)]
+pub struct HaskellAnalyzer {
+    query_engine: QueryEngine,
+}
+
+impl HaskellAnalyzer {
+    pub fn new() -> Self {
+        let language = tree_sitter_haskell::language();
+        let query_engine =
+            QueryEngine::new(language, "haskell").expect("Failed to create Haskell query engine");
+        Self { query_engine }
+    }
+}
+
+impl LanguageAnalyzer for HaskellAnalyzer {
+    fn language_name(&self) -> &'static str {
+        "Haskell"
+    }
+
+    fn analyze_file(
+        &self,
+        path: &Path,
+        content: &str,
+        context: &SemanticContext,
+    ) -> SemanticResult<AnalysisResult> {
+        let mut parser = Parser::new();
+        parser
+            .set_language(tree_sitter_haskell::language())
+            .map_err(|e| ContextCreatorError::ParseError(format!("Failed to set language: {e}")))?;
+
+        let tree = parser
+            .parse(content, None)
+            .ok_or_else(|| ContextCreatorError::ParseError("Failed to parse file".to_string()))?;
+        let exports = haskell_export_list(tree.root_node(), content);
+        let declared_types = haskell_declared_types(tree.root_node(), content);
+
+        let mut result = self
+            .query_engine
+            .analyze_with_parser(&mut parser, content)?;
+
+        // A signature and its equations define the same function
+        let mut seen = HashSet::new();
+        result
+            .exported_functions
+            .retain(|function| seen.insert(function.name.clone()));
+        if let Some(exports) = &exports {
+            for function in &mut result.exported_functions {
+                function.is_exported = exports.contains(&function.name);
+            }
+        }
+
+        result.type_references.retain(|type_ref| {
+            type_ref.module.is_some() || !declared_types.contains(&type_ref.name)
+        });
+
+        self.resolve_qualified_names(&mut result, path, &context.base_dir);
+
+        Ok(result)
+    }
+
+    fn can_handle_extension(&self, extension: &str) -> bool {
+        extension == "hs"
+    }
+
+    fn supported_extensions(&self) -> Vec<&'static str> {
+        vec!["hs"]
+    }
+
+    fn query_engine(&self) -> Option<&QueryEngine> {
+        Some(&self.query_engine)
+    }
+}
+
+impl HaskellAnalyzer {
+    /// Expand `as` aliases in qualified calls and types to the imported module, and
+    /// point type references at the project module that declares them.
+    fn resolve_qualified_names(
+        &self,
+        result: &mut AnalysisResult,
+        path: &Path,
+        project_root: &Path,
+    ) {
+        let mut qualifiers: HashMap<String, String> = HashMap::new();
+        for import in &result.imports {
+            qualifiers
+                .entry(import.module.clone())
+                .or_insert_with(|| import.module.clone());
+            for alias in import
+                .items
+                .iter()
+                .filter_map(|item| item.strip_prefix("as "))
+            {
+                qualifiers
+                    .entry(alias.to_string())
+                    .or_insert_with(|| import.module.clone());
+            }
+        }
+
+        for call in &mut result.function_calls {
+            if let Some(module) = call.module.as_deref().and_then(|name| qualifiers.get(name)) {
+                call.module = Some(module.clone());
+            }
+        }
+
+        let resolver = HaskellModuleResolver;
+        let mut module_types: HashMap<String, Option<(PathBuf, HashSet<String>)>> = HashMap::new();
+        let mut declarations = |module: &str| {
+            module_types
+                .entry(module.to_string())
+                .or_insert_with(|| {
+                    let resolved = resolver.resolve_import(module, path, project_root).ok()?;
+                    if resolved.is_external {
+                        return None;
+                    }
+                    let types = haskell_file_declared_types(&resolved.path);
+                    Some((resolved.path, types))
+                })
+                .clone()
+        };
+
+        for type_ref in &mut result.type_references {
+            if let Some(qualifier) = type_ref.module.clone() {
+                let module = qualifiers.get(&qualifier).cloned().unwrap_or(qualifier);
+                type_ref.module = Some(module.clone());
+                match declarations(&module) {
+                    Some((file, _)) => type_ref.definition_path = Some(file),
+                    None if resolver.is_external_module(&module) => {
+                        type_ref.is_external = true;
+                        type_ref.external_package = Some(module);
+                    }
+                    None => {}
+                }
+                continue;
+            }
+
+            // Unqualified names come from an import listing them, or else from any
+            // open import whose module declares them
+            let listed = result
+                .imports
+                .iter()
+                .find(|import| import.items.contains(&type_ref.name));
+            let candidates = listed.into_iter().chain(
+                result
+                    .imports
+                    .iter()
+                    .filter(|import| import_is_open(import)),
+            );
+            for import in candidates {
+                if let Some((file, types)) = declarations(&import.module) {
+                    if types.contains(&type_ref.name) {
+                        type_ref.module = Some(import.module.clone());
+                        type_ref.definition_path = Some(file);
+                        break;
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Whether an import brings every exported name into scope, as plain and `hiding`
+/// imports do.
+fn import_is_open(import: &Import) -> bool {
+    import.items.iter().all(|item| item.starts_with("as "))
+}
+
+/// Names in the module's export list, or `None` when it exports everything.
+fn haskell_export_list(root: Node<'_>, content: &str) -> Option<HashSet<String>> {
+    let mut cursor = root.walk();
+    let exports = root
+        .named_children(&mut cursor)
+        .find(|child| child.kind() == "exports")?;
+
+    let mut names = HashSet::new();
+    let mut export_cursor = exports.walk();
+    for export in exports.named_children(&mut export_cursor) {
+        if export.kind() != "export" {
+            continue;
+        }
+        if let Some(name) = export
+            .named_child(0)
+            .filter(|name| name.kind() == "variable")
+            .and_then(|name| name.utf8_text(content.as_bytes()).ok())
+        {
+            names.insert(name.to_string());
+        }
+    }
+    Some(names)
+}
+
+/// Data types, newtypes, type synonyms, and classes declared at the top level.
+fn haskell_declared_types(root: Node<'_>, content: &str) -> HashSet<String> {
+    let mut types = HashSet::new();
+    let mut cursor = root.walk();
+    for child in root.named_children(&mut cursor) {
+        let name = match child.kind() {
+            "adt" | "newtype" | "type_alias" | "type_family" => child.child_by_field_name("name"),
+            "class" => child
+                .named_child(0)
+                .and_then(|head| head.child_by_field_name("class"))
+                .and_then(|class| class.named_child(0)),
+            _ => None,
+        };
+        if let Some(name) = name.and_then(|name| name.utf8_text(content.as_bytes()).ok()) {
+            types.insert(name.to_string());
+        }
+    }
+    types
+}
+
+fn haskell_file_declared_types(path: &Path) -> HashSet<String> {
+    let mut parser = Parser::new();
+    if parser
+        .set_language(tree_sitter_haskell::language())
+        .is_err()
+    {
+        return HashSet::new();
+    }
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|content| {
+            let tree = parser.parse(&content, None)?;
+            Some(haskell_declared_types(tree.root_node(), &content))
+        })
+        .unwrap_or_default()
+}
+
+pub struct HaskellModuleResolver;
+
+impl ModuleResolver for HaskellModuleResolver {
+    fn resolve_import(
+        &self,
+        module_path: &str,
+        from_file: &Path,
+        base_dir: &Path,
+    ) -> Result<ResolvedPath, ContextCreatorError> {
+        validate_module_name(module_path)?;
+        let packages = haskell_packages(base_dir);
+        let module_file = format!("{}.hs", module_path.replace('.', "/"));
+
+        if let Some(file) = packages.find_module(&module_file, from_file) {
+            return Ok(ResolvedPath {
+                path: validate_import_path(base_dir, &file)?,
+                is_external: false,
+                confidence: 0.95,
+            });
+        }
+
+        Ok(ResolvedPath {
+            path: packages
+                .manifest_for(from_file)
+                .unwrap_or_else(|| base_dir.to_path_buf()),
+            is_external: true,
+            confidence: if self.is_external_module(module_path) {
+                0.9
+            } else {
+                0.5
+            },
+        })
+    }
+
+    fn get_file_extensions(&self) -> Vec<&'static str> {
+        vec!["hs"]
+    }
+
+    fn is_external_module(&self, module_path: &str) -> bool {
+        let root = module_path.split('.').next().unwrap_or(module_path);
+        matches!(
+            root,
+            "Prelude"
+                | "Data"
+                | "Control"
+                | "System"
+                | "GHC"
+                | "Text"
+                | "Foreign"
+                | "Numeric"
+                | "Debug"
+                | "Unsafe"
+                | "Network"
+                | "Test"
+                | "Options"
+                | "Servant"
+                | "Yesod"
+                | "Database"
+                | "Lens"
+        )
+    }
+}
+
+/// A Cabal or hpack package and the directories its components compile from.
+#[derive(Debug)]
+struct HaskellPackage {
+    root: PathBuf,
+    manifest: PathBuf,
+    source_dirs: Vec<PathBuf>,
+}
+
+/// Every package in the project, so modules of sibling packages in a `cabal.project`
+/// or `stack.yaml` workspace resolve too.
+#[derive(Debug, Default)]
+struct HaskellPackages {
+    packages: Vec<HaskellPackage>,
+}
+
+impl HaskellPackages {
+    fn build(base_dir: &Path) -> Self {
+        let mut manifests: HashMap<PathBuf, (PathBuf, Vec<String>)> = HashMap::new();
+
+        let walker = walkdir::WalkDir::new(base_dir)
+            .max_depth(8)
+            .sort_by_file_name()
+            .into_iter()
+            .filter_entry(|entry| {
+                entry.depth() == 0
+                    || !entry.file_name().to_str().is_some_and(|name| {
+                        name.starts_with('.') || HASKELL_SKIPPED_DIRS.contains(&name)
+                    })
+            });
+
+        for entry in walker.flatten() {
+            let path = entry.path();
+            if !entry.file_type().is_file() {
+                continue;
+            }
+            let source_dirs = match path.extension().and_then(|ext| ext.to_str()) {
+                Some("cabal") => fs::read_to_string(path)
+                    .map(|cabal| cabal_source_dirs(&cabal))
+                    .unwrap_or_default(),
+                _ if entry.file_name() == "package.yaml" => fs::read_to_string(path)
+                    .map(|package| hpack_source_dirs(&package))
+                    .unwrap_or_default(),
+                _ => continue,
+            };
+            let Some(root) = path.parent() else {
+                continue;
+            };
+
+            // hpack generates the .cabal file, so both describe the same package
+            let package = manifests
+                .entry(root.to_path_buf())
+                .or_insert_with(|| (path.to_path_buf(), Vec::new()));
+            for dir in source_dirs {
+                if !package.1.contains(&dir) {
+                    package.1.push(dir);
+                }
+            }
+        }
+
+        if manifests.is_empty() {
+            manifests.insert(base_dir.to_path_buf(), (base_dir.to_path_buf(), Vec::new()));
+        }
+
+        let mut packages: Vec<HaskellPackage> = manifests
+            .into_iter()
+            .map(|(root, (manifest, dirs))| {
+                let dirs: Vec<&str> = if dirs.is_empty() {
+                    DEFAULT_SOURCE_DIRS.to_vec()
+                } else {
+                    dirs.iter().map(String::as_str).collect()
+                };
+                HaskellPackage {
+                    source_dirs: dirs
+                        .into_iter()
+                        .filter(|dir| is_relative_source_dir(dir))
+                        .map(|dir| root.join(dir))
+                        .collect(),
+                    root,
+                    manifest,
+                }
+            })
+            .collect();
+        packages.sort_by(|left, right| left.root.cmp(&right.root));
+
+        Self { packages }
+    }
+
+    /// Source directories of the package owning the importing file come first, then
+    /// those of the other packages in the project.
+    fn find_module(&self, module_file: &str, from_file: &Path) -> Option<PathBuf> {
+        let owner = self.owning_package(from_file);
+        owner
+            .into_iter()
+            .chain(
+                self.packages
+                    .iter()
+                    .filter(|package| owner.map_or(true, |owner| owner.root != package.root)),
+            )
+            .flat_map(|package| &package.source_dirs)
+            .map(|dir| dir.join(module_file))
+            .find(|candidate| candidate.is_file())
+    }
+
+    fn owning_package(&self, file: &Path) -> Option<&HaskellPackage> {
+        self.packages
+            .iter()
+            .filter(|package| file.starts_with(&package.root))
+            .max_by_key(|package| package.root.components().count())
+    }
+
+    /// Unresolved modules come from dependencies declared in the package manifest.
+    fn manifest_for(&self, from_file: &Path) -> Option<PathBuf> {
+        self.owning_package(from_file)
+            .map(|package| package.manifest.clone())
+    }
+}
+
+/// `hs-source-dirs` of every component in a `.cabal` file, written inline or on
+/// indented continuation lines.
+fn cabal_source_dirs(cabal: &str) -> Vec<String> {
+    let mut dirs = Vec::new();
+    let mut lines = cabal.lines().peekable();
+
+    while let Some(line) = lines.next() {
+        let trimmed = line.trim_start();
+        let Some((field, value)) = trimmed.split_once(':') else {
+            continue;
+        };
+        if !field.trim().eq_ignore_ascii_case("hs-source-dirs") {
+            continue;
+        }
+
+        let indent = line.len() - trimmed.len();
+        let mut values = vec![value.to_string()];
+        while let Some(next) = lines.peek() {
+            let next_trimmed = next.trim_start();
+            if next_trimmed.is_empty() || next.len() - next_trimmed.len() <= indent {
+                break;
+            }
+            values.push(next_trimmed.to_string());
+            lines.next();
+        }
+
+        for value in values {
+            dirs.extend(
+                value
+                    .split(|c: char| c == ',' || c.is_whitespace())
+                    .map(|dir| dir.trim_matches('"'))
+                    .filter(|dir| !dir.is_empty() && !dir.starts_with("--"))
+                    .map(str::to_string),
+            );
+        }
+    }
+
+    dirs
+}
+
+/// `source-dirs` at any level of an hpack `package.yaml`, as a scalar, a flow
+/// sequence, or a block sequence.
+fn hpack_source_dirs(package: &str) -> Vec<String> {
+    let mut dirs = Vec::new();
+    let mut lines = package.lines().peekable();
+
+    while let Some(line) = lines.next() {
+        let trimmed = line.trim_start();
+        let Some(value) = trimmed.strip_prefix("source-dirs:") else {
+            continue;
+        };
+        let value = value.split('#').next().unwrap_or_default().trim();
+        let unquote = |dir: &str| dir.trim().trim_matches(['"', '\'']).to_string();
+
+        if let Some(list) = value.strip_prefix('[') {
+            dirs.extend(
+                list.trim_end_matches(']')
+                    .split(',')
+                    .map(unquote)
+                    .filter(|dir| !dir.is_empty()),
+            );
+        } else if !value.is_empty() {
+            dirs.push(unquote(value));
+        } else {
+            while let Some(item) = lines
+                .peek()
+                .and_then(|next| next.trim_start().strip_prefix("- "))
+            {
+                dirs.push(unquote(item));
+                lines.next();
+            }
+        }
+    }
+
+    dirs
+}
+
+/// Source directories must stay inside the package.
+fn is_relative_source_dir(dir: &str) -> bool {
+    !dir.is_empty()
+        && dir.len() <= 255
+        && !dir.contains('\0')
+        && Path::new(dir)
+            .components()
+            .all(|component| matches!(component, Component::Normal(_) | Component::CurDir))
+}
+
+fn haskell_package_cache() -> &'static Mutex<HashMap<PathBuf, Arc<HaskellPackages>>> {
+    static CACHE: OnceLock<Mutex<HashMap<PathBuf, Arc<HaskellPackages>>>> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn haskell_packages(base_dir: &Path) -> Arc<HaskellPackages> {
+    if let Some(packages) = haskell_package_cache()
+        .lock()
+        .ok()
+        .and_then(|cache| cache.get(base_dir).cloned())
+    {
+        return packages;
+    }
+
+    let packages = Arc::new(HaskellPackages::build(base_dir));
+    if let Ok(mut cache) = haskell_package_cache().lock() {
+        cache.insert(base_dir.to_path_buf(), packages.clone());
+    }
+    packages
+}
@@ -0,0 +1,747 @@
+//! Semantic analyzer for Terraform, following local `module` sources and the variables,
+//! locals, resources, data sources, and module outputs a configuration references
+//!
+//! A Terraform module is a directory: every `.tf` file in it shares one namespace, so a
+//! reference such as `var.region` or `aws_iam_role.app.arn` points at whichever sibling file
+//! declares it. No HCL grammar is available for the tree-sitter version used here, so files
+//! are read with a small lexer that understands comments, strings with interpolations, and
+//! heredocs well enough to find blocks, attributes, and references.
+
+use crate::core::semantic::{
+    analyzer::{AnalysisResult, Import, LanguageAnalyzer, SemanticContext, SemanticResult},
+    path_validator::validate_import_path,
+    resolver::{ModuleResolver, ResolvedPath},
+};
+use crate::utils::error::ContextCreatorError;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+
+/// Roots of references that are not declared in the module: `each.value`, `path.module`.
+const HCL_BUILTIN_ROOTS: &[&str] = &["each", "count", "path", "self", "terraform"];
+
+/// Built-in functions whose first argument is a path to a file of the module.
+const HCL_FILE_FUNCTIONS: &[&str] = &[
+    "file",
+    "filebase64",
+    "filemd5",
+    "filesha1",
+    "filesha256",
+    "filesha512",
+    "filebase64sha256",
+    "filebase64sha512",
+    "templatefile",
+];
+
+#[allow(clippy::new_without_default)]
+pub struct HclAnalyzer;
+
+impl HclAnalyzer {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl LanguageAnalyzer for HclAnalyzer {
+    fn language_name(&self) -> &'static str {
+        "Terraform"
+    }
+
+    fn analyze_file(
+        &self,
+        path: &Path,
+        content: &str,
+        context: &SemanticContext,
+    ) -> SemanticResult<AnalysisResult> {
+        let source = HclSource::scan(&hcl_tokens(content));
+        let module_dir = path.parent().unwrap_or(&context.base_dir);
+        let directory = hcl_directory(module_dir, &context.base_dir);
+        let current_file = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+
+        let mut imports = HclImports::new(&current_file);
+
+        for (name, source_path, line) in &source.modules {
+            let Some(source_path) = source_path else {
+                continue;
+            };
+            if !is_local_source(source_path) {
+                imports.add(source_path.clone(), format!("module.{name}"), *line, false);
+                continue;
+            }
+            for file in module_files(&module_dir.join(source_path), &context.base_dir) {
+                imports.add(
+                    format!("{}/{file}", source_path.trim_end_matches('/')),
+                    format!("module.{name}"),
+                    *line,
+                    true,
+                );
+            }
+        }
+
+        for (parts, line) in &source.references {
+            let symbol = parts.join(".");
+            match parts.as_slice() {
+                [root, name, ..] if root == "var" => {
+                    if let Some(file) = directory.variables.get(name) {
+                        imports.add_sibling(file, symbol.clone(), *line);
+                    }
+                    for file in directory.assignments.get(name).into_iter().flatten() {
+                        imports.add_sibling(file, symbol.clone(), *line);
+                    }
+                }
+                [root, name, ..] if root == "local" => {
+                    if let Some(file) = directory.locals.get(name) {
+                        imports.add_sibling(file, symbol, *line);
+                    }
+                }
+                [root, kind, name, ..] if root == "data" => {
+                    if let Some(file) = directory.data.get(&(kind.clone(), name.clone())) {
+                        imports.add_sibling(file, symbol, *line);
+                    }
+                }
+                [root, name, rest @ ..] if root == "module" => {
+                    let Some((file, module_source)) = directory.modules.get(name) else {
+                        continue;
+                    };
+                    imports.add_sibling(file, symbol.clone(), *line);
+
+                    // `module.vpc.vpc_id` is declared by an `output` of the module
+                    let output_file = module_source
+                        .as_ref()
+                        .filter(|module_source| is_local_source(module_source))
+                        .zip(rest.first())
+                        .and_then(|(module_source, output)| {
+                            let module =
+                                hcl_directory(&module_dir.join(module_source), &context.base_dir);
+                            let file = module.outputs.get(output)?.file_name()?.to_str()?;
+                            Some(format!("{}/{file}", module_source.trim_end_matches('/')))
+                        });
+                    if let Some(output_file) = output_file {
+                        imports.add(output_file, symbol, *line, true);
+                    }
+                }
+                [kind, name, ..] if !HCL_BUILTIN_ROOTS.contains(&kind.as_str()) => {
+                    if let Some(file) = directory.resources.get(&(kind.clone(), name.clone())) {
+                        imports.add_sibling(file, symbol, *line);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        // A variable file links the assignments to the variables they set
+        for (name, line) in &source.attributes {
+            if path
+                .extension()
+                .is_some_and(|extension| extension == "tfvars")
+            {
+                if let Some(file) = directory.variables.get(name) {
+                    imports.add_sibling(file, format!("var.{name}"), *line);
+                }
+            }
+        }
+
+        for (file, line) in &source.files {
+            imports.add(format!("./{file}"), "file".to_string(), *line, true);
+        }
+
+        Ok(AnalysisResult {
+            imports: imports.into_imports(),
+            ..AnalysisResult::default()
+        })
+    }
+
+    fn can_handle_extension(&self, extension: &str) -> bool {
+        matches!(extension, "tf" | "tfvars")
+    }
+
+    fn supported_extensions(&self) -> Vec<&'static str> {
+        vec!["tf", "tfvars"]
+    }
+}
+
+/// Imports of a Terraform file by path, with the names each one is used for.
+struct HclImports<'a> {
+    current_file: &'a Path,
+    imports: BTreeMap<String, (Vec<String>, usize, bool)>,
+}
+
+impl<'a> HclImports<'a> {
+    fn new(current_file: &'a Path) -> Self {
+        Self {
+            current_file,
+            imports: BTreeMap::new(),
+        }
+    }
+
+    fn add(&mut self, module: String, item: String, line: usize, is_relative: bool) {
+        let (items, _, _) = self
+            .imports
+            .entry(module)
+            .or_insert_with(|| (Vec::new(), line, is_relative));
+        if !items.contains(&item) {
+            items.push(item);
+        }
+    }
+
+    /// Import a file of the same module directory, unless it is the file itself.
+    fn add_sibling(&mut self, file: &Path, item: String, line: usize) {
+        if file == self.current_file {
+            return;
+        }
+        if let Some(name) = file.file_name().and_then(|name| name.to_str()) {
+            self.add(format!("./{name}"), item, line, true);
+        }
+    }
+
+    fn into_imports(self) -> Vec<Import> {
+        self.imports
+            .into_iter()
+            .map(|(module, (items, line, is_relative))| Import {
+                module,
+                items,
+                is_relative,
+                line,
+            })
+            .collect()
+    }
+}
+
+pub struct HclModuleResolver;
+
+impl ModuleResolver for HclModuleResolver {
+    fn resolve_import(
+        &self,
+        module_path: &str,
+        from_file: &Path,
+        base_dir: &Path,
+    ) -> Result<ResolvedPath, ContextCreatorError> {
+        if self.is_external_module(module_path) {
+            return Ok(ResolvedPath {
+                path: base_dir.to_path_buf(),
+                is_external: true,
+                confidence: 0.9,
+            });
+        }
+
+        validate_source_path(module_path)?;
+        let candidate = from_file.parent().unwrap_or(base_dir).join(module_path);
+        if candidate.is_file() {
+            return Ok(ResolvedPath {
+                path: validate_import_path(base_dir, &candidate)?,
+                is_external: false,
+                confidence: 0.95,
+            });
+        }
+
+        Ok(ResolvedPath {
+            path: base_dir.to_path_buf(),
+            is_external: true,
+            confidence: 0.3,
+        })
+    }
+
+    fn get_file_extensions(&self) -> Vec<&'static str> {
+        vec!["tf", "tfvars"]
+    }
+
+    fn is_external_module(&self, module_path: &str) -> bool {
+        // Registry addresses, Git and HTTP URLs, and archives
+        !is_local_source(module_path)
+    }
+}
+
+/// Terraform only treats sources starting with `./` or `../` as local paths.
+fn is_local_source(source: &str) -> bool {
+    source.starts_with("./") || source.starts_with("../")
+}
+
+/// Module sources may climb with `../`; containment is checked after resolution.
+fn validate_source_path(source: &str) -> Result<(), ContextCreatorError> {
+    let path = Path::new(source);
+    if source.len() > 255
+        || source.contains('\0')
+        || path
+            .components()
+            .any(|component| matches!(component, Component::Prefix(_) | Component::RootDir))
+    {
+        return Err(ContextCreatorError::SecurityError(format!(
+            "Invalid module source: {source}"
+        )));
+    }
+    Ok(())
+}
+
+/// Names of the `.tf` files of a module directory inside the project.
+fn module_files(dir: &Path, base_dir: &Path) -> Vec<String> {
+    let Ok(dir) = validate_import_path(base_dir, dir) else {
+        return Vec::new();
+    };
+    let Ok(entries) = fs::read_dir(&dir) else {
+        return Vec::new();
+    };
+    let mut files: Vec<String> = entries
+        .flatten()
+        .filter(|entry| entry.path().is_file())
+        .filter_map(|entry| entry.file_name().to_str().map(str::to_string))
+        .filter(|name| name.ends_with(".tf"))
+        .collect();
+    files.sort();
+    files
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum HclTokenKind {
+    Identifier,
+    Str,
+    Punct,
+    Newline,
+}
+
+#[derive(Debug, Clone)]
+struct HclToken {
+    kind: HclTokenKind,
+    text: String,
+    line: usize,
+}
+
+impl HclToken {
+    fn is(&self, kind: HclTokenKind, text: &str) -> bool {
+        self.kind == kind && self.text == text
+    }
+}
+
+fn hcl_tokens(content: &str) -> Vec<HclToken> {
+    let mut tokens = Vec::new();
+    lex_hcl(content, 1, &mut tokens);
+    tokens
+}
+
+/// Lex `content` starting at `line`, lexing the expressions of `${}` interpolations in
+/// strings and heredocs as well.
+fn lex_hcl(content: &str, line: usize, tokens: &mut Vec<HclToken>) {
+    let chars: Vec<char> = content.chars().collect();
+    let mut line = line;
+    let mut i = 0;
+    let push = |tokens: &mut Vec<HclToken>, kind, text: String, line| {
+        tokens.push(HclToken { kind, text, line });
+    };
+
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+        if c == '\n' {
+            push(tokens, HclTokenKind::Newline, "\n".to_string(), line);
+            line += 1;
+            i += 1;
+        } else if c == '#' || (c == '/' && next == Some('/')) {
+            while i < chars.len() && chars[i] != '\n' {
+                i += 1;
+            }
+        } else if c == '/' && next == Some('*') {
+            i += 2;
+            while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                if chars[i] == '\n' {
+                    line += 1;
+                }
+                i += 1;
+            }
+            i += 2;
+        } else if c == '"' {
+            let start_line = line;
+            let mut text = String::new();
+            i += 1;
+            while i < chars.len() && chars[i] != '"' && chars[i] != '\n' {
+                if chars[i] == '\\' {
+                    text.extend(chars.get(i..i + 2).unwrap_or(&chars[i..]));
+                    i += 2;
+                } else if let Some(end) = interpolation_end(&chars, i) {
+                    let expression: String = chars[i + 2..end].iter().collect();
+                    lex_hcl(&expression, line, tokens);
+                    text.extend(&chars[i..=end]);
+                    i = end + 1;
+                } else {
+                    text.push(chars[i]);
+                    i += 1;
+                }
+            }
+            i += 1;
+            push(tokens, HclTokenKind::Str, text, start_line);
+        } else if c == '<' && next == Some('<') {
+            // Heredoc: <<EOT or <<-EOT, up to a line holding only the marker
+            let marker_start = i + 2 + usize::from(chars.get(i + 2) == Some(&'-'));
+            let mut marker_end = marker_start;
+            while marker_end < chars.len()
+                && (chars[marker_end].is_alphanumeric() || chars[marker_end] == '_')
+            {
+                marker_end += 1;
+            }
+            let marker: String = chars[marker_start..marker_end].iter().collect();
+            if marker.is_empty() {
+                push(tokens, HclTokenKind::Punct, "<".to_string(), line);
+                i += 1;
+                continue;
+            }
+
+            let start_line = line;
+            let rest: String = chars[marker_end..].iter().collect();
+            let mut body = String::new();
+            let mut consumed = 0;
+            for (index, body_line) in rest.split_inclusive('\n').enumerate() {
+                // The newline after the closing marker is left to end the line
+                if index > 0 && body_line.trim() == marker {
+                    consumed += body_line.trim_end().chars().count();
+                    break;
+                }
+                consumed += body_line.chars().count();
+                if index > 0 {
+                    body.push_str(body_line);
+                }
+            }
+            line += rest.chars().take(consumed).filter(|c| *c == '\n').count();
+
+            let body_chars: Vec<char> = body.chars().collect();
+            let mut body_line = start_line + 1;
+            let mut j = 0;
+            while j < body_chars.len() {
+                if body_chars[j] == '\n' {
+                    body_line += 1;
+                } else if let Some(end) = interpolation_end(&body_chars, j) {
+                    let expression: String = body_chars[j + 2..end].iter().collect();
+                    lex_hcl(&expression, body_line, tokens);
+                    j = end;
+                }
+                j += 1;
+            }
+            push(tokens, HclTokenKind::Str, body, start_line);
+            i = marker_end + consumed;
+        } else if c.is_alphabetic() || c == '_' {
+            let start = i;
+            while i < chars.len()
+                && (chars[i].is_alphanumeric() || chars[i] == '_' || chars[i] == '-')
+            {
+                i += 1;
+            }
+            push(
+                tokens,
+                HclTokenKind::Identifier,
+                chars[start..i].iter().collect(),
+                line,
+            );
+        } else if c.is_ascii_digit() {
+            while i < chars.len() && (chars[i].is_ascii_alphanumeric() || chars[i] == '.') {
+                i += 1;
+            }
+        } else {
+            if !c.is_whitespace() {
+                push(tokens, HclTokenKind::Punct, c.to_string(), line);
+            }
+            i += 1;
+        }
+    }
+}
+
+/// The index of the `}` closing a `${` or `%{` template sequence starting at `i`.
+fn interpolation_end(chars: &[char], i: usize) -> Option<usize> {
+    if !matches!(chars.get(i), Some('$' | '%')) || chars.get(i + 1) != Some(&'{') {
+        return None;
+    }
+    // `$${` escapes an interpolation
+    if i > 0 && chars[i - 1] == chars[i] {
+        return None;
+    }
+    let mut depth = 0;
+    for (offset, c) in chars[i + 1..].iter().enumerate() {
+        match c {
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i + 1 + offset);
+                }
+            }
+            '\n' => return None,
+            _ => {}
+        }
+    }
+    None
+}
+
+/// The declarations and references of one Terraform file.
+#[derive(Debug, Default)]
+struct HclSource {
+    variables: Vec<String>,
+    outputs: Vec<String>,
+    locals: Vec<String>,
+    resources: Vec<(String, String)>,
+    data: Vec<(String, String)>,
+    /// Module calls with their source and line
+    modules: Vec<(String, Option<String>, usize)>,
+    /// Top-level attributes, as assigned in `.tfvars` files
+    attributes: Vec<(String, usize)>,
+    /// Traversals such as `var.region` or `aws_iam_role.app.arn`
+    references: Vec<(Vec<String>, usize)>,
+    /// Module files read with `file()` or `templatefile()`
+    files: Vec<(String, usize)>,
+}
+
+#[derive(Debug, PartialEq)]
+enum HclBlock {
+    Locals,
+    Module(usize),
+    Other,
+}
+
+impl HclSource {
+    fn scan(tokens: &[HclToken]) -> Self {
+        let mut source = Self::default();
+        let mut depth = 0usize;
+        let mut block = HclBlock::Other;
+        let mut at_line_start = true;
+
+        let mut i = 0;
+        while i < tokens.len() {
+            let token = &tokens[i];
+            let line_start = std::mem::replace(&mut at_line_start, false);
+            match token.kind {
+                HclTokenKind::Newline => at_line_start = true,
+                // Attributes may follow the brace of a single-line block
+                HclTokenKind::Punct if token.text == "{" => {
+                    depth += 1;
+                    at_line_start = true;
+                }
+                HclTokenKind::Punct if token.text == "}" => depth = depth.saturating_sub(1),
+                HclTokenKind::Identifier => {
+                    let assigns = tokens
+                        .get(i + 1)
+                        .is_some_and(|next| next.is(HclTokenKind::Punct, "="));
+                    if line_start && depth == 0 && !assigns {
+                        if let Some(next) = source.scan_block_header(tokens, i, &mut block) {
+                            i = next;
+                            continue;
+                        }
+                    }
+                    if line_start && assigns {
+                        source.scan_attribute(tokens, i, depth, &block);
+                    }
+
+                    let is_traversal_start = i == 0 || !tokens[i - 1].is(HclTokenKind::Punct, ".");
+                    if is_traversal_start {
+                        source.scan_reference(tokens, i);
+                    }
+                    if HCL_FILE_FUNCTIONS.contains(&token.text.as_str()) {
+                        source.scan_file_call(tokens, i);
+                    }
+                }
+                _ => {}
+            }
+            i += 1;
+        }
+        source
+    }
+
+    /// Record a block such as `resource "aws_s3_bucket" "logs" {`, returning the index of
+    /// its opening brace.
+    fn scan_block_header(
+        &mut self,
+        tokens: &[HclToken],
+        i: usize,
+        block: &mut HclBlock,
+    ) -> Option<usize> {
+        let mut labels = Vec::new();
+        let mut j = i + 1;
+        while let Some(token) = tokens.get(j) {
+            match token.kind {
+                HclTokenKind::Str | HclTokenKind::Identifier => labels.push(token.text.clone()),
+                HclTokenKind::Punct if token.text == "{" => break,
+                _ => return None,
+            }
+            j += 1;
+        }
+        tokens.get(j)?;
+
+        let line = tokens[i].line;
+        *block = HclBlock::Other;
+        match (tokens[i].text.as_str(), labels.as_slice()) {
+            ("variable", [name]) => self.variables.push(name.clone()),
+            ("output", [name]) => self.outputs.push(name.clone()),
+            ("locals", []) => *block = HclBlock::Locals,
+            ("resource", [kind, name]) => self.resources.push((kind.clone(), name.clone())),
+            ("data", [kind, name]) => self.data.push((kind.clone(), name.clone())),
+            ("module", [name]) => {
+                *block = HclBlock::Module(self.modules.len());
+                self.modules.push((name.clone(), None, line));
+            }
+            _ => {}
+        }
+        Some(j)
+    }
+
+    fn scan_attribute(&mut self, tokens: &[HclToken], i: usize, depth: usize, block: &HclBlock) {
+        let name = &tokens[i].text;
+        match (depth, block) {
+            (0, _) => self.attributes.push((name.clone(), tokens[i].line)),
+            (1, HclBlock::Locals) => self.locals.push(name.clone()),
+            (1, HclBlock::Module(index)) if name == "source" => {
+                if let Some(value) = tokens
+                    .get(i + 2)
+                    .filter(|value| value.kind == HclTokenKind::Str)
+                {
+                    self.modules[*index].1 = Some(value.text.clone());
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Record a traversal of at least two names, such as `var.region`.
+    fn scan_reference(&mut self, tokens: &[HclToken], i: usize) {
+        let mut parts = vec![tokens[i].text.clone()];
+        let mut j = i + 1;
+        while parts.len() < 3
+            && tokens
+                .get(j)
+                .is_some_and(|token| token.is(HclTokenKind::Punct, "."))
+        {
+            match tokens.get(j + 1) {
+                Some(token) if token.kind == HclTokenKind::Identifier => {
+                    parts.push(token.text.clone());
+                    j += 2;
+                }
+                _ => break,
+            }
+        }
+        if parts.len() > 1 {
+            self.references.push((parts, tokens[i].line));
+        }
+    }
+
+    /// Record the file read by `file("${path.module}/scripts/init.sh")`.
+    fn scan_file_call(&mut self, tokens: &[HclToken], i: usize) {
+        if !tokens
+            .get(i + 1)
+            .is_some_and(|token| token.is(HclTokenKind::Punct, "("))
+        {
+            return;
+        }
+        // The tokens of the interpolation come before the string holding it
+        let Some(argument) = tokens[i + 2..]
+            .iter()
+            .take_while(|token| !token.is(HclTokenKind::Punct, ")"))
+            .find(|token| token.kind == HclTokenKind::Str)
+        else {
+            return;
+        };
+        let path = argument
+            .text
+            .strip_prefix("${path.module}/")
+            .unwrap_or(&argument.text)
+            .trim_start_matches("./");
+        if !path.is_empty() && !path.contains("${") && !path.starts_with('/') {
+            self.files.push((path.to_string(), tokens[i].line));
+        }
+    }
+}
+
+/// The declarations of a Terraform module directory, by the file declaring each.
+#[derive(Debug, Default)]
+struct HclDirectory {
+    variables: HashMap<String, PathBuf>,
+    outputs: HashMap<String, PathBuf>,
+    locals: HashMap<String, PathBuf>,
+    resources: HashMap<(String, String), PathBuf>,
+    data: HashMap<(String, String), PathBuf>,
+    modules: HashMap<String, (PathBuf, Option<String>)>,
+    /// Variable values assigned by `terraform.tfvars` and `*.auto.tfvars`, which Terraform
+    /// loads automatically
+    assignments: HashMap<String, Vec<PathBuf>>,
+}
+
+impl HclDirectory {
+    fn build(dir: &Path) -> Self {
+        let mut directory = Self::default();
+        let Ok(entries) = fs::read_dir(dir) else {
+            return directory;
+        };
+        let mut files: Vec<PathBuf> = entries
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.is_file())
+            .collect();
+        files.sort();
+
+        for file in files {
+            let Some(name) = file.file_name().and_then(|name| name.to_str()) else {
+                continue;
+            };
+            let is_variable_file = name == "terraform.tfvars" || name.ends_with(".auto.tfvars");
+            if !name.ends_with(".tf") && !is_variable_file {
+                continue;
+            }
+            let Ok(content) = fs::read_to_string(&file) else {
+                continue;
+            };
+            let source = HclSource::scan(&hcl_tokens(&content));
+
+            if is_variable_file {
+                for (name, _) in source.attributes {
+                    directory
+                        .assignments
+                        .entry(name)
+                        .or_default()
+                        .push(file.clone());
+                }
+                continue;
+            }
+            for name in source.variables {
+                directory.variables.entry(name).or_insert(file.clone());
+            }
+            for name in source.outputs {
+                directory.outputs.entry(name).or_insert(file.clone());
+            }
+            for name in source.locals {
+                directory.locals.entry(name).or_insert(file.clone());
+            }
+            for key in source.resources {
+                directory.resources.entry(key).or_insert(file.clone());
+            }
+            for key in source.data {
+                directory.data.entry(key).or_insert(file.clone());
+            }
+            for (name, module_source, _) in source.modules {
+                directory
+                    .modules
+                    .entry(name)
+                    .or_insert((file.clone(), module_source));
+            }
+        }
+        directory
+    }
+}
+
+fn hcl_directory_cache() -> &'static Mutex<HashMap<PathBuf, Arc<HclDirectory>>> {
+    static CACHE: OnceLock<Mutex<HashMap<PathBuf, Arc<HclDirectory>>>> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// The declarations of a module directory inside the project; directories outside it are
+/// never read.
+fn hcl_directory(dir: &Path, base_dir: &Path) -> Arc<HclDirectory> {
+    let Ok(dir) = validate_import_path(base_dir, dir) else {
+        return Arc::new(HclDirectory::default());
+    };
+    if let Some(directory) = hcl_directory_cache()
+        .lock()
+        .ok()
+        .and_then(|cache| cache.get(&dir).cloned())
+    {
+        return directory;
+    }
+
+    let directory = Arc::new(HclDirectory::build(&dir));
+    if let Ok(mut cache) = hcl_directory_cache().lock() {
+        cache.insert(dir, directory.clone());
+    }
+    directory
+}
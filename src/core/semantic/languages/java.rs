@@ -1,27 +1,44 @@
-//! Semantic analyzer for Java
+//! Semantic analyzer for Java and Maven/Gradle source layouts
 
-use crate::core::semantic::analyzer::{
-    AnalysisResult, LanguageAnalyzer, SemanticContext, SemanticResult,
+use crate::core::semantic::{
+    analyzer::{AnalysisResult, Import, LanguageAnalyzer, SemanticContext, SemanticResult},
+    languages::{is_capitalized_type_name, kotlin::kotlin_declaration_file},
+    path_validator::{validate_import_path, validate_module_name},
+    query_engine::QueryEngine,
+    resolver::{ModuleResolver, ResolvedPath},
 };
-use std::path::Path;
+use crate::utils::error::ContextCreatorError;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
 use tree_sitter::Parser;
 
+/// Source roots used by Maven and Gradle modules, relative to the module directory.
+const JAVA_SOURCE_ROOTS: &[&str] = &["src/main/java", "src/test/java"];
+
+/// Build files that mark a Maven or Gradle module directory.
+const JAVA_BUILD_FILES: &[&str] = &["pom.xml", "build.gradle", "build.gradle.kts"];
+
 #[allow(clippy::new_without_default)]
 pub struct JavaAnalyzer {
-    #[allow(dead_code)]
-    #[allow(dead_code)]
-    parser: Parser,
+    query_engine: QueryEngine,
 }
 
 impl JavaAnalyzer {
     pub fn new() -> Self {
-        let parser = Parser::new();
-        // Note: tree_sitter_java::language() would be used here when the crate is added
-        // parser.set_language(tree_sitter_java::language()).unwrap();
-        Self { parser }
+        let language = tree_sitter_java::language();
+        let query_engine =
+            QueryEngine::new(language, "java").expect("Failed to create Java query engine");
+        Self { query_engine }
     }
 }
 
+fn java_source_root_cache() -> &'static Mutex<HashMap<PathBuf, Vec<PathBuf>>> {
+    static CACHE: OnceLock<Mutex<HashMap<PathBuf, Vec<PathBuf>>>> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
 impl LanguageAnalyzer for JavaAnalyzer {
     fn language_name(&self) -> &'static str {
         "Java"
@@ -29,12 +46,36 @@ impl LanguageAnalyzer for JavaAnalyzer {
 
     fn analyze_file(
         &self,
-        _path: &Path,
-        _content: &str,
-        _context: &SemanticContext,
+        path: &Path,
+        content: &str,
+        context: &SemanticContext,
     ) -> SemanticResult<AnalysisResult> {
-        // TODO: Implement Java analysis
-        Ok(AnalysisResult::default())
+        let mut parser = Parser::new();
+        parser
+            .set_language(tree_sitter_java::language())
+            .map_err(|e| ContextCreatorError::ParseError(format!("Failed to set language: {e}")))?;
+
+        let mut result = self
+            .query_engine
+            .analyze_with_parser(&mut parser, content)?;
+
+        // Receiver calls match both the unqualified and the qualified call pattern, and type
+        // patterns also capture package segments
+        result.dedupe_function_calls(|_| true);
+        result.dedupe_type_references(|type_ref| is_capitalized_type_name(&type_ref.name));
+
+        // Every class lookup of this file searches the same roots
+        let package = java_package_name(content);
+        let file = JavaFile {
+            path,
+            package: package.as_deref(),
+            source_roots: java_source_roots(path, package.as_deref(), &context.base_dir),
+            base_dir: &context.base_dir,
+        };
+        self.expand_on_demand_imports(&mut result, &file);
+        self.resolve_java_type_definitions(&mut result, &file);
+
+        Ok(result)
     }
 
     fn can_handle_extension(&self, extension: &str) -> bool {
@@ -44,4 +85,447 @@ impl LanguageAnalyzer for JavaAnalyzer {
     fn supported_extensions(&self) -> Vec<&'static str> {
         vec!["java"]
     }
+
+    fn query_engine(&self) -> Option<&QueryEngine> {
+        Some(&self.query_engine)
+    }
+}
+
+impl JavaAnalyzer {
+    /// Replace `import com.acme.model.*;` with the classes this file actually uses,
+    /// so import tracing does not pull in an arbitrary file of the package.
+    fn expand_on_demand_imports(&self, result: &mut AnalysisResult, file: &JavaFile<'_>) {
+        let explicitly_imported: HashSet<String> = result
+            .imports
+            .iter()
+            .filter(|import| !import.items.iter().any(|item| item == "*"))
+            .filter_map(|import| import.items.first().cloned())
+            .collect();
+        let referenced: Vec<String> = result
+            .type_references
+            .iter()
+            .map(|type_ref| type_ref.name.clone())
+            .filter(|name| !explicitly_imported.contains(name))
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+
+        let resolver = JavaModuleResolver;
+        let mut imports = Vec::with_capacity(result.imports.len());
+        for import in result.imports.drain(..) {
+            if import.items != ["*"] {
+                imports.push(import);
+                continue;
+            }
+
+            let mut expanded = Vec::new();
+            for name in &referenced {
+                let class_path = format!("{}.{name}", import.module);
+                if let Some(class_file) = resolver.resolve_class_file(
+                    &class_path,
+                    file.path,
+                    &file.source_roots,
+                    file.base_dir,
+                ) {
+                    if class_file.file_stem().and_then(|stem| stem.to_str()) == Some(name.as_str())
+                    {
+                        expanded.push(Import {
+                            module: class_path,
+                            items: vec![name.clone()],
+                            is_relative: false,
+                            line: import.line,
+                        });
+                    }
+                }
+            }
+
+            if expanded.is_empty() {
+                imports.push(import);
+            } else {
+                expanded.sort_by(|left, right| left.module.cmp(&right.module));
+                imports.extend(expanded);
+            }
+        }
+
+        result.imports = imports;
+    }
+
+    fn resolve_java_type_definitions(&self, result: &mut AnalysisResult, file: &JavaFile<'_>) {
+        let resolver = JavaModuleResolver;
+        let imported_classes: HashMap<&str, &str> = result
+            .imports
+            .iter()
+            .filter(|import| import.items.len() == 1 && import.items[0] != "*")
+            .filter(|import| import.module.rsplit('.').next() == Some(import.items[0].as_str()))
+            .map(|import| (import.items[0].as_str(), import.module.as_str()))
+            .collect();
+        let on_demand_packages: Vec<&str> = result
+            .imports
+            .iter()
+            .filter(|import| import.items == ["*"])
+            .map(|import| import.module.as_str())
+            .collect();
+        for type_ref in &mut result.type_references {
+            if type_ref.definition_path.is_some() || type_ref.is_external {
+                continue;
+            }
+
+            let mut candidates = Vec::new();
+            if let Some(module) = type_ref.module.as_deref() {
+                let qualifier = imported_classes
+                    .get(module)
+                    .copied()
+                    .unwrap_or(module)
+                    .to_string();
+                candidates.push(format!("{qualifier}.{}", type_ref.name));
+                candidates.push(qualifier);
+            } else if let Some(class_path) = imported_classes.get(type_ref.name.as_str()) {
+                if resolver
+                    .resolve_class_file(class_path, file.path, &file.source_roots, file.base_dir)
+                    .is_none()
+                {
+                    type_ref.is_external = true;
+                    type_ref.external_package = Some((*class_path).to_string());
+                    continue;
+                }
+                candidates.push((*class_path).to_string());
+            } else {
+                if let Some(package) = file.package {
+                    candidates.push(format!("{package}.{}", type_ref.name));
+                } else if let Some(sibling) = file
+                    .path
+                    .parent()
+                    .map(|dir| dir.join(format!("{}.java", type_ref.name)))
+                    .filter(|sibling| sibling.is_file())
+                {
+                    if let Ok(validated) = validate_import_path(file.base_dir, &sibling) {
+                        type_ref.definition_path = Some(validated);
+                    }
+                    continue;
+                }
+                for package in &on_demand_packages {
+                    candidates.push(format!("{package}.{}", type_ref.name));
+                }
+            }
+
+            let definition = candidates
+                .iter()
+                .find_map(|class_path| {
+                    resolver.resolve_class_file(
+                        class_path,
+                        file.path,
+                        &file.source_roots,
+                        file.base_dir,
+                    )
+                })
+                .filter(|definition| definition != file.path);
+
+            match definition {
+                Some(definition) => match validate_import_path(file.base_dir, &definition) {
+                    Ok(validated) => type_ref.definition_path = Some(validated),
+                    Err(_) => type_ref.is_external = true,
+                },
+                None if type_ref.module.is_none() && is_java_lang_type(&type_ref.name) => {
+                    type_ref.is_external = true;
+                    type_ref.external_package = Some("java.lang".to_string());
+                }
+                None => {}
+            }
+        }
+    }
+}
+
+/// A Java file being analyzed, with the source roots its class lookups search
+struct JavaFile<'a> {
+    path: &'a Path,
+    package: Option<&'a str>,
+    source_roots: Vec<PathBuf>,
+    base_dir: &'a Path,
+}
+
+pub struct JavaModuleResolver;
+
+impl ModuleResolver for JavaModuleResolver {
+    fn resolve_import(
+        &self,
+        module_path: &str,
+        from_file: &Path,
+        base_dir: &Path,
+    ) -> Result<ResolvedPath, ContextCreatorError> {
+        let module_path = clean_java_import_path(module_path);
+        validate_module_name(&module_path)?;
+
+        let package = fs::read_to_string(from_file)
+            .ok()
+            .and_then(|content| java_package_name(&content));
+        let source_roots = java_source_roots(from_file, package.as_deref(), base_dir);
+        if let Some(path) =
+            self.resolve_class_file(&module_path, from_file, &source_roots, base_dir)
+        {
+            return resolved_java_path(path, base_dir, 0.95);
+        }
+
+        let package_path = java_package_to_path(&module_path);
+        for root in &source_roots {
+            if let Some(path) = first_java_file_in_package(&root.join(&package_path)) {
+                return resolved_java_path(path, base_dir, 0.8);
+            }
+        }
+
+        Ok(ResolvedPath {
+            path: java_build_file(from_file, base_dir),
+            is_external: true,
+            confidence: if self.is_external_module(&module_path) {
+                0.9
+            } else {
+                0.5
+            },
+        })
+    }
+
+    fn get_file_extensions(&self) -> Vec<&'static str> {
+        vec!["java"]
+    }
+
+    fn is_external_module(&self, module_path: &str) -> bool {
+        let module_path = clean_java_import_path(module_path);
+        [
+            "java.", "javax.", "jakarta.", "jdk.", "sun.", "com.sun.", "org.w3c.", "org.xml.",
+        ]
+        .iter()
+        .any(|prefix| module_path.starts_with(prefix))
+    }
+}
+
+impl JavaModuleResolver {
+    /// Find the file declaring a class, trying the longest package prefix first so
+    /// nested classes (`Outer.Inner`) and static members land on the outer class file.
+    /// Falls back to Kotlin declarations in mixed Kotlin/Java modules.
+    pub(crate) fn resolve_class_file(
+        &self,
+        class_path: &str,
+        from_file: &Path,
+        source_roots: &[PathBuf],
+        base_dir: &Path,
+    ) -> Option<PathBuf> {
+        let segments: Vec<&str> = class_path
+            .split('.')
+            .filter(|segment| !segment.is_empty())
+            .collect();
+        if segments.is_empty() {
+            return None;
+        }
+
+        for length in (1..=segments.len()).rev() {
+            let (package, class_name) = segments[..length].split_at(length - 1);
+            if !class_name[0].starts_with(|c: char| c.is_uppercase()) {
+                continue;
+            }
+
+            for root in source_roots {
+                let candidate = package
+                    .iter()
+                    .fold(root.clone(), |path, segment| path.join(segment))
+                    .join(format!("{}.java", class_name[0]));
+                if candidate.is_file() {
+                    return Some(candidate);
+                }
+            }
+        }
+
+        // Mixed modules: the class may be declared in Kotlin
+        kotlin_declaration_file(class_path, from_file, base_dir)
+    }
+}
+
+/// Source roots that may contain the packages visible from `from_file`: the root implied
+/// by its `package` declaration, the Maven/Gradle roots of its module, and every
+/// `src/main/java`/`src/test/java` root in the project.
+pub(crate) fn java_source_roots(
+    from_file: &Path,
+    package: Option<&str>,
+    base_dir: &Path,
+) -> Vec<PathBuf> {
+    let mut roots = Vec::new();
+
+    if let Some(root) = package.and_then(|package| package_source_root(from_file, package)) {
+        roots.push(root);
+    }
+
+    if let Some(module_dir) = from_file
+        .ancestors()
+        .skip(1)
+        .take_while(|dir| dir.starts_with(base_dir))
+        .find(|dir| JAVA_BUILD_FILES.iter().any(|file| dir.join(file).is_file()))
+    {
+        for source_root in JAVA_SOURCE_ROOTS {
+            roots.push(module_dir.join(source_root));
+        }
+    }
+
+    roots.extend(project_java_source_roots(base_dir));
+    roots.push(base_dir.join("src"));
+    roots.push(base_dir.to_path_buf());
+
+    let mut seen = HashSet::new();
+    roots
+        .into_iter()
+        .filter(|root| root.is_dir() && seen.insert(root.clone()))
+        .collect()
+}
+
+fn project_java_source_roots(base_dir: &Path) -> Vec<PathBuf> {
+    if let Some(roots) = java_source_root_cache()
+        .lock()
+        .ok()
+        .and_then(|cache| cache.get(base_dir).cloned())
+    {
+        return roots;
+    }
+
+    let mut roots = Vec::new();
+    let walker = walkdir::WalkDir::new(base_dir)
+        .max_depth(6)
+        .into_iter()
+        .filter_entry(|entry| {
+            entry.depth() == 0
+                || !entry.file_name().to_str().is_some_and(|name| {
+                    name.starts_with('.')
+                        || matches!(name, "target" | "build" | "out" | "node_modules")
+                })
+        });
+    for entry in walker.flatten() {
+        if !entry.file_type().is_dir() {
+            continue;
+        }
+        if JAVA_SOURCE_ROOTS
+            .iter()
+            .any(|source_root| entry.path().ends_with(source_root))
+        {
+            roots.push(entry.path().to_path_buf());
+        }
+    }
+    roots.sort();
+
+    if let Ok(mut cache) = java_source_root_cache().lock() {
+        cache.insert(base_dir.to_path_buf(), roots.clone());
+    }
+
+    roots
+}
+
+/// Walk up from the file's directory, one level per package segment, to reach the source root.
+fn package_source_root(file: &Path, package: &str) -> Option<PathBuf> {
+    let mut dir = file.parent()?;
+    for segment in package.rsplit('.') {
+        if dir.file_name().and_then(|name| name.to_str()) != Some(segment) {
+            return None;
+        }
+        dir = dir.parent()?;
+    }
+    Some(dir.to_path_buf())
+}
+
+pub(crate) fn java_package_name(content: &str) -> Option<String> {
+    content.lines().find_map(|line| {
+        let declaration = line.trim().strip_prefix("package ")?;
+        let package = declaration.split(';').next()?.trim();
+        (!package.is_empty()).then(|| package.to_string())
+    })
+}
+
+fn first_java_file_in_package(package_dir: &Path) -> Option<PathBuf> {
+    let mut files = fs::read_dir(package_dir)
+        .ok()?
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().and_then(|ext| ext.to_str()) == Some("java"))
+        .collect::<Vec<_>>();
+
+    files.sort();
+    files.into_iter().next()
+}
+
+fn java_build_file(from_file: &Path, base_dir: &Path) -> PathBuf {
+    from_file
+        .ancestors()
+        .skip(1)
+        .take_while(|dir| dir.starts_with(base_dir))
+        .flat_map(|dir| JAVA_BUILD_FILES.iter().map(move |file| dir.join(file)))
+        .find(|path| path.is_file())
+        .unwrap_or_else(|| base_dir.join("pom.xml"))
+}
+
+fn java_package_to_path(package: &str) -> PathBuf {
+    package
+        .split('.')
+        .filter(|segment| !segment.is_empty())
+        .collect()
+}
+
+fn clean_java_import_path(module_path: &str) -> String {
+    module_path
+        .trim()
+        .trim_start_matches("static ")
+        .trim_end_matches(".*")
+        .trim()
+        .to_string()
+}
+
+fn resolved_java_path(
+    path: PathBuf,
+    base_dir: &Path,
+    confidence: f32,
+) -> Result<ResolvedPath, ContextCreatorError> {
+    Ok(ResolvedPath {
+        path: validate_import_path(base_dir, &path)?,
+        is_external: false,
+        confidence,
+    })
+}
+
+/// Types from `java.lang`, which every compilation unit imports implicitly.
+fn is_java_lang_type(type_name: &str) -> bool {
+    matches!(
+        type_name,
+        "Object"
+            | "String"
+            | "StringBuilder"
+            | "CharSequence"
+            | "Integer"
+            | "Long"
+            | "Short"
+            | "Byte"
+            | "Double"
+            | "Float"
+            | "Boolean"
+            | "Character"
+            | "Number"
+            | "Void"
+            | "Math"
+            | "System"
+            | "Thread"
+            | "Runnable"
+            | "Iterable"
+            | "Comparable"
+            | "AutoCloseable"
+            | "Class"
+            | "Enum"
+            | "Record"
+            | "Throwable"
+            | "Exception"
+            | "Error"
+            | "RuntimeException"
+            | "IllegalArgumentException"
+            | "IllegalStateException"
+            | "NullPointerException"
+            | "UnsupportedOperationException"
+            | "IndexOutOfBoundsException"
+            | "InterruptedException"
+            | "Override"
+            | "Deprecated"
+            | "FunctionalInterface"
+            | "SuppressWarnings"
+            | "SafeVarargs"
+    )
 }
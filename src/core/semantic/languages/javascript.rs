@@ -1,5 +1,8 @@
 //! Semantic analyzer for JavaScript
 
+use crate::core::semantic::languages::node::{
+    resolve_bare_specifier, resolve_module_file, trace_barrel_imports, validate_relative_specifier,
+};
 use crate::core::semantic::{
     analyzer::{AnalysisResult, LanguageAnalyzer, SemanticContext, SemanticResult},
     path_validator::{validate_import_path, validate_module_name},
@@ -44,6 +47,14 @@ impl LanguageAnalyzer for JavaScriptAnalyzer {
             .query_engine
             .analyze_with_parser(&mut parser, content)?;
 
+        // Imported names come from the files a barrel re-exports them from
+        trace_barrel_imports(
+            &mut result.imports,
+            path,
+            &context.base_dir,
+            &JavaScriptModuleResolver,
+        );
+
         // Resolve type definitions for the type references found
         self.query_engine.resolve_type_definitions(
             &mut result.type_references,
@@ -61,8 +72,18 @@ impl LanguageAnalyzer for JavaScriptAnalyzer {
     fn supported_extensions(&self) -> Vec<&'static str> {
         vec!["js", "jsx"]
     }
+
+    fn query_engine(&self) -> Option<&QueryEngine> {
+        Some(&self.query_engine)
+    }
 }
 
+/// Extensions tried when resolving a module path, JavaScript first, then TypeScript and
+/// single-file components.
+const EXTENSIONS: &[&str] = &[
+    "js", "jsx", "mjs", "cjs", "ts", "tsx", "d.ts", "vue", "svelte", "astro",
+];
+
 pub struct JavaScriptModuleResolver;
 
 impl ModuleResolver for JavaScriptModuleResolver {
@@ -73,87 +94,59 @@ impl ModuleResolver for JavaScriptModuleResolver {
         base_dir: &Path,
     ) -> Result<ResolvedPath, ContextCreatorError> {
         // Validate module name for security
-        validate_module_name(module_path)?;
-
-        // Handle Node.js built-in modules
-        if self.is_external_module(module_path) {
-            return Ok(ResolvedPath {
-                path: base_dir.join("package.json"), // Point to package.json as indicator
-                is_external: true,
-                confidence: 1.0,
-            });
+        if module_path.starts_with('.') {
+            validate_relative_specifier(module_path)?;
+        } else {
+            validate_module_name(module_path)?;
         }
 
         // Handle relative imports (./, ../)
         if module_path.starts_with('.') {
             if let Some(parent) = from_file.parent() {
-                // Properly resolve relative paths by removing leading "./"
-                let clean_path = if let Some(stripped) = module_path.strip_prefix("./") {
-                    stripped // Remove "./" prefix
-                } else {
-                    module_path // Keep as-is for "../" or other relative paths
-                };
-                let resolved_path = parent.join(clean_path);
-
-                // Try different extensions
-                for ext in &["js", "jsx", "ts", "tsx"] {
-                    let with_ext = resolved_path.with_extension(ext);
-                    if with_ext.exists() {
-                        let validated_path = validate_import_path(base_dir, &with_ext)?;
-                        return Ok(ResolvedPath {
-                            path: validated_path,
-                            is_external: false,
-                            confidence: 0.9,
-                        });
-                    }
-                }
-
-                // Try as directory with index file
-                for ext in &["js", "jsx", "ts", "tsx"] {
-                    let index_path = resolved_path.join(format!("index.{ext}"));
-                    if index_path.exists() {
-                        let validated_path = validate_import_path(base_dir, &index_path)?;
-                        return Ok(ResolvedPath {
-                            path: validated_path,
-                            is_external: false,
-                            confidence: 0.9,
-                        });
-                    }
+                if let Some(file) = resolve_module_file(&parent.join(module_path), EXTENSIONS) {
+                    let validated_path = validate_import_path(base_dir, &file)?;
+                    return Ok(ResolvedPath {
+                        path: validated_path,
+                        is_external: false,
+                        confidence: 0.9,
+                    });
                 }
             }
         }
 
-        // Handle absolute imports from node_modules or project root
+        // Handle tsconfig/jsconfig path aliases and workspace packages
+        if let Some(file) = resolve_bare_specifier(module_path, from_file, base_dir, EXTENSIONS) {
+            let validated_path = validate_import_path(base_dir, &file)?;
+            return Ok(ResolvedPath {
+                path: validated_path,
+                is_external: false,
+                confidence: 0.9,
+            });
+        }
+
+        // Handle Node.js built-in modules and common packages
+        if self.is_external_module(module_path) {
+            return Ok(ResolvedPath {
+                path: base_dir.join("package.json"), // Point to package.json as indicator
+                is_external: true,
+                confidence: 1.0,
+            });
+        }
+
+        // Handle absolute imports from the project root
         let search_paths = vec![
             base_dir.to_path_buf(),
             from_file.parent().unwrap_or(base_dir).to_path_buf(),
         ];
 
         for search_path in &search_paths {
-            // Try as a file
-            for ext in &["js", "jsx", "ts", "tsx"] {
-                let file_path = search_path.join(format!("{module_path}.{ext}"));
-                if file_path.exists() {
-                    let validated_path = validate_import_path(base_dir, &file_path)?;
-                    return Ok(ResolvedPath {
-                        path: validated_path,
-                        is_external: false,
-                        confidence: 0.8,
-                    });
-                }
-            }
-
-            // Try as a directory with index file
-            for ext in &["js", "jsx", "ts", "tsx"] {
-                let index_path = search_path.join(module_path).join(format!("index.{ext}"));
-                if index_path.exists() {
-                    let validated_path = validate_import_path(base_dir, &index_path)?;
-                    return Ok(ResolvedPath {
-                        path: validated_path,
-                        is_external: false,
-                        confidence: 0.8,
-                    });
-                }
+            if let Some(file) = resolve_module_file(&search_path.join(module_path), EXTENSIONS) {
+                let validated_path = validate_import_path(base_dir, &file)?;
+                return Ok(ResolvedPath {
+                    path: validated_path,
+                    is_external: false,
+                    confidence: 0.8,
+                });
             }
         }
 
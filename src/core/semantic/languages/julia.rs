@@ -1,24 +1,122 @@
-//! Semantic analyzer for Julia
+//! Semantic analyzer for Julia, following `include()` to source files and `using` and
+//! `import` to the modules of the project's packages
+//!
+//! No Julia grammar is available for the tree-sitter version used here, so files are
+//! read with a small lexer that understands comments, strings, and character literals
+//! well enough to find statements and calls.
 
-use crate::core::semantic::analyzer::{
-    AnalysisResult, LanguageAnalyzer, SemanticContext, SemanticResult,
+use crate::core::semantic::{
+    analyzer::{
+        AnalysisResult, FunctionCall, FunctionDefinition, Import, LanguageAnalyzer,
+        SemanticContext, SemanticResult, TypeReference,
+    },
+    path_validator::{validate_import_path, validate_module_name},
+    resolver::{ModuleResolver, ResolvedPath},
 };
-use std::path::Path;
-use tree_sitter::Parser;
+use crate::utils::error::ContextCreatorError;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+
+/// Keywords that can be followed by `(` without being calls.
+const JULIA_KEYWORDS: &[&str] = &[
+    "if",
+    "elseif",
+    "else",
+    "for",
+    "while",
+    "return",
+    "function",
+    "macro",
+    "end",
+    "begin",
+    "let",
+    "do",
+    "try",
+    "catch",
+    "finally",
+    "module",
+    "baremodule",
+    "struct",
+    "mutable",
+    "abstract",
+    "primitive",
+    "type",
+    "using",
+    "import",
+    "export",
+    "const",
+    "local",
+    "global",
+    "quote",
+    "where",
+    "in",
+    "isa",
+    "include",
+];
+
+/// Built-in types that never resolve to project files.
+const JULIA_BUILTIN_TYPES: &[&str] = &[
+    "Any",
+    "Nothing",
+    "Missing",
+    "Bool",
+    "Char",
+    "String",
+    "Symbol",
+    "Int",
+    "Int8",
+    "Int16",
+    "Int32",
+    "Int64",
+    "Int128",
+    "UInt",
+    "UInt8",
+    "UInt16",
+    "UInt32",
+    "UInt64",
+    "UInt128",
+    "Float16",
+    "Float32",
+    "Float64",
+    "Integer",
+    "Real",
+    "Number",
+    "AbstractFloat",
+    "AbstractString",
+    "Vector",
+    "Matrix",
+    "Array",
+    "AbstractArray",
+    "AbstractVector",
+    "AbstractMatrix",
+    "Dict",
+    "AbstractDict",
+    "Set",
+    "Tuple",
+    "NamedTuple",
+    "Union",
+    "Function",
+    "Type",
+    "DataType",
+    "Exception",
+    "IO",
+    "Ref",
+    "Ptr",
+    "Val",
+    "Pair",
+];
+
+/// Directories holding build output or registries.
+const JULIA_SKIPPED_DIRS: &[&str] = &["node_modules", "build", "deps/usr"];
 
 #[allow(clippy::new_without_default)]
-pub struct JuliaAnalyzer {
-    #[allow(dead_code)]
-    #[allow(dead_code)]
-    parser: Parser,
-}
+pub struct JuliaAnalyzer;
 
 impl JuliaAnalyzer {
     pub fn new() -> Self {
-        let parser = Parser::new();
-        // Note: tree_sitter_julia::language() would be used here when the crate is added
-        // parser.set_language(tree_sitter_julia::language()).unwrap();
-        Self { parser }
+        Self
     }
 }
 
@@ -29,19 +127,871 @@ impl LanguageAnalyzer for JuliaAnalyzer {
 
     fn analyze_file(
         &self,
-        _path: &Path,
-        _content: &str,
-        _context: &SemanticContext,
+        path: &Path,
+        content: &str,
+        context: &SemanticContext,
     ) -> SemanticResult<AnalysisResult> {
-        // TODO: Implement Julia analysis
-        Ok(AnalysisResult::default())
+        let tokens = julia_tokens(content);
+        let source = JuliaSource::scan(&tokens);
+
+        let mut result = AnalysisResult {
+            imports: source.imports.clone(),
+            function_calls: source.calls.clone(),
+            exported_functions: source
+                .functions
+                .iter()
+                .map(|(name, line)| FunctionDefinition {
+                    name: name.clone(),
+                    // Every function is reachable through its module; `export` only
+                    // decides which names `using` brings into scope
+                    is_exported: true,
+                    line: *line,
+                })
+                .collect(),
+            ..AnalysisResult::default()
+        };
+
+        self.resolve_module_references(&mut result, &source, path, &context.base_dir);
+
+        Ok(result)
     }
 
     fn can_handle_extension(&self, extension: &str) -> bool {
-        matches!(extension, "jl")
+        extension == "jl"
     }
 
     fn supported_extensions(&self) -> Vec<&'static str> {
         vec!["jl"]
     }
 }
+
+impl JuliaAnalyzer {
+    /// Point type annotations at the project file declaring them and record modules
+    /// that resolve outside the project as external packages.
+    fn resolve_module_references(
+        &self,
+        result: &mut AnalysisResult,
+        source: &JuliaSource,
+        path: &Path,
+        project_root: &Path,
+    ) {
+        let resolver = JuliaModuleResolver;
+        let index = julia_project_index(project_root);
+
+        let current_file = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+        let mut used_modules = Vec::new();
+        let mut external = Vec::new();
+        let mut enclosing = Vec::new();
+        for import in &result.imports {
+            if import.module.ends_with(".jl") {
+                continue;
+            }
+            match resolver.resolve_import(&import.module, path, project_root) {
+                // `using ..Parent` from a file the parent includes names the enclosing
+                // module rather than a dependency
+                Ok(resolved)
+                    if !resolved.is_external
+                        && import.is_relative
+                        && index.module_files(&resolved.path).contains(&current_file) =>
+                {
+                    enclosing.push(import.module.clone());
+                }
+                Ok(resolved) if !resolved.is_external => used_modules.push(resolved.path),
+                _ if !import.is_relative => external.push((import.module.clone(), import.line)),
+                _ => {}
+            }
+        }
+        result
+            .imports
+            .retain(|import| !enclosing.contains(&import.module));
+
+        for (name, module, line) in &source.types {
+            if source.declared_types.contains(name) || JULIA_BUILTIN_TYPES.contains(&name.as_str())
+            {
+                continue;
+            }
+
+            let definition = match module {
+                Some(module) => resolver
+                    .resolve_import(module, path, project_root)
+                    .ok()
+                    .filter(|resolved| !resolved.is_external)
+                    .and_then(|resolved| index.type_file(name, &resolved.path)),
+                None => used_modules
+                    .iter()
+                    .find_map(|module_file| index.type_file(name, module_file))
+                    .or_else(|| index.type_file_near(name, &current_file)),
+            };
+            if let Some(definition) = definition.filter(|file| *file != current_file) {
+                result.type_references.push(TypeReference {
+                    name: name.clone(),
+                    module: module.clone(),
+                    line: *line,
+                    definition_path: validate_import_path(project_root, &definition).ok(),
+                    is_external: false,
+                    external_package: None,
+                });
+            }
+        }
+
+        let mut recorded = HashSet::new();
+        for (module, line) in external {
+            let package = module.split('.').next().unwrap_or(&module).to_string();
+            if recorded.insert(package.clone()) {
+                result.type_references.push(TypeReference {
+                    name: package.clone(),
+                    module: None,
+                    line,
+                    definition_path: None,
+                    is_external: true,
+                    external_package: Some(package),
+                });
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum JuliaTokenKind {
+    Identifier,
+    Str,
+    Punct,
+    Newline,
+}
+
+#[derive(Debug, Clone)]
+struct JuliaToken {
+    kind: JuliaTokenKind,
+    text: String,
+    line: usize,
+    /// Whether whitespace separates this token from the previous one; Julia calls
+    /// require the `(` to follow the callee directly.
+    spaced: bool,
+}
+
+impl JuliaToken {
+    fn is(&self, kind: JuliaTokenKind, text: &str) -> bool {
+        self.kind == kind && self.text == text
+    }
+
+    fn is_punct(&self, text: &str) -> bool {
+        self.is(JuliaTokenKind::Punct, text)
+    }
+}
+
+/// Split Julia source into identifiers, string contents, punctuation, and newlines,
+/// dropping comments. Interpolations inside strings are not tokenized.
+fn julia_tokens(content: &str) -> Vec<JuliaToken> {
+    let chars: Vec<char> = content.chars().collect();
+    let mut tokens: Vec<JuliaToken> = Vec::new();
+    let mut line = 1;
+    let mut spaced = false;
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            '\n' => {
+                tokens.push(JuliaToken {
+                    kind: JuliaTokenKind::Newline,
+                    text: String::new(),
+                    line,
+                    spaced,
+                });
+                line += 1;
+                spaced = true;
+                i += 1;
+                continue;
+            }
+            c if c.is_whitespace() => {
+                spaced = true;
+                i += 1;
+                continue;
+            }
+            '#' if chars.get(i + 1) == Some(&'=') => {
+                // Block comments nest
+                let mut depth = 0;
+                while i < chars.len() {
+                    if chars[i] == '#' && chars.get(i + 1) == Some(&'=') {
+                        depth += 1;
+                        i += 2;
+                    } else if chars[i] == '=' && chars.get(i + 1) == Some(&'#') {
+                        depth -= 1;
+                        i += 2;
+                        if depth == 0 {
+                            break;
+                        }
+                    } else {
+                        if chars[i] == '\n' {
+                            line += 1;
+                        }
+                        i += 1;
+                    }
+                }
+                spaced = true;
+                continue;
+            }
+            '#' => {
+                while i < chars.len() && chars[i] != '\n' {
+                    i += 1;
+                }
+                continue;
+            }
+            '"' | '`' => {
+                let start_line = line;
+                let triple = chars.get(i + 1) == Some(&c) && chars.get(i + 2) == Some(&c);
+                i += if triple { 3 } else { 1 };
+                let mut text = String::new();
+                while i < chars.len() {
+                    if chars[i] == '\\' {
+                        text.extend(chars.get(i..i + 2).into_iter().flatten());
+                        i += 2;
+                        continue;
+                    }
+                    if chars[i] == c
+                        && (!triple
+                            || (chars.get(i + 1) == Some(&c) && chars.get(i + 2) == Some(&c)))
+                    {
+                        i += if triple { 3 } else { 1 };
+                        break;
+                    }
+                    if chars[i] == '\n' {
+                        line += 1;
+                    }
+                    text.push(chars[i]);
+                    i += 1;
+                }
+                tokens.push(JuliaToken {
+                    kind: JuliaTokenKind::Str,
+                    text,
+                    line: start_line,
+                    spaced,
+                });
+            }
+            '\'' => {
+                // A quote after a value is the adjoint operator, otherwise a character
+                let is_adjoint = !spaced
+                    && tokens.last().is_some_and(|token| {
+                        token.kind == JuliaTokenKind::Identifier
+                            || token.is_punct(")")
+                            || token.is_punct("]")
+                    });
+                i += 1;
+                if !is_adjoint {
+                    while i < chars.len() && chars[i] != '\'' && chars[i] != '\n' {
+                        i += if chars[i] == '\\' { 2 } else { 1 };
+                    }
+                    i += 1;
+                }
+            }
+            c if c.is_alphabetic() || c == '_' || c == '@' => {
+                let start = i;
+                i += 1;
+                while i < chars.len()
+                    && (chars[i].is_alphanumeric() || chars[i] == '_' || chars[i] == '!')
+                {
+                    i += 1;
+                }
+                tokens.push(JuliaToken {
+                    kind: JuliaTokenKind::Identifier,
+                    text: chars[start..i].iter().collect(),
+                    line,
+                    spaced,
+                });
+            }
+            c if c.is_ascii_digit() => {
+                while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                    i += 1;
+                }
+                tokens.push(JuliaToken {
+                    kind: JuliaTokenKind::Punct,
+                    text: "0".to_string(),
+                    line,
+                    spaced,
+                });
+            }
+            c => {
+                let text = match (c, chars.get(i + 1)) {
+                    ('<', Some(':')) | (':', Some(':')) | ('=', Some('=')) | ('.', Some('(')) => {
+                        i += 1;
+                        [c, chars[i]].iter().collect()
+                    }
+                    _ => c.to_string(),
+                };
+                i += 1;
+                tokens.push(JuliaToken {
+                    kind: JuliaTokenKind::Punct,
+                    text,
+                    line,
+                    spaced,
+                });
+            }
+        }
+        spaced = false;
+    }
+
+    tokens
+}
+
+/// What a Julia file declares and uses.
+#[derive(Debug, Default)]
+struct JuliaSource {
+    imports: Vec<Import>,
+    calls: Vec<FunctionCall>,
+    /// Top-level and nested function definitions with their lines.
+    functions: Vec<(String, usize)>,
+    modules: Vec<String>,
+    declared_types: HashSet<String>,
+    /// Annotated, supertype, and constructed types with their qualifying module.
+    types: Vec<(String, Option<String>, usize)>,
+}
+
+impl JuliaSource {
+    fn scan(tokens: &[JuliaToken]) -> Self {
+        let mut source = Self::default();
+        let mut depth = 0usize;
+        let mut at_statement_start = true;
+        let mut i = 0;
+
+        while i < tokens.len() {
+            let token = &tokens[i];
+            let statement_start = at_statement_start && depth == 0;
+            at_statement_start = false;
+
+            match token.kind {
+                JuliaTokenKind::Newline => {
+                    at_statement_start = true;
+                    i += 1;
+                    continue;
+                }
+                JuliaTokenKind::Punct => {
+                    match token.text.as_str() {
+                        "(" | "[" | "{" => depth += 1,
+                        ")" | "]" | "}" => depth = depth.saturating_sub(1),
+                        ";" => at_statement_start = true,
+                        "::" | "<:" => {
+                            if let Some((name, module, next)) = qualified_name(tokens, i + 1) {
+                                source.types.push((name, module, token.line));
+                                i = next;
+                                continue;
+                            }
+                        }
+                        _ => {}
+                    }
+                    i += 1;
+                    continue;
+                }
+                JuliaTokenKind::Str => {
+                    i += 1;
+                    continue;
+                }
+                JuliaTokenKind::Identifier => {}
+            }
+
+            match token.text.as_str() {
+                "using" | "import" if statement_start || depth == 0 => {
+                    i = source.scan_using(tokens, i + 1);
+                    at_statement_start = true;
+                    continue;
+                }
+                "module" | "baremodule" => {
+                    if let Some(name) = tokens.get(i + 1) {
+                        source.modules.push(name.text.clone());
+                    }
+                    i += 2;
+                    at_statement_start = true;
+                    continue;
+                }
+                "struct" | "type" => {
+                    if let Some(name) = tokens
+                        .get(i + 1)
+                        .filter(|name| name.kind == JuliaTokenKind::Identifier)
+                    {
+                        source.declared_types.insert(name.text.clone());
+                    }
+                    i += 2;
+                    continue;
+                }
+                "function" | "macro" => {
+                    if let Some((name, _, next)) = qualified_name(tokens, i + 1) {
+                        source.functions.push((name, token.line));
+                        i = next;
+                        continue;
+                    }
+                }
+                "include" if tokens.get(i + 1).is_some_and(|next| next.is_punct("(")) => {
+                    if let Some(path) = include_path(tokens, i + 2) {
+                        source.imports.push(Import {
+                            module: path,
+                            items: Vec::new(),
+                            is_relative: true,
+                            line: token.line,
+                        });
+                    }
+                }
+                _ => {}
+            }
+
+            if let Some((name, module, next)) = qualified_name(tokens, i) {
+                let call_start = tokens
+                    .get(next)
+                    .filter(|next| !next.spaced)
+                    .is_some_and(|next| next.is_punct("(") || next.is_punct(".("));
+                if call_start && !JULIA_KEYWORDS.contains(&name.as_str()) && !name.starts_with('@')
+                {
+                    if statement_start && module.is_none() && is_short_definition(tokens, next) {
+                        source.functions.push((name.clone(), token.line));
+                    } else {
+                        if name.starts_with(char::is_uppercase) {
+                            source
+                                .types
+                                .push((name.clone(), module.clone(), token.line));
+                        }
+                        source.calls.push(FunctionCall {
+                            name,
+                            module,
+                            line: token.line,
+                        });
+                    }
+                }
+                i = next;
+                continue;
+            }
+            i += 1;
+        }
+
+        source
+    }
+
+    /// `using A, B.C`, `using .Sub`, `import A: f, g`, `import A as B`; returns the index
+    /// after the statement.
+    fn scan_using(&mut self, tokens: &[JuliaToken], mut i: usize) -> usize {
+        let line = tokens.get(i).map_or(0, |token| token.line);
+        let mut paths: Vec<String> = Vec::new();
+        let mut current = String::new();
+        let mut items = Vec::new();
+        let mut in_items = false;
+        let mut after_as = false;
+
+        while let Some(token) = tokens.get(i) {
+            if token.kind == JuliaTokenKind::Newline || token.is_punct(";") {
+                // A trailing comma continues the statement on the next line
+                if !(token.kind == JuliaTokenKind::Newline
+                    && tokens[..i].last().is_some_and(|prev| prev.is_punct(",")))
+                {
+                    break;
+                }
+            } else if token.is_punct(":") && !in_items {
+                in_items = true;
+            } else if token.is_punct(",") {
+                if !in_items && !current.is_empty() {
+                    paths.push(std::mem::take(&mut current));
+                }
+                after_as = false;
+            } else if token.kind == JuliaTokenKind::Identifier && token.text == "as" {
+                after_as = true;
+            } else if in_items {
+                if token.kind == JuliaTokenKind::Identifier && !after_as {
+                    items.push(token.text.clone());
+                } else if after_as {
+                    items.push(format!("as {}", token.text));
+                }
+            } else if after_as {
+                items.push(format!("as {}", token.text));
+            } else if token.is_punct(".") || token.kind == JuliaTokenKind::Identifier {
+                current.push_str(&token.text);
+            }
+            i += 1;
+        }
+        if !current.is_empty() {
+            paths.push(current);
+        }
+
+        let count = paths.len();
+        for (position, module) in paths.into_iter().enumerate() {
+            if validate_module_name(module.trim_start_matches('.')).is_err() {
+                continue;
+            }
+            self.imports.push(Import {
+                is_relative: module.starts_with('.'),
+                // Items and aliases belong to the last (and usually only) module
+                items: if position + 1 == count {
+                    items.clone()
+                } else {
+                    Vec::new()
+                },
+                module,
+                line,
+            });
+        }
+        i
+    }
+}
+
+/// A possibly qualified name starting at `i`: `f`, `Base.show`, `Shop.Orders.checkout`.
+/// Returns the last segment, the qualifying module, and the index after the name.
+fn qualified_name(tokens: &[JuliaToken], i: usize) -> Option<(String, Option<String>, usize)> {
+    let first = tokens
+        .get(i)
+        .filter(|token| token.kind == JuliaTokenKind::Identifier)?;
+    let mut segments = vec![first.text.clone()];
+    let mut next = i + 1;
+    while let (Some(dot), Some(segment)) = (tokens.get(next), tokens.get(next + 1)) {
+        if !dot.is_punct(".") || dot.spaced || segment.kind != JuliaTokenKind::Identifier {
+            break;
+        }
+        segments.push(segment.text.clone());
+        next += 2;
+    }
+
+    let name = segments.pop()?;
+    let module = (!segments.is_empty()).then(|| segments.join("."));
+    Some((name, module, next))
+}
+
+/// Whether the call starting at `i` is the signature of a short-form definition:
+/// `area(r) = pi * r^2` or `scale(x::T) where {T} = x`.
+fn is_short_definition(tokens: &[JuliaToken], i: usize) -> bool {
+    let mut depth = 0usize;
+    let mut j = i;
+    while let Some(token) = tokens.get(j) {
+        if token.is_punct("(") || token.is_punct(".(") || token.is_punct("{") {
+            depth += 1;
+        } else if token.is_punct(")") || token.is_punct("}") {
+            depth = depth.saturating_sub(1);
+            if depth == 0 {
+                break;
+            }
+        } else if token.kind == JuliaTokenKind::Newline && depth == 0 {
+            return false;
+        }
+        j += 1;
+    }
+
+    let mut after = tokens.get(j + 1..).unwrap_or_default().iter();
+    match after.next() {
+        Some(token) if token.is_punct("=") => true,
+        Some(token) if token.is(JuliaTokenKind::Identifier, "where") => after
+            .take_while(|token| token.kind != JuliaTokenKind::Newline)
+            .any(|token| token.is_punct("=")),
+        Some(token) if token.is_punct("::") => after
+            .take_while(|token| token.kind != JuliaTokenKind::Newline)
+            .any(|token| token.is_punct("=")),
+        _ => false,
+    }
+}
+
+/// The path of `include("file.jl")` or `include(joinpath(@__DIR__, "dir", "file.jl"))`.
+fn include_path(tokens: &[JuliaToken], i: usize) -> Option<String> {
+    let first = tokens.get(i)?;
+    if first.kind == JuliaTokenKind::Str {
+        return tokens
+            .get(i + 1)
+            .filter(|close| close.is_punct(")"))
+            .map(|_| first.text.clone());
+    }
+
+    if first.text != "joinpath" || !tokens.get(i + 1)?.is_punct("(") {
+        return None;
+    }
+    let mut parts = Vec::new();
+    for token in &tokens[i + 2..] {
+        match token.kind {
+            JuliaTokenKind::Str => parts.push(token.text.clone()),
+            JuliaTokenKind::Identifier if token.text == "@__DIR__" => {}
+            JuliaTokenKind::Punct if token.text == "," => {}
+            JuliaTokenKind::Punct if token.text == ")" => break,
+            _ => return None,
+        }
+    }
+    (!parts.is_empty()).then(|| parts.join("/"))
+}
+
+pub struct JuliaModuleResolver;
+
+impl ModuleResolver for JuliaModuleResolver {
+    fn resolve_import(
+        &self,
+        module_path: &str,
+        from_file: &Path,
+        base_dir: &Path,
+    ) -> Result<ResolvedPath, ContextCreatorError> {
+        // `include` paths are relative to the including file
+        if module_path.ends_with(".jl") {
+            validate_include_path(module_path)?;
+            let candidate = from_file.parent().unwrap_or(base_dir).join(module_path);
+            if candidate.is_file() {
+                return Ok(ResolvedPath {
+                    path: validate_import_path(base_dir, &candidate)?,
+                    is_external: false,
+                    confidence: 0.95,
+                });
+            }
+            return Ok(external_julia_path(from_file, base_dir, 0.3));
+        }
+
+        let relative = module_path.starts_with('.');
+        let module_name = module_path.trim_start_matches('.');
+        validate_module_name(module_name)?;
+        let index = julia_project_index(base_dir);
+
+        if let Some(file) = index.module_file(module_name, relative, from_file) {
+            return Ok(ResolvedPath {
+                path: validate_import_path(base_dir, &file)?,
+                is_external: false,
+                confidence: 0.9,
+            });
+        }
+
+        Ok(external_julia_path(
+            from_file,
+            base_dir,
+            if self.is_external_module(module_name) {
+                0.9
+            } else {
+                0.7
+            },
+        ))
+    }
+
+    fn get_file_extensions(&self) -> Vec<&'static str> {
+        vec!["jl"]
+    }
+
+    fn is_external_module(&self, module_path: &str) -> bool {
+        let root = module_path.split('.').next().unwrap_or(module_path);
+        matches!(
+            root,
+            "Base"
+                | "Core"
+                | "Main"
+                | "LinearAlgebra"
+                | "Statistics"
+                | "Random"
+                | "Dates"
+                | "Printf"
+                | "Test"
+                | "Pkg"
+                | "Distributed"
+                | "SparseArrays"
+                | "Serialization"
+                | "Logging"
+                | "Markdown"
+                | "InteractiveUtils"
+                | "DelimitedFiles"
+                | "Sockets"
+                | "UUIDs"
+                | "TOML"
+                | "DataFrames"
+                | "CSV"
+                | "Plots"
+                | "Makie"
+                | "JuMP"
+                | "Flux"
+                | "DifferentialEquations"
+                | "Distributions"
+                | "StatsBase"
+                | "JSON"
+                | "JSON3"
+                | "HTTP"
+        )
+    }
+}
+
+/// Unresolved packages are declared in the nearest `Project.toml`.
+fn external_julia_path(from_file: &Path, base_dir: &Path, confidence: f32) -> ResolvedPath {
+    let project = from_file
+        .ancestors()
+        .skip(1)
+        .take_while(|dir| dir.starts_with(base_dir))
+        .flat_map(|dir| [dir.join("Project.toml"), dir.join("JuliaProject.toml")])
+        .find(|path| path.is_file())
+        .unwrap_or_else(|| base_dir.to_path_buf());
+
+    ResolvedPath {
+        path: project,
+        is_external: true,
+        confidence,
+    }
+}
+
+/// Included paths may climb with `../`; containment is checked after resolution.
+fn validate_include_path(include: &str) -> Result<(), ContextCreatorError> {
+    let path = Path::new(include);
+    if include.is_empty()
+        || include.len() > 255
+        || include.contains('\0')
+        || path.is_absolute()
+        || path
+            .components()
+            .any(|component| matches!(component, Component::Prefix(_) | Component::RootDir))
+    {
+        return Err(ContextCreatorError::SecurityError(format!(
+            "Invalid include path: {include}"
+        )));
+    }
+
+    Ok(())
+}
+
+/// The `name` of a Julia `Project.toml`.
+fn julia_project_name(project: &str) -> Option<String> {
+    project.lines().find_map(|line| {
+        let value = line.trim().strip_prefix("name")?.trim().strip_prefix('=')?;
+        let name = value.trim().trim_matches('"');
+        (!name.is_empty()).then(|| name.to_string())
+    })
+}
+
+/// Modules, includes, and types of every Julia file in the project.
+#[derive(Debug, Default)]
+struct JuliaProjectIndex {
+    /// Package name from `Project.toml` to its `src/<Name>.jl` entry file.
+    packages: HashMap<String, PathBuf>,
+    /// Module name to the files declaring it, in path order.
+    modules: HashMap<String, Vec<PathBuf>>,
+    /// File to the files it includes.
+    includes: HashMap<PathBuf, Vec<PathBuf>>,
+    declared_types: HashMap<PathBuf, HashSet<String>>,
+}
+
+impl JuliaProjectIndex {
+    fn build(base_dir: &Path) -> Self {
+        let mut index = Self::default();
+
+        let walker = walkdir::WalkDir::new(base_dir)
+            .max_depth(16)
+            .sort_by_file_name()
+            .into_iter()
+            .filter_entry(|entry| {
+                entry.depth() == 0
+                    || !entry.file_name().to_str().is_some_and(|name| {
+                        name.starts_with('.') || JULIA_SKIPPED_DIRS.contains(&name)
+                    })
+            });
+
+        for entry in walker.flatten() {
+            let path = entry.path();
+            if !entry.file_type().is_file() {
+                continue;
+            }
+            let file_name = entry.file_name().to_str().unwrap_or_default();
+            if file_name == "Project.toml" || file_name == "JuliaProject.toml" {
+                let name = fs::read_to_string(path)
+                    .ok()
+                    .and_then(|project| julia_project_name(&project));
+                if let (Some(name), Some(root)) = (name, path.parent()) {
+                    let entry_file = root.join("src").join(format!("{name}.jl"));
+                    if entry_file.is_file() {
+                        let entry_file = entry_file.canonicalize().unwrap_or(entry_file);
+                        index.packages.entry(name).or_insert(entry_file);
+                    }
+                }
+                continue;
+            }
+            if path.extension().and_then(|ext| ext.to_str()) != Some("jl") {
+                continue;
+            }
+            let Ok(content) = fs::read_to_string(path) else {
+                continue;
+            };
+
+            let file = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+            let source = JuliaSource::scan(&julia_tokens(&content));
+            for module in source.modules {
+                index.modules.entry(module).or_default().push(file.clone());
+            }
+            let includes: Vec<PathBuf> = source
+                .imports
+                .iter()
+                .filter(|import| import.module.ends_with(".jl"))
+                .filter(|import| validate_include_path(&import.module).is_ok())
+                .filter_map(|import| file.parent()?.join(&import.module).canonicalize().ok())
+                .collect();
+            index.includes.insert(file.clone(), includes);
+            index.declared_types.insert(file, source.declared_types);
+        }
+
+        index
+    }
+
+    /// The file declaring a module. Packages of the project resolve to their entry
+    /// file; submodules (`using .Sub`, `import Pkg.Sub`) to the file declaring the
+    /// last segment, preferring declarations closest to the importing file.
+    fn module_file(&self, module: &str, relative: bool, from_file: &Path) -> Option<PathBuf> {
+        let segments: Vec<&str> = module.split('.').collect();
+        if !relative {
+            let package = self.packages.get(*segments.first()?)?;
+            if segments.len() == 1 {
+                return Some(package.clone());
+            }
+        }
+
+        let name = segments.last()?;
+        let from_file = from_file
+            .canonicalize()
+            .unwrap_or_else(|_| from_file.to_path_buf());
+        self.modules
+            .get(*name)?
+            .iter()
+            .max_by_key(|file| {
+                file.components()
+                    .zip(from_file.components())
+                    .take_while(|(left, right)| left == right)
+                    .count()
+            })
+            .cloned()
+    }
+
+    /// A module file followed by every file it includes, transitively.
+    fn module_files(&self, module_file: &Path) -> Vec<PathBuf> {
+        let mut pending = vec![module_file.to_path_buf()];
+        let mut files = Vec::new();
+        while let Some(file) = pending.pop() {
+            if files.contains(&file) {
+                continue;
+            }
+            pending.extend(self.includes.get(&file).into_iter().flatten().cloned());
+            files.push(file);
+        }
+        files
+    }
+
+    /// The file declaring a type within a module file or the files it includes.
+    fn type_file(&self, type_name: &str, module_file: &Path) -> Option<PathBuf> {
+        self.module_files(module_file).into_iter().find(|file| {
+            self.declared_types
+                .get(file)
+                .is_some_and(|types| types.contains(type_name))
+        })
+    }
+
+    /// Types of sibling files included into the same module as `file`.
+    fn type_file_near(&self, type_name: &str, file: &Path) -> Option<PathBuf> {
+        self.includes
+            .iter()
+            .filter(|(_, included)| included.iter().any(|included| included == file))
+            .find_map(|(parent, _)| self.type_file(type_name, parent))
+    }
+}
+
+fn julia_index_cache() -> &'static Mutex<HashMap<PathBuf, Arc<JuliaProjectIndex>>> {
+    static CACHE: OnceLock<Mutex<HashMap<PathBuf, Arc<JuliaProjectIndex>>>> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn julia_project_index(base_dir: &Path) -> Arc<JuliaProjectIndex> {
+    if let Some(index) = julia_index_cache()
+        .lock()
+        .ok()
+        .and_then(|cache| cache.get(base_dir).cloned())
+    {
+        return index;
+    }
+
+    let index = Arc::new(JuliaProjectIndex::build(base_dir));
+    if let Ok(mut cache) = julia_index_cache().lock() {
+        cache.insert(base_dir.to_path_buf(), index.clone());
+    }
+    index
+}
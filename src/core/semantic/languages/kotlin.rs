@@ -1,23 +1,36 @@
-//! Semantic analyzer for Kotlin
+//! Semantic analyzer for Kotlin and Gradle source sets, including mixed Kotlin/Java modules
 
-use crate::core::semantic::analyzer::{
-    AnalysisResult, LanguageAnalyzer, SemanticContext, SemanticResult,
+use crate::core::semantic::{
+    analyzer::{AnalysisResult, Import, LanguageAnalyzer, SemanticContext, SemanticResult},
+    languages::{is_capitalized_type_name, java::java_package_name},
+    path_validator::{validate_import_path, validate_module_name},
+    query_engine::QueryEngine,
+    resolver::{ModuleResolver, ResolvedPath},
 };
-use std::path::Path;
-use tree_sitter::Parser;
+use crate::utils::error::ContextCreatorError;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+use tree_sitter::{Node, Parser};
+
+/// Build files that mark a Gradle module directory.
+const KOTLIN_BUILD_FILES: &[&str] = &["build.gradle.kts", "build.gradle", "pom.xml"];
+
+/// Directories holding build output or tooling state rather than sources.
+const KOTLIN_SKIPPED_DIRS: &[&str] = &["build", "out", "target", "node_modules"];
 
 #[allow(clippy::new_without_default)]
 pub struct KotlinAnalyzer {
-    #[allow(dead_code)]
-    parser: Parser,
+    query_engine: QueryEngine,
 }
 
 impl KotlinAnalyzer {
     pub fn new() -> Self {
-        let parser = Parser::new();
-        // Note: tree_sitter_kotlin::language() would be used here when the crate is added
-        // parser.set_language(tree_sitter_kotlin::language()).unwrap();
-        Self { parser }
+        let language = tree_sitter_kotlin::language();
+        let query_engine =
+            QueryEngine::new(language, "kotlin").expect("Failed to create Kotlin query engine");
+        Self { query_engine }
     }
 }
 
@@ -28,19 +41,438 @@ impl LanguageAnalyzer for KotlinAnalyzer {
 
     fn analyze_file(
         &self,
-        _path: &Path,
-        _content: &str,
-        _context: &SemanticContext,
+        path: &Path,
+        content: &str,
+        context: &SemanticContext,
     ) -> SemanticResult<AnalysisResult> {
-        // TODO: Implement Kotlin analysis
-        Ok(AnalysisResult::default())
+        let mut parser = Parser::new();
+        parser
+            .set_language(tree_sitter_kotlin::language())
+            .map_err(|e| ContextCreatorError::ParseError(format!("Failed to set language: {e}")))?;
+
+        let mut result = self
+            .query_engine
+            .analyze_with_parser(&mut parser, content)?;
+
+        // Receiver calls match both the named-receiver and the generic navigation pattern, and
+        // constructor calls and receivers are captured as candidate types
+        result.dedupe_function_calls(|_| true);
+        result.dedupe_type_references(|type_ref| is_capitalized_type_name(&type_ref.name));
+
+        let index = jvm_declaration_index(&context.base_dir);
+        self.expand_star_imports(&mut result, path, &index);
+        self.resolve_kotlin_type_definitions(&mut result, path, content, &context.base_dir);
+
+        Ok(result)
     }
 
     fn can_handle_extension(&self, extension: &str) -> bool {
-        extension == "kt" || extension == "kts"
+        matches!(extension, "kt" | "kts")
     }
 
     fn supported_extensions(&self) -> Vec<&'static str> {
         vec!["kt", "kts"]
     }
+
+    fn query_engine(&self) -> Option<&QueryEngine> {
+        Some(&self.query_engine)
+    }
+}
+
+impl KotlinAnalyzer {
+    /// Replace `import com.acme.domain.*` with the declarations this file actually uses,
+    /// so import tracing does not pull in an arbitrary file of the package.
+    fn expand_star_imports(
+        &self,
+        result: &mut AnalysisResult,
+        path: &Path,
+        index: &JvmDeclarationIndex,
+    ) {
+        let explicitly_imported: HashSet<String> = result
+            .imports
+            .iter()
+            .filter(|import| import.items != ["*"])
+            .filter_map(|import| import.module.rsplit('.').next().map(str::to_string))
+            .collect();
+        let mut referenced: Vec<String> = result
+            .type_references
+            .iter()
+            .map(|type_ref| type_ref.name.clone())
+            .chain(
+                result
+                    .function_calls
+                    .iter()
+                    .filter(|call| call.module.is_none())
+                    .map(|call| call.name.clone()),
+            )
+            .filter(|name| !explicitly_imported.contains(name))
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        referenced.sort();
+
+        let mut imports = Vec::with_capacity(result.imports.len());
+        for import in result.imports.drain(..) {
+            if import.items != ["*"] {
+                imports.push(import);
+                continue;
+            }
+
+            let expanded: Vec<Import> = referenced
+                .iter()
+                .filter(|name| {
+                    index
+                        .declaration_file(&format!("{}.{name}", import.module), path)
+                        .is_some()
+                })
+                .map(|name| Import {
+                    module: format!("{}.{name}", import.module),
+                    items: vec![name.clone()],
+                    is_relative: false,
+                    line: import.line,
+                })
+                .collect();
+
+            if expanded.is_empty() {
+                imports.push(import);
+            } else {
+                imports.extend(expanded);
+            }
+        }
+
+        result.imports = imports;
+    }
+
+    /// Resolve types through explicit and aliased imports, the file's own package (which
+    /// Kotlin imports implicitly), and star-imported packages, in that order.
+    fn resolve_kotlin_type_definitions(
+        &self,
+        result: &mut AnalysisResult,
+        current_file: &Path,
+        content: &str,
+        project_root: &Path,
+    ) {
+        let index = jvm_declaration_index(project_root);
+        let mut imported: HashMap<String, String> = HashMap::new();
+        let mut star_packages = Vec::new();
+        for import in &result.imports {
+            match import.items.first().map(String::as_str) {
+                Some("*") => star_packages.push(import.module.clone()),
+                Some(item) => {
+                    let name = item.strip_prefix("as ").unwrap_or(item);
+                    imported.insert(name.to_string(), import.module.clone());
+                }
+                None => {}
+            }
+        }
+        let current_package = java_package_name(content);
+        let current_file = current_file
+            .canonicalize()
+            .unwrap_or_else(|_| current_file.to_path_buf());
+
+        for type_ref in &mut result.type_references {
+            if type_ref.definition_path.is_some() || type_ref.is_external {
+                continue;
+            }
+
+            let mut candidates = Vec::new();
+            if let Some(module) = type_ref.module.as_deref() {
+                let qualifier = imported.get(module).map_or(module, String::as_str);
+                candidates.push(format!("{qualifier}.{}", type_ref.name));
+            } else if let Some(path) = imported.get(&type_ref.name) {
+                candidates.push(path.clone());
+            } else {
+                if let Some(package) = current_package.as_deref() {
+                    candidates.push(format!("{package}.{}", type_ref.name));
+                } else {
+                    candidates.push(type_ref.name.clone());
+                }
+                for package in &star_packages {
+                    candidates.push(format!("{package}.{}", type_ref.name));
+                }
+            }
+
+            let definition = candidates
+                .iter()
+                .find_map(|path| index.declaration_file(path, &current_file))
+                .filter(|file| file != &current_file);
+
+            match definition {
+                Some(file) => match validate_import_path(project_root, &file) {
+                    Ok(validated) => type_ref.definition_path = Some(validated),
+                    Err(_) => type_ref.is_external = true,
+                },
+                None if type_ref.module.is_none()
+                    && imported
+                        .get(&type_ref.name)
+                        .is_some_and(|path| KotlinModuleResolver.is_external_module(path)) =>
+                {
+                    type_ref.is_external = true;
+                    type_ref.external_package = imported.get(&type_ref.name).cloned();
+                }
+                None => {}
+            }
+        }
+    }
+}
+
+pub struct KotlinModuleResolver;
+
+impl ModuleResolver for KotlinModuleResolver {
+    fn resolve_import(
+        &self,
+        module_path: &str,
+        from_file: &Path,
+        base_dir: &Path,
+    ) -> Result<ResolvedPath, ContextCreatorError> {
+        let module_path = module_path.trim().trim_end_matches(".*");
+        validate_module_name(module_path)?;
+
+        let index = jvm_declaration_index(base_dir);
+        if let Some(file) = index.declaration_file(module_path, from_file) {
+            return resolved_kotlin_path(file, base_dir, 0.95);
+        }
+        if let Some(file) = index.package_file(module_path, from_file) {
+            return resolved_kotlin_path(file, base_dir, 0.7);
+        }
+
+        Ok(ResolvedPath {
+            path: kotlin_build_file(from_file, base_dir),
+            is_external: true,
+            confidence: if self.is_external_module(module_path) {
+                0.9
+            } else {
+                0.5
+            },
+        })
+    }
+
+    fn get_file_extensions(&self) -> Vec<&'static str> {
+        vec!["kt", "kts", "java"]
+    }
+
+    fn is_external_module(&self, module_path: &str) -> bool {
+        [
+            "kotlin.",
+            "kotlinx.",
+            "java.",
+            "javax.",
+            "jakarta.",
+            "android.",
+            "androidx.",
+            "org.jetbrains.",
+            "com.google.android.",
+        ]
+        .iter()
+        .any(|prefix| module_path.starts_with(prefix))
+    }
+}
+
+fn resolved_kotlin_path(
+    path: PathBuf,
+    base_dir: &Path,
+    confidence: f32,
+) -> Result<ResolvedPath, ContextCreatorError> {
+    Ok(ResolvedPath {
+        path: validate_import_path(base_dir, &path)?,
+        is_external: false,
+        confidence,
+    })
+}
+
+fn kotlin_build_file(from_file: &Path, base_dir: &Path) -> PathBuf {
+    from_file
+        .ancestors()
+        .skip(1)
+        .take_while(|dir| dir.starts_with(base_dir))
+        .flat_map(|dir| KOTLIN_BUILD_FILES.iter().map(move |file| dir.join(file)))
+        .find(|path| path.is_file())
+        .unwrap_or_else(|| base_dir.join("build.gradle.kts"))
+}
+
+/// Top-level Kotlin declarations and Java classes of every source set in the project.
+/// Kotlin files may declare any number of classes and functions and need not live in a
+/// directory matching their package, so lookups go through declarations, not paths.
+#[derive(Debug, Default)]
+struct JvmDeclarationIndex {
+    /// Fully-qualified declaration name to the files declaring it. Multiplatform
+    /// `expect`/`actual` pairs declare the same name in several source sets.
+    declarations: HashMap<String, Vec<PathBuf>>,
+    /// Package name to the files in it.
+    packages: HashMap<String, Vec<PathBuf>>,
+}
+
+impl JvmDeclarationIndex {
+    fn build(base_dir: &Path) -> Self {
+        let mut index = Self::default();
+        let mut parser = Parser::new();
+        let has_kotlin_parser = parser.set_language(tree_sitter_kotlin::language()).is_ok();
+
+        let walker = walkdir::WalkDir::new(base_dir)
+            .max_depth(16)
+            .sort_by_file_name()
+            .into_iter()
+            .filter_entry(|entry| {
+                entry.depth() == 0
+                    || !entry.file_name().to_str().is_some_and(|name| {
+                        name.starts_with('.') || KOTLIN_SKIPPED_DIRS.contains(&name)
+                    })
+            });
+
+        for entry in walker.flatten() {
+            if !entry.file_type().is_file() {
+                continue;
+            }
+            let path = entry.path();
+            let extension = path.extension().and_then(|ext| ext.to_str());
+            if !matches!(extension, Some("kt" | "java")) {
+                continue;
+            }
+            let Ok(content) = fs::read_to_string(path) else {
+                continue;
+            };
+            let file = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+            let package = java_package_name(&content).unwrap_or_default();
+            let qualify = |name: &str| {
+                if package.is_empty() {
+                    name.to_string()
+                } else {
+                    format!("{package}.{name}")
+                }
+            };
+
+            let mut names = Vec::new();
+            if extension == Some("java") {
+                // One public top-level class per Java file, named after the file
+                if let Some(stem) = path.file_stem().and_then(|stem| stem.to_str()) {
+                    names.push(stem.to_string());
+                }
+            } else if has_kotlin_parser {
+                if let Some(tree) = parser.parse(&content, None) {
+                    collect_kotlin_declarations(tree.root_node(), &content, &mut names);
+                }
+                // Java sees top-level functions through the `FileNameKt` facade class
+                if let Some(stem) = path.file_stem().and_then(|stem| stem.to_str()) {
+                    names.push(format!("{stem}Kt"));
+                }
+            }
+
+            for name in names {
+                let files = index.declarations.entry(qualify(&name)).or_default();
+                if !files.contains(&file) {
+                    files.push(file.clone());
+                }
+            }
+            index.packages.entry(package).or_default().push(file);
+        }
+
+        index
+    }
+
+    /// The file declaring a fully-qualified name, trying the longest prefix first so
+    /// nested classes (`Outer.Inner`) and members land on the outer declaration.
+    fn declaration_file(&self, qualified_name: &str, from_file: &Path) -> Option<PathBuf> {
+        let segments: Vec<&str> = qualified_name.split('.').collect();
+        (1..=segments.len()).rev().find_map(|length| {
+            let is_full_name = length == segments.len();
+            if !is_full_name && !segments[length - 1].starts_with(|c: char| c.is_uppercase()) {
+                return None;
+            }
+            let files = self.declarations.get(&segments[..length].join("."))?;
+            closest_file(files, from_file)
+        })
+    }
+
+    fn package_file(&self, package: &str, from_file: &Path) -> Option<PathBuf> {
+        closest_file(self.packages.get(package)?, from_file)
+    }
+}
+
+/// Prefer the candidate sharing the longest path with the importing file, which keeps
+/// lookups inside the same source set (`jvmMain` before `commonMain`) and Gradle module.
+pub(crate) fn closest_file(files: &[PathBuf], from_file: &Path) -> Option<PathBuf> {
+    let from_file = from_file
+        .canonicalize()
+        .unwrap_or_else(|_| from_file.to_path_buf());
+    files
+        .iter()
+        .max_by_key(|file| {
+            let shared = file
+                .components()
+                .zip(from_file.components())
+                .take_while(|(left, right)| left == right)
+                .count();
+            (shared, std::cmp::Reverse((*file).clone()))
+        })
+        .cloned()
+}
+
+/// Names of top-level classes, objects, interfaces, type aliases, functions, and properties.
+fn collect_kotlin_declarations(root: Node<'_>, content: &str, names: &mut Vec<String>) {
+    let mut cursor = root.walk();
+    for child in root.named_children(&mut cursor) {
+        let name_kind = match child.kind() {
+            "class_declaration" | "object_declaration" | "type_alias" => "type_identifier",
+            "function_declaration" => "simple_identifier",
+            "property_declaration" => "variable_declaration",
+            _ => continue,
+        };
+
+        let mut child_cursor = child.walk();
+        let Some(name_node) = child
+            .named_children(&mut child_cursor)
+            .find(|node| node.kind() == name_kind)
+        else {
+            continue;
+        };
+        let name_node = if name_kind == "variable_declaration" {
+            let mut variable_cursor = name_node.walk();
+            let identifier = name_node
+                .named_children(&mut variable_cursor)
+                .find(|node| node.kind() == "simple_identifier");
+            match identifier {
+                Some(identifier) => identifier,
+                None => continue,
+            }
+        } else {
+            name_node
+        };
+
+        if let Ok(name) = name_node.utf8_text(content.as_bytes()) {
+            names.push(name.to_string());
+        }
+    }
+}
+
+fn jvm_index_cache() -> &'static Mutex<HashMap<PathBuf, Arc<JvmDeclarationIndex>>> {
+    static CACHE: OnceLock<Mutex<HashMap<PathBuf, Arc<JvmDeclarationIndex>>>> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn jvm_declaration_index(base_dir: &Path) -> Arc<JvmDeclarationIndex> {
+    if let Some(index) = jvm_index_cache()
+        .lock()
+        .ok()
+        .and_then(|cache| cache.get(base_dir).cloned())
+    {
+        return index;
+    }
+
+    let index = Arc::new(JvmDeclarationIndex::build(base_dir));
+    if let Ok(mut cache) = jvm_index_cache().lock() {
+        cache.insert(base_dir.to_path_buf(), index.clone());
+    }
+    index
+}
+
+/// The Kotlin file declaring a class Java code refers to, including the `FileNameKt`
+/// facades that hold top-level functions.
+pub(crate) fn kotlin_declaration_file(
+    class_path: &str,
+    from_file: &Path,
+    base_dir: &Path,
+) -> Option<PathBuf> {
+    jvm_declaration_index(base_dir)
+        .declaration_file(class_path, from_file)
+        .filter(|file| file.extension().and_then(|ext| ext.to_str()) == Some("kt"))
 }
@@ -1,24 +1,41 @@
-//! Semantic analyzer for Lua
+//! Semantic analyzer for Lua, covering `package.path` search templates, LuaRocks
+//! rockspecs, and the Neovim `lua/` runtime directory
 
-use crate::core::semantic::analyzer::{
-    AnalysisResult, LanguageAnalyzer, SemanticContext, SemanticResult,
+use crate::core::semantic::{
+    analyzer::{AnalysisResult, LanguageAnalyzer, SemanticContext, SemanticResult},
+    path_validator::{validate_import_path, validate_module_name},
+    query_engine::QueryEngine,
+    resolver::{ModuleResolver, ResolvedPath},
 };
-use std::path::Path;
-use tree_sitter::Parser;
+use crate::utils::error::ContextCreatorError;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+use tree_sitter::{Node, Parser};
+
+/// `package.path` templates used when the project does not configure its own.
+const DEFAULT_LUA_PATH: &[&str] = &["?.lua", "?/init.lua"];
+
+/// Neovim loads plugin modules from `lua/` on the runtimepath, and LuaRocks projects
+/// conventionally keep theirs under `src/`.
+const CONVENTIONAL_LUA_PATH: &[&str] =
+    &["lua/?.lua", "lua/?/init.lua", "src/?.lua", "src/?/init.lua"];
+
+/// Directories holding installed rocks, build output, or editor state.
+const LUA_SKIPPED_DIRS: &[&str] = &["lua_modules", "node_modules", "build", "dist"];
 
 #[allow(clippy::new_without_default)]
 pub struct LuaAnalyzer {
-    #[allow(dead_code)]
-    #[allow(dead_code)]
-    parser: Parser,
+    query_engine: QueryEngine,
 }
 
 impl LuaAnalyzer {
     pub fn new() -> Self {
-        let parser = Parser::new();
-        // Note: tree_sitter_lua::language() would be used here when the crate is added
-        // parser.set_language(tree_sitter_lua::language()).unwrap();
-        Self { parser }
+        let language = tree_sitter_lua::language();
+        let query_engine =
+            QueryEngine::new(language, "lua").expect("Failed to create Lua query engine");
+        Self { query_engine }
     }
 }
 
@@ -29,12 +46,41 @@ impl LanguageAnalyzer for LuaAnalyzer {
 
     fn analyze_file(
         &self,
-        _path: &Path,
-        _content: &str,
-        _context: &SemanticContext,
+        path: &Path,
+        content: &str,
+        context: &SemanticContext,
     ) -> SemanticResult<AnalysisResult> {
-        // TODO: Implement Lua analysis
-        Ok(AnalysisResult::default())
+        let mut parser = Parser::new();
+        parser
+            .set_language(tree_sitter_lua::language())
+            .map_err(|e| ContextCreatorError::ParseError(format!("Failed to set language: {e}")))?;
+
+        let tree = parser
+            .parse(content, None)
+            .ok_or_else(|| ContextCreatorError::ParseError("Failed to parse file".to_string()))?;
+        let mut bindings = HashMap::new();
+        collect_require_bindings(tree.root_node(), content, &mut bindings);
+
+        let mut result = self
+            .query_engine
+            .analyze_with_parser(&mut parser, content)?;
+
+        result
+            .function_calls
+            .retain(|call| call.module.is_some() || call.name != "require");
+
+        for import in &mut result.imports {
+            import.items = bindings
+                .iter()
+                .filter(|(_, module)| **module == import.module)
+                .map(|(name, _)| name.clone())
+                .collect();
+            import.items.sort();
+        }
+
+        self.resolve_module_references(&mut result, path, &bindings, &context.base_dir);
+
+        Ok(result)
     }
 
     fn can_handle_extension(&self, extension: &str) -> bool {
@@ -44,4 +90,363 @@ impl LanguageAnalyzer for LuaAnalyzer {
     fn supported_extensions(&self) -> Vec<&'static str> {
         vec!["lua"]
     }
+
+    fn query_engine(&self) -> Option<&QueryEngine> {
+        Some(&self.query_engine)
+    }
+}
+
+impl LuaAnalyzer {
+    /// Replace names bound by `local x = require("a.b")` with the module they hold, so
+    /// `x.run()` is recorded as a call into `a.b` and `X.new()` as a use of its table.
+    fn resolve_module_references(
+        &self,
+        result: &mut AnalysisResult,
+        path: &Path,
+        bindings: &HashMap<String, String>,
+        project_root: &Path,
+    ) {
+        for call in &mut result.function_calls {
+            if let Some(module) = call.module.as_deref().and_then(|name| bindings.get(name)) {
+                call.module = Some(module.clone());
+            }
+        }
+
+        let resolver = LuaModuleResolver;
+        for type_ref in &mut result.type_references {
+            let Some(module) = bindings.get(&type_ref.name) else {
+                continue;
+            };
+            type_ref.module = Some(module.clone());
+            match resolver.resolve_import(module, path, project_root) {
+                Ok(resolved) if !resolved.is_external => {
+                    type_ref.definition_path = Some(resolved.path);
+                }
+                _ => {
+                    type_ref.is_external = true;
+                    type_ref.external_package = Some(module.clone());
+                }
+            }
+        }
+
+        // Globals defined in another file, e.g. `Player = {}` loaded by main.lua
+        let _ = self.query_engine.resolve_type_definitions(
+            &mut result.type_references,
+            path,
+            project_root,
+        );
+        let current_file = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+        result.type_references.retain(|type_ref| {
+            type_ref.is_external
+                || type_ref
+                    .definition_path
+                    .as_ref()
+                    .is_some_and(|definition| *definition != current_file)
+        });
+    }
+}
+
+/// Names assigned the result of a `require` call: `local util = require("plugin.util")`.
+fn collect_require_bindings(node: Node<'_>, content: &str, bindings: &mut HashMap<String, String>) {
+    let mut cursor = node.walk();
+    for child in node.named_children(&mut cursor) {
+        if child.kind() != "assignment_statement" {
+            collect_require_bindings(child, content, bindings);
+            continue;
+        }
+
+        let mut child_cursor = child.walk();
+        let parts: Vec<_> = child.named_children(&mut child_cursor).collect();
+        let name = parts
+            .iter()
+            .find(|part| part.kind() == "variable_list")
+            .and_then(|list| list.named_child(0))
+            .filter(|name| name.kind() == "identifier")
+            .and_then(|name| name.utf8_text(content.as_bytes()).ok());
+        let module = parts
+            .iter()
+            .find(|part| part.kind() == "expression_list")
+            .and_then(|list| list.named_child(0))
+            .and_then(|value| required_module(value, content));
+
+        if let (Some(name), Some(module)) = (name, module) {
+            bindings.insert(name.to_string(), module);
+        }
+        collect_require_bindings(child, content, bindings);
+    }
+}
+
+/// The module named by a `require("a.b")` or `require "a.b"` call.
+fn required_module(node: Node<'_>, content: &str) -> Option<String> {
+    if node.kind() != "function_call"
+        || node
+            .child_by_field_name("name")?
+            .utf8_text(content.as_bytes())
+            .ok()?
+            != "require"
+    {
+        return None;
+    }
+
+    let arguments = node.child_by_field_name("arguments")?;
+    let string = if arguments.kind() == "string" {
+        arguments
+    } else {
+        arguments
+            .named_child(0)
+            .filter(|arg| arg.kind() == "string")?
+    };
+    string
+        .child_by_field_name("content")?
+        .utf8_text(content.as_bytes())
+        .ok()
+        .map(str::to_string)
+}
+
+pub struct LuaModuleResolver;
+
+impl ModuleResolver for LuaModuleResolver {
+    fn resolve_import(
+        &self,
+        module_path: &str,
+        from_file: &Path,
+        base_dir: &Path,
+    ) -> Result<ResolvedPath, ContextCreatorError> {
+        validate_module_name(module_path)?;
+        let search_path = lua_search_path(base_dir);
+
+        if let Some(file) = search_path.find_module(module_path, from_file, base_dir) {
+            return Ok(ResolvedPath {
+                path: validate_import_path(base_dir, &file)?,
+                is_external: false,
+                confidence: 0.9,
+            });
+        }
+
+        Ok(ResolvedPath {
+            path: search_path
+                .rockspecs
+                .first()
+                .cloned()
+                .unwrap_or_else(|| base_dir.to_path_buf()),
+            is_external: true,
+            confidence: if self.is_external_module(module_path) {
+                0.9
+            } else {
+                0.5
+            },
+        })
+    }
+
+    fn get_file_extensions(&self) -> Vec<&'static str> {
+        vec!["lua"]
+    }
+
+    fn is_external_module(&self, module_path: &str) -> bool {
+        let root = module_path.split('.').next().unwrap_or(module_path);
+        matches!(
+            root,
+            "string"
+                | "table"
+                | "math"
+                | "io"
+                | "os"
+                | "coroutine"
+                | "debug"
+                | "utf8"
+                | "package"
+                | "bit"
+                | "bit32"
+                | "ffi"
+                | "jit"
+                | "vim"
+                | "lpeg"
+                | "lfs"
+                | "socket"
+                | "ssl"
+                | "cjson"
+                | "luv"
+                | "plenary"
+                | "telescope"
+                | "nvim-treesitter"
+                | "lspconfig"
+                | "love"
+                | "busted"
+                | "luassert"
+                | "penlight"
+                | "pl"
+        )
+    }
+}
+
+/// Where `require` looks for project modules: the configured or default `package.path`
+/// templates, the Neovim and LuaRocks conventions, and modules a rockspec maps to files.
+#[derive(Debug, Default)]
+struct LuaSearchPath {
+    templates: Vec<String>,
+    /// Module name to source file from `build.modules` in the project's rockspecs.
+    rock_modules: HashMap<String, PathBuf>,
+    rockspecs: Vec<PathBuf>,
+}
+
+impl LuaSearchPath {
+    fn build(base_dir: &Path) -> Self {
+        let mut templates = luarc_runtime_path(base_dir)
+            .unwrap_or_else(|| DEFAULT_LUA_PATH.iter().map(|t| t.to_string()).collect());
+        for template in CONVENTIONAL_LUA_PATH {
+            if !templates.iter().any(|existing| existing == template) {
+                templates.push(template.to_string());
+            }
+        }
+
+        let mut search_path = Self {
+            templates,
+            ..Self::default()
+        };
+
+        let walker = walkdir::WalkDir::new(base_dir)
+            .max_depth(4)
+            .sort_by_file_name()
+            .into_iter()
+            .filter_entry(|entry| {
+                entry.depth() == 0
+                    || !entry.file_name().to_str().is_some_and(|name| {
+                        name.starts_with('.') || LUA_SKIPPED_DIRS.contains(&name)
+                    })
+            });
+
+        for entry in walker.flatten() {
+            let path = entry.path();
+            if !entry.file_type().is_file()
+                || path.extension().and_then(|ext| ext.to_str()) != Some("rockspec")
+            {
+                continue;
+            }
+            let Some(rock_dir) = path.parent() else {
+                continue;
+            };
+            let Ok(rockspec) = fs::read_to_string(path) else {
+                continue;
+            };
+            for (module, file) in rockspec_modules(&rockspec) {
+                if is_relative_lua_path(&file) {
+                    search_path
+                        .rock_modules
+                        .entry(module)
+                        .or_insert_with(|| rock_dir.join(file));
+                }
+            }
+            search_path.rockspecs.push(path.to_path_buf());
+        }
+
+        search_path
+    }
+
+    /// `require` is resolved against the working directory rather than the requiring
+    /// file, so templates are tried from the project root first and then from each
+    /// directory between it and the requiring file, which covers nested plugins and games.
+    fn find_module(&self, module: &str, from_file: &Path, base_dir: &Path) -> Option<PathBuf> {
+        if let Some(file) = self.rock_modules.get(module).filter(|file| file.is_file()) {
+            return Some(file.clone());
+        }
+
+        let module_file = module.replace('.', "/");
+        let mut roots: Vec<&Path> = from_file
+            .ancestors()
+            .skip(1)
+            .take_while(|dir| dir.starts_with(base_dir))
+            .collect();
+        roots.reverse();
+        if roots.is_empty() {
+            roots.push(base_dir);
+        }
+
+        roots.into_iter().find_map(|root| {
+            self.templates
+                .iter()
+                .map(|template| root.join(template.replace('?', &module_file)))
+                .find(|candidate| candidate.is_file())
+        })
+    }
+}
+
+/// `runtime.path` from a lua-language-server `.luarc.json`, written either nested or
+/// with dotted keys, as a list or a `;`-separated string.
+fn luarc_runtime_path(base_dir: &Path) -> Option<Vec<String>> {
+    let config = fs::read_to_string(base_dir.join(".luarc.json"))
+        .ok()
+        .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())?;
+
+    let value = [
+        &config["runtime"]["path"],
+        &config["runtime.path"],
+        &config["Lua.runtime.path"],
+        &config["Lua"]["runtime"]["path"],
+    ]
+    .into_iter()
+    .find(|value| !value.is_null())?;
+
+    let entries: Vec<&str> = match value {
+        serde_json::Value::String(path) => path.split(';').collect(),
+        serde_json::Value::Array(paths) => paths.iter().filter_map(|path| path.as_str()).collect(),
+        _ => return None,
+    };
+    let templates: Vec<String> = entries
+        .into_iter()
+        .map(str::trim)
+        .filter(|template| template.contains('?') && is_relative_lua_path(template))
+        .map(str::to_string)
+        .collect();
+
+    (!templates.is_empty()).then_some(templates)
+}
+
+/// `build.modules` entries of a rockspec: `["plugin.util"] = "src/plugin/util.lua"`.
+fn rockspec_modules(rockspec: &str) -> Vec<(String, String)> {
+    rockspec
+        .lines()
+        .filter_map(|line| {
+            let (key, value) = line.trim().strip_prefix('[')?.split_once(']')?;
+            let module = key.trim().trim_matches(['"', '\'']);
+            let file = value
+                .trim()
+                .strip_prefix('=')?
+                .trim()
+                .trim_end_matches(',')
+                .trim_matches(['"', '\'']);
+            (file.ends_with(".lua") && !module.is_empty())
+                .then(|| (module.to_string(), file.to_string()))
+        })
+        .collect()
+}
+
+/// Search templates and rockspec sources must stay inside the project.
+fn is_relative_lua_path(path: &str) -> bool {
+    !path.is_empty()
+        && path.len() <= 255
+        && !path.contains('\0')
+        && Path::new(path)
+            .components()
+            .all(|component| matches!(component, Component::Normal(_) | Component::CurDir))
+}
+
+fn lua_search_path_cache() -> &'static Mutex<HashMap<PathBuf, Arc<LuaSearchPath>>> {
+    static CACHE: OnceLock<Mutex<HashMap<PathBuf, Arc<LuaSearchPath>>>> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn lua_search_path(base_dir: &Path) -> Arc<LuaSearchPath> {
+    if let Some(search_path) = lua_search_path_cache()
+        .lock()
+        .ok()
+        .and_then(|cache| cache.get(base_dir).cloned())
+    {
+        return search_path;
+    }
+
+    let search_path = Arc::new(LuaSearchPath::build(base_dir));
+    if let Ok(mut cache) = lua_search_path_cache().lock() {
+        cache.insert(base_dir.to_path_buf(), search_path.clone());
+    }
+    search_path
 }
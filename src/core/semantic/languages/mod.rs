@@ -1,5 +1,6 @@
 //! Language-specific semantic analyzers
 
+pub mod build_files;
 pub mod c;
 pub mod cpp;
 pub mod csharp;
@@ -7,16 +8,28 @@ pub mod dart;
 pub mod elixir;
 pub mod elm;
 pub mod go;
+pub mod haskell;
+pub mod hcl;
 pub mod java;
 pub mod javascript;
 pub mod julia;
 pub mod kotlin;
 pub mod lua;
+pub mod node;
 pub mod php;
 pub mod python;
 pub mod r;
 pub mod ruby;
 pub mod rust;
 pub mod scala;
+pub mod schema;
+pub mod sfc;
 pub mod swift;
 pub mod typescript;
+
+/// Types are capitalized by convention in the JVM, .NET, and Dart languages; lowercase
+/// captures are packages or values, and single capital letters are almost always type
+/// parameters.
+pub(crate) fn is_capitalized_type_name(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_uppercase()) && name.len() > 1
+}
@@ -0,0 +1,1255 @@
+//! Module resolution shared by the JavaScript and TypeScript resolvers: `tsconfig.json`
+//! `baseUrl` and `paths`, `package.json` entry points and `exports`, npm, Yarn, and
+//! pnpm workspaces, and re-exports of barrel files
+//!
+//! Workspace packages usually point their entry points at build output (`dist/`) that is
+//! not checked in, so targets are mapped back to the sources they were compiled from.
+
+use crate::core::semantic::analyzer::Import;
+use crate::core::semantic::resolver::ModuleResolver;
+use crate::utils::error::ContextCreatorError;
+use std::collections::{HashMap, HashSet};
+use std::ffi::OsString;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+use tree_sitter::{Node, Parser};
+
+/// Directories holding installed dependencies or build output.
+const NODE_SKIPPED_DIRS: &[&str] = &["node_modules", "dist", "build", "coverage"];
+
+/// Directories compilers and bundlers write to, replaced by `src` when mapping an entry
+/// point back to its source.
+const OUTPUT_DIRS: &[&str] = &["dist", "lib", "build", "out", "esm", "cjs", "es", "types"];
+
+/// Conditions of an `exports` entry, in the order they are tried. Source-like conditions
+/// come first so that sources win over build output.
+const EXPORT_CONDITIONS: &[&str] = &[
+    "source",
+    "development",
+    "types",
+    "import",
+    "module",
+    "default",
+    "require",
+    "node",
+    "browser",
+];
+
+/// Fields of a `package.json` naming the package entry point, in the order they are tried.
+const ENTRY_FIELDS: &[&str] = &["source", "types", "typings", "module", "main"];
+
+/// How many re-exports are followed from a barrel file to the module defining a name.
+const MAX_REEXPORT_DEPTH: usize = 16;
+
+/// Relative specifiers may climb with `../`; containment is checked after resolution.
+pub(crate) fn validate_relative_specifier(specifier: &str) -> Result<(), ContextCreatorError> {
+    let path = Path::new(specifier);
+    if specifier.is_empty()
+        || specifier.len() > 255
+        || specifier.contains('\0')
+        || specifier.contains(':')
+        || path.is_absolute()
+        || path
+            .components()
+            .any(|component| matches!(component, Component::Prefix(_) | Component::RootDir))
+    {
+        return Err(ContextCreatorError::SecurityError(format!(
+            "Invalid relative specifier: {specifier}"
+        )));
+    }
+
+    Ok(())
+}
+
+/// Resolve a bare specifier through the `paths` and `baseUrl` of the tsconfig or
+/// jsconfig governing `from_file`, then through the packages of the workspace.
+pub(crate) fn resolve_bare_specifier(
+    specifier: &str,
+    from_file: &Path,
+    base_dir: &Path,
+    extensions: &[&str],
+) -> Option<PathBuf> {
+    if let Some(config) = governing_config(from_file, base_dir) {
+        if let Some(file) = config.resolve(specifier, extensions) {
+            return Some(file);
+        }
+    }
+
+    let (package_name, subpath) = split_package_specifier(specifier)?;
+    let package_dir = workspace_packages(base_dir).get(package_name)?.clone();
+    resolve_package_entry(&package_dir, &subpath, extensions)
+}
+
+/// Resolve a path without or with an extension to a source file: `./format`,
+/// `./format.js` compiled from `format.ts`, a `.d.ts` declaration, or a directory
+/// with a `package.json` or an index file.
+pub(crate) fn resolve_module_file(path: &Path, extensions: &[&str]) -> Option<PathBuf> {
+    let has_known_extension = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| ext == "json" || extensions.contains(&ext));
+    if has_known_extension && path.is_file() {
+        return Some(path.to_path_buf());
+    }
+
+    // TypeScript sources are imported by the name of their compiled output
+    if let Some(ext) = path.extension().and_then(|ext| ext.to_str()) {
+        let sources: &[&str] = match ext {
+            "js" => &["ts", "tsx"],
+            "jsx" => &["tsx"],
+            "mjs" => &["mts"],
+            "cjs" => &["cts"],
+            _ => &[],
+        };
+        if let Some(file) = sources
+            .iter()
+            .map(|source| path.with_extension(source))
+            .find(|candidate| candidate.is_file())
+        {
+            return Some(file);
+        }
+    }
+
+    if let Some(file) = extensions
+        .iter()
+        .map(|ext| with_added_extension(path, ext))
+        .find(|candidate| candidate.is_file())
+    {
+        return Some(file);
+    }
+
+    if path.is_dir() {
+        if path.join("package.json").is_file() {
+            if let Some(file) = resolve_package_entry(path, ".", extensions) {
+                return Some(file);
+            }
+        }
+        return extensions
+            .iter()
+            .map(|ext| path.join(format!("index.{ext}")))
+            .find(|candidate| candidate.is_file());
+    }
+
+    None
+}
+
+/// `foo.service` + `ts` is `foo.service.ts`, where `with_extension` would replace
+/// `.service`.
+fn with_added_extension(path: &Path, extension: &str) -> PathBuf {
+    let mut file = OsString::from(path.as_os_str());
+    file.push(".");
+    file.push(extension);
+    PathBuf::from(file)
+}
+
+/// Split `@scope/name/sub/path` into the package name and the `./sub/path` subpath.
+fn split_package_specifier(specifier: &str) -> Option<(&str, String)> {
+    let mut segments = specifier.splitn(if specifier.starts_with('@') { 3 } else { 2 }, '/');
+    let first = segments.next()?;
+    let name_len = if specifier.starts_with('@') {
+        first.len() + 1 + segments.next()?.len()
+    } else {
+        first.len()
+    };
+    let subpath = match segments.next() {
+        Some(rest) if !rest.is_empty() => format!("./{rest}"),
+        _ => ".".to_string(),
+    };
+    Some((&specifier[..name_len], subpath))
+}
+
+/// The file a package exposes at a subpath (`.` for the package itself), preferring
+/// `exports` over the legacy entry fields.
+fn resolve_package_entry(
+    package_dir: &Path,
+    subpath: &str,
+    extensions: &[&str],
+) -> Option<PathBuf> {
+    let manifest = read_json(&package_dir.join("package.json"))?;
+
+    if let Some(exports) = manifest.get("exports") {
+        return export_targets(exports, subpath)
+            .iter()
+            .find_map(|target| resolve_package_target(package_dir, target, extensions));
+    }
+
+    if subpath == "." {
+        ENTRY_FIELDS
+            .iter()
+            .filter_map(|field| manifest.get(*field).and_then(|value| value.as_str()))
+            .find_map(|target| resolve_package_target(package_dir, target, extensions))
+            .or_else(|| {
+                ["src/index", "index"]
+                    .iter()
+                    .find_map(|entry| resolve_module_file(&package_dir.join(entry), extensions))
+            })
+    } else {
+        resolve_package_target(package_dir, subpath, extensions)
+    }
+}
+
+/// Targets an `exports` field maps a subpath to, best condition first. Subpath keys
+/// may contain one `*`, replaced in the target by what it matched.
+fn export_targets(exports: &serde_json::Value, subpath: &str) -> Vec<String> {
+    let subpath_map = exports
+        .as_object()
+        .filter(|map| map.keys().any(|key| key.starts_with('.')));
+
+    let Some(map) = subpath_map else {
+        // A single export stands for the package root
+        return if subpath == "." {
+            condition_targets(exports)
+        } else {
+            Vec::new()
+        };
+    };
+
+    if let Some(value) = map.get(subpath) {
+        return condition_targets(value);
+    }
+
+    map.iter()
+        .filter_map(|(key, value)| {
+            let (prefix, suffix) = key.split_once('*')?;
+            let matched = subpath.strip_prefix(prefix)?.strip_suffix(suffix)?;
+            Some((prefix.len(), matched.to_string(), value))
+        })
+        .max_by_key(|(prefix_len, _, _)| *prefix_len)
+        .map(|(_, matched, value)| {
+            condition_targets(value)
+                .into_iter()
+                .map(|target| target.replace('*', &matched))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Flatten a target, an array of fallbacks, or nested condition objects.
+fn condition_targets(value: &serde_json::Value) -> Vec<String> {
+    match value {
+        serde_json::Value::String(target) => vec![target.clone()],
+        serde_json::Value::Array(targets) => targets.iter().flat_map(condition_targets).collect(),
+        serde_json::Value::Object(conditions) => EXPORT_CONDITIONS
+            .iter()
+            .filter_map(|condition| conditions.get(*condition))
+            .flat_map(condition_targets)
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Resolve a target inside a package, trying the source it was built from before the
+/// target itself: `./dist/index.d.ts` maps to `src/index.ts`.
+fn resolve_package_target(
+    package_dir: &Path,
+    target: &str,
+    extensions: &[&str],
+) -> Option<PathBuf> {
+    let relative = Path::new(target);
+    // `"main": "."` would lead straight back to the package directory
+    if !is_contained_relative_path(relative)
+        || !relative
+            .components()
+            .any(|component| matches!(component, Component::Normal(_)))
+    {
+        return None;
+    }
+
+    let mut components: Vec<&str> = relative
+        .components()
+        .filter_map(|component| match component {
+            Component::Normal(part) => part.to_str(),
+            _ => None,
+        })
+        .collect();
+    let stripped_output_dir = components
+        .first()
+        .is_some_and(|first| OUTPUT_DIRS.contains(first));
+    while components
+        .first()
+        .is_some_and(|first| OUTPUT_DIRS.contains(first))
+    {
+        components.remove(0);
+    }
+
+    if stripped_output_dir && !components.is_empty() {
+        let file_name = components.pop().unwrap_or_default();
+        let stem = [".d.ts", ".d.mts", ".d.cts", ".js", ".mjs", ".cjs", ".jsx"]
+            .iter()
+            .find_map(|ext| file_name.strip_suffix(ext))
+            .unwrap_or(file_name);
+        let mut source = package_dir.join("src");
+        source.extend(components);
+        if let Some(file) = resolve_module_file(&source.join(stem), extensions) {
+            return Some(file);
+        }
+    }
+
+    resolve_module_file(&package_dir.join(relative), extensions)
+}
+
+/// Paths from configuration must stay below the directory declaring them.
+fn is_contained_relative_path(path: &Path) -> bool {
+    path.components()
+        .all(|component| matches!(component, Component::Normal(_) | Component::CurDir))
+}
+
+/// Module resolution options of a tsconfig or jsconfig after following `extends`.
+#[derive(Debug, Default, Clone)]
+struct CompilerPaths {
+    base_url: Option<PathBuf>,
+    paths: Option<PathAliases>,
+}
+
+/// Alias patterns and their targets, with the directory targets are relative to.
+#[derive(Debug, Clone)]
+struct PathAliases {
+    dir: PathBuf,
+    patterns: Vec<(String, Vec<String>)>,
+}
+
+/// A tsconfig or jsconfig with the project references it lists.
+#[derive(Debug, Default)]
+struct NodeConfig {
+    dir: PathBuf,
+    compiler: CompilerPaths,
+    /// Directories named by `include`, or `None` when the config covers its directory.
+    include_dirs: Option<Vec<PathBuf>>,
+    references: Vec<PathBuf>,
+}
+
+impl NodeConfig {
+    fn covers(&self, file: &Path) -> bool {
+        match &self.include_dirs {
+            Some(dirs) => dirs.iter().any(|dir| file.starts_with(dir)),
+            None => file.starts_with(&self.dir),
+        }
+    }
+
+    /// Map a specifier through `paths`, then try it below `baseUrl`.
+    fn resolve(&self, specifier: &str, extensions: &[&str]) -> Option<PathBuf> {
+        if let Some(aliases) = &self.compiler.paths {
+            let root = self.compiler.base_url.as_deref().unwrap_or(&aliases.dir);
+            let best = aliases
+                .patterns
+                .iter()
+                .filter_map(|(pattern, targets)| match pattern.split_once('*') {
+                    None => (pattern == specifier).then(|| (pattern.len(), String::new(), targets)),
+                    Some((prefix, suffix)) => {
+                        let matched = specifier.strip_prefix(prefix)?.strip_suffix(suffix)?;
+                        Some((prefix.len(), matched.to_string(), targets))
+                    }
+                })
+                .max_by_key(|(prefix_len, _, _)| *prefix_len);
+
+            if let Some((_, matched, targets)) = best {
+                if let Some(file) = targets.iter().find_map(|target| {
+                    let target = target.replace('*', &matched);
+                    let relative = Path::new(&target);
+                    if relative.is_absolute()
+                        || relative
+                            .components()
+                            .any(|component| matches!(component, Component::Prefix(_)))
+                    {
+                        return None;
+                    }
+                    resolve_module_file(&root.join(relative), extensions)
+                }) {
+                    return Some(file);
+                }
+            }
+        }
+
+        let base_url = self.compiler.base_url.as_ref()?;
+        if !is_contained_relative_path(Path::new(specifier)) {
+            return None;
+        }
+        resolve_module_file(&base_url.join(specifier), extensions)
+    }
+}
+
+/// The config that compiles `from_file`: the nearest `tsconfig.json` or
+/// `jsconfig.json`, or the project it references that includes the file, as in a
+/// solution-style config listing `tsconfig.app.json` and `tsconfig.node.json`.
+fn governing_config(from_file: &Path, base_dir: &Path) -> Option<Arc<NodeConfig>> {
+    let nearest = from_file
+        .ancestors()
+        .skip(1)
+        .take_while(|dir| dir.starts_with(base_dir))
+        .flat_map(|dir| [dir.join("tsconfig.json"), dir.join("jsconfig.json")])
+        .find(|config| config.is_file())?;
+    let config = node_config(&nearest, base_dir)?;
+
+    let referenced = config
+        .references
+        .iter()
+        .filter_map(|reference| node_config(reference, base_dir))
+        .find(|reference| reference.covers(from_file));
+    Some(referenced.unwrap_or(config))
+}
+
+/// Read a config, following `extends` chains. Options of the extending config win;
+/// `paths` stay relative to the config that declared them.
+fn load_node_config(path: &Path, base_dir: &Path, depth: usize) -> Option<NodeConfig> {
+    let json = read_json(path)?;
+    let dir = path.parent()?.to_path_buf();
+
+    let mut compiler = CompilerPaths::default();
+    if depth < 8 {
+        let parents = match json.get("extends") {
+            Some(serde_json::Value::String(parent)) => vec![parent.clone()],
+            Some(serde_json::Value::Array(parents)) => parents
+                .iter()
+                .filter_map(|parent| parent.as_str().map(str::to_string))
+                .collect(),
+            _ => Vec::new(),
+        };
+        for parent in parents {
+            let Some(parent_path) = extended_config_path(&parent, &dir, base_dir) else {
+                continue;
+            };
+            if let Some(parent) = load_node_config(&parent_path, base_dir, depth + 1) {
+                if parent.compiler.base_url.is_some() {
+                    compiler.base_url = parent.compiler.base_url;
+                }
+                if parent.compiler.paths.is_some() {
+                    compiler.paths = parent.compiler.paths;
+                }
+            }
+        }
+    }
+
+    let options = json.get("compilerOptions");
+    if let Some(base_url) = options
+        .and_then(|options| options.get("baseUrl"))
+        .and_then(|value| value.as_str())
+        .filter(|base_url| is_contained_relative_path(Path::new(base_url)) || *base_url == ".")
+    {
+        compiler.base_url = Some(dir.join(base_url));
+    }
+    if let Some(paths) = options
+        .and_then(|options| options.get("paths"))
+        .and_then(|value| value.as_object())
+    {
+        let patterns = paths
+            .iter()
+            .map(|(pattern, targets)| {
+                let targets = targets
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|target| target.as_str().map(str::to_string))
+                    .collect();
+                (pattern.clone(), targets)
+            })
+            .collect();
+        compiler.paths = Some(PathAliases {
+            dir: dir.clone(),
+            patterns,
+        });
+    }
+
+    let include_dirs = json
+        .get("include")
+        .and_then(|value| value.as_array())
+        .map(|patterns| {
+            patterns
+                .iter()
+                .filter_map(|pattern| pattern.as_str())
+                .map(|pattern| {
+                    // `src/**/*.ts` covers `src`
+                    let fixed: PathBuf = Path::new(pattern)
+                        .components()
+                        .take_while(|component| {
+                            !component.as_os_str().to_string_lossy().contains('*')
+                        })
+                        .collect();
+                    dir.join(fixed)
+                })
+                .collect()
+        });
+
+    let references = json
+        .get("references")
+        .and_then(|value| value.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|reference| reference.get("path").and_then(|path| path.as_str()))
+        .map(|reference| {
+            let path = dir.join(reference);
+            if path.is_dir() {
+                path.join("tsconfig.json")
+            } else {
+                path
+            }
+        })
+        .filter(|path| path.is_file() && path.starts_with(base_dir))
+        .collect();
+
+    Some(NodeConfig {
+        dir,
+        compiler,
+        include_dirs,
+        references,
+    })
+}
+
+/// `extends` names a relative config file, with or without `.json`, or a config
+/// published in a package under `node_modules`.
+fn extended_config_path(parent: &str, dir: &Path, base_dir: &Path) -> Option<PathBuf> {
+    let candidates: Vec<PathBuf> = if parent.starts_with('.') {
+        let path = dir.join(parent);
+        vec![path.clone(), with_added_extension(&path, "json")]
+    } else {
+        dir.ancestors()
+            .take_while(|ancestor| ancestor.starts_with(base_dir))
+            .map(|ancestor| ancestor.join("node_modules").join(parent))
+            .flat_map(|path| {
+                [
+                    path.clone(),
+                    with_added_extension(&path, "json"),
+                    path.join("tsconfig.json"),
+                ]
+            })
+            .collect()
+    };
+
+    candidates
+        .into_iter()
+        .find(|candidate| candidate.is_file())
+        .filter(|candidate| {
+            candidate
+                .canonicalize()
+                .is_ok_and(|path| path.starts_with(base_dir.canonicalize().unwrap_or_default()))
+        })
+}
+
+/// Workspace package names mapped to their directories.
+type WorkspacePackages = HashMap<String, PathBuf>;
+
+/// Packages of every workspace in the project. Workspaces are declared by `workspaces` in a `package.json` (as an
+/// array, or Yarn's `{ "packages": [...] }`) or by `pnpm-workspace.yaml`.
+fn build_workspace_packages(base_dir: &Path) -> WorkspacePackages {
+    let manifests: Vec<PathBuf> = walkdir::WalkDir::new(base_dir)
+        .max_depth(6)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|entry| {
+            entry.depth() == 0
+                || !entry
+                    .file_name()
+                    .to_str()
+                    .is_some_and(|name| name.starts_with('.') || NODE_SKIPPED_DIRS.contains(&name))
+        })
+        .flatten()
+        .filter(|entry| entry.file_type().is_file() && entry.file_name() == "package.json")
+        .map(|entry| entry.into_path())
+        .collect();
+
+    let mut packages = HashMap::new();
+    for manifest in &manifests {
+        let Some(root) = manifest.parent() else {
+            continue;
+        };
+        let mut patterns = read_json(manifest)
+            .and_then(|json| {
+                let workspaces = json.get("workspaces")?;
+                let list = workspaces.get("packages").unwrap_or(workspaces);
+                Some(
+                    list.as_array()?
+                        .iter()
+                        .filter_map(|pattern| pattern.as_str().map(str::to_string))
+                        .collect::<Vec<_>>(),
+                )
+            })
+            .unwrap_or_default();
+        if let Ok(pnpm) = fs::read_to_string(root.join("pnpm-workspace.yaml")) {
+            patterns.extend(pnpm_workspace_patterns(&pnpm));
+        }
+        if patterns.is_empty() {
+            continue;
+        }
+
+        for member in &manifests {
+            let Some(member_dir) = member.parent() else {
+                continue;
+            };
+            let Ok(relative) = member_dir.strip_prefix(root) else {
+                continue;
+            };
+            if relative.as_os_str().is_empty() || !workspace_patterns_match(&patterns, relative) {
+                continue;
+            }
+            if let Some(name) = read_json(member).and_then(|json| {
+                json.get("name")
+                    .and_then(|name| name.as_str())
+                    .map(str::to_string)
+            }) {
+                packages
+                    .entry(name)
+                    .or_insert_with(|| member_dir.to_path_buf());
+            }
+        }
+    }
+
+    packages
+}
+
+/// Entries of the `packages:` list in `pnpm-workspace.yaml`.
+fn pnpm_workspace_patterns(yaml: &str) -> Vec<String> {
+    let mut patterns = Vec::new();
+    let mut in_packages = false;
+    for line in yaml.lines() {
+        let content = line.split('#').next().unwrap_or_default();
+        if content.trim().is_empty() {
+            continue;
+        }
+        if !content.starts_with(char::is_whitespace) && !content.starts_with('-') {
+            in_packages = content.trim_end() == "packages:";
+            continue;
+        }
+        if let Some(item) = content.trim().strip_prefix("- ").filter(|_| in_packages) {
+            patterns.push(item.trim().trim_matches(['"', '\'']).to_string());
+        }
+    }
+    patterns
+}
+
+/// Whether a member directory matches the workspace globs, honoring `!` exclusions.
+fn workspace_patterns_match(patterns: &[String], relative: &Path) -> bool {
+    let segments: Vec<String> = relative
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy().into_owned())
+        .collect();
+    let matches = |pattern: &str| {
+        let pattern: Vec<&str> = pattern
+            .trim_start_matches("./")
+            .trim_end_matches('/')
+            .split('/')
+            .collect();
+        glob_segments_match(&pattern, &segments)
+    };
+
+    patterns
+        .iter()
+        .filter(|pattern| !pattern.starts_with('!'))
+        .any(|pattern| matches(pattern))
+        && !patterns
+            .iter()
+            .filter_map(|pattern| pattern.strip_prefix('!'))
+            .any(matches)
+}
+
+/// Match path segments against glob segments, where `**` spans any number of
+/// segments and `*` any part of one.
+fn glob_segments_match(pattern: &[&str], segments: &[String]) -> bool {
+    match pattern.split_first() {
+        None => segments.is_empty(),
+        Some((&"**", rest)) => {
+            (0..=segments.len()).any(|skip| glob_segments_match(rest, &segments[skip..]))
+        }
+        Some((first, rest)) => segments.split_first().is_some_and(|(segment, remaining)| {
+            wildcard_match(first, segment) && glob_segments_match(rest, remaining)
+        }),
+    }
+}
+
+fn wildcard_match(pattern: &str, text: &str) -> bool {
+    match pattern.split_once('*') {
+        None => pattern == text,
+        Some((prefix, rest)) => {
+            text.starts_with(prefix)
+                && (0..=text.len() - prefix.len()).any(|skip| {
+                    text.is_char_boundary(prefix.len() + skip)
+                        && wildcard_match(rest, &text[prefix.len() + skip..])
+                })
+        }
+    }
+}
+
+/// Parse JSON that may contain comments and trailing commas, as tsconfig files do.
+fn read_json(path: &Path) -> Option<serde_json::Value> {
+    let content = fs::read_to_string(path).ok()?;
+    serde_json::from_str(&content)
+        .or_else(|_| serde_json::from_str(&strip_json_comments(&content)))
+        .ok()
+}
+
+/// Remove `//` and `/* */` comments and trailing commas outside of strings.
+fn strip_json_comments(content: &str) -> String {
+    let without_comments = scan_json_outside_strings(content, |chars, i, output| {
+        match (chars[i], chars.get(i + 1)) {
+            ('/', Some('/')) => {
+                let mut end = i;
+                while end < chars.len() && chars[end] != '\n' {
+                    end += 1;
+                }
+                end
+            }
+            ('/', Some('*')) => {
+                let mut end = i + 2;
+                while end < chars.len() && !(chars[end] == '*' && chars.get(end + 1) == Some(&'/'))
+                {
+                    end += 1;
+                }
+                end + 2
+            }
+            (c, _) => {
+                output.push(c);
+                i + 1
+            }
+        }
+    });
+
+    scan_json_outside_strings(&without_comments, |chars, i, output| {
+        let trailing = chars[i] == ','
+            && matches!(
+                chars[i + 1..].iter().find(|next| !next.is_whitespace()),
+                Some('}') | Some(']')
+            );
+        if !trailing {
+            output.push(chars[i]);
+        }
+        i + 1
+    })
+}
+
+/// Copy strings verbatim and let `step` handle every other character, returning the
+/// index to continue from.
+fn scan_json_outside_strings(
+    content: &str,
+    mut step: impl FnMut(&[char], usize, &mut String) -> usize,
+) -> String {
+    let chars: Vec<char> = content.chars().collect();
+    let mut output = String::with_capacity(content.len());
+    let mut i = 0;
+
+    while i < chars.len() {
+        if chars[i] != '"' {
+            i = step(&chars, i, &mut output);
+            continue;
+        }
+
+        output.push('"');
+        i += 1;
+        while i < chars.len() {
+            output.push(chars[i]);
+            if chars[i] == '\\' {
+                if let Some(&escaped) = chars.get(i + 1) {
+                    output.push(escaped);
+                    i += 1;
+                }
+            } else if chars[i] == '"' {
+                i += 1;
+                break;
+            }
+            i += 1;
+        }
+    }
+
+    output
+}
+
+/// What a module exports, as far as following barrel files is concerned.
+#[derive(Debug, Default)]
+struct ModuleExports {
+    /// Names the module defines and exports itself
+    declared: HashSet<String>,
+    /// Exported names forwarded from another module, with the specifier and the name
+    /// they have there; `*` stands for the whole module (`export * as ns from`)
+    forwarded: HashMap<String, (String, String)>,
+    /// Specifiers of `export * from` and `module.exports = require()`
+    stars: Vec<String>,
+}
+
+impl ModuleExports {
+    fn is_barrel(&self) -> bool {
+        !self.forwarded.is_empty() || !self.stars.is_empty()
+    }
+}
+
+/// Point imports of barrel files at the files defining the imported names, so that
+/// importing one component from `components/index.ts` doesn't pull in every module the
+/// barrel re-exports. Imports keep the barrel when a name can't be followed.
+pub(crate) fn trace_barrel_imports(
+    imports: &mut Vec<Import>,
+    from_file: &Path,
+    base_dir: &Path,
+    resolver: &dyn ModuleResolver,
+) {
+    // `const { a } = require('./x')` also matches the plain `require()` pattern
+    let destructured: HashSet<(String, usize)> = imports
+        .iter()
+        .filter(|import| !import.items.is_empty())
+        .map(|import| (import.module.clone(), import.line))
+        .collect();
+    imports.retain(|import| {
+        !import.items.is_empty() || !destructured.contains(&(import.module.clone(), import.line))
+    });
+
+    let current_file = from_file
+        .canonicalize()
+        .unwrap_or_else(|_| from_file.to_path_buf());
+    let mut traced = Vec::with_capacity(imports.len());
+    for import in imports.drain(..) {
+        match trace_barrel_import(&import, &current_file, base_dir, resolver) {
+            Some(definitions) => traced.extend(definitions),
+            None => traced.push(import),
+        }
+    }
+    *imports = traced;
+}
+
+fn trace_barrel_import(
+    import: &Import,
+    current_file: &Path,
+    base_dir: &Path,
+    resolver: &dyn ModuleResolver,
+) -> Option<Vec<Import>> {
+    if import.items.is_empty() || import.items.iter().any(|item| item == "*") {
+        return None;
+    }
+    let resolved = resolver
+        .resolve_import(&import.module, current_file, base_dir)
+        .ok()
+        .filter(|resolved| !resolved.is_external)?;
+    if !module_exports(&resolved.path).is_barrel() {
+        return None;
+    }
+
+    let mut by_file: Vec<(PathBuf, Vec<String>)> = Vec::new();
+    for item in &import.items {
+        let file = defining_file(&resolved.path, item, base_dir, resolver, 0)?;
+        match by_file.iter_mut().find(|(existing, _)| *existing == file) {
+            Some((_, items)) => items.push(item.clone()),
+            None => by_file.push((file, vec![item.clone()])),
+        }
+    }
+    if by_file.iter().all(|(file, _)| *file == resolved.path) {
+        return None;
+    }
+
+    by_file
+        .into_iter()
+        .map(|(file, items)| {
+            let module = if file == resolved.path {
+                import.module.clone()
+            } else {
+                relative_specifier(current_file, &file)?
+            };
+            Some(Import {
+                module,
+                items,
+                is_relative: true,
+                line: import.line,
+            })
+        })
+        .collect()
+}
+
+/// The file defining `name` among the exports of `file`, following named re-exports
+/// first and `export *` in declaration order.
+fn defining_file(
+    file: &Path,
+    name: &str,
+    base_dir: &Path,
+    resolver: &dyn ModuleResolver,
+    depth: usize,
+) -> Option<PathBuf> {
+    if depth > MAX_REEXPORT_DEPTH {
+        return None;
+    }
+    let exports = module_exports(file);
+    if exports.declared.contains(name) {
+        return Some(file.to_path_buf());
+    }
+
+    let resolve = |specifier: &str| {
+        resolver
+            .resolve_import(specifier, file, base_dir)
+            .ok()
+            .filter(|resolved| !resolved.is_external)
+            .map(|resolved| resolved.path)
+    };
+    if let Some((specifier, original)) = exports.forwarded.get(name) {
+        let target = resolve(specifier)?;
+        return if original == "*" {
+            Some(target)
+        } else {
+            defining_file(&target, original, base_dir, resolver, depth + 1)
+        };
+    }
+
+    // `export *` never forwards the default export
+    if name == "default" {
+        return None;
+    }
+    exports.stars.iter().find_map(|specifier| {
+        let target = resolve(specifier)?;
+        defining_file(&target, name, base_dir, resolver, depth + 1)
+    })
+}
+
+fn module_exports(file: &Path) -> ModuleExports {
+    let language = match file.extension().and_then(|ext| ext.to_str()) {
+        Some("ts" | "mts" | "cts") => tree_sitter_typescript::language_typescript(),
+        Some("tsx") => tree_sitter_typescript::language_tsx(),
+        _ => tree_sitter_javascript::language(),
+    };
+    let mut parser = Parser::new();
+    if parser.set_language(language).is_err() {
+        return ModuleExports::default();
+    }
+    let Ok(content) = fs::read_to_string(file) else {
+        return ModuleExports::default();
+    };
+    let Some(tree) = parser.parse(&content, None) else {
+        return ModuleExports::default();
+    };
+    let source = content.as_bytes();
+    let text = |node: Node| node.utf8_text(source).unwrap_or_default().to_string();
+
+    // Local bindings created by imports and requires: local name -> (specifier, name)
+    let mut bindings: HashMap<String, (String, String)> = HashMap::new();
+    let mut exports = ModuleExports::default();
+    let root = tree.root_node();
+    let mut cursor = root.walk();
+    for node in root.named_children(&mut cursor) {
+        match node.kind() {
+            "import_statement" => {
+                let Some(specifier) = node
+                    .child_by_field_name("source")
+                    .and_then(|source_node| string_value(source_node, source))
+                else {
+                    continue;
+                };
+                let mut clause_cursor = node.walk();
+                let Some(clause) = node
+                    .named_children(&mut clause_cursor)
+                    .find(|child| child.kind() == "import_clause")
+                else {
+                    continue;
+                };
+                let mut binding_cursor = clause.walk();
+                for binding in clause.named_children(&mut binding_cursor) {
+                    match binding.kind() {
+                        "identifier" => {
+                            bindings.insert(text(binding), (specifier.clone(), "default".into()));
+                        }
+                        "namespace_import" => {
+                            if let Some(local) = binding.named_child(0) {
+                                bindings.insert(text(local), (specifier.clone(), "*".into()));
+                            }
+                        }
+                        "named_imports" => {
+                            let mut specifier_cursor = binding.walk();
+                            for import in binding.named_children(&mut specifier_cursor) {
+                                let Some(name) = import.child_by_field_name("name") else {
+                                    continue;
+                                };
+                                let local = import.child_by_field_name("alias").unwrap_or(name);
+                                bindings.insert(text(local), (specifier.clone(), text(name)));
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            "export_statement" => {
+                let source_specifier = node
+                    .child_by_field_name("source")
+                    .and_then(|source_node| string_value(source_node, source));
+                let mut child_cursor = node.walk();
+                let children: Vec<Node> = node.children(&mut child_cursor).collect();
+
+                if let Some(declaration) = node.child_by_field_name("declaration") {
+                    exports.declared.extend(declared_names(declaration, source));
+                } else if children.iter().any(|child| child.kind() == "default") {
+                    exports.declared.insert("default".to_string());
+                } else if let Some(clause) = children
+                    .iter()
+                    .find(|child| child.kind() == "export_clause")
+                {
+                    let mut specifier_cursor = clause.walk();
+                    for export in clause.named_children(&mut specifier_cursor) {
+                        let Some(name) = export.child_by_field_name("name") else {
+                            continue;
+                        };
+                        let exported = text(export.child_by_field_name("alias").unwrap_or(name));
+                        let forwarded = match &source_specifier {
+                            Some(specifier) => Some((specifier.clone(), text(name))),
+                            None => bindings.get(&text(name)).cloned(),
+                        };
+                        match forwarded {
+                            Some(forwarded) => {
+                                exports.forwarded.insert(exported, forwarded);
+                            }
+                            None => {
+                                exports.declared.insert(exported);
+                            }
+                        }
+                    }
+                } else if let Some(specifier) = source_specifier {
+                    match children
+                        .iter()
+                        .find(|child| child.kind() == "namespace_export")
+                        .and_then(|namespace| namespace.named_child(0))
+                    {
+                        Some(local) => {
+                            exports
+                                .forwarded
+                                .insert(text(local), (specifier, "*".into()));
+                        }
+                        None => exports.stars.push(specifier),
+                    }
+                }
+            }
+            "lexical_declaration" | "variable_declaration" => {
+                let mut declarator_cursor = node.walk();
+                for declarator in node.named_children(&mut declarator_cursor) {
+                    let (Some(name), Some(specifier)) = (
+                        declarator.child_by_field_name("name"),
+                        declarator
+                            .child_by_field_name("value")
+                            .and_then(|value| required_specifier(value, source)),
+                    ) else {
+                        continue;
+                    };
+                    if name.kind() == "identifier" {
+                        bindings.insert(text(name), (specifier, "*".into()));
+                        continue;
+                    }
+                    let mut pattern_cursor = name.walk();
+                    for property in name.named_children(&mut pattern_cursor) {
+                        let (key, local) = match property.kind() {
+                            "shorthand_property_identifier_pattern" => (property, property),
+                            "pair_pattern" => match (
+                                property.child_by_field_name("key"),
+                                property.child_by_field_name("value"),
+                            ) {
+                                (Some(key), Some(value)) => (key, value),
+                                _ => continue,
+                            },
+                            _ => continue,
+                        };
+                        bindings.insert(text(local), (specifier.clone(), text(key)));
+                    }
+                }
+            }
+            "expression_statement" => {
+                if let Some(assignment) = node
+                    .named_child(0)
+                    .filter(|child| child.kind() == "assignment_expression")
+                {
+                    add_commonjs_exports(assignment, source, &bindings, &mut exports);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    exports
+}
+
+/// `module.exports = require('./x')`, `module.exports = { a, b: require('./b') }`, and
+/// `exports.a = ...` or `module.exports.a = ...`.
+fn add_commonjs_exports(
+    assignment: Node,
+    source: &[u8],
+    bindings: &HashMap<String, (String, String)>,
+    exports: &mut ModuleExports,
+) {
+    let text = |node: Node| node.utf8_text(source).unwrap_or_default().to_string();
+    let (Some(left), Some(right)) = (
+        assignment.child_by_field_name("left"),
+        assignment.child_by_field_name("right"),
+    ) else {
+        return;
+    };
+    let left_text = text(left);
+
+    if left_text == "module.exports" {
+        if let Some(specifier) = required_specifier(right, source) {
+            exports.stars.push(specifier);
+            return;
+        }
+        if right.kind() != "object" {
+            exports.declared.insert("default".to_string());
+            return;
+        }
+        let mut property_cursor = right.walk();
+        for property in right.named_children(&mut property_cursor) {
+            match property.kind() {
+                "shorthand_property_identifier" => {
+                    let name = text(property);
+                    match bindings.get(&name) {
+                        Some(forwarded) => {
+                            exports.forwarded.insert(name, forwarded.clone());
+                        }
+                        None => {
+                            exports.declared.insert(name);
+                        }
+                    }
+                }
+                "pair" => {
+                    let (Some(key), Some(value)) = (
+                        property.child_by_field_name("key"),
+                        property.child_by_field_name("value"),
+                    ) else {
+                        continue;
+                    };
+                    let name = text(key);
+                    let forwarded = required_specifier(value, source)
+                        .map(|specifier| (specifier, "*".to_string()))
+                        .or_else(|| bindings.get(&text(value)).cloned());
+                    match forwarded {
+                        Some(forwarded) => {
+                            exports.forwarded.insert(name, forwarded);
+                        }
+                        None => {
+                            exports.declared.insert(name);
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        return;
+    }
+
+    let Some(name) = left_text
+        .strip_prefix("module.exports.")
+        .or_else(|| left_text.strip_prefix("exports."))
+    else {
+        return;
+    };
+    match required_specifier(right, source) {
+        Some(specifier) => {
+            exports
+                .forwarded
+                .insert(name.to_string(), (specifier, "*".to_string()));
+        }
+        None => {
+            exports.declared.insert(name.to_string());
+        }
+    }
+}
+
+/// Names introduced by an exported declaration.
+fn declared_names(declaration: Node, source: &[u8]) -> Vec<String> {
+    let text = |node: Node| node.utf8_text(source).unwrap_or_default().to_string();
+    match declaration.kind() {
+        "lexical_declaration" | "variable_declaration" => {
+            let mut cursor = declaration.walk();
+            declaration
+                .named_children(&mut cursor)
+                .filter_map(|declarator| declarator.child_by_field_name("name"))
+                .filter(|name| name.kind() == "identifier")
+                .map(text)
+                .collect()
+        }
+        _ => declaration
+            .child_by_field_name("name")
+            .map(text)
+            .into_iter()
+            .collect(),
+    }
+}
+
+/// The specifier of `require('./x')`.
+fn required_specifier(node: Node, source: &[u8]) -> Option<String> {
+    if node.kind() != "call_expression"
+        || node
+            .child_by_field_name("function")
+            .and_then(|function| function.utf8_text(source).ok())
+            != Some("require")
+    {
+        return None;
+    }
+    let argument = node.child_by_field_name("arguments")?.named_child(0)?;
+    string_value(argument, source)
+}
+
+fn string_value(node: Node, source: &[u8]) -> Option<String> {
+    if node.kind() != "string" {
+        return None;
+    }
+    let value = node
+        .utf8_text(source)
+        .ok()?
+        .trim_matches(|c| c == '"' || c == '\'');
+    (!value.is_empty()).then(|| value.to_string())
+}
+
+/// The specifier from one module to another, as written in an `import`.
+pub(crate) fn relative_specifier(from_file: &Path, to_file: &Path) -> Option<String> {
+    let from_dir: Vec<_> = from_file.parent()?.components().collect();
+    let to: Vec<_> = to_file.components().collect();
+    let shared = from_dir
+        .iter()
+        .zip(&to)
+        .take_while(|(left, right)| left == right)
+        .count();
+    if shared == 0 {
+        return None;
+    }
+
+    let climbs = from_dir.len() - shared;
+    let segments: Vec<String> = std::iter::repeat("..".to_string())
+        .take(climbs)
+        .chain(
+            to[shared..]
+                .iter()
+                .map(|component| component.as_os_str().to_string_lossy().into_owned()),
+        )
+        .collect();
+    let specifier = segments.join("/");
+    Some(if climbs == 0 {
+        format!("./{specifier}")
+    } else {
+        specifier
+    })
+}
+
+fn node_config_cache() -> &'static Mutex<HashMap<PathBuf, Option<Arc<NodeConfig>>>> {
+    static CACHE: OnceLock<Mutex<HashMap<PathBuf, Option<Arc<NodeConfig>>>>> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn node_config(path: &Path, base_dir: &Path) -> Option<Arc<NodeConfig>> {
+    if let Some(config) = node_config_cache()
+        .lock()
+        .ok()
+        .and_then(|cache| cache.get(path).cloned())
+    {
+        return config;
+    }
+
+    let config = load_node_config(path, base_dir, 0).map(Arc::new);
+    if let Ok(mut cache) = node_config_cache().lock() {
+        cache.insert(path.to_path_buf(), config.clone());
+    }
+    config
+}
+
+fn workspace_cache() -> &'static Mutex<HashMap<PathBuf, Arc<WorkspacePackages>>> {
+    static CACHE: OnceLock<Mutex<HashMap<PathBuf, Arc<WorkspacePackages>>>> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn workspace_packages(base_dir: &Path) -> Arc<WorkspacePackages> {
+    if let Some(packages) = workspace_cache()
+        .lock()
+        .ok()
+        .and_then(|cache| cache.get(base_dir).cloned())
+    {
+        return packages;
+    }
+
+    let packages = Arc::new(build_workspace_packages(base_dir));
+    if let Ok(mut cache) = workspace_cache().lock() {
+        cache.insert(base_dir.to_path_buf(), packages.clone());
+    }
+    packages
+}
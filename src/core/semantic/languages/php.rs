@@ -1,24 +1,31 @@
-//! Semantic analyzer for Php
+//! Semantic analyzer for PHP, with Composer autoload resolution
 
-use crate::core::semantic::analyzer::{
-    AnalysisResult, LanguageAnalyzer, SemanticContext, SemanticResult,
+use crate::core::semantic::{
+    analyzer::{
+        AnalysisResult, Import, LanguageAnalyzer, SemanticContext, SemanticResult, TypeReference,
+    },
+    path_validator::validate_import_path,
+    query_engine::QueryEngine,
+    resolver::{ModuleResolver, ResolvedPath},
 };
-use std::path::Path;
-use tree_sitter::Parser;
+use crate::utils::error::ContextCreatorError;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+use tree_sitter::{Node, Parser};
 
 #[allow(clippy::new_without_default)]
 pub struct PhpAnalyzer {
-    #[allow(dead_code)]
-    #[allow(dead_code)]
-    parser: Parser,
+    query_engine: QueryEngine,
 }
 
 impl PhpAnalyzer {
     pub fn new() -> Self {
-        let parser = Parser::new();
-        // Note: tree_sitter_php::language() would be used here when the crate is added
-        // parser.set_language(tree_sitter_php::language()).unwrap();
-        Self { parser }
+        let language = tree_sitter_php::language_php();
+        let query_engine =
+            QueryEngine::new(language, "php").expect("Failed to create PHP query engine");
+        Self { query_engine }
     }
 }
 
@@ -29,12 +36,29 @@ impl LanguageAnalyzer for PhpAnalyzer {
 
     fn analyze_file(
         &self,
-        _path: &Path,
-        _content: &str,
-        _context: &SemanticContext,
+        path: &Path,
+        content: &str,
+        context: &SemanticContext,
     ) -> SemanticResult<AnalysisResult> {
-        // TODO: Implement Php analysis
-        Ok(AnalysisResult::default())
+        let mut parser = Parser::new();
+        parser
+            .set_language(tree_sitter_php::language_php())
+            .map_err(|e| ContextCreatorError::ParseError(format!("Failed to set language: {e}")))?;
+
+        let mut result = self
+            .query_engine
+            .analyze_with_parser(&mut parser, content)?;
+
+        result.dedupe_function_calls(|_| true);
+        self.expand_use_declarations(&mut result);
+
+        let scope = PhpScope {
+            namespace: php_namespace(content).unwrap_or_default(),
+            aliases: php_use_aliases(&result.imports),
+        };
+        self.resolve_php_type_definitions(&mut result, path, &context.base_dir, &scope);
+
+        Ok(result)
     }
 
     fn can_handle_extension(&self, extension: &str) -> bool {
@@ -44,4 +68,565 @@ impl LanguageAnalyzer for PhpAnalyzer {
     fn supported_extensions(&self) -> Vec<&'static str> {
         vec!["php"]
     }
+
+    fn query_engine(&self) -> Option<&QueryEngine> {
+        Some(&self.query_engine)
+    }
+}
+
+impl PhpAnalyzer {
+    /// One import per imported class: `use App\Services\{Billing, Tax as T};` becomes
+    /// `App\Services\Billing` and `App\Services\Tax` (aliased as `T`).
+    fn expand_use_declarations(&self, result: &mut AnalysisResult) {
+        let mut imports = Vec::new();
+
+        for import in result.imports.drain(..) {
+            let is_class_use = !import.is_relative;
+            let is_group = import
+                .items
+                .first()
+                .is_some_and(|item| !item.starts_with("as "));
+            if !is_class_use || !is_group {
+                imports.push(import);
+                continue;
+            }
+
+            for item in &import.items {
+                let (class, alias) = match item.split_once(" as ") {
+                    Some((class, alias)) => (class.trim(), Some(alias.trim())),
+                    None => (item.trim(), None),
+                };
+                let module = if import.module.is_empty() {
+                    class.to_string()
+                } else {
+                    format!("{}\\{class}", import.module)
+                };
+                imports.push(Import {
+                    module,
+                    items: alias
+                        .map(|alias| vec![format!("as {alias}")])
+                        .unwrap_or_default(),
+                    is_relative: false,
+                    line: import.line,
+                });
+            }
+        }
+
+        result.imports = imports;
+    }
+
+    /// Resolve class names the way PHP does: fully qualified names as written, the first
+    /// segment through `use` aliases, otherwise relative to the current namespace. Classes
+    /// reached without a `use` (same namespace, inline FQCNs) are added as imports.
+    fn resolve_php_type_definitions(
+        &self,
+        result: &mut AnalysisResult,
+        current_file: &Path,
+        project_root: &Path,
+        scope: &PhpScope,
+    ) {
+        let autoload = composer_autoload(project_root);
+        let current_file = current_file
+            .canonicalize()
+            .unwrap_or_else(|_| current_file.to_path_buf());
+        let mut implicit_imports: Vec<(String, usize)> = Vec::new();
+        let mut type_refs = Vec::new();
+
+        for mut type_ref in result.type_references.drain(..) {
+            if matches!(
+                type_ref.name.to_ascii_lowercase().as_str(),
+                "self" | "static" | "parent"
+            ) {
+                continue;
+            }
+
+            let class_name = scope.qualify(&type_ref.name);
+            match class_name.rsplit_once('\\') {
+                Some((class_namespace, short_name)) => {
+                    type_ref.name = short_name.to_string();
+                    type_ref.module = Some(class_namespace.to_string());
+                }
+                None => type_ref.module = None,
+            }
+            if type_refs.iter().any(|existing: &TypeReference| {
+                existing.name == type_ref.name
+                    && existing.module == type_ref.module
+                    && existing.line == type_ref.line
+            }) {
+                continue;
+            }
+
+            if let Some(file) = autoload.class_file(&class_name) {
+                if file.canonicalize().ok().as_ref() != Some(&current_file) {
+                    match validate_import_path(project_root, &file) {
+                        Ok(validated) => {
+                            type_ref.definition_path = Some(validated);
+                            if !implicit_imports
+                                .iter()
+                                .any(|(class, _)| class == &class_name)
+                            {
+                                implicit_imports.push((class_name, type_ref.line));
+                            }
+                        }
+                        Err(_) => type_ref.is_external = true,
+                    }
+                }
+            } else if autoload.is_configured() && !autoload.owns_namespace(&class_name) {
+                // Classes outside the project's autoload prefixes come from vendor/
+                type_ref.is_external = true;
+            }
+
+            type_refs.push(type_ref);
+        }
+
+        result.type_references = type_refs;
+        for (class_name, line) in implicit_imports {
+            if result
+                .imports
+                .iter()
+                .any(|import| import.module.trim_start_matches('\\') == class_name)
+            {
+                continue;
+            }
+            result.imports.push(Import {
+                module: class_name,
+                items: Vec::new(),
+                is_relative: false,
+                line,
+            });
+        }
+    }
+}
+
+/// The namespace and `use` aliases class names in a file are resolved against.
+struct PhpScope {
+    namespace: String,
+    aliases: HashMap<String, String>,
+}
+
+impl PhpScope {
+    /// Fully qualify a class name as written in the file.
+    fn qualify(&self, name: &str) -> String {
+        if let Some(absolute) = name.strip_prefix('\\') {
+            return absolute.to_string();
+        }
+
+        let (first, rest) = match name.split_once('\\') {
+            Some((first, rest)) => (first, Some(rest)),
+            None => (name, None),
+        };
+        let base = match self.aliases.get(&first.to_ascii_lowercase()) {
+            Some(class) => class.clone(),
+            None if self.namespace.is_empty() => first.to_string(),
+            None => format!("{}\\{first}", self.namespace),
+        };
+        match rest {
+            Some(rest) => format!("{base}\\{rest}"),
+            None => base,
+        }
+    }
+}
+
+/// Short names introduced by `use` declarations, keyed case-insensitively like PHP.
+fn php_use_aliases(imports: &[Import]) -> HashMap<String, String> {
+    imports
+        .iter()
+        .filter(|import| !import.is_relative)
+        .map(|import| {
+            let class = import.module.trim_start_matches('\\').to_string();
+            let alias = import
+                .items
+                .first()
+                .and_then(|item| item.strip_prefix("as "))
+                .map(str::to_string)
+                .unwrap_or_else(|| class.rsplit('\\').next().unwrap_or(&class).to_string());
+            (alias.to_ascii_lowercase(), class)
+        })
+        .collect()
+}
+
+/// The namespace declared by a file, from `namespace App\Http;` or `namespace App\Http {`.
+fn php_namespace(content: &str) -> Option<String> {
+    content.lines().find_map(|line| {
+        let declaration = line.trim().strip_prefix("namespace ")?;
+        let namespace = declaration.split([';', '{']).next()?.trim();
+        (!namespace.is_empty()).then(|| namespace.trim_start_matches('\\').to_string())
+    })
+}
+
+pub struct PhpModuleResolver;
+
+impl ModuleResolver for PhpModuleResolver {
+    fn resolve_import(
+        &self,
+        module_path: &str,
+        from_file: &Path,
+        base_dir: &Path,
+    ) -> Result<ResolvedPath, ContextCreatorError> {
+        let module_path = module_path.trim();
+
+        if is_php_file_path(module_path) {
+            validate_php_include_path(module_path)?;
+            let mut search_dirs: Vec<PathBuf> = from_file
+                .parent()
+                .map(Path::to_path_buf)
+                .into_iter()
+                .collect();
+            if !module_path.starts_with('.') {
+                search_dirs.push(base_dir.to_path_buf());
+            }
+
+            for dir in search_dirs {
+                let candidate = dir.join(module_path);
+                if candidate.is_file() {
+                    return Ok(ResolvedPath {
+                        path: validate_import_path(base_dir, &candidate)?,
+                        is_external: false,
+                        confidence: 0.9,
+                    });
+                }
+            }
+            return Ok(external_php_path(base_dir));
+        }
+
+        let class_name = module_path.trim_start_matches('\\');
+        validate_php_class_name(class_name)?;
+        match composer_autoload(base_dir).class_file(class_name) {
+            Some(file) => Ok(ResolvedPath {
+                path: validate_import_path(base_dir, &file)?,
+                is_external: false,
+                confidence: 0.95,
+            }),
+            None => Ok(external_php_path(base_dir)),
+        }
+    }
+
+    fn get_file_extensions(&self) -> Vec<&'static str> {
+        vec!["php"]
+    }
+
+    fn is_external_module(&self, module_path: &str) -> bool {
+        let root = module_path
+            .trim_start_matches('\\')
+            .split('\\')
+            .next()
+            .unwrap_or(module_path);
+        matches!(
+            root,
+            "Illuminate"
+                | "Symfony"
+                | "Doctrine"
+                | "Psr"
+                | "GuzzleHttp"
+                | "Monolog"
+                | "Carbon"
+                | "PHPUnit"
+                | "Laravel"
+                | "Livewire"
+                | "Twig"
+        )
+    }
+}
+
+fn is_php_file_path(module_path: &str) -> bool {
+    module_path.contains('/') || module_path.ends_with(".php") || module_path.starts_with('.')
+}
+
+/// Unresolved classes come from Composer packages.
+fn external_php_path(base_dir: &Path) -> ResolvedPath {
+    let composer_json = base_dir.join("composer.json");
+    ResolvedPath {
+        path: if composer_json.exists() {
+            composer_json
+        } else {
+            base_dir.to_path_buf()
+        },
+        is_external: true,
+        confidence: 0.8,
+    }
+}
+
+fn validate_php_class_name(class_name: &str) -> Result<(), ContextCreatorError> {
+    if class_name.is_empty()
+        || class_name.len() > 255
+        || !class_name
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '_' || c == '\\')
+    {
+        return Err(ContextCreatorError::SecurityError(format!(
+            "Invalid class name: {class_name}"
+        )));
+    }
+
+    Ok(())
+}
+
+/// Include paths may climb with `../`; containment is checked after resolution.
+fn validate_php_include_path(include: &str) -> Result<(), ContextCreatorError> {
+    let path = Path::new(include);
+    if include.is_empty()
+        || include.len() > 255
+        || include.contains('\0')
+        || path.is_absolute()
+        || path
+            .components()
+            .any(|component| matches!(component, Component::Prefix(_) | Component::RootDir))
+    {
+        return Err(ContextCreatorError::SecurityError(format!(
+            "Invalid include path: {include}"
+        )));
+    }
+
+    Ok(())
+}
+
+/// The `autoload` and `autoload-dev` sections of `composer.json`.
+#[derive(Debug, Default)]
+struct ComposerAutoload {
+    /// Namespace prefixes with their directories, longest prefix first.
+    psr4: Vec<(String, Vec<PathBuf>)>,
+    psr0: Vec<(String, Vec<PathBuf>)>,
+    /// Fully-qualified class names found in `classmap` directories and files.
+    classmap: HashMap<String, PathBuf>,
+}
+
+impl ComposerAutoload {
+    fn load(base_dir: &Path) -> Self {
+        let mut autoload = Self::default();
+        let Some(manifest) = fs::read_to_string(base_dir.join("composer.json"))
+            .ok()
+            .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+        else {
+            return autoload;
+        };
+
+        let mut classmap_paths = Vec::new();
+        for section in ["autoload", "autoload-dev"] {
+            let Some(section) = manifest.get(section) else {
+                continue;
+            };
+            autoload
+                .psr4
+                .extend(namespace_dirs(section.get("psr-4"), base_dir));
+            autoload
+                .psr0
+                .extend(namespace_dirs(section.get("psr-0"), base_dir));
+            if let Some(paths) = section.get("classmap").and_then(|paths| paths.as_array()) {
+                classmap_paths.extend(
+                    paths
+                        .iter()
+                        .filter_map(|path| path.as_str())
+                        .map(|path| base_dir.join(path)),
+                );
+            }
+        }
+        autoload
+            .psr4
+            .sort_by_key(|(prefix, _)| std::cmp::Reverse(prefix.len()));
+        autoload
+            .psr0
+            .sort_by_key(|(prefix, _)| std::cmp::Reverse(prefix.len()));
+
+        let mut files = Vec::new();
+        for path in classmap_paths {
+            collect_php_files(&path, 0, &mut files);
+        }
+        let mut parser = Parser::new();
+        if parser.set_language(tree_sitter_php::language_php()).is_ok() {
+            files.sort();
+            for file in files {
+                let Ok(content) = fs::read_to_string(&file) else {
+                    continue;
+                };
+                let Some(tree) = parser.parse(&content, None) else {
+                    continue;
+                };
+                let mut classes = Vec::new();
+                collect_class_declarations(tree.root_node(), &content, "", &mut classes);
+                for class in classes {
+                    autoload
+                        .classmap
+                        .entry(class)
+                        .or_insert_with(|| file.clone());
+                }
+            }
+        }
+
+        autoload
+    }
+
+    fn is_configured(&self) -> bool {
+        !self.psr4.is_empty() || !self.psr0.is_empty() || !self.classmap.is_empty()
+    }
+
+    /// Whether a class belongs to one of the project's own autoload prefixes.
+    fn owns_namespace(&self, class_name: &str) -> bool {
+        self.classmap.contains_key(class_name)
+            || self
+                .psr4
+                .iter()
+                .chain(&self.psr0)
+                .any(|(prefix, _)| !prefix.is_empty() && class_name.starts_with(prefix.as_str()))
+    }
+
+    /// The file Composer would load for a fully-qualified class name.
+    fn class_file(&self, class_name: &str) -> Option<PathBuf> {
+        if let Some(file) = self.classmap.get(class_name) {
+            return Some(file.clone());
+        }
+
+        for (prefix, dirs) in &self.psr4 {
+            let Some(relative) = class_name.strip_prefix(prefix.as_str()) else {
+                continue;
+            };
+            let relative = format!("{}.php", relative.replace('\\', "/"));
+            if let Some(file) = dirs
+                .iter()
+                .map(|dir| dir.join(&relative))
+                .find(|file| file.is_file())
+            {
+                return Some(file);
+            }
+        }
+
+        // PSR-0 keeps the namespace in the path and maps `_` in the class name to directories
+        for (prefix, dirs) in &self.psr0 {
+            if !class_name.starts_with(prefix.as_str()) {
+                continue;
+            }
+            let (namespace, class) = class_name.rsplit_once('\\').unwrap_or(("", class_name));
+            let mut relative = namespace.replace('\\', "/");
+            if !relative.is_empty() {
+                relative.push('/');
+            }
+            relative.push_str(&class.replace('_', "/"));
+            relative.push_str(".php");
+            if let Some(file) = dirs
+                .iter()
+                .map(|dir| dir.join(&relative))
+                .find(|file| file.is_file())
+            {
+                return Some(file);
+            }
+        }
+
+        None
+    }
+}
+
+/// `{"App\\": "app/", "Lib\\": ["src/", "lib/"]}` entries of a psr-4 or psr-0 map.
+fn namespace_dirs(
+    mapping: Option<&serde_json::Value>,
+    base_dir: &Path,
+) -> Vec<(String, Vec<PathBuf>)> {
+    let Some(mapping) = mapping.and_then(|mapping| mapping.as_object()) else {
+        return Vec::new();
+    };
+
+    mapping
+        .iter()
+        .map(|(prefix, dirs)| {
+            let dirs = match dirs {
+                serde_json::Value::String(dir) => vec![base_dir.join(dir)],
+                serde_json::Value::Array(dirs) => dirs
+                    .iter()
+                    .filter_map(|dir| dir.as_str())
+                    .map(|dir| base_dir.join(dir))
+                    .collect(),
+                _ => Vec::new(),
+            };
+            (prefix.trim_start_matches('\\').to_string(), dirs)
+        })
+        .collect()
+}
+
+fn collect_php_files(path: &Path, depth: usize, files: &mut Vec<PathBuf>) {
+    if depth > 12 {
+        return;
+    }
+    if path.is_file() {
+        if path.extension().and_then(|ext| ext.to_str()) == Some("php") {
+            files.push(path.to_path_buf());
+        }
+        return;
+    }
+
+    let Ok(entries) = fs::read_dir(path) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let child = entry.path();
+        let is_hidden = child
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| name.starts_with('.'));
+        if !is_hidden {
+            collect_php_files(&child, depth + 1, files);
+        }
+    }
+}
+
+/// Fully-qualified names of classes, interfaces, traits, and enums declared in a file.
+fn collect_class_declarations(
+    node: Node<'_>,
+    content: &str,
+    namespace: &str,
+    classes: &mut Vec<String>,
+) {
+    let mut current_namespace = namespace.to_string();
+    let mut cursor = node.walk();
+    for child in node.named_children(&mut cursor) {
+        let name = || {
+            child
+                .child_by_field_name("name")
+                .and_then(|name| name.utf8_text(content.as_bytes()).ok())
+        };
+        match child.kind() {
+            "namespace_definition" => {
+                let child_namespace = name().unwrap_or_default().to_string();
+                match child.child_by_field_name("body") {
+                    // Braced namespaces scope their body
+                    Some(body) => {
+                        collect_class_declarations(body, content, &child_namespace, classes)
+                    }
+                    // `namespace App;` applies to the declarations that follow
+                    None => current_namespace = child_namespace,
+                }
+            }
+            "class_declaration"
+            | "interface_declaration"
+            | "trait_declaration"
+            | "enum_declaration" => {
+                if let Some(name) = name() {
+                    classes.push(if current_namespace.is_empty() {
+                        name.to_string()
+                    } else {
+                        format!("{current_namespace}\\{name}")
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn composer_cache() -> &'static Mutex<HashMap<PathBuf, Arc<ComposerAutoload>>> {
+    static CACHE: OnceLock<Mutex<HashMap<PathBuf, Arc<ComposerAutoload>>>> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn composer_autoload(base_dir: &Path) -> Arc<ComposerAutoload> {
+    if let Some(autoload) = composer_cache()
+        .lock()
+        .ok()
+        .and_then(|cache| cache.get(base_dir).cloned())
+    {
+        return autoload;
+    }
+
+    let autoload = Arc::new(ComposerAutoload::load(base_dir));
+    if let Ok(mut cache) = composer_cache().lock() {
+        cache.insert(base_dir.to_path_buf(), autoload.clone());
+    }
+    autoload
 }
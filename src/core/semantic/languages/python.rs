@@ -1,14 +1,58 @@
 //! Semantic analyzer for Python
 
 use crate::core::semantic::{
-    analyzer::{AnalysisResult, LanguageAnalyzer, SemanticContext, SemanticResult},
+    analyzer::{
+        AnalysisResult, Import, LanguageAnalyzer, SemanticContext, SemanticResult, TypeReference,
+    },
     path_validator::{validate_import_path, validate_module_name},
     query_engine::QueryEngine,
     resolver::{ModuleResolver, ResolvedPath, ResolverUtils},
 };
 use crate::utils::error::ContextCreatorError;
-use std::path::Path;
-use tree_sitter::Parser;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+use tree_sitter::{Node, Parser};
+
+/// Directories holding virtual environments, build output, or installed dependencies.
+const PYTHON_SKIPPED_DIRS: &[&str] = &[
+    "venv",
+    "env",
+    "node_modules",
+    "__pycache__",
+    "build",
+    "dist",
+    "site-packages",
+];
+
+/// Project configuration files that declare where packages live.
+const PYTHON_PROJECT_FILES: &[&str] = &["pyproject.toml", "setup.cfg", "setup.py"];
+
+/// Capitalized names from `typing` that appear in string annotations.
+const PYTHON_TYPING_NAMES: &[&str] = &[
+    "Any",
+    "Callable",
+    "ClassVar",
+    "Dict",
+    "Final",
+    "FrozenSet",
+    "Iterable",
+    "Iterator",
+    "List",
+    "Literal",
+    "Mapping",
+    "Optional",
+    "Self",
+    "Sequence",
+    "Set",
+    "Tuple",
+    "Type",
+    "Union",
+];
+
+/// How many `__init__.py` re-exports are followed before giving up.
+const MAX_REEXPORT_DEPTH: usize = 8;
 
 #[allow(clippy::new_without_default)]
 pub struct PythonAnalyzer {
@@ -44,9 +88,21 @@ impl LanguageAnalyzer for PythonAnalyzer {
             .query_engine
             .analyze_with_parser(&mut parser, content)?;
 
+        // Forward references such as "User" or "list[models.Order]" name their types
+        // inside a string
+        self.split_string_annotations(&mut result);
+
+        // `from acme.billing import invoices` imports a submodule, which is the only
+        // file to follow when `acme.billing` is a namespace package
+        self.add_submodule_imports(&mut result, path, &context.base_dir);
+
         // Correlate type references with imports to populate module information
         self.correlate_types_with_imports(&mut result);
 
+        // Follow the imports, including those guarded by `if TYPE_CHECKING:`, through
+        // `__init__.py` re-exports to the module defining each type
+        self.resolve_imported_types(&mut result, path, &context.base_dir);
+
         // Resolve type definitions for the type references found
         self.query_engine.resolve_type_definitions(
             &mut result.type_references,
@@ -64,15 +120,120 @@ impl LanguageAnalyzer for PythonAnalyzer {
     fn supported_extensions(&self) -> Vec<&'static str> {
         vec!["py", "pyw", "pyi"]
     }
+
+    fn query_engine(&self) -> Option<&QueryEngine> {
+        Some(&self.query_engine)
+    }
 }
 
 impl PythonAnalyzer {
+    /// Replace string annotations with the class names they mention.
+    fn split_string_annotations(&self, result: &mut AnalysisResult) {
+        let mut type_references = Vec::with_capacity(result.type_references.len());
+        for type_ref in result.type_references.drain(..) {
+            if is_python_identifier(&type_ref.name) {
+                type_references.push(type_ref);
+                continue;
+            }
+
+            for token in type_ref
+                .name
+                .split(|c: char| !(c.is_alphanumeric() || c == '_' || c == '.'))
+                .filter(|token| !token.is_empty())
+            {
+                let (module, name) = match token.rsplit_once('.') {
+                    Some((module, name)) => (Some(module.to_string()), name),
+                    None => (None, token),
+                };
+                if !name.chars().next().is_some_and(|c| c.is_uppercase())
+                    || PYTHON_TYPING_NAMES.contains(&name)
+                {
+                    continue;
+                }
+                type_references.push(TypeReference {
+                    name: name.to_string(),
+                    module,
+                    ..type_ref.clone()
+                });
+            }
+        }
+        type_references.dedup();
+        result.type_references = type_references;
+    }
+
+    /// Add an import of `package.item` for every imported name that is a module of the package.
+    fn add_submodule_imports(&self, result: &mut AnalysisResult, path: &Path, base_dir: &Path) {
+        let resolver = PythonModuleResolver;
+        let resolves_locally = |module: &str| {
+            resolver
+                .resolve_import(module, path, base_dir)
+                .is_ok_and(|resolved| !resolved.is_external)
+        };
+
+        let mut imports = Vec::with_capacity(result.imports.len());
+        for import in result.imports.drain(..) {
+            if import.items.is_empty() || import.items.iter().any(|item| item == "*") {
+                imports.push(import);
+                continue;
+            }
+
+            let submodules: Vec<Import> = import
+                .items
+                .iter()
+                .map(|item| {
+                    if import.module.ends_with('.') {
+                        format!("{}{item}", import.module)
+                    } else {
+                        format!("{}.{item}", import.module)
+                    }
+                })
+                .filter(|module| resolves_locally(module))
+                .map(|module| Import {
+                    module,
+                    items: Vec::new(),
+                    is_relative: import.is_relative,
+                    line: import.line,
+                })
+                .collect();
+
+            // A namespace package has no file of its own, only its submodules
+            if submodules.is_empty() || resolves_locally(&import.module) {
+                imports.push(import);
+            }
+            imports.extend(submodules);
+        }
+        result.imports = imports;
+    }
+
+    /// Resolve each type imported from a local module to the file that defines it.
+    fn resolve_imported_types(&self, result: &mut AnalysisResult, path: &Path, base_dir: &Path) {
+        let resolver = PythonModuleResolver;
+        for type_ref in &mut result.type_references {
+            if type_ref.definition_path.is_some() {
+                continue;
+            }
+            let Some(module) = type_ref.module.as_deref() else {
+                continue;
+            };
+            let Ok(resolved) = resolver.resolve_import(module, path, base_dir) else {
+                continue;
+            };
+            if resolved.is_external {
+                continue;
+            }
+
+            let definition = python_definition_file(&type_ref.name, &resolved.path, base_dir, 0)
+                .unwrap_or(resolved.path);
+            type_ref.definition_path = validate_import_path(base_dir, &definition).ok();
+        }
+    }
+
     /// Correlate type references with imports to populate module information
     fn correlate_types_with_imports(&self, result: &mut AnalysisResult) {
-        use std::collections::HashMap;
-
         // Create a mapping from imported type names to their module paths
         let mut type_to_module: HashMap<String, String> = HashMap::new();
+        // Modules imported by name, as in "from shop import models" for "models.Order"
+        let mut imported_modules: HashMap<String, String> = HashMap::new();
 
         for import in &result.imports {
             // Handle "from module import Type" style imports
@@ -82,6 +243,10 @@ impl PythonAnalyzer {
                     // We'll check if they start with uppercase (convention for classes)
                     if item.chars().next().is_some_and(|c| c.is_uppercase()) {
                         type_to_module.insert(item.clone(), import.module.clone());
+                    } else if import.module.ends_with('.') {
+                        imported_modules.insert(item.clone(), format!("{}{item}", import.module));
+                    } else {
+                        imported_modules.insert(item.clone(), format!("{}.{item}", import.module));
                     }
                 }
             } else if !import.module.is_empty() {
@@ -93,6 +258,12 @@ impl PythonAnalyzer {
 
         // Update type references with module information
         for type_ref in &mut result.type_references {
+            if let Some(qualifier) = &type_ref.module {
+                if let Some(module) = imported_modules.get(qualifier) {
+                    type_ref.module = Some(module.clone());
+                }
+                continue;
+            }
             if let Some(module) = type_to_module.get(&type_ref.name) {
                 type_ref.module = Some(module.clone());
             }
@@ -184,49 +355,35 @@ impl ModuleResolver for PythonModuleResolver {
             }
         }
 
-        // Handle absolute imports
-        let parts: Vec<&str> = module_path.split('.').collect();
-
-        // Start from base directory or parent of current file
-        let search_paths = vec![
-            base_dir.to_path_buf(),
-            from_file.parent().unwrap_or(base_dir).to_path_buf(),
-        ];
-
-        for search_path in &search_paths {
-            let mut current_path = search_path.clone();
-
-            // Build path from module parts
-            for (i, part) in parts.iter().enumerate() {
-                current_path = current_path.join(part);
-
-                // Check if this is the final part
-                if i == parts.len() - 1 {
-                    // Try as a Python file
-                    let py_file = current_path.with_extension("py");
-                    if py_file.exists() {
-                        let validated_path = validate_import_path(base_dir, &py_file)?;
-                        return Ok(ResolvedPath {
-                            path: validated_path,
-                            is_external: false,
-                            confidence: 0.8,
-                        });
-                    }
-
-                    // Try as a package directory
-                    let init_path = current_path.join("__init__.py");
-                    if init_path.exists() {
-                        let validated_path = validate_import_path(base_dir, &init_path)?;
-                        return Ok(ResolvedPath {
-                            path: validated_path,
-                            is_external: false,
-                            confidence: 0.8,
-                        });
-                    }
-                }
+        // Handle absolute imports: source roots of the project owning the file come
+        // first, then those of the other projects, then the historical fallbacks
+        let projects = python_projects(base_dir);
+        let mut search_paths = projects.source_roots(from_file);
+        for fallback in [base_dir, from_file.parent().unwrap_or(base_dir)] {
+            if !search_paths.iter().any(|root| root == fallback) {
+                search_paths.push(fallback.to_path_buf());
             }
         }
 
+        let parts: Vec<&str> = module_path.split('.').collect();
+        let found = projects
+            .find_package_dir(&parts)
+            .into_iter()
+            .chain(
+                search_paths
+                    .iter()
+                    .filter_map(|root| find_module_file(root, &parts)),
+            )
+            .next();
+        if let Some(resolved) = found {
+            let validated_path = validate_import_path(base_dir, &resolved)?;
+            return Ok(ResolvedPath {
+                path: validated_path,
+                is_external: false,
+                confidence: 0.8,
+            });
+        }
+
         // Otherwise, assume it's an external package
         Ok(ResolvedPath {
             path: base_dir.join("requirements.txt"),
@@ -388,3 +545,501 @@ impl ModuleResolver for PythonModuleResolver {
         stdlib_modules.contains(&first_part) || third_party.contains(&first_part)
     }
 }
+
+fn is_python_identifier(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_alphanumeric() || c == '_')
+}
+
+/// Find the module file for `parts` under a source root: `a/b.py`, `a/b/__init__.py`, or a stub.
+fn find_module_file(root: &Path, parts: &[&str]) -> Option<PathBuf> {
+    if parts.iter().any(|part| !is_python_identifier(part)) {
+        return None;
+    }
+    let module_dir = parts
+        .iter()
+        .fold(root.to_path_buf(), |dir, part| dir.join(part));
+    let mut candidates = Vec::with_capacity(4);
+    if !parts.is_empty() {
+        candidates.push(module_dir.with_extension("py"));
+    }
+    candidates.push(module_dir.join("__init__.py"));
+    if !parts.is_empty() {
+        candidates.push(module_dir.with_extension("pyi"));
+    }
+    candidates.push(module_dir.join("__init__.pyi"));
+    candidates.into_iter().find(|candidate| candidate.is_file())
+}
+
+/// Find the file defining `name` in a module, following re-exports such as
+/// `from .models import User` or `from .order import *` in `__init__.py`.
+fn python_definition_file(
+    name: &str,
+    module_file: &Path,
+    base_dir: &Path,
+    depth: usize,
+) -> Option<PathBuf> {
+    if depth > MAX_REEXPORT_DEPTH {
+        return None;
+    }
+    let content = fs::read_to_string(module_file).ok()?;
+    let mut parser = Parser::new();
+    parser.set_language(tree_sitter_python::language()).ok()?;
+    let tree = parser.parse(&content, None)?;
+    let root = tree.root_node();
+
+    let mut wildcard_modules = Vec::new();
+    let mut cursor = root.walk();
+    for statement in root.named_children(&mut cursor) {
+        let statement = if statement.kind() == "decorated_definition" {
+            match statement.child_by_field_name("definition") {
+                Some(definition) => definition,
+                None => continue,
+            }
+        } else {
+            statement
+        };
+
+        match statement.kind() {
+            "class_definition" | "function_definition"
+                if node_text(statement.child_by_field_name("name"), &content) == Some(name) =>
+            {
+                return Some(module_file.to_path_buf());
+            }
+            "expression_statement" => {
+                let assigned = statement
+                    .named_child(0)
+                    .filter(|child| child.kind() == "assignment")
+                    .and_then(|assignment| assignment.child_by_field_name("left"));
+                if node_text(assigned, &content) == Some(name) {
+                    return Some(module_file.to_path_buf());
+                }
+            }
+            "import_from_statement" => {
+                let Some(module) =
+                    node_text(statement.child_by_field_name("module_name"), &content)
+                else {
+                    continue;
+                };
+                let mut names = statement.walk();
+                let imported = statement
+                    .children_by_field_name("name", &mut names)
+                    .find_map(|item| reexported_name(item, name, &content));
+                if let Some(original) = imported {
+                    let source = python_local_module(module, module_file, base_dir)?;
+                    return python_definition_file(original, &source, base_dir, depth + 1)
+                        .or(Some(source));
+                }
+                let mut children = statement.walk();
+                if statement
+                    .named_children(&mut children)
+                    .any(|child| child.kind() == "wildcard_import")
+                {
+                    wildcard_modules.push(module.to_string());
+                }
+            }
+            _ => {}
+        }
+    }
+
+    wildcard_modules.iter().find_map(|module| {
+        let source = python_local_module(module, module_file, base_dir)?;
+        python_definition_file(name, &source, base_dir, depth + 1)
+    })
+}
+
+/// The original name of `name` when an import item binds it: `User` or `BaseUser as User`.
+fn reexported_name<'a>(item: Node, name: &str, content: &'a str) -> Option<&'a str> {
+    match item.kind() {
+        "dotted_name" => node_text(Some(item), content).filter(|text| *text == name),
+        "aliased_import" => {
+            if node_text(item.child_by_field_name("alias"), content) == Some(name) {
+                node_text(item.child_by_field_name("name"), content)
+            } else {
+                None
+            }
+        }
+        _ => None,
+    }
+}
+
+fn python_local_module(module: &str, from_file: &Path, base_dir: &Path) -> Option<PathBuf> {
+    PythonModuleResolver
+        .resolve_import(module, from_file, base_dir)
+        .ok()
+        .filter(|resolved| !resolved.is_external)
+        .map(|resolved| resolved.path)
+}
+
+fn node_text<'a>(node: Option<Node>, content: &'a str) -> Option<&'a str> {
+    node.and_then(|node| node.utf8_text(content.as_bytes()).ok())
+}
+
+/// A Python project and the directories its packages are imported from.
+#[derive(Debug)]
+struct PythonProject {
+    root: PathBuf,
+    source_roots: Vec<PathBuf>,
+    /// Packages mapped to their own directory, as in `package-dir = {"shop" = "lib"}`
+    package_dirs: HashMap<String, PathBuf>,
+}
+
+/// Every project in the repository, including local packages installed in editable mode,
+/// so imports across a monorepo resolve to the sources of the sibling package.
+#[derive(Debug, Default)]
+struct PythonProjects {
+    projects: Vec<PythonProject>,
+}
+
+/// Package layout read from the project configuration files of one directory.
+#[derive(Debug, Default)]
+struct PythonLayout {
+    source_dirs: Vec<String>,
+    package_dirs: Vec<(String, String)>,
+    editable_paths: Vec<String>,
+}
+
+impl PythonProjects {
+    fn build(base_dir: &Path) -> Self {
+        let mut layouts: HashMap<PathBuf, PythonLayout> = HashMap::new();
+
+        let walker = walkdir::WalkDir::new(base_dir)
+            .max_depth(8)
+            .sort_by_file_name()
+            .into_iter()
+            .filter_entry(|entry| {
+                entry.depth() == 0
+                    || !entry.file_name().to_str().is_some_and(|name| {
+                        name.starts_with('.') || PYTHON_SKIPPED_DIRS.contains(&name)
+                    })
+            });
+
+        for entry in walker.flatten() {
+            let path = entry.path();
+            let Some(file_name) = entry.file_name().to_str() else {
+                continue;
+            };
+            let is_requirements =
+                file_name.starts_with("requirements") && file_name.ends_with(".txt");
+            if !entry.file_type().is_file()
+                || !(PYTHON_PROJECT_FILES.contains(&file_name) || is_requirements)
+            {
+                continue;
+            }
+            let (Some(root), Ok(content)) = (path.parent(), fs::read_to_string(path)) else {
+                continue;
+            };
+
+            let layout = layouts.entry(root.to_path_buf()).or_default();
+            match file_name {
+                "pyproject.toml" => read_pyproject_layout(&content, layout),
+                "setup.cfg" => read_setup_cfg_layout(&content, layout),
+                "setup.py" => read_setup_py_layout(&content, layout),
+                _ => layout
+                    .editable_paths
+                    .extend(requirements_editable_paths(&content)),
+            }
+        }
+
+        // Editable path dependencies are projects even without configuration of their own
+        let editable_roots: Vec<PathBuf> = layouts
+            .iter()
+            .flat_map(|(root, layout)| {
+                layout
+                    .editable_paths
+                    .iter()
+                    .map(move |relative| join_lexically(root, relative))
+            })
+            .filter(|root| root.starts_with(base_dir) && root.is_dir())
+            .collect();
+        for root in editable_roots {
+            layouts.entry(root).or_default();
+        }
+        layouts.entry(base_dir.to_path_buf()).or_default();
+
+        let mut projects: Vec<PythonProject> = layouts
+            .into_iter()
+            .map(|(root, layout)| {
+                let mut source_roots: Vec<PathBuf> = layout
+                    .source_dirs
+                    .iter()
+                    .map(|dir| join_lexically(&root, dir))
+                    .filter(|dir| dir.starts_with(&root) && dir.is_dir())
+                    .collect();
+                if source_roots.is_empty() {
+                    // Without configuration both the `src/` and the flat layout are common
+                    let src = root.join("src");
+                    if src.is_dir() {
+                        source_roots.push(src);
+                    }
+                    source_roots.push(root.clone());
+                }
+                let package_dirs = layout
+                    .package_dirs
+                    .iter()
+                    .map(|(package, dir)| (package.clone(), join_lexically(&root, dir)))
+                    .filter(|(_, dir)| dir.starts_with(&root) && dir.is_dir())
+                    .collect();
+                PythonProject {
+                    source_roots: source_roots
+                        .into_iter()
+                        .map(|dir| dir.canonicalize().unwrap_or(dir))
+                        .collect(),
+                    root: root.canonicalize().unwrap_or(root),
+                    package_dirs,
+                }
+            })
+            .collect();
+        projects.sort_by(|left, right| left.root.cmp(&right.root));
+
+        Self { projects }
+    }
+
+    /// Source roots of the project owning the importing file come first, then those of
+    /// the other projects in the repository.
+    fn source_roots(&self, from_file: &Path) -> Vec<PathBuf> {
+        let from_file = from_file
+            .canonicalize()
+            .unwrap_or_else(|_| from_file.to_path_buf());
+        let owner = self
+            .projects
+            .iter()
+            .filter(|project| from_file.starts_with(&project.root))
+            .max_by_key(|project| project.root.components().count());
+
+        let mut roots: Vec<PathBuf> = Vec::new();
+        for project in owner.into_iter().chain(
+            self.projects
+                .iter()
+                .filter(|project| owner.map_or(true, |owner| owner.root != project.root)),
+        ) {
+            for root in &project.source_roots {
+                if !roots.contains(root) {
+                    roots.push(root.clone());
+                }
+            }
+        }
+        roots
+    }
+
+    fn find_package_dir(&self, parts: &[&str]) -> Option<PathBuf> {
+        let (package, rest) = parts.split_first()?;
+        self.projects
+            .iter()
+            .filter_map(|project| project.package_dirs.get(*package))
+            .find_map(|dir| find_module_file(dir, rest))
+    }
+}
+
+fn read_pyproject_layout(content: &str, layout: &mut PythonLayout) {
+    let Ok(pyproject) = content.parse::<toml::Table>() else {
+        return;
+    };
+    let tool = |path: &[&str]| {
+        path.iter()
+            .try_fold(pyproject.get("tool")?, |value, key| value.get(*key))
+    };
+    let strings = |value: Option<&toml::Value>| -> Vec<String> {
+        match value {
+            Some(toml::Value::String(dir)) => vec![dir.clone()],
+            Some(toml::Value::Array(dirs)) => dirs
+                .iter()
+                .filter_map(|dir| dir.as_str().map(str::to_string))
+                .collect(),
+            _ => Vec::new(),
+        }
+    };
+
+    // setuptools
+    if let Some(package_dir) = tool(&["setuptools", "package-dir"]).and_then(|v| v.as_table()) {
+        for (package, dir) in package_dir {
+            if let Some(dir) = dir.as_str() {
+                add_package_dir(layout, package, dir);
+            }
+        }
+    }
+    layout
+        .source_dirs
+        .extend(strings(tool(&["setuptools", "packages", "find", "where"])));
+
+    // Poetry: packages = [{ include = "shop", from = "src" }]
+    if let Some(packages) = tool(&["poetry", "packages"]).and_then(|v| v.as_array()) {
+        for package in packages {
+            if let Some(dir) = package.get("from").and_then(|v| v.as_str()) {
+                layout.source_dirs.push(dir.to_string());
+            } else if package.get("include").is_some() {
+                layout.source_dirs.push(".".to_string());
+            }
+        }
+    }
+
+    // Hatch lists package directories, which are imported from their parent
+    for package in strings(tool(&["hatch", "build", "targets", "wheel", "packages"])) {
+        let parent = Path::new(&package)
+            .parent()
+            .and_then(|parent| parent.to_str())
+            .filter(|parent| !parent.is_empty())
+            .unwrap_or(".");
+        layout.source_dirs.push(parent.to_string());
+    }
+
+    // PDM and maturin
+    layout
+        .source_dirs
+        .extend(strings(tool(&["pdm", "build", "package-dir"])));
+    layout
+        .source_dirs
+        .extend(strings(tool(&["maturin", "python-source"])));
+
+    // Path dependencies of uv and Poetry are installed in editable mode
+    let mut dependency_tables: Vec<&toml::Value> = Vec::new();
+    dependency_tables.extend(tool(&["uv", "sources"]));
+    dependency_tables.extend(tool(&["poetry", "dependencies"]));
+    dependency_tables.extend(tool(&["poetry", "dev-dependencies"]));
+    if let Some(groups) = tool(&["poetry", "group"]).and_then(|v| v.as_table()) {
+        dependency_tables.extend(
+            groups
+                .values()
+                .filter_map(|group| group.get("dependencies")),
+        );
+    }
+    for table in dependency_tables
+        .iter()
+        .filter_map(|table| table.as_table())
+    {
+        layout.editable_paths.extend(
+            table
+                .values()
+                .filter_map(|source| source.get("path")?.as_str().map(str::to_string)),
+        );
+    }
+}
+
+/// Read `package_dir` and `[options.packages.find] where` from a `setup.cfg`.
+fn read_setup_cfg_layout(content: &str, layout: &mut PythonLayout) {
+    let mut section = String::new();
+    let mut key = String::new();
+    for line in content.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') || trimmed.starts_with(';') {
+            continue;
+        }
+        if trimmed.starts_with('[') && trimmed.ends_with(']') {
+            section = trimmed[1..trimmed.len() - 1].trim().to_string();
+            key.clear();
+            continue;
+        }
+
+        // Indented lines continue the value of the previous key
+        let value = if line.starts_with(char::is_whitespace) && !key.is_empty() {
+            trimmed
+        } else if let Some((name, value)) = trimmed.split_once('=').or(trimmed.split_once(':')) {
+            key = name.trim().to_string();
+            value.trim()
+        } else {
+            continue;
+        };
+        if value.is_empty() {
+            continue;
+        }
+
+        match (section.as_str(), key.as_str()) {
+            ("options", "package_dir") => {
+                for entry in value.split(',') {
+                    if let Some((package, dir)) = entry.split_once('=') {
+                        add_package_dir(layout, package.trim(), dir.trim());
+                    }
+                }
+            }
+            ("options.packages.find", "where") => layout.source_dirs.push(value.to_string()),
+            _ => {}
+        }
+    }
+}
+
+/// Read the `package_dir={"": "src"}` argument of a `setup.py`.
+fn read_setup_py_layout(content: &str, layout: &mut PythonLayout) {
+    let Some(start) = content.find("package_dir") else {
+        return;
+    };
+    let rest = &content[start..];
+    let (Some(open), Some(close)) = (rest.find('{'), rest.find('}')) else {
+        return;
+    };
+    if open > close {
+        return;
+    }
+    for entry in rest[open + 1..close].split(',') {
+        if let Some((package, dir)) = entry.split_once(':') {
+            let unquote = |text: &str| {
+                text.trim()
+                    .trim_matches(|c| c == '"' || c == '\'')
+                    .to_string()
+            };
+            add_package_dir(layout, &unquote(package), &unquote(dir));
+        }
+    }
+}
+
+/// The root package directory (`""`) is a source root; any other maps a single package.
+fn add_package_dir(layout: &mut PythonLayout, package: &str, dir: &str) {
+    if package.is_empty() {
+        layout.source_dirs.push(dir.to_string());
+    } else {
+        layout
+            .package_dirs
+            .push((package.to_string(), dir.to_string()));
+    }
+}
+
+/// Local paths installed with `-e ./libs/billing` in a requirements file.
+fn requirements_editable_paths(content: &str) -> Vec<String> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let line = line.trim();
+            line.strip_prefix("-e ")
+                .or_else(|| line.strip_prefix("--editable "))
+                .or_else(|| line.strip_prefix("--editable="))
+        })
+        .map(|path| path.trim().trim_start_matches("file:"))
+        .map(|path| path.split(['#', '[']).next().unwrap_or(path).trim())
+        .filter(|path| path.starts_with('.') || path.starts_with('/'))
+        .map(str::to_string)
+        .collect()
+}
+
+/// Join a configured directory onto a project root, resolving `..` lexically.
+fn join_lexically(root: &Path, relative: &str) -> PathBuf {
+    let mut path = root.to_path_buf();
+    for component in Path::new(relative).components() {
+        match component {
+            Component::ParentDir => {
+                path.pop();
+            }
+            Component::Normal(segment) => path.push(segment),
+            _ => {}
+        }
+    }
+    path
+}
+
+fn python_project_cache() -> &'static Mutex<HashMap<PathBuf, Arc<PythonProjects>>> {
+    static CACHE: OnceLock<Mutex<HashMap<PathBuf, Arc<PythonProjects>>>> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn python_projects(base_dir: &Path) -> Arc<PythonProjects> {
+    if let Some(projects) = python_project_cache()
+        .lock()
+        .ok()
+        .and_then(|cache| cache.get(base_dir).cloned())
+    {
+        return projects;
+    }
+
+    let projects = Arc::new(PythonProjects::build(base_dir));
+    if let Ok(mut cache) = python_project_cache().lock() {
+        cache.insert(base_dir.to_path_buf(), projects.clone());
+    }
+    projects
+}
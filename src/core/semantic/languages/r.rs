@@ -1,23 +1,45 @@
-//! Semantic analyzer for R
+//! Semantic analyzer for R scripts and packages, following `source()` to local files
+//! and `library()` and `pkg::fn` to packages in the project or on CRAN
 
-use crate::core::semantic::analyzer::{
-    AnalysisResult, LanguageAnalyzer, SemanticContext, SemanticResult,
+use crate::core::semantic::{
+    analyzer::{
+        AnalysisResult, Import, LanguageAnalyzer, SemanticContext, SemanticResult, TypeReference,
+    },
+    path_validator::{validate_import_path, validate_module_name},
+    query_engine::QueryEngine,
+    resolver::{ModuleResolver, ResolvedPath},
 };
-use std::path::Path;
-use tree_sitter::Parser;
+use crate::utils::error::ContextCreatorError;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+use tree_sitter::{Node, Parser};
+
+/// Calls that load code rather than call into it.
+const R_LOADERS: &[&str] = &[
+    "source",
+    "sys.source",
+    "library",
+    "require",
+    "requireNamespace",
+    "loadNamespace",
+];
+
+/// Directories holding package libraries, build output, or editor state.
+const R_SKIPPED_DIRS: &[&str] = &["renv", "packrat", "node_modules", "revdep"];
 
 #[allow(clippy::new_without_default)]
 pub struct RAnalyzer {
-    #[allow(dead_code)]
-    parser: Parser,
+    query_engine: QueryEngine,
 }
 
 impl RAnalyzer {
     pub fn new() -> Self {
-        let parser = Parser::new();
-        // Note: tree_sitter_r::language() would be used here when the crate is added
-        // parser.set_language(tree_sitter_r::language()).unwrap();
-        Self { parser }
+        let language = tree_sitter_r::language();
+        let query_engine =
+            QueryEngine::new(language, "r").expect("Failed to create R query engine");
+        Self { query_engine }
     }
 }
 
@@ -28,12 +50,29 @@ impl LanguageAnalyzer for RAnalyzer {
 
     fn analyze_file(
         &self,
-        _path: &Path,
-        _content: &str,
-        _context: &SemanticContext,
+        path: &Path,
+        content: &str,
+        context: &SemanticContext,
     ) -> SemanticResult<AnalysisResult> {
-        // TODO: Implement R analysis
-        Ok(AnalysisResult::default())
+        let mut parser = Parser::new();
+        parser
+            .set_language(tree_sitter_r::language())
+            .map_err(|e| ContextCreatorError::ParseError(format!("Failed to set language: {e}")))?;
+
+        let mut result = self
+            .query_engine
+            .analyze_with_parser(&mut parser, content)?;
+
+        result
+            .function_calls
+            .retain(|call| call.module.is_some() || !R_LOADERS.contains(&call.name.as_str()));
+        for type_ref in &mut result.type_references {
+            type_ref.name = type_ref.name.trim_matches(['"', '\'']).to_string();
+        }
+
+        self.resolve_package_references(&mut result, path, &context.base_dir);
+
+        Ok(result)
     }
 
     fn can_handle_extension(&self, extension: &str) -> bool {
@@ -43,4 +82,428 @@ impl LanguageAnalyzer for RAnalyzer {
     fn supported_extensions(&self) -> Vec<&'static str> {
         vec!["r", "R"]
     }
+
+    fn query_engine(&self) -> Option<&QueryEngine> {
+        Some(&self.query_engine)
+    }
+}
+
+impl RAnalyzer {
+    /// Packages used through `library()` or `pkg::fn` are recorded as external type
+    /// references unless the project contains them, in which case `pkg::fn` calls import
+    /// the file defining `fn`. S4 and R6 classes resolve to the file defining them.
+    fn resolve_package_references(
+        &self,
+        result: &mut AnalysisResult,
+        path: &Path,
+        project_root: &Path,
+    ) {
+        let index = r_project_index(project_root);
+
+        let mut packages: Vec<(String, usize)> = result
+            .imports
+            .iter()
+            .filter(|import| !import.is_relative)
+            .map(|import| (import.module.clone(), import.line))
+            .collect();
+        let mut imported: HashSet<String> = result
+            .imports
+            .iter()
+            .map(|import| import.module.clone())
+            .collect();
+
+        for call in &result.function_calls {
+            let Some(package) = call.module.as_deref() else {
+                continue;
+            };
+            packages.push((package.to_string(), call.line));
+
+            let qualified = format!("{package}::{}", call.name);
+            if index.function_file(package, &call.name).is_some()
+                && imported.insert(qualified.clone())
+            {
+                result.imports.push(Import {
+                    module: qualified,
+                    items: vec![call.name.clone()],
+                    is_relative: false,
+                    line: call.line,
+                });
+            }
+        }
+
+        let current_file = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+        result
+            .type_references
+            .retain_mut(|type_ref| match index.classes.get(&type_ref.name) {
+                Some(file) if *file != current_file => {
+                    type_ref.definition_path = validate_import_path(project_root, file).ok();
+                    type_ref.definition_path.is_some()
+                }
+                _ => false,
+            });
+
+        let mut recorded = HashSet::new();
+        for (package, line) in packages {
+            if index.packages.contains_key(&package) || !recorded.insert(package.clone()) {
+                continue;
+            }
+            result.type_references.push(TypeReference {
+                name: package.clone(),
+                module: None,
+                line,
+                definition_path: None,
+                is_external: true,
+                external_package: Some(package),
+            });
+        }
+    }
+}
+
+pub struct RModuleResolver;
+
+impl ModuleResolver for RModuleResolver {
+    fn resolve_import(
+        &self,
+        module_path: &str,
+        from_file: &Path,
+        base_dir: &Path,
+    ) -> Result<ResolvedPath, ContextCreatorError> {
+        let index = r_project_index(base_dir);
+
+        // `pkg::fn` calls into a package of the project
+        if let Some((package, function)) = module_path.split_once("::") {
+            validate_module_name(module_path)?;
+            if let Some(file) = index.function_file(package, function) {
+                return Ok(ResolvedPath {
+                    path: validate_import_path(base_dir, file)?,
+                    is_external: false,
+                    confidence: 0.9,
+                });
+            }
+            return Ok(external_r_path(from_file, base_dir, 0.8));
+        }
+
+        // Scripts are sourced relative to the working directory, which is usually the
+        // script's own directory or one of the project directories above it
+        if module_path.contains('/') || module_path.ends_with(".R") || module_path.ends_with(".r") {
+            validate_source_path(module_path)?;
+            let candidate = from_file
+                .ancestors()
+                .skip(1)
+                .take_while(|dir| dir.starts_with(base_dir))
+                .map(|dir| dir.join(module_path))
+                .find(|candidate| candidate.is_file());
+            return match candidate {
+                Some(file) => Ok(ResolvedPath {
+                    path: validate_import_path(base_dir, &file)?,
+                    is_external: false,
+                    confidence: 0.9,
+                }),
+                None => Ok(external_r_path(from_file, base_dir, 0.3)),
+            };
+        }
+
+        validate_module_name(module_path)?;
+        if let Some(package) = index.packages.get(module_path) {
+            return Ok(ResolvedPath {
+                path: validate_import_path(base_dir, &package.entry_file())?,
+                is_external: false,
+                confidence: 0.9,
+            });
+        }
+
+        Ok(external_r_path(
+            from_file,
+            base_dir,
+            if self.is_external_module(module_path) {
+                0.9
+            } else {
+                0.7
+            },
+        ))
+    }
+
+    fn get_file_extensions(&self) -> Vec<&'static str> {
+        vec!["R", "r"]
+    }
+
+    fn is_external_module(&self, module_path: &str) -> bool {
+        let package = module_path.split("::").next().unwrap_or(module_path);
+        matches!(
+            package,
+            "base"
+                | "stats"
+                | "utils"
+                | "methods"
+                | "graphics"
+                | "grDevices"
+                | "datasets"
+                | "tools"
+                | "parallel"
+                | "grid"
+                | "splines"
+                | "compiler"
+                | "MASS"
+                | "Matrix"
+                | "survival"
+                | "dplyr"
+                | "tidyr"
+                | "ggplot2"
+                | "readr"
+                | "purrr"
+                | "tibble"
+                | "stringr"
+                | "forcats"
+                | "lubridate"
+                | "magrittr"
+                | "data.table"
+                | "jsonlite"
+                | "httr"
+                | "shiny"
+                | "R6"
+                | "rlang"
+                | "testthat"
+                | "here"
+                | "knitr"
+                | "rmarkdown"
+        )
+    }
+}
+
+/// Unresolved packages are declared in the `DESCRIPTION` of the package using them, or
+/// pinned in the project's `renv.lock`.
+fn external_r_path(from_file: &Path, base_dir: &Path, confidence: f32) -> ResolvedPath {
+    let manifest = from_file
+        .ancestors()
+        .skip(1)
+        .take_while(|dir| dir.starts_with(base_dir))
+        .flat_map(|dir| [dir.join("DESCRIPTION"), dir.join("renv.lock")])
+        .find(|path| path.is_file())
+        .unwrap_or_else(|| base_dir.to_path_buf());
+
+    ResolvedPath {
+        path: manifest,
+        is_external: true,
+        confidence,
+    }
+}
+
+/// Sourced paths may climb with `../`; containment is checked after resolution.
+fn validate_source_path(source: &str) -> Result<(), ContextCreatorError> {
+    let path = Path::new(source);
+    if source.is_empty()
+        || source.len() > 255
+        || source.contains('\0')
+        || source.contains("://")
+        || path.is_absolute()
+        || path
+            .components()
+            .any(|component| matches!(component, Component::Prefix(_) | Component::RootDir))
+    {
+        return Err(ContextCreatorError::SecurityError(format!(
+            "Invalid source path: {source}"
+        )));
+    }
+
+    Ok(())
+}
+
+/// The root of the R package whose `R/` directory holds a file, identified by the
+/// `DESCRIPTION` next to that directory.
+pub(crate) fn r_package_root(file: &Path) -> Option<PathBuf> {
+    let code_dir = file.parent()?;
+    if code_dir.file_name()? != "R" {
+        return None;
+    }
+    let root = code_dir.parent()?;
+    root.join("DESCRIPTION")
+        .is_file()
+        .then(|| root.to_path_buf())
+}
+
+/// The `Package:` field of a `DESCRIPTION` file.
+pub(crate) fn r_package_name(package_root: &Path) -> Option<String> {
+    let description = fs::read_to_string(package_root.join("DESCRIPTION")).ok()?;
+    description.lines().find_map(|line| {
+        let name = line.strip_prefix("Package:")?.trim();
+        (!name.is_empty()).then(|| name.to_string())
+    })
+}
+
+/// A package in the project and the top-level functions its `R/` files define.
+#[derive(Debug, Default)]
+struct RPackage {
+    root: PathBuf,
+    functions: HashMap<String, PathBuf>,
+}
+
+impl RPackage {
+    /// The file `library(pkg)` stands for: the package documentation file by
+    /// convention, else the first file in `R/`, else the `DESCRIPTION`.
+    fn entry_file(&self) -> PathBuf {
+        let code_dir = self.root.join("R");
+        let name = r_package_name(&self.root).unwrap_or_default();
+        [format!("{name}-package.R"), format!("{name}.R")]
+            .into_iter()
+            .map(|file| code_dir.join(file))
+            .find(|file| file.is_file())
+            .or_else(|| {
+                let mut files: Vec<PathBuf> = self.functions.values().cloned().collect();
+                files.sort();
+                files.into_iter().next()
+            })
+            .unwrap_or_else(|| self.root.join("DESCRIPTION"))
+    }
+}
+
+/// Packages and classes defined anywhere in the project.
+#[derive(Debug, Default)]
+struct RProjectIndex {
+    packages: HashMap<String, RPackage>,
+    /// S4, reference, and R6 class names to the file defining them.
+    classes: HashMap<String, PathBuf>,
+}
+
+impl RProjectIndex {
+    fn build(base_dir: &Path) -> Self {
+        let mut index = Self::default();
+        let mut parser = Parser::new();
+        if parser.set_language(tree_sitter_r::language()).is_err() {
+            return index;
+        }
+
+        let walker = walkdir::WalkDir::new(base_dir)
+            .max_depth(16)
+            .sort_by_file_name()
+            .into_iter()
+            .filter_entry(|entry| {
+                entry.depth() == 0
+                    || !entry
+                        .file_name()
+                        .to_str()
+                        .is_some_and(|name| name.starts_with('.') || R_SKIPPED_DIRS.contains(&name))
+            });
+
+        for entry in walker.flatten() {
+            let path = entry.path();
+            if !entry.file_type().is_file()
+                || !matches!(
+                    path.extension().and_then(|ext| ext.to_str()),
+                    Some("R" | "r")
+                )
+            {
+                continue;
+            }
+            let Ok(content) = fs::read_to_string(path) else {
+                continue;
+            };
+            let Some(tree) = parser.parse(&content, None) else {
+                continue;
+            };
+
+            let file = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+            let (functions, classes) = r_top_level_definitions(tree.root_node(), &content);
+            for class in classes {
+                index.classes.entry(class).or_insert_with(|| file.clone());
+            }
+
+            let Some(root) = r_package_root(&file) else {
+                continue;
+            };
+            let Some(name) = r_package_name(&root) else {
+                continue;
+            };
+            let package = index.packages.entry(name).or_insert_with(|| RPackage {
+                root,
+                functions: HashMap::new(),
+            });
+            for function in functions {
+                package
+                    .functions
+                    .entry(function)
+                    .or_insert_with(|| file.clone());
+            }
+        }
+
+        index
+    }
+
+    fn function_file(&self, package: &str, function: &str) -> Option<&PathBuf> {
+        self.packages.get(package)?.functions.get(function)
+    }
+}
+
+/// Top-level function names and class names defined with `setClass()`,
+/// `setRefClass()`, or `R6Class()`.
+fn r_top_level_definitions(root: Node<'_>, content: &str) -> (Vec<String>, Vec<String>) {
+    let text = |node: Node<'_>| {
+        node.utf8_text(content.as_bytes())
+            .ok()
+            .map(|text| text.trim_matches(['"', '\'']).to_string())
+    };
+    let class_name = |call: Node<'_>| {
+        let function = text(call.child_by_field_name("function")?)?;
+        let constructor = function.rsplit("::").next().unwrap_or(&function);
+        if !matches!(constructor, "setClass" | "setRefClass" | "R6Class") {
+            return None;
+        }
+        let name = call
+            .child_by_field_name("arguments")?
+            .child_by_field_name("value")
+            .filter(|value| value.kind() == "string")?;
+        text(name)
+    };
+
+    let mut functions = Vec::new();
+    let mut classes = Vec::new();
+    let mut cursor = root.walk();
+    for node in root.named_children(&mut cursor) {
+        match node.kind() {
+            "left_assignment" | "equals_assignment" | "super_assignment" => {
+                let (Some(name), Some(value)) = (
+                    node.child_by_field_name("name").and_then(text),
+                    node.child_by_field_name("value"),
+                ) else {
+                    continue;
+                };
+                match value.kind() {
+                    "function_definition" => functions.push(name),
+                    "call" => {
+                        if let Some(class) = class_name(value) {
+                            if class != name {
+                                classes.push(name);
+                            }
+                            classes.push(class);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            "call" => classes.extend(class_name(node)),
+            _ => {}
+        }
+    }
+
+    (functions, classes)
+}
+
+fn r_index_cache() -> &'static Mutex<HashMap<PathBuf, Arc<RProjectIndex>>> {
+    static CACHE: OnceLock<Mutex<HashMap<PathBuf, Arc<RProjectIndex>>>> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn r_project_index(base_dir: &Path) -> Arc<RProjectIndex> {
+    if let Some(index) = r_index_cache()
+        .lock()
+        .ok()
+        .and_then(|cache| cache.get(base_dir).cloned())
+    {
+        return index;
+    }
+
+    let index = Arc::new(RProjectIndex::build(base_dir));
+    if let Ok(mut cache) = r_index_cache().lock() {
+        cache.insert(base_dir.to_path_buf(), index.clone());
+    }
+    index
 }
@@ -1,24 +1,48 @@
-//! Semantic analyzer for Ruby
+//! Semantic analyzer for Ruby, with Rails/Zeitwerk autoload conventions
 
-use crate::core::semantic::analyzer::{
-    AnalysisResult, LanguageAnalyzer, SemanticContext, SemanticResult,
+use crate::core::semantic::{
+    analyzer::{
+        AnalysisResult, Import, LanguageAnalyzer, SemanticContext, SemanticResult, TypeReference,
+    },
+    path_validator::{validate_import_path, validate_module_name},
+    query_engine::QueryEngine,
+    resolver::{ModuleResolver, ResolvedPath},
 };
-use std::path::Path;
-use tree_sitter::Parser;
+use crate::utils::error::ContextCreatorError;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use tree_sitter::{Node, Parser};
+
+/// Calls that declare structure rather than invoke project methods.
+const RUBY_DECLARATION_CALLS: &[&str] = &[
+    "require",
+    "require_relative",
+    "load",
+    "autoload",
+    "include",
+    "extend",
+    "prepend",
+    "private",
+    "protected",
+    "public",
+    "private_class_method",
+    "attr_reader",
+    "attr_writer",
+    "attr_accessor",
+];
 
 #[allow(clippy::new_without_default)]
 pub struct RubyAnalyzer {
-    #[allow(dead_code)]
-    #[allow(dead_code)]
-    parser: Parser,
+    query_engine: QueryEngine,
 }
 
 impl RubyAnalyzer {
     pub fn new() -> Self {
-        let parser = Parser::new();
-        // Note: tree_sitter_ruby::language() would be used here when the crate is added
-        // parser.set_language(tree_sitter_ruby::language()).unwrap();
-        Self { parser }
+        let language = tree_sitter_ruby::language();
+        let query_engine =
+            QueryEngine::new(language, "ruby").expect("Failed to create Ruby query engine");
+        Self { query_engine }
     }
 }
 
@@ -29,12 +53,55 @@ impl LanguageAnalyzer for RubyAnalyzer {
 
     fn analyze_file(
         &self,
-        _path: &Path,
-        _content: &str,
-        _context: &SemanticContext,
+        path: &Path,
+        content: &str,
+        context: &SemanticContext,
     ) -> SemanticResult<AnalysisResult> {
-        // TODO: Implement Ruby analysis
-        Ok(AnalysisResult::default())
+        let mut parser = Parser::new();
+        parser
+            .set_language(tree_sitter_ruby::language())
+            .map_err(|e| ContextCreatorError::ParseError(format!("Failed to set language: {e}")))?;
+
+        let tree = parser
+            .parse(content, None)
+            .ok_or_else(|| ContextCreatorError::ParseError("Failed to parse file".to_string()))?;
+        let nesting = ruby_nesting_spans(tree.root_node(), content);
+
+        let mut result = self
+            .query_engine
+            .analyze_with_parser(&mut parser, content)?;
+
+        // Constant receivers match both the qualified and the generic receiver pattern
+        result.dedupe_function_calls(|call| {
+            call.module.is_some() || !RUBY_DECLARATION_CALLS.contains(&call.name.as_str())
+        });
+        self.dedupe_type_references(&mut result);
+        self.mark_relative_requires(&mut result);
+
+        let autoloaded =
+            self.resolve_autoloaded_constants(&mut result, path, &context.base_dir, &nesting);
+        self.query_engine.resolve_type_definitions(
+            &mut result.type_references,
+            path,
+            &context.base_dir,
+        )?;
+
+        // Zeitwerk apps rarely require project files, so autoloaded constants are the imports
+        for (constant_path, line) in autoloaded {
+            let name = constant_path
+                .rsplit("::")
+                .next()
+                .unwrap_or(&constant_path)
+                .to_string();
+            result.imports.push(Import {
+                module: constant_path,
+                items: vec![name],
+                is_relative: false,
+                line,
+            });
+        }
+
+        Ok(result)
     }
 
     fn can_handle_extension(&self, extension: &str) -> bool {
@@ -44,4 +111,365 @@ impl LanguageAnalyzer for RubyAnalyzer {
     fn supported_extensions(&self) -> Vec<&'static str> {
         vec!["rb"]
     }
+
+    fn query_engine(&self) -> Option<&QueryEngine> {
+        Some(&self.query_engine)
+    }
+}
+
+impl RubyAnalyzer {
+    /// `A::B::C` also matches as `A::B`; keep only the full constant path, and the
+    /// qualified reference when several patterns capture the same constant.
+    fn dedupe_type_references(&self, result: &mut AnalysisResult) {
+        for type_ref in &mut result.type_references {
+            if let Some(module) = &type_ref.module {
+                type_ref.module = Some(module.trim_start_matches("::").to_string())
+                    .filter(|module| !module.is_empty());
+            }
+        }
+
+        let scopes: HashSet<(String, usize)> = result
+            .type_references
+            .iter()
+            .filter_map(|type_ref| Some((type_ref.module.clone()?, type_ref.line)))
+            .collect();
+
+        result.dedupe_type_references(|type_ref| {
+            !scopes.contains(&(constant_path(type_ref), type_ref.line))
+        });
+    }
+
+    /// The resolver only sees the module string, so make `require_relative "money"`
+    /// explicitly relative to the requiring file.
+    fn mark_relative_requires(&self, result: &mut AnalysisResult) {
+        for import in &mut result.imports {
+            if import.is_relative && !import.module.starts_with('.') {
+                import.module = format!("./{}", import.module);
+            }
+        }
+    }
+
+    /// Resolve constants the way Ruby looks them up lexically, mapping each candidate to
+    /// a file with Zeitwerk's naming rules. Returns the resolved constant paths and the
+    /// line they were first referenced on.
+    fn resolve_autoloaded_constants(
+        &self,
+        result: &mut AnalysisResult,
+        current_file: &Path,
+        project_root: &Path,
+        nesting: &[NestingSpan],
+    ) -> Vec<(String, usize)> {
+        let roots = ruby_autoload_roots(project_root);
+        let current_file = current_file
+            .canonicalize()
+            .unwrap_or_else(|_| current_file.to_path_buf());
+        let mut resolved: Vec<(String, usize)> = Vec::new();
+
+        for type_ref in &mut result.type_references {
+            if type_ref.definition_path.is_some() || type_ref.is_external {
+                continue;
+            }
+
+            let Some((path, file)) = lexical_scopes(nesting, type_ref.line)
+                .iter()
+                .map(|scope| {
+                    let relative = constant_path(type_ref);
+                    if scope.is_empty() {
+                        relative
+                    } else {
+                        format!("{scope}::{relative}")
+                    }
+                })
+                .find_map(|path| Some((path.clone(), zeitwerk_file(&path, &roots)?)))
+            else {
+                continue;
+            };
+
+            if file.canonicalize().ok().as_ref() == Some(&current_file) {
+                continue;
+            }
+
+            match validate_import_path(project_root, &file) {
+                Ok(validated) => {
+                    type_ref.definition_path = Some(validated);
+                    if !resolved.iter().any(|(existing, _)| existing == &path) {
+                        resolved.push((path, type_ref.line));
+                    }
+                }
+                Err(_) => type_ref.is_external = true,
+            }
+        }
+
+        resolved
+    }
+}
+
+/// A `class`/`module` body and the constant names enclosing it, outermost first.
+struct NestingSpan {
+    start_line: usize,
+    end_line: usize,
+    names: Vec<String>,
+}
+
+fn ruby_nesting_spans(root: Node<'_>, content: &str) -> Vec<NestingSpan> {
+    fn visit(node: Node<'_>, content: &str, names: &[String], spans: &mut Vec<NestingSpan>) {
+        let mut cursor = node.walk();
+        for child in node.named_children(&mut cursor) {
+            let mut child_names = names.to_vec();
+            if matches!(child.kind(), "class" | "module") {
+                if let Some(name) = child
+                    .child_by_field_name("name")
+                    .and_then(|name| name.utf8_text(content.as_bytes()).ok())
+                {
+                    child_names.push(name.trim_start_matches("::").to_string());
+                    spans.push(NestingSpan {
+                        start_line: child.start_position().row + 1,
+                        end_line: child.end_position().row + 1,
+                        names: child_names.clone(),
+                    });
+                }
+            }
+            visit(child, content, &child_names, spans);
+        }
+    }
+
+    let mut spans = Vec::new();
+    visit(root, content, &[], &mut spans);
+    spans
+}
+
+/// Lexical scopes searched for a constant on a line, innermost first, ending with the
+/// top level. A compact `class Billing::Invoice` adds a single scope, as in Ruby.
+fn lexical_scopes(nesting: &[NestingSpan], line: usize) -> Vec<String> {
+    let innermost = nesting
+        .iter()
+        .filter(|span| span.start_line <= line && line <= span.end_line)
+        .max_by_key(|span| span.names.len());
+
+    let mut scopes: Vec<String> = innermost
+        .map(|span| {
+            (1..=span.names.len())
+                .rev()
+                .map(|depth| span.names[..depth].join("::"))
+                .collect()
+        })
+        .unwrap_or_default();
+    scopes.push(String::new());
+    scopes
+}
+
+fn constant_path(type_ref: &TypeReference) -> String {
+    match &type_ref.module {
+        Some(module) => format!("{module}::{}", type_ref.name),
+        None => type_ref.name.clone(),
+    }
+}
+
+/// Directories Zeitwerk treats as top-level namespaces: every `app/*` directory, the
+/// `concerns` directories inside them, and `lib/` for gems and `config.autoload_lib`.
+fn ruby_autoload_roots(project_root: &Path) -> Vec<PathBuf> {
+    let mut roots = Vec::new();
+
+    if let Ok(entries) = fs::read_dir(project_root.join("app")) {
+        let mut app_dirs: Vec<PathBuf> = entries
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.is_dir())
+            .collect();
+        app_dirs.sort();
+        for dir in &app_dirs {
+            roots.push(dir.clone());
+        }
+        for dir in &app_dirs {
+            let concerns = dir.join("concerns");
+            if concerns.is_dir() {
+                roots.push(concerns);
+            }
+        }
+    }
+
+    let lib = project_root.join("lib");
+    if lib.is_dir() {
+        roots.push(lib);
+    }
+
+    roots
+}
+
+/// The file Zeitwerk expects to define a constant path, e.g. `Billing::InvoiceMailer`
+/// in `app/mailers/billing/invoice_mailer.rb`.
+fn zeitwerk_file(constant_path: &str, roots: &[PathBuf]) -> Option<PathBuf> {
+    let relative = constant_path
+        .trim_start_matches("::")
+        .split("::")
+        .map(underscore)
+        .collect::<Vec<_>>()
+        .join("/");
+    if relative.is_empty() {
+        return None;
+    }
+
+    roots
+        .iter()
+        .map(|root| root.join(format!("{relative}.rb")))
+        .find(|candidate| candidate.is_file())
+}
+
+/// ActiveSupport's `underscore`: `HTMLParser` → `html_parser`, `InvoiceMailer` → `invoice_mailer`.
+fn underscore(constant: &str) -> String {
+    let chars: Vec<char> = constant.chars().collect();
+    let mut snake = String::with_capacity(constant.len() + 4);
+
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_uppercase() {
+            let previous = i.checked_sub(1).map(|j| chars[j]);
+            let next = chars.get(i + 1);
+            let starts_word = previous.is_some_and(|p| p.is_lowercase() || p.is_ascii_digit())
+                || (previous.is_some_and(char::is_uppercase)
+                    && next.is_some_and(|n| n.is_lowercase()));
+            if starts_word {
+                snake.push('_');
+            }
+            snake.extend(c.to_lowercase());
+        } else {
+            snake.push(c);
+        }
+    }
+
+    snake
+}
+
+pub struct RubyModuleResolver;
+
+impl ModuleResolver for RubyModuleResolver {
+    fn resolve_import(
+        &self,
+        module_path: &str,
+        from_file: &Path,
+        base_dir: &Path,
+    ) -> Result<ResolvedPath, ContextCreatorError> {
+        // Constant paths come from autoloaded references
+        if module_path.starts_with(|c: char| c.is_uppercase()) {
+            validate_module_name(module_path)?;
+            if let Some(file) = zeitwerk_file(module_path, &ruby_autoload_roots(base_dir)) {
+                return Ok(ResolvedPath {
+                    path: validate_import_path(base_dir, &file)?,
+                    is_external: false,
+                    confidence: 0.9,
+                });
+            }
+            return Ok(external_ruby_path(base_dir));
+        }
+
+        validate_require_path(module_path)?;
+        let file_name = if Path::new(module_path).extension().is_some() {
+            module_path.to_string()
+        } else {
+            format!("{module_path}.rb")
+        };
+
+        let search_dirs = if module_path.starts_with('.') {
+            from_file
+                .parent()
+                .map(Path::to_path_buf)
+                .into_iter()
+                .collect()
+        } else {
+            // $LOAD_PATH for gems and Rails apps, then the working directory for `load`
+            vec![base_dir.join("lib"), base_dir.to_path_buf()]
+        };
+
+        for dir in search_dirs {
+            let candidate = dir.join(&file_name);
+            if candidate.is_file() {
+                return Ok(ResolvedPath {
+                    path: validate_import_path(base_dir, &candidate)?,
+                    is_external: false,
+                    confidence: 0.9,
+                });
+            }
+        }
+
+        Ok(external_ruby_path(base_dir))
+    }
+
+    fn get_file_extensions(&self) -> Vec<&'static str> {
+        vec!["rb"]
+    }
+
+    fn is_external_module(&self, module_path: &str) -> bool {
+        let root = module_path.split('/').next().unwrap_or(module_path);
+        matches!(
+            root,
+            "json"
+                | "set"
+                | "yaml"
+                | "psych"
+                | "date"
+                | "time"
+                | "securerandom"
+                | "net"
+                | "uri"
+                | "open-uri"
+                | "csv"
+                | "digest"
+                | "erb"
+                | "fileutils"
+                | "logger"
+                | "pathname"
+                | "tempfile"
+                | "bigdecimal"
+                | "openssl"
+                | "base64"
+                | "benchmark"
+                | "forwardable"
+                | "ostruct"
+                | "singleton"
+                | "socket"
+                | "stringio"
+                | "timeout"
+                | "zlib"
+                | "rails"
+                | "active_support"
+                | "active_record"
+                | "action_controller"
+                | "bundler"
+                | "rake"
+                | "rspec"
+                | "minitest"
+        )
+    }
+}
+
+/// Unresolved requires name gems, which are declared in the Gemfile.
+fn external_ruby_path(base_dir: &Path) -> ResolvedPath {
+    let gemfile = base_dir.join("Gemfile");
+    ResolvedPath {
+        path: if gemfile.exists() {
+            gemfile
+        } else {
+            base_dir.to_path_buf()
+        },
+        is_external: true,
+        confidence: 0.8,
+    }
+}
+
+/// Require paths may climb with `../`; containment is checked after resolution.
+fn validate_require_path(require: &str) -> Result<(), ContextCreatorError> {
+    let path = Path::new(require);
+    if require.is_empty()
+        || require.len() > 255
+        || require.contains('\0')
+        || path.is_absolute()
+        || path
+            .components()
+            .any(|component| matches!(component, Component::Prefix(_) | Component::RootDir))
+    {
+        return Err(ContextCreatorError::SecurityError(format!(
+            "Invalid require path: {require}"
+        )));
+    }
+
+    Ok(())
 }
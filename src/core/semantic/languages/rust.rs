@@ -1,14 +1,26 @@
 //! Semantic analyzer for Rust
 
 use crate::core::semantic::{
-    analyzer::{AnalysisResult, LanguageAnalyzer, SemanticContext, SemanticResult},
+    analyzer::{AnalysisResult, Import, LanguageAnalyzer, SemanticContext, SemanticResult},
     path_validator::{validate_import_path, validate_module_name},
     query_engine::QueryEngine,
     resolver::{ModuleResolver, ResolvedPath, ResolverUtils},
 };
 use crate::utils::error::ContextCreatorError;
-use std::path::Path;
-use tree_sitter::Parser;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+use tree_sitter::{Node, Parser};
+
+/// Directories holding build output or vendored dependencies.
+const RUST_SKIPPED_DIRS: &[&str] = &["target", "node_modules"];
+
+/// How many `pub use` re-exports are followed before giving up.
+const MAX_REEXPORT_DEPTH: usize = 16;
+
+/// Upper bound on the modules read for a single crate.
+const MAX_CRATE_MODULES: usize = 4096;
 
 #[allow(clippy::new_without_default)]
 pub struct RustAnalyzer {
@@ -16,6 +28,61 @@ pub struct RustAnalyzer {
 }
 
 impl RustAnalyzer {
+    /// Add an import of `module::Item` for every item that a workspace crate defines in
+    /// another file than the module it is imported from, such as a `pub use` re-export.
+    fn add_item_imports(&self, result: &mut AnalysisResult, path: &Path, base_dir: &Path) {
+        let resolver = RustModuleResolver;
+        let mut item_imports = Vec::new();
+        for import in &result.imports {
+            let Ok(Some(module)) =
+                resolver.resolve_through_workspace(&import.module, path, base_dir)
+            else {
+                continue;
+            };
+            for (_, item_path) in rust_imported_items(import) {
+                if let Ok(Some(item)) =
+                    resolver.resolve_through_workspace(&item_path, path, base_dir)
+                {
+                    if item.path != module.path {
+                        item_imports.push(Import {
+                            module: item_path,
+                            items: Vec::new(),
+                            is_relative: import.is_relative,
+                            line: import.line,
+                        });
+                    }
+                }
+            }
+        }
+        result.imports.extend(item_imports);
+    }
+
+    /// Resolve types imported from the current crate or a workspace crate to their files.
+    fn resolve_imported_types(&self, result: &mut AnalysisResult, path: &Path, base_dir: &Path) {
+        let imported: HashMap<String, String> = result
+            .imports
+            .iter()
+            .flat_map(rust_imported_items)
+            .collect();
+
+        let resolver = RustModuleResolver;
+        for type_ref in &mut result.type_references {
+            if type_ref.definition_path.is_some() || type_ref.is_external {
+                continue;
+            }
+            let item_path = match (imported.get(&type_ref.name), type_ref.module.as_deref()) {
+                (Some(item_path), _) => item_path.clone(),
+                (None, Some(module)) => format!("{module}::{}", type_ref.name),
+                (None, None) => continue,
+            };
+            if let Ok(Some(resolved)) =
+                resolver.resolve_through_workspace(&item_path, path, base_dir)
+            {
+                type_ref.definition_path = Some(resolved.path);
+            }
+        }
+    }
+
     pub fn new() -> Self {
         let language = tree_sitter_rust::language();
         let query_engine =
@@ -47,6 +114,10 @@ impl LanguageAnalyzer for RustAnalyzer {
         // Correlate type references with imports to populate module information
         self.correlate_types_with_imports(&mut result);
 
+        // Follow the module tree and `pub use` re-exports to the file defining each type
+        self.resolve_imported_types(&mut result, path, &context.base_dir);
+        self.add_item_imports(&mut result, path, &context.base_dir);
+
         // Resolve type definitions for the type references found
         self.query_engine.resolve_type_definitions(
             &mut result.type_references,
@@ -64,13 +135,15 @@ impl LanguageAnalyzer for RustAnalyzer {
     fn supported_extensions(&self) -> Vec<&'static str> {
         vec!["rs"]
     }
+
+    fn query_engine(&self) -> Option<&QueryEngine> {
+        Some(&self.query_engine)
+    }
 }
 
 impl RustAnalyzer {
     /// Correlate type references with imports to populate module information
     fn correlate_types_with_imports(&self, result: &mut AnalysisResult) {
-        use std::collections::HashMap;
-
         // Create a mapping from imported type names to their module paths
         let mut type_to_module: HashMap<String, String> = HashMap::new();
 
@@ -134,6 +207,12 @@ impl ModuleResolver for RustModuleResolver {
         // Validate module name for security
         validate_module_name(module_path)?;
 
+        // Paths into the current crate or a workspace crate resolve through the module
+        // tree, following `pub use` re-exports to the file defining the item
+        if let Some(resolved) = self.resolve_through_workspace(module_path, from_file, base_dir)? {
+            return Ok(resolved);
+        }
+
         // Handle current crate imports FIRST (e.g., my_lib::module)
         // Check if this might be the current crate by looking for Cargo.toml
         let cargo_path = base_dir.join("Cargo.toml");
@@ -449,6 +528,84 @@ impl ModuleResolver for RustModuleResolver {
 }
 
 impl RustModuleResolver {
+    fn is_child_module(
+        &self,
+        workspace: &RustWorkspace,
+        krate: usize,
+        name: &str,
+        from_file: &Path,
+    ) -> bool {
+        let from_file = from_file
+            .canonicalize()
+            .unwrap_or_else(|_| from_file.to_path_buf());
+        let tree = workspace.modules(krate);
+        match tree.module_for_file(&from_file) {
+            Some(module) => tree.modules[module].children.contains_key(name),
+            None => from_file.parent().is_some_and(|dir| {
+                dir.join(format!("{name}.rs")).is_file() || dir.join(name).join("mod.rs").is_file()
+            }),
+        }
+    }
+
+    fn resolve_through_workspace(
+        &self,
+        module_path: &str,
+        from_file: &Path,
+        base_dir: &Path,
+    ) -> Result<Option<ResolvedPath>, ContextCreatorError> {
+        let segments = rust_path_segments(module_path);
+        let Some(first) = segments.first() else {
+            return Ok(None);
+        };
+        // A single segment without `::` is only external for the standard library crates
+        if self.is_external_module(first) {
+            return Ok(None);
+        }
+
+        let workspace = rust_workspace(base_dir);
+        let Some(krate) = workspace.owning_crate(from_file) else {
+            return Ok(None);
+        };
+
+        let target = match first.as_str() {
+            // Relative paths need the module of the importing file, which binaries and
+            // examples outside the crate's module tree don't have
+            "crate" | "self" | "super" => {
+                let from_file = from_file
+                    .canonicalize()
+                    .unwrap_or_else(|_| from_file.to_path_buf());
+                let Some(module) = workspace.modules(krate).module_for_file(&from_file) else {
+                    return Ok(None);
+                };
+                workspace.resolve_path(krate, module, &segments, 0)
+            }
+            // `mod name;` declarations name a child module, not a crate
+            name if segments.len() == 1
+                && self.is_child_module(&workspace, krate, name, from_file) =>
+            {
+                return Ok(None);
+            }
+            name => match workspace.crate_named(krate, name) {
+                Some(target_crate) => workspace.resolve_segments(
+                    RustTarget::Module(target_crate, 0),
+                    &segments[1..],
+                    0,
+                ),
+                None => None,
+            },
+        };
+
+        let Some(target) = target else {
+            return Ok(None);
+        };
+        let validated_path = validate_import_path(base_dir, &workspace.target_file(&target))?;
+        Ok(Some(ResolvedPath {
+            path: validated_path,
+            is_external: false,
+            confidence: 0.95,
+        }))
+    }
+
     fn resolve_unprefixed_local_module(
         &self,
         module_path: &str,
@@ -500,3 +657,604 @@ impl RustModuleResolver {
         Ok(None)
     }
 }
+
+/// The names bound by an import mapped to the full path they refer to:
+/// `use a::{b::C, D as E}` binds `C` to `a::b::C` and `E` to `a::D`.
+fn rust_imported_items(import: &Import) -> Vec<(String, String)> {
+    import
+        .items
+        .iter()
+        .filter_map(|item| {
+            let (path, alias) = match item.split_once(" as ") {
+                Some((path, alias)) => (path.trim(), alias.trim()),
+                None => (item.trim(), item.rsplit("::").next()?.trim()),
+            };
+            if matches!(path, "*" | "self" | "") || path.contains('{') || alias == "_" {
+                return None;
+            }
+            Some((alias.to_string(), format!("{}::{path}", import.module)))
+        })
+        .collect()
+}
+
+/// A crate of the workspace and the crates it depends on by path.
+#[derive(Debug)]
+struct RustCrate {
+    /// Name the crate is used by in paths, with `-` replaced by `_`
+    ident: String,
+    root: PathBuf,
+    /// `src/lib.rs` (or `[lib] path`), else `src/main.rs`
+    entry: PathBuf,
+    /// Dependency names, including renamed ones, mapped to the crate directory
+    dependencies: HashMap<String, PathBuf>,
+    modules: OnceLock<RustModuleTree>,
+}
+
+/// Every crate of the project: workspace members and local `path =` dependencies.
+#[derive(Debug, Default)]
+struct RustWorkspace {
+    crates: Vec<RustCrate>,
+}
+
+/// Where a dependency of a manifest lives before workspace inheritance is applied.
+enum RustDependencySource {
+    Path(PathBuf),
+    /// `name = { workspace = true }`, keyed by the workspace dependency name
+    Workspace(String),
+}
+
+struct RustManifest {
+    root: PathBuf,
+    package: Option<(String, PathBuf)>,
+    dependencies: Vec<(String, RustDependencySource)>,
+    /// `[workspace.dependencies]` entries with a `path`
+    workspace_dependencies: HashMap<String, PathBuf>,
+}
+
+impl RustWorkspace {
+    fn build(base_dir: &Path) -> Self {
+        let walker = walkdir::WalkDir::new(base_dir)
+            .max_depth(8)
+            .sort_by_file_name()
+            .into_iter()
+            .filter_entry(|entry| {
+                entry.depth() == 0
+                    || !entry.file_name().to_str().is_some_and(|name| {
+                        name.starts_with('.') || RUST_SKIPPED_DIRS.contains(&name)
+                    })
+            });
+
+        let manifests: Vec<RustManifest> = walker
+            .flatten()
+            .filter(|entry| entry.file_type().is_file() && entry.file_name() == "Cargo.toml")
+            .filter_map(|entry| read_rust_manifest(entry.path()))
+            .collect();
+
+        let mut crates = Vec::new();
+        for manifest in &manifests {
+            let Some((ident, entry)) = &manifest.package else {
+                continue;
+            };
+
+            // Inherited dependencies come from the closest enclosing workspace
+            let workspace = manifests
+                .iter()
+                .filter(|workspace| {
+                    !workspace.workspace_dependencies.is_empty()
+                        && manifest.root.starts_with(&workspace.root)
+                })
+                .max_by_key(|workspace| workspace.root.components().count());
+
+            let dependencies = manifest
+                .dependencies
+                .iter()
+                .filter_map(|(name, source)| {
+                    let dir = match source {
+                        RustDependencySource::Path(dir) => dir.clone(),
+                        RustDependencySource::Workspace(key) => {
+                            workspace?.workspace_dependencies.get(key)?.clone()
+                        }
+                    };
+                    Some((name.replace('-', "_"), dir))
+                })
+                .collect();
+
+            crates.push(RustCrate {
+                ident: ident.clone(),
+                root: manifest.root.clone(),
+                entry: entry.clone(),
+                dependencies,
+                modules: OnceLock::new(),
+            });
+        }
+
+        Self { crates }
+    }
+
+    fn owning_crate(&self, file: &Path) -> Option<usize> {
+        let file = file.canonicalize().unwrap_or_else(|_| file.to_path_buf());
+        self.crates
+            .iter()
+            .enumerate()
+            .filter(|(_, krate)| file.starts_with(&krate.root))
+            .max_by_key(|(_, krate)| krate.root.components().count())
+            .map(|(index, _)| index)
+    }
+
+    /// The crate a path starting with `name` refers to from inside `from_crate`.
+    fn crate_named(&self, from_crate: usize, name: &str) -> Option<usize> {
+        let krate = &self.crates[from_crate];
+        if krate.ident == name {
+            return Some(from_crate);
+        }
+        if let Some(dir) = krate.dependencies.get(name) {
+            return self.crates.iter().position(|other| &other.root == dir);
+        }
+        // Workspace members are also reachable through `workspace = true` without a path
+        self.crates.iter().position(|other| other.ident == name)
+    }
+
+    fn modules(&self, krate: usize) -> &RustModuleTree {
+        let krate = &self.crates[krate];
+        krate
+            .modules
+            .get_or_init(|| RustModuleTree::build(&krate.entry))
+    }
+
+    /// Resolve `segments` as written inside module `module` of `krate`.
+    fn resolve_path(
+        &self,
+        krate: usize,
+        module: usize,
+        segments: &[String],
+        depth: usize,
+    ) -> Option<RustTarget> {
+        if depth > MAX_REEXPORT_DEPTH {
+            return None;
+        }
+        let (first, rest) = segments.split_first()?;
+        let tree = self.modules(krate);
+
+        let mut current = match first.as_str() {
+            "crate" => RustTarget::Module(krate, 0),
+            "self" => RustTarget::Module(krate, module),
+            "super" => RustTarget::Module(krate, tree.modules[module].parent?),
+            // `::name` always names a crate
+            "" => {
+                let (name, rest) = rest.split_first()?;
+                let target = RustTarget::Module(self.crate_named(krate, name)?, 0);
+                return self.resolve_segments(target, rest, depth);
+            }
+            name => match self.lookup(krate, module, name, depth) {
+                Some(target) => target,
+                None => RustTarget::Module(self.crate_named(krate, name)?, 0),
+            },
+        };
+
+        // `super::super::x`
+        let mut rest = rest;
+        while let (RustTarget::Module(krate, module), Some((next, tail))) =
+            (&current, rest.split_first())
+        {
+            if next != "super" {
+                break;
+            }
+            current = RustTarget::Module(*krate, self.modules(*krate).modules[*module].parent?);
+            rest = tail;
+        }
+
+        self.resolve_segments(current, rest, depth)
+    }
+
+    fn resolve_segments(
+        &self,
+        mut current: RustTarget,
+        segments: &[String],
+        depth: usize,
+    ) -> Option<RustTarget> {
+        for segment in segments {
+            match current {
+                RustTarget::Module(krate, module) => {
+                    current = self.lookup(krate, module, segment, depth)?;
+                }
+                // Enum variants and associated items live with their type
+                RustTarget::Item(_) => break,
+            }
+        }
+        Some(current)
+    }
+
+    /// Find `name` among the modules, items, and re-exports of a module.
+    fn lookup(&self, krate: usize, module: usize, name: &str, depth: usize) -> Option<RustTarget> {
+        if depth > MAX_REEXPORT_DEPTH {
+            return None;
+        }
+        let tree = self.modules(krate);
+        let scope = &tree.modules[module];
+
+        if let Some(&child) = scope.children.get(name) {
+            return Some(RustTarget::Module(krate, child));
+        }
+        if scope.items.contains(name) {
+            return Some(RustTarget::Item(scope.file.clone()));
+        }
+        if let Some((_, path)) = scope.reexports.iter().find(|(alias, _)| alias == name) {
+            return self.resolve_path(krate, module, path, depth + 1);
+        }
+        scope.globs.iter().find_map(|path| {
+            match self.resolve_path(krate, module, path, depth + 1)? {
+                RustTarget::Module(krate, module) => self.lookup(krate, module, name, depth + 1),
+                RustTarget::Item(_) => None,
+            }
+        })
+    }
+
+    fn target_file(&self, target: &RustTarget) -> PathBuf {
+        match target {
+            RustTarget::Module(krate, module) => self.modules(*krate).modules[*module].file.clone(),
+            RustTarget::Item(file) => file.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum RustTarget {
+    Module(usize, usize),
+    /// An item, identified by the file that defines it
+    Item(PathBuf),
+}
+
+fn read_rust_manifest(path: &Path) -> Option<RustManifest> {
+    let manifest = fs::read_to_string(path).ok()?.parse::<toml::Table>().ok()?;
+    let root = path.parent()?;
+    let root = root.canonicalize().unwrap_or_else(|_| root.to_path_buf());
+
+    let package = manifest.get("package").and_then(|package| {
+        let lib = manifest.get("lib");
+        let name = lib
+            .and_then(|lib| lib.get("name"))
+            .or_else(|| package.get("name"))?
+            .as_str()?
+            .replace('-', "_");
+        let entry = lib
+            .and_then(|lib| lib.get("path"))
+            .and_then(|path| path.as_str())
+            .map(|path| root.join(path))
+            .into_iter()
+            .chain([root.join("src/lib.rs"), root.join("src/main.rs")])
+            .find(|entry| entry.is_file())?;
+        Some((name, entry))
+    });
+
+    let mut tables: Vec<&toml::Value> = ["dependencies", "dev-dependencies", "build-dependencies"]
+        .iter()
+        .filter_map(|key| manifest.get(*key))
+        .collect();
+    if let Some(targets) = manifest.get("target").and_then(|target| target.as_table()) {
+        for target in targets.values() {
+            tables.extend(
+                ["dependencies", "dev-dependencies", "build-dependencies"]
+                    .iter()
+                    .filter_map(|key| target.get(*key)),
+            );
+        }
+    }
+
+    let mut dependencies = Vec::new();
+    for (name, dependency) in tables.iter().filter_map(|table| table.as_table()).flatten() {
+        if let Some(dir) = dependency.get("path").and_then(|path| path.as_str()) {
+            let dir = root.join(dir);
+            let dir = dir.canonicalize().unwrap_or(dir);
+            dependencies.push((name.clone(), RustDependencySource::Path(dir)));
+        } else if dependency
+            .get("workspace")
+            .and_then(|value| value.as_bool())
+            == Some(true)
+        {
+            // A renamed dependency inherits the entry of the package it renames
+            let key = dependency
+                .get("package")
+                .and_then(|package| package.as_str())
+                .unwrap_or(name);
+            dependencies.push((
+                name.clone(),
+                RustDependencySource::Workspace(key.to_string()),
+            ));
+        }
+    }
+
+    let workspace_dependencies = manifest
+        .get("workspace")
+        .and_then(|workspace| workspace.get("dependencies"))
+        .and_then(|dependencies| dependencies.as_table())
+        .into_iter()
+        .flatten()
+        .filter_map(|(name, dependency)| {
+            let dir = root.join(dependency.get("path")?.as_str()?);
+            Some((name.clone(), dir.canonicalize().unwrap_or(dir)))
+        })
+        .collect();
+
+    Some(RustManifest {
+        root,
+        package,
+        dependencies,
+        workspace_dependencies,
+    })
+}
+
+/// A module of a crate: a file, or an inline `mod name { ... }` block inside one.
+#[derive(Debug, Default)]
+struct RustModule {
+    file: PathBuf,
+    /// Directory holding the files of child modules
+    dir: PathBuf,
+    parent: Option<usize>,
+    children: HashMap<String, usize>,
+    items: HashSet<String>,
+    /// `pub use path::Item as alias`, keyed by the name visible from outside
+    reexports: Vec<(String, Vec<String>)>,
+    /// `pub use path::*`
+    globs: Vec<Vec<String>>,
+}
+
+/// The module tree of a crate, read from its entry file through `mod` declarations.
+#[derive(Debug, Default)]
+struct RustModuleTree {
+    modules: Vec<RustModule>,
+}
+
+impl RustModuleTree {
+    fn build(entry: &Path) -> Self {
+        let mut tree = Self::default();
+        let dir = entry.parent().unwrap_or(entry).to_path_buf();
+        tree.load_file(entry, dir, None);
+        tree
+    }
+
+    /// The module declared by a file, as opposed to inline modules inside it.
+    fn module_for_file(&self, file: &Path) -> Option<usize> {
+        self.modules.iter().position(|module| module.file == file)
+    }
+
+    fn load_file(&mut self, file: &Path, dir: PathBuf, parent: Option<usize>) -> Option<usize> {
+        if self.modules.len() >= MAX_CRATE_MODULES || self.module_for_file(file).is_some() {
+            return None;
+        }
+        let content = fs::read_to_string(file).ok()?;
+        let mut parser = Parser::new();
+        parser.set_language(tree_sitter_rust::language()).ok()?;
+        let syntax = parser.parse(&content, None)?;
+
+        let module = self.modules.len();
+        self.modules.push(RustModule {
+            file: file.to_path_buf(),
+            dir,
+            parent,
+            ..RustModule::default()
+        });
+        self.load_items(syntax.root_node(), &content, module);
+        Some(module)
+    }
+
+    fn load_items(&mut self, container: Node, content: &str, module: usize) {
+        let mut path_attribute: Option<String> = None;
+        let mut cursor = container.walk();
+        for item in container.named_children(&mut cursor) {
+            if item.kind() == "attribute_item" {
+                path_attribute = path_attribute.or_else(|| rust_path_attribute(item, content));
+                continue;
+            }
+            let attribute = path_attribute.take();
+
+            let name = item
+                .child_by_field_name("name")
+                .and_then(|name| name.utf8_text(content.as_bytes()).ok())
+                .map(|name| name.trim_start_matches("r#").to_string());
+
+            match (item.kind(), name) {
+                ("mod_item", Some(name)) => {
+                    let child = self.load_child(item, content, module, &name, attribute);
+                    if let Some(child) = child {
+                        self.modules[module].children.insert(name, child);
+                    }
+                }
+                (
+                    "struct_item" | "enum_item" | "union_item" | "trait_item" | "type_item"
+                    | "function_item" | "const_item" | "static_item" | "macro_definition",
+                    Some(name),
+                ) => {
+                    self.modules[module].items.insert(name);
+                }
+                ("use_declaration", _) if has_visibility(item) => {
+                    if let Some(argument) = item.child_by_field_name("argument") {
+                        let mut prefix = Vec::new();
+                        self.load_use_tree(argument, content, module, &mut prefix);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn load_child(
+        &mut self,
+        item: Node,
+        content: &str,
+        module: usize,
+        name: &str,
+        path_attribute: Option<String>,
+    ) -> Option<usize> {
+        let parent_dir = self.modules[module].dir.clone();
+
+        if let Some(body) = item.child_by_field_name("body") {
+            let child = self.modules.len();
+            let file = self.modules[module].file.clone();
+            self.modules.push(RustModule {
+                file,
+                dir: parent_dir.join(path_attribute.as_deref().unwrap_or(name)),
+                parent: Some(module),
+                ..RustModule::default()
+            });
+            self.load_items(body, content, child);
+            return Some(child);
+        }
+
+        // `#[path]` files own their directory like `mod.rs`; other files use one named
+        // after the module
+        let (file, dir) = match path_attribute {
+            Some(path) => {
+                // Outside inline modules the path is relative to the declaring file
+                let file_module = self.module_for_file(&self.modules[module].file);
+                let base = match file_module == Some(module) {
+                    true => self.modules[module].file.parent().unwrap_or(&parent_dir),
+                    false => &parent_dir,
+                };
+                let file = base.join(path);
+                let dir = file.parent().unwrap_or(&parent_dir).to_path_buf();
+                (file, dir)
+            }
+            None => {
+                let file = [
+                    parent_dir.join(format!("{name}.rs")),
+                    parent_dir.join(name).join("mod.rs"),
+                ]
+                .into_iter()
+                .find(|candidate| candidate.is_file())
+                .unwrap_or_default();
+                (file, parent_dir.join(name))
+            }
+        };
+        self.load_file(&file, dir, Some(module))
+    }
+
+    fn load_use_tree(
+        &mut self,
+        node: Node,
+        content: &str,
+        module: usize,
+        prefix: &mut Vec<String>,
+    ) {
+        let text = |node: Node| node.utf8_text(content.as_bytes()).unwrap_or_default();
+        match node.kind() {
+            "scoped_use_list" => {
+                let depth = prefix.len();
+                if let Some(path) = node.child_by_field_name("path") {
+                    prefix.extend(rust_path_segments(text(path)));
+                }
+                if let Some(list) = node.child_by_field_name("list") {
+                    self.load_use_tree(list, content, module, prefix);
+                }
+                prefix.truncate(depth);
+            }
+            "use_list" => {
+                let mut cursor = node.walk();
+                for child in node.named_children(&mut cursor) {
+                    self.load_use_tree(child, content, module, prefix);
+                }
+            }
+            "use_wildcard" => {
+                let path = text(node).trim_end_matches('*').trim_end_matches("::");
+                let mut glob = prefix.clone();
+                glob.extend(rust_path_segments(path));
+                self.modules[module].globs.push(glob);
+            }
+            "use_as_clause" => {
+                let (Some(path), Some(alias)) = (
+                    node.child_by_field_name("path"),
+                    node.child_by_field_name("alias"),
+                ) else {
+                    return;
+                };
+                let mut target = prefix.clone();
+                target.extend(rust_path_segments(text(path)));
+                self.add_reexport(module, text(alias).to_string(), target);
+            }
+            "self" => {
+                if let Some(alias) = prefix.last().cloned() {
+                    self.add_reexport(module, alias, prefix.clone());
+                }
+            }
+            "identifier" | "scoped_identifier" | "crate" | "super" => {
+                let mut target = prefix.clone();
+                target.extend(rust_path_segments(text(node)));
+                if let Some(alias) = target.last().cloned() {
+                    self.add_reexport(module, alias, target);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn add_reexport(&mut self, module: usize, alias: String, target: Vec<String>) {
+        let alias = alias.trim_start_matches("r#").to_string();
+        // `pub use self::x` of a child module adds nothing new
+        if target.len() == 2 && target[0] == "self" && target[1] == alias {
+            return;
+        }
+        self.modules[module].reexports.push((alias, target));
+    }
+}
+
+/// `crate::a::b` → `["crate", "a", "b"]`, keeping an empty first segment for `::a`.
+fn rust_path_segments(path: &str) -> Vec<String> {
+    let path: String = path.chars().filter(|c| !c.is_whitespace()).collect();
+    let mut segments: Vec<String> = path
+        .split("::")
+        .map(|segment| segment.trim_start_matches("r#").to_string())
+        .collect();
+    if segments.len() > 1 {
+        segments.retain(|segment| !segment.is_empty());
+        if path.starts_with("::") {
+            segments.insert(0, String::new());
+        }
+    }
+    segments
+}
+
+fn has_visibility(item: Node) -> bool {
+    let mut cursor = item.walk();
+    let visible = item
+        .named_children(&mut cursor)
+        .any(|child| child.kind() == "visibility_modifier");
+    visible
+}
+
+/// The file of `#[path = "imp/unix.rs"]`.
+fn rust_path_attribute(attribute_item: Node, content: &str) -> Option<String> {
+    let attribute = attribute_item.named_child(0)?;
+    let name = attribute
+        .named_child(0)?
+        .utf8_text(content.as_bytes())
+        .ok()?;
+    if name != "path" {
+        return None;
+    }
+    let value = attribute
+        .child_by_field_name("value")?
+        .utf8_text(content.as_bytes())
+        .ok()?;
+    Some(value.trim_matches('"').to_string())
+}
+
+fn rust_workspace_cache() -> &'static Mutex<HashMap<PathBuf, Arc<RustWorkspace>>> {
+    static CACHE: OnceLock<Mutex<HashMap<PathBuf, Arc<RustWorkspace>>>> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn rust_workspace(base_dir: &Path) -> Arc<RustWorkspace> {
+    if let Some(workspace) = rust_workspace_cache()
+        .lock()
+        .ok()
+        .and_then(|cache| cache.get(base_dir).cloned())
+    {
+        return workspace;
+    }
+
+    let workspace = Arc::new(RustWorkspace::build(base_dir));
+    if let Ok(mut cache) = rust_workspace_cache().lock() {
+        cache.insert(base_dir.to_path_buf(), workspace.clone());
+    }
+    workspace
+}
@@ -1,23 +1,33 @@
-//! Semantic analyzer for Scala
+//! Semantic analyzer for Scala 2/3 and sbt multi-project builds
 
-use crate::core::semantic::analyzer::{
-    AnalysisResult, LanguageAnalyzer, SemanticContext, SemanticResult,
+use crate::core::semantic::{
+    analyzer::{AnalysisResult, Import, LanguageAnalyzer, SemanticContext, SemanticResult},
+    languages::{is_capitalized_type_name, java::java_package_name, kotlin::closest_file},
+    path_validator::{validate_import_path, validate_module_name},
+    query_engine::QueryEngine,
+    resolver::{ModuleResolver, ResolvedPath},
 };
-use std::path::Path;
-use tree_sitter::Parser;
+use crate::utils::error::ContextCreatorError;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+use tree_sitter::{Node, Parser};
+
+/// Directories holding build output or tooling state rather than sources.
+const SCALA_SKIPPED_DIRS: &[&str] = &["target", "project", "node_modules", "out"];
 
 #[allow(clippy::new_without_default)]
 pub struct ScalaAnalyzer {
-    #[allow(dead_code)]
-    parser: Parser,
+    query_engine: QueryEngine,
 }
 
 impl ScalaAnalyzer {
     pub fn new() -> Self {
-        let parser = Parser::new();
-        // Note: tree_sitter_scala::language() would be used here when the crate is added
-        // parser.set_language(tree_sitter_scala::language()).unwrap();
-        Self { parser }
+        let language = tree_sitter_scala::language();
+        let query_engine =
+            QueryEngine::new(language, "scala").expect("Failed to create Scala query engine");
+        Self { query_engine }
     }
 }
 
@@ -28,19 +38,701 @@ impl LanguageAnalyzer for ScalaAnalyzer {
 
     fn analyze_file(
         &self,
-        _path: &Path,
-        _content: &str,
-        _context: &SemanticContext,
+        path: &Path,
+        content: &str,
+        context: &SemanticContext,
     ) -> SemanticResult<AnalysisResult> {
-        // TODO: Implement Scala analysis
-        Ok(AnalysisResult::default())
+        let mut parser = Parser::new();
+        parser
+            .set_language(tree_sitter_scala::language())
+            .map_err(|e| ContextCreatorError::ParseError(format!("Failed to set language: {e}")))?;
+
+        let tree = parser
+            .parse(content, None)
+            .ok_or_else(|| ContextCreatorError::ParseError("Failed to parse file".to_string()))?;
+        let packages = scala_package_chain(tree.root_node(), content);
+
+        let mut result = self
+            .query_engine
+            .analyze_with_parser(&mut parser, content)?;
+
+        // Receiver calls match both the named-receiver and the generic field pattern, and apply
+        // calls and receivers are captured as candidate types
+        result.dedupe_function_calls(|_| true);
+        result.dedupe_type_references(|type_ref| is_capitalized_type_name(&type_ref.name));
+
+        let index = scala_declaration_index(&context.base_dir);
+        self.expand_import_selectors(&mut result, path, &packages, &index);
+        self.expand_wildcard_imports(&mut result, path, &packages, &index);
+        self.resolve_scala_type_definitions(&mut result, path, &packages, &context.base_dir);
+
+        Ok(result)
     }
 
     fn can_handle_extension(&self, extension: &str) -> bool {
-        extension == "scala" || extension == "sc"
+        matches!(extension, "scala" | "sc")
     }
 
     fn supported_extensions(&self) -> Vec<&'static str> {
         vec!["scala", "sc"]
     }
+
+    fn query_engine(&self) -> Option<&QueryEngine> {
+        Some(&self.query_engine)
+    }
+}
+
+impl ScalaAnalyzer {
+    /// Split selector imports into one import per selected name, and qualify imports
+    /// written relative to an enclosing package (`package com.acme; import core.Money`).
+    fn expand_import_selectors(
+        &self,
+        result: &mut AnalysisResult,
+        path: &Path,
+        packages: &[String],
+        index: &ScalaDeclarationIndex,
+    ) {
+        let is_known =
+            |module: &str| index.contains(module, path) || index.packages.contains_key(module);
+        let qualify = |module: String| {
+            if is_known(&module) {
+                return module;
+            }
+            packages
+                .iter()
+                .rev()
+                .map(|package| format!("{package}.{module}"))
+                .find(|qualified| is_known(qualified))
+                .unwrap_or(module)
+        };
+
+        let mut imports = Vec::new();
+        for import in result.imports.drain(..) {
+            for item in &import.items {
+                let (name, alias) = match item.split_once(" as ") {
+                    Some((name, alias)) => (name, Some(alias)),
+                    None => (item.as_str(), None),
+                };
+                let (module, items) = if name == "*" {
+                    (import.module.clone(), vec!["*".to_string()])
+                } else {
+                    let module = if import.module.is_empty() {
+                        name.to_string()
+                    } else {
+                        format!("{}.{name}", import.module)
+                    };
+                    let wildcard = module
+                        .strip_suffix("._")
+                        .or_else(|| module.strip_suffix(".*"));
+                    match (wildcard, alias) {
+                        (Some(package), _) => (package.to_string(), vec!["*".to_string()]),
+                        (None, Some(alias)) => (module, vec![format!("as {alias}")]),
+                        (None, None) => {
+                            let name = module.rsplit('.').next().unwrap_or_default().to_string();
+                            (module, vec![name])
+                        }
+                    }
+                };
+                if module.is_empty() {
+                    continue;
+                }
+
+                imports.push(Import {
+                    module: qualify(module),
+                    items,
+                    is_relative: false,
+                    line: import.line,
+                });
+            }
+        }
+
+        result.imports = imports;
+    }
+
+    /// Replace `import com.acme.model._` with the declarations this file actually uses,
+    /// so import tracing does not pull in an arbitrary file of the package. Names the
+    /// file's own packages declare are left to them.
+    fn expand_wildcard_imports(
+        &self,
+        result: &mut AnalysisResult,
+        path: &Path,
+        packages: &[String],
+        index: &ScalaDeclarationIndex,
+    ) {
+        let explicitly_imported: HashSet<String> = result
+            .imports
+            .iter()
+            .filter(|import| import.items != ["*"])
+            .filter_map(|import| import.module.rsplit('.').next().map(str::to_string))
+            .collect();
+        let mut referenced: Vec<String> = result
+            .type_references
+            .iter()
+            .map(|type_ref| type_ref.name.clone())
+            .chain(
+                result
+                    .function_calls
+                    .iter()
+                    .filter(|call| call.module.is_none())
+                    .map(|call| call.name.clone()),
+            )
+            .filter(|name| !explicitly_imported.contains(name))
+            .filter(|name| {
+                !packages.iter().any(|package| {
+                    index
+                        .exact_declaration_file(&format!("{package}.{name}"), path)
+                        .is_some()
+                })
+            })
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        referenced.sort();
+
+        let mut imports = Vec::with_capacity(result.imports.len());
+        for import in result.imports.drain(..) {
+            if import.items != ["*"] {
+                imports.push(import);
+                continue;
+            }
+
+            let expanded: Vec<Import> = referenced
+                .iter()
+                .filter(|name| index.wildcard_brings(&import.module, name, path))
+                .map(|name| Import {
+                    module: format!("{}.{name}", import.module),
+                    items: vec![name.clone()],
+                    is_relative: false,
+                    line: import.line,
+                })
+                .collect();
+
+            if expanded.is_empty() {
+                imports.push(import);
+            } else {
+                imports.extend(expanded);
+            }
+        }
+
+        result.imports = imports;
+    }
+
+    /// Resolve types through explicit and renamed imports, the enclosing packages, and
+    /// wildcard-imported packages, in that order. `extends`/`with` parents are plain type
+    /// references, so traits and base classes resolve the same way.
+    fn resolve_scala_type_definitions(
+        &self,
+        result: &mut AnalysisResult,
+        current_file: &Path,
+        packages: &[String],
+        project_root: &Path,
+    ) {
+        let index = scala_declaration_index(project_root);
+        let mut imported: HashMap<String, String> = HashMap::new();
+        let mut wildcard_packages = Vec::new();
+        for import in &result.imports {
+            match import.items.first().map(String::as_str) {
+                Some("*") => wildcard_packages.push(import.module.clone()),
+                Some(item) => {
+                    let name = item.strip_prefix("as ").unwrap_or(item);
+                    imported.insert(name.to_string(), import.module.clone());
+                }
+                None => {}
+            }
+        }
+        let current_file = current_file
+            .canonicalize()
+            .unwrap_or_else(|_| current_file.to_path_buf());
+
+        for type_ref in &mut result.type_references {
+            if type_ref.definition_path.is_some() || type_ref.is_external {
+                continue;
+            }
+
+            let mut candidates = Vec::new();
+            if let Some(path) = imported.get(&type_ref.name) {
+                candidates.push(path.clone());
+            } else {
+                for package in packages.iter().rev() {
+                    candidates.push(format!("{package}.{}", type_ref.name));
+                }
+                if packages.is_empty() {
+                    candidates.push(type_ref.name.clone());
+                }
+                for package in &wildcard_packages {
+                    if index.wildcard_brings(package, &type_ref.name, &current_file) {
+                        candidates.push(format!("{package}.{}", type_ref.name));
+                    }
+                }
+            }
+
+            let definition = candidates
+                .iter()
+                .find_map(|path| index.declaration_file(path, &current_file))
+                .filter(|file| file != &current_file);
+
+            match definition {
+                Some(file) => match validate_import_path(project_root, &file) {
+                    Ok(validated) => type_ref.definition_path = Some(validated),
+                    Err(_) => type_ref.is_external = true,
+                },
+                None if imported
+                    .get(&type_ref.name)
+                    .is_some_and(|path| ScalaModuleResolver.is_external_module(path)) =>
+                {
+                    type_ref.is_external = true;
+                    type_ref.external_package = imported.get(&type_ref.name).cloned();
+                }
+                None => {}
+            }
+        }
+    }
+}
+
+pub struct ScalaModuleResolver;
+
+impl ModuleResolver for ScalaModuleResolver {
+    fn resolve_import(
+        &self,
+        module_path: &str,
+        from_file: &Path,
+        base_dir: &Path,
+    ) -> Result<ResolvedPath, ContextCreatorError> {
+        let module_path = module_path
+            .trim()
+            .trim_end_matches("._")
+            .trim_end_matches(".*");
+        validate_module_name(module_path)?;
+
+        let index = scala_declaration_index(base_dir);
+        if let Some(file) = index.declaration_file(module_path, from_file) {
+            return resolved_scala_path(file, base_dir, 0.95);
+        }
+        if let Some(file) = index.package_file(module_path, from_file) {
+            return resolved_scala_path(file, base_dir, 0.7);
+        }
+
+        Ok(ResolvedPath {
+            path: base_dir.join("build.sbt"),
+            is_external: true,
+            confidence: if self.is_external_module(module_path) {
+                0.9
+            } else {
+                0.5
+            },
+        })
+    }
+
+    fn get_file_extensions(&self) -> Vec<&'static str> {
+        vec!["scala", "sc", "java"]
+    }
+
+    fn is_external_module(&self, module_path: &str) -> bool {
+        [
+            "scala.",
+            "java.",
+            "javax.",
+            "akka.",
+            "cats.",
+            "zio.",
+            "org.apache.spark.",
+            "org.scalatest.",
+            "com.typesafe.",
+        ]
+        .iter()
+        .any(|prefix| module_path.starts_with(prefix))
+    }
+}
+
+fn resolved_scala_path(
+    path: PathBuf,
+    base_dir: &Path,
+    confidence: f32,
+) -> Result<ResolvedPath, ContextCreatorError> {
+    Ok(ResolvedPath {
+        path: validate_import_path(base_dir, &path)?,
+        is_external: false,
+        confidence,
+    })
+}
+
+/// The packages a file's declarations see without imports. Chained clauses
+/// (`package com.acme` then `package jobs`) open each enclosing package in turn.
+fn scala_package_chain(root: Node<'_>, content: &str) -> Vec<String> {
+    let mut packages: Vec<String> = Vec::new();
+    let mut cursor = root.walk();
+    for clause in root
+        .named_children(&mut cursor)
+        .filter(|node| node.kind() == "package_clause")
+    {
+        let Some(name) = clause
+            .child_by_field_name("name")
+            .and_then(|name| name.utf8_text(content.as_bytes()).ok())
+        else {
+            continue;
+        };
+        let package = match packages.last() {
+            Some(outer) => format!("{outer}.{name}"),
+            None => name.to_string(),
+        };
+        packages.push(package);
+    }
+    packages
+}
+
+/// An sbt subproject: `lazy val core = (project in file("modules/core")).dependsOn(util)`.
+#[derive(Debug)]
+struct SbtProject {
+    name: String,
+    dir: PathBuf,
+    depends_on: Vec<String>,
+}
+
+/// Parse the subprojects of a `build.sbt`. Projects without `in file(...)` live in a
+/// directory named after their `val`, and `dependsOn` configurations are ignored.
+fn parse_build_sbt(content: &str, base_dir: &Path) -> Vec<SbtProject> {
+    let content: String = content
+        .lines()
+        .map(|line| line.split("//").next().unwrap_or_default())
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let mut starts: Vec<usize> = content
+        .match_indices("val ")
+        .map(|(start, _)| start)
+        .filter(|&start| {
+            content[..start]
+                .chars()
+                .next_back()
+                .map_or(true, |c| !c.is_alphanumeric() && c != '_')
+        })
+        .collect();
+    starts.push(content.len());
+
+    let mut projects = Vec::new();
+    for window in starts.windows(2) {
+        let statement = &content[window[0] + "val ".len()..window[1]];
+        let Some((name, definition)) = statement.split_once('=') else {
+            continue;
+        };
+        let name = name.trim();
+        let definition_start = definition.trim_start();
+        let is_project = definition_start.starts_with("(project")
+            || definition_start.starts_with("project")
+            || definition.contains("Project(");
+        if !is_project || name.is_empty() || !name.chars().all(|c| c.is_alphanumeric() || c == '_')
+        {
+            continue;
+        }
+
+        let dir = definition
+            .split_once("file(\"")
+            .and_then(|(_, rest)| rest.split_once('"'))
+            .map_or(name, |(dir, _)| dir);
+        let dir = base_dir.join(dir);
+
+        let mut depends_on = Vec::new();
+        for (start, pattern) in definition.match_indices("dependsOn(") {
+            let arguments = definition[start + pattern.len()..]
+                .split(')')
+                .next()
+                .unwrap_or_default();
+            depends_on.extend(arguments.split(',').filter_map(|argument| {
+                let project: String = argument
+                    .trim()
+                    .chars()
+                    .take_while(|c| c.is_alphanumeric() || *c == '_')
+                    .collect();
+                (!project.is_empty()).then_some(project)
+            }));
+        }
+
+        projects.push(SbtProject {
+            name: name.to_string(),
+            dir: dir.canonicalize().unwrap_or(dir),
+            depends_on,
+        });
+    }
+
+    projects
+}
+
+/// Top-level Scala declarations and Java classes of every sbt subproject, with the
+/// project graph used to keep lookups inside the importing project and its
+/// `dependsOn` dependencies.
+#[derive(Debug, Default)]
+struct ScalaDeclarationIndex {
+    /// Fully-qualified declaration name to the files declaring it. Companion objects
+    /// and cross-built sources (`scala-2.13`, `scala-3`) may declare a name twice.
+    declarations: HashMap<String, Vec<PathBuf>>,
+    /// Package name to the files in it.
+    packages: HashMap<String, Vec<PathBuf>>,
+    /// Fully-qualified top-level object name to the names defined in its body.
+    object_members: HashMap<String, HashSet<String>>,
+    projects: Vec<SbtProject>,
+}
+
+impl ScalaDeclarationIndex {
+    fn build(base_dir: &Path) -> Self {
+        let mut index = Self {
+            projects: fs::read_to_string(base_dir.join("build.sbt"))
+                .map(|content| parse_build_sbt(&content, base_dir))
+                .unwrap_or_default(),
+            ..Self::default()
+        };
+        let mut parser = Parser::new();
+        let has_scala_parser = parser.set_language(tree_sitter_scala::language()).is_ok();
+
+        let walker = walkdir::WalkDir::new(base_dir)
+            .max_depth(16)
+            .sort_by_file_name()
+            .into_iter()
+            .filter_entry(|entry| {
+                entry.depth() == 0
+                    || !entry.file_name().to_str().is_some_and(|name| {
+                        name.starts_with('.') || SCALA_SKIPPED_DIRS.contains(&name)
+                    })
+            });
+
+        for entry in walker.flatten() {
+            if !entry.file_type().is_file() {
+                continue;
+            }
+            let path = entry.path();
+            let extension = path.extension().and_then(|ext| ext.to_str());
+            if !matches!(extension, Some("scala" | "java")) {
+                continue;
+            }
+            let Ok(content) = fs::read_to_string(path) else {
+                continue;
+            };
+            let file = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+
+            let mut declarations = Vec::new();
+            let mut members = Vec::new();
+            if extension == Some("java") {
+                // One public top-level class per Java file, named after the file
+                if let (Some(package), Some(stem)) = (
+                    java_package_name(&content),
+                    path.file_stem().and_then(|stem| stem.to_str()),
+                ) {
+                    declarations.push((package, stem.to_string()));
+                }
+            } else if has_scala_parser {
+                if let Some(tree) = parser.parse(&content, None) {
+                    collect_scala_declarations(
+                        tree.root_node(),
+                        &content,
+                        String::new(),
+                        &mut declarations,
+                        &mut members,
+                    );
+                }
+            }
+
+            for (package, name) in declarations {
+                let qualified = if package.is_empty() {
+                    name
+                } else {
+                    format!("{package}.{name}")
+                };
+                let files = index.declarations.entry(qualified).or_default();
+                if !files.contains(&file) {
+                    files.push(file.clone());
+                }
+                let files = index.packages.entry(package).or_default();
+                if !files.contains(&file) {
+                    files.push(file.clone());
+                }
+            }
+            for (object, member) in members {
+                index
+                    .object_members
+                    .entry(object)
+                    .or_default()
+                    .insert(member);
+            }
+        }
+
+        index
+    }
+
+    fn contains(&self, qualified_name: &str, from_file: &Path) -> bool {
+        self.declaration_file(qualified_name, from_file).is_some()
+    }
+
+    /// The file declaring a fully-qualified name, trying the longest prefix first so
+    /// members (`Money.format`) and nested types land on the enclosing declaration.
+    fn declaration_file(&self, qualified_name: &str, from_file: &Path) -> Option<PathBuf> {
+        let segments: Vec<&str> = qualified_name.split('.').collect();
+        (1..=segments.len()).rev().find_map(|length| {
+            let is_full_name = length == segments.len();
+            if !is_full_name && !segments[length - 1].starts_with(|c: char| c.is_uppercase()) {
+                return None;
+            }
+            let files = self.declarations.get(&segments[..length].join("."))?;
+            self.closest_visible_file(files, from_file)
+        })
+    }
+
+    /// The file declaring exactly a fully-qualified name, without falling back to an
+    /// enclosing declaration.
+    fn exact_declaration_file(&self, qualified_name: &str, from_file: &Path) -> Option<PathBuf> {
+        self.closest_visible_file(self.declarations.get(qualified_name)?, from_file)
+    }
+
+    /// Whether a wildcard import of `module` brings `name` into scope: `name` is declared
+    /// in the package `module`, or defined in the body of the object `module`.
+    fn wildcard_brings(&self, module: &str, name: &str, from_file: &Path) -> bool {
+        self.exact_declaration_file(&format!("{module}.{name}"), from_file)
+            .is_some()
+            || (self
+                .object_members
+                .get(module)
+                .is_some_and(|members| members.contains(name))
+                && self.exact_declaration_file(module, from_file).is_some())
+    }
+
+    fn package_file(&self, package: &str, from_file: &Path) -> Option<PathBuf> {
+        self.closest_visible_file(self.packages.get(package)?, from_file)
+    }
+
+    fn closest_visible_file(&self, files: &[PathBuf], from_file: &Path) -> Option<PathBuf> {
+        let from_file = from_file
+            .canonicalize()
+            .unwrap_or_else(|_| from_file.to_path_buf());
+        let Some(visible) = self.visible_projects(&from_file) else {
+            return closest_file(files, &from_file);
+        };
+
+        let files: Vec<PathBuf> = files
+            .iter()
+            .filter(|file| {
+                self.owning_project(file)
+                    .map_or(true, |project| visible.contains(project.name.as_str()))
+            })
+            .cloned()
+            .collect();
+        closest_file(&files, &from_file)
+    }
+
+    /// The innermost subproject whose directory holds the file.
+    fn owning_project(&self, file: &Path) -> Option<&SbtProject> {
+        self.projects
+            .iter()
+            .filter(|project| file.starts_with(&project.dir))
+            .max_by_key(|project| project.dir.components().count())
+    }
+
+    /// The importing project and everything it transitively `dependsOn`. Files outside
+    /// every subproject see the whole build.
+    fn visible_projects(&self, from_file: &Path) -> Option<HashSet<&str>> {
+        let project = self.owning_project(from_file)?;
+        let mut visible = HashSet::new();
+        let mut pending = vec![project.name.as_str()];
+        while let Some(name) = pending.pop() {
+            if !visible.insert(name) {
+                continue;
+            }
+            if let Some(project) = self.projects.iter().find(|project| project.name == name) {
+                pending.extend(project.depends_on.iter().map(String::as_str));
+            }
+        }
+        Some(visible)
+    }
+}
+
+/// (package, name) of top-level classes, objects, traits, enums, type aliases, and
+/// Scala 3 top-level definitions, following chained and block package clauses, and
+/// (object, name) of the definitions inside top-level objects.
+fn collect_scala_declarations(
+    node: Node<'_>,
+    content: &str,
+    mut package: String,
+    declarations: &mut Vec<(String, String)>,
+    members: &mut Vec<(String, String)>,
+) {
+    let mut cursor = node.walk();
+    for child in node.named_children(&mut cursor) {
+        if child.kind() == "package_clause" {
+            let Some(name) = child
+                .child_by_field_name("name")
+                .and_then(|name| name.utf8_text(content.as_bytes()).ok())
+            else {
+                continue;
+            };
+            let nested = if package.is_empty() {
+                name.to_string()
+            } else {
+                format!("{package}.{name}")
+            };
+            match child.child_by_field_name("body") {
+                Some(body) => {
+                    collect_scala_declarations(body, content, nested, declarations, members)
+                }
+                None => package = nested,
+            }
+            continue;
+        }
+
+        let Some(name) = scala_definition_name(child, content) else {
+            continue;
+        };
+        if child.kind() == "object_definition" {
+            let object = if package.is_empty() {
+                name.to_string()
+            } else {
+                format!("{package}.{name}")
+            };
+            if let Some(body) = child.child_by_field_name("body") {
+                let mut body_cursor = body.walk();
+                members.extend(
+                    body.named_children(&mut body_cursor)
+                        .filter_map(|member| scala_definition_name(member, content))
+                        .map(|member| (object.clone(), member.to_string())),
+                );
+            }
+        }
+        declarations.push((package.clone(), name.to_string()));
+    }
+}
+
+/// Name of a class, object, trait, enum, type alias, method, or simple `val`/`var`
+/// definition.
+fn scala_definition_name<'a>(node: Node<'_>, content: &'a str) -> Option<&'a str> {
+    let name_node = match node.kind() {
+        "class_definition"
+        | "object_definition"
+        | "trait_definition"
+        | "enum_definition"
+        | "type_definition"
+        | "function_definition"
+        | "function_declaration" => node.child_by_field_name("name"),
+        "val_definition" | "var_definition" => node
+            .child_by_field_name("pattern")
+            .filter(|pattern| pattern.kind() == "identifier"),
+        _ => None,
+    };
+    name_node.and_then(|name| name.utf8_text(content.as_bytes()).ok())
+}
+
+fn scala_index_cache() -> &'static Mutex<HashMap<PathBuf, Arc<ScalaDeclarationIndex>>> {
+    static CACHE: OnceLock<Mutex<HashMap<PathBuf, Arc<ScalaDeclarationIndex>>>> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn scala_declaration_index(base_dir: &Path) -> Arc<ScalaDeclarationIndex> {
+    if let Some(index) = scala_index_cache()
+        .lock()
+        .ok()
+        .and_then(|cache| cache.get(base_dir).cloned())
+    {
+        return index;
+    }
+
+    let index = Arc::new(ScalaDeclarationIndex::build(base_dir));
+    if let Ok(mut cache) = scala_index_cache().lock() {
+        cache.insert(base_dir.to_path_buf(), index.clone());
+    }
+    index
 }
@@ -0,0 +1,959 @@
+//! Dependency edges between schema files and the code generated from or driven by them
+//!
+//! Protobuf, GraphQL, OpenAPI, and SQL files declare messages, services, types, operations,
+//! and tables. Their declarations are indexed under the names code generators and ORMs give
+//! them (`service UserService` as `UserServiceClient`, `query GetUser` as `useGetUserQuery`,
+//! `CREATE TABLE users` as `"users"` or `class User`), and source files using those names are
+//! linked to the schema. Schema files also follow their own imports: Protobuf `import`,
+//! GraphQL `#import`, and OpenAPI `$ref`s to other files. Schemas are read with a lexical
+//! scanner rather than a parser.
+
+use crate::core::semantic::{
+    analyzer::{AnalysisResult, Import, LanguageAnalyzer, SemanticContext, SemanticResult},
+    path_validator::validate_import_path,
+    resolver::{ModuleResolver, ResolvedPath},
+};
+use crate::utils::error::ContextCreatorError;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::path::{Component, Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+
+/// Directories holding installed dependencies or build output.
+const SCHEMA_SKIPPED_DIRS: &[&str] = &["node_modules", "target", "vendor", "dist", "build"];
+
+/// Schema files larger than this are generated or vendored and not indexed.
+const MAX_SCHEMA_FILE_SIZE: u64 = 2 * 1024 * 1024;
+
+/// Root operation types and built-in scalars of GraphQL, which every client uses.
+const GRAPHQL_BUILTIN_TYPES: &[&str] = &[
+    "Query",
+    "Mutation",
+    "Subscription",
+    "String",
+    "Int",
+    "Float",
+    "Boolean",
+    "ID",
+];
+
+/// SQL keywords followed by a table name in queries: `FROM users`, `INSERT INTO users`.
+const SQL_TABLE_KEYWORDS: &[&str] = &["from", "join", "into", "update", "table", "references"];
+
+/// Words after which source code defines a model named after a table.
+const MODEL_KEYWORDS: &[&str] = &["class", "struct"];
+
+/// The schema format of a file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SchemaKind {
+    Protobuf,
+    GraphQl,
+    OpenApi,
+    Sql,
+}
+
+impl SchemaKind {
+    /// The schema format of `path`; OpenAPI documents are YAML or JSON files named
+    /// `openapi.*` or `swagger.*`, possibly with a prefix such as `billing.openapi.yaml`.
+    pub fn for_path(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+        match extension.as_str() {
+            "proto" => Some(SchemaKind::Protobuf),
+            "graphql" | "graphqls" | "gql" => Some(SchemaKind::GraphQl),
+            "sql" => Some(SchemaKind::Sql),
+            "yaml" | "yml" | "json" => {
+                let stem = path.file_stem()?.to_str()?.to_ascii_lowercase();
+                (stem.contains("openapi") || stem.contains("swagger"))
+                    .then_some(SchemaKind::OpenApi)
+            }
+            _ => None,
+        }
+    }
+}
+
+pub struct SchemaAnalyzer {
+    kind: SchemaKind,
+}
+
+impl SchemaAnalyzer {
+    pub fn new(kind: SchemaKind) -> Self {
+        Self { kind }
+    }
+}
+
+impl LanguageAnalyzer for SchemaAnalyzer {
+    fn language_name(&self) -> &'static str {
+        match self.kind {
+            SchemaKind::Protobuf => "Protobuf",
+            SchemaKind::GraphQl => "GraphQL",
+            SchemaKind::OpenApi => "OpenAPI",
+            SchemaKind::Sql => "SQL",
+        }
+    }
+
+    fn analyze_file(
+        &self,
+        _path: &Path,
+        content: &str,
+        _context: &SemanticContext,
+    ) -> SemanticResult<AnalysisResult> {
+        let schema = parse_schema(self.kind, content);
+        let imports = schema
+            .imports
+            .into_iter()
+            .map(|(module, line)| Import {
+                module,
+                items: Vec::new(),
+                is_relative: true,
+                line,
+            })
+            .collect();
+
+        Ok(AnalysisResult {
+            imports,
+            ..AnalysisResult::default()
+        })
+    }
+
+    fn can_handle_extension(&self, extension: &str) -> bool {
+        self.supported_extensions().contains(&extension)
+    }
+
+    fn supported_extensions(&self) -> Vec<&'static str> {
+        match self.kind {
+            SchemaKind::Protobuf => vec!["proto"],
+            SchemaKind::GraphQl => vec!["graphql", "graphqls", "gql"],
+            SchemaKind::OpenApi => vec!["yaml", "yml", "json"],
+            SchemaKind::Sql => vec!["sql"],
+        }
+    }
+}
+
+/// Resolves the file paths schema files import from each other
+pub struct SchemaModuleResolver;
+
+impl ModuleResolver for SchemaModuleResolver {
+    fn resolve_import(
+        &self,
+        module_path: &str,
+        from_file: &Path,
+        base_dir: &Path,
+    ) -> Result<ResolvedPath, ContextCreatorError> {
+        let path = Path::new(module_path);
+        if module_path.is_empty()
+            || module_path.len() > 255
+            || module_path.contains('\0')
+            || path
+                .components()
+                .any(|component| matches!(component, Component::Prefix(_) | Component::RootDir))
+        {
+            return Err(ContextCreatorError::SecurityError(format!(
+                "Invalid schema import: {module_path}"
+            )));
+        }
+
+        if self.is_external_module(module_path) {
+            return Ok(ResolvedPath {
+                path: base_dir.to_path_buf(),
+                is_external: true,
+                confidence: 1.0,
+            });
+        }
+
+        // Protobuf imports are relative to an include root, which is usually an ancestor of
+        // the importing file, so every directory up to the project root is tried
+        let importing_dir = from_file.parent().unwrap_or(base_dir);
+        let search_dirs = importing_dir
+            .ancestors()
+            .take_while(|dir| dir.starts_with(base_dir))
+            .chain(std::iter::once(base_dir));
+        for dir in search_dirs {
+            let candidate = dir.join(path);
+            if candidate.is_file() {
+                return Ok(ResolvedPath {
+                    path: validate_import_path(base_dir, &candidate)?,
+                    is_external: false,
+                    confidence: 0.9,
+                });
+            }
+            if module_path.starts_with("./") || module_path.starts_with("../") {
+                break;
+            }
+        }
+
+        Ok(ResolvedPath {
+            path: base_dir.to_path_buf(),
+            is_external: true,
+            confidence: 0.5,
+        })
+    }
+
+    fn get_file_extensions(&self) -> Vec<&'static str> {
+        vec![
+            "proto", "graphql", "graphqls", "gql", "yaml", "yml", "json", "sql",
+        ]
+    }
+
+    fn is_external_module(&self, module_path: &str) -> bool {
+        // Well-known protos ship with protoc, and remote references are not in the project
+        module_path.starts_with("google/protobuf/") || module_path.contains("://")
+    }
+}
+
+/// What a schema file declares and imports.
+#[derive(Debug, Default)]
+struct Schema {
+    declarations: Vec<Declaration>,
+    imports: Vec<(String, usize)>,
+}
+
+#[derive(Debug)]
+struct Declaration {
+    name: String,
+    kind: DeclarationKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DeclarationKind {
+    /// Protobuf messages and enums, GraphQL types, and OpenAPI schemas
+    Type,
+    /// Protobuf services
+    Service,
+    /// Protobuf RPCs
+    Rpc,
+    /// GraphQL operations, with the generated suffix of their type
+    Operation(&'static str),
+    /// GraphQL fragments
+    Fragment,
+    /// OpenAPI operations
+    OperationId,
+    /// SQL tables and views
+    Table,
+}
+
+impl Declaration {
+    /// The identifiers code generated from the declaration uses.
+    fn generated_names(&self) -> Vec<String> {
+        let name = &self.name;
+        let words = split_words(name);
+        let pascal = pascal_case(&words);
+        let names = match self.kind {
+            DeclarationKind::Type => vec![name.clone()],
+            DeclarationKind::Service => vec![
+                name.clone(),
+                format!("{name}Client"),
+                format!("{name}Server"),
+                format!("{name}Stub"),
+                format!("{name}Servicer"),
+                format!("{name}Grpc"),
+                format!("{name}ImplBase"),
+                format!("{name}BlockingStub"),
+                format!("{name}AsyncClient"),
+                format!("New{name}Client"),
+                format!("Register{name}Server"),
+                format!("Unimplemented{name}Server"),
+                format!("add_{name}Servicer_to_server"),
+                format!("{}_client", snake_case(&words)),
+                format!("{}_server", snake_case(&words)),
+            ],
+            DeclarationKind::Rpc | DeclarationKind::OperationId => vec![
+                pascal.clone(),
+                camel_case(&words),
+                snake_case(&words),
+                format!("use{pascal}"),
+            ],
+            DeclarationKind::Operation(suffix) => {
+                let mut names = vec![
+                    pascal.clone(),
+                    format!("{pascal}{suffix}"),
+                    format!("{pascal}{suffix}Variables"),
+                    format!("{pascal}Document"),
+                    format!("use{pascal}{suffix}"),
+                ];
+                if suffix == "Query" {
+                    names.push(format!("use{pascal}LazyQuery"));
+                    names.push(format!("use{pascal}SuspenseQuery"));
+                }
+                names
+            }
+            DeclarationKind::Fragment => vec![
+                pascal.clone(),
+                format!("{pascal}Fragment"),
+                format!("{pascal}FragmentDoc"),
+            ],
+            DeclarationKind::Table => Vec::new(),
+        };
+
+        // Single words such as `User` or `get` name too many unrelated things in code
+        names
+            .into_iter()
+            .filter(|generated| split_words(generated).len() > 1)
+            .collect()
+    }
+}
+
+fn parse_schema(kind: SchemaKind, content: &str) -> Schema {
+    match kind {
+        SchemaKind::Protobuf => parse_protobuf(content),
+        SchemaKind::GraphQl => parse_graphql(content),
+        SchemaKind::OpenApi => parse_openapi(content),
+        SchemaKind::Sql => parse_sql(content),
+    }
+}
+
+/// A word or quoted string of a schema file with its line.
+#[derive(Debug, PartialEq)]
+enum Token<'a> {
+    Word(&'a str),
+    Quoted(&'a str),
+    Punct(char),
+}
+
+/// Split `content` into words, quoted strings, and punctuation, skipping comments that
+/// start with one of `line_comments` and `/* */` blocks when `block_comments` is set.
+fn tokenize<'a>(
+    content: &'a str,
+    line_comments: &[&str],
+    block_comments: bool,
+) -> Vec<(Token<'a>, usize)> {
+    let bytes = content.as_bytes();
+    let mut tokens = Vec::new();
+    let mut line = 1;
+    let mut index = 0;
+    while index < bytes.len() {
+        let rest = &content[index..];
+        let byte = bytes[index];
+        if byte == b'\n' {
+            line += 1;
+            index += 1;
+        } else if line_comments
+            .iter()
+            .any(|comment| rest.starts_with(comment))
+        {
+            index += rest.find('\n').unwrap_or(rest.len());
+        } else if block_comments && rest.starts_with("/*") {
+            let end = rest[2..].find("*/").map_or(rest.len(), |end| end + 4);
+            line += rest[..end].matches('\n').count();
+            index += end;
+        } else if byte == b'"' || byte == b'\'' || byte == b'`' {
+            let quote = byte as char;
+            let body = &rest[1..];
+            let end = body.find([quote, '\n']).unwrap_or(body.len());
+            tokens.push((Token::Quoted(&body[..end]), line));
+            index += 1 + end + usize::from(body[end..].starts_with(quote));
+        } else if byte.is_ascii_alphanumeric() || byte == b'_' {
+            let end = rest
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                .unwrap_or(rest.len());
+            tokens.push((Token::Word(&rest[..end]), line));
+            index += end;
+        } else {
+            let c = rest.chars().next().unwrap_or(' ');
+            if !c.is_whitespace() {
+                tokens.push((Token::Punct(c), line));
+            }
+            index += c.len_utf8();
+        }
+    }
+    tokens
+}
+
+fn parse_protobuf(content: &str) -> Schema {
+    let tokens = tokenize(content, &["//"], true);
+    let mut schema = Schema::default();
+    let mut depth = 0usize;
+    let mut service_depth = None;
+
+    for (index, (token, line)) in tokens.iter().enumerate() {
+        let next_word = |offset: usize| match tokens.get(index + offset) {
+            Some((Token::Word(word), _)) => Some(*word),
+            _ => None,
+        };
+        match token {
+            Token::Punct('{') => depth += 1,
+            Token::Punct('}') => {
+                depth = depth.saturating_sub(1);
+                if service_depth == Some(depth) {
+                    service_depth = None;
+                }
+            }
+            Token::Word("import") => {
+                // import "a.proto"; import public "b.proto";
+                let path = tokens[index + 1..]
+                    .iter()
+                    .take(2)
+                    .find_map(|(token, _)| match token {
+                        Token::Quoted(path) => Some(*path),
+                        _ => None,
+                    });
+                if let Some(path) = path {
+                    schema.imports.push((path.to_string(), *line));
+                }
+            }
+            Token::Word(keyword @ ("message" | "enum" | "service")) => {
+                if let Some(name) = next_word(1) {
+                    let kind = if *keyword == "service" {
+                        service_depth = Some(depth);
+                        DeclarationKind::Service
+                    } else {
+                        DeclarationKind::Type
+                    };
+                    schema.declarations.push(Declaration {
+                        name: name.to_string(),
+                        kind,
+                    });
+                }
+            }
+            Token::Word("rpc") if service_depth.is_some() => {
+                if let Some(name) = next_word(1) {
+                    schema.declarations.push(Declaration {
+                        name: name.to_string(),
+                        kind: DeclarationKind::Rpc,
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+    schema
+}
+
+fn parse_graphql(content: &str) -> Schema {
+    let mut schema = Schema::default();
+
+    // `#import "./fragments.graphql"` is a comment to GraphQL itself
+    for (index, line) in content.lines().enumerate() {
+        if let Some(rest) = line.trim_start().strip_prefix("#import") {
+            let path = rest.trim().trim_matches(|c| c == '"' || c == '\'');
+            if !path.is_empty() {
+                schema.imports.push((path.to_string(), index + 1));
+            }
+        }
+    }
+
+    let tokens = tokenize(content, &["#"], false);
+    let mut depth = 0usize;
+    for (index, (token, _)) in tokens.iter().enumerate() {
+        let name = match tokens.get(index + 1) {
+            Some((Token::Word(word), _)) => Some(*word),
+            _ => None,
+        };
+        match token {
+            Token::Punct('{') | Token::Punct('(') => depth += 1,
+            Token::Punct('}') | Token::Punct(')') => depth = depth.saturating_sub(1),
+            // Only top-level definitions, so fields named `type` or `query` are skipped
+            Token::Word(keyword) if depth == 0 => {
+                let kind = match *keyword {
+                    "type" | "input" | "enum" | "interface" | "union" | "scalar" => {
+                        DeclarationKind::Type
+                    }
+                    "query" => DeclarationKind::Operation("Query"),
+                    "mutation" => DeclarationKind::Operation("Mutation"),
+                    "subscription" => DeclarationKind::Operation("Subscription"),
+                    "fragment" => DeclarationKind::Fragment,
+                    _ => continue,
+                };
+                let Some(name) = name else {
+                    continue;
+                };
+                if kind == DeclarationKind::Type && GRAPHQL_BUILTIN_TYPES.contains(&name) {
+                    continue;
+                }
+                schema.declarations.push(Declaration {
+                    name: name.to_string(),
+                    kind,
+                });
+            }
+            _ => {}
+        }
+    }
+    schema
+}
+
+fn parse_openapi(content: &str) -> Schema {
+    if content.trim_start().starts_with('{') {
+        if let Ok(document) = serde_json::from_str::<serde_json::Value>(content) {
+            return parse_openapi_json(&document);
+        }
+    }
+    parse_openapi_yaml(content)
+}
+
+fn parse_openapi_json(document: &serde_json::Value) -> Schema {
+    let mut schema = Schema::default();
+
+    let schema_maps = [
+        document.pointer("/components/schemas"),
+        document.pointer("/definitions"),
+    ];
+    for names in schema_maps
+        .into_iter()
+        .flatten()
+        .filter_map(|map| map.as_object())
+    {
+        for name in names.keys() {
+            schema.declarations.push(Declaration {
+                name: name.clone(),
+                kind: DeclarationKind::Type,
+            });
+        }
+    }
+
+    let mut pending = vec![document];
+    while let Some(value) = pending.pop() {
+        match value {
+            serde_json::Value::Object(map) => {
+                for (key, value) in map {
+                    match (key.as_str(), value) {
+                        ("operationId", serde_json::Value::String(operation)) => {
+                            schema.declarations.push(Declaration {
+                                name: operation.clone(),
+                                kind: DeclarationKind::OperationId,
+                            });
+                        }
+                        ("$ref", serde_json::Value::String(reference)) => {
+                            if let Some(file) = openapi_ref_file(reference) {
+                                schema.imports.push((file, 0));
+                            }
+                        }
+                        _ => pending.push(value),
+                    }
+                }
+            }
+            serde_json::Value::Array(values) => pending.extend(values),
+            _ => {}
+        }
+    }
+    schema
+}
+
+fn parse_openapi_yaml(content: &str) -> Schema {
+    let mut schema = Schema::default();
+    // Indentation of the `schemas:` or `definitions:` key being read, and of its entries
+    let mut schema_section: Option<(usize, Option<usize>)> = None;
+    let mut components_indent = None;
+
+    for (index, raw_line) in content.lines().enumerate() {
+        let trimmed = raw_line.trim_start();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        let indent = raw_line.len() - trimmed.len();
+        let line_number = index + 1;
+        let Some((key, value)) = trimmed.trim_start_matches("- ").split_once(':') else {
+            continue;
+        };
+        let key = key.trim().trim_matches(|c| c == '"' || c == '\'');
+        let value = value
+            .split(" #")
+            .next()
+            .unwrap_or("")
+            .trim()
+            .trim_matches(|c| c == '"' || c == '\'');
+
+        if let Some((section_indent, entry_indent)) = schema_section {
+            if indent <= section_indent {
+                schema_section = None;
+            } else if entry_indent.map_or(true, |entry| indent == entry) {
+                schema_section = Some((section_indent, Some(indent)));
+                schema.declarations.push(Declaration {
+                    name: key.to_string(),
+                    kind: DeclarationKind::Type,
+                });
+            }
+        }
+        if components_indent.is_some_and(|components| indent <= components) && key != "components" {
+            components_indent = None;
+        }
+
+        match key {
+            "components" if value.is_empty() => components_indent = Some(indent),
+            "schemas" if value.is_empty() && components_indent.is_some() => {
+                schema_section = Some((indent, None));
+            }
+            "definitions" if value.is_empty() && indent == 0 => {
+                schema_section = Some((indent, None));
+            }
+            "operationId" if !value.is_empty() => schema.declarations.push(Declaration {
+                name: value.to_string(),
+                kind: DeclarationKind::OperationId,
+            }),
+            "$ref" => {
+                if let Some(file) = openapi_ref_file(value) {
+                    schema.imports.push((file, line_number));
+                }
+            }
+            _ => {}
+        }
+    }
+    schema
+}
+
+/// The file a `$ref` points into, if it is not a reference inside the same document.
+fn openapi_ref_file(reference: &str) -> Option<String> {
+    let file = reference.split('#').next()?.trim();
+    (!file.is_empty()).then(|| file.to_string())
+}
+
+fn parse_sql(content: &str) -> Schema {
+    let tokens = tokenize(content, &["--"], true);
+    let mut schema = Schema::default();
+
+    let words: Vec<(String, &Token)> = tokens
+        .iter()
+        .map(|(token, _)| match token {
+            Token::Word(word) => (word.to_ascii_lowercase(), token),
+            _ => (String::new(), token),
+        })
+        .collect();
+
+    for index in 0..words.len() {
+        let is_create = words[index].0 == "create";
+        let is_alter = words[index].0 == "alter";
+        if !is_create && !is_alter {
+            continue;
+        }
+
+        // CREATE [OR REPLACE] [TEMP] [MATERIALIZED] TABLE|VIEW [IF NOT EXISTS] name,
+        // ALTER TABLE [IF EXISTS] [ONLY] name
+        let mut position = index + 1;
+        while position < words.len()
+            && matches!(
+                words[position].0.as_str(),
+                "or" | "replace" | "temp" | "temporary" | "unlogged" | "materialized"
+            )
+        {
+            position += 1;
+        }
+        if !matches!(
+            words.get(position).map(|(word, _)| word.as_str()),
+            Some("table" | "view")
+        ) {
+            continue;
+        }
+        position += 1;
+        while position < words.len()
+            && matches!(words[position].0.as_str(), "if" | "not" | "exists" | "only")
+        {
+            position += 1;
+        }
+
+        // The last part of a qualified name: public.users, "app"."users", `users`
+        let mut name = None;
+        while let Some((_, token)) = words.get(position) {
+            match token {
+                Token::Word(word) | Token::Quoted(word) => name = Some(*word),
+                _ => break,
+            }
+            if !matches!(words.get(position + 1), Some((_, Token::Punct('.')))) {
+                break;
+            }
+            position += 2;
+        }
+        if let Some(name) = name {
+            schema.declarations.push(Declaration {
+                name: name.to_ascii_lowercase(),
+                kind: DeclarationKind::Table,
+            });
+        }
+    }
+    schema
+}
+
+/// A schema file linked to a source file, with the declarations the source uses.
+#[derive(Debug, Clone)]
+struct SchemaLink {
+    file: PathBuf,
+    declaration: String,
+}
+
+/// Schema declarations of a project by the names source code refers to them with.
+#[derive(Debug, Default)]
+struct SchemaIndex {
+    identifiers: HashMap<String, Vec<SchemaLink>>,
+    tables: HashMap<String, Vec<SchemaLink>>,
+    models: HashMap<String, Vec<SchemaLink>>,
+}
+
+impl SchemaIndex {
+    fn build(base_dir: &Path) -> Self {
+        let walker = walkdir::WalkDir::new(base_dir)
+            .max_depth(8)
+            .sort_by_file_name()
+            .into_iter()
+            .filter_entry(|entry| {
+                entry.depth() == 0
+                    || !entry.file_name().to_str().is_some_and(|name| {
+                        name.starts_with('.') || SCHEMA_SKIPPED_DIRS.contains(&name)
+                    })
+            });
+
+        let mut index = Self::default();
+        for entry in walker.flatten() {
+            let path = entry.path();
+            let Some(kind) = SchemaKind::for_path(path) else {
+                continue;
+            };
+            let is_small_file = entry
+                .metadata()
+                .is_ok_and(|metadata| metadata.is_file() && metadata.len() <= MAX_SCHEMA_FILE_SIZE);
+            if !is_small_file {
+                continue;
+            }
+            let Ok(content) = std::fs::read_to_string(path) else {
+                continue;
+            };
+
+            let file = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+            for declaration in parse_schema(kind, &content).declarations {
+                let link = SchemaLink {
+                    file: file.clone(),
+                    declaration: declaration.name.clone(),
+                };
+                if declaration.kind == DeclarationKind::Table {
+                    let model = pascal_case(&split_words(&singular(&declaration.name)));
+                    index.models.entry(model).or_default().push(link.clone());
+                    index
+                        .tables
+                        .entry(declaration.name.clone())
+                        .or_default()
+                        .push(link);
+                    continue;
+                }
+                for name in declaration.generated_names() {
+                    index
+                        .identifiers
+                        .entry(name)
+                        .or_default()
+                        .push(link.clone());
+                }
+            }
+        }
+        index
+    }
+
+    fn is_empty(&self) -> bool {
+        self.identifiers.is_empty() && self.tables.is_empty()
+    }
+}
+
+fn schema_index_cache() -> &'static Mutex<HashMap<PathBuf, Arc<SchemaIndex>>> {
+    static CACHE: OnceLock<Mutex<HashMap<PathBuf, Arc<SchemaIndex>>>> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn schema_index(base_dir: &Path) -> Arc<SchemaIndex> {
+    if let Some(index) = schema_index_cache()
+        .lock()
+        .ok()
+        .and_then(|cache| cache.get(base_dir).cloned())
+    {
+        return index;
+    }
+
+    let index = Arc::new(SchemaIndex::build(base_dir));
+    if let Ok(mut cache) = schema_index_cache().lock() {
+        cache.insert(base_dir.to_path_buf(), index.clone());
+    }
+    index
+}
+
+/// Names a source file uses that may refer to schema declarations.
+#[derive(Debug, Default)]
+struct SourceReferences<'a> {
+    identifiers: HashSet<&'a str>,
+    tables: HashSet<String>,
+    definitions: HashSet<&'a str>,
+}
+
+impl<'a> SourceReferences<'a> {
+    fn scan(content: &'a str) -> Self {
+        let mut references = Self::default();
+        let mut previous_word: Option<&str> = None;
+
+        for (token, _) in tokenize(content, &[], false) {
+            match token {
+                Token::Word(word) => {
+                    references.identifiers.insert(word);
+                    match previous_word {
+                        Some(previous) if previous.eq_ignore_ascii_case("table_name") => {
+                            references.tables.insert(word.to_ascii_lowercase());
+                        }
+                        Some(previous) if MODEL_KEYWORDS.contains(&previous) => {
+                            references.definitions.insert(word);
+                        }
+                        _ => {}
+                    }
+                    previous_word = Some(word);
+                }
+                Token::Quoted(text) => {
+                    references.scan_string(text);
+                    previous_word = None;
+                }
+                // `table_name = users`, `#[diesel(table_name = users)]`
+                Token::Punct('=' | ':' | '(') => {}
+                Token::Punct(_) => previous_word = None,
+            }
+        }
+        references
+    }
+
+    /// Table names in ORM mappings (`"users"`, `'public.users'`) and SQL queries
+    /// (`"SELECT * FROM users"`).
+    fn scan_string(&mut self, text: &str) {
+        let name = text.rsplit('.').next().unwrap_or(text);
+        if !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            self.tables.insert(name.to_ascii_lowercase());
+            return;
+        }
+
+        // Every part of a qualified name after a keyword counts: `FROM public.users`
+        let mut after_keyword = false;
+        let mut after_table = false;
+        for (token, _) in tokenize(text, &[], false) {
+            match token {
+                Token::Word(word) => {
+                    if after_keyword {
+                        self.tables.insert(word.to_ascii_lowercase());
+                    }
+                    after_table = after_keyword;
+                    after_keyword = SQL_TABLE_KEYWORDS
+                        .iter()
+                        .any(|keyword| word.eq_ignore_ascii_case(keyword));
+                }
+                Token::Punct('.') => after_keyword = after_table,
+                _ => {
+                    after_keyword = false;
+                    after_table = false;
+                }
+            }
+        }
+    }
+}
+
+/// The schema files a source file refers to, with the declarations it uses from each.
+pub fn schema_references(
+    path: &Path,
+    content: &str,
+    base_dir: &Path,
+) -> Vec<(PathBuf, Vec<String>)> {
+    if SchemaKind::for_path(path).is_some() {
+        return Vec::new();
+    }
+    let index = schema_index(base_dir);
+    if index.is_empty() {
+        return Vec::new();
+    }
+
+    let references = SourceReferences::scan(content);
+    let mut links: BTreeMap<PathBuf, BTreeSet<String>> = BTreeMap::new();
+    let matches = references
+        .identifiers
+        .iter()
+        .filter_map(|name| index.identifiers.get(*name))
+        .chain(
+            references
+                .tables
+                .iter()
+                .filter_map(|name| index.tables.get(name)),
+        )
+        .chain(
+            references
+                .definitions
+                .iter()
+                .filter_map(|name| index.models.get(*name)),
+        )
+        .flatten();
+    for link in matches {
+        links
+            .entry(link.file.clone())
+            .or_default()
+            .insert(link.declaration.clone());
+    }
+
+    links
+        .into_iter()
+        .map(|(file, declarations)| (file, declarations.into_iter().collect()))
+        .collect()
+}
+
+/// `UserService`, `get_user`, and `list-users` split into their words.
+fn split_words(name: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let chars: Vec<char> = name.chars().collect();
+    for (index, &c) in chars.iter().enumerate() {
+        if !c.is_ascii_alphanumeric() {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            continue;
+        }
+        let previous = index.checked_sub(1).map(|previous| chars[previous]);
+        let next = chars.get(index + 1);
+        let starts_word = c.is_ascii_uppercase()
+            && previous.is_some_and(|previous| {
+                previous.is_ascii_lowercase()
+                    || previous.is_ascii_digit()
+                    || (previous.is_ascii_uppercase()
+                        && next.is_some_and(|next| next.is_ascii_lowercase()))
+            });
+        if starts_word && !current.is_empty() {
+            words.push(std::mem::take(&mut current));
+        }
+        current.push(c);
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+    words
+}
+
+fn pascal_case(words: &[String]) -> String {
+    words
+        .iter()
+        .map(|word| {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .map(|first| {
+                    first.to_ascii_uppercase().to_string() + &chars.as_str().to_ascii_lowercase()
+                })
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+fn camel_case(words: &[String]) -> String {
+    let pascal = pascal_case(words);
+    let mut chars = pascal.chars();
+    chars
+        .next()
+        .map(|first| first.to_ascii_lowercase().to_string() + chars.as_str())
+        .unwrap_or_default()
+}
+
+fn snake_case(words: &[String]) -> String {
+    words
+        .iter()
+        .map(|word| word.to_ascii_lowercase())
+        .collect::<Vec<_>>()
+        .join("_")
+}
+
+/// The singular of the last word of a table name: `users` → `user`, `order_items` →
+/// `order_item`, `categories` → `category`.
+fn singular(table: &str) -> String {
+    if let Some(stem) = table.strip_suffix("ies") {
+        format!("{stem}y")
+    } else if table.ends_with("sses")
+        || table.ends_with("xes")
+        || table.ends_with("ches")
+        || table.ends_with("shes")
+    {
+        table[..table.len() - 2].to_string()
+    } else if table.ends_with('s') && !table.ends_with("ss") {
+        table[..table.len() - 1].to_string()
+    } else {
+        table.to_string()
+    }
+}
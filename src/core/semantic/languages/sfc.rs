@@ -0,0 +1,421 @@
+//! Semantic analyzer for Vue, Svelte, and Astro single-file components
+//!
+//! The `<script>` blocks of a component, and the frontmatter of an Astro component, are
+//! analyzed by the JavaScript or TypeScript analyzer on a copy of the file with everything
+//! else blanked out, so lines and columns match the component. Components used in the
+//! template without an import, like auto-imported Vue components, are found by name.
+
+use crate::core::semantic::analyzer::{
+    AnalysisResult, Import, LanguageAnalyzer, SemanticContext, SemanticResult,
+};
+use crate::core::semantic::languages::javascript::JavaScriptAnalyzer;
+use crate::core::semantic::languages::node::relative_specifier;
+use crate::core::semantic::languages::typescript::TypeScriptAnalyzer;
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+
+/// Directories holding installed dependencies or build output.
+const SFC_SKIPPED_DIRS: &[&str] = &["node_modules", "dist", "build", "coverage"];
+
+/// Component file extensions.
+const SFC_EXTENSIONS: &[&str] = &["vue", "svelte", "astro"];
+
+/// The component format of a file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SfcKind {
+    Vue,
+    Svelte,
+    Astro,
+}
+
+impl SfcKind {
+    fn extension(self) -> &'static str {
+        match self {
+            SfcKind::Vue => "vue",
+            SfcKind::Svelte => "svelte",
+            SfcKind::Astro => "astro",
+        }
+    }
+}
+
+pub struct SfcAnalyzer {
+    kind: SfcKind,
+}
+
+impl SfcAnalyzer {
+    pub fn new(kind: SfcKind) -> Self {
+        Self { kind }
+    }
+}
+
+fn javascript_analyzer() -> &'static JavaScriptAnalyzer {
+    static ANALYZER: OnceLock<JavaScriptAnalyzer> = OnceLock::new();
+    ANALYZER.get_or_init(JavaScriptAnalyzer::new)
+}
+
+fn typescript_analyzer() -> &'static TypeScriptAnalyzer {
+    static ANALYZER: OnceLock<TypeScriptAnalyzer> = OnceLock::new();
+    ANALYZER.get_or_init(TypeScriptAnalyzer::new)
+}
+
+impl LanguageAnalyzer for SfcAnalyzer {
+    fn language_name(&self) -> &'static str {
+        match self.kind {
+            SfcKind::Vue => "Vue",
+            SfcKind::Svelte => "Svelte",
+            SfcKind::Astro => "Astro",
+        }
+    }
+
+    fn analyze_file(
+        &self,
+        path: &Path,
+        content: &str,
+        context: &SemanticContext,
+    ) -> SemanticResult<AnalysisResult> {
+        let blocks = script_blocks(content, self.kind);
+        let script = masked_scripts(content, &blocks);
+
+        let mut result = if blocks.iter().any(|block| block.typescript) {
+            typescript_analyzer().analyze_file(path, &script, context)?
+        } else {
+            javascript_analyzer().analyze_file(path, &script, context)?
+        };
+
+        let tags = template_component_tags(content, &blocks, self.kind);
+        self.add_template_component_imports(&mut result, path, tags, context);
+        Ok(result)
+    }
+
+    fn can_handle_extension(&self, extension: &str) -> bool {
+        extension == self.kind.extension()
+    }
+
+    fn supported_extensions(&self) -> Vec<&'static str> {
+        vec![self.kind.extension()]
+    }
+}
+
+impl SfcAnalyzer {
+    /// Add imports for components the template uses without importing them, which Vue
+    /// projects get from auto-import plugins and Nuxt's `components/` directories.
+    fn add_template_component_imports(
+        &self,
+        result: &mut AnalysisResult,
+        path: &Path,
+        tags: Vec<(String, usize)>,
+        context: &SemanticContext,
+    ) {
+        let imported: HashSet<&str> = result
+            .imports
+            .iter()
+            .flat_map(|import| import.items.iter().map(String::as_str))
+            .collect();
+        let tags: Vec<(String, usize)> = tags
+            .into_iter()
+            .filter(|(name, _)| !imported.contains(name.as_str()))
+            .collect();
+        if tags.is_empty() {
+            return;
+        }
+
+        let current_file = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+        let index = component_index(&context.base_dir);
+        let mut added = HashSet::new();
+        for (name, line) in tags {
+            if !added.insert(name.clone()) {
+                continue;
+            }
+            let Some(file) = index.find(&name, &current_file, self.kind.extension()) else {
+                continue;
+            };
+            if let Some(module) = relative_specifier(&current_file, file) {
+                result.imports.push(Import {
+                    module,
+                    items: vec![name],
+                    is_relative: true,
+                    line,
+                });
+            }
+        }
+    }
+}
+
+/// The source of a `<script>` block or of Astro frontmatter.
+#[derive(Debug)]
+struct ScriptBlock {
+    range: Range<usize>,
+    typescript: bool,
+}
+
+fn script_blocks(content: &str, kind: SfcKind) -> Vec<ScriptBlock> {
+    let mut blocks = Vec::new();
+    if kind == SfcKind::Astro {
+        if let Some(range) = astro_frontmatter(content) {
+            blocks.push(ScriptBlock {
+                range,
+                typescript: true,
+            });
+        }
+    }
+
+    for (attributes, range) in element_blocks(content, "script") {
+        // Scripts loaded with `src` have no inline source
+        if attribute_value(attributes, "src").is_some() {
+            continue;
+        }
+        let typescript = kind == SfcKind::Astro
+            || matches!(
+                attribute_value(attributes, "lang"),
+                Some("ts" | "tsx" | "typescript")
+            );
+        blocks.push(ScriptBlock { range, typescript });
+    }
+
+    blocks
+}
+
+/// The byte range between the `---` fences opening an Astro component.
+fn astro_frontmatter(content: &str) -> Option<Range<usize>> {
+    let leading = content.len() - content.trim_start().len();
+    let rest = &content[leading..];
+    let first_line_end = rest.find('\n')?;
+    if rest[..first_line_end].trim_end() != "---" {
+        return None;
+    }
+
+    let start = leading + first_line_end + 1;
+    let mut offset = start;
+    for line in content[start..].split_inclusive('\n') {
+        if line.trim_end() == "---" {
+            return Some(start..offset);
+        }
+        offset += line.len();
+    }
+    None
+}
+
+/// The attributes and body ranges of every `<name ...>...</name>` element.
+fn element_blocks<'a>(content: &'a str, name: &str) -> Vec<(&'a str, Range<usize>)> {
+    let open = format!("<{name}");
+    let close = format!("</{name}");
+    let mut blocks = Vec::new();
+    let mut offset = 0;
+    while let Some(found) = content[offset..].find(&open) {
+        let after_name = offset + found + open.len();
+        let boundary = content[after_name..].chars().next();
+        if !boundary.is_some_and(|c| c.is_whitespace() || c == '>' || c == '/') {
+            offset = after_name;
+            continue;
+        }
+        let Some(tag_end) = content[after_name..].find('>') else {
+            break;
+        };
+        let attributes = &content[after_name..after_name + tag_end];
+        let body_start = after_name + tag_end + 1;
+        if attributes.trim_end().ends_with('/') {
+            offset = body_start;
+            continue;
+        }
+        let Some(body_len) = content[body_start..].find(&close) else {
+            break;
+        };
+        blocks.push((attributes, body_start..body_start + body_len));
+        offset = body_start + body_len;
+    }
+    blocks
+}
+
+fn attribute_value<'a>(attributes: &'a str, name: &str) -> Option<&'a str> {
+    attributes.split_whitespace().find_map(|attribute| {
+        let (key, value) = attribute.split_once('=').unwrap_or((attribute, ""));
+        (key == name).then(|| {
+            value
+                .trim_end_matches('/')
+                .trim_matches(|c| c == '"' || c == '\'')
+        })
+    })
+}
+
+/// The component with everything outside its scripts replaced by spaces, keeping line
+/// breaks so positions stay the same.
+fn masked_scripts(content: &str, blocks: &[ScriptBlock]) -> String {
+    let mut masked: Vec<u8> = content
+        .bytes()
+        .map(|byte| if byte == b'\n' { b'\n' } else { b' ' })
+        .collect();
+    for block in blocks {
+        masked[block.range.clone()].copy_from_slice(&content.as_bytes()[block.range.clone()]);
+    }
+    // Script ranges start and end next to ASCII delimiters, so the result is UTF-8
+    String::from_utf8(masked).unwrap_or_default()
+}
+
+/// Component tags in the template with their line: `<UserCard/>` and, in Vue,
+/// `<user-card>` as `UserCard`.
+fn template_component_tags(
+    content: &str,
+    blocks: &[ScriptBlock],
+    kind: SfcKind,
+) -> Vec<(String, usize)> {
+    let skipped: Vec<Range<usize>> = blocks
+        .iter()
+        .map(|block| block.range.clone())
+        .chain(
+            element_blocks(content, "style")
+                .into_iter()
+                .map(|(_, range)| range),
+        )
+        .collect();
+
+    let bytes = content.as_bytes();
+    let mut tags = Vec::new();
+    let mut line = 1;
+    for (index, byte) in bytes.iter().enumerate() {
+        if *byte == b'\n' {
+            line += 1;
+            continue;
+        }
+        if *byte != b'<' || skipped.iter().any(|range| range.contains(&index)) {
+            continue;
+        }
+
+        let name: String = content[index + 1..]
+            .chars()
+            .take_while(|c| c.is_ascii_alphanumeric() || *c == '-' || *c == '_')
+            .collect();
+        let is_pascal = name.starts_with(|c: char| c.is_ascii_uppercase());
+        let is_kebab = kind == SfcKind::Vue
+            && name.contains('-')
+            && name.starts_with(|c: char| c.is_ascii_lowercase());
+        if is_pascal || is_kebab {
+            tags.push((pascal_case(&name), line));
+        }
+    }
+    tags
+}
+
+/// `user-card` and `user_card` as `UserCard`; PascalCase names are kept.
+fn pascal_case(name: &str) -> String {
+    name.split(['-', '_'])
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            chars
+                .next()
+                .map(|first| first.to_ascii_uppercase().to_string() + chars.as_str())
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+/// Component files of the project by the names templates use for them.
+#[derive(Debug, Default)]
+struct ComponentIndex {
+    by_name: HashMap<String, Vec<PathBuf>>,
+}
+
+impl ComponentIndex {
+    fn build(base_dir: &Path) -> Self {
+        let walker = walkdir::WalkDir::new(base_dir)
+            .max_depth(8)
+            .sort_by_file_name()
+            .into_iter()
+            .filter_entry(|entry| {
+                entry.depth() == 0
+                    || !entry.file_name().to_str().is_some_and(|name| {
+                        name.starts_with('.') || SFC_SKIPPED_DIRS.contains(&name)
+                    })
+            });
+
+        let mut index = Self::default();
+        for entry in walker.flatten() {
+            let path = entry.path();
+            let is_component = path
+                .extension()
+                .and_then(|ext| ext.to_str())
+                .is_some_and(|ext| SFC_EXTENSIONS.contains(&ext));
+            if !entry.file_type().is_file() || !is_component {
+                continue;
+            }
+            let file = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+            for name in component_names(path) {
+                index.by_name.entry(name).or_default().push(file.clone());
+            }
+        }
+        index
+    }
+
+    /// The component named `name` with the given extension closest to `from_file`.
+    fn find(&self, name: &str, from_file: &Path, extension: &str) -> Option<&PathBuf> {
+        self.by_name
+            .get(name)?
+            .iter()
+            .filter(|file| file.extension().and_then(|ext| ext.to_str()) == Some(extension))
+            .filter(|file| file.as_path() != from_file)
+            // `max_by_key` keeps the last of equal keys, so reverse to prefer the first
+            .rev()
+            .max_by_key(|file| {
+                file.components()
+                    .zip(from_file.components())
+                    .take_while(|(left, right)| left == right)
+                    .count()
+            })
+    }
+}
+
+/// The file name as a component name, and for files below a `components/` directory the
+/// name Nuxt registers, prefixed with the directories in between (`base/Button.vue` is
+/// `BaseButton`).
+fn component_names(path: &Path) -> Vec<String> {
+    let Some(stem) = path.file_stem().and_then(|stem| stem.to_str()) else {
+        return Vec::new();
+    };
+    let file_name = pascal_case(stem);
+    let mut names = vec![file_name.clone()];
+
+    let directories: Vec<&str> = path
+        .parent()
+        .into_iter()
+        .flat_map(|parent| parent.components())
+        .filter_map(|component| component.as_os_str().to_str())
+        .collect();
+    if let Some(position) = directories.iter().rposition(|dir| *dir == "components") {
+        let prefix: String = directories[position + 1..]
+            .iter()
+            .map(|dir| pascal_case(dir))
+            .collect();
+        let nested = if file_name.starts_with(&prefix) {
+            file_name
+        } else {
+            format!("{prefix}{file_name}")
+        };
+        if !names.contains(&nested) {
+            names.push(nested);
+        }
+    }
+    names
+}
+
+fn component_index_cache() -> &'static Mutex<HashMap<PathBuf, Arc<ComponentIndex>>> {
+    static CACHE: OnceLock<Mutex<HashMap<PathBuf, Arc<ComponentIndex>>>> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn component_index(base_dir: &Path) -> Arc<ComponentIndex> {
+    if let Some(index) = component_index_cache()
+        .lock()
+        .ok()
+        .and_then(|cache| cache.get(base_dir).cloned())
+    {
+        return index;
+    }
+
+    let index = Arc::new(ComponentIndex::build(base_dir));
+    if let Ok(mut cache) = component_index_cache().lock() {
+        cache.insert(base_dir.to_path_buf(), index.clone());
+    }
+    index
+}
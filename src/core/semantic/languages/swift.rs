@@ -13,7 +13,7 @@ use crate::utils::error::ContextCreatorError;
 use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
-use std::sync::{Mutex, OnceLock};
+use std::sync::{Arc, Mutex, OnceLock};
 use tree_sitter::{Node, Parser, Query, QueryCursor};
 
 #[allow(clippy::new_without_default)]
@@ -25,6 +25,18 @@ impl SwiftAnalyzer {
     }
 }
 
+/// Calls in `Package.swift` that declare a target.
+const SWIFT_TARGET_MARKERS: &[&str] = &[
+    ".target(",
+    ".executableTarget(",
+    ".testTarget(",
+    ".macro(",
+    ".plugin(",
+];
+
+/// Upper bound on local path packages followed from one manifest.
+const MAX_LOCAL_SWIFT_PACKAGES: usize = 64;
+
 fn swift_query_engine() -> &'static QueryEngine {
     static ENGINE: OnceLock<QueryEngine> = OnceLock::new();
     ENGINE.get_or_init(|| {
@@ -38,9 +50,8 @@ fn swift_module_type_cache() -> &'static Mutex<HashMap<PathBuf, HashMap<String,
     CACHE.get_or_init(|| Mutex::new(HashMap::new()))
 }
 
-fn swift_manifest_target_path_cache() -> &'static Mutex<HashMap<PathBuf, HashMap<String, PathBuf>>>
-{
-    static CACHE: OnceLock<Mutex<HashMap<PathBuf, HashMap<String, PathBuf>>>> = OnceLock::new();
+fn swift_package_cache() -> &'static Mutex<HashMap<PathBuf, Arc<SwiftPackage>>> {
+    static CACHE: OnceLock<Mutex<HashMap<PathBuf, Arc<SwiftPackage>>>> = OnceLock::new();
     CACHE.get_or_init(|| Mutex::new(HashMap::new()))
 }
 
@@ -79,6 +90,7 @@ impl LanguageAnalyzer for SwiftAnalyzer {
                 ..AnalysisResult::default()
             };
             self.normalize_imports(&mut result);
+            self.add_target_dependency_imports(&mut result, path, &context.base_dir);
             return Ok(result);
         }
 
@@ -92,6 +104,7 @@ impl LanguageAnalyzer for SwiftAnalyzer {
         let mut result = swift_query_engine().analyze_with_parser(&mut parser, content)?;
         result.imports = extract_swift_imports_fast(content);
         self.normalize_imports(&mut result);
+        self.add_target_dependency_imports(&mut result, path, &context.base_dir);
 
         if include_function_calls {
             result.function_calls = extract_swift_function_calls(content)?;
@@ -102,7 +115,7 @@ impl LanguageAnalyzer for SwiftAnalyzer {
         if include_type_references {
             self.merge_swift_type_references(&mut result, content)?;
             self.correlate_qualified_symbols_with_imports(&mut result);
-            self.dedupe_type_references(&mut result);
+            result.dedupe_type_references(|type_ref| !is_swift_builtin_type(&type_ref.name));
             self.resolve_swift_type_definitions(
                 &mut result.type_references,
                 &result.imports,
@@ -130,6 +143,10 @@ impl LanguageAnalyzer for SwiftAnalyzer {
     fn supported_extensions(&self) -> Vec<&'static str> {
         vec!["swift"]
     }
+
+    fn query_engine(&self) -> Option<&QueryEngine> {
+        Some(swift_query_engine())
+    }
 }
 
 impl SwiftAnalyzer {
@@ -163,6 +180,32 @@ impl SwiftAnalyzer {
         result.imports = merged;
     }
 
+    /// Link test target files to the local targets their `Package.swift` entry depends on,
+    /// even when the file itself doesn't import them.
+    fn add_target_dependency_imports(
+        &self,
+        result: &mut AnalysisResult,
+        path: &Path,
+        base_dir: &Path,
+    ) {
+        for dependency in swift_target_dependencies(path, base_dir) {
+            if result
+                .imports
+                .iter()
+                .any(|import| import.module == dependency)
+            {
+                continue;
+            }
+            result.imports.push(Import {
+                module: dependency,
+                items: Vec::new(),
+                is_relative: false,
+                // Declared in the manifest rather than on a line of this file
+                line: 0,
+            });
+        }
+    }
+
     fn merge_swift_type_references(
         &self,
         result: &mut AnalysisResult,
@@ -209,28 +252,6 @@ impl SwiftAnalyzer {
         }
     }
 
-    fn dedupe_type_references(&self, result: &mut AnalysisResult) {
-        let mut deduped = Vec::new();
-        let mut seen = HashSet::new();
-
-        for type_ref in result.type_references.drain(..) {
-            if is_swift_builtin_type(&type_ref.name) {
-                continue;
-            }
-
-            let key = (
-                type_ref.name.clone(),
-                type_ref.module.clone(),
-                type_ref.line,
-            );
-            if seen.insert(key) {
-                deduped.push(type_ref);
-            }
-        }
-
-        result.type_references = deduped;
-    }
-
     fn resolve_swift_type_definitions(
         &self,
         type_refs: &mut [TypeReference],
@@ -357,6 +378,14 @@ impl ModuleResolver for SwiftModuleResolver {
         validate_module_name(&module)?;
 
         let package_root = find_swift_package_root(from_file, base_dir);
+        if package_root.join("Package.swift").exists() {
+            if let Some(path) = swift_target_in_packages(&package_root, &module)
+                .and_then(|target| find_swift_target_file(&target))
+            {
+                return resolved_swift_path(path, base_dir, false, 0.95);
+            }
+        }
+
         for candidate in swift_module_candidate_dirs(&module, from_file, &package_root, base_dir) {
             if let Some(path) = find_swift_module_file(&candidate, &module) {
                 return resolved_swift_path(path, base_dir, false, 0.95);
@@ -906,6 +935,23 @@ fn find_swift_module_file(candidate: &Path, module: &str) -> Option<PathBuf> {
     swift_source_files(candidate).into_iter().next()
 }
 
+fn find_swift_target_file(target: &SwiftTarget) -> Option<PathBuf> {
+    let files = swift_source_files(&target.dir)
+        .into_iter()
+        .filter(|file| target.contains(file))
+        .collect::<Vec<_>>();
+    let preferred = [format!("{}.swift", target.name), "main.swift".to_string()];
+    preferred
+        .iter()
+        .find_map(|name| {
+            files
+                .iter()
+                .find(|file| file.file_name().and_then(|n| n.to_str()) == Some(name.as_str()))
+        })
+        .or_else(|| files.first())
+        .cloned()
+}
+
 fn swift_source_files(root: &Path) -> Vec<PathBuf> {
     fn visit(dir: &Path, files: &mut Vec<PathBuf>) {
         let Ok(entries) = fs::read_dir(dir) else {
@@ -944,13 +990,8 @@ fn swift_source_files(root: &Path) -> Vec<PathBuf> {
 pub(crate) fn swift_target_dir_for_file(file: &Path, project_root: &Path) -> Option<PathBuf> {
     let package_root = find_swift_package_root(file, project_root);
     if package_root.join("Package.swift").exists() {
-        let mut matching_targets = swift_manifest_target_paths(&package_root)
-            .into_values()
-            .filter(|target_dir| file.starts_with(target_dir))
-            .collect::<Vec<_>>();
-        matching_targets.sort_by_key(|path| path.components().count());
-        if let Some(target_dir) = matching_targets.pop() {
-            return Some(target_dir);
+        if let Some(target) = swift_package(&package_root).target_for_file(file) {
+            return Some(target.dir.clone());
         }
     }
 
@@ -974,75 +1015,246 @@ pub(crate) fn swift_target_dir_for_file(file: &Path, project_root: &Path) -> Opt
     file.parent().map(Path::to_path_buf)
 }
 
-fn swift_manifest_target_paths(package_root: &Path) -> HashMap<String, PathBuf> {
+/// How SwiftPM builds a target, which decides its default directory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SwiftTargetKind {
+    Regular,
+    Test,
+    Plugin,
+}
+
+/// A target declared in `Package.swift`.
+#[derive(Debug, Clone)]
+struct SwiftTarget {
+    name: String,
+    kind: SwiftTargetKind,
+    dir: PathBuf,
+    /// Files or directories of `sources:`; the whole target directory when empty
+    sources: Vec<PathBuf>,
+    exclude: Vec<PathBuf>,
+    /// Names of the targets and products the target depends on
+    dependencies: Vec<String>,
+}
+
+impl SwiftTarget {
+    fn contains(&self, file: &Path) -> bool {
+        file.starts_with(&self.dir)
+            && (self.sources.is_empty()
+                || self.sources.iter().any(|source| file.starts_with(source)))
+            && !self
+                .exclude
+                .iter()
+                .any(|excluded| file.starts_with(excluded))
+    }
+}
+
+/// The targets, products, and local package dependencies of a `Package.swift`.
+#[derive(Debug, Default)]
+struct SwiftPackage {
+    targets: Vec<SwiftTarget>,
+    /// Library and executable products mapped to the targets they vend
+    products: HashMap<String, Vec<String>>,
+    /// Roots of the packages added with `.package(path:)`
+    local_packages: Vec<PathBuf>,
+}
+
+impl SwiftPackage {
+    fn target(&self, name: &str) -> Option<&SwiftTarget> {
+        self.targets.iter().find(|target| target.name == name)
+    }
+
+    fn target_for_file(&self, file: &Path) -> Option<&SwiftTarget> {
+        self.targets
+            .iter()
+            .filter(|target| target.contains(file))
+            .max_by_key(|target| target.dir.components().count())
+    }
+}
+
+fn swift_package(package_root: &Path) -> Arc<SwiftPackage> {
     let cache_key = package_root
         .canonicalize()
         .unwrap_or_else(|_| package_root.to_path_buf());
 
-    if let Some(paths) = swift_manifest_target_path_cache()
+    if let Some(package) = swift_package_cache()
         .lock()
         .ok()
         .and_then(|cache| cache.get(&cache_key).cloned())
     {
-        return paths;
+        return package;
     }
 
-    let paths = parse_swift_manifest_target_paths(package_root);
-    if let Ok(mut cache) = swift_manifest_target_path_cache().lock() {
-        cache.insert(cache_key, paths.clone());
+    let package = Arc::new(parse_swift_package(package_root));
+    if let Ok(mut cache) = swift_package_cache().lock() {
+        cache.insert(cache_key, package.clone());
     }
-    paths
+    package
+}
+
+fn swift_manifest_target_paths(package_root: &Path) -> HashMap<String, PathBuf> {
+    swift_package(package_root)
+        .targets
+        .iter()
+        .map(|target| (target.name.clone(), target.dir.clone()))
+        .collect()
 }
 
-fn parse_swift_manifest_target_paths(package_root: &Path) -> HashMap<String, PathBuf> {
+fn parse_swift_package(package_root: &Path) -> SwiftPackage {
     let package_file = package_root.join("Package.swift");
     let Ok(content) = fs::read_to_string(package_file) else {
-        return HashMap::new();
+        return SwiftPackage::default();
     };
 
-    let mut target_paths = HashMap::new();
-    for call in swift_manifest_target_calls(&content) {
+    let mut package = SwiftPackage::default();
+    for (marker, call) in swift_manifest_calls(&content, SWIFT_TARGET_MARKERS) {
         let Some(name) = swift_call_string_argument(&call, "name") else {
             continue;
         };
-        let Some(path) = swift_call_string_argument(&call, "path") else {
-            continue;
+        let kind = match marker {
+            ".testTarget(" => SwiftTargetKind::Test,
+            ".plugin(" => SwiftTargetKind::Plugin,
+            _ => SwiftTargetKind::Regular,
         };
+        let dir = match swift_call_string_argument(&call, "path") {
+            Some(path) => package_root.join(path),
+            None => swift_default_target_dir(package_root, kind, &name),
+        };
+        let in_target = |paths: Vec<String>| paths.into_iter().map(|path| dir.join(path)).collect();
+
+        package.targets.push(SwiftTarget {
+            sources: in_target(swift_call_string_array_argument(&call, "sources")),
+            exclude: in_target(swift_call_string_array_argument(&call, "exclude")),
+            dependencies: swift_call_dependency_names(&call),
+            name,
+            kind,
+            dir,
+        });
+    }
+
+    for (_, call) in swift_manifest_calls(&content, &[".library(", ".executable("]) {
+        if let Some(name) = swift_call_string_argument(&call, "name") {
+            let targets = swift_call_string_array_argument(&call, "targets");
+            package.products.insert(name, targets);
+        }
+    }
 
-        target_paths.insert(name, package_root.join(path));
+    for (_, call) in swift_manifest_calls(&content, &[".package("]) {
+        if let Some(path) = swift_call_string_argument(&call, "path") {
+            let root = package_root.join(path);
+            package
+                .local_packages
+                .push(root.canonicalize().unwrap_or(root));
+        }
     }
 
-    target_paths
+    package
 }
 
-fn swift_manifest_target_calls(content: &str) -> Vec<String> {
-    const TARGET_MARKERS: &[&str] = &[
-        ".target(",
-        ".executableTarget(",
-        ".testTarget(",
-        ".macro(",
-        ".plugin(",
-    ];
+/// SwiftPM looks for target directories under `Sources`, `Source`, `src`, or `srcs`;
+/// test targets live under `Tests` and plugins under `Plugins`.
+fn swift_default_target_dir(package_root: &Path, kind: SwiftTargetKind, name: &str) -> PathBuf {
+    let parents: &[&str] = match kind {
+        SwiftTargetKind::Regular => &["Sources", "Source", "src", "srcs"],
+        SwiftTargetKind::Test => &["Tests"],
+        SwiftTargetKind::Plugin => &["Plugins"],
+    };
+    parents
+        .iter()
+        .map(|parent| package_root.join(parent).join(name))
+        .find(|dir| dir.is_dir())
+        .unwrap_or_else(|| package_root.join(parents[0]).join(name))
+}
 
+/// The package directory declaring a target or product named `module`, searched in the
+/// package itself and then in its local package dependencies.
+fn swift_target_in_packages(package_root: &Path, module: &str) -> Option<SwiftTarget> {
+    let mut pending = vec![package_root
+        .canonicalize()
+        .unwrap_or_else(|_| package_root.to_path_buf())];
+    let mut visited = HashSet::new();
+
+    while let Some(root) = pending.pop() {
+        if !visited.insert(root.clone()) || visited.len() > MAX_LOCAL_SWIFT_PACKAGES {
+            continue;
+        }
+        let package = swift_package(&root);
+        if let Some(target) = package.target(module) {
+            return Some(target.clone());
+        }
+        if let Some(target) = package
+            .products
+            .get(module)
+            .and_then(|targets| targets.first())
+            .and_then(|name| package.target(name))
+        {
+            return Some(target.clone());
+        }
+        pending.extend(package.local_packages.iter().rev().cloned());
+    }
+
+    None
+}
+
+/// Local targets a file's target depends on, so test targets link to the targets they test.
+fn swift_target_dependencies(file: &Path, project_root: &Path) -> Vec<String> {
+    let package_root = find_swift_package_root(file, project_root);
+    if !package_root.join("Package.swift").exists() {
+        return Vec::new();
+    }
+    let package = swift_package(&package_root);
+    let Some(target) = package.target_for_file(file) else {
+        return Vec::new();
+    };
+    if target.kind != SwiftTargetKind::Test {
+        return Vec::new();
+    }
+    target
+        .dependencies
+        .iter()
+        .filter(|name| swift_target_in_packages(&package_root, name).is_some())
+        .cloned()
+        .collect()
+}
+
+/// Whether `file` belongs to the target at `target_dir` given its `sources:` and `exclude:`.
+pub(crate) fn swift_target_includes_file(
+    target_dir: &Path,
+    file: &Path,
+    project_root: &Path,
+) -> bool {
+    let package_root = find_swift_package_root(target_dir, project_root);
+    if !package_root.join("Package.swift").exists() {
+        return true;
+    }
+    swift_package(&package_root)
+        .targets
+        .iter()
+        .find(|target| target.dir == target_dir)
+        .map_or(true, |target| target.contains(file))
+}
+
+/// The argument lists of the calls to any of `markers` in a manifest, with the marker
+/// that opened each. Calls nested inside a matched call are not listed separately.
+fn swift_manifest_calls(content: &str, markers: &[&'static str]) -> Vec<(&'static str, String)> {
     let mut calls = Vec::new();
     let mut offset = 0;
     while offset < content.len() {
-        let next = TARGET_MARKERS
+        let next = markers
             .iter()
             .filter_map(|marker| {
                 content[offset..]
                     .find(marker)
-                    .map(|index| (offset + index, marker.len()))
+                    .map(|index| (offset + index, *marker))
             })
             .min_by_key(|(index, _)| *index);
 
-        let Some((marker_index, marker_len)) = next else {
+        let Some((marker_index, marker)) = next else {
             break;
         };
 
-        let open_paren = marker_index + marker_len - 1;
+        let open_paren = marker_index + marker.len() - 1;
         if let Some((call, end_index)) = swift_balanced_parenthesized_content(content, open_paren) {
-            calls.push(call);
+            calls.push((marker, call));
             offset = end_index;
         } else {
             offset = open_paren + 1;
@@ -1125,6 +1337,83 @@ fn swift_call_string_argument(call: &str, label: &str) -> Option<String> {
     None
 }
 
+/// The string literals of an array argument such as `sources: ["A", "B"]`.
+fn swift_call_string_array_argument(call: &str, label: &str) -> Vec<String> {
+    swift_call_array_elements(call, label)
+        .iter()
+        .filter_map(|element| swift_first_string_literal(element))
+        .collect()
+}
+
+/// Target and product names of a `dependencies:` array: `"Core"`, `.target(name: "Core")`,
+/// `.byName(name: "Core")`, or `.product(name: "Core", package: "core-kit")`.
+fn swift_call_dependency_names(call: &str) -> Vec<String> {
+    swift_call_array_elements(call, "dependencies")
+        .iter()
+        .filter_map(|element| {
+            swift_call_string_argument(element, "name")
+                .or_else(|| swift_first_string_literal(element))
+        })
+        .collect()
+}
+
+/// The top-level, comma-separated elements of the array following `label:`.
+fn swift_call_array_elements(call: &str, label: &str) -> Vec<String> {
+    let label = format!("{label}:");
+    let Some(label_index) = call.find(&label) else {
+        return Vec::new();
+    };
+    let after_label = &call[label_index + label.len()..];
+    if !after_label.trim_start().starts_with('[') {
+        return Vec::new();
+    }
+    let open = after_label.find('[').unwrap_or_default();
+
+    let mut elements = Vec::new();
+    let mut current = String::new();
+    let mut depth = 0usize;
+    let mut in_string = false;
+    let mut escaping = false;
+    for ch in after_label[open + 1..].chars() {
+        if in_string {
+            if escaping {
+                escaping = false;
+            } else if ch == '\\' {
+                escaping = true;
+            } else if ch == '"' {
+                in_string = false;
+            }
+            current.push(ch);
+            continue;
+        }
+        match ch {
+            '"' => in_string = true,
+            '(' | '[' => depth += 1,
+            ')' | ']' if depth == 0 => break,
+            ')' | ']' => depth -= 1,
+            ',' if depth == 0 => {
+                elements.push(std::mem::take(&mut current));
+                continue;
+            }
+            _ => {}
+        }
+        current.push(ch);
+    }
+    elements.push(current);
+
+    elements
+        .into_iter()
+        .map(|element| element.trim().to_string())
+        .filter(|element| !element.is_empty())
+        .collect()
+}
+
+fn swift_first_string_literal(text: &str) -> Option<String> {
+    let start = text.find('"')? + 1;
+    let end = text[start..].find('"')? + start;
+    Some(text[start..end].to_string())
+}
+
 fn swift_sources_dir_for_file(file: &Path) -> Option<PathBuf> {
     let mut current = file.parent()?;
     loop {
@@ -1,5 +1,8 @@
 //! Semantic analyzer for TypeScript
 
+use crate::core::semantic::languages::node::{
+    resolve_bare_specifier, resolve_module_file, trace_barrel_imports, validate_relative_specifier,
+};
 use crate::core::semantic::{
     analyzer::{AnalysisResult, LanguageAnalyzer, SemanticContext, SemanticResult},
     path_validator::{validate_import_path, validate_module_name},
@@ -44,6 +47,17 @@ impl LanguageAnalyzer for TypeScriptAnalyzer {
             .query_engine
             .analyze_with_parser(&mut parser, content)?;
 
+        // Imported names come from the files a barrel re-exports them from
+        trace_barrel_imports(
+            &mut result.imports,
+            path,
+            &context.base_dir,
+            &TypeScriptModuleResolver,
+        );
+
+        // Types imported by name come from the module the import resolves to
+        self.resolve_imported_types(&mut result, path, &context.base_dir);
+
         // Resolve type definitions for the type references found
         self.query_engine.resolve_type_definitions(
             &mut result.type_references,
@@ -61,8 +75,54 @@ impl LanguageAnalyzer for TypeScriptAnalyzer {
     fn supported_extensions(&self) -> Vec<&'static str> {
         vec!["ts", "tsx"]
     }
+
+    fn query_engine(&self) -> Option<&QueryEngine> {
+        Some(&self.query_engine)
+    }
+}
+
+impl TypeScriptAnalyzer {
+    /// Point type references at the file an import of the same name resolves to,
+    /// following path aliases and workspace packages down to `.d.ts` declarations.
+    fn resolve_imported_types(
+        &self,
+        result: &mut AnalysisResult,
+        path: &Path,
+        project_root: &Path,
+    ) {
+        let resolver = TypeScriptModuleResolver;
+        for type_ref in &mut result.type_references {
+            if type_ref.definition_path.is_some() || type_ref.is_external {
+                continue;
+            }
+            let Some(import) = result
+                .imports
+                .iter()
+                .find(|import| import.items.contains(&type_ref.name))
+            else {
+                continue;
+            };
+            let Ok(resolved) = resolver.resolve_import(&import.module, path, project_root) else {
+                continue;
+            };
+
+            type_ref.module = Some(import.module.clone());
+            if resolved.is_external {
+                type_ref.is_external = true;
+                type_ref.external_package = Some(import.module.clone());
+            } else {
+                type_ref.definition_path = Some(resolved.path);
+            }
+        }
+    }
 }
 
+/// Extensions tried when resolving a module path, TypeScript first, then declarations,
+/// JavaScript, and single-file components.
+const EXTENSIONS: &[&str] = &[
+    "ts", "tsx", "d.ts", "js", "jsx", "mjs", "cjs", "vue", "svelte", "astro",
+];
+
 pub struct TypeScriptModuleResolver;
 
 impl ModuleResolver for TypeScriptModuleResolver {
@@ -73,7 +133,35 @@ impl ModuleResolver for TypeScriptModuleResolver {
         base_dir: &Path,
     ) -> Result<ResolvedPath, ContextCreatorError> {
         // Validate module name for security
-        validate_module_name(module_path)?;
+        if module_path.starts_with('.') {
+            validate_relative_specifier(module_path)?;
+        } else {
+            validate_module_name(module_path)?;
+        }
+
+        // Handle relative imports (./, ../)
+        if module_path.starts_with('.') {
+            if let Some(parent) = from_file.parent() {
+                if let Some(file) = resolve_module_file(&parent.join(module_path), EXTENSIONS) {
+                    let validated_path = validate_import_path(base_dir, &file)?;
+                    return Ok(ResolvedPath {
+                        path: validated_path,
+                        is_external: false,
+                        confidence: 0.9,
+                    });
+                }
+            }
+        }
+
+        // Handle tsconfig/jsconfig path aliases and workspace packages
+        if let Some(file) = resolve_bare_specifier(module_path, from_file, base_dir, EXTENSIONS) {
+            let validated_path = validate_import_path(base_dir, &file)?;
+            return Ok(ResolvedPath {
+                path: validated_path,
+                is_external: false,
+                confidence: 0.9,
+            });
+        }
 
         // Handle Node.js built-in modules and common packages
         if self.is_external_module(module_path) {
@@ -84,70 +172,20 @@ impl ModuleResolver for TypeScriptModuleResolver {
             });
         }
 
-        // Handle relative imports (./, ../)
-        if module_path.starts_with('.') {
-            if let Some(parent) = from_file.parent() {
-                let resolved_path = parent.join(module_path);
-
-                // Try different extensions (TypeScript first, then JavaScript)
-                for ext in &["ts", "tsx", "js", "jsx"] {
-                    let with_ext = resolved_path.with_extension(ext);
-                    if with_ext.exists() {
-                        let validated_path = validate_import_path(base_dir, &with_ext)?;
-                        return Ok(ResolvedPath {
-                            path: validated_path,
-                            is_external: false,
-                            confidence: 0.9,
-                        });
-                    }
-                }
-
-                // Try as directory with index file
-                for ext in &["ts", "tsx", "js", "jsx"] {
-                    let index_path = resolved_path.join(format!("index.{ext}"));
-                    if index_path.exists() {
-                        let validated_path = validate_import_path(base_dir, &index_path)?;
-                        return Ok(ResolvedPath {
-                            path: validated_path,
-                            is_external: false,
-                            confidence: 0.9,
-                        });
-                    }
-                }
-            }
-        }
-
-        // Handle absolute imports from node_modules or project root
+        // Handle absolute imports from the project root
         let search_paths = vec![
             base_dir.to_path_buf(),
             from_file.parent().unwrap_or(base_dir).to_path_buf(),
         ];
 
         for search_path in &search_paths {
-            // Try as a file
-            for ext in &["ts", "tsx", "js", "jsx"] {
-                let file_path = search_path.join(format!("{module_path}.{ext}"));
-                if file_path.exists() {
-                    let validated_path = validate_import_path(base_dir, &file_path)?;
-                    return Ok(ResolvedPath {
-                        path: validated_path,
-                        is_external: false,
-                        confidence: 0.8,
-                    });
-                }
-            }
-
-            // Try as a directory with index file
-            for ext in &["ts", "tsx", "js", "jsx"] {
-                let index_path = search_path.join(module_path).join(format!("index.{ext}"));
-                if index_path.exists() {
-                    let validated_path = validate_import_path(base_dir, &index_path)?;
-                    return Ok(ResolvedPath {
-                        path: validated_path,
-                        is_external: false,
-                        confidence: 0.8,
-                    });
-                }
+            if let Some(file) = resolve_module_file(&search_path.join(module_path), EXTENSIONS) {
+                let validated_path = validate_import_path(base_dir, &file)?;
+                return Ok(ResolvedPath {
+                    path: validated_path,
+                    is_external: false,
+                    confidence: 0.8,
+                });
             }
         }
 
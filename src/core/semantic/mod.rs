@@ -14,12 +14,14 @@ pub mod dependency_types;
 pub mod function_call_index;
 pub mod graph_builder;
 pub mod graph_traverser;
+pub mod language_pack;
 pub mod languages;
 pub mod parallel_analyzer;
 pub mod parser_pool;
 pub mod path_validator;
 pub mod query_engine;
 pub mod resolver;
+pub mod symbol_graph;
 pub mod type_resolver;
 
 #[cfg(test)]
@@ -80,6 +82,20 @@ pub fn get_analyzer_for_file(
 ) -> Result<Option<Box<dyn LanguageAnalyzer>>, ContextCreatorError> {
     let extension = path.extension().and_then(|ext| ext.to_str()).unwrap_or("");
 
+    // Language packs take precedence, so they can replace a built-in analyzer
+    if let Some(pack) = language_pack::language_pack_for_extension(extension) {
+        return Ok(Some(Box::new(language_pack::LanguagePackAnalyzer::new(
+            pack,
+        ))));
+    }
+
+    // Build and CI files are recognized by name, and some have no extension
+    if let Some(kind) = languages::build_files::BuildFileKind::for_path(path) {
+        return Ok(Some(Box::new(
+            languages::build_files::BuildFileAnalyzer::new(kind),
+        )));
+    }
+
     let analyzer: Option<Box<dyn LanguageAnalyzer>> = match extension {
         "rs" => Some(Box::new(languages::rust::RustAnalyzer::new())),
         "py" => Some(Box::new(languages::python::PythonAnalyzer::new())),
@@ -87,6 +103,46 @@ pub fn get_analyzer_for_file(
         "ts" | "tsx" => Some(Box::new(languages::typescript::TypeScriptAnalyzer::new())),
         "go" => Some(Box::new(languages::go::GoAnalyzer::new())),
         "swift" => Some(Box::new(languages::swift::SwiftAnalyzer::new())),
+        "java" => Some(Box::new(languages::java::JavaAnalyzer::new())),
+        "c" => Some(Box::new(languages::c::CAnalyzer::new())),
+        // `.h` headers are shared by C and C++ sources, and the C++ grammar parses both
+        "cpp" | "cc" | "cxx" | "hpp" | "hh" | "hxx" | "h" => {
+            Some(Box::new(languages::cpp::CppAnalyzer::new()))
+        }
+        "cs" => Some(Box::new(languages::csharp::CSharpAnalyzer::new())),
+        "rb" => Some(Box::new(languages::ruby::RubyAnalyzer::new())),
+        "php" => Some(Box::new(languages::php::PhpAnalyzer::new())),
+        "kt" | "kts" => Some(Box::new(languages::kotlin::KotlinAnalyzer::new())),
+        "scala" | "sc" => Some(Box::new(languages::scala::ScalaAnalyzer::new())),
+        "dart" => Some(Box::new(languages::dart::DartAnalyzer::new())),
+        "ex" | "exs" => Some(Box::new(languages::elixir::ElixirAnalyzer::new())),
+        "lua" => Some(Box::new(languages::lua::LuaAnalyzer::new())),
+        "hs" => Some(Box::new(languages::haskell::HaskellAnalyzer::new())),
+        "r" | "R" => Some(Box::new(languages::r::RAnalyzer::new())),
+        "jl" => Some(Box::new(languages::julia::JuliaAnalyzer::new())),
+        "elm" => Some(Box::new(languages::elm::ElmAnalyzer::new())),
+        "tf" | "tfvars" => Some(Box::new(languages::hcl::HclAnalyzer::new())),
+        "vue" => Some(Box::new(languages::sfc::SfcAnalyzer::new(
+            languages::sfc::SfcKind::Vue,
+        ))),
+        "svelte" => Some(Box::new(languages::sfc::SfcAnalyzer::new(
+            languages::sfc::SfcKind::Svelte,
+        ))),
+        "astro" => Some(Box::new(languages::sfc::SfcAnalyzer::new(
+            languages::sfc::SfcKind::Astro,
+        ))),
+        "proto" | "graphql" | "graphqls" | "gql" | "sql" => {
+            languages::schema::SchemaKind::for_path(path).map(|kind| {
+                Box::new(languages::schema::SchemaAnalyzer::new(kind)) as Box<dyn LanguageAnalyzer>
+            })
+        }
+        // Only OpenAPI documents among YAML and JSON files
+        "yaml" | "yml" | "json" => match languages::schema::SchemaKind::for_path(path) {
+            Some(kind @ languages::schema::SchemaKind::OpenApi) => {
+                Some(Box::new(languages::schema::SchemaAnalyzer::new(kind)))
+            }
+            _ => None,
+        },
         _ => None,
     };
 
@@ -99,6 +155,16 @@ pub fn get_resolver_for_file(
 ) -> Result<Option<Box<dyn ModuleResolver>>, ContextCreatorError> {
     let extension = path.extension().and_then(|ext| ext.to_str()).unwrap_or("");
 
+    if let Some(pack) = language_pack::language_pack_for_extension(extension) {
+        return Ok(Some(Box::new(language_pack::LanguagePackResolver::new(
+            pack,
+        ))));
+    }
+
+    if languages::build_files::BuildFileKind::for_path(path).is_some() {
+        return Ok(Some(Box::new(languages::build_files::BuildFileResolver)));
+    }
+
     let resolver: Option<Box<dyn ModuleResolver>> = match extension {
         "rs" => Some(Box::new(languages::rust::RustModuleResolver)),
         "py" => Some(Box::new(languages::python::PythonModuleResolver)),
@@ -106,6 +172,33 @@ pub fn get_resolver_for_file(
         "ts" | "tsx" => Some(Box::new(languages::typescript::TypeScriptModuleResolver)),
         "go" => Some(Box::new(languages::go::GoModuleResolver)),
         "swift" => Some(Box::new(languages::swift::SwiftModuleResolver)),
+        "java" => Some(Box::new(languages::java::JavaModuleResolver)),
+        "c" => Some(Box::new(languages::c::CModuleResolver)),
+        "cpp" | "cc" | "cxx" | "hpp" | "hh" | "hxx" | "h" => {
+            Some(Box::new(languages::cpp::CppModuleResolver))
+        }
+        "cs" => Some(Box::new(languages::csharp::CSharpModuleResolver)),
+        "rb" => Some(Box::new(languages::ruby::RubyModuleResolver)),
+        "php" => Some(Box::new(languages::php::PhpModuleResolver)),
+        "kt" | "kts" => Some(Box::new(languages::kotlin::KotlinModuleResolver)),
+        "scala" | "sc" => Some(Box::new(languages::scala::ScalaModuleResolver)),
+        "dart" => Some(Box::new(languages::dart::DartModuleResolver)),
+        "ex" | "exs" => Some(Box::new(languages::elixir::ElixirModuleResolver)),
+        "lua" => Some(Box::new(languages::lua::LuaModuleResolver)),
+        "hs" => Some(Box::new(languages::haskell::HaskellModuleResolver)),
+        "r" | "R" => Some(Box::new(languages::r::RModuleResolver)),
+        "jl" => Some(Box::new(languages::julia::JuliaModuleResolver)),
+        "elm" => Some(Box::new(languages::elm::ElmModuleResolver)),
+        "tf" | "tfvars" => Some(Box::new(languages::hcl::HclModuleResolver)),
+        // Component scripts import like TypeScript modules
+        "vue" | "svelte" | "astro" => {
+            Some(Box::new(languages::typescript::TypeScriptModuleResolver))
+        }
+        "proto" | "graphql" | "graphqls" | "gql" | "sql" => {
+            Some(Box::new(languages::schema::SchemaModuleResolver))
+        }
+        "yaml" | "yml" | "json" => languages::schema::SchemaKind::for_path(path)
+            .map(|_| Box::new(languages::schema::SchemaModuleResolver) as Box<dyn ModuleResolver>),
         _ => None,
     };
 
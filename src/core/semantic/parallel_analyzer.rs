@@ -6,6 +6,7 @@
 use crate::core::cache::FileCache;
 use crate::core::semantic::analyzer::SemanticContext;
 use crate::core::semantic::dependency_types::{DependencyEdgeType, FileAnalysisResult};
+use crate::core::semantic::languages::schema::schema_references;
 use crate::core::semantic::{get_analyzer_for_file, get_resolver_for_file};
 use crate::core::semantic_cache::SemanticCache;
 use anyhow::Result;
@@ -25,6 +26,8 @@ pub struct AnalysisOptions {
     pub include_types: bool,
     /// Whether to include function calls
     pub include_functions: bool,
+    /// Extra C and C++ include directories, relative to the project root
+    pub include_dirs: Vec<PathBuf>,
 }
 
 impl Default for AnalysisOptions {
@@ -34,6 +37,7 @@ impl Default for AnalysisOptions {
             trace_imports: true,
             include_types: true,
             include_functions: true,
+            include_dirs: Vec::new(),
         }
     }
 }
@@ -172,6 +176,13 @@ impl<'a> ParallelAnalyzer<'a> {
             hasher.finish()
         };
 
+        let context = SemanticContext::new(
+            file_path.to_path_buf(),
+            project_root.to_path_buf(),
+            options.semantic_depth,
+        )
+        .with_include_dirs(options.include_dirs.clone());
+
         // Check semantic cache first
         let analysis_result =
             if let Some(cached_result) = self.semantic_cache.get(file_path, content_hash) {
@@ -179,13 +190,6 @@ impl<'a> ParallelAnalyzer<'a> {
                 (*cached_result).clone()
             } else {
                 // Cache miss - perform analysis
-                // Create semantic context
-                let context = SemanticContext::new(
-                    file_path.to_path_buf(),
-                    project_root.to_path_buf(),
-                    options.semantic_depth,
-                );
-
                 // Perform only the analysis required by the active semantic options.
                 let result = analyzer.analyze_requested(
                     file_path,
@@ -206,12 +210,18 @@ impl<'a> ParallelAnalyzer<'a> {
 
         // Process imports if enabled
         let imports = if options.trace_imports {
-            self.process_imports(
-                file_path,
-                project_root,
-                &analysis_result.imports,
-                valid_files,
-            )?
+            let mut imports =
+                self.process_imports(&context, &analysis_result.imports, valid_files)?;
+            // Schemas the file uses by their generated names, so schemas find their callers
+            for (schema, declarations) in schema_references(file_path, &content, project_root) {
+                if !imports.iter().any(|(path, _)| *path == schema) {
+                    let edge_type = DependencyEdgeType::Import {
+                        symbols: declarations,
+                    };
+                    imports.push((schema, edge_type));
+                }
+            }
+            imports
         } else {
             Vec::new()
         };
@@ -249,11 +259,11 @@ impl<'a> ParallelAnalyzer<'a> {
     /// Process imports to create typed edges
     fn process_imports(
         &self,
-        file_path: &Path,
-        project_root: &Path,
+        context: &SemanticContext,
         imports: &[crate::core::semantic::analyzer::Import],
         _valid_files: &std::collections::HashSet<PathBuf>,
     ) -> Result<Vec<(PathBuf, DependencyEdgeType)>> {
+        let file_path = context.current_file.as_path();
         let mut typed_imports = Vec::new();
 
         // Get resolver for the file type
@@ -268,7 +278,7 @@ impl<'a> ParallelAnalyzer<'a> {
                 );
 
                 // Try to resolve the import
-                match resolver.resolve_import(&import.module, file_path, project_root) {
+                match resolver.resolve_import_in_context(&import.module, file_path, context) {
                     Ok(resolved) => {
                         tracing::debug!(
                             "  Resolved to: {} (external: {})",
@@ -77,6 +77,7 @@ fn test_parallel_file_analysis() {
         trace_imports: true,
         include_types: true,
         include_functions: true,
+        include_dirs: Vec::new(),
     };
 
     let valid_files: HashSet<PathBuf> = files.iter().cloned().collect();
@@ -115,6 +116,7 @@ fn test_thread_pool_management() {
         trace_imports: true,
         include_types: true,
         include_functions: true,
+        include_dirs: Vec::new(),
     };
 
     // Should handle analysis with limited threads
@@ -151,6 +153,7 @@ fn test_analysis_error_handling() {
         trace_imports: true,
         include_types: true,
         include_functions: true,
+        include_dirs: Vec::new(),
     };
 
     let valid_files: HashSet<PathBuf> = [bad_file.clone()].iter().cloned().collect();
@@ -206,6 +209,7 @@ fn test_large_file_set() {
         trace_imports: false,
         include_types: false,
         include_functions: true,
+        include_dirs: Vec::new(),
     };
 
     let start = std::time::Instant::now();
@@ -311,6 +315,7 @@ fn test_selective_analysis_options() {
         trace_imports: true,
         include_types: false,
         include_functions: false,
+        include_dirs: Vec::new(),
     };
 
     let valid_files: HashSet<PathBuf> = files.iter().cloned().collect();
@@ -99,8 +99,17 @@ pub fn validate_module_name(module_name: &str) -> Result<(), ContextCreatorError
 
     // Allow only safe characters using a fast check
     let valid_chars = module_name.chars().all(|c| {
-        c.is_alphanumeric() || c == '_' || c == '-' || c == '.' || c == '@' || c == '/' || c == ':'
-        // For scoped packages like @types/node
+        c.is_alphanumeric()
+            || c == '_'
+            || c == '-'
+            || c == '.'
+            // For scoped packages like @types/node
+            || c == '@'
+            || c == '/'
+            || c == ':'
+            // For path aliases like ~/components and subpath imports like #internal/db
+            || c == '~'
+            || c == '#'
     });
 
     if !valid_chars {
@@ -235,6 +244,8 @@ mod tests {
         assert!(validate_module_name("lodash").is_ok());
         assert!(validate_module_name("@angular/core").is_ok());
         assert!(validate_module_name("@types/node").is_ok());
+        assert!(validate_module_name("~/components/Button").is_ok());
+        assert!(validate_module_name("#internal/db").is_ok());
 
         // Invalid names
         assert!(validate_module_name("").is_err());
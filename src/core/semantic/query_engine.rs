@@ -4,15 +4,47 @@
 //! using Tree-sitter's query engine, replacing manual AST traversal.
 
 use crate::core::semantic::analyzer::{
-    AnalysisResult, FunctionCall, FunctionDefinition, Import, TypeReference,
+    AnalysisResult, FileSymbols, FunctionCall, FunctionDefinition, Import, SymbolDefinition,
+    SymbolKind, SymbolReference, SymbolReferenceKind, TypeReference,
 };
 use crate::utils::error::ContextCreatorError;
-use std::collections::HashMap;
-use tree_sitter::{Language, Parser, Query, QueryCursor, Tree};
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
+use tree_sitter::{Language, Node, Parser, Query, QueryCursor, Tree};
+
+/// Syntax nodes defining a named type across the supported grammars
+const TYPE_DEFINITION_KINDS: &[&str] = &[
+    "struct_item",
+    "enum_item",
+    "union_item",
+    "trait_item",
+    "type_item",
+    "class_definition",
+    "object_definition",
+    "trait_definition",
+    "class_declaration",
+    "abstract_class_declaration",
+    "interface_declaration",
+    "enum_declaration",
+    "record_declaration",
+    "struct_declaration",
+    "trait_declaration",
+    "protocol_declaration",
+    "object_declaration",
+    "mixin_declaration",
+    "type_alias_declaration",
+    "type_spec",
+    "type_alias",
+    "struct_specifier",
+    "class_specifier",
+    "union_specifier",
+    "enum_specifier",
+    "class",
+    "module",
+];
 
 /// Query engine for semantic analysis using Tree-sitter queries
 pub struct QueryEngine {
-    #[allow(dead_code)]
     language: Language,
     #[allow(dead_code)]
     language_name: String,
@@ -20,6 +52,19 @@ pub struct QueryEngine {
     function_call_query: Query,
     type_reference_query: Query,
     function_definition_query: Query,
+    /// Queries come from a language pack, so definitions are exported unless captured as
+    /// `@private`
+    from_language_pack: bool,
+}
+
+/// Query sources of a language loaded at runtime, using the capture names of the built-in
+/// queries
+#[derive(Debug, Clone, Default)]
+pub struct QuerySources {
+    pub imports: String,
+    pub calls: String,
+    pub definitions: String,
+    pub types: String,
 }
 
 impl QueryEngine {
@@ -38,6 +83,32 @@ impl QueryEngine {
             function_call_query,
             type_reference_query,
             function_definition_query,
+            from_language_pack: false,
+        })
+    }
+
+    /// Create a query engine from query sources of a language pack
+    pub fn from_sources(
+        language: Language,
+        language_name: &str,
+        sources: &QuerySources,
+    ) -> Result<Self, ContextCreatorError> {
+        let query = |kind: &str, text: &str| {
+            Query::new(language, text).map_err(|e| {
+                ContextCreatorError::ParseError(format!(
+                    "Failed to create {kind} query for {language_name}: {e}"
+                ))
+            })
+        };
+
+        Ok(Self {
+            language,
+            language_name: language_name.to_string(),
+            import_query: query("import", &sources.imports)?,
+            function_call_query: query("function call", &sources.calls)?,
+            type_reference_query: query("type reference", &sources.types)?,
+            function_definition_query: query("function definition", &sources.definitions)?,
+            from_language_pack: true,
         })
     }
 
@@ -92,6 +163,241 @@ impl QueryEngine {
         Ok(result)
     }
 
+    /// Extract the functions, methods, and types defined in content and the references made
+    /// from it, with their byte positions
+    pub fn analyze_symbols(&self, content: &str) -> Result<FileSymbols, ContextCreatorError> {
+        let mut parser = Parser::new();
+        parser.set_language(self.language).map_err(|e| {
+            ContextCreatorError::ParseError(format!(
+                "Failed to set {} language: {e}",
+                self.language_name
+            ))
+        })?;
+        let tree = parser.parse(content, None).ok_or_else(|| {
+            ContextCreatorError::ParseError("Failed to parse content".to_string())
+        })?;
+
+        self.symbols_from_tree(&tree, content)
+    }
+
+    /// Extract symbols from a parsed tree using the definition, call, and type queries
+    pub fn symbols_from_tree(
+        &self,
+        tree: &Tree,
+        content: &str,
+    ) -> Result<FileSymbols, ContextCreatorError> {
+        let mut query_cursor = QueryCursor::new();
+        let root_node = tree.root_node();
+        let source = content.as_bytes();
+
+        let mut functions = self.collect_function_definitions(
+            query_cursor.matches(&self.function_definition_query, root_node, source),
+            content,
+        )?;
+        // `export function f` is captured both with and without its export statement
+        let wrapped: Vec<Range<usize>> = functions
+            .iter()
+            .filter(|(function, range)| {
+                functions.iter().any(|(other, inner)| {
+                    other.name == function.name
+                        && inner != range
+                        && range.start <= inner.start
+                        && inner.end <= range.end
+                })
+            })
+            .map(|(_, range)| range.clone())
+            .collect();
+        functions.retain(|(_, range)| !wrapped.contains(range));
+        let calls = self.collect_function_calls(
+            query_cursor.matches(&self.function_call_query, root_node, source),
+            content,
+        )?;
+        let type_refs = self.collect_type_references(
+            query_cursor.matches(&self.type_reference_query, root_node, source),
+            content,
+        )?;
+
+        let mut definitions: Vec<SymbolDefinition> = Vec::new();
+        let mut seen_ranges = HashSet::new();
+        let mut add_definition = |definitions: &mut Vec<SymbolDefinition>,
+                                  name: &str,
+                                  kind: SymbolKind,
+                                  node_range: Range<usize>,
+                                  line: usize,
+                                  is_exported: bool| {
+            if seen_ranges.insert(node_range.clone()) {
+                definitions.push(SymbolDefinition {
+                    name: name.to_string(),
+                    kind,
+                    byte_range: node_range,
+                    line,
+                    is_exported,
+                    container: None,
+                });
+            }
+        };
+
+        // Types defined anywhere in the file, as type queries capture where types are used
+        let mut definition_name_offsets = HashSet::new();
+        let mut impl_blocks = Vec::new();
+        let mut pending = vec![root_node];
+        while let Some(node) = pending.pop() {
+            if node.kind() == "impl_item" {
+                if let Some(name) = implemented_type_name(node, content) {
+                    impl_blocks.push((name, node.byte_range()));
+                }
+            } else if let Some(name_node) = type_definition_name(node) {
+                if let Ok(name) = name_node.utf8_text(source) {
+                    definition_name_offsets.insert(name_node.start_byte());
+                    add_definition(
+                        &mut definitions,
+                        name,
+                        SymbolKind::Type,
+                        node.byte_range(),
+                        node.start_position().row + 1,
+                        true,
+                    );
+                }
+            }
+            let mut cursor = node.walk();
+            pending.extend(node.named_children(&mut cursor));
+        }
+
+        // Go methods belong to the type of their receiver
+        let receivers: Vec<(String, Range<usize>)> = functions
+            .iter()
+            .filter_map(|(_, function_range)| {
+                root_node
+                    .named_descendant_for_byte_range(function_range.start, function_range.end)
+                    .and_then(|node| receiver_type_name(node, content))
+                    .map(|name| (name, function_range.clone()))
+            })
+            .collect();
+
+        // Impl blocks of types defined elsewhere stand in for the type
+        for (name, impl_range) in &impl_blocks {
+            if !definitions
+                .iter()
+                .any(|definition| definition.kind == SymbolKind::Type && &definition.name == name)
+            {
+                let line = content[..impl_range.start].matches('\n').count() + 1;
+                add_definition(
+                    &mut definitions,
+                    name,
+                    SymbolKind::Type,
+                    impl_range.clone(),
+                    line,
+                    true,
+                );
+            }
+        }
+
+        for (function, function_range) in &functions {
+            add_definition(
+                &mut definitions,
+                &function.name,
+                SymbolKind::Function,
+                function_range.clone(),
+                function.line,
+                function.is_exported,
+            );
+        }
+
+        // Outer definitions first, so containers precede what they contain
+        definitions.sort_by_key(|definition| {
+            (
+                definition.byte_range.start,
+                std::cmp::Reverse(definition.byte_range.end),
+            )
+        });
+
+        for index in 0..definitions.len() {
+            let range = definitions[index].byte_range.clone();
+            let mut container = definitions
+                .iter()
+                .enumerate()
+                .filter(|(other, definition)| {
+                    *other != index
+                        && definition.kind != SymbolKind::Module
+                        && definition.byte_range.start <= range.start
+                        && range.end <= definition.byte_range.end
+                        && definition.byte_range != range
+                })
+                .min_by_key(|(_, definition)| definition.byte_range.len())
+                .map(|(other, _)| other);
+
+            // Methods of an impl block or with a receiver belong to the type they implement
+            if container.is_none() {
+                container = impl_blocks
+                    .iter()
+                    .chain(&receivers)
+                    .find(|(_, impl_range)| {
+                        impl_range.start <= range.start && range.end <= impl_range.end
+                    })
+                    .and_then(|(name, _)| {
+                        definitions.iter().position(|definition| {
+                            definition.kind == SymbolKind::Type && &definition.name == name
+                        })
+                    });
+            }
+
+            definitions[index].container = container;
+        }
+
+        for index in 0..definitions.len() {
+            if definitions[index].kind == SymbolKind::Function
+                && (definitions[index]
+                    .container
+                    .is_some_and(|container| definitions[container].kind == SymbolKind::Type)
+                    || receivers
+                        .iter()
+                        .any(|(_, range)| *range == definitions[index].byte_range))
+            {
+                definitions[index].kind = SymbolKind::Method;
+            }
+        }
+
+        let mut references: Vec<SymbolReference> = calls
+            .into_iter()
+            .map(|(call, offset)| SymbolReference {
+                name: call.name,
+                module: call.module,
+                kind: SymbolReferenceKind::Call,
+                byte_offset: offset,
+                line: call.line,
+            })
+            .chain(
+                type_refs
+                    .into_iter()
+                    .filter(|(_, offset)| !definition_name_offsets.contains(offset))
+                    .map(|(type_ref, offset)| SymbolReference {
+                        name: type_ref.name,
+                        module: type_ref.module,
+                        kind: SymbolReferenceKind::Type,
+                        byte_offset: offset,
+                        line: type_ref.line,
+                    }),
+            )
+            .collect();
+        references.sort_by(|left, right| {
+            (left.byte_offset, left.kind as u8, &left.name).cmp(&(
+                right.byte_offset,
+                right.kind as u8,
+                &right.name,
+            ))
+        });
+        references.dedup_by(|right, left| {
+            left.byte_offset == right.byte_offset
+                && left.kind == right.kind
+                && left.name == right.name
+        });
+
+        Ok(FileSymbols {
+            definitions,
+            references,
+        })
+    }
+
     /// Create import query for the specified language
     fn create_import_query(
         language: Language,
@@ -210,11 +516,45 @@ impl QueryEngine {
                   source: (string) @module_path
                 ) @js_import
 
+                ; Re-exports: export { A } from './a', export * as ns from './b', export * from './c'
+                (export_statement
+                  [
+                    (namespace_export (identifier) @import_name)
+                    (export_clause
+                      (export_specifier
+                        name: (identifier) @import_name
+                      )
+                    )
+                  ]?
+                  source: (string) @module_path
+                ) @js_reexport
+
                 ; Require calls (CommonJS)
                 (call_expression
                   function: (identifier) @require_fn (#eq? @require_fn "require")
-                  arguments: (arguments (string) @module_path)
+                  arguments: (arguments [(string) (template_string)] @module_path)
                 ) @require
+
+                ; Destructured requires: const { a, b } = require('./x')
+                (variable_declarator
+                  name: (object_pattern
+                    [
+                      (shorthand_property_identifier_pattern) @import_name
+                      (pair_pattern key: (property_identifier) @import_name)
+                    ]
+                  )
+                  value: (call_expression
+                    function: (identifier) @destructured_require_fn
+                    (#eq? @destructured_require_fn "require")
+                    arguments: (arguments [(string) (template_string)] @module_path)
+                  )
+                ) @destructured_require
+
+                ; Dynamic imports with a literal specifier: import('./lazy')
+                (call_expression
+                  function: (import)
+                  arguments: (arguments [(string) (template_string)] @module_path)
+                ) @dynamic_import
             "#
             }
             "typescript" => {
@@ -238,11 +578,45 @@ impl QueryEngine {
                   source: (string) @module_path
                 ) @ts_import
 
+                ; Re-exports: export { A } from './a', export * as ns from './b', export * from './c'
+                (export_statement
+                  [
+                    (namespace_export (identifier) @import_name)
+                    (export_clause
+                      (export_specifier
+                        name: (identifier) @import_name
+                      )
+                    )
+                  ]?
+                  source: (string) @module_path
+                ) @ts_reexport
+
                 ; Require calls (CommonJS)
                 (call_expression
                   function: (identifier) @require_fn (#eq? @require_fn "require")
-                  arguments: (arguments (string) @module_path)
+                  arguments: (arguments [(string) (template_string)] @module_path)
                 ) @require
+
+                ; Destructured requires: const { a, b } = require('./x')
+                (variable_declarator
+                  name: (object_pattern
+                    [
+                      (shorthand_property_identifier_pattern) @import_name
+                      (pair_pattern key: (property_identifier) @import_name)
+                    ]
+                  )
+                  value: (call_expression
+                    function: (identifier) @destructured_require_fn
+                    (#eq? @destructured_require_fn "require")
+                    arguments: (arguments [(string) (template_string)] @module_path)
+                  )
+                ) @destructured_require
+
+                ; Dynamic imports with a literal specifier: import('./lazy')
+                (call_expression
+                  function: (import)
+                  arguments: (arguments [(string) (template_string)] @module_path)
+                ) @dynamic_import
             "#
             }
             "go" => {
@@ -269,6 +643,134 @@ impl QueryEngine {
                 ) @go_named_import
             "#
             }
+            "java" => {
+                r#"
+                ; Single-type, on-demand, and static imports:
+                ; import com.acme.User; import com.acme.model.*; import static com.acme.Strings.format;
+                (import_declaration) @java_import
+            "#
+            }
+            "c" | "cpp" => {
+                r#"
+                ; Local and system includes: #include "drivers/uart.h", #include <stdint.h>
+                (preproc_include
+                  path: [
+                    (string_literal) @c_include_path
+                    (system_lib_string) @c_include_path
+                  ]
+                ) @c_include
+            "#
+            }
+            "csharp" => {
+                r#"
+                ; Namespace, static, alias, and global usings:
+                ; using Acme.Services; using static Acme.Guard; using Dto = Acme.Contracts.OrderDto;
+                (using_directive) @csharp_using
+            "#
+            }
+            "ruby" => {
+                r#"
+                ; Load-path, relative, file, and autoload requires:
+                ; require "billing/tax", require_relative "../support/money", load "tasks/seed.rb"
+                (call
+                  !receiver
+                  method: (identifier) @ruby_require_method
+                  arguments: (argument_list (string (string_content) @module_path))
+                  (#match? @ruby_require_method "^(require|require_relative|load|autoload)$")
+                ) @ruby_require
+            "#
+            }
+            "php" => {
+                r#"
+                ; Class, aliased, and group uses:
+                ; use App\Models\Invoice; use App\Services\{Billing, Tax as TaxService};
+                (namespace_use_declaration) @php_use
+
+                ; File includes: require_once __DIR__ . '/helpers.php', include 'header.php'
+                [
+                  (require_expression)
+                  (require_once_expression)
+                  (include_expression)
+                  (include_once_expression)
+                ] @php_include
+            "#
+            }
+            "kotlin" => {
+                r#"
+                ; Declaration, aliased, and star imports:
+                ; import com.acme.Order, import com.acme.format as fmt, import com.acme.domain.*
+                (import_header) @kotlin_import
+            "#
+            }
+            "scala" => {
+                r#"
+                ; Single, wildcard, selector, and renaming imports: import com.acme.Order,
+                ; import com.acme.model._, import org.apache.spark.sql.{SparkSession, DataFrame => DF}
+                (import_declaration) @scala_import
+            "#
+            }
+            "dart" => {
+                r#"
+                ; Library imports and exports: import 'package:shop/models/cart.dart' as cart show Cart;
+                ; export 'src/widgets.dart'; part 'home_page.g.dart'; part of 'home_page.dart';
+                [
+                  (library_import)
+                  (library_export)
+                  (part_directive)
+                  (part_of_directive)
+                ] @dart_directive
+            "#
+            }
+            "elixir" => {
+                r#"
+                ; Module directives: alias MyApp.Orders.{Order, LineItem}, alias MyApp.Repo, as: Db,
+                ; import Ecto.Query, only: [from: 2], require Logger, use MyAppWeb, :controller
+                (call
+                  target: (identifier) @elixir_directive
+                  (arguments)
+                  (#match? @elixir_directive "^(alias|import|require|use)$")
+                ) @elixir_import
+            "#
+            }
+            "lua" => {
+                r#"
+                ; Module loads: require("plugin.config"), require "game.entities.player",
+                ; local util = require('plugin.util')
+                (function_call
+                  name: (identifier) @require_fn
+                  arguments: [
+                    (arguments . (string content: (string_content) @module_path) .)
+                    (string content: (string_content) @module_path)
+                  ]
+                  (#eq? @require_fn "require")
+                ) @lua_require
+            "#
+            }
+            "haskell" => {
+                r#"
+                ; Module imports: import Shop.Types (Order(..), LineItem),
+                ; import qualified Data.Map.Strict as Map, import Shop.Pricing hiding (discount)
+                (import) @haskell_import
+            "#
+            }
+            "r" => {
+                r#"
+                ; Sourced scripts and attached packages: source("R/utils.R"),
+                ; source(file.path("R", "model.R")), library(dplyr), require("ggplot2")
+                (call
+                  function: (identifier) @r_loader
+                  arguments: (arguments . value: (_))
+                  (#match? @r_loader "^(source|sys\\.source|library|require|requireNamespace|loadNamespace)$")
+                ) @r_import
+            "#
+            }
+            "elm" => {
+                r#"
+                ; Module imports: import Api.Orders as Orders exposing (Order, submit),
+                ; import Html exposing (..), import Cart
+                (import_clause) @elm_import
+            "#
+            }
             "swift" => {
                 r#"
                 ; Module imports: import Foundation, import Models
@@ -403,86 +905,428 @@ impl QueryEngine {
                 ) @selector_call
             "#
             }
-            "swift" => {
+            "java" => {
                 r#"
-                ; Swift call expressions are parsed manually by SwiftAnalyzer because
-                ; the grammar's expression recursion is too broad for target-specific queries.
-                (call_expression) @swift_call
+                ; Unqualified method calls: process()
+                (method_invocation
+                  name: (identifier) @fn_name
+                ) @call
+
+                ; Receiver and static calls: service.process(), Strings.format()
+                (method_invocation
+                  object: (identifier) @module_name
+                  name: (identifier) @fn_name
+                ) @receiver_call
+
+                ; Constructor calls: new UserService(), new Repository<>()
+                (object_creation_expression
+                  type: [
+                    (type_identifier) @fn_name
+                    (generic_type (type_identifier) @fn_name)
+                  ]
+                ) @constructor_call
             "#
             }
-            _ => {
-                return Err(ContextCreatorError::ParseError(format!(
-                    "Unsupported language for function call queries: {language_name}"
-                )))
+            "c" => {
+                r#"
+                ; Direct function calls: uart_write(buf)
+                (call_expression
+                  function: (identifier) @fn_name
+                ) @call
+
+                ; Calls through struct members: dev->ops->reset()
+                (call_expression
+                  function: (field_expression
+                    field: (field_identifier) @method_name
+                  )
+                ) @field_call
+            "#
             }
-        };
+            "cpp" => {
+                r#"
+                ; Free function calls: helper(), make<Widget>()
+                (call_expression
+                  function: [
+                    (identifier) @fn_name
+                    (template_function name: (identifier) @fn_name)
+                  ]
+                ) @call
 
-        Query::new(language, query_text).map_err(|e| {
-            ContextCreatorError::ParseError(format!("Failed to create function call query: {e}"))
-        })
-    }
+                ; Member calls: widget.draw(), this->render()
+                (call_expression
+                  function: (field_expression
+                    field: (field_identifier) @method_name
+                  )
+                ) @member_call
 
-    /// Create function definition query for the specified language
-    fn create_function_definition_query(
-        language: Language,
-        language_name: &str,
-    ) -> Result<Query, ContextCreatorError> {
-        let query_text = match language_name {
-            "rust" => {
+                ; Namespace- or class-qualified calls: net::connect(), Widget::make()
+                (call_expression
+                  function: (qualified_identifier
+                    scope: (namespace_identifier) @module_name
+                    name: (identifier) @fn_name
+                  )
+                ) @qualified_call
+
+                ; Constructor calls through new: new Widget(1)
+                (new_expression
+                  type: (type_identifier) @fn_name
+                ) @constructor_call
+            "#
+            }
+            "csharp" => {
                 r#"
-                ; Function declarations with visibility
-                (function_item
-                  (visibility_modifier)? @visibility
-                  name: (identifier) @fn_name
-                ) @function
-                
-                ; Method declarations in impl blocks
-                (impl_item
-                  body: (declaration_list
-                    (function_item
-                      (visibility_modifier)? @method_visibility
-                      name: (identifier) @method_name
-                    ) @method
+                ; Unqualified calls: Validate(), Ok(result)
+                (invocation_expression
+                  function: [
+                    (identifier) @fn_name
+                    (generic_name (identifier) @fn_name)
+                  ]
+                ) @call
+
+                ; Receiver and static calls: _service.FindAsync(id), Guard.NotNull(x)
+                (invocation_expression
+                  function: (member_access_expression
+                    expression: (identifier) @module_name
+                    name: [
+                      (identifier) @method_name
+                      (generic_name (identifier) @method_name)
+                    ]
                   )
-                )
-                
-                ; Trait method declarations
-                (trait_item
-                  body: (declaration_list
-                    (function_signature_item
-                      name: (identifier) @trait_fn_name
-                    ) @trait_function
+                ) @receiver_call
+
+                ; Chained calls: Models.OrderDto.From(order), _db.Orders.Where(...)
+                (invocation_expression
+                  function: (member_access_expression
+                    name: [
+                      (identifier) @method_name
+                      (generic_name (identifier) @method_name)
+                    ]
                   )
+                ) @member_call
+
+                ; Constructor calls: new OrderMapper(), new Repository<Order>()
+                (object_creation_expression
+                  type: [
+                    (identifier) @fn_name
+                    (generic_name (identifier) @fn_name)
+                  ]
+                ) @constructor_call
+            "#
+            }
+            "ruby" => {
+                r#"
+                ; Receiverless calls: format_total(invoice)
+                (call
+                  !receiver
+                  method: (identifier) @fn_name
+                )
+
+                ; Constant receivers: InvoiceMailer.deliver_later, Billing::InvoiceMailer.new
+                (call
+                  receiver: [
+                    (constant) @module_name
+                    (scope_resolution) @module_name
+                  ]
+                  method: (identifier) @method_name
+                )
+
+                ; Any other receiver: @invoice.total, user.save
+                (call
+                  receiver: (_)
+                  method: (identifier) @method_name
                 )
             "#
             }
-            "python" => {
+            "php" => {
                 r#"
-                ; Function definitions
-                (function_definition
-                  name: (identifier) @fn_name
-                ) @function
-                
-                ; Method definitions in classes
-                (class_definition
-                  body: (block
-                    (function_definition
-                      name: (identifier) @method_name
-                    ) @method
-                  )
+                ; Function calls: format_money($total), \App\Support\format_money($total)
+                (function_call_expression
+                  function: [
+                    (name) @fn_name
+                    (qualified_name (name) @fn_name)
+                  ]
+                )
+
+                ; Static calls: Invoice::findOrFail($id), \App\Models\Invoice::first()
+                (scoped_call_expression
+                  scope: [
+                    (name) @module_name
+                    (qualified_name (name) @module_name)
+                  ]
+                  name: (name) @method_name
+                )
+
+                ; Instance calls: $this->billing->charge($invoice), $user?->save()
+                (member_call_expression
+                  name: (name) @method_name
+                )
+                (nullsafe_member_call_expression
+                  name: (name) @method_name
                 )
-                
-                ; Async function definitions
-                (function_definition
-                  "async" @async_marker
-                  name: (identifier) @async_fn_name
-                ) @async_function
             "#
             }
-            "javascript" => {
+            "kotlin" => {
                 r#"
-                ; Function declarations
-                (function_declaration
+                ; Function and constructor calls: formatMoney(total), OrderDto(id)
+                (call_expression
+                  (simple_identifier) @fn_name
+                )
+
+                ; Calls on a named receiver: service.find(id), Logger.info(message)
+                (call_expression
+                  (navigation_expression
+                    (simple_identifier) @module_name
+                    (navigation_suffix (simple_identifier) @method_name)
+                  )
+                )
+
+                ; Calls on any other receiver: order.items.first()
+                (call_expression
+                  (navigation_expression
+                    (navigation_suffix (simple_identifier) @method_name)
+                  )
+                )
+            "#
+            }
+            "scala" => {
+                r#"
+                ; Function and companion apply calls: helper(2), Order(1)
+                (call_expression
+                  function: (identifier) @fn_name
+                )
+
+                ; Calls on a named receiver: Money.format(total), spark.read(path)
+                (call_expression
+                  function: (field_expression
+                    value: (identifier) @module_name
+                    field: (identifier) @method_name
+                  )
+                )
+
+                ; Calls on any other receiver: df.select(cols).where(filter)
+                (call_expression
+                  function: (field_expression
+                    field: (identifier) @method_name
+                  )
+                )
+
+                ; Instantiation: new OrderReader(spark)
+                (instance_expression (type_identifier) @fn_name)
+            "#
+            }
+            "dart" => {
+                r#"
+                ; Function and constructor calls: helper(items), CartItem(1)
+                (member_access
+                  .
+                  (identifier) @fn_name
+                  .
+                  (selector (argument_part))
+                )
+
+                ; Calls on a named receiver: service.fetch(id), Navigator.of(context)
+                (member_access
+                  .
+                  (identifier) @module_name
+                  .
+                  (selector
+                    [
+                      (unconditional_assignable_selector (identifier) @method_name)
+                      (conditional_assignable_selector (identifier) @method_name)
+                    ]
+                  )
+                  .
+                  (selector (argument_part))
+                )
+
+                ; Calls on any other receiver: items.where(test).toList()
+                (member_access
+                  (selector
+                    [
+                      (unconditional_assignable_selector (identifier) @method_name)
+                      (conditional_assignable_selector (identifier) @method_name)
+                    ]
+                  )
+                  .
+                  (selector (argument_part))
+                )
+            "#
+            }
+            "elixir" => {
+                r#"
+                ; Local and imported calls: helper(order), from(o in Order)
+                (call
+                  target: (identifier) @fn_name
+                )
+
+                ; Remote calls and captures: Repo.insert(order), &Invoice.render/1
+                (call
+                  target: (dot
+                    left: (alias) @module_name
+                    right: (identifier) @method_name
+                  )
+                )
+            "#
+            }
+            "lua" => {
+                r#"
+                ; Global and local function calls: setup(opts), helper()
+                (function_call
+                  name: (identifier) @fn_name
+                )
+
+                ; Module and method calls: util.format(x), player:update(dt)
+                (function_call
+                  name: [
+                    (dot_index_expression
+                      table: (identifier) @module_name
+                      field: (identifier) @method_name
+                    )
+                    (method_index_expression
+                      table: (identifier) @module_name
+                      method: (identifier) @method_name
+                    )
+                  ]
+                )
+
+                ; Calls through nested tables: vim.api.nvim_create_autocmd(...)
+                (function_call
+                  name: [
+                    (dot_index_expression
+                      table: [(dot_index_expression) (function_call) (bracket_index_expression)]
+                      field: (identifier) @method_name
+                    )
+                    (method_index_expression
+                      table: [(dot_index_expression) (function_call) (bracket_index_expression)]
+                      method: (identifier) @method_name
+                    )
+                  ]
+                )
+            "#
+            }
+            "haskell" => {
+                r#"
+                ; Function application: checkout order cart, Map.insert key value
+                (exp_apply . (exp_name (variable) @fn_name))
+                (exp_apply . (exp_name (qualified_variable) @qualified_name))
+
+                ; Backtick infix application: x `elem` xs, key `Map.member` m
+                (exp_infix (variable) @fn_name)
+                (exp_infix (qualified_variable) @qualified_name)
+            "#
+            }
+            "r" => {
+                r#"
+                ; Function calls: fit_model(df, y ~ x), helper(data)
+                (call function: (identifier) @fn_name)
+
+                ; Namespaced calls: dplyr::filter(df, x > 1), stats:::print.lm(fit)
+                (call
+                  function: [
+                    (namespace_get
+                      namespace: (identifier) @module_name
+                      function: (identifier) @method_name
+                    )
+                    (namespace_get_internal
+                      namespace: (identifier) @module_name
+                      function: (identifier) @method_name
+                    )
+                  ]
+                )
+            "#
+            }
+            "elm" => {
+                r#"
+                ; Function application: update msg model, submit Submitted items
+                (function_call_expr
+                  target: (value_expr name: (value_qid . (lower_case_identifier) @fn_name .))
+                )
+
+                ; Qualified values, applied or passed along: Cart.items model.cart,
+                ; List.map Cart.describe carts
+                (value_expr name: (value_qid (dot)) @qualified_name)
+            "#
+            }
+            "swift" => {
+                r#"
+                ; Swift call expressions are parsed manually by SwiftAnalyzer because
+                ; the grammar's expression recursion is too broad for target-specific queries.
+                (call_expression) @swift_call
+            "#
+            }
+            _ => {
+                return Err(ContextCreatorError::ParseError(format!(
+                    "Unsupported language for function call queries: {language_name}"
+                )))
+            }
+        };
+
+        Query::new(language, query_text).map_err(|e| {
+            ContextCreatorError::ParseError(format!("Failed to create function call query: {e}"))
+        })
+    }
+
+    /// Create function definition query for the specified language
+    fn create_function_definition_query(
+        language: Language,
+        language_name: &str,
+    ) -> Result<Query, ContextCreatorError> {
+        let query_text = match language_name {
+            "rust" => {
+                r#"
+                ; Function declarations with visibility
+                (function_item
+                  (visibility_modifier)? @visibility
+                  name: (identifier) @fn_name
+                ) @function
+                
+                ; Method declarations in impl blocks
+                (impl_item
+                  body: (declaration_list
+                    (function_item
+                      (visibility_modifier)? @method_visibility
+                      name: (identifier) @method_name
+                    ) @method
+                  )
+                )
+                
+                ; Trait method declarations
+                (trait_item
+                  body: (declaration_list
+                    (function_signature_item
+                      name: (identifier) @trait_fn_name
+                    ) @trait_function
+                  )
+                )
+            "#
+            }
+            "python" => {
+                r#"
+                ; Function definitions
+                (function_definition
+                  name: (identifier) @fn_name
+                ) @function
+                
+                ; Method definitions in classes
+                (class_definition
+                  body: (block
+                    (function_definition
+                      name: (identifier) @method_name
+                    ) @method
+                  )
+                )
+                
+                ; Async function definitions
+                (function_definition
+                  "async" @async_marker
+                  name: (identifier) @async_fn_name
+                ) @async_function
+            "#
+            }
+            "javascript" => {
+                r#"
+                ; Function declarations
+                (function_declaration
                   name: (identifier) @fn_name
                 ) @function
                 
@@ -521,6 +1365,22 @@ impl QueryEngine {
                     (arrow_function)
                   ]
                 ) @commonjs_export
+
+                ; CommonJS exports pattern: module.exports.functionName = function()
+                (assignment_expression
+                  left: (member_expression
+                    object: (member_expression
+                      object: (identifier) @module_obj (#eq? @module_obj "module")
+                      property: (property_identifier) @module_exports_prop
+                      (#eq? @module_exports_prop "exports")
+                    )
+                    property: (property_identifier) @commonjs_export_name
+                  )
+                  right: [
+                    (function_expression)
+                    (arrow_function)
+                  ]
+                ) @commonjs_export
             "#
             }
             "typescript" => {
@@ -553,6 +1413,34 @@ impl QueryEngine {
                     name: (identifier) @export_fn_name
                   )
                 ) @export_function
+                
+                ; CommonJS exports pattern: exports.functionName = function()
+                (assignment_expression
+                  left: (member_expression
+                    object: (identifier) @exports_obj (#eq? @exports_obj "exports")
+                    property: (property_identifier) @commonjs_export_name
+                  )
+                  right: [
+                    (function_expression)
+                    (arrow_function)
+                  ]
+                ) @commonjs_export
+
+                ; CommonJS exports pattern: module.exports.functionName = function()
+                (assignment_expression
+                  left: (member_expression
+                    object: (member_expression
+                      object: (identifier) @module_obj (#eq? @module_obj "module")
+                      property: (property_identifier) @module_exports_prop
+                      (#eq? @module_exports_prop "exports")
+                    )
+                    property: (property_identifier) @commonjs_export_name
+                  )
+                  right: [
+                    (function_expression)
+                    (arrow_function)
+                  ]
+                ) @commonjs_export
             "#
             }
             "go" => {
@@ -568,6 +1456,221 @@ impl QueryEngine {
                 ) @method
             "#
             }
+            "java" => {
+                r#"
+                ; Methods, including interface methods without bodies
+                (method_declaration
+                  (modifiers)? @java_modifiers
+                  name: (identifier) @method_name
+                ) @method
+
+                ; Constructors are invoked through `new Type()`
+                (constructor_declaration
+                  (modifiers)? @java_modifiers
+                  name: (identifier) @method_name
+                ) @method
+            "#
+            }
+            "c" => {
+                r#"
+                ; Function definitions, including ones returning pointers
+                (function_definition
+                  declarator: [
+                    (function_declarator declarator: (identifier) @fn_name)
+                    (pointer_declarator declarator: (function_declarator declarator: (identifier) @fn_name))
+                  ]
+                ) @c_function
+
+                ; Prototypes in headers describe the callable API of the implementation file
+                (declaration
+                  declarator: [
+                    (function_declarator declarator: (identifier) @fn_name)
+                    (pointer_declarator declarator: (function_declarator declarator: (identifier) @fn_name))
+                  ]
+                ) @c_function
+            "#
+            }
+            "cpp" => {
+                r#"
+                ; Free functions and out-of-line member definitions: void Widget::draw()
+                (function_definition
+                  declarator: [
+                    (function_declarator
+                      declarator: [
+                        (identifier) @fn_name
+                        (field_identifier) @method_name
+                        (qualified_identifier name: (identifier) @method_name)
+                      ]
+                    )
+                    (pointer_declarator
+                      declarator: (function_declarator
+                        declarator: [
+                          (identifier) @fn_name
+                          (qualified_identifier name: (identifier) @method_name)
+                        ]
+                      )
+                    )
+                    (reference_declarator
+                      (function_declarator
+                        declarator: [
+                          (identifier) @fn_name
+                          (qualified_identifier name: (identifier) @method_name)
+                        ]
+                      )
+                    )
+                  ]
+                ) @c_function
+
+                ; Free function prototypes and member declarations in headers
+                (declaration
+                  declarator: (function_declarator declarator: (identifier) @fn_name)
+                ) @c_function
+                (field_declaration
+                  declarator: (function_declarator declarator: (field_identifier) @method_name)
+                ) @method
+            "#
+            }
+            "csharp" => {
+                r#"
+                ; Methods, including interface members without bodies
+                (method_declaration
+                  name: (identifier) @method_name
+                ) @csharp_member
+
+                ; Constructors are invoked through `new Type()`
+                (constructor_declaration
+                  name: (identifier) @method_name
+                ) @csharp_member
+            "#
+            }
+            "ruby" => {
+                r#"
+                ; Instance methods, visibility decided by preceding private/protected calls
+                (method
+                  name: (identifier) @method_name
+                ) @ruby_method
+
+                ; Class methods: def self.build
+                (singleton_method
+                  name: (identifier) @method_name
+                ) @ruby_method
+            "#
+            }
+            "php" => {
+                r#"
+                ; Namespaced functions
+                (function_definition
+                  name: (name) @fn_name
+                ) @function
+
+                ; Methods, private ones excluded from the public surface
+                (method_declaration
+                  name: (name) @method_name
+                ) @php_method
+            "#
+            }
+            "kotlin" => {
+                r#"
+                ; Top-level functions, members, and extension functions
+                (function_declaration
+                  (simple_identifier) @fn_name
+                ) @kotlin_function
+            "#
+            }
+            "scala" => {
+                r#"
+                ; Concrete and abstract methods, private ones excluded from the public surface
+                (function_definition
+                  name: (identifier) @fn_name
+                ) @scala_function
+
+                (function_declaration
+                  name: (identifier) @fn_name
+                ) @scala_function
+            "#
+            }
+            "dart" => {
+                r#"
+                ; Top-level functions and methods; `_`-prefixed names are library-private
+                (function_signature
+                  name: (identifier) @fn_name
+                ) @dart_function
+            "#
+            }
+            "elixir" => {
+                r#"
+                ; Public and private functions, macros, guards, and delegates:
+                ; def run(order), defp helper(o), do: o, def valid?(x) when is_map(x)
+                (call
+                  target: (identifier) @elixir_def_keyword
+                  (arguments
+                    [
+                      (call target: (identifier) @fn_name)
+                      (identifier) @fn_name
+                      (binary_operator left: (call target: (identifier) @fn_name))
+                    ]
+                  )
+                  (#match? @elixir_def_keyword "^(def|defp|defmacro|defmacrop|defguard|defguardp|defdelegate)$")
+                ) @elixir_function
+            "#
+            }
+            "lua" => {
+                r#"
+                ; Global, local, module, and method functions: function setup(opts),
+                ; local function helper(), function M.format(x), function Player:update(dt)
+                (function_declaration
+                  name: [
+                    (identifier) @fn_name
+                    (dot_index_expression field: (identifier) @fn_name)
+                    (method_index_expression method: (identifier) @fn_name)
+                  ]
+                ) @lua_function
+
+                ; Functions assigned to names: M.format = function(x) end, local f = function() end
+                (assignment_statement
+                  (variable_list
+                    name: [
+                      (identifier) @fn_name
+                      (dot_index_expression field: (identifier) @fn_name)
+                    ]
+                  )
+                  (expression_list value: (function_definition))
+                ) @lua_function
+            "#
+            }
+            "haskell" => {
+                r#"
+                ; Top-level functions and bindings with or without a type signature:
+                ; checkout :: Order -> IO Int, applyTax x = x * 2
+                (haskell (function name: (variable) @fn_name) @haskell_function)
+                (haskell (signature name: (variable) @fn_name) @haskell_function)
+
+                ; Class methods: class Priced a where price :: a -> Int
+                (class_body (signature name: (variable) @fn_name) @haskell_function)
+            "#
+            }
+            "r" => {
+                r#"
+                ; Top-level functions: fit_model <- function(data) {}, helper = function(x) x
+                (program
+                  [
+                    (left_assignment name: (identifier) @fn_name value: (function_definition))
+                    (equals_assignment name: (identifier) @fn_name value: (function_definition))
+                    (super_assignment name: (identifier) @fn_name value: (function_definition))
+                  ] @function
+                )
+            "#
+            }
+            "elm" => {
+                r#"
+                ; Top-level values and functions: init = ..., update msg model = ...
+                (file
+                  (value_declaration
+                    functionDeclarationLeft: (function_declaration_left . (lower_case_identifier) @fn_name)
+                  ) @elm_function
+                )
+            "#
+            }
             "swift" => {
                 r#"
                 ; Free functions and methods with bodies
@@ -670,6 +1773,9 @@ impl QueryEngine {
                 ; Type identifiers in type positions
                 (type (identifier) @type_name)
 
+                ; Forward references in string annotations: def pay(user: "User")
+                (type (string (string_content) @type_name))
+
                 ; Function parameter type annotations 
                 (typed_parameter (identifier) @param_type)
 
@@ -792,6 +1898,298 @@ impl QueryEngine {
                 )
             "#
             }
+            "java" => {
+                r#"
+                ; Type identifiers in fields, parameters, generics, casts, and throws clauses.
+                (type_identifier) @type_name
+
+                ; Package- or outer-class-qualified types: com.acme.model.User, Map.Entry
+                (scoped_type_identifier
+                  (scoped_type_identifier) @scoped_module
+                  (type_identifier) @type_name
+                )
+                (scoped_type_identifier
+                  .
+                  (type_identifier) @module_name
+                  .
+                  (type_identifier) @type_name
+                )
+
+                ; Inheritance: extends and implements clauses.
+                (superclass
+                  [
+                    (type_identifier) @parent_class
+                    (generic_type (type_identifier) @parent_class)
+                  ]
+                )
+                (super_interfaces
+                  (type_list
+                    [
+                      (type_identifier) @base_type
+                      (generic_type (type_identifier) @base_type)
+                    ]
+                  )
+                )
+                (extends_interfaces
+                  (type_list
+                    [
+                      (type_identifier) @base_type
+                      (generic_type (type_identifier) @base_type)
+                    ]
+                  )
+                )
+
+                ; Annotation types: @Service, @Inject, @Table(name = "users")
+                (marker_annotation name: (identifier) @type_name)
+                (annotation name: (identifier) @type_name)
+            "#
+            }
+            "c" => {
+                r#"
+                ; Typedef names and struct/union/enum tags in declarations, parameters, and casts
+                (type_identifier) @type_name
+            "#
+            }
+            "cpp" => {
+                r#"
+                ; Class, struct, alias, and template argument types
+                (type_identifier) @type_name
+
+                ; Namespace-qualified types: net::Socket, std::vector<Point>
+                (qualified_identifier
+                  scope: (namespace_identifier) @module_name
+                  name: [
+                    (type_identifier) @type_name
+                    (template_type name: (type_identifier) @type_name)
+                  ]
+                )
+
+                ; Base classes: class Widget : public Base, private ui::Mixin
+                (base_class_clause
+                  [
+                    (type_identifier) @parent_class
+                    (qualified_identifier name: (type_identifier) @parent_class)
+                    (template_type name: (type_identifier) @parent_class)
+                  ]
+                )
+            "#
+            }
+            "csharp" => {
+                r#"
+                ; C# types are plain identifiers, so capture them by position:
+                ; locals and fields, parameters, return types, and properties.
+                (variable_declaration
+                  type: [
+                    (identifier) @type_name
+                    (generic_name (identifier) @type_name)
+                    (qualified_name (_) @module_name . (identifier) @type_name .)
+                  ]
+                )
+                (parameter
+                  type: [
+                    (identifier) @param_type
+                    (generic_name (identifier) @param_type)
+                    (qualified_name (_) @module_name . (identifier) @param_type .)
+                  ]
+                )
+                (method_declaration
+                  type: [
+                    (identifier) @return_type
+                    (generic_name (identifier) @return_type)
+                    (qualified_name (_) @module_name . (identifier) @return_type .)
+                  ]
+                )
+                (property_declaration
+                  type: [
+                    (identifier) @field_type
+                    (generic_name (identifier) @field_type)
+                    (qualified_name (_) @module_name . (identifier) @field_type .)
+                  ]
+                )
+
+                ; Base classes and implemented interfaces.
+                (base_list
+                  [
+                    (identifier) @base_type
+                    (generic_name (identifier) @base_type)
+                    (qualified_name (_) @module_name . (identifier) @base_type .)
+                  ]
+                )
+
+                ; Generic arguments: Task<OrderDto>, IRepository<Order>
+                (type_argument_list
+                  [
+                    (identifier) @type_arg
+                    (generic_name (identifier) @type_arg)
+                    (qualified_name (_) @module_name . (identifier) @type_arg .)
+                  ]
+                )
+
+                ; Object creation: new OrderMapper(), new Contracts.Invoice()
+                (object_creation_expression
+                  type: [
+                    (identifier) @type_name
+                    (generic_name (identifier) @type_name)
+                    (qualified_name (_) @module_name . (identifier) @type_name .)
+                  ]
+                )
+
+                ; Attributes: [Authorize], [Route("orders")]
+                (attribute name: (identifier) @type_name)
+            "#
+            }
+            "ruby" => {
+                r#"
+                ; Namespaced constants: Billing::InvoiceMailer, ::Payments::Gateway
+                (scope_resolution
+                  scope: (_) @scoped_module
+                  name: (constant) @type_name
+                )
+
+                ; Superclasses: class Invoice < ApplicationRecord
+                (superclass (constant) @parent_class)
+
+                ; Constant receivers: Invoice.find(id)
+                (call receiver: (constant) @type_name)
+
+                ; Mixins and arguments: include Auditable, rescue_from PaymentError
+                (argument_list (constant) @type_name)
+
+                ; Rescued exceptions: rescue Billing::Declined, PaymentError
+                (exceptions (constant) @type_name)
+
+                ; Constants as values: klass = Invoice, { model: Invoice }
+                (assignment right: (constant) @type_name)
+                (pair value: (constant) @type_name)
+            "#
+            }
+            "php" => {
+                r#"
+                ; Inheritance: extends Controller, implements HasMiddleware
+                (base_clause [(name) (qualified_name)] @parent_class)
+                (class_interface_clause [(name) (qualified_name)] @interface_name)
+
+                ; Trait uses inside classes: use Auditable;
+                (use_declaration [(name) (qualified_name)] @trait_name)
+
+                ; Parameter, return, and property types, including nullable and union types
+                (named_type [(name) (qualified_name)] @type_name)
+
+                ; Instantiation, static access, and ::class constants
+                (object_creation_expression [(name) (qualified_name)] @type_name)
+                (scoped_call_expression scope: [(name) (qualified_name)] @type_name)
+                (class_constant_access_expression . [(name) (qualified_name)] @type_name)
+                (scoped_property_access_expression scope: [(name) (qualified_name)] @type_name)
+            "#
+            }
+            "kotlin" => {
+                r#"
+                ; Parameter, property, return, supertype, annotation, and generic argument types
+                (user_type (type_identifier) @type_name)
+
+                ; Constructor calls: OrderDto(id, total)
+                (call_expression (simple_identifier) @type_name)
+
+                ; Objects and companions used as receivers: Logger.info(message)
+                (navigation_expression
+                  (simple_identifier) @type_name
+                  (navigation_suffix)
+                )
+            "#
+            }
+            "scala" => {
+                r#"
+                ; Parameter, return, field, extends/with, and type-argument types
+                (type_identifier) @type_name
+
+                ; Companion apply calls: Order(1)
+                (call_expression function: (identifier) @type_name)
+
+                ; Objects used as receivers: Money.format(total)
+                (field_expression value: (identifier) @type_name)
+            "#
+            }
+            "dart" => {
+                r#"
+                ; Field, parameter, return, superclass, mixin, interface, and type-argument types
+                (type_identifier) @type_name
+
+                ; Constructor calls and static access: CartItem(1), Navigator.of(context)
+                (member_access . (identifier) @type_name . (selector))
+            "#
+            }
+            "elixir" => {
+                r#"
+                ; Structs: %Order{}, %MyApp.Orders.Order{total: 0}
+                (struct (alias) @type_name)
+
+                ; Typespecs: @spec run(Order.t()) :: :ok
+                (call
+                  target: (dot
+                    left: (alias) @type_name
+                    right: (identifier) @type_member
+                  )
+                  (#eq? @type_member "t")
+                )
+
+                ; Behaviours: @behaviour MyApp.PaymentGateway
+                (unary_operator
+                  operand: (call
+                    target: (identifier) @attribute_name
+                    (arguments (alias) @type_name)
+                  )
+                  (#eq? @attribute_name "behaviour")
+                )
+            "#
+            }
+            "lua" => {
+                r#"
+                ; Class-style tables used as receivers: Player.new(x, y), Vector:zero()
+                (function_call
+                  name: [
+                    (dot_index_expression table: (identifier) @type_name)
+                    (method_index_expression table: (identifier) @type_name)
+                  ]
+                  (#match? @type_name "^[A-Z]")
+                )
+            "#
+            }
+            "haskell" => {
+                r#"
+                ; Types in signatures, fields, aliases, and instance heads:
+                ; checkout :: Order -> M.Map Text Int, instance Show Cart
+                (type_name (type) @type_name)
+                (type_name (qualified_type) @qualified_name)
+
+                ; Classes in constraints, instances, and deriving clauses: (Priced a) =>
+                (class_name (type) @type_name)
+                (class_name (qualified_type) @qualified_name)
+            "#
+            }
+            "r" => {
+                r#"
+                ; S4 and reference class instantiation: new("Account", balance = 0)
+                (call
+                  function: (identifier) @r_constructor
+                  arguments: (arguments . value: (string) @type_name)
+                  (#eq? @r_constructor "new")
+                )
+
+                ; R6 class instantiation: Account$new(balance = 0)
+                (call
+                  function: (dollar (identifier) @type_name (identifier) @r_method)
+                  (#eq? @r_method "new")
+                )
+            "#
+            }
+            "elm" => {
+                r#"
+                ; Types in annotations, aliases, and variants: update : Msg -> Model,
+                ; type alias Model = { cart : Cart.Cart }
+                (type_ref (upper_case_qid . (upper_case_identifier) @type_name .))
+                (type_ref (upper_case_qid (dot)) @qualified_name)
+            "#
+            }
             "swift" => {
                 r#"
                 ; Swift user types include nominal types, qualified module types, and generic arguments.
@@ -878,22 +2276,113 @@ impl QueryEngine {
                                 || path_text.starts_with("crate::");
                         }
                     }
-                    "rust_import_item" => {
-                        // Capture individual items in a scoped import
-                        if let Ok(item_text) = node.utf8_text(content.as_bytes()) {
-                            items.push(item_text.to_string());
-                        }
+                    "rust_import_item" => {
+                        // Capture individual items in a scoped import
+                        if let Ok(item_text) = node.utf8_text(content.as_bytes()) {
+                            items.push(item_text.to_string());
+                        }
+                    }
+                    "rust_import_alias" => {
+                        // For aliased imports, we might want to track the alias
+                        // For now, we'll just add it to items
+                        if let Ok(alias_text) = node.utf8_text(content.as_bytes()) {
+                            items.push(format!("as {alias_text}"));
+                        }
+                    }
+                    "java_import" => {
+                        let (parsed_module, parsed_items) =
+                            self.parse_java_import_declaration(node, content);
+                        module = parsed_module;
+                        items = parsed_items;
+                    }
+                    "kotlin_import" => {
+                        let (parsed_module, parsed_items) =
+                            self.parse_kotlin_import_header(node, content);
+                        module = parsed_module;
+                        items = parsed_items;
+                    }
+                    "dart_directive" => {
+                        let (parsed_module, parsed_items) =
+                            self.parse_dart_directive(node, content);
+                        // `package:` and `dart:` URIs are absolute, anything else is
+                        // relative to the importing file
+                        is_relative = !parsed_module.contains(':');
+                        module = parsed_module;
+                        items = parsed_items;
+                    }
+                    "haskell_import" => {
+                        let (parsed_module, parsed_items) =
+                            self.parse_haskell_import(node, content);
+                        module = parsed_module;
+                        items = parsed_items;
+                    }
+                    "r_import" => {
+                        // Sourced scripts are files, attached packages are names
+                        module = self.parse_r_loader_call(node, content);
+                        is_relative = module.contains('/')
+                            || module.ends_with(".R")
+                            || module.ends_with(".r");
+                    }
+                    "elm_import" => {
+                        let (parsed_module, parsed_items) = self.parse_elm_import(node, content);
+                        module = parsed_module;
+                        items = parsed_items;
+                    }
+                    "elixir_import" => {
+                        let (parsed_module, parsed_items) =
+                            self.parse_elixir_directive(node, content);
+                        module = parsed_module;
+                        items = parsed_items;
+                    }
+                    "scala_import" => {
+                        let (parsed_module, parsed_items) =
+                            self.parse_scala_import_declaration(node, content);
+                        module = parsed_module;
+                        items = parsed_items;
+                    }
+                    "php_use" => {
+                        let (parsed_module, parsed_items) =
+                            self.parse_php_use_declaration(node, content);
+                        module = parsed_module;
+                        items = parsed_items;
+                    }
+                    "php_include" => {
+                        // Includes resolve against the including file before the include path
+                        module = self.parse_php_include_expression(node, content);
+                        is_relative = true;
                     }
-                    "rust_import_alias" => {
-                        // For aliased imports, we might want to track the alias
-                        // For now, we'll just add it to items
-                        if let Ok(alias_text) = node.utf8_text(content.as_bytes()) {
-                            items.push(format!("as {alias_text}"));
+                    "ruby_require_method" => {
+                        // require_relative paths are relative to the requiring file
+                        is_relative = node.utf8_text(content.as_bytes()) == Ok("require_relative");
+                    }
+                    "csharp_using" => {
+                        let (parsed_module, parsed_items) =
+                            self.parse_csharp_using_directive(node, content);
+                        module = parsed_module;
+                        items = parsed_items;
+                    }
+                    "c_include_path" => {
+                        if let Ok(path_text) = node.utf8_text(content.as_bytes()) {
+                            // Quoted includes are searched relative to the including file first
+                            is_relative = path_text.starts_with('"');
+                            module = path_text
+                                .trim_matches(|c| matches!(c, '"' | '<' | '>'))
+                                .to_string();
                         }
                     }
-                    "js_import" | "ts_import" | "go_import" | "go_named_import"
-                    | "swift_import" => {
-                        // For JavaScript/TypeScript/Go/Swift, we rely on module_path and import_name captures
+                    "js_import"
+                    | "ts_import"
+                    | "js_reexport"
+                    | "ts_reexport"
+                    | "require"
+                    | "destructured_require"
+                    | "dynamic_import"
+                    | "go_import"
+                    | "go_named_import"
+                    | "swift_import"
+                    | "c_include"
+                    | "ruby_require" => {
+                        // For JavaScript/TypeScript/Go/Swift/C, we rely on module_path and import_name captures
                         // The module and items will be set by those specific captures
                     }
                     "simple_import" => {
@@ -967,6 +2456,10 @@ impl QueryEngine {
                                 .trim_matches('\'')
                                 .trim_matches('`')
                                 .to_string();
+                            // Template literals with substitutions aren't static specifiers
+                            if module.contains("${") {
+                                module.clear();
+                            }
                             // Check if it's a relative import for JavaScript/TypeScript
                             if module.starts_with('.') {
                                 is_relative = true;
@@ -1002,6 +2495,19 @@ impl QueryEngine {
         matches: tree_sitter::QueryMatches<'a, 'a, &'a [u8]>,
         content: &str,
     ) -> Result<Vec<FunctionCall>, ContextCreatorError> {
+        Ok(self
+            .collect_function_calls(matches, content)?
+            .into_iter()
+            .map(|(call, _)| call)
+            .collect())
+    }
+
+    /// Extract function calls from query matches with the byte offset of each called name
+    fn collect_function_calls<'a>(
+        &self,
+        matches: tree_sitter::QueryMatches<'a, 'a, &'a [u8]>,
+        content: &str,
+    ) -> Result<Vec<(FunctionCall, usize)>, ContextCreatorError> {
         let mut calls = Vec::new();
         let call_query_captures = self.function_call_query.capture_names();
 
@@ -1009,6 +2515,7 @@ impl QueryEngine {
             let mut name = String::new();
             let mut module = None;
             let mut line = 0;
+            let mut offset = 0;
             let mut module_name = String::new();
             let mut type_name = String::new();
 
@@ -1021,6 +2528,7 @@ impl QueryEngine {
                     "fn_name" | "method_name" => {
                         if let Ok(fn_name) = node.utf8_text(content.as_bytes()) {
                             name = fn_name.to_string();
+                            offset = node.start_byte();
                         }
                     }
                     "module_name" => {
@@ -1037,6 +2545,20 @@ impl QueryEngine {
                     "macro_name" => {
                         if let Ok(macro_name) = node.utf8_text(content.as_bytes()) {
                             name = macro_name.to_string();
+                            offset = node.start_byte();
+                        }
+                    }
+                    "qualified_name" => {
+                        // Haskell-style qualified names: Map.insert, Shop.Db.lookup
+                        if let Some((qualifier, fn_name)) = node
+                            .utf8_text(content.as_bytes())
+                            .ok()
+                            .and_then(|text| text.rsplit_once('.'))
+                        {
+                            name = fn_name.to_string();
+                            module_name = qualifier.to_string();
+                            module = Some(qualifier.to_string());
+                            offset = node.start_byte() + qualifier.len() + 1;
                         }
                     }
                     _ => {}
@@ -1049,7 +2571,7 @@ impl QueryEngine {
             }
 
             if !name.is_empty() {
-                calls.push(FunctionCall { name, module, line });
+                calls.push((FunctionCall { name, module, line }, offset));
             }
         }
 
@@ -1062,6 +2584,19 @@ impl QueryEngine {
         matches: tree_sitter::QueryMatches<'a, 'a, &'a [u8]>,
         content: &str,
     ) -> Result<Vec<TypeReference>, ContextCreatorError> {
+        Ok(self
+            .collect_type_references(matches, content)?
+            .into_iter()
+            .map(|(type_ref, _)| type_ref)
+            .collect())
+    }
+
+    /// Extract type references from query matches with the byte offset of each type name
+    fn collect_type_references<'a>(
+        &self,
+        matches: tree_sitter::QueryMatches<'a, 'a, &'a [u8]>,
+        content: &str,
+    ) -> Result<Vec<(TypeReference, usize)>, ContextCreatorError> {
         let mut type_refs = Vec::new();
         let type_query_captures = self.type_reference_query.capture_names();
 
@@ -1081,7 +2616,10 @@ impl QueryEngine {
                         | "trait_name" | "imported_type" | "interface_name" | "type_alias"
                         | "jsx_type" | "parent_class" | "type_arg" | "base_type"
                         | "subscript_type" => {
-                            names.insert(capture_name.to_string(), text.to_string());
+                            names.insert(
+                                capture_name.to_string(),
+                                (text.to_string(), node.start_byte()),
+                            );
                         }
                         "module_name" => {
                             module = Some(text.to_string());
@@ -1090,26 +2628,42 @@ impl QueryEngine {
                             // For scoped modules like "crate::models", use as-is
                             module = Some(text.to_string());
                         }
+                        "qualified_name" => {
+                            // Haskell-style qualified types: M.Map, Shop.Types.Order
+                            if let Some((qualifier, type_name)) = text.rsplit_once('.') {
+                                names.insert(
+                                    capture_name.to_string(),
+                                    (
+                                        type_name.to_string(),
+                                        node.start_byte() + qualifier.len() + 1,
+                                    ),
+                                );
+                                module = Some(qualifier.to_string());
+                            }
+                        }
                         _ => {}
                     }
                 }
             }
 
             // Create type references for each captured type name
-            for (_, type_name) in names {
+            for (_, (type_name, offset)) in names {
                 // Skip built-in types and primitives
                 if self.is_builtin_type(&type_name) {
                     continue;
                 }
 
-                type_refs.push(TypeReference {
-                    name: type_name.clone(),
-                    module: module.clone(),
-                    line,
-                    definition_path: None,
-                    is_external: false,
-                    external_package: None,
-                });
+                type_refs.push((
+                    TypeReference {
+                        name: type_name.clone(),
+                        module: module.clone(),
+                        line,
+                        definition_path: None,
+                        is_external: false,
+                        external_package: None,
+                    },
+                    offset,
+                ));
             }
         }
 
@@ -1294,6 +2848,21 @@ impl QueryEngine {
             Some("js") | Some("jsx") => Some(tree_sitter_javascript::language()),
             Some("go") => Some(tree_sitter_go::language()),
             Some("swift") => Some(tree_sitter_swift::language()),
+            Some("java") => Some(tree_sitter_java::language()),
+            Some("cs") => Some(tree_sitter_c_sharp::language()),
+            Some("rb") => Some(tree_sitter_ruby::language()),
+            Some("php") => Some(tree_sitter_php::language_php()),
+            Some("kt") | Some("kts") => Some(tree_sitter_kotlin::language()),
+            Some("scala") | Some("sc") => Some(tree_sitter_scala::language()),
+            Some("dart") => Some(tree_sitter_dart::language()),
+            Some("ex") | Some("exs") => Some(tree_sitter_elixir::language()),
+            Some("lua") => Some(tree_sitter_lua::language()),
+            Some("hs") => Some(tree_sitter_haskell::language()),
+            Some("elm") => Some(tree_sitter_elm::language()),
+            Some("c") => Some(tree_sitter_c::language()),
+            Some("h" | "hh" | "hpp" | "hxx" | "cc" | "cpp" | "cxx") => {
+                Some(tree_sitter_cpp::language())
+            }
             _ => None,
         };
 
@@ -1362,82 +2931,837 @@ impl QueryEngine {
                         ]
                     "#
                     }
-                    _ => return Ok(false),
-                };
+                    Some("java") => {
+                        r#"
+                        [
+                          (class_declaration name: (identifier) @name)
+                          (interface_declaration name: (identifier) @name)
+                          (enum_declaration name: (identifier) @name)
+                          (record_declaration name: (identifier) @name)
+                          (annotation_type_declaration name: (identifier) @name)
+                        ]
+                    "#
+                    }
+                    Some("cs") => {
+                        r#"
+                        [
+                          (class_declaration name: (identifier) @name)
+                          (interface_declaration name: (identifier) @name)
+                          (struct_declaration name: (identifier) @name)
+                          (record_declaration name: (identifier) @name)
+                          (enum_declaration name: (identifier) @name)
+                          (delegate_declaration name: (identifier) @name)
+                        ]
+                    "#
+                    }
+                    Some("rb") => {
+                        r#"
+                        [
+                          (class name: (constant) @name)
+                          (class name: (scope_resolution name: (constant) @name))
+                          (module name: (constant) @name)
+                          (module name: (scope_resolution name: (constant) @name))
+                        ]
+                    "#
+                    }
+                    Some("php") => {
+                        r#"
+                        [
+                          (class_declaration name: (name) @name)
+                          (interface_declaration name: (name) @name)
+                          (trait_declaration name: (name) @name)
+                          (enum_declaration name: (name) @name)
+                        ]
+                    "#
+                    }
+                    Some("kt") | Some("kts") => {
+                        r#"
+                        [
+                          (class_declaration (type_identifier) @name)
+                          (object_declaration (type_identifier) @name)
+                          (type_alias (type_identifier) @name)
+                        ]
+                    "#
+                    }
+                    Some("scala") | Some("sc") => {
+                        r#"
+                        [
+                          (class_definition name: (identifier) @name)
+                          (object_definition name: (identifier) @name)
+                          (trait_definition name: (identifier) @name)
+                          (enum_definition name: (identifier) @name)
+                          (type_definition name: (type_identifier) @name)
+                        ]
+                    "#
+                    }
+                    Some("ex") | Some("exs") => {
+                        r#"
+                        (call
+                          target: (identifier) @keyword
+                          (arguments . (alias) @name)
+                          (#match? @keyword "^(defmodule|defprotocol)$")
+                        )
+                    "#
+                    }
+                    Some("hs") => {
+                        r#"
+                        [
+                          (adt name: (type) @name)
+                          (newtype name: (type) @name)
+                          (type_alias name: (type) @name)
+                          (class (class_head class: (class_name (type) @name)))
+                        ]
+                    "#
+                    }
+                    Some("elm") => {
+                        r#"
+                        [
+                          (type_declaration name: (upper_case_identifier) @name)
+                          (type_alias_declaration name: (upper_case_identifier) @name)
+                        ]
+                    "#
+                    }
+                    Some("lua") => {
+                        r#"
+                        (assignment_statement
+                          (variable_list name: (identifier) @name)
+                          (expression_list value: (table_constructor))
+                        )
+                    "#
+                    }
+                    Some("dart") => {
+                        r#"
+                        [
+                          (class_definition name: (identifier) @name)
+                          (mixin_declaration . (identifier) @name)
+                          (enum_declaration name: (identifier) @name)
+                          (extension_declaration name: (identifier) @name)
+                          (type_alias . (type_identifier) @name)
+                        ]
+                    "#
+                    }
+                    Some("c") => {
+                        r#"
+                        [
+                          (struct_specifier name: (type_identifier) @name body: (field_declaration_list))
+                          (union_specifier name: (type_identifier) @name body: (field_declaration_list))
+                          (enum_specifier name: (type_identifier) @name body: (enumerator_list))
+                          (type_definition declarator: (type_identifier) @name)
+                        ]
+                    "#
+                    }
+                    Some("h" | "hh" | "hpp" | "hxx" | "cc" | "cpp" | "cxx") => {
+                        r#"
+                        [
+                          (class_specifier name: (type_identifier) @name body: (field_declaration_list))
+                          (struct_specifier name: (type_identifier) @name body: (field_declaration_list))
+                          (union_specifier name: (type_identifier) @name body: (field_declaration_list))
+                          (enum_specifier name: (type_identifier) @name body: (enumerator_list))
+                          (type_definition declarator: (type_identifier) @name)
+                          (alias_declaration name: (type_identifier) @name)
+                        ]
+                    "#
+                    }
+                    _ => return Ok(false),
+                };
+
+                if let Ok(query) = tree_sitter::Query::new(language, query_text) {
+                    let mut cursor = tree_sitter::QueryCursor::new();
+                    let matches = cursor.matches(&query, tree.root_node(), content.as_bytes());
+
+                    // Check each match to see if the captured name matches our target type
+                    for m in matches {
+                        for capture in m.captures {
+                            if let Ok(captured_text) = capture.node.utf8_text(content.as_bytes()) {
+                                if captured_text == type_name {
+                                    return Ok(true);
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(false)
+    }
+
+    /// Get appropriate file extensions for searching based on current file
+    fn get_search_extensions(&self, current_file: &std::path::Path) -> Vec<&'static str> {
+        match current_file.extension().and_then(|s| s.to_str()) {
+            Some("rs") => vec!["rs"],
+            Some("py") => vec!["py"],
+            Some("ts") | Some("tsx") => vec!["ts", "tsx", "js", "jsx"],
+            Some("js") | Some("jsx") => vec!["js", "jsx", "ts", "tsx"],
+            Some("vue" | "svelte" | "astro") => vec!["ts", "tsx", "js", "jsx"],
+            Some("go") => vec!["go"],
+            Some("swift") => vec!["swift"],
+            Some("java") => vec!["java"],
+            Some("cs") => vec!["cs"],
+            Some("rb") => vec!["rb"],
+            Some("php") => vec!["php"],
+            Some("kt") | Some("kts") => vec!["kt", "java"],
+            Some("scala") | Some("sc") => vec!["scala", "java"],
+            Some("dart") => vec!["dart"],
+            Some("ex") | Some("exs") => vec!["ex", "exs"],
+            Some("lua") => vec!["lua"],
+            Some("hs") => vec!["hs"],
+            Some("elm") => vec!["elm"],
+            Some("r" | "R") => vec!["R", "r"],
+            Some("c") => vec!["h", "c"],
+            Some("h" | "hh" | "hpp" | "hxx" | "cc" | "cpp" | "cxx") => {
+                vec!["h", "hpp", "hh", "cpp", "cc", "cxx", "c"]
+            }
+            _ => vec!["rs", "py", "ts", "js", "go", "swift"], // Default fallback
+        }
+    }
+
+    /// Parse Rust use tree structure
+    #[allow(dead_code)]
+    fn parse_rust_use_tree(
+        &self,
+        node: tree_sitter::Node,
+        content: &str,
+    ) -> (String, Vec<String>, bool) {
+        // Implementation would recursively parse the use tree structure
+        // For now, simplified implementation
+        if let Ok(text) = node.utf8_text(content.as_bytes()) {
+            let is_relative =
+                text.contains("self::") || text.contains("super::") || text.contains("crate::");
+            (text.to_string(), Vec::new(), is_relative)
+        } else {
+            (String::new(), Vec::new(), false)
+        }
+    }
+
+    /// Parse Rust module declaration structure
+    fn parse_rust_module_declaration(
+        &self,
+        node: tree_sitter::Node,
+        content: &str,
+    ) -> (String, Vec<String>, bool) {
+        // Parse module declaration like "mod config;"
+        if let Ok(text) = node.utf8_text(content.as_bytes()) {
+            // Look for the module name after "mod"
+            if let Some(mod_start) = text.find("mod ") {
+                let after_mod = &text[mod_start + 4..];
+                if let Some(end_pos) = after_mod.find(';') {
+                    let module_name = after_mod[..end_pos].trim();
+                    return (module_name.to_string(), Vec::new(), true);
+                } else if let Some(end_pos) = after_mod.find(' ') {
+                    let module_name = after_mod[..end_pos].trim();
+                    return (module_name.to_string(), Vec::new(), true);
+                }
+            }
+        }
+        (String::new(), Vec::new(), false)
+    }
+
+    /// Parse Java import declaration structure
+    ///
+    /// Single-type imports keep the class as the module and the simple name as the item,
+    /// static imports split the member off the class, and on-demand imports use `*`.
+    fn parse_java_import_declaration(
+        &self,
+        node: tree_sitter::Node,
+        content: &str,
+    ) -> (String, Vec<String>) {
+        let Ok(text) = node.utf8_text(content.as_bytes()) else {
+            return (String::new(), Vec::new());
+        };
+
+        let declaration = text
+            .trim()
+            .trim_start_matches("import")
+            .trim_end_matches(';')
+            .trim();
+        let (is_static, path) = match declaration.strip_prefix("static") {
+            Some(rest) if rest.starts_with(char::is_whitespace) => (true, rest),
+            _ => (false, declaration),
+        };
+        let path: String = path.chars().filter(|c| !c.is_whitespace()).collect();
+
+        if let Some(package) = path.strip_suffix(".*") {
+            return (package.to_string(), vec!["*".to_string()]);
+        }
+
+        match path.rsplit_once('.') {
+            Some((class, member)) if is_static => (class.to_string(), vec![member.to_string()]),
+            Some((_, class_name)) => (path.clone(), vec![class_name.to_string()]),
+            None => (path, Vec::new()),
+        }
+    }
+
+    /// Parse a C# using directive into (namespace or type, items)
+    fn parse_csharp_using_directive(
+        &self,
+        node: tree_sitter::Node,
+        content: &str,
+    ) -> (String, Vec<String>) {
+        let Ok(text) = node.utf8_text(content.as_bytes()) else {
+            return (String::new(), Vec::new());
+        };
+
+        let directive = text.trim().trim_end_matches(';').trim();
+        let directive = directive.strip_prefix("global").unwrap_or(directive).trim();
+        let directive = directive.strip_prefix("using").unwrap_or(directive).trim();
+        let (is_static, directive) = match directive.strip_prefix("static ") {
+            Some(rest) => (true, rest.trim()),
+            None => (false, directive),
+        };
+
+        let path = |value: &str| value.split_whitespace().collect::<String>();
+        match directive.split_once('=') {
+            Some((alias, target)) => (path(target), vec![format!("as {}", alias.trim())]),
+            None if is_static => (path(directive), vec!["*".to_string()]),
+            None => (path(directive), Vec::new()),
+        }
+    }
+
+    /// Whether a C# method or constructor can be called from other types. Members without
+    /// an access modifier are private, except inside interfaces.
+    fn is_csharp_member_accessible(&self, node: tree_sitter::Node, content: &str) -> bool {
+        let mut cursor = node.walk();
+        let modifiers: Vec<&str> = node
+            .children(&mut cursor)
+            .filter(|child| child.kind() == "modifier")
+            .filter_map(|child| child.utf8_text(content.as_bytes()).ok())
+            .collect();
+
+        if modifiers.contains(&"private") {
+            return false;
+        }
+        if modifiers
+            .iter()
+            .any(|modifier| matches!(*modifier, "public" | "internal" | "protected"))
+        {
+            return true;
+        }
+
+        node.parent()
+            .and_then(|body| body.parent())
+            .is_some_and(|declaration| declaration.kind() == "interface_declaration")
+    }
+
+    /// Parse a Kotlin import header into (path, items) using the Java conventions: the
+    /// imported name as the item, `*` for star imports, and `as Alias` for aliases.
+    fn parse_kotlin_import_header(
+        &self,
+        node: tree_sitter::Node,
+        content: &str,
+    ) -> (String, Vec<String>) {
+        let Ok(text) = node.utf8_text(content.as_bytes()) else {
+            return (String::new(), Vec::new());
+        };
+
+        let declaration = text
+            .trim()
+            .trim_start_matches("import")
+            .trim_end_matches(';')
+            .trim();
+        let (path, alias) = match declaration.split_once(" as ") {
+            Some((path, alias)) => (path, Some(alias.trim())),
+            None => (declaration, None),
+        };
+        let path: String = path.chars().filter(|c| !c.is_whitespace()).collect();
+
+        if let Some(package) = path.strip_suffix(".*") {
+            return (package.to_string(), vec!["*".to_string()]);
+        }
+        if let Some(alias) = alias {
+            return (path, vec![format!("as {alias}")]);
+        }
+
+        match path.rsplit_once('.') {
+            Some((_, name)) => (path.clone(), vec![name.to_string()]),
+            None => (path, Vec::new()),
+        }
+    }
+
+    /// Parse an Elixir `alias`, `import`, `require`, or `use` into (module, items).
+    /// Aliases list the names they bind: the last segment, each name of a multi-alias
+    /// group (with the group prefix as module), or "as Name" for `as:`.
+    fn parse_elixir_directive(
+        &self,
+        node: tree_sitter::Node,
+        content: &str,
+    ) -> (String, Vec<String>) {
+        let text = |n: tree_sitter::Node| n.utf8_text(content.as_bytes()).unwrap_or_default();
+        let Some(directive) = node.child_by_field_name("target").map(text) else {
+            return (String::new(), Vec::new());
+        };
+        let mut cursor = node.walk();
+        let Some(arguments) = node
+            .named_children(&mut cursor)
+            .find(|child| child.kind() == "arguments")
+        else {
+            return (String::new(), Vec::new());
+        };
+        let mut cursor = arguments.walk();
+        let arguments: Vec<_> = arguments.named_children(&mut cursor).collect();
+        let Some(target) = arguments.first().copied() else {
+            return (String::new(), Vec::new());
+        };
+
+        // alias MyApp.Orders.{Order, LineItem}
+        let is_group = target.kind() == "dot"
+            && target
+                .child_by_field_name("right")
+                .is_some_and(|right| right.kind() == "tuple");
+        if is_group {
+            let (Some(prefix), Some(group)) = (
+                target.child_by_field_name("left"),
+                target.child_by_field_name("right"),
+            ) else {
+                return (String::new(), Vec::new());
+            };
+            let mut cursor = group.walk();
+            let names = group
+                .named_children(&mut cursor)
+                .filter(|child| child.kind() == "alias")
+                .map(|child| text(child).to_string())
+                .collect();
+            return (text(prefix).to_string(), names);
+        }
+        // alias __MODULE__.Child is a dot, not an alias
+        if !matches!(target.kind(), "alias" | "dot") {
+            return (String::new(), Vec::new());
+        }
+
+        let module: String = text(target).split_whitespace().collect();
+        if directive != "alias" {
+            return (module, Vec::new());
+        }
+
+        let alias = arguments
+            .iter()
+            .filter(|argument| argument.kind() == "keywords")
+            .flat_map(|keywords| {
+                let mut cursor = keywords.walk();
+                keywords.named_children(&mut cursor).collect::<Vec<_>>()
+            })
+            .find(|pair| {
+                pair.child_by_field_name("key")
+                    .is_some_and(|key| text(key).trim() == "as:")
+            })
+            .and_then(|pair| pair.child_by_field_name("value"))
+            .map(|value| format!("as {}", text(value)));
+        let items = match alias {
+            Some(alias) => vec![alias],
+            None => vec![module.rsplit('.').next().unwrap_or_default().to_string()],
+        };
+
+        (module, items)
+    }
+
+    /// The script or package named by an R `source()`, `library()`, or `require()` call.
+    /// Paths built with `file.path()` or `here::here()` from string literals are joined.
+    fn parse_r_loader_call(&self, node: tree_sitter::Node, content: &str) -> String {
+        let string_value = |value: tree_sitter::Node| {
+            value
+                .utf8_text(content.as_bytes())
+                .ok()
+                .map(|text| text.trim_matches(['"', '\'']).to_string())
+        };
+        let Some(value) = node
+            .child_by_field_name("arguments")
+            .and_then(|arguments| arguments.child_by_field_name("value"))
+        else {
+            return String::new();
+        };
+
+        match value.kind() {
+            "string" | "identifier" => string_value(value).unwrap_or_default(),
+            "call" => {
+                let builder = value
+                    .child_by_field_name("function")
+                    .and_then(|function| function.utf8_text(content.as_bytes()).ok());
+                if !matches!(builder, Some("file.path" | "here" | "here::here")) {
+                    return String::new();
+                }
+                let Some(arguments) = value.child_by_field_name("arguments") else {
+                    return String::new();
+                };
+                let mut cursor = arguments.walk();
+                let parts: Vec<_> = arguments
+                    .children_by_field_name("value", &mut cursor)
+                    .collect();
+                if parts.iter().any(|part| part.kind() != "string") {
+                    return String::new();
+                }
+                parts
+                    .into_iter()
+                    .filter_map(string_value)
+                    .collect::<Vec<_>>()
+                    .join("/")
+            }
+            _ => String::new(),
+        }
+    }
+
+    /// Parse a Haskell import into (module, items). Explicit import lists become the
+    /// items and an `as` alias is recorded as `as <Alias>`; `hiding` lists import the
+    /// whole module.
+    fn parse_haskell_import(
+        &self,
+        node: tree_sitter::Node,
+        content: &str,
+    ) -> (String, Vec<String>) {
+        let mut module = String::new();
+        let mut items = Vec::new();
+        let mut after_as = false;
+
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            match child.kind() {
+                "as" => after_as = true,
+                "qualified_module" | "module" => {
+                    let Ok(text) = child.utf8_text(content.as_bytes()) else {
+                        continue;
+                    };
+                    if after_as {
+                        items.push(format!("as {text}"));
+                    } else if module.is_empty() {
+                        module = text.to_string();
+                    }
+                }
+                "import_list" => {
+                    let mut list_cursor = child.walk();
+                    let is_hiding = child
+                        .children(&mut list_cursor)
+                        .any(|part| part.kind() == "hiding");
+                    if is_hiding {
+                        continue;
+                    }
+                    let mut list_cursor = child.walk();
+                    items.extend(
+                        child
+                            .named_children(&mut list_cursor)
+                            .filter(|item| item.kind() == "import_item")
+                            .filter_map(|item| item.named_child(0))
+                            .filter_map(|name| name.utf8_text(content.as_bytes()).ok())
+                            .map(str::to_string),
+                    );
+                }
+                _ => {}
+            }
+        }
 
-                if let Ok(query) = tree_sitter::Query::new(language, query_text) {
-                    let mut cursor = tree_sitter::QueryCursor::new();
-                    let matches = cursor.matches(&query, tree.root_node(), content.as_bytes());
+        (module, items)
+    }
 
-                    // Check each match to see if the captured name matches our target type
-                    for m in matches {
-                        for capture in m.captures {
-                            if let Ok(captured_text) = capture.node.utf8_text(content.as_bytes()) {
-                                if captured_text == type_name {
-                                    return Ok(true);
-                                }
-                            }
+    /// Parse an Elm import into (module, items). Exposed values and types become the
+    /// items, `exposing (..)` is kept as `..`, and an `as` alias is recorded as
+    /// `as <Alias>`.
+    fn parse_elm_import(&self, node: tree_sitter::Node, content: &str) -> (String, Vec<String>) {
+        let text = |node: tree_sitter::Node| {
+            node.utf8_text(content.as_bytes())
+                .map(str::to_string)
+                .unwrap_or_default()
+        };
+        let module = node
+            .child_by_field_name("moduleName")
+            .map(text)
+            .unwrap_or_default();
+
+        let mut items = Vec::new();
+        if let Some(exposing) = node.child_by_field_name("exposing") {
+            let mut cursor = exposing.walk();
+            for exposed in exposing.named_children(&mut cursor) {
+                match exposed.kind() {
+                    "double_dot" => items.push("..".to_string()),
+                    "exposed_value" | "exposed_operator" => items.push(text(exposed)),
+                    "exposed_type" => {
+                        if let Some(name) = exposed.named_child(0) {
+                            items.push(text(name));
                         }
                     }
+                    _ => {}
                 }
             }
         }
+        if let Some(alias) = node
+            .child_by_field_name("asClause")
+            .and_then(|clause| clause.child_by_field_name("name"))
+        {
+            items.push(format!("as {}", text(alias)));
+        }
 
-        Ok(false)
+        (module, items)
     }
 
-    /// Get appropriate file extensions for searching based on current file
-    fn get_search_extensions(&self, current_file: &std::path::Path) -> Vec<&'static str> {
-        match current_file.extension().and_then(|s| s.to_str()) {
-            Some("rs") => vec!["rs"],
-            Some("py") => vec!["py"],
-            Some("ts") | Some("tsx") => vec!["ts", "tsx", "js", "jsx"],
-            Some("js") | Some("jsx") => vec!["js", "jsx", "ts", "tsx"],
-            Some("go") => vec!["go"],
-            Some("swift") => vec!["swift"],
-            _ => vec!["rs", "py", "ts", "js", "go", "swift"], // Default fallback
+    /// Parse a Dart import, export, or part directive into (URI, items). `show` names
+    /// become items and an import prefix is kept as "as prefix"; `part of` a library
+    /// name rather than a URI yields no module.
+    fn parse_dart_directive(
+        &self,
+        node: tree_sitter::Node,
+        content: &str,
+    ) -> (String, Vec<String>) {
+        let Ok(text) = node.utf8_text(content.as_bytes()) else {
+            return (String::new(), Vec::new());
+        };
+
+        let mut quoted = text.split(['\'', '"']);
+        let (Some(_), Some(uri), Some(rest)) = (quoted.next(), quoted.next(), quoted.next()) else {
+            return (String::new(), Vec::new());
+        };
+
+        let mut items = Vec::new();
+        let rest = rest.trim().trim_end_matches(';');
+        let mut words = rest
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|word| !word.is_empty());
+        let mut in_show = false;
+        while let Some(word) = words.next() {
+            match word {
+                "as" => {
+                    if let Some(prefix) = words.next() {
+                        items.push(format!("as {prefix}"));
+                    }
+                    in_show = false;
+                }
+                "show" => in_show = true,
+                "hide" | "deferred" => in_show = false,
+                name if in_show => items.push(name.to_string()),
+                _ => {}
+            }
         }
+
+        (uri.to_string(), items)
     }
 
-    /// Parse Rust use tree structure
-    #[allow(dead_code)]
-    fn parse_rust_use_tree(
+    /// Parse a Scala import into (prefix, selectors). Selector groups list each name,
+    /// with Scala 2 `A => B` and Scala 3 `A as B` renames as "A as B" and `_`/`*`
+    /// wildcards as "*"; hidden names (`A => _`) and `given` selectors are dropped.
+    /// Comma-separated clauses (`import a.B, c.D`) keep an empty prefix and list each path.
+    fn parse_scala_import_declaration(
         &self,
         node: tree_sitter::Node,
         content: &str,
-    ) -> (String, Vec<String>, bool) {
-        // Implementation would recursively parse the use tree structure
-        // For now, simplified implementation
-        if let Ok(text) = node.utf8_text(content.as_bytes()) {
-            let is_relative =
-                text.contains("self::") || text.contains("super::") || text.contains("crate::");
-            (text.to_string(), Vec::new(), is_relative)
-        } else {
-            (String::new(), Vec::new(), false)
-        }
+    ) -> (String, Vec<String>) {
+        let Ok(text) = node.utf8_text(content.as_bytes()) else {
+            return (String::new(), Vec::new());
+        };
+
+        let declaration = text
+            .trim()
+            .trim_start_matches("import")
+            .trim_end_matches(';')
+            .trim();
+
+        let (prefix, selectors) = match declaration.split_once('{') {
+            Some((prefix, selectors)) => (
+                prefix.trim().trim_end_matches('.').to_string(),
+                selectors.trim_end().trim_end_matches('}').to_string(),
+            ),
+            None if declaration.contains(',') => (String::new(), declaration.to_string()),
+            None => match declaration.rsplit_once('.') {
+                Some((prefix, selector)) => (prefix.trim().to_string(), selector.to_string()),
+                None => (String::new(), declaration.to_string()),
+            },
+        };
+        let prefix: String = prefix.chars().filter(|c| !c.is_whitespace()).collect();
+
+        let items = selectors
+            .split(',')
+            .filter_map(|selector| {
+                let selector = selector.trim();
+                if selector == "_" || selector == "*" {
+                    return Some("*".to_string());
+                }
+                if selector.is_empty() || selector == "given" || selector.starts_with("given ") {
+                    return None;
+                }
+                let renamed = selector
+                    .split_once("=>")
+                    .or_else(|| selector.split_once(" as "));
+                match renamed {
+                    Some((_, alias)) if alias.trim() == "_" => None,
+                    Some((name, alias)) => Some(format!("{} as {}", name.trim(), alias.trim())),
+                    None => Some(selector.chars().filter(|c| !c.is_whitespace()).collect()),
+                }
+            })
+            .collect();
+
+        (prefix, items)
     }
 
-    /// Parse Rust module declaration structure
-    fn parse_rust_module_declaration(
+    /// Parse a PHP use declaration into (class or group prefix, items). Group and
+    /// multi-clause uses keep the prefix as the module and list each class as an item;
+    /// `use function` and `use const` import no classes and are skipped.
+    fn parse_php_use_declaration(
         &self,
         node: tree_sitter::Node,
         content: &str,
-    ) -> (String, Vec<String>, bool) {
-        // Parse module declaration like "mod config;"
-        if let Ok(text) = node.utf8_text(content.as_bytes()) {
-            // Look for the module name after "mod"
-            if let Some(mod_start) = text.find("mod ") {
-                let after_mod = &text[mod_start + 4..];
-                if let Some(end_pos) = after_mod.find(';') {
-                    let module_name = after_mod[..end_pos].trim();
-                    return (module_name.to_string(), Vec::new(), true);
-                } else if let Some(end_pos) = after_mod.find(' ') {
-                    let module_name = after_mod[..end_pos].trim();
-                    return (module_name.to_string(), Vec::new(), true);
+    ) -> (String, Vec<String>) {
+        let Ok(text) = node.utf8_text(content.as_bytes()) else {
+            return (String::new(), Vec::new());
+        };
+
+        let declaration = text.trim().trim_end_matches(';').trim();
+        let declaration = declaration
+            .strip_prefix("use")
+            .unwrap_or(declaration)
+            .trim();
+        if declaration.starts_with("function ") || declaration.starts_with("const ") {
+            return (String::new(), Vec::new());
+        }
+
+        let clean = |value: &str| {
+            value
+                .split_whitespace()
+                .collect::<Vec<_>>()
+                .join(" ")
+                .trim_start_matches('\\')
+                .to_string()
+        };
+
+        if let Some((prefix, group)) = declaration.split_once('{') {
+            let items = group
+                .trim_end_matches('}')
+                .split(',')
+                .map(clean)
+                .filter(|item| {
+                    !item.is_empty()
+                        && !item.starts_with("function ")
+                        && !item.starts_with("const ")
+                })
+                .collect();
+            return (clean(prefix).trim_end_matches('\\').to_string(), items);
+        }
+
+        let clauses: Vec<String> = declaration.split(',').map(clean).collect();
+        match clauses.as_slice() {
+            [clause] => match clause.split_once(" as ") {
+                Some((class, alias)) => (class.to_string(), vec![format!("as {alias}")]),
+                None => (clause.clone(), Vec::new()),
+            },
+            _ => (String::new(), clauses),
+        }
+    }
+
+    /// The path of a PHP include. `__DIR__ . '/x.php'` becomes `./x.php`.
+    fn parse_php_include_expression(&self, node: tree_sitter::Node, content: &str) -> String {
+        let Ok(text) = node.utf8_text(content.as_bytes()) else {
+            return String::new();
+        };
+
+        let Some(path) = text
+            .split(['\'', '"'])
+            .nth(1)
+            .filter(|path| !path.is_empty())
+        else {
+            return String::new();
+        };
+
+        if text.contains("__DIR__") || text.contains("dirname(__FILE__)") {
+            format!("./{}", path.trim_start_matches('/'))
+        } else {
+            path.to_string()
+        }
+    }
+
+    /// Whether a Ruby method is hidden by `private`/`protected`, either as a bare section
+    /// marker before it, wrapping it (`private def x`), or naming it (`private :x`).
+    fn is_ruby_method_private(&self, node: tree_sitter::Node, content: &str) -> bool {
+        let text = |n: tree_sitter::Node| n.utf8_text(content.as_bytes()).unwrap_or_default();
+        let is_singleton = node.kind() == "singleton_method";
+        let hiding_calls: &[&str] = if is_singleton {
+            &["private_class_method"]
+        } else {
+            &["private", "protected"]
+        };
+
+        if let Some(call) = node
+            .parent()
+            .filter(|parent| parent.kind() == "argument_list")
+            .and_then(|arguments| arguments.parent())
+        {
+            if call.kind() == "call"
+                && call
+                    .child_by_field_name("method")
+                    .is_some_and(|method| hiding_calls.contains(&text(method)))
+            {
+                return true;
+            }
+            return false;
+        }
+
+        let name = node
+            .child_by_field_name("name")
+            .map(text)
+            .unwrap_or_default();
+        let Some(body) = node.parent() else {
+            return false;
+        };
+
+        let mut in_hidden_section = false;
+        let mut cursor = body.walk();
+        for sibling in body.named_children(&mut cursor) {
+            if sibling.id() == node.id() {
+                if in_hidden_section && !is_singleton {
+                    return true;
+                }
+                continue;
+            }
+
+            match sibling.kind() {
+                "identifier" => match text(sibling) {
+                    "private" | "protected" => in_hidden_section = true,
+                    "public" => in_hidden_section = false,
+                    _ => {}
+                },
+                "call" => {
+                    let hides = sibling
+                        .child_by_field_name("method")
+                        .is_some_and(|method| hiding_calls.contains(&text(method)));
+                    let names_method = sibling
+                        .child_by_field_name("arguments")
+                        .map(|arguments| {
+                            let mut arguments_cursor = arguments.walk();
+                            let symbols: Vec<_> = arguments
+                                .named_children(&mut arguments_cursor)
+                                .map(|argument| text(argument).trim_start_matches(':').to_string())
+                                .collect();
+                            symbols.iter().any(|symbol| symbol == name)
+                        })
+                        .unwrap_or(false);
+                    if hides && names_method {
+                        return true;
+                    }
                 }
+                _ => {}
             }
         }
-        (String::new(), Vec::new(), false)
+
+        false
+    }
+
+    /// Whether a Scala definition is `private` or `private[this]`. Qualified
+    /// `private[pkg]` definitions stay visible to the rest of the package.
+    fn is_scala_definition_private(&self, node: tree_sitter::Node, content: &str) -> bool {
+        let mut cursor = node.walk();
+        let is_private = node
+            .children(&mut cursor)
+            .filter(|child| child.kind() == "modifiers")
+            .filter_map(|modifiers| modifiers.utf8_text(content.as_bytes()).ok())
+            .any(|modifiers| {
+                modifiers
+                    .split_whitespace()
+                    .any(|word| word == "private" || word == "private[this]")
+            });
+        is_private
+    }
+
+    /// Whether a C/C++ function definition or prototype is declared `static`.
+    fn has_static_storage(&self, node: tree_sitter::Node, content: &str) -> bool {
+        let mut cursor = node.walk();
+        let has_static = node.children(&mut cursor).any(|child| {
+            child.kind() == "storage_class_specifier"
+                && child.utf8_text(content.as_bytes()) == Ok("static")
+        });
+        has_static
     }
 
     /// Parse Rust use declaration structure
@@ -1462,8 +3786,9 @@ impl QueryEngine {
                 || clean_text.contains("crate::");
 
             if clean_text.contains('{') && clean_text.contains('}') {
-                // Handle scoped imports like "model::{Account, DatabaseFactory}"
-                if let Some(colon_pos) = clean_text.find("::") {
+                // Handle scoped imports like "model::{Account, DatabaseFactory}", keeping the
+                // whole path before the braces as the module
+                if let Some(colon_pos) = clean_text.find("::{").or_else(|| clean_text.find("::")) {
                     let module = clean_text[..colon_pos].to_string();
 
                     // Extract items from braces
@@ -1577,12 +3902,27 @@ impl QueryEngine {
         matches: tree_sitter::QueryMatches<'a, 'a, &'a [u8]>,
         content: &str,
     ) -> Result<Vec<FunctionDefinition>, ContextCreatorError> {
+        Ok(self
+            .collect_function_definitions(matches, content)?
+            .into_iter()
+            .map(|(definition, _)| definition)
+            .collect())
+    }
+
+    /// Extract function definitions from query matches with the byte range of each definition
+    fn collect_function_definitions<'a>(
+        &self,
+        matches: tree_sitter::QueryMatches<'a, 'a, &'a [u8]>,
+        content: &str,
+    ) -> Result<Vec<(FunctionDefinition, Range<usize>)>, ContextCreatorError> {
         let mut definitions = Vec::new();
         let def_query_captures = self.function_definition_query.capture_names();
 
         for match_ in matches {
             let mut name = String::new();
+            let mut name_node = None;
             let mut is_exported = false;
+            let mut has_private_modifier = false;
             let mut line = 0;
 
             for capture in match_.captures {
@@ -1602,6 +3942,7 @@ impl QueryEngine {
                     | "commonjs_export_name" => {
                         if let Ok(fn_name) = node.utf8_text(content.as_bytes()) {
                             name = fn_name.to_string();
+                            name_node = Some(node);
                         }
                     }
                     "visibility" | "method_visibility" => {
@@ -1610,6 +3951,60 @@ impl QueryEngine {
                             is_exported = vis.contains("pub");
                         }
                     }
+                    "java_modifiers" => {
+                        if let Ok(modifiers) = node.utf8_text(content.as_bytes()) {
+                            has_private_modifier =
+                                modifiers.split_whitespace().any(|word| word == "private");
+                        }
+                    }
+                    "csharp_member" => {
+                        has_private_modifier = !self.is_csharp_member_accessible(node, content);
+                    }
+                    "ruby_method" => {
+                        has_private_modifier = self.is_ruby_method_private(node, content);
+                    }
+                    "kotlin_function" => {
+                        let mut cursor = node.walk();
+                        has_private_modifier = node
+                            .children(&mut cursor)
+                            .filter(|child| child.kind() == "modifiers")
+                            .filter_map(|modifiers| modifiers.utf8_text(content.as_bytes()).ok())
+                            .any(|modifiers| {
+                                modifiers.split_whitespace().any(|word| word == "private")
+                            });
+                    }
+                    "elixir_function" => {
+                        // defp, defmacrop, and defguardp are private to the module
+                        has_private_modifier = node
+                            .child_by_field_name("target")
+                            .and_then(|target| target.utf8_text(content.as_bytes()).ok())
+                            .is_some_and(|keyword| keyword.ends_with('p'));
+                    }
+                    "lua_function" => {
+                        // `local function f` and `local f = function` are chunk-private
+                        has_private_modifier =
+                            node.child(0).is_some_and(|child| child.kind() == "local")
+                                || node
+                                    .parent()
+                                    .is_some_and(|parent| parent.kind() == "variable_declaration");
+                    }
+                    "scala_function" => {
+                        has_private_modifier = self.is_scala_definition_private(node, content);
+                    }
+                    "php_method" => {
+                        let mut cursor = node.walk();
+                        has_private_modifier = node.children(&mut cursor).any(|child| {
+                            child.kind() == "visibility_modifier"
+                                && child.utf8_text(content.as_bytes()) == Ok("private")
+                        });
+                    }
+                    "c_function" => {
+                        // Functions with internal linkage are invisible to other translation units
+                        has_private_modifier = self.has_static_storage(node, content);
+                    }
+                    "private" => {
+                        has_private_modifier = true;
+                    }
                     "export_function" | "commonjs_export" => {
                         // JavaScript/TypeScript export
                         is_exported = true;
@@ -1644,6 +4039,40 @@ impl QueryEngine {
                     is_exported = true;
                 }
 
+                // In Java, everything but private members is reachable from other classes
+                if self.language_name == "java" {
+                    is_exported = !has_private_modifier;
+                }
+
+                if self.language_name == "c"
+                    || self.language_name == "cpp"
+                    || self.language_name == "csharp"
+                    || self.language_name == "ruby"
+                    || self.language_name == "php"
+                    || self.language_name == "kotlin"
+                    || self.language_name == "scala"
+                    || self.language_name == "elixir"
+                    || self.language_name == "lua"
+                    || self.language_name == "haskell"
+                    || self.language_name == "elm"
+                {
+                    is_exported = !has_private_modifier;
+                }
+
+                if self.from_language_pack {
+                    is_exported = !has_private_modifier;
+                }
+
+                // R hides names starting with a dot from listings and imports
+                if self.language_name == "r" {
+                    is_exported = !name.starts_with('.');
+                }
+
+                // Dart has no visibility keywords; a leading underscore makes a name library-private
+                if self.language_name == "dart" {
+                    is_exported = !name.starts_with('_');
+                }
+
                 // Special handling for JavaScript/TypeScript/Go without explicit export
                 if (self.language_name == "javascript"
                     || self.language_name == "typescript"
@@ -1655,11 +4084,33 @@ impl QueryEngine {
                     is_exported = true;
                 }
 
-                definitions.push(FunctionDefinition {
-                    name,
-                    is_exported,
-                    line,
-                });
+                // The definition spans the smallest capture around its name
+                let range = name_node
+                    .map(|name_node| {
+                        match_
+                            .captures
+                            .iter()
+                            .map(|capture| capture.node)
+                            .filter(|node| {
+                                node.start_byte() <= name_node.start_byte()
+                                    && name_node.end_byte() <= node.end_byte()
+                                    && node.byte_range() != name_node.byte_range()
+                            })
+                            .min_by_key(|node| node.end_byte() - node.start_byte())
+                            .or_else(|| name_node.parent())
+                            .unwrap_or(name_node)
+                            .byte_range()
+                    })
+                    .unwrap_or_default();
+
+                definitions.push((
+                    FunctionDefinition {
+                        name,
+                        is_exported,
+                        line,
+                    },
+                    range,
+                ));
             }
         }
 
@@ -1756,6 +4207,47 @@ impl QueryEngine {
     }
 }
 
+/// Name of the type a node defines
+fn type_definition_name(node: Node<'_>) -> Option<Node<'_>> {
+    if !TYPE_DEFINITION_KINDS.contains(&node.kind()) {
+        return None;
+    }
+    // `struct Point p;` names a C type without defining it
+    if node.kind().ends_with("_specifier") && node.child_by_field_name("body").is_none() {
+        return None;
+    }
+    node.child_by_field_name("name")
+}
+
+/// Name of the type a Rust impl block implements
+fn implemented_type_name(node: Node<'_>, content: &str) -> Option<String> {
+    let type_node = node.child_by_field_name("type")?;
+    type_node
+        .child_by_field_name("type")
+        .unwrap_or(type_node)
+        .utf8_text(content.as_bytes())
+        .ok()
+        .map(str::to_string)
+}
+
+/// Type named by the receiver of a Go method
+fn receiver_type_name(node: Node<'_>, content: &str) -> Option<String> {
+    let receiver = node.child_by_field_name("receiver")?;
+    let mut pending = vec![receiver];
+    while let Some(current) = pending.pop() {
+        if current.kind() == "type_identifier" {
+            return current
+                .utf8_text(content.as_bytes())
+                .ok()
+                .map(str::to_string);
+        }
+        let mut cursor = current.walk();
+        let children: Vec<Node<'_>> = current.named_children(&mut cursor).collect();
+        pending.extend(children.into_iter().rev());
+    }
+    None
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1811,6 +4303,132 @@ mod tests {
         assert!(engine.is_ok());
     }
 
+    #[test]
+    fn test_java_query_creation() {
+        let engine = QueryEngine::new(tree_sitter_java::language(), "java");
+        if let Err(e) = &engine {
+            println!("Java QueryEngine error: {e}");
+        }
+        assert!(engine.is_ok());
+    }
+
+    #[test]
+    fn test_c_query_creation() {
+        let engine = QueryEngine::new(tree_sitter_c::language(), "c");
+        if let Err(e) = &engine {
+            println!("C QueryEngine error: {e}");
+        }
+        assert!(engine.is_ok());
+    }
+
+    #[test]
+    fn test_cpp_query_creation() {
+        let engine = QueryEngine::new(tree_sitter_cpp::language(), "cpp");
+        if let Err(e) = &engine {
+            println!("C++ QueryEngine error: {e}");
+        }
+        assert!(engine.is_ok());
+    }
+
+    #[test]
+    fn test_csharp_query_creation() {
+        let engine = QueryEngine::new(tree_sitter_c_sharp::language(), "csharp");
+        if let Err(e) = &engine {
+            println!("C# QueryEngine error: {e}");
+        }
+        assert!(engine.is_ok());
+    }
+
+    #[test]
+    fn test_ruby_query_creation() {
+        let engine = QueryEngine::new(tree_sitter_ruby::language(), "ruby");
+        if let Err(e) = &engine {
+            println!("Ruby QueryEngine error: {e}");
+        }
+        assert!(engine.is_ok());
+    }
+
+    #[test]
+    fn test_php_query_creation() {
+        let engine = QueryEngine::new(tree_sitter_php::language_php(), "php");
+        if let Err(e) = &engine {
+            println!("PHP QueryEngine error: {e}");
+        }
+        assert!(engine.is_ok());
+    }
+
+    #[test]
+    fn test_kotlin_query_creation() {
+        let engine = QueryEngine::new(tree_sitter_kotlin::language(), "kotlin");
+        if let Err(e) = &engine {
+            println!("Kotlin QueryEngine error: {e}");
+        }
+        assert!(engine.is_ok());
+    }
+
+    #[test]
+    fn test_scala_query_creation() {
+        let engine = QueryEngine::new(tree_sitter_scala::language(), "scala");
+        if let Err(e) = &engine {
+            println!("Scala QueryEngine error: {e}");
+        }
+        assert!(engine.is_ok());
+    }
+
+    #[test]
+    fn test_dart_query_creation() {
+        let engine = QueryEngine::new(tree_sitter_dart::language(), "dart");
+        if let Err(e) = &engine {
+            println!("Dart QueryEngine error: {e}");
+        }
+        assert!(engine.is_ok());
+    }
+
+    #[test]
+    fn test_elixir_query_creation() {
+        let engine = QueryEngine::new(tree_sitter_elixir::language(), "elixir");
+        if let Err(e) = &engine {
+            println!("Elixir QueryEngine error: {e}");
+        }
+        assert!(engine.is_ok());
+    }
+
+    #[test]
+    fn test_lua_query_creation() {
+        let engine = QueryEngine::new(tree_sitter_lua::language(), "lua");
+        if let Err(e) = &engine {
+            println!("Lua QueryEngine error: {e}");
+        }
+        assert!(engine.is_ok());
+    }
+
+    #[test]
+    fn test_elm_query_creation() {
+        let engine = QueryEngine::new(tree_sitter_elm::language(), "elm");
+        if let Err(e) = &engine {
+            println!("Elm QueryEngine error: {e}");
+        }
+        assert!(engine.is_ok());
+    }
+
+    #[test]
+    fn test_haskell_query_creation() {
+        let engine = QueryEngine::new(tree_sitter_haskell::language(), "haskell");
+        if let Err(e) = &engine {
+            println!("Haskell QueryEngine error: {e}");
+        }
+        assert!(engine.is_ok());
+    }
+
+    #[test]
+    fn test_r_query_creation() {
+        let engine = QueryEngine::new(tree_sitter_r::language(), "r");
+        if let Err(e) = &engine {
+            println!("R QueryEngine error: {e}");
+        }
+        assert!(engine.is_ok());
+    }
+
     #[test]
     fn test_builtin_type_detection() {
         let engine = QueryEngine::new(tree_sitter_rust::language(), "rust").unwrap();
@@ -1820,4 +4438,56 @@ mod tests {
         assert!(engine.is_builtin_type("i32"));
         assert!(!engine.is_builtin_type("MyCustomType"));
     }
+
+    #[test]
+    fn test_go_methods_belong_to_their_receiver_type() {
+        let engine = QueryEngine::new(tree_sitter_go::language(), "go").unwrap();
+        let content = "package main\n\ntype Server struct{ port int }\n\nfunc (s *Server) Run() error { return listen(s.port) }\n\nfunc listen(port int) error { return nil }\n";
+        let symbols = engine.analyze_symbols(content).unwrap();
+
+        let names: Vec<(&str, SymbolKind)> = symbols
+            .definitions
+            .iter()
+            .map(|definition| (definition.name.as_str(), definition.kind))
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                ("Server", SymbolKind::Type),
+                ("Run", SymbolKind::Method),
+                ("listen", SymbolKind::Function)
+            ]
+        );
+        assert_eq!(symbols.definitions[1].container, Some(0));
+        assert!(content[symbols.definitions[1].byte_range.clone()].starts_with("func (s *Server)"));
+
+        let call = symbols
+            .references
+            .iter()
+            .find(|reference| reference.kind == SymbolReferenceKind::Call)
+            .unwrap();
+        assert_eq!(call.name, "listen");
+        assert_eq!(&content[call.byte_offset..call.byte_offset + 6], "listen");
+    }
+
+    #[test]
+    fn test_exported_typescript_functions_are_one_symbol() {
+        let engine =
+            QueryEngine::new(tree_sitter_typescript::language_typescript(), "typescript").unwrap();
+        let content = "export interface Cart { id: string }\nexport function save(cart: Cart) { return cart; }\n";
+        let symbols = engine.analyze_symbols(content).unwrap();
+
+        let names: Vec<&str> = symbols
+            .definitions
+            .iter()
+            .map(|definition| definition.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["Cart", "save"]);
+        assert!(content[symbols.definitions[1].byte_range.clone()].starts_with("function save"));
+        // The interface name is its definition, not a reference
+        assert!(symbols
+            .references
+            .iter()
+            .all(|reference| reference.line == 2 || reference.name != "Cart"));
+    }
 }
@@ -1,5 +1,6 @@
 //! Module resolution for converting import strings to file paths
 
+use crate::core::semantic::analyzer::SemanticContext;
 use crate::utils::error::ContextCreatorError;
 use std::path::{Path, PathBuf};
 
@@ -24,6 +25,17 @@ pub trait ModuleResolver: Send + Sync {
         base_dir: &Path,
     ) -> Result<ResolvedPath, ContextCreatorError>;
 
+    /// Resolve a module import with the settings of an analysis, such as the configured
+    /// include directories
+    fn resolve_import_in_context(
+        &self,
+        module_path: &str,
+        from_file: &Path,
+        context: &SemanticContext,
+    ) -> Result<ResolvedPath, ContextCreatorError> {
+        self.resolve_import(module_path, from_file, &context.base_dir)
+    }
+
     /// Get common file extensions for this language
     fn get_file_extensions(&self) -> Vec<&'static str>;
 
@@ -0,0 +1,413 @@
+//! Symbol-level dependency graph
+//!
+//! Where the file graph links whole files, this graph links the functions, methods, and types
+//! defined in them. Nodes carry the byte range of each definition; edges are calls and type
+//! references between symbols and the containment of methods in their types. Symbols come from
+//! the query engine captures of each file's language.
+
+use crate::core::semantic::analyzer::{
+    FileSymbols, LanguageAnalyzer, SymbolKind, SymbolReference, SymbolReferenceKind,
+};
+use crate::core::semantic::dependency_types::{SymbolEdgeType, SymbolNode};
+use crate::core::semantic::get_analyzer_for_file;
+use crate::utils::error::ContextCreatorError;
+use petgraph::graph::{DiGraph, NodeIndex};
+use petgraph::visit::EdgeRef;
+use petgraph::Direction;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+/// Unqualified references matching more definitions in other files are left unresolved
+const MAX_AMBIGUOUS_TARGETS: usize = 3;
+
+/// Graph of the symbols defined in a set of files
+#[derive(Debug, Default)]
+pub struct SymbolGraph {
+    graph: DiGraph<SymbolNode, SymbolEdgeType>,
+    file_symbols: HashMap<PathBuf, Vec<NodeIndex>>,
+    symbols_by_name: HashMap<String, Vec<NodeIndex>>,
+}
+
+/// Edge from a symbol of one file to a symbol of another
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SymbolLink {
+    /// The dependent symbol
+    pub from: SymbolNode,
+    /// The symbol it calls or references
+    pub to: SymbolNode,
+    /// How `from` uses `to`
+    pub edge: SymbolEdgeType,
+}
+
+impl std::fmt::Display for SymbolLink {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let file_name = |path: &Path| {
+            path.file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_else(|| path.display().to_string())
+        };
+        let (verb, line) = match self.edge {
+            SymbolEdgeType::Calls { line } => ("calls", line),
+            SymbolEdgeType::References { line } => ("references", line),
+            SymbolEdgeType::Contains => ("contains", self.to.line),
+        };
+        write!(
+            f,
+            "{} `{}` in {} {} {} `{}` in {} (line {})",
+            self.from.kind,
+            self.from.name,
+            file_name(&self.from.path),
+            verb,
+            self.to.kind,
+            self.to.name,
+            file_name(&self.to.path),
+            line
+        )
+    }
+}
+
+impl SymbolGraph {
+    /// The underlying graph
+    pub fn graph(&self) -> &DiGraph<SymbolNode, SymbolEdgeType> {
+        &self.graph
+    }
+
+    /// Get a symbol by index
+    pub fn symbol(&self, index: NodeIndex) -> &SymbolNode {
+        &self.graph[index]
+    }
+
+    /// Symbols defined in a file, in source order
+    pub fn symbols_in_file(&self, path: &Path) -> &[NodeIndex] {
+        self.file_symbols
+            .get(path)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Symbols with the given name across all files
+    pub fn symbols_named(&self, name: &str) -> &[NodeIndex] {
+        self.symbols_by_name
+            .get(name)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Innermost symbol whose definition contains a byte offset of a file
+    pub fn symbol_at(&self, path: &Path, byte_offset: usize) -> Option<NodeIndex> {
+        self.symbols_in_file(path)
+            .iter()
+            .copied()
+            .filter(|&index| {
+                let node = &self.graph[index];
+                node.kind != SymbolKind::Module && node.byte_range.contains(&byte_offset)
+            })
+            .min_by_key(|&index| self.graph[index].byte_range.len())
+    }
+
+    /// Symbols that the given symbol calls, references, or contains
+    pub fn dependencies(&self, symbol: NodeIndex) -> Vec<(NodeIndex, &SymbolEdgeType)> {
+        self.graph
+            .edges_directed(symbol, Direction::Outgoing)
+            .map(|edge| (edge.target(), edge.weight()))
+            .collect()
+    }
+
+    /// Symbols that call, reference, or contain the given symbol
+    pub fn dependents(&self, symbol: NodeIndex) -> Vec<(NodeIndex, &SymbolEdgeType)> {
+        self.graph
+            .edges_directed(symbol, Direction::Incoming)
+            .map(|edge| (edge.source(), edge.weight()))
+            .collect()
+    }
+
+    /// Calls and type references from symbols of `from_file` to symbols of `to_file`, those
+    /// from definitions first
+    pub fn links_between(&self, from_file: &Path, to_file: &Path) -> Vec<SymbolLink> {
+        let mut links: Vec<SymbolLink> = self
+            .symbols_in_file(from_file)
+            .iter()
+            .flat_map(|&index| self.graph.edges_directed(index, Direction::Outgoing))
+            .filter(|edge| self.graph[edge.target()].path == to_file)
+            .map(|edge| SymbolLink {
+                from: self.graph[edge.source()].clone(),
+                to: self.graph[edge.target()].clone(),
+                edge: edge.weight().clone(),
+            })
+            .collect();
+        // Links from definitions are more telling than those from top-level code like imports
+        links.sort_by_key(|link| {
+            let line = match link.edge {
+                SymbolEdgeType::Calls { line } | SymbolEdgeType::References { line } => line,
+                SymbolEdgeType::Contains => link.to.line,
+            };
+            (link.from.kind == SymbolKind::Module, line)
+        });
+        links
+    }
+
+    /// Type containing a method, or definition containing a nested one
+    fn container_of(&self, symbol: NodeIndex) -> Option<NodeIndex> {
+        self.graph
+            .edges_directed(symbol, Direction::Incoming)
+            .find(|edge| *edge.weight() == SymbolEdgeType::Contains)
+            .map(|edge| edge.source())
+    }
+}
+
+struct SourceFile {
+    path: PathBuf,
+    symbols: FileSymbols,
+    imports: Vec<PathBuf>,
+}
+
+/// Builder for constructing symbol graphs
+#[derive(Default)]
+pub struct SymbolGraphBuilder {
+    files: Vec<SourceFile>,
+    analyzers: HashMap<String, Option<Box<dyn LanguageAnalyzer>>>,
+}
+
+impl SymbolGraphBuilder {
+    /// Create a new SymbolGraphBuilder
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Extract the symbols of a file with the query engine of its language
+    ///
+    /// `imports` are the files the file imports, used to pick between definitions with the
+    /// same name. Returns false if the language has no query engine.
+    pub fn add_file(
+        &mut self,
+        path: &Path,
+        content: &str,
+        imports: &[PathBuf],
+    ) -> Result<bool, ContextCreatorError> {
+        let extension = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .unwrap_or("")
+            .to_string();
+        if !self.analyzers.contains_key(&extension) {
+            let analyzer = get_analyzer_for_file(path)?;
+            self.analyzers.insert(extension.clone(), analyzer);
+        }
+        let Some(query_engine) = self.analyzers[&extension]
+            .as_ref()
+            .and_then(|analyzer| analyzer.query_engine())
+        else {
+            return Ok(false);
+        };
+
+        let symbols = query_engine.analyze_symbols(content)?;
+        self.add_symbols(path, symbols, imports);
+        Ok(true)
+    }
+
+    /// Add symbols that were already extracted from a file
+    pub fn add_symbols(&mut self, path: &Path, symbols: FileSymbols, imports: &[PathBuf]) {
+        self.files.push(SourceFile {
+            path: path.to_path_buf(),
+            symbols,
+            imports: imports.to_vec(),
+        });
+    }
+
+    /// Build the graph, resolving references to the definitions they name
+    pub fn build(self) -> SymbolGraph {
+        let mut symbol_graph = SymbolGraph::default();
+
+        // Nodes for every definition, and containment edges within each file
+        let mut definition_nodes = Vec::with_capacity(self.files.len());
+        for file in &self.files {
+            let nodes: Vec<NodeIndex> = file
+                .symbols
+                .definitions
+                .iter()
+                .map(|definition| {
+                    let index = symbol_graph.graph.add_node(SymbolNode {
+                        name: definition.name.clone(),
+                        kind: definition.kind,
+                        path: file.path.clone(),
+                        byte_range: definition.byte_range.clone(),
+                        line: definition.line,
+                        is_exported: definition.is_exported,
+                    });
+                    symbol_graph
+                        .symbols_by_name
+                        .entry(definition.name.clone())
+                        .or_default()
+                        .push(index);
+                    index
+                })
+                .collect();
+
+            for (definition, &index) in file.symbols.definitions.iter().zip(&nodes) {
+                if let Some(&container) = definition.container.and_then(|c| nodes.get(c)) {
+                    symbol_graph
+                        .graph
+                        .add_edge(container, index, SymbolEdgeType::Contains);
+                }
+            }
+
+            symbol_graph
+                .file_symbols
+                .entry(file.path.clone())
+                .or_default()
+                .extend(&nodes);
+            definition_nodes.push(nodes);
+        }
+
+        // Reference edges from the innermost enclosing symbol to each resolved definition
+        for (file, nodes) in self.files.iter().zip(&definition_nodes) {
+            let mut added = HashSet::new();
+            let mut module_node = None;
+
+            for reference in &file.symbols.references {
+                let source = match innermost_definition(&file.symbols, reference.byte_offset) {
+                    Some(local) => nodes[local],
+                    None => *module_node.get_or_insert_with(|| symbol_graph.add_module_node(file)),
+                };
+
+                for target in symbol_graph.resolve(reference, file) {
+                    if target == source || !added.insert((source, target, reference.kind)) {
+                        continue;
+                    }
+                    let edge = match reference.kind {
+                        SymbolReferenceKind::Call => SymbolEdgeType::Calls {
+                            line: reference.line,
+                        },
+                        SymbolReferenceKind::Type => SymbolEdgeType::References {
+                            line: reference.line,
+                        },
+                    };
+                    symbol_graph.graph.add_edge(source, target, edge);
+                }
+            }
+        }
+
+        symbol_graph
+    }
+}
+
+impl SymbolGraph {
+    /// Node standing for the top-level code of a file
+    fn add_module_node(&mut self, file: &SourceFile) -> NodeIndex {
+        let name = file
+            .path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let end = file
+            .symbols
+            .definitions
+            .iter()
+            .map(|definition| definition.byte_range.end)
+            .chain(
+                file.symbols
+                    .references
+                    .iter()
+                    .map(|reference| reference.byte_offset + reference.name.len()),
+            )
+            .max()
+            .unwrap_or(0);
+        let index = self.graph.add_node(SymbolNode {
+            name,
+            kind: SymbolKind::Module,
+            path: file.path.clone(),
+            byte_range: 0..end,
+            line: 1,
+            is_exported: false,
+        });
+        self.file_symbols
+            .entry(file.path.clone())
+            .or_default()
+            .push(index);
+        index
+    }
+
+    /// Definitions a reference made in `file` names
+    ///
+    /// Definitions in the same file win. Otherwise exported definitions elsewhere are narrowed
+    /// by the qualifying module or type, then by the files `file` imports.
+    fn resolve(&self, reference: &SymbolReference, file: &SourceFile) -> Vec<NodeIndex> {
+        let candidates: Vec<NodeIndex> = self
+            .symbols_named(&reference.name)
+            .iter()
+            .copied()
+            .filter(|&index| match reference.kind {
+                SymbolReferenceKind::Call => self.graph[index].kind != SymbolKind::Module,
+                SymbolReferenceKind::Type => self.graph[index].kind == SymbolKind::Type,
+            })
+            .collect();
+
+        let local: Vec<NodeIndex> = candidates
+            .iter()
+            .copied()
+            .filter(|&index| self.graph[index].path == file.path)
+            .collect();
+        if !local.is_empty() {
+            return local;
+        }
+
+        let remote: Vec<NodeIndex> = candidates
+            .into_iter()
+            .filter(|&index| self.graph[index].is_exported)
+            .collect();
+
+        if let Some(qualifier) = reference.module.as_deref().and_then(last_path_segment) {
+            let qualified: Vec<NodeIndex> = remote
+                .iter()
+                .copied()
+                .filter(|&index| {
+                    let node = &self.graph[index];
+                    node.path.file_stem().is_some_and(|stem| stem == qualifier)
+                        || self
+                            .container_of(index)
+                            .is_some_and(|container| self.graph[container].name == qualifier)
+                })
+                .collect();
+            if !qualified.is_empty() {
+                return qualified;
+            }
+        }
+
+        let imported: Vec<NodeIndex> = remote
+            .iter()
+            .copied()
+            .filter(|&index| file.imports.contains(&self.graph[index].path))
+            .collect();
+        if !imported.is_empty() {
+            return imported;
+        }
+
+        if remote.len() <= MAX_AMBIGUOUS_TARGETS {
+            remote
+        } else {
+            Vec::new()
+        }
+    }
+}
+
+/// Index of the innermost definition containing a byte offset
+fn innermost_definition(symbols: &FileSymbols, byte_offset: usize) -> Option<usize> {
+    symbols
+        .definitions
+        .iter()
+        .enumerate()
+        .filter(|(_, definition)| definition.byte_range.contains(&byte_offset))
+        .min_by_key(|(_, definition)| definition.byte_range.len())
+        .map(|(index, _)| index)
+}
+
+/// Last segment of a qualifier like `crate::db`, `models.user`, or `lib/db`
+fn last_path_segment(qualifier: &str) -> Option<&str> {
+    qualifier
+        .rsplit([':', '.', '/', '\\'])
+        .find(|segment| !segment.is_empty())
+}
+
+#[cfg(test)]
+#[path = "symbol_graph_tests.rs"]
+mod tests;
@@ -0,0 +1,176 @@
+//! Tests for the symbol graph
+//!
+//! These tests verify that symbols are extracted with the byte ranges of their definitions and
+//! that calls, type references, and containment link the right symbols across files.
+
+use crate::core::semantic::analyzer::SymbolKind;
+use crate::core::semantic::dependency_types::SymbolEdgeType;
+use crate::core::semantic::symbol_graph::{SymbolGraph, SymbolGraphBuilder};
+use std::path::{Path, PathBuf};
+
+fn build_graph(files: &[(&str, &str, &[&str])]) -> SymbolGraph {
+    let mut builder = SymbolGraphBuilder::new();
+    for (path, content, imports) in files {
+        let imports: Vec<PathBuf> = imports.iter().map(PathBuf::from).collect();
+        assert!(builder
+            .add_file(Path::new(path), content, &imports)
+            .unwrap());
+    }
+    builder.build()
+}
+
+fn find<'a>(
+    graph: &'a SymbolGraph,
+    path: &str,
+    name: &str,
+) -> &'a crate::core::semantic::dependency_types::SymbolNode {
+    let index = graph
+        .symbols_in_file(Path::new(path))
+        .iter()
+        .copied()
+        .find(|&index| graph.symbol(index).name == name)
+        .unwrap_or_else(|| panic!("{name} not found in {path}"));
+    graph.symbol(index)
+}
+
+const MODELS_RS: &str = r#"pub struct User {
+    pub name: String,
+}
+
+impl User {
+    pub fn new(name: String) -> Self {
+        Self { name }
+    }
+}
+"#;
+
+const DB_RS: &str = r#"use crate::models::User;
+
+pub fn save(user: &User) -> bool {
+    validate(user)
+}
+
+fn validate(user: &User) -> bool {
+    !user.name.is_empty()
+}
+"#;
+
+const API_RS: &str = r#"use crate::db::save;
+use crate::models::User;
+
+pub fn handle(name: String) -> bool {
+    let user = User::new(name);
+    save(&user)
+}
+"#;
+
+#[test]
+fn test_symbols_have_definition_byte_ranges() {
+    let graph = build_graph(&[("src/models.rs", MODELS_RS, &[])]);
+
+    let user = find(&graph, "src/models.rs", "User");
+    assert_eq!(user.kind, SymbolKind::Type);
+    assert!(MODELS_RS[user.byte_range.clone()].starts_with("pub struct User"));
+    assert_eq!(user.line, 1);
+
+    let new = find(&graph, "src/models.rs", "new");
+    assert_eq!(new.kind, SymbolKind::Method);
+    assert!(MODELS_RS[new.byte_range.clone()].starts_with("pub fn new"));
+    assert!(MODELS_RS[new.byte_range.clone()].ends_with('}'));
+    assert_eq!(new.line, 6);
+}
+
+#[test]
+fn test_methods_are_contained_in_their_type() {
+    let graph = build_graph(&[("src/models.rs", MODELS_RS, &[])]);
+
+    let user = graph.symbols_named("User")[0];
+    let contained: Vec<&str> = graph
+        .dependencies(user)
+        .into_iter()
+        .filter(|(_, edge)| **edge == SymbolEdgeType::Contains)
+        .map(|(index, _)| graph.symbol(index).name.as_str())
+        .collect();
+    assert_eq!(contained, vec!["new"]);
+}
+
+#[test]
+fn test_calls_and_type_references_link_symbols_across_files() {
+    let graph = build_graph(&[
+        ("src/models.rs", MODELS_RS, &[]),
+        ("src/db.rs", DB_RS, &["src/models.rs"]),
+        ("src/api.rs", API_RS, &["src/db.rs", "src/models.rs"]),
+    ]);
+
+    let links = graph.links_between(Path::new("src/api.rs"), Path::new("src/db.rs"));
+    assert_eq!(links.len(), 1);
+    assert_eq!(links[0].from.name, "handle");
+    assert_eq!(links[0].to.name, "save");
+    assert_eq!(links[0].edge, SymbolEdgeType::Calls { line: 6 });
+    assert_eq!(
+        links[0].to_string(),
+        "function `handle` in api.rs calls function `save` in db.rs (line 6)"
+    );
+
+    // User::new resolves through the qualifying type
+    let links = graph.links_between(Path::new("src/api.rs"), Path::new("src/models.rs"));
+    assert!(links
+        .iter()
+        .any(|link| link.to.name == "new" && matches!(link.edge, SymbolEdgeType::Calls { .. })));
+
+    let links = graph.links_between(Path::new("src/db.rs"), Path::new("src/models.rs"));
+    assert!(links.iter().any(|link| link.from.name == "save"
+        && link.to.name == "User"
+        && matches!(link.edge, SymbolEdgeType::References { .. })));
+
+    // Calls within a file stay within it
+    let validate = graph.symbols_named("validate")[0];
+    let callers: Vec<&str> = graph
+        .dependents(validate)
+        .into_iter()
+        .map(|(index, _)| graph.symbol(index).name.as_str())
+        .collect();
+    assert_eq!(callers, vec!["save"]);
+}
+
+#[test]
+fn test_imports_pick_between_definitions_with_the_same_name() {
+    let graph = build_graph(&[
+        ("app/csv_export.py", "def process(rows):\n    return rows\n", &[]),
+        ("app/json_export.py", "def process(rows):\n    return rows\n", &[]),
+        (
+            "app/main.py",
+            "from app.json_export import process\n\nclass Job:\n    def run(self):\n        return process([])\n\nJob().run()\n",
+            &["app/json_export.py"],
+        ),
+    ]);
+
+    let run = find(&graph, "app/main.py", "run");
+    assert_eq!(run.kind, SymbolKind::Method);
+
+    let links = graph.links_between(Path::new("app/main.py"), Path::new("app/json_export.py"));
+    assert_eq!(links.len(), 1);
+    assert_eq!(links[0].from.name, "run");
+    assert!(graph
+        .links_between(Path::new("app/main.py"), Path::new("app/csv_export.py"))
+        .is_empty());
+
+    // Top-level code calls from the module itself
+    let job = graph.symbols_named("Job")[0];
+    assert!(graph
+        .dependents(job)
+        .iter()
+        .any(|(index, _)| graph.symbol(*index).kind == SymbolKind::Module));
+}
+
+#[test]
+fn test_symbol_at_finds_innermost_definition() {
+    let graph = build_graph(&[("src/models.rs", MODELS_RS, &[])]);
+
+    let offset = MODELS_RS.find("Self { name }").unwrap();
+    let symbol = graph.symbol_at(Path::new("src/models.rs"), offset).unwrap();
+    assert_eq!(graph.symbol(symbol).name, "new");
+
+    assert!(graph.symbol_at(Path::new("src/models.rs"), 0).is_some());
+    assert!(graph.symbol_at(Path::new("src/other.rs"), 0).is_none());
+}
@@ -8,6 +8,7 @@
 //! This module now serves as a thin coordination layer that maintains backward compatibility.
 
 use crate::core::cache::FileCache;
+use crate::core::project_root::detect_project_root;
 use crate::core::semantic::cycle_detector::{CycleResolution, TarjanCycleDetector};
 use crate::core::semantic::graph_builder::GraphBuilder;
 use crate::core::semantic::graph_traverser::GraphTraverser;
@@ -55,6 +56,7 @@ pub fn perform_semantic_analysis_graph_with_root(
         trace_imports: semantic_options.trace_imports || semantic_options.include_callers,
         include_types: semantic_options.include_types,
         include_functions: semantic_options.include_callers,
+        include_dirs: config.include_dirs.clone(),
     };
 
     let file_paths: Vec<_> = files.iter().map(|f| f.path.clone()).collect();
@@ -148,58 +150,6 @@ pub fn perform_semantic_analysis_graph_with_root(
     Ok(())
 }
 
-/// Detect the project root directory
-fn detect_project_root(start_path: &std::path::Path) -> PathBuf {
-    let mut current = start_path.parent().unwrap_or(start_path);
-
-    // First try to find git root
-    loop {
-        if current.join(".git").exists() {
-            return absolute_project_root(current);
-        }
-        if let Some(parent) = current.parent() {
-            current = parent;
-        } else {
-            break;
-        }
-    }
-
-    // Fallback: Look for common project markers
-    current = start_path.parent().unwrap_or(start_path);
-    loop {
-        if current.join("Cargo.toml").exists()
-            || current.join("package.json").exists()
-            || current.join("pyproject.toml").exists()
-            || current.join("setup.py").exists()
-            || current.join("go.mod").exists()
-            || current.join("Package.swift").exists()
-        {
-            return absolute_project_root(current);
-        }
-
-        if let Some(parent) = current.parent() {
-            current = parent;
-        } else {
-            break;
-        }
-    }
-
-    // Ultimate fallback
-    absolute_project_root(start_path.parent().unwrap_or(start_path))
-}
-
-fn absolute_project_root(path: &std::path::Path) -> PathBuf {
-    path.canonicalize().unwrap_or_else(|_| {
-        if path.is_absolute() {
-            path.to_path_buf()
-        } else {
-            std::env::current_dir()
-                .map(|current_dir| current_dir.join(path))
-                .unwrap_or_else(|_| path.to_path_buf())
-        }
-    })
-}
-
 /// Apply import relationships from analysis results
 fn apply_import_relationships(
     files: &mut [FileInfo],
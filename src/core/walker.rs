@@ -10,6 +10,9 @@ use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tracing::warn;
 
+/// Hidden directories whose workflows and actions run the project's scripts
+pub const CI_WORKFLOW_DIRS: &[&str] = &[".github/workflows", ".github/actions"];
+
 /// Compiled priority rule for efficient pattern matching
 ///
 /// This struct represents a custom priority rule that has been compiled from
@@ -187,6 +190,9 @@ impl FileInfo {
             FileType::Julia => "Julia",
             FileType::Elixir => "Elixir",
             FileType::Elm => "Elm",
+            FileType::Vue => "Vue",
+            FileType::Svelte => "Svelte",
+            FileType::Astro => "Astro",
             FileType::Markdown => "Markdown",
             FileType::Json => "JSON",
             FileType::Yaml => "YAML",
@@ -253,6 +259,36 @@ pub fn walk_directory(root: &Path, options: WalkOptions) -> Result<Vec<FileInfo>
     }
 }
 
+/// Walk the CI workflow and action directories under `root`, which a walk skipping hidden files
+/// leaves out. Semantic analysis adds them to the project so workflows link to the scripts they
+/// run; they are not part of the output unless something pulls them in.
+pub fn walk_ci_workflow_files(root: &Path, options: &WalkOptions) -> Result<Vec<FileInfo>> {
+    if options.include_hidden || !root.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let mut options = options.clone();
+    options.include_patterns.clear();
+
+    let mut files = Vec::new();
+    for dir in CI_WORKFLOW_DIRS {
+        let ci_dir = root.join(dir);
+        if !ci_dir.is_dir() {
+            continue;
+        }
+        for mut file in walk_directory(&ci_dir, options.clone())? {
+            file.relative_path = Path::new(dir).join(&file.relative_path);
+            file.priority = calculate_priority(
+                &file.file_type,
+                &file.relative_path,
+                &options.custom_priorities,
+            );
+            files.push(file);
+        }
+    }
+    Ok(files)
+}
+
 /// Sanitize include patterns to prevent security issues
 pub fn sanitize_pattern(pattern: &str) -> Result<String> {
     // Length limit to prevent resource exhaustion
@@ -548,6 +584,9 @@ fn calculate_base_priority(file_type: &FileType, relative_path: &Path) -> f32 {
         FileType::Julia => 0.8,
         FileType::Elixir => 0.8,
         FileType::Elm => 0.75,
+        FileType::Vue => 0.9,
+        FileType::Svelte => 0.9,
+        FileType::Astro => 0.9,
         FileType::Markdown => 0.6,
         FileType::Json => 0.5,
         FileType::Yaml => 0.5,
@@ -813,6 +852,31 @@ mod tests {
             .any(|f| f.relative_path.as_path() == Path::new("src/utils/helpers.rs")));
     }
 
+    #[test]
+    fn test_walk_skips_ci_workflows_unless_asked_for() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+
+        fs::create_dir_all(root.join(".github/workflows")).unwrap();
+        fs::create_dir_all(root.join(".github/ISSUE_TEMPLATE")).unwrap();
+        File::create(root.join("main.rs")).unwrap();
+        File::create(root.join(".github/workflows/ci.yml")).unwrap();
+        File::create(root.join(".github/workflows/.draft.yml")).unwrap();
+        File::create(root.join(".github/ISSUE_TEMPLATE/bug.md")).unwrap();
+
+        let files = walk_directory(root, WalkOptions::default()).unwrap();
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].relative_path, PathBuf::from("main.rs"));
+
+        let files = walk_ci_workflow_files(root, &WalkOptions::default()).unwrap();
+        assert_eq!(files.len(), 1);
+        assert_eq!(
+            files[0].relative_path,
+            PathBuf::from(".github/workflows/ci.yml")
+        );
+        assert_eq!(files[0].path, root.join(".github/workflows/ci.yml"));
+    }
+
     #[test]
     fn test_priority_edge_cases() {
         // Test priority calculation for edge cases
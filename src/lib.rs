@@ -32,6 +32,9 @@ pub fn run(mut config: Config) -> Result<()> {
     // This ensures mutual exclusivity checks work correctly
     config.validate()?;
 
+    // Register language packs before any file is analyzed
+    core::semantic::language_pack::load_configured_language_packs(&config)?;
+
     // Handle commands if present
     match &config.command {
         Some(cli::Commands::Search { .. }) => return commands::run_search(config),
@@ -230,10 +233,28 @@ fn process_directory(
             info!("Analyzing semantic dependencies...");
         }
 
+        // Workflows named by the include patterns are starting points, though hidden
+        if !walk_options.include_patterns.is_empty() {
+            let patterns: Vec<glob::Pattern> = walk_options
+                .include_patterns
+                .iter()
+                .filter_map(|pattern| glob::Pattern::new(pattern).ok())
+                .collect();
+            files.extend(
+                core::walker::walk_ci_workflow_files(path, &walk_options)?
+                    .into_iter()
+                    .filter(|file| {
+                        patterns
+                            .iter()
+                            .any(|pattern| pattern.matches_path(&file.relative_path))
+                    }),
+            );
+        }
+
         // Caller discovery needs a project-wide index. Import and type expansion can
         // stay on-demand, which keeps single-file runs on real projects responsive.
         let mut initial_files_map = std::collections::HashMap::new();
-        let files_map = if config.include_callers {
+        let (files_map, mut inclusions) = if config.include_callers {
             let project_analysis = core::project_analyzer::ProjectAnalysis::analyze_project(
                 path,
                 &walk_options,
@@ -249,19 +270,25 @@ fn process_directory(
                 }
             }
 
-            core::file_expander::expand_file_list_with_context(
+            core::file_expander::expand_file_list_with_inclusions(
                 initial_files_map,
                 config,
                 &cache,
                 &walk_options,
-                &project_analysis.file_map,
+                Some(&project_analysis.file_map),
             )?
         } else {
             for file in files {
                 initial_files_map.insert(file.path.clone(), file);
             }
 
-            core::file_expander::expand_file_list(initial_files_map, config, &cache, &walk_options)?
+            core::file_expander::expand_file_list_with_inclusions(
+                initial_files_map,
+                config,
+                &cache,
+                &walk_options,
+                None,
+            )?
         };
 
         // Expand file list based on semantic relationships.
@@ -269,6 +296,16 @@ fn process_directory(
             info!("Expanding file list based on semantic relationships...");
         }
 
+        if config.verbose > 0 {
+            core::file_expander::explain_inclusions(&mut inclusions, &files_map, &cache);
+            for inclusion in &inclusions {
+                let path = files_map
+                    .get(&inclusion.path)
+                    .map_or(&inclusion.path, |file| &file.relative_path);
+                debug!("Included {}: {}", path.display(), inclusion.reason());
+            }
+        }
+
         // Convert back to Vec<FileInfo>
         files = files_map.into_values().collect();
 
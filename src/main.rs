@@ -28,6 +28,9 @@ fn run_main() -> Result<()> {
         // Initialize logging for MCP server
         context_creator::logging::init_logging(&config)?;
 
+        // Register language packs for the semantic analysis of requests
+        context_creator::core::semantic::language_pack::load_configured_language_packs(&config)?;
+
         // Start MCP server - this runs forever
         run_mcp_server(config)?;
         return Ok(());
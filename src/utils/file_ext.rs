@@ -27,6 +27,9 @@ pub enum FileType {
     Julia,
     Elixir,
     Elm,
+    Vue,
+    Svelte,
+    Astro,
 
     // Data formats
     Markdown,
@@ -73,6 +76,9 @@ impl FileType {
             "jl" => FileType::Julia,
             "ex" | "exs" => FileType::Elixir,
             "elm" => FileType::Elm,
+            "vue" => FileType::Vue,
+            "svelte" => FileType::Svelte,
+            "astro" => FileType::Astro,
             "md" | "markdown" => FileType::Markdown,
             "json" => FileType::Json,
             "yaml" | "yml" => FileType::Yaml,
@@ -150,6 +156,7 @@ pub fn get_language_from_extension(path: &Path) -> &'static str {
         "less" => "less",
         "vue" => "vue",
         "svelte" => "svelte",
+        "astro" => "astro",
 
         // Data formats
         "json" => "json",
@@ -3340,3 +3340,4501 @@ enum ProfileState {
     assert!(paths.contains(&"App/Services/ProfileServices.swift"));
     assert!(!paths.contains(&"App/Unused/Unused.swift"));
 }
+
+fn create_maven_java_project(project_dir: &Path) {
+    fs::write(
+        project_dir.join("pom.xml"),
+        "<project><artifactId>billing</artifactId></project>\n",
+    )
+    .unwrap();
+
+    for dir in [
+        "src/main/java/com/acme/billing",
+        "src/main/java/com/acme/model",
+        "src/main/java/com/acme/util",
+        "src/test/java/com/acme/billing",
+    ] {
+        fs::create_dir_all(project_dir.join(dir)).unwrap();
+    }
+
+    fs::write(
+        project_dir.join("src/main/java/com/acme/billing/InvoiceService.java"),
+        r#"package com.acme.billing;
+
+import com.acme.model.*;
+import static com.acme.util.Money.format;
+import java.util.List;
+
+public class InvoiceService extends AbstractService implements Auditable {
+    private final List<Invoice> invoices;
+
+    public InvoiceService(List<Invoice> invoices) {
+        this.invoices = invoices;
+    }
+
+    @Override
+    public String describe(Customer customer) {
+        return customer.getName() + " owes " + format(invoices.size());
+    }
+}
+"#,
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("src/main/java/com/acme/billing/AbstractService.java"),
+        "package com.acme.billing;\n\npublic abstract class AbstractService {}\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("src/main/java/com/acme/billing/Auditable.java"),
+        "package com.acme.billing;\n\npublic interface Auditable {\n    String describe(com.acme.model.Customer customer);\n}\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("src/main/java/com/acme/model/Invoice.java"),
+        "package com.acme.model;\n\npublic record Invoice(long cents) {}\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("src/main/java/com/acme/model/Customer.java"),
+        r#"package com.acme.model;
+
+public class Customer {
+    private final String name;
+
+    public Customer(String name) {
+        this.name = name;
+    }
+
+    public String getName() {
+        return name;
+    }
+}
+"#,
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("src/main/java/com/acme/model/Unused.java"),
+        "package com.acme.model;\n\npublic class Unused {}\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("src/main/java/com/acme/util/Money.java"),
+        r#"package com.acme.util;
+
+public final class Money {
+    public static String format(long cents) {
+        return "$" + cents;
+    }
+}
+"#,
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("src/test/java/com/acme/billing/InvoiceServiceTest.java"),
+        r#"package com.acme.billing;
+
+import com.acme.model.Customer;
+
+class InvoiceServiceTest {
+    void describesCustomer() {
+        new InvoiceService(java.util.List.of()).describe(new Customer("ada"));
+    }
+}
+"#,
+    )
+    .unwrap();
+}
+
+/// Java import tracing follows single-class, on-demand, and static imports
+/// through Maven `src/main/java` roots, without pulling in unused package members.
+#[test]
+fn test_e2e_java_trace_imports_resolves_maven_source_roots() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path();
+    create_maven_java_project(project_dir);
+
+    let output_file = temp_dir.path().join("java_trace_imports.paths");
+    let mut cmd = Command::cargo_bin("context-creator").unwrap();
+    cmd.current_dir(project_dir)
+        .arg("--include")
+        .arg("src/main/java/com/acme/billing/InvoiceService.java")
+        .arg("--trace-imports")
+        .arg("--style")
+        .arg("paths")
+        .arg("--output-file")
+        .arg(&output_file);
+
+    cmd.assert().success();
+    let content = fs::read_to_string(&output_file).unwrap();
+    let paths = content.lines().collect::<Vec<_>>();
+
+    assert!(paths.contains(&"src/main/java/com/acme/billing/InvoiceService.java"));
+    assert!(paths.contains(&"src/main/java/com/acme/model/Invoice.java"));
+    assert!(paths.contains(&"src/main/java/com/acme/model/Customer.java"));
+    assert!(paths.contains(&"src/main/java/com/acme/util/Money.java"));
+    assert!(!paths.contains(&"src/main/java/com/acme/model/Unused.java"));
+}
+
+/// Java type expansion resolves superclasses and interfaces from the same
+/// package, including from tests under `src/test/java`.
+#[test]
+fn test_e2e_java_include_types_resolves_same_package_supertypes() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path();
+    create_maven_java_project(project_dir);
+
+    let output_file = temp_dir.path().join("java_include_types.paths");
+    let mut cmd = Command::cargo_bin("context-creator").unwrap();
+    cmd.current_dir(project_dir)
+        .arg("--include")
+        .arg("src/main/java/com/acme/billing/InvoiceService.java")
+        .arg("--include-types")
+        .arg("--style")
+        .arg("paths")
+        .arg("--output-file")
+        .arg(&output_file);
+
+    cmd.assert().success();
+    let content = fs::read_to_string(&output_file).unwrap();
+    let paths = content.lines().collect::<Vec<_>>();
+
+    assert!(paths.contains(&"src/main/java/com/acme/billing/AbstractService.java"));
+    assert!(paths.contains(&"src/main/java/com/acme/billing/Auditable.java"));
+    assert!(!paths.contains(&"src/main/java/com/acme/model/Unused.java"));
+}
+
+/// Java caller expansion finds callers in the same package across the
+/// `src/main/java` and `src/test/java` roots.
+#[test]
+fn test_e2e_java_include_callers_finds_test_sources() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path();
+    create_maven_java_project(project_dir);
+
+    let output_file = temp_dir.path().join("java_include_callers.paths");
+    let mut cmd = Command::cargo_bin("context-creator").unwrap();
+    cmd.current_dir(project_dir)
+        .arg("--include")
+        .arg("src/main/java/com/acme/billing/InvoiceService.java")
+        .arg("--include-callers")
+        .arg("--style")
+        .arg("paths")
+        .arg("--output-file")
+        .arg(&output_file);
+
+    cmd.assert().success();
+    let content = fs::read_to_string(&output_file).unwrap();
+    let paths = content.lines().collect::<Vec<_>>();
+
+    assert!(paths.contains(&"src/test/java/com/acme/billing/InvoiceServiceTest.java"));
+    assert!(!paths.contains(&"src/main/java/com/acme/model/Customer.java"));
+}
+
+fn create_c_firmware_project(project_dir: &Path) {
+    for dir in [
+        "app",
+        "drivers/include/drivers",
+        "drivers/src",
+        "common",
+        "unused",
+    ] {
+        fs::create_dir_all(project_dir.join(dir)).unwrap();
+    }
+
+    fs::write(
+        project_dir.join("compile_commands.json"),
+        r#"[
+  {"directory": ".", "file": "app/main.c", "command": "cc -Idrivers/include -I common -c app/main.c"},
+  {"directory": ".", "file": "drivers/src/uart.c", "arguments": ["cc", "-Idrivers/include", "-c", "drivers/src/uart.c"]}
+]
+"#,
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("app/main.c"),
+        r#"#include <stdint.h>
+#include "drivers/uart.h"
+#include "board.h"
+
+int main(void) {
+    uart_config_t cfg = { .baud = BOARD_BAUD };
+    uart_init(&cfg);
+    return 0;
+}
+"#,
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("drivers/include/drivers/uart.h"),
+        r#"#ifndef DRIVERS_UART_H
+#define DRIVERS_UART_H
+
+#include <stdint.h>
+
+typedef struct {
+    uint32_t baud;
+} uart_config_t;
+
+void uart_init(const uart_config_t *cfg);
+
+#endif
+"#,
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("drivers/src/uart.c"),
+        r#"#include "drivers/uart.h"
+
+static int configured;
+
+void uart_init(const uart_config_t *cfg) {
+    configured = cfg->baud > 0;
+}
+"#,
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("common/board.h"),
+        "#define BOARD_BAUD 115200\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("unused/unused.c"),
+        "int unused(void) { return 0; }\n",
+    )
+    .unwrap();
+}
+
+/// C import tracing resolves headers through `-I` paths from
+/// `compile_commands.json` and links each header to its implementation file.
+#[test]
+fn test_e2e_c_trace_imports_uses_compile_commands_include_paths() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path();
+    create_c_firmware_project(project_dir);
+
+    let output_file = temp_dir.path().join("c_trace_imports.paths");
+    let mut cmd = Command::cargo_bin("context-creator").unwrap();
+    cmd.current_dir(project_dir)
+        .arg("--include")
+        .arg("app/main.c")
+        .arg("--trace-imports")
+        .arg("--style")
+        .arg("paths")
+        .arg("--output-file")
+        .arg(&output_file);
+
+    cmd.assert().success();
+    let content = fs::read_to_string(&output_file).unwrap();
+    let paths = content.lines().collect::<Vec<_>>();
+
+    assert!(paths.contains(&"app/main.c"));
+    assert!(paths.contains(&"drivers/include/drivers/uart.h"));
+    assert!(paths.contains(&"drivers/src/uart.c"));
+    assert!(paths.contains(&"common/board.h"));
+    assert!(!paths.contains(&"unused/unused.c"));
+}
+
+/// A header reached through a type reference brings its implementation file too
+#[test]
+fn test_e2e_c_include_types_adds_header_implementation() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path();
+    create_c_firmware_project(project_dir);
+
+    let output_file = temp_dir.path().join("c_include_types.paths");
+    let mut cmd = Command::cargo_bin("context-creator").unwrap();
+    cmd.current_dir(project_dir)
+        .arg("--include")
+        .arg("app/main.c")
+        .arg("--include-types")
+        .arg("--style")
+        .arg("paths")
+        .arg("--output-file")
+        .arg(&output_file);
+
+    cmd.assert().success();
+    let content = fs::read_to_string(&output_file).unwrap();
+    let paths = content.lines().collect::<Vec<_>>();
+
+    assert!(
+        paths.contains(&"drivers/include/drivers/uart.h"),
+        "{paths:?}"
+    );
+    assert!(paths.contains(&"drivers/src/uart.c"), "{paths:?}");
+    assert!(!paths.contains(&"unused/unused.c"));
+}
+
+/// Headers outside the compilation database are found through `--include-dir`
+#[test]
+fn test_e2e_c_trace_imports_searches_configured_include_dirs() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path();
+    create_c_firmware_project(project_dir);
+    fs::create_dir_all(project_dir.join("hal/include")).unwrap();
+    fs::write(
+        project_dir.join("hal/include/hal.h"),
+        "void hal_init(void);\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("unused/tool.c"),
+        "#include \"hal.h\"\n\nint main(void) { hal_init(); return 0; }\n",
+    )
+    .unwrap();
+
+    let output_file = temp_dir.path().join("c_include_dirs.paths");
+    let mut cmd = Command::cargo_bin("context-creator").unwrap();
+    cmd.current_dir(project_dir)
+        .arg("--include")
+        .arg("unused/tool.c")
+        .arg("--trace-imports")
+        .arg("--include-dir")
+        .arg("hal/include")
+        .arg("--style")
+        .arg("paths")
+        .arg("--output-file")
+        .arg(&output_file);
+
+    cmd.assert().success();
+    let content = fs::read_to_string(&output_file).unwrap();
+    let paths = content.lines().collect::<Vec<_>>();
+
+    assert!(paths.contains(&"unused/tool.c"));
+    assert!(paths.contains(&"hal/include/hal.h"));
+}
+
+/// C caller expansion finds translation units that call a function through
+/// the header declaring it.
+#[test]
+fn test_e2e_c_include_callers_follows_header_declarations() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path();
+    create_c_firmware_project(project_dir);
+
+    let output_file = temp_dir.path().join("c_include_callers.paths");
+    let mut cmd = Command::cargo_bin("context-creator").unwrap();
+    cmd.current_dir(project_dir)
+        .arg("--include")
+        .arg("drivers/src/uart.c")
+        .arg("--include-callers")
+        .arg("--style")
+        .arg("paths")
+        .arg("--output-file")
+        .arg(&output_file);
+
+    cmd.assert().success();
+    let content = fs::read_to_string(&output_file).unwrap();
+    let paths = content.lines().collect::<Vec<_>>();
+
+    assert!(paths.contains(&"drivers/src/uart.c"));
+    assert!(paths.contains(&"app/main.c"));
+    assert!(!paths.contains(&"unused/unused.c"));
+}
+
+/// C++ type expansion resolves namespaced classes and their base classes
+/// through `compile_flags.txt` include paths.
+#[test]
+fn test_e2e_cpp_include_types_resolves_namespaced_classes() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path();
+
+    fs::create_dir_all(project_dir.join("include/geo")).unwrap();
+    fs::create_dir_all(project_dir.join("src/geo")).unwrap();
+    fs::create_dir_all(project_dir.join("tools")).unwrap();
+    fs::write(project_dir.join("CMakeLists.txt"), "project(geo CXX)\n").unwrap();
+    fs::write(
+        project_dir.join("compile_flags.txt"),
+        "-std=c++17\n-Iinclude\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("include/geo/shape.hpp"),
+        r#"#pragma once
+
+namespace geo {
+class Shape {
+ public:
+  virtual ~Shape() = default;
+  virtual double area() const = 0;
+};
+}
+"#,
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("include/geo/circle.hpp"),
+        r#"#pragma once
+
+#include "geo/shape.hpp"
+
+namespace geo {
+class Circle : public Shape {
+ public:
+  explicit Circle(double radius);
+  double area() const override;
+
+ private:
+  double radius_;
+};
+}
+"#,
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("src/geo/circle.cpp"),
+        r#"#include "geo/circle.hpp"
+
+namespace geo {
+Circle::Circle(double radius) : radius_(radius) {}
+double Circle::area() const { return 3.14159 * radius_ * radius_; }
+}
+"#,
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("tools/report.cpp"),
+        r#"#include <memory>
+#include <vector>
+
+#include "geo/circle.hpp"
+
+int main() {
+  std::vector<geo::Circle> circles;
+  auto circle = std::make_unique<geo::Circle>(2.0);
+  return static_cast<int>(circle->area());
+}
+"#,
+    )
+    .unwrap();
+
+    let output_file = temp_dir.path().join("cpp_include_types.paths");
+    let mut cmd = Command::cargo_bin("context-creator").unwrap();
+    cmd.current_dir(project_dir)
+        .arg("--include")
+        .arg("tools/report.cpp")
+        .arg("--include-types")
+        .arg("--style")
+        .arg("paths")
+        .arg("--output-file")
+        .arg(&output_file);
+
+    cmd.assert().success();
+    let content = fs::read_to_string(&output_file).unwrap();
+    let paths = content.lines().collect::<Vec<_>>();
+
+    assert!(paths.contains(&"tools/report.cpp"));
+    assert!(paths.contains(&"include/geo/circle.hpp"));
+    assert!(paths.contains(&"include/geo/shape.hpp"));
+}
+
+fn create_csharp_solution(project_dir: &Path) {
+    for dir in [
+        "src/Shop.Api/Controllers",
+        "src/Shop.Core/Services",
+        "src/Shop.Core/Contracts",
+        "src/Legacy/Services",
+    ] {
+        fs::create_dir_all(project_dir.join(dir)).unwrap();
+    }
+
+    fs::write(
+        project_dir.join("Shop.sln"),
+        r#"Microsoft Visual Studio Solution File, Format Version 12.00
+Project("{FAE04EC0-301F-11D3-BF4B-00C04F79EFBC}") = "Shop.Api", "src\Shop.Api\Shop.Api.csproj", "{5A1F7E0C-1C2B-4B8E-9D3A-2F1E6C7B8A90}"
+EndProject
+Project("{FAE04EC0-301F-11D3-BF4B-00C04F79EFBC}") = "Shop.Core", "src\Shop.Core\Shop.Core.csproj", "{8C3D2B1A-4E5F-4A6B-8C7D-9E0F1A2B3C4D}"
+EndProject
+"#,
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("src/Shop.Api/Shop.Api.csproj"),
+        r#"<Project Sdk="Microsoft.NET.Sdk.Web">
+  <ItemGroup>
+    <ProjectReference Include="..\Shop.Core\Shop.Core.csproj" />
+  </ItemGroup>
+</Project>
+"#,
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("src/Shop.Core/Shop.Core.csproj"),
+        "<Project Sdk=\"Microsoft.NET.Sdk\">\n</Project>\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("src/Legacy/Legacy.csproj"),
+        "<Project Sdk=\"Microsoft.NET.Sdk\">\n</Project>\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("src/Shop.Api/Controllers/OrdersController.cs"),
+        r#"using System.Threading.Tasks;
+using Microsoft.AspNetCore.Mvc;
+using Shop.Core.Contracts;
+using Shop.Core.Services;
+
+namespace Shop.Api.Controllers;
+
+[ApiController]
+public class OrdersController : ControllerBase
+{
+    private readonly IOrderService _orders;
+
+    public OrdersController(IOrderService orders)
+    {
+        _orders = orders;
+    }
+
+    public async Task<OrderDto> Get(int id)
+    {
+        return await _orders.FindAsync(id);
+    }
+}
+"#,
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("src/Shop.Core/Services/IOrderService.cs"),
+        r#"using System.Threading.Tasks;
+using Shop.Core.Contracts;
+
+namespace Shop.Core.Services
+{
+    public interface IOrderService
+    {
+        Task<OrderDto> FindAsync(int id);
+    }
+}
+"#,
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("src/Shop.Core/Services/OrderService.cs"),
+        r#"using System.Threading.Tasks;
+using Shop.Core.Contracts;
+
+namespace Shop.Core.Services
+{
+    public class OrderService : IOrderService
+    {
+        public Task<OrderDto> FindAsync(int id)
+        {
+            return Task.FromResult(new OrderDto(id));
+        }
+    }
+}
+"#,
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("src/Shop.Core/Contracts/OrderDto.cs"),
+        "namespace Shop.Core.Contracts;\n\npublic record OrderDto(int Id);\n",
+    )
+    .unwrap();
+    // Same namespace and type name, but not referenced by Shop.Api
+    fs::write(
+        project_dir.join("src/Legacy/Services/IOrderService.cs"),
+        "namespace Shop.Core.Services;\n\npublic interface IOrderService\n{\n    void Find(int id);\n}\n",
+    )
+    .unwrap();
+}
+
+/// C# import tracing maps `using` namespaces to the types a controller uses,
+/// following `.csproj` project references.
+#[test]
+fn test_e2e_csharp_trace_imports_follows_project_references() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path();
+    create_csharp_solution(project_dir);
+
+    let output_file = temp_dir.path().join("csharp_trace_imports.paths");
+    let mut cmd = Command::cargo_bin("context-creator").unwrap();
+    cmd.current_dir(project_dir)
+        .arg("--include")
+        .arg("src/Shop.Api/**/*.cs")
+        .arg("--trace-imports")
+        .arg("--style")
+        .arg("paths")
+        .arg("--output-file")
+        .arg(&output_file);
+
+    cmd.assert().success();
+    let content = fs::read_to_string(&output_file).unwrap();
+    let paths = content.lines().collect::<Vec<_>>();
+
+    assert!(paths.contains(&"src/Shop.Api/Controllers/OrdersController.cs"));
+    assert!(paths.contains(&"src/Shop.Core/Services/IOrderService.cs"));
+    assert!(paths.contains(&"src/Shop.Core/Contracts/OrderDto.cs"));
+    assert!(!paths.contains(&"src/Legacy/Services/IOrderService.cs"));
+}
+
+/// C# caller expansion finds controllers that call a service through its interface.
+#[test]
+fn test_e2e_csharp_include_callers_follows_service_interfaces() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path();
+    create_csharp_solution(project_dir);
+
+    let output_file = temp_dir.path().join("csharp_include_callers.paths");
+    let mut cmd = Command::cargo_bin("context-creator").unwrap();
+    cmd.current_dir(project_dir)
+        .arg("--include")
+        .arg("src/Shop.Core/Services/OrderService.cs")
+        .arg("--include-callers")
+        .arg("--style")
+        .arg("paths")
+        .arg("--output-file")
+        .arg(&output_file);
+
+    cmd.assert().success();
+    let content = fs::read_to_string(&output_file).unwrap();
+    let paths = content.lines().collect::<Vec<_>>();
+
+    assert!(paths.contains(&"src/Shop.Core/Services/OrderService.cs"));
+    assert!(paths.contains(&"src/Shop.Api/Controllers/OrdersController.cs"));
+}
+
+fn create_rails_app(project_dir: &Path) {
+    for dir in [
+        "app/controllers/billing",
+        "app/controllers/concerns",
+        "app/mailers/billing",
+        "app/models",
+        "app/services",
+        "lib/support",
+    ] {
+        fs::create_dir_all(project_dir.join(dir)).unwrap();
+    }
+
+    fs::write(
+        project_dir.join("Gemfile"),
+        "source \"https://rubygems.org\"\n\ngem \"rails\"\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("app/controllers/billing/invoices_controller.rb"),
+        r#"require "json"
+require_relative "../../../lib/support/money"
+
+module Billing
+  class InvoicesController < ApplicationController
+    include Auditable
+
+    def show
+      invoice = Invoice.find(params[:id])
+      InvoiceMailer.receipt(invoice).deliver_later
+      render json: Money.format(invoice.total)
+    end
+  end
+end
+"#,
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("app/controllers/application_controller.rb"),
+        "class ApplicationController < ActionController::Base\nend\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("app/controllers/concerns/auditable.rb"),
+        "module Auditable\n  def audit!(label)\n    Rails.logger.info(label)\n  end\nend\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("app/mailers/billing/invoice_mailer.rb"),
+        r#"module Billing
+  class InvoiceMailer < ApplicationMailer
+    def receipt(invoice)
+      mail(to: invoice.email)
+    end
+  end
+end
+"#,
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("app/mailers/application_mailer.rb"),
+        "class ApplicationMailer < ActionMailer::Base\nend\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("app/models/invoice.rb"),
+        "class Invoice < ApplicationRecord\nend\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("app/services/unused_service.rb"),
+        "class UnusedService\n  def receipt; end\nend\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("lib/support/money.rb"),
+        "module Money\n  def self.format(cents)\n    \"$#{cents / 100.0}\"\n  end\nend\n",
+    )
+    .unwrap();
+}
+
+/// Ruby import tracing follows `require_relative` and maps autoloaded constants
+/// to files with Zeitwerk naming, including constants nested in the current module.
+#[test]
+fn test_e2e_ruby_trace_imports_follows_zeitwerk_autoloading() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path();
+    create_rails_app(project_dir);
+
+    let output_file = temp_dir.path().join("ruby_trace_imports.paths");
+    let mut cmd = Command::cargo_bin("context-creator").unwrap();
+    cmd.current_dir(project_dir)
+        .arg("--include")
+        .arg("app/controllers/billing/invoices_controller.rb")
+        .arg("--trace-imports")
+        .arg("--style")
+        .arg("paths")
+        .arg("--output-file")
+        .arg(&output_file);
+
+    cmd.assert().success();
+    let content = fs::read_to_string(&output_file).unwrap();
+    let paths = content.lines().collect::<Vec<_>>();
+
+    assert!(paths.contains(&"app/controllers/billing/invoices_controller.rb"));
+    assert!(paths.contains(&"lib/support/money.rb"));
+    assert!(paths.contains(&"app/mailers/billing/invoice_mailer.rb"));
+    assert!(paths.contains(&"app/models/invoice.rb"));
+    assert!(paths.contains(&"app/controllers/concerns/auditable.rb"));
+    assert!(paths.contains(&"app/controllers/application_controller.rb"));
+    assert!(!paths.contains(&"app/services/unused_service.rb"));
+}
+
+/// Ruby caller expansion finds controllers that call a method on an autoloaded class.
+#[test]
+fn test_e2e_ruby_include_callers_matches_constant_receivers() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path();
+    create_rails_app(project_dir);
+
+    let output_file = temp_dir.path().join("ruby_include_callers.paths");
+    let mut cmd = Command::cargo_bin("context-creator").unwrap();
+    cmd.current_dir(project_dir)
+        .arg("--include")
+        .arg("app/mailers/billing/invoice_mailer.rb")
+        .arg("--include-callers")
+        .arg("--style")
+        .arg("paths")
+        .arg("--output-file")
+        .arg(&output_file);
+
+    cmd.assert().success();
+    let content = fs::read_to_string(&output_file).unwrap();
+    let paths = content.lines().collect::<Vec<_>>();
+
+    assert!(paths.contains(&"app/mailers/billing/invoice_mailer.rb"));
+    assert!(paths.contains(&"app/controllers/billing/invoices_controller.rb"));
+    assert!(!paths.contains(&"app/services/unused_service.rb"));
+}
+
+fn create_composer_project(project_dir: &Path) {
+    for dir in [
+        "app/Http/Controllers",
+        "app/Models",
+        "app/Services",
+        "app/Contracts",
+        "bootstrap",
+        "legacy",
+        "src/Acme/Mail",
+    ] {
+        fs::create_dir_all(project_dir.join(dir)).unwrap();
+    }
+
+    fs::write(
+        project_dir.join("composer.json"),
+        r#"{
+    "name": "acme/shop",
+    "autoload": {
+        "psr-4": { "App\\": "app/" },
+        "psr-0": { "Acme_": "src/" },
+        "classmap": ["legacy/"]
+    }
+}
+"#,
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("app/Http/Controllers/InvoiceController.php"),
+        r#"<?php
+
+namespace App\Http\Controllers;
+
+use App\Models\Invoice;
+use App\Services\{BillingService, TaxService as Tax};
+use Illuminate\Http\Request;
+
+require_once __DIR__ . '/../../../bootstrap/helpers.php';
+
+class InvoiceController extends Controller
+{
+    public function __construct(private BillingService $billing) {}
+
+    public function show(Request $request, int $id): Invoice
+    {
+        $invoice = Invoice::findOrFail($id);
+        $this->billing->charge($invoice);
+        $tax = new Tax();
+        $report = new \LegacyReport();
+        $mailer = new \Acme_Mail_Sender();
+        return $invoice;
+    }
+}
+"#,
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("app/Http/Controllers/Controller.php"),
+        "<?php\n\nnamespace App\\Http\\Controllers;\n\nabstract class Controller\n{\n}\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("app/Models/Invoice.php"),
+        r#"<?php
+
+namespace App\Models;
+
+class Invoice
+{
+    public static function findOrFail(int $id): self
+    {
+        return new self();
+    }
+}
+"#,
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("app/Services/BillingService.php"),
+        r#"<?php
+
+namespace App\Services;
+
+use App\Contracts\Gateway;
+use App\Models\Invoice;
+
+class BillingService
+{
+    public function __construct(private Gateway $gateway) {}
+
+    public function charge(Invoice $invoice): void {}
+}
+"#,
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("app/Services/TaxService.php"),
+        "<?php\n\nnamespace App\\Services;\n\nclass TaxService {}\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("app/Services/UnusedService.php"),
+        "<?php\n\nnamespace App\\Services;\n\nclass UnusedService {}\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("app/Contracts/Gateway.php"),
+        "<?php\n\nnamespace App\\Contracts;\n\ninterface Gateway {}\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("bootstrap/helpers.php"),
+        "<?php\n\nfunction money(int $cents): string\n{\n    return (string) $cents;\n}\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("legacy/reports.php"),
+        "<?php\n\nclass LegacyReport {}\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("src/Acme/Mail/Sender.php"),
+        "<?php\n\nclass Acme_Mail_Sender {}\n",
+    )
+    .unwrap();
+}
+
+/// PHP import tracing resolves group uses and inline class names through the
+/// `composer.json` psr-4, psr-0, and classmap autoload rules.
+#[test]
+fn test_e2e_php_trace_imports_uses_composer_autoload() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path();
+    create_composer_project(project_dir);
+
+    let output_file = temp_dir.path().join("php_trace_imports.paths");
+    let mut cmd = Command::cargo_bin("context-creator").unwrap();
+    cmd.current_dir(project_dir)
+        .arg("--include")
+        .arg("app/Http/Controllers/InvoiceController.php")
+        .arg("--trace-imports")
+        .arg("--style")
+        .arg("paths")
+        .arg("--output-file")
+        .arg(&output_file);
+
+    cmd.assert().success();
+    let content = fs::read_to_string(&output_file).unwrap();
+    let paths = content.lines().collect::<Vec<_>>();
+
+    assert!(paths.contains(&"app/Http/Controllers/InvoiceController.php"));
+    assert!(paths.contains(&"app/Http/Controllers/Controller.php"));
+    assert!(paths.contains(&"app/Models/Invoice.php"));
+    assert!(paths.contains(&"app/Services/BillingService.php"));
+    assert!(paths.contains(&"app/Services/TaxService.php"));
+    assert!(paths.contains(&"app/Contracts/Gateway.php"));
+    assert!(paths.contains(&"bootstrap/helpers.php"));
+    assert!(paths.contains(&"legacy/reports.php"));
+    assert!(paths.contains(&"src/Acme/Mail/Sender.php"));
+    assert!(!paths.contains(&"app/Services/UnusedService.php"));
+}
+
+/// PHP type expansion follows promoted constructor properties and parameter types.
+#[test]
+fn test_e2e_php_include_types_follows_signatures_and_inheritance() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path();
+    create_composer_project(project_dir);
+
+    let output_file = temp_dir.path().join("php_include_types.paths");
+    let mut cmd = Command::cargo_bin("context-creator").unwrap();
+    cmd.current_dir(project_dir)
+        .arg("--include")
+        .arg("app/Services/BillingService.php")
+        .arg("--include-types")
+        .arg("--style")
+        .arg("paths")
+        .arg("--output-file")
+        .arg(&output_file);
+
+    cmd.assert().success();
+    let content = fs::read_to_string(&output_file).unwrap();
+    let paths = content.lines().collect::<Vec<_>>();
+
+    assert!(paths.contains(&"app/Services/BillingService.php"));
+    assert!(paths.contains(&"app/Contracts/Gateway.php"));
+    assert!(paths.contains(&"app/Models/Invoice.php"));
+    assert!(!paths.contains(&"app/Http/Controllers/InvoiceController.php"));
+}
+
+fn create_gradle_multiplatform_project(project_dir: &Path) {
+    for dir in [
+        "app/src/main/kotlin/com/acme/orders/api",
+        "app/src/main/java/com/acme/legacy",
+        "app/src/test/kotlin/com/acme/orders/api",
+        "shared/src/commonMain/kotlin/com/acme/orders/domain",
+        "shared/src/jvmMain/kotlin/com/acme/orders/domain",
+        "shared/src/commonMain/kotlin/com/acme/util",
+    ] {
+        fs::create_dir_all(project_dir.join(dir)).unwrap();
+    }
+
+    fs::write(
+        project_dir.join("settings.gradle.kts"),
+        "include(\":app\", \":shared\")\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("app/build.gradle.kts"),
+        "plugins { kotlin(\"jvm\") }\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("shared/build.gradle.kts"),
+        "plugins { kotlin(\"multiplatform\") }\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("app/src/main/kotlin/com/acme/orders/api/OrderController.kt"),
+        r#"package com.acme.orders.api
+
+import com.acme.orders.domain.*
+import com.acme.util.formatMoney as fmt
+import com.acme.legacy.LegacyAudit
+import java.time.Instant
+
+class OrderController(private val service: OrderService) {
+    fun show(id: Long): String {
+        val order: Order = service.find(id)
+        LegacyAudit.record(order.id)
+        return fmt(order.total)
+    }
+}
+"#,
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("app/src/main/kotlin/com/acme/orders/api/OrderService.kt"),
+        r#"package com.acme.orders.api
+
+import com.acme.orders.domain.Order
+import com.acme.orders.domain.Clock
+
+class OrderService {
+    fun find(id: Long): Order = Order(id, 100, Clock.now())
+}
+"#,
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("app/src/main/java/com/acme/legacy/LegacyAudit.java"),
+        r#"package com.acme.legacy;
+
+import com.acme.orders.domain.Order;
+
+public class LegacyAudit {
+    public static void record(long id) {}
+    public static void inspect(Order order) {}
+}
+"#,
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("app/src/test/kotlin/com/acme/orders/api/OrderControllerTest.kt"),
+        r#"package com.acme.orders.api
+
+class OrderControllerTest {
+    fun showsOrder() {
+        OrderController(OrderService()).show(1)
+    }
+}
+"#,
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("shared/src/commonMain/kotlin/com/acme/orders/domain/Models.kt"),
+        "package com.acme.orders.domain\n\ndata class Order(val id: Long, val total: Long, val createdAt: Long)\ndata class Unused(val x: Int)\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("shared/src/commonMain/kotlin/com/acme/orders/domain/Clock.kt"),
+        "package com.acme.orders.domain\n\nexpect object Clock { fun now(): Long }\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("shared/src/jvmMain/kotlin/com/acme/orders/domain/Clock.kt"),
+        "package com.acme.orders.domain\n\nactual object Clock { actual fun now(): Long = System.currentTimeMillis() }\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("shared/src/commonMain/kotlin/com/acme/util/Money.kt"),
+        "package com.acme.util\n\nfun formatMoney(cents: Long): String = cents.toString()\n",
+    )
+    .unwrap();
+}
+
+/// Kotlin import tracing crosses Gradle modules, multiplatform source sets,
+/// aliased top-level functions, and Java sources in the same module.
+#[test]
+fn test_e2e_kotlin_trace_imports_crosses_source_sets_and_java() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path();
+    create_gradle_multiplatform_project(project_dir);
+
+    let output_file = temp_dir.path().join("kotlin_trace_imports.paths");
+    let mut cmd = Command::cargo_bin("context-creator").unwrap();
+    cmd.current_dir(project_dir)
+        .arg("--include")
+        .arg("app/src/main/kotlin/com/acme/orders/api/OrderController.kt")
+        .arg("--trace-imports")
+        .arg("--style")
+        .arg("paths")
+        .arg("--output-file")
+        .arg(&output_file);
+
+    cmd.assert().success();
+    let content = fs::read_to_string(&output_file).unwrap();
+    let paths = content.lines().collect::<Vec<_>>();
+
+    assert!(paths.contains(&"app/src/main/kotlin/com/acme/orders/api/OrderController.kt"));
+    assert!(paths.contains(&"shared/src/commonMain/kotlin/com/acme/orders/domain/Models.kt"));
+    assert!(paths.contains(&"shared/src/commonMain/kotlin/com/acme/util/Money.kt"));
+    assert!(paths.contains(&"app/src/main/java/com/acme/legacy/LegacyAudit.java"));
+    assert!(!paths.contains(&"shared/src/commonMain/kotlin/com/acme/orders/domain/Clock.kt"));
+}
+
+/// Java sources in a mixed module import Kotlin declarations, and Kotlin
+/// callers are found in the test source set.
+#[test]
+fn test_e2e_kotlin_java_interop_and_test_source_set_callers() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path();
+    create_gradle_multiplatform_project(project_dir);
+
+    let output_file = temp_dir.path().join("kotlin_java_interop.paths");
+    let mut cmd = Command::cargo_bin("context-creator").unwrap();
+    cmd.current_dir(project_dir)
+        .arg("--include")
+        .arg("app/src/main/java/com/acme/legacy/LegacyAudit.java")
+        .arg("--trace-imports")
+        .arg("--style")
+        .arg("paths")
+        .arg("--output-file")
+        .arg(&output_file);
+
+    cmd.assert().success();
+    let content = fs::read_to_string(&output_file).unwrap();
+    let paths = content.lines().collect::<Vec<_>>();
+
+    assert!(paths.contains(&"app/src/main/java/com/acme/legacy/LegacyAudit.java"));
+    assert!(paths.contains(&"shared/src/commonMain/kotlin/com/acme/orders/domain/Models.kt"));
+
+    let output_file = temp_dir.path().join("kotlin_callers.paths");
+    let mut cmd = Command::cargo_bin("context-creator").unwrap();
+    cmd.current_dir(project_dir)
+        .arg("--include")
+        .arg("app/src/main/kotlin/com/acme/orders/api/OrderController.kt")
+        .arg("--include-callers")
+        .arg("--style")
+        .arg("paths")
+        .arg("--output-file")
+        .arg(&output_file);
+
+    cmd.assert().success();
+    let content = fs::read_to_string(&output_file).unwrap();
+    let paths = content.lines().collect::<Vec<_>>();
+
+    assert!(paths.contains(&"app/src/main/kotlin/com/acme/orders/api/OrderController.kt"));
+    assert!(paths.contains(&"app/src/test/kotlin/com/acme/orders/api/OrderControllerTest.kt"));
+}
+
+fn create_sbt_multi_project(project_dir: &Path) {
+    for dir in [
+        "project",
+        "core/src/main/scala/com/acme/core/model",
+        "core/src/main/scala/com/acme/core/util",
+        "jobs/src/main/scala/com/acme/jobs",
+        "jobs/src/test/scala/com/acme/jobs",
+        "legacy/src/main/scala/com/acme/core/model",
+    ] {
+        fs::create_dir_all(project_dir.join(dir)).unwrap();
+    }
+
+    fs::write(
+        project_dir.join("build.sbt"),
+        r#"ThisBuild / scalaVersion := "2.13.12"
+
+lazy val root = (project in file("."))
+  .aggregate(core, jobs, legacy)
+
+lazy val core = (project in file("core"))
+
+lazy val jobs = project
+  .dependsOn(core % "compile->compile;test->test")
+
+lazy val legacy = project.in(file("legacy"))
+"#,
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("project/build.properties"),
+        "sbt.version=1.9.7\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("core/src/main/scala/com/acme/core/model/Order.scala"),
+        r#"package com.acme.core.model
+
+case class Order(id: Long, total: BigDecimal)
+
+object Order {
+  def empty: Order = Order(0, 0)
+}
+"#,
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("core/src/main/scala/com/acme/core/model/Customer.scala"),
+        "package com.acme.core.model\n\ncase class Customer(id: Long)\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("core/src/main/scala/com/acme/core/util/Money.scala"),
+        r#"package com.acme.core
+package util
+
+object Money {
+  def format(amount: BigDecimal): String = amount.toString
+}
+"#,
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("core/src/main/scala/com/acme/core/util/Logging.scala"),
+        r#"package com.acme.core.util
+
+trait Logging {
+  def log(message: String): Unit = println(message)
+}
+"#,
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("legacy/src/main/scala/com/acme/core/model/Order.scala"),
+        "package com.acme.core.model\n\nclass Order\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("jobs/src/main/scala/com/acme/jobs/OrdersJob.scala"),
+        r#"package com.acme
+package jobs
+
+import org.apache.spark.sql.{SparkSession, DataFrame => DF}
+import core.model._
+import com.acme.core.util.{Money, Logging => Log}
+
+trait SparkJob {
+  def run(spark: SparkSession): DF
+}
+
+object OrdersJob extends SparkJob with Log {
+  def run(spark: SparkSession): DF = {
+    val order = Order.empty
+    log(Money.format(order.total))
+    spark.read.parquet("orders")
+  }
+}
+"#,
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("jobs/src/test/scala/com/acme/jobs/OrdersJobSpec.scala"),
+        r#"package com.acme.jobs
+
+class OrdersJobSpec {
+  def runsJob(): Unit = OrdersJob.run(null)
+}
+"#,
+    )
+    .unwrap();
+}
+
+/// Scala import tracing expands wildcard, relative, and renaming selectors and only
+/// looks inside the sbt subprojects the importing project `dependsOn`.
+#[test]
+fn test_e2e_scala_trace_imports_follows_sbt_project_graph() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path();
+    create_sbt_multi_project(project_dir);
+
+    let output_file = temp_dir.path().join("scala_trace_imports.paths");
+    let mut cmd = Command::cargo_bin("context-creator").unwrap();
+    cmd.current_dir(project_dir)
+        .arg("--include")
+        .arg("jobs/src/main/scala/com/acme/jobs/OrdersJob.scala")
+        .arg("--trace-imports")
+        .arg("--style")
+        .arg("paths")
+        .arg("--output-file")
+        .arg(&output_file);
+
+    cmd.assert().success();
+    let content = fs::read_to_string(&output_file).unwrap();
+    let paths = content.lines().collect::<Vec<_>>();
+
+    assert!(paths.contains(&"jobs/src/main/scala/com/acme/jobs/OrdersJob.scala"));
+    assert!(paths.contains(&"core/src/main/scala/com/acme/core/model/Order.scala"));
+    assert!(paths.contains(&"core/src/main/scala/com/acme/core/util/Money.scala"));
+    assert!(paths.contains(&"core/src/main/scala/com/acme/core/util/Logging.scala"));
+    assert!(!paths.contains(&"core/src/main/scala/com/acme/core/model/Customer.scala"));
+    assert!(!paths.contains(&"legacy/src/main/scala/com/acme/core/model/Order.scala"));
+}
+
+/// Scala type expansion follows `extends`/`with` parents, and callers are found in
+/// the test source set of the same subproject.
+#[test]
+fn test_e2e_scala_include_types_and_callers() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path();
+    create_sbt_multi_project(project_dir);
+
+    let output_file = temp_dir.path().join("scala_include_types.paths");
+    let mut cmd = Command::cargo_bin("context-creator").unwrap();
+    cmd.current_dir(project_dir)
+        .arg("--include")
+        .arg("jobs/src/main/scala/com/acme/jobs/OrdersJob.scala")
+        .arg("--include-types")
+        .arg("--style")
+        .arg("paths")
+        .arg("--output-file")
+        .arg(&output_file);
+
+    cmd.assert().success();
+    let content = fs::read_to_string(&output_file).unwrap();
+    let paths = content.lines().collect::<Vec<_>>();
+
+    assert!(paths.contains(&"core/src/main/scala/com/acme/core/util/Logging.scala"));
+    assert!(paths.contains(&"core/src/main/scala/com/acme/core/model/Order.scala"));
+    assert!(!paths.contains(&"legacy/src/main/scala/com/acme/core/model/Order.scala"));
+
+    let output_file = temp_dir.path().join("scala_callers.paths");
+    let mut cmd = Command::cargo_bin("context-creator").unwrap();
+    cmd.current_dir(project_dir)
+        .arg("--include")
+        .arg("jobs/src/main/scala/com/acme/jobs/OrdersJob.scala")
+        .arg("--include-callers")
+        .arg("--style")
+        .arg("paths")
+        .arg("--output-file")
+        .arg(&output_file);
+
+    cmd.assert().success();
+    let content = fs::read_to_string(&output_file).unwrap();
+    let paths = content.lines().collect::<Vec<_>>();
+
+    assert!(paths.contains(&"jobs/src/test/scala/com/acme/jobs/OrdersJobSpec.scala"));
+}
+
+/// A wildcard import of an object brings only the object's members, so a parent declared
+/// in the importing file's own package resolves to that package.
+#[test]
+fn test_e2e_scala_object_wildcard_does_not_claim_package_types() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path();
+    fs::create_dir_all(project_dir.join("src/main/scala/acme/util")).unwrap();
+    fs::create_dir_all(project_dir.join("src/main/scala/acme/jobs")).unwrap();
+    fs::write(
+        project_dir.join("build.sbt"),
+        "scalaVersion := \"2.13.12\"\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("src/main/scala/acme/util/Helpers.scala"),
+        r#"package acme.util
+
+object Helpers {
+  def banner(name: String): String = s"== $name =="
+}
+"#,
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("src/main/scala/acme/jobs/Logging2.scala"),
+        r#"package acme.jobs
+
+trait Logging2 {
+  def log(message: String): Unit = println(message)
+}
+"#,
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("src/main/scala/acme/jobs/Job7.scala"),
+        r#"package acme.jobs
+
+import acme.util.Helpers._
+
+class Job7 extends Logging2 {
+  def run(): Unit = log(banner("job7"))
+}
+"#,
+    )
+    .unwrap();
+
+    for (flag, name) in [
+        ("--include-types", "scala_types.paths"),
+        ("--trace-imports", "scala_imports.paths"),
+    ] {
+        let output_file = temp_dir.path().join(name);
+        let mut cmd = Command::cargo_bin("context-creator").unwrap();
+        cmd.current_dir(project_dir)
+            .arg("--include")
+            .arg("src/main/scala/acme/jobs/Job7.scala")
+            .arg(flag)
+            .arg("--style")
+            .arg("paths")
+            .arg("--output-file")
+            .arg(&output_file);
+
+        cmd.assert().success();
+        let content = fs::read_to_string(&output_file).unwrap();
+        let paths = content.lines().collect::<Vec<_>>();
+
+        if flag == "--include-types" {
+            assert!(
+                paths.contains(&"src/main/scala/acme/jobs/Logging2.scala"),
+                "{paths:?}"
+            );
+        } else {
+            // `banner` is a member of the imported object
+            assert!(
+                paths.contains(&"src/main/scala/acme/util/Helpers.scala"),
+                "{paths:?}"
+            );
+        }
+    }
+}
+
+fn create_flutter_workspace(project_dir: &Path) {
+    for dir in [
+        "app/lib/screens",
+        "app/lib/state",
+        "app/.dart_tool",
+        "packages/shop_core/lib/src/models",
+        "packages/shop_core/lib/src/services",
+        "packages/legacy/lib",
+    ] {
+        fs::create_dir_all(project_dir.join(dir)).unwrap();
+    }
+
+    fs::write(
+        project_dir.join("app/pubspec.yaml"),
+        r#"name: shop_app
+dependencies:
+  flutter:
+    sdk: flutter
+  shop_core:
+    path: ../packages/shop_core
+"#,
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("app/.dart_tool/package_config.json"),
+        r#"{
+  "configVersion": 2,
+  "packages": [
+    {"name": "shop_core", "rootUri": "../../packages/shop_core", "packageUri": "lib/"},
+    {"name": "flutter", "rootUri": "file:///opt/flutter/packages/flutter", "packageUri": "lib/"},
+    {"name": "shop_app", "rootUri": "../", "packageUri": "lib/"}
+  ]
+}
+"#,
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("packages/shop_core/pubspec.yaml"),
+        "name: shop_core\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("packages/legacy/pubspec.yaml"),
+        "name: legacy\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("packages/legacy/lib/cart.dart"),
+        "class Cart {}\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("packages/shop_core/lib/shop_core.dart"),
+        r#"library shop_core;
+
+export 'src/models/cart.dart';
+export 'src/models/product.dart' show Product;
+export 'src/services/cart_service.dart';
+"#,
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("packages/shop_core/lib/src/models/cart.dart"),
+        r#"class Cart {
+  final int count;
+  Cart(this.count);
+}
+"#,
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("packages/shop_core/lib/src/models/product.dart"),
+        "class Product {}\n\nclass InternalSku {}\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("packages/shop_core/lib/src/services/cart_service.dart"),
+        r#"import '../models/cart.dart';
+
+class CartService {
+  Cart load() => Cart(0);
+}
+
+String formatPrice(int cents) => '$cents';
+"#,
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("app/lib/main.dart"),
+        r#"import 'package:flutter/material.dart';
+import 'package:shop_app/screens/cart_screen.dart';
+
+void main() {
+  runApp(const CartScreen());
+}
+"#,
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("app/lib/screens/cart_screen.dart"),
+        r#"import 'package:flutter/material.dart';
+import 'package:shop_core/shop_core.dart' show Cart, formatPrice;
+import '../state/cart_state.dart';
+
+part 'cart_screen_view.dart';
+
+class CartScreen extends StatefulWidget {
+  const CartScreen({super.key});
+
+  @override
+  State<CartScreen> createState() => _CartScreenState();
+}
+
+class _CartScreenState extends State<CartScreen> {
+  final CartState state = CartState();
+
+  @override
+  Widget build(BuildContext context) => _buildView(state.cart);
+}
+"#,
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("app/lib/screens/cart_screen_view.dart"),
+        r#"part of 'cart_screen.dart';
+
+Widget _buildView(Cart cart) {
+  return Text(formatPrice(cart.count));
+}
+"#,
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("app/lib/state/cart_state.dart"),
+        r#"import 'package:shop_core/shop_core.dart';
+
+class CartState {
+  final CartService service = CartService();
+  Cart get cart => service.load();
+}
+"#,
+    )
+    .unwrap();
+}
+
+/// Dart import tracing maps `package:` URIs through `package_config.json`, follows
+/// `part` files, and reaches the files a barrel library re-exports.
+#[test]
+fn test_e2e_dart_trace_imports_resolves_packages_and_parts() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path();
+    create_flutter_workspace(project_dir);
+
+    let output_file = temp_dir.path().join("dart_trace_imports.paths");
+    let mut cmd = Command::cargo_bin("context-creator").unwrap();
+    cmd.current_dir(project_dir)
+        .arg("--include")
+        .arg("app/lib/main.dart")
+        .arg("--trace-imports")
+        .arg("--style")
+        .arg("paths")
+        .arg("--output-file")
+        .arg(&output_file);
+
+    cmd.assert().success();
+    let content = fs::read_to_string(&output_file).unwrap();
+    let paths = content.lines().collect::<Vec<_>>();
+
+    assert!(paths.contains(&"app/lib/main.dart"));
+    assert!(paths.contains(&"app/lib/screens/cart_screen.dart"));
+    assert!(paths.contains(&"app/lib/screens/cart_screen_view.dart"));
+    assert!(paths.contains(&"app/lib/state/cart_state.dart"));
+    assert!(paths.contains(&"packages/shop_core/lib/shop_core.dart"));
+    assert!(paths.contains(&"packages/shop_core/lib/src/models/cart.dart"));
+    assert!(paths.contains(&"packages/shop_core/lib/src/services/cart_service.dart"));
+    assert!(!paths.contains(&"packages/legacy/lib/cart.dart"));
+}
+
+/// Dart type expansion looks through `show` combinators and barrel exports, and a
+/// `part` file sees the imports of its library.
+#[test]
+fn test_e2e_dart_include_types_through_barrels_and_parts() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path();
+    create_flutter_workspace(project_dir);
+
+    let output_file = temp_dir.path().join("dart_include_types.paths");
+    let mut cmd = Command::cargo_bin("context-creator").unwrap();
+    cmd.current_dir(project_dir)
+        .arg("--include")
+        .arg("app/lib/screens/cart_screen_view.dart")
+        .arg("--include-types")
+        .arg("--style")
+        .arg("paths")
+        .arg("--output-file")
+        .arg(&output_file);
+
+    cmd.assert().success();
+    let content = fs::read_to_string(&output_file).unwrap();
+    let paths = content.lines().collect::<Vec<_>>();
+
+    assert!(paths.contains(&"app/lib/screens/cart_screen_view.dart"));
+    assert!(paths.contains(&"packages/shop_core/lib/src/models/cart.dart"));
+    assert!(!paths.contains(&"packages/legacy/lib/cart.dart"));
+    assert!(!paths.contains(&"packages/shop_core/lib/src/models/product.dart"));
+}
+
+fn create_mix_umbrella(project_dir: &Path) {
+    for dir in [
+        "apps/shop/lib/shop/orders",
+        "apps/shop/lib/shop/billing",
+        "apps/shop/test",
+        "apps/shop_web/lib/shop_web/controllers",
+        "deps/ecto/lib/ecto",
+    ] {
+        fs::create_dir_all(project_dir.join(dir)).unwrap();
+    }
+
+    fs::write(
+        project_dir.join("mix.exs"),
+        "defmodule Umbrella.MixProject do\n  use Mix.Project\n\n  def project, do: [apps_path: \"apps\"]\nend\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("apps/shop/mix.exs"),
+        "defmodule Shop.MixProject do\n  use Mix.Project\nend\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("apps/shop_web/mix.exs"),
+        "defmodule ShopWeb.MixProject do\n  use Mix.Project\nend\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("apps/shop/lib/shop/orders.ex"),
+        r#"defmodule Shop.Orders do
+  alias Shop.Orders.{Order, LineItem}
+  alias Shop.Billing.Invoice, as: Bill
+  import Shop.Helpers
+  import Ecto.Query
+  require Logger
+
+  @spec checkout(Order.t()) :: :ok
+  def checkout(%Order{} = order) do
+    Bill.create(order)
+    Shop.Payments.charge(order, LineItem.total(order))
+    money(order.total) |> Logger.info()
+  end
+end
+"#,
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("apps/shop/lib/shop/orders/order.ex"),
+        "defmodule Shop.Orders.Order do\n  defstruct [:id, :total]\n\n  @type t :: %__MODULE__{}\nend\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("apps/shop/lib/shop/orders/line_item.ex"),
+        "defmodule Shop.Orders.LineItem do\n  def total(order), do: order.total\nend\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("apps/shop/lib/shop/billing/invoice.ex"),
+        "defmodule Shop.Billing.Invoice do\n  def create(order), do: {:ok, order}\nend\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("apps/shop/lib/shop/payments.ex"),
+        "defmodule Shop.Payments do\n  @behaviour Shop.Gateway\n\n  def charge(_order, amount), do: amount\nend\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("apps/shop/lib/shop/gateway.ex"),
+        "defmodule Shop.Gateway do\n  @callback charge(term(), integer()) :: integer()\nend\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("apps/shop/lib/shop/helpers.ex"),
+        "defmodule Shop.Helpers do\n  def money(cents), do: \"#{cents}\"\nend\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("apps/shop/lib/shop/unused.ex"),
+        "defmodule Shop.Unused do\nend\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("apps/shop_web/lib/shop_web/controllers/order_controller.ex"),
+        r#"defmodule ShopWeb.OrderController do
+  use ShopWeb, :controller
+  alias Shop.Orders
+
+  def create(conn, params) do
+    Orders.checkout(params)
+    conn
+  end
+end
+"#,
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("apps/shop/test/orders_test.exs"),
+        r#"defmodule Shop.OrdersTest do
+  use ExUnit.Case
+
+  test "checkout" do
+    Shop.Orders.checkout(%Shop.Orders.Order{})
+  end
+end
+"#,
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("deps/ecto/lib/ecto/query.ex"),
+        "defmodule Ecto.Query do\nend\n",
+    )
+    .unwrap();
+}
+
+/// Elixir import tracing resolves multi-aliases, `as:` aliases, imports, and fully
+/// qualified remote calls through the `defmodule` index of the umbrella apps.
+#[test]
+fn test_e2e_elixir_trace_imports_resolves_umbrella_modules() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path();
+    create_mix_umbrella(project_dir);
+
+    let output_file = temp_dir.path().join("elixir_trace_imports.paths");
+    let mut cmd = Command::cargo_bin("context-creator").unwrap();
+    cmd.current_dir(project_dir)
+        .arg("--include")
+        .arg("apps/shop/lib/shop/orders.ex")
+        .arg("--trace-imports")
+        .arg("--style")
+        .arg("paths")
+        .arg("--output-file")
+        .arg(&output_file);
+
+    cmd.assert().success();
+    let content = fs::read_to_string(&output_file).unwrap();
+    let paths = content.lines().collect::<Vec<_>>();
+
+    assert!(paths.contains(&"apps/shop/lib/shop/orders.ex"));
+    assert!(paths.contains(&"apps/shop/lib/shop/orders/order.ex"));
+    assert!(paths.contains(&"apps/shop/lib/shop/orders/line_item.ex"));
+    assert!(paths.contains(&"apps/shop/lib/shop/billing/invoice.ex"));
+    assert!(paths.contains(&"apps/shop/lib/shop/payments.ex"));
+    assert!(paths.contains(&"apps/shop/lib/shop/helpers.ex"));
+    assert!(!paths.contains(&"apps/shop/lib/shop/unused.ex"));
+    assert!(!paths.contains(&"deps/ecto/lib/ecto/query.ex"));
+}
+
+/// Elixir callers are found across umbrella apps through aliased remote calls.
+#[test]
+fn test_e2e_elixir_include_callers_across_umbrella_apps() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path();
+    create_mix_umbrella(project_dir);
+
+    let output_file = temp_dir.path().join("elixir_callers.paths");
+    let mut cmd = Command::cargo_bin("context-creator").unwrap();
+    cmd.current_dir(project_dir)
+        .arg("--include")
+        .arg("apps/shop/lib/shop/orders.ex")
+        .arg("--include-callers")
+        .arg("--style")
+        .arg("paths")
+        .arg("--output-file")
+        .arg(&output_file);
+
+    cmd.assert().success();
+    let content = fs::read_to_string(&output_file).unwrap();
+    let paths = content.lines().collect::<Vec<_>>();
+
+    assert!(paths.contains(&"apps/shop/lib/shop/orders.ex"));
+    assert!(paths.contains(&"apps/shop_web/lib/shop_web/controllers/order_controller.ex"));
+    assert!(paths.contains(&"apps/shop/test/orders_test.exs"));
+    assert!(!paths.contains(&"apps/shop/lib/shop/helpers.ex"));
+}
+
+/// Create a Neovim plugin with modules under `lua/` and a `plugin/` entry script
+fn create_neovim_plugin(project_dir: &Path) {
+    for dir in ["lua/notes/ui", "plugin"] {
+        fs::create_dir_all(project_dir.join(dir)).unwrap();
+    }
+
+    fs::write(project_dir.join("README.md"), "# notes.nvim\n").unwrap();
+    fs::write(
+        project_dir.join("plugin/notes.lua"),
+        "require(\"notes\").setup({})\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("lua/notes/init.lua"),
+        r#"local config = require("notes.config")
+local ui = require("notes.ui")
+
+local M = {}
+
+function M.setup(opts)
+  config.apply(opts)
+  ui.open()
+end
+
+return M
+"#,
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("lua/notes/config.lua"),
+        r#"local M = {}
+
+local function merge(defaults, opts)
+  return vim.tbl_deep_extend("force", defaults, opts)
+end
+
+function M.apply(opts)
+  M.options = merge({}, opts or {})
+end
+
+return M
+"#,
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("lua/notes/ui/init.lua"),
+        r#"local Window = require("notes.ui.window")
+
+local M = {}
+
+function M.open()
+  Window.new():show()
+end
+
+return M
+"#,
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("lua/notes/ui/window.lua"),
+        r#"local Window = {}
+Window.__index = Window
+
+function Window.new()
+  return setmetatable({}, Window)
+end
+
+function Window:show() end
+
+return Window
+"#,
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("lua/notes/health.lua"),
+        r#"local M = {}
+
+function M.apply() end
+
+return M
+"#,
+    )
+    .unwrap();
+}
+
+/// Create a game whose `.luarc.json` puts scripts on the search path and whose
+/// rockspec maps a module to a file outside it
+fn create_lua_game(project_dir: &Path) {
+    for dir in ["scripts/entities", "scripts/systems/physics", "src/gamekit"] {
+        fs::create_dir_all(project_dir.join(dir)).unwrap();
+    }
+
+    fs::write(
+        project_dir.join(".luarc.json"),
+        r#"{ "runtime.path": ["scripts/?.lua", "scripts/?/init.lua"] }"#,
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("gamekit-1.0-1.rockspec"),
+        r#"package = "gamekit"
+version = "1.0-1"
+build = {
+  type = "builtin",
+  modules = {
+    ["gamekit"] = "src/gamekit/core.lua",
+  },
+}
+"#,
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("main.lua"),
+        r#"local Player = require("entities.player")
+local physics = require "systems.physics"
+local gamekit = require("gamekit")
+local json = require("cjson")
+
+local player = Player.new(0, 0)
+
+function love.update(dt)
+  physics.step(dt)
+  gamekit.tick()
+end
+"#,
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("scripts/entities/player.lua"),
+        r#"local Player = {}
+
+function Player.new(x, y)
+  return setmetatable({ x = x, y = y }, { __index = Player })
+end
+
+return Player
+"#,
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("scripts/entities/enemy.lua"),
+        "local Enemy = {}\nreturn Enemy\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("scripts/systems/physics/init.lua"),
+        "local M = {}\n\nfunction M.step(dt) end\n\nreturn M\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("src/gamekit/core.lua"),
+        "local M = {}\n\nfunction M.tick() end\n\nreturn M\n",
+    )
+    .unwrap();
+}
+
+/// Lua import tracing honors `.luarc.json` search templates, `?/init.lua` packages,
+/// and rockspec `build.modules` entries.
+#[test]
+fn test_e2e_lua_trace_imports_follows_search_path() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path();
+    create_lua_game(project_dir);
+
+    let output_file = temp_dir.path().join("lua_trace_imports.paths");
+    let mut cmd = Command::cargo_bin("context-creator").unwrap();
+    cmd.current_dir(project_dir)
+        .arg("--include")
+        .arg("main.lua")
+        .arg("--trace-imports")
+        .arg("--style")
+        .arg("paths")
+        .arg("--output-file")
+        .arg(&output_file);
+
+    cmd.assert().success();
+    let content = fs::read_to_string(&output_file).unwrap();
+    let paths = content.lines().collect::<Vec<_>>();
+
+    assert!(paths.contains(&"main.lua"));
+    assert!(paths.contains(&"scripts/entities/player.lua"));
+    assert!(paths.contains(&"scripts/systems/physics/init.lua"));
+    assert!(paths.contains(&"src/gamekit/core.lua"));
+    assert!(!paths.contains(&"scripts/entities/enemy.lua"));
+}
+
+/// Lua callers are found through modules required from the Neovim `lua/` directory.
+#[test]
+fn test_e2e_lua_include_callers_in_neovim_plugin() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path();
+    create_neovim_plugin(project_dir);
+
+    let output_file = temp_dir.path().join("lua_callers.paths");
+    let mut cmd = Command::cargo_bin("context-creator").unwrap();
+    cmd.current_dir(project_dir)
+        .arg("--include")
+        .arg("lua/notes/config.lua")
+        .arg("--include-callers")
+        .arg("--style")
+        .arg("paths")
+        .arg("--output-file")
+        .arg(&output_file);
+
+    cmd.assert().success();
+    let content = fs::read_to_string(&output_file).unwrap();
+    let paths = content.lines().collect::<Vec<_>>();
+
+    assert!(paths.contains(&"lua/notes/config.lua"));
+    assert!(paths.contains(&"lua/notes/init.lua"));
+    assert!(!paths.contains(&"lua/notes/health.lua"));
+    assert!(!paths.contains(&"plugin/notes.lua"));
+}
+
+/// Create a `cabal.project` with a Cabal library and an hpack application package
+fn create_cabal_project(project_dir: &Path) {
+    for dir in ["core/src/Shop/Orders", "app/app", "app/src/Web"] {
+        fs::create_dir_all(project_dir.join(dir)).unwrap();
+    }
+
+    fs::write(project_dir.join("cabal.project"), "packages: core app\n").unwrap();
+    fs::write(
+        project_dir.join("core/shop-core.cabal"),
+        r#"cabal-version: 2.4
+name:          shop-core
+version:       0.1.0
+
+library
+  exposed-modules: Shop.Types
+                   Shop.Orders
+  hs-source-dirs:
+      src
+  build-depends: base, containers
+"#,
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("core/src/Shop/Types.hs"),
+        r#"module Shop.Types (Order(..), LineItem(..), orderTotal) where
+
+data LineItem = LineItem { sku :: String, qty :: Int }
+
+data Order = Order { lineItems :: [LineItem] }
+
+orderTotal :: Order -> Int
+orderTotal = sum . map qty . lineItems
+"#,
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("core/src/Shop/Orders.hs"),
+        r#"module Shop.Orders (checkout) where
+
+import qualified Data.Map.Strict as Map
+import Shop.Types
+import qualified Shop.Orders.Tax as Tax
+
+checkout :: Order -> Map.Map String Int -> Int
+checkout order prices = Tax.apply (orderTotal order)
+"#,
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("core/src/Shop/Orders/Tax.hs"),
+        "module Shop.Orders.Tax where\n\napply :: Int -> Int\napply x = x * 2\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("core/src/Shop/Legacy.hs"),
+        "module Shop.Legacy where\n\ncheckout :: Int\ncheckout = 0\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("app/package.yaml"),
+        r#"name: shop-app
+dependencies:
+  - base
+  - shop-core
+library:
+  source-dirs: src
+executables:
+  shop:
+    main: Main.hs
+    source-dirs:
+      - app
+"#,
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("app/app/Main.hs"),
+        r#"module Main where
+
+import Shop.Orders (checkout)
+import Shop.Types (Order(..))
+import Web.Server (serve)
+
+main :: IO ()
+main = serve (checkout (Order []) mempty)
+"#,
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("app/src/Web/Server.hs"),
+        "module Web.Server (serve) where\n\nserve :: Int -> IO ()\nserve n = print n\n",
+    )
+    .unwrap();
+}
+
+/// Haskell import tracing maps module names through `hs-source-dirs` and hpack
+/// `source-dirs`, across the packages of a `cabal.project`.
+#[test]
+fn test_e2e_haskell_trace_imports_across_cabal_packages() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path();
+    create_cabal_project(project_dir);
+
+    let output_file = temp_dir.path().join("haskell_trace_imports.paths");
+    let mut cmd = Command::cargo_bin("context-creator").unwrap();
+    cmd.current_dir(project_dir)
+        .arg("--include")
+        .arg("app/app/Main.hs")
+        .arg("--trace-imports")
+        .arg("--style")
+        .arg("paths")
+        .arg("--output-file")
+        .arg(&output_file);
+
+    cmd.assert().success();
+    let content = fs::read_to_string(&output_file).unwrap();
+    let paths = content.lines().collect::<Vec<_>>();
+
+    assert!(paths.contains(&"app/app/Main.hs"));
+    assert!(paths.contains(&"app/src/Web/Server.hs"));
+    assert!(paths.contains(&"core/src/Shop/Orders.hs"));
+    assert!(paths.contains(&"core/src/Shop/Orders/Tax.hs"));
+    assert!(paths.contains(&"core/src/Shop/Types.hs"));
+    assert!(!paths.contains(&"core/src/Shop/Legacy.hs"));
+}
+
+/// Haskell callers are found through qualified `as` aliases and explicit import lists.
+#[test]
+fn test_e2e_haskell_include_callers_through_qualified_imports() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path();
+    create_cabal_project(project_dir);
+
+    let output_file = temp_dir.path().join("haskell_callers.paths");
+    let mut cmd = Command::cargo_bin("context-creator").unwrap();
+    cmd.current_dir(project_dir)
+        .arg("--include")
+        .arg("core/src/Shop/Orders/Tax.hs")
+        .arg("--include-callers")
+        .arg("--style")
+        .arg("paths")
+        .arg("--output-file")
+        .arg(&output_file);
+
+    cmd.assert().success();
+    let content = fs::read_to_string(&output_file).unwrap();
+    let paths = content.lines().collect::<Vec<_>>();
+
+    assert!(paths.contains(&"core/src/Shop/Orders/Tax.hs"));
+    assert!(paths.contains(&"core/src/Shop/Orders.hs"));
+    assert!(!paths.contains(&"app/app/Main.hs"));
+}
+
+/// Create an R analysis project that sources its helpers and uses a local package
+fn create_r_analysis_project(project_dir: &Path) {
+    fs::create_dir_all(project_dir.join("analysis/R")).unwrap();
+    fs::create_dir_all(project_dir.join("pkgs/cleaner/R")).unwrap();
+
+    fs::write(
+        project_dir.join("analysis/main.R"),
+        r#"library(dplyr)
+library(cleaner)
+source("R/model.R")
+source(file.path("R", "plots.R"))
+
+df <- read.csv("data.csv")
+df <- cleaner::clean_names(df)
+fit <- fit_model(df)
+plot_fit(fit)
+"#,
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("analysis/R/model.R"),
+        r#"Model <- R6::R6Class("Model", public = list(fit = function(df) df))
+fit_model <- function(df) {
+  m <- Model$new()
+  m$fit(df)
+}
+"#,
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("analysis/R/plots.R"),
+        "plot_fit <- function(fit) ggplot2::ggplot(fit)\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("pkgs/cleaner/DESCRIPTION"),
+        "Package: cleaner\nVersion: 0.1.0\nImports: dplyr\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("pkgs/cleaner/R/clean.R"),
+        "clean_names <- function(df) {\n  normalize(names(df))\n}\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("pkgs/cleaner/R/normalize.R"),
+        "normalize <- function(x) tolower(x)\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("pkgs/cleaner/R/unused.R"),
+        "summarize_all <- function(df) df\n",
+    )
+    .unwrap();
+}
+
+/// R import tracing follows `source()` paths, including `file.path()` arguments, and
+/// `pkg::fn` calls into local packages.
+#[test]
+fn test_e2e_r_trace_imports_follows_source_and_package_calls() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path();
+    create_r_analysis_project(project_dir);
+
+    let output_file = temp_dir.path().join("r_trace_imports.paths");
+    let mut cmd = Command::cargo_bin("context-creator").unwrap();
+    cmd.current_dir(project_dir)
+        .arg("--include")
+        .arg("analysis/main.R")
+        .arg("--trace-imports")
+        .arg("--style")
+        .arg("paths")
+        .arg("--output-file")
+        .arg(&output_file);
+
+    cmd.assert().success();
+    let content = fs::read_to_string(&output_file).unwrap();
+    let paths = content.lines().collect::<Vec<_>>();
+
+    assert!(paths.contains(&"analysis/main.R"));
+    assert!(paths.contains(&"analysis/R/model.R"));
+    assert!(paths.contains(&"analysis/R/plots.R"));
+    assert!(paths.contains(&"pkgs/cleaner/R/clean.R"));
+    assert!(!paths.contains(&"pkgs/cleaner/R/unused.R"));
+}
+
+/// R callers of a package function are found through `pkg::fn` calls outside the
+/// package and plain calls within it.
+#[test]
+fn test_e2e_r_include_callers_through_package_namespace() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path();
+    create_r_analysis_project(project_dir);
+
+    let output_file = temp_dir.path().join("r_callers.paths");
+    let mut cmd = Command::cargo_bin("context-creator").unwrap();
+    cmd.current_dir(project_dir)
+        .arg("--include")
+        .arg("pkgs/cleaner/R/clean.R")
+        .arg("--include-callers")
+        .arg("--style")
+        .arg("paths")
+        .arg("--output-file")
+        .arg(&output_file);
+
+    cmd.assert().success();
+    let content = fs::read_to_string(&output_file).unwrap();
+    let paths = content.lines().collect::<Vec<_>>();
+
+    assert!(paths.contains(&"pkgs/cleaner/R/clean.R"));
+    assert!(paths.contains(&"analysis/main.R"));
+    assert!(!paths.contains(&"analysis/R/model.R"));
+}
+
+/// Create a Julia package whose module file includes its sources and a script using it
+fn create_julia_package(project_dir: &Path) {
+    fs::create_dir_all(project_dir.join("src/solvers")).unwrap();
+    fs::create_dir_all(project_dir.join("scripts")).unwrap();
+
+    fs::write(
+        project_dir.join("Project.toml"),
+        r#"name = "Optim"
+uuid = "12345678-1234-1234-1234-123456789abc"
+
+[deps]
+LinearAlgebra = "37e2e46d-f89d-539d-b4ee-838fcccc9c8e"
+"#,
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("src/Optim.jl"),
+        r#"module Optim
+
+using LinearAlgebra
+
+export minimize, Problem
+
+include("problem.jl")
+include(joinpath(@__DIR__, "solvers", "gradient.jl"))
+include("Reporting.jl")
+
+#= include("legacy.jl") =#
+end # module
+"#,
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("src/problem.jl"),
+        r#"struct Problem{T}
+    f::Function
+    x0::Vector{T}
+end
+
+dimension(p::Problem) = length(p.x0)
+"#,
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("src/solvers/gradient.jl"),
+        r#"function minimize(p::Problem; steps = 100)
+    x = copy(p.x0)
+    for _ in 1:steps
+        x .-= 0.1 .* numerical_grad(p.f, x)
+    end
+    x'
+end
+
+numerical_grad(f, x) = [f(x) for _ in x]
+"#,
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("src/Reporting.jl"),
+        r#"module Reporting
+using ..Optim: Problem
+summary_line(p::Problem) = string("dim=", dimension(p))
+end
+"#,
+    )
+    .unwrap();
+    fs::write(project_dir.join("src/legacy.jl"), "legacy() = nothing\n").unwrap();
+    fs::write(
+        project_dir.join("scripts/run.jl"),
+        r#"using Optim
+using Plots
+
+prob = Problem(x -> sum(x .^ 2), [1.0, 2.0])
+result = minimize(prob)
+"#,
+    )
+    .unwrap();
+}
+
+/// Julia import tracing resolves a package through `Project.toml` to `src/<Name>.jl`
+/// and follows its `include()` calls, skipping commented-out includes.
+#[test]
+fn test_e2e_julia_trace_imports_through_project_package() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path();
+    create_julia_package(project_dir);
+
+    let output_file = temp_dir.path().join("julia_trace_imports.paths");
+    let mut cmd = Command::cargo_bin("context-creator").unwrap();
+    cmd.current_dir(project_dir)
+        .arg("--include")
+        .arg("scripts/run.jl")
+        .arg("--trace-imports")
+        .arg("--style")
+        .arg("paths")
+        .arg("--output-file")
+        .arg(&output_file);
+
+    cmd.assert().success();
+    let content = fs::read_to_string(&output_file).unwrap();
+    let paths = content.lines().collect::<Vec<_>>();
+
+    assert!(paths.contains(&"scripts/run.jl"));
+    assert!(paths.contains(&"src/Optim.jl"));
+    assert!(paths.contains(&"src/problem.jl"));
+    assert!(paths.contains(&"src/solvers/gradient.jl"));
+    assert!(!paths.contains(&"src/legacy.jl"));
+}
+
+/// Julia callers include sibling files included into the same module.
+#[test]
+fn test_e2e_julia_include_callers_across_included_files() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path();
+    create_julia_package(project_dir);
+
+    let output_file = temp_dir.path().join("julia_callers.paths");
+    let mut cmd = Command::cargo_bin("context-creator").unwrap();
+    cmd.current_dir(project_dir)
+        .arg("--include")
+        .arg("src/problem.jl")
+        .arg("--include-callers")
+        .arg("--style")
+        .arg("paths")
+        .arg("--output-file")
+        .arg(&output_file);
+
+    cmd.assert().success();
+    let content = fs::read_to_string(&output_file).unwrap();
+    let paths = content.lines().collect::<Vec<_>>();
+
+    assert!(paths.contains(&"src/problem.jl"));
+    assert!(paths.contains(&"src/Reporting.jl"));
+    assert!(!paths.contains(&"scripts/run.jl"));
+}
+
+/// Create an Elm application whose modules span two `source-directories`
+fn create_elm_application(project_dir: &Path) {
+    fs::create_dir_all(project_dir.join("src/Page")).unwrap();
+    fs::create_dir_all(project_dir.join("src/Api")).unwrap();
+    fs::create_dir_all(project_dir.join("src/Types")).unwrap();
+    fs::create_dir_all(project_dir.join("shared/src")).unwrap();
+
+    fs::write(
+        project_dir.join("elm.json"),
+        r#"{
+    "type": "application",
+    "source-directories": ["src", "shared/src"],
+    "elm-version": "0.19.1",
+    "dependencies": { "direct": { "elm/html": "1.0.0" }, "indirect": {} },
+    "test-dependencies": { "direct": {}, "indirect": {} }
+}
+"#,
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("src/Page/Checkout.elm"),
+        r#"module Page.Checkout exposing (Model, Msg(..), init, update)
+
+import Api.Orders as Orders exposing (Order, submit)
+import Cart
+import Html exposing (Html)
+import Types.Money exposing (Money(..))
+
+
+type alias Model =
+    { cart : Cart.Cart
+    , order : Maybe Order
+    , total : Money
+    }
+
+
+type Msg
+    = Submit
+    | Submitted (Result String Order)
+
+
+init : Model
+init =
+    { cart = Cart.empty, order = Nothing, total = Money 0 }
+
+
+update : Msg -> Model -> ( Model, Cmd Msg )
+update msg model =
+    case msg of
+        Submit ->
+            ( model, submit Submitted (Cart.items model.cart) )
+
+        Submitted _ ->
+            ( model, Orders.track model.total )
+"#,
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("src/Api/Orders.elm"),
+        r#"module Api.Orders exposing (Order, submit, track)
+
+import Types.Money exposing (Money)
+
+
+type alias Order =
+    { id : String, total : Money }
+
+
+submit : (Result String Order -> msg) -> List String -> Cmd msg
+submit toMsg items =
+    Cmd.none
+
+
+track : Money -> Cmd msg
+track _ =
+    Cmd.none
+"#,
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("src/Types/Money.elm"),
+        "module Types.Money exposing (Money(..))\n\n\ntype Money\n    = Money Int\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("shared/src/Cart.elm"),
+        r#"module Cart exposing (Cart, empty, items)
+
+
+type Cart
+    = Cart (List String)
+
+
+empty : Cart
+empty =
+    Cart []
+
+
+items : Cart -> List String
+items (Cart xs) =
+    xs
+"#,
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("src/Unused.elm"),
+        "module Unused exposing (unused)\n\n\nunused : Int\nunused =\n    1\n",
+    )
+    .unwrap();
+}
+
+/// Elm type expansion follows exposed and qualified types to the modules declaring
+/// them, across every directory in `source-directories`.
+#[test]
+fn test_e2e_elm_include_types_across_source_directories() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path();
+    create_elm_application(project_dir);
+
+    let output_file = temp_dir.path().join("elm_types.paths");
+    let mut cmd = Command::cargo_bin("context-creator").unwrap();
+    cmd.current_dir(project_dir)
+        .arg("--include")
+        .arg("src/Page/Checkout.elm")
+        .arg("--include-types")
+        .arg("--style")
+        .arg("paths")
+        .arg("--output-file")
+        .arg(&output_file);
+
+    cmd.assert().success();
+    let content = fs::read_to_string(&output_file).unwrap();
+    let paths = content.lines().collect::<Vec<_>>();
+
+    assert!(paths.contains(&"src/Page/Checkout.elm"));
+    assert!(paths.contains(&"src/Api/Orders.elm"));
+    assert!(paths.contains(&"src/Types/Money.elm"));
+    assert!(paths.contains(&"shared/src/Cart.elm"));
+    assert!(!paths.contains(&"src/Unused.elm"));
+}
+
+/// Elm callers are found through qualified calls with `as` aliases and exposed names.
+#[test]
+fn test_e2e_elm_include_callers_through_aliased_imports() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path();
+    create_elm_application(project_dir);
+
+    let output_file = temp_dir.path().join("elm_callers.paths");
+    let mut cmd = Command::cargo_bin("context-creator").unwrap();
+    cmd.current_dir(project_dir)
+        .arg("--include")
+        .arg("src/Api/Orders.elm")
+        .arg("--include-callers")
+        .arg("--style")
+        .arg("paths")
+        .arg("--output-file")
+        .arg(&output_file);
+
+    cmd.assert().success();
+    let content = fs::read_to_string(&output_file).unwrap();
+    let paths = content.lines().collect::<Vec<_>>();
+
+    assert!(paths.contains(&"src/Api/Orders.elm"));
+    assert!(paths.contains(&"src/Page/Checkout.elm"));
+    assert!(!paths.contains(&"shared/src/Cart.elm"));
+}
+
+/// Create a pnpm monorepo with tsconfig path aliases, a solution-style app config, and
+/// workspace packages whose entry points name unbuilt `dist/` output
+fn create_pnpm_monorepo(project_dir: &Path) {
+    fs::create_dir_all(project_dir.join("apps/web/src/utils")).unwrap();
+    fs::create_dir_all(project_dir.join("packages/ui/src")).unwrap();
+    fs::create_dir_all(project_dir.join("packages/models/src")).unwrap();
+    fs::create_dir_all(project_dir.join("packages/legacy/lib")).unwrap();
+    fs::create_dir_all(project_dir.join("packages/types")).unwrap();
+
+    fs::write(
+        project_dir.join("package.json"),
+        r#"{ "name": "mono", "private": true }"#,
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("pnpm-workspace.yaml"),
+        "packages:\n  - \"apps/*\"\n  - \"packages/*\"\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("tsconfig.base.json"),
+        r#"{
+  // Shared by every package
+  "compilerOptions": {
+    "strict": true,
+    "baseUrl": ".",
+    "paths": {
+      "@shared/*": ["packages/models/src/*"], /* domain types */
+      "@app/*": ["apps/web/src/*"],
+    },
+  },
+}
+"#,
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("apps/web/tsconfig.json"),
+        r#"{ "files": [], "references": [{ "path": "./tsconfig.app.json" }] }"#,
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("apps/web/tsconfig.app.json"),
+        r#"{ "extends": "../../tsconfig.base", "include": ["src/**/*"] }"#,
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("apps/web/package.json"),
+        r#"{ "name": "@mono/web", "dependencies": { "@mono/ui": "workspace:*", "react": "18.2.0" } }"#,
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("apps/web/src/main.ts"),
+        r#"import React from 'react';
+import { formatPrice } from '@app/utils/format';
+import { Button } from '@mono/ui';
+import { Theme } from '@mono/ui/theme';
+import type { Order } from '@mono/models';
+import { Money } from '@shared/money';
+import { LegacyWidget } from '@mono/legacy';
+import type { Settings } from '@mono/types';
+
+export function render(order: Order, settings: Settings): string {
+  LegacyWidget();
+  return Button({ label: formatPrice(order.total as Money), theme: Theme.dark });
+}
+"#,
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("apps/web/src/utils/format.ts"),
+        "export function formatPrice(amount: number): string {\n  return `$${amount}`;\n}\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("apps/web/src/utils/unused.ts"),
+        "export const unused = 1;\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("packages/ui/package.json"),
+        r#"{
+  "name": "@mono/ui",
+  "exports": {
+    ".": { "types": "./dist/index.d.ts", "import": "./dist/index.js" },
+    "./*": "./dist/*.js"
+  }
+}
+"#,
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("packages/ui/src/index.ts"),
+        "export function Button(props: { label: string; theme: string }): string {\n  return props.label;\n}\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("packages/ui/src/theme.ts"),
+        "export const Theme = { dark: 'dark' };\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("packages/models/package.json"),
+        r#"{ "name": "@mono/models", "main": "./lib/index.js", "types": "./lib/index.d.ts" }"#,
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("packages/models/src/index.ts"),
+        "export interface Order {\n  total: number;\n}\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("packages/models/src/money.ts"),
+        "export type Money = number;\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("packages/legacy/package.json"),
+        r#"{ "name": "@mono/legacy", "main": "lib/widget.js" }"#,
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("packages/legacy/lib/widget.js"),
+        "exports.LegacyWidget = function () {};\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("packages/types/package.json"),
+        r#"{ "name": "@mono/types", "types": "index.d.ts" }"#,
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("packages/types/index.d.ts"),
+        "export interface Settings {\n  theme: string;\n}\n",
+    )
+    .unwrap();
+}
+
+/// TypeScript import tracing maps tsconfig `paths` inherited through `extends` and
+/// project `references`, and workspace packages through `exports` and `main` back to
+/// their sources.
+#[test]
+fn test_e2e_typescript_trace_imports_through_paths_and_workspaces() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path();
+    create_pnpm_monorepo(project_dir);
+
+    let output_file = temp_dir.path().join("typescript_trace_imports.paths");
+    let mut cmd = Command::cargo_bin("context-creator").unwrap();
+    cmd.current_dir(project_dir)
+        .arg("--include")
+        .arg("apps/web/src/main.ts")
+        .arg("--trace-imports")
+        .arg("--style")
+        .arg("paths")
+        .arg("--output-file")
+        .arg(&output_file);
+
+    cmd.assert().success();
+    let content = fs::read_to_string(&output_file).unwrap();
+    let paths = content.lines().collect::<Vec<_>>();
+
+    assert!(paths.contains(&"apps/web/src/main.ts"));
+    assert!(paths.contains(&"apps/web/src/utils/format.ts"));
+    assert!(paths.contains(&"packages/ui/src/index.ts"));
+    assert!(paths.contains(&"packages/ui/src/theme.ts"));
+    assert!(paths.contains(&"packages/models/src/index.ts"));
+    assert!(paths.contains(&"packages/models/src/money.ts"));
+    assert!(paths.contains(&"packages/legacy/lib/widget.js"));
+    assert!(!paths.contains(&"apps/web/src/utils/unused.ts"));
+}
+
+/// TypeScript type expansion follows imported type names to workspace sources and to
+/// declaration-only packages.
+#[test]
+fn test_e2e_typescript_include_types_through_workspace_declarations() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path();
+    create_pnpm_monorepo(project_dir);
+
+    let output_file = temp_dir.path().join("typescript_types.paths");
+    let mut cmd = Command::cargo_bin("context-creator").unwrap();
+    cmd.current_dir(project_dir)
+        .arg("--include")
+        .arg("apps/web/src/main.ts")
+        .arg("--include-types")
+        .arg("--style")
+        .arg("paths")
+        .arg("--output-file")
+        .arg(&output_file);
+
+    cmd.assert().success();
+    let content = fs::read_to_string(&output_file).unwrap();
+    let paths = content.lines().collect::<Vec<_>>();
+
+    assert!(paths.contains(&"apps/web/src/main.ts"));
+    assert!(paths.contains(&"packages/models/src/index.ts"));
+    assert!(paths.contains(&"packages/types/index.d.ts"));
+    assert!(!paths.contains(&"packages/legacy/lib/widget.js"));
+}
+
+/// JavaScript resolves workspace packages declared in `package.json` `workspaces` and
+/// aliases from `jsconfig.json`.
+#[test]
+fn test_e2e_javascript_trace_imports_through_npm_workspaces_and_jsconfig() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path();
+    fs::create_dir_all(project_dir.join("packages/api/src/routes")).unwrap();
+    fs::create_dir_all(project_dir.join("packages/db/src")).unwrap();
+
+    fs::write(
+        project_dir.join("package.json"),
+        r#"{ "name": "shop", "private": true, "workspaces": { "packages": ["packages/*"] } }"#,
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("packages/api/jsconfig.json"),
+        r##"{ "compilerOptions": { "baseUrl": "src", "paths": { "#routes/*": ["routes/*"] } } }"##,
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("packages/api/package.json"),
+        r#"{ "name": "@shop/api", "main": "src/server.js" }"#,
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("packages/api/src/server.js"),
+        "import { orders } from '#routes/orders';\nimport { connect } from '@shop/db';\nimport express from 'express';\n\nconnect();\nexpress().use(orders);\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("packages/api/src/routes/orders.js"),
+        "export const orders = () => {};\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("packages/db/package.json"),
+        r#"{ "name": "@shop/db", "exports": { ".": { "require": "./dist/index.cjs", "import": "./dist/index.mjs" } } }"#,
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("packages/db/src/index.js"),
+        "export function connect() {}\n",
+    )
+    .unwrap();
+
+    let output_file = temp_dir.path().join("javascript_workspaces.paths");
+    let mut cmd = Command::cargo_bin("context-creator").unwrap();
+    cmd.current_dir(project_dir)
+        .arg("--include")
+        .arg("packages/api/src/server.js")
+        .arg("--trace-imports")
+        .arg("--style")
+        .arg("paths")
+        .arg("--output-file")
+        .arg(&output_file);
+
+    cmd.assert().success();
+    let content = fs::read_to_string(&output_file).unwrap();
+    let paths = content.lines().collect::<Vec<_>>();
+
+    assert!(paths.contains(&"packages/api/src/server.js"));
+    assert!(paths.contains(&"packages/api/src/routes/orders.js"));
+    assert!(paths.contains(&"packages/db/src/index.js"));
+}
+
+/// Create a Python `src/` layout project with an editable uv path dependency, a
+/// `setup.cfg` namespace package, and models re-exported through `__init__.py`
+fn create_python_src_layout(project_dir: &Path) {
+    fs::create_dir_all(project_dir.join("src/shop/models")).unwrap();
+    fs::create_dir_all(project_dir.join("src/shop/services")).unwrap();
+    fs::create_dir_all(project_dir.join("libs/billing/src/acme/billing")).unwrap();
+    fs::create_dir_all(project_dir.join("libs/notify/lib/acme/notify")).unwrap();
+
+    fs::write(
+        project_dir.join("pyproject.toml"),
+        r#"[project]
+name = "shop"
+dependencies = ["acme-billing"]
+
+[tool.setuptools.packages.find]
+where = ["src"]
+
+[tool.uv.sources]
+acme-billing = { path = "libs/billing", editable = true }
+"#,
+    )
+    .unwrap();
+    fs::write(project_dir.join("src/shop/__init__.py"), "").unwrap();
+    fs::write(
+        project_dir.join("src/shop/models/__init__.py"),
+        "from .user import User\nfrom .order import *\n\n__all__ = [\"User\", \"Order\"]\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("src/shop/models/user.py"),
+        "class User:\n    email: str\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("src/shop/models/order.py"),
+        "class Order:\n    total: int\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("src/shop/models/refund.py"),
+        "class Refund:\n    amount: int\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("src/shop/services/checkout.py"),
+        r#"from __future__ import annotations
+
+from typing import TYPE_CHECKING
+
+from acme.billing import invoices
+from acme.notify.email import send_receipt
+from shop.models import Order
+
+if TYPE_CHECKING:
+    from shop.models import User
+    from shop.models import refund
+
+
+def checkout(user: "User", order: Order) -> None:
+    invoices.create_invoice(order)
+    send_receipt(user)
+
+
+def refund_all(refunds: "list[refund.Refund]") -> None:
+    pass
+"#,
+    )
+    .unwrap();
+    fs::write(project_dir.join("src/shop/unused.py"), "UNUSED = True\n").unwrap();
+
+    fs::write(
+        project_dir.join("libs/billing/pyproject.toml"),
+        "[project]\nname = \"acme-billing\"\n\n[tool.setuptools]\npackage-dir = {\"\" = \"src\"}\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("libs/billing/src/acme/billing/invoices.py"),
+        "def create_invoice(order):\n    return order\n",
+    )
+    .unwrap();
+
+    fs::write(
+        project_dir.join("libs/notify/setup.cfg"),
+        "[metadata]\nname = acme-notify\n\n[options]\npackages = find_namespace:\npackage_dir =\n    =lib\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("libs/notify/lib/acme/notify/email.py"),
+        "def send_receipt(user):\n    return user\n",
+    )
+    .unwrap();
+}
+
+/// Python import tracing resolves modules under configured source roots, across
+/// namespace packages without `__init__.py`, and into editable local packages.
+#[test]
+fn test_e2e_python_trace_imports_through_src_layouts_and_namespace_packages() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path();
+    create_python_src_layout(project_dir);
+
+    let output_file = temp_dir.path().join("python_trace_imports.paths");
+    let mut cmd = Command::cargo_bin("context-creator").unwrap();
+    cmd.current_dir(project_dir)
+        .arg("--include")
+        .arg("src/shop/services/checkout.py")
+        .arg("--trace-imports")
+        .arg("--style")
+        .arg("paths")
+        .arg("--output-file")
+        .arg(&output_file);
+
+    cmd.assert().success();
+    let content = fs::read_to_string(&output_file).unwrap();
+    let paths = content.lines().collect::<Vec<_>>();
+
+    assert!(paths.contains(&"src/shop/services/checkout.py"));
+    assert!(paths.contains(&"src/shop/models/__init__.py"));
+    assert!(paths.contains(&"libs/billing/src/acme/billing/invoices.py"));
+    assert!(paths.contains(&"libs/notify/lib/acme/notify/email.py"));
+    assert!(!paths.contains(&"src/shop/unused.py"));
+}
+
+/// Python type expansion follows `TYPE_CHECKING` imports and string annotations through
+/// `__init__.py` re-exports to the modules defining each class.
+#[test]
+fn test_e2e_python_include_types_through_reexports_and_type_checking_imports() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path();
+    create_python_src_layout(project_dir);
+
+    let output_file = temp_dir.path().join("python_types.paths");
+    let mut cmd = Command::cargo_bin("context-creator").unwrap();
+    cmd.current_dir(project_dir)
+        .arg("--include")
+        .arg("src/shop/services/checkout.py")
+        .arg("--include-types")
+        .arg("--style")
+        .arg("paths")
+        .arg("--output-file")
+        .arg(&output_file);
+
+    cmd.assert().success();
+    let content = fs::read_to_string(&output_file).unwrap();
+    let paths = content.lines().collect::<Vec<_>>();
+
+    assert!(paths.contains(&"src/shop/services/checkout.py"));
+    assert!(paths.contains(&"src/shop/models/user.py"));
+    assert!(paths.contains(&"src/shop/models/order.py"));
+    assert!(paths.contains(&"src/shop/models/refund.py"));
+    assert!(!paths.contains(&"src/shop/unused.py"));
+}
+
+/// Create a Cargo workspace whose binary uses a renamed path dependency that re-exports
+/// its items through `pub use`, a `#[path]` module, and an inherited workspace dependency
+fn create_cargo_workspace(project_dir: &Path) {
+    fs::create_dir_all(project_dir.join("crates/core/src/internal")).unwrap();
+    fs::create_dir_all(project_dir.join("crates/core/src/sys")).unwrap();
+    fs::create_dir_all(project_dir.join("crates/util/src")).unwrap();
+    fs::create_dir_all(project_dir.join("crates/app/src")).unwrap();
+
+    fs::write(
+        project_dir.join("Cargo.toml"),
+        "[workspace]\nmembers = [\"crates/*\"]\n\n[workspace.dependencies]\nshop-util = { path = \"crates/util\" }\n",
+    )
+    .unwrap();
+
+    fs::write(
+        project_dir.join("crates/core/Cargo.toml"),
+        "[package]\nname = \"shop-core\"\nversion = \"0.1.0\"\n\n[dependencies]\nserde = \"1\"\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("crates/core/src/lib.rs"),
+        r#"mod internal;
+#[path = "sys/unix.rs"]
+mod platform;
+
+pub mod prelude {
+    pub use crate::internal::config::Config;
+    pub use crate::platform::*;
+}
+
+pub use internal::config::Config;
+pub use internal::ledger::{Entry, Ledger as Book};
+"#,
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("crates/core/src/internal/mod.rs"),
+        "pub mod config;\npub mod ledger;\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("crates/core/src/internal/config.rs"),
+        "pub struct Config {\n    pub name: String,\n}\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("crates/core/src/internal/ledger.rs"),
+        "pub struct Ledger;\npub struct Entry;\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("crates/core/src/sys/unix.rs"),
+        "pub struct Clock;\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("crates/core/src/unused.rs"),
+        "pub struct Unused;\n",
+    )
+    .unwrap();
+
+    fs::write(
+        project_dir.join("crates/util/Cargo.toml"),
+        "[package]\nname = \"shop-util\"\nversion = \"0.1.0\"\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("crates/util/src/lib.rs"),
+        "pub mod fmt {\n    pub fn money(cents: u64) -> String {\n        format!(\"{cents}\")\n    }\n}\n",
+    )
+    .unwrap();
+
+    fs::write(
+        project_dir.join("crates/app/Cargo.toml"),
+        r#"[package]
+name = "app"
+version = "0.1.0"
+
+[dependencies]
+shop = { package = "shop-core", path = "../core" }
+shop-util = { workspace = true }
+"#,
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("crates/app/src/main.rs"),
+        r#"use shop::Config;
+use shop::{prelude::Clock, Book};
+use shop_util::fmt::money;
+
+fn run(config: Config, book: Book, clock: Clock) {
+    println!("{}", money(1));
+}
+
+fn main() {}
+"#,
+    )
+    .unwrap();
+}
+
+/// Rust import tracing crosses workspace crates by their dependency names and lands on
+/// the files defining re-exported items.
+#[test]
+fn test_e2e_rust_trace_imports_across_workspace_crates() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path();
+    create_cargo_workspace(project_dir);
+
+    let output_file = temp_dir.path().join("rust_workspace_imports.paths");
+    let mut cmd = Command::cargo_bin("context-creator").unwrap();
+    cmd.current_dir(project_dir)
+        .arg("--include")
+        .arg("crates/app/src/main.rs")
+        .arg("--trace-imports")
+        .arg("--style")
+        .arg("paths")
+        .arg("--output-file")
+        .arg(&output_file);
+
+    cmd.assert().success();
+    let content = fs::read_to_string(&output_file).unwrap();
+    let paths = content.lines().collect::<Vec<_>>();
+
+    assert!(paths.contains(&"crates/app/src/main.rs"));
+    assert!(paths.contains(&"crates/core/src/lib.rs"));
+    assert!(paths.contains(&"crates/core/src/internal/config.rs"));
+    assert!(paths.contains(&"crates/util/src/lib.rs"));
+    assert!(!paths.contains(&"crates/core/src/unused.rs"));
+}
+
+/// Rust type expansion follows `pub use` re-exports, renames, and globs to the file
+/// defining each type, including modules loaded through `#[path]`.
+#[test]
+fn test_e2e_rust_include_types_through_pub_use_reexports() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path();
+    create_cargo_workspace(project_dir);
+
+    let output_file = temp_dir.path().join("rust_workspace_types.paths");
+    let mut cmd = Command::cargo_bin("context-creator").unwrap();
+    cmd.current_dir(project_dir)
+        .arg("--include")
+        .arg("crates/app/src/main.rs")
+        .arg("--include-types")
+        .arg("--style")
+        .arg("paths")
+        .arg("--output-file")
+        .arg(&output_file);
+
+    cmd.assert().success();
+    let content = fs::read_to_string(&output_file).unwrap();
+    let paths = content.lines().collect::<Vec<_>>();
+
+    assert!(paths.contains(&"crates/app/src/main.rs"));
+    assert!(paths.contains(&"crates/core/src/internal/config.rs"));
+    assert!(paths.contains(&"crates/core/src/internal/ledger.rs"));
+    assert!(paths.contains(&"crates/core/src/sys/unix.rs"));
+    assert!(!paths.contains(&"crates/core/src/unused.rs"));
+}
+
+/// Create a `go.work` workspace whose service replaces one module with a local directory
+/// and vendors a third-party package
+fn create_go_workspace(project_dir: &Path) {
+    fs::create_dir_all(project_dir.join("services/api/handlers")).unwrap();
+    fs::create_dir_all(project_dir.join("services/api/vendor/github.com/acme/log")).unwrap();
+    fs::create_dir_all(project_dir.join("libs/money")).unwrap();
+    fs::create_dir_all(project_dir.join("libs/auth/token")).unwrap();
+
+    fs::write(
+        project_dir.join("go.work"),
+        "go 1.22\n\nuse (\n\t./services/api\n\t./libs/auth\n)\n",
+    )
+    .unwrap();
+
+    fs::write(
+        project_dir.join("services/api/go.mod"),
+        r#"module example.com/shop/api
+
+go 1.22
+
+require (
+	example.com/lib/money v0.0.0
+	github.com/acme/log v1.2.0
+)
+
+replace example.com/lib/money => ../../libs/money
+"#,
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("services/api/vendor/modules.txt"),
+        "# github.com/acme/log v1.2.0\n## explicit; go 1.20\ngithub.com/acme/log\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("services/api/vendor/github.com/acme/log/log.go"),
+        "package log\n\ntype Logger struct{}\n\nfunc New() *Logger { return &Logger{} }\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("services/api/handlers/checkout.go"),
+        r#"package handlers
+
+import (
+	"fmt"
+
+	"example.com/lib/money"
+	"example.com/shop/auth/token"
+	"github.com/acme/log"
+)
+
+func Checkout(total money.Amount, raw string) token.Claims {
+	log.New()
+	fmt.Println(total)
+	return token.Parse(raw)
+}
+"#,
+    )
+    .unwrap();
+
+    fs::write(
+        project_dir.join("libs/money/go.mod"),
+        "module example.com/lib/money\n\ngo 1.22\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("libs/money/money.go"),
+        "package money\n\ntype Amount struct{ Cents int64 }\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("libs/auth/go.mod"),
+        "module example.com/shop/auth\n\ngo 1.22\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("libs/auth/token/token.go"),
+        "package token\n\ntype Claims struct{ Sub string }\n\nfunc Parse(s string) Claims { return Claims{Sub: s} }\n",
+    )
+    .unwrap();
+}
+
+/// Go import tracing resolves `go.work` modules, local `replace` directives, and packages
+/// listed in `vendor/modules.txt`.
+#[test]
+fn test_e2e_go_trace_imports_through_workspace_replace_and_vendor() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path();
+    create_go_workspace(project_dir);
+
+    let output_file = temp_dir.path().join("go_workspace_imports.paths");
+    let mut cmd = Command::cargo_bin("context-creator").unwrap();
+    cmd.current_dir(project_dir)
+        .arg("--include")
+        .arg("services/api/handlers/checkout.go")
+        .arg("--trace-imports")
+        .arg("--style")
+        .arg("paths")
+        .arg("--output-file")
+        .arg(&output_file);
+
+    cmd.assert().success();
+    let content = fs::read_to_string(&output_file).unwrap();
+    let paths = content.lines().collect::<Vec<_>>();
+
+    assert!(paths.contains(&"services/api/handlers/checkout.go"));
+    assert!(paths.contains(&"libs/money/money.go"));
+    assert!(paths.contains(&"libs/auth/token/token.go"));
+    assert!(paths.contains(&"services/api/vendor/github.com/acme/log/log.go"));
+}
+
+/// Go type expansion finds types in packages of replaced and workspace modules.
+#[test]
+fn test_e2e_go_include_types_across_workspace_modules() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path();
+    create_go_workspace(project_dir);
+
+    let output_file = temp_dir.path().join("go_workspace_types.paths");
+    let mut cmd = Command::cargo_bin("context-creator").unwrap();
+    cmd.current_dir(project_dir)
+        .arg("--include")
+        .arg("services/api/handlers/checkout.go")
+        .arg("--include-types")
+        .arg("--style")
+        .arg("paths")
+        .arg("--output-file")
+        .arg(&output_file);
+
+    cmd.assert().success();
+    let content = fs::read_to_string(&output_file).unwrap();
+    let paths = content.lines().collect::<Vec<_>>();
+
+    assert!(paths.contains(&"services/api/handlers/checkout.go"));
+    assert!(paths.contains(&"libs/money/money.go"));
+    assert!(paths.contains(&"libs/auth/token/token.go"));
+}
+
+/// Create a Swift package whose `CoreKit` target lives in `Sources/Core`, excludes a legacy
+/// directory, and depends on a product of a local path package
+fn create_swift_package_graph(project_dir: &Path) {
+    fs::create_dir_all(project_dir.join("App/Sources/Core/Legacy")).unwrap();
+    fs::create_dir_all(project_dir.join("App/Sources/Main")).unwrap();
+    fs::create_dir_all(project_dir.join("App/Tests/CoreTests")).unwrap();
+    fs::create_dir_all(project_dir.join("Packages/Networking/Sources/NetClient")).unwrap();
+
+    fs::write(
+        project_dir.join("App/Package.swift"),
+        r#"// swift-tools-version:5.9
+import PackageDescription
+
+let package = Package(
+    name: "App",
+    dependencies: [
+        .package(path: "../Packages/Networking"),
+    ],
+    targets: [
+        .target(
+            name: "CoreKit",
+            dependencies: [.product(name: "Networking", package: "Networking")],
+            path: "Sources/Core",
+            exclude: ["Legacy"]
+        ),
+        .executableTarget(name: "Main", dependencies: ["CoreKit"]),
+        .testTarget(name: "CoreTests", dependencies: [.target(name: "CoreKit")]),
+    ]
+)
+"#,
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("App/Sources/Core/Store.swift"),
+        "import Networking\n\npublic struct Store {\n    let client: HTTPClient\n}\n\npublic func makeStore() -> Store { Store(client: HTTPClient()) }\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("App/Sources/Core/Cache.swift"),
+        "public struct Cache {}\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("App/Sources/Core/Legacy/OldStore.swift"),
+        "public struct OldStore {}\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("App/Sources/Main/main.swift"),
+        "import CoreKit\n\nlet store = makeStore()\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("App/Tests/CoreTests/StoreTests.swift"),
+        "import XCTest\n\nfinal class StoreTests: XCTestCase {\n    func testMake() { _ = makeStore() }\n}\n",
+    )
+    .unwrap();
+
+    fs::write(
+        project_dir.join("Packages/Networking/Package.swift"),
+        r#"// swift-tools-version:5.9
+import PackageDescription
+
+let package = Package(
+    name: "Networking",
+    products: [.library(name: "Networking", targets: ["NetClient"])],
+    targets: [.target(name: "NetClient")]
+)
+"#,
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("Packages/Networking/Sources/NetClient/HTTPClient.swift"),
+        "public struct HTTPClient {\n    public init() {}\n}\n",
+    )
+    .unwrap();
+}
+
+/// Swift import tracing resolves modules through `Package.swift` target names, `path:` and
+/// `exclude:` overrides, and products of local path packages.
+#[test]
+fn test_e2e_swift_trace_imports_through_package_targets() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path();
+    create_swift_package_graph(project_dir);
+
+    let output_file = temp_dir.path().join("swift_package_imports.paths");
+    let mut cmd = Command::cargo_bin("context-creator").unwrap();
+    cmd.current_dir(project_dir)
+        .arg("--include")
+        .arg("App/Sources/Main/main.swift")
+        .arg("--trace-imports")
+        .arg("--style")
+        .arg("paths")
+        .arg("--output-file")
+        .arg(&output_file);
+
+    cmd.assert().success();
+    let content = fs::read_to_string(&output_file).unwrap();
+    let paths = content.lines().collect::<Vec<_>>();
+
+    assert!(paths.contains(&"App/Sources/Main/main.swift"));
+    assert!(paths.contains(&"App/Sources/Core/Store.swift"));
+    assert!(paths.contains(&"App/Sources/Core/Cache.swift"));
+    assert!(paths.contains(&"Packages/Networking/Sources/NetClient/HTTPClient.swift"));
+    assert!(!paths.contains(&"App/Sources/Core/Legacy/OldStore.swift"));
+}
+
+/// Swift test targets are linked to the targets they depend on in `Package.swift`, both when
+/// tracing a test's imports and when looking for callers of the tested target.
+#[test]
+fn test_e2e_swift_test_targets_link_to_tested_targets() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path();
+    create_swift_package_graph(project_dir);
+
+    let output_file = temp_dir.path().join("swift_test_target_imports.paths");
+    let mut cmd = Command::cargo_bin("context-creator").unwrap();
+    cmd.current_dir(project_dir)
+        .arg("--include")
+        .arg("App/Tests/CoreTests/StoreTests.swift")
+        .arg("--trace-imports")
+        .arg("--style")
+        .arg("paths")
+        .arg("--output-file")
+        .arg(&output_file);
+
+    cmd.assert().success();
+    let content = fs::read_to_string(&output_file).unwrap();
+    let paths = content.lines().collect::<Vec<_>>();
+
+    assert!(paths.contains(&"App/Tests/CoreTests/StoreTests.swift"));
+    assert!(paths.contains(&"App/Sources/Core/Store.swift"));
+
+    let output_file = temp_dir.path().join("swift_test_target_callers.paths");
+    let mut cmd = Command::cargo_bin("context-creator").unwrap();
+    cmd.current_dir(project_dir)
+        .arg("--include")
+        .arg("App/Sources/Core/Store.swift")
+        .arg("--include-callers")
+        .arg("--style")
+        .arg("paths")
+        .arg("--output-file")
+        .arg(&output_file);
+
+    cmd.assert().success();
+    let content = fs::read_to_string(&output_file).unwrap();
+    let paths = content.lines().collect::<Vec<_>>();
+
+    assert!(paths.contains(&"App/Sources/Core/Store.swift"));
+    assert!(paths.contains(&"App/Sources/Main/main.swift"));
+    assert!(paths.contains(&"App/Tests/CoreTests/StoreTests.swift"));
+}
+
+/// Create a Node project mixing ES barrel files, CommonJS `require()` and `module.exports`
+/// barrels, and a dynamic `import()`
+fn create_node_barrel_project(project_dir: &Path) {
+    fs::create_dir_all(project_dir.join("src/components/forms")).unwrap();
+    fs::create_dir_all(project_dir.join("src/services")).unwrap();
+    fs::create_dir_all(project_dir.join("src/helpers")).unwrap();
+    fs::create_dir_all(project_dir.join("src/pages")).unwrap();
+
+    fs::write(
+        project_dir.join("src/components/index.ts"),
+        "export * from './Button';\nexport { Modal as Dialog } from './Modal';\nexport * from './forms';\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("src/components/forms/index.ts"),
+        "export { TextField } from './TextField';\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("src/components/forms/TextField.ts"),
+        "export function TextField(label: string) { return label; }\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("src/components/Button.ts"),
+        "export interface ButtonProps { label: string }\n\nexport function Button(props: ButtonProps) { return props.label; }\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("src/components/Modal.ts"),
+        "export function Modal() { return 'modal'; }\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("src/components/Table.ts"),
+        "export function Table() { return 'table'; }\n",
+    )
+    .unwrap();
+
+    fs::write(
+        project_dir.join("src/services/index.js"),
+        "const { sendMail } = require('./mail');\n\nmodule.exports = {\n  sendMail,\n  sms: require('./sms'),\n};\nmodule.exports.audit = require('./audit');\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("src/services/mail.js"),
+        "module.exports.sendMail = function (to) { return to; };\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("src/services/sms.js"),
+        "module.exports = function sms(to) { return to; };\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("src/services/audit.js"),
+        "module.exports = function audit() {};\n",
+    )
+    .unwrap();
+
+    fs::write(
+        project_dir.join("src/helpers/settings.js"),
+        "module.exports = { theme: 'dark' };\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("src/helpers/chart.js"),
+        "export function Chart() { return 'chart'; }\n",
+    )
+    .unwrap();
+
+    fs::write(
+        project_dir.join("src/pages/home.ts"),
+        r#"import { Button, ButtonProps } from '../components';
+import { TextField } from '../components';
+
+export function render(props: ButtonProps) {
+  return Button(props) + TextField('name');
+}
+"#,
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("src/pages/notify.js"),
+        r#"const settings = require('../helpers/settings');
+const { sendMail } = require('../services');
+
+async function notify(to) {
+  const { Chart } = await import('../helpers/chart');
+  return sendMail(to) + settings.theme + Chart();
+}
+
+module.exports.notify = notify;
+"#,
+    )
+    .unwrap();
+}
+
+/// Import tracing follows barrel re-exports to the files defining the imported names
+/// instead of pulling in everything the barrel re-exports.
+#[test]
+fn test_e2e_typescript_trace_imports_through_barrel_reexports() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path();
+    create_node_barrel_project(project_dir);
+
+    let output_file = temp_dir.path().join("ts_barrel_imports.paths");
+    let mut cmd = Command::cargo_bin("context-creator").unwrap();
+    cmd.current_dir(project_dir)
+        .arg("--include")
+        .arg("src/pages/home.ts")
+        .arg("--trace-imports")
+        .arg("--style")
+        .arg("paths")
+        .arg("--output-file")
+        .arg(&output_file);
+
+    cmd.assert().success();
+    let content = fs::read_to_string(&output_file).unwrap();
+    let paths = content.lines().collect::<Vec<_>>();
+
+    assert!(paths.contains(&"src/pages/home.ts"));
+    assert!(paths.contains(&"src/components/Button.ts"));
+    assert!(paths.contains(&"src/components/forms/TextField.ts"));
+    assert!(!paths.contains(&"src/components/index.ts"));
+    assert!(!paths.contains(&"src/components/Modal.ts"));
+    assert!(!paths.contains(&"src/components/Table.ts"));
+}
+
+/// Import tracing extracts `require()`, destructured requires, and `import()` with a
+/// literal specifier, and follows `module.exports` barrels.
+#[test]
+fn test_e2e_javascript_trace_imports_through_commonjs_and_dynamic_imports() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path();
+    create_node_barrel_project(project_dir);
+
+    let output_file = temp_dir.path().join("js_commonjs_imports.paths");
+    let mut cmd = Command::cargo_bin("context-creator").unwrap();
+    cmd.current_dir(project_dir)
+        .arg("--include")
+        .arg("src/pages/notify.js")
+        .arg("--trace-imports")
+        .arg("--style")
+        .arg("paths")
+        .arg("--output-file")
+        .arg(&output_file);
+
+    cmd.assert().success();
+    let content = fs::read_to_string(&output_file).unwrap();
+    let paths = content.lines().collect::<Vec<_>>();
+
+    assert!(paths.contains(&"src/pages/notify.js"));
+    assert!(paths.contains(&"src/helpers/settings.js"));
+    assert!(paths.contains(&"src/helpers/chart.js"));
+    assert!(paths.contains(&"src/services/mail.js"));
+    assert!(!paths.contains(&"src/services/index.js"));
+    assert!(!paths.contains(&"src/services/sms.js"));
+    assert!(!paths.contains(&"src/services/audit.js"));
+}
+
+/// Callers of a module re-exported by a barrel include the files importing it through
+/// the barrel.
+#[test]
+fn test_e2e_typescript_include_callers_through_barrel_reexports() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path();
+    create_node_barrel_project(project_dir);
+
+    let output_file = temp_dir.path().join("ts_barrel_callers.paths");
+    let mut cmd = Command::cargo_bin("context-creator").unwrap();
+    cmd.current_dir(project_dir)
+        .arg("--include")
+        .arg("src/components/forms/TextField.ts")
+        .arg("--include-callers")
+        .arg("--style")
+        .arg("paths")
+        .arg("--output-file")
+        .arg(&output_file);
+
+    cmd.assert().success();
+    let content = fs::read_to_string(&output_file).unwrap();
+    let paths = content.lines().collect::<Vec<_>>();
+
+    assert!(paths.contains(&"src/components/forms/TextField.ts"));
+    assert!(paths.contains(&"src/pages/home.ts"));
+}
+
+/// Create a frontend with Vue, Svelte, and Astro components, including Vue components used
+/// in a template without an import
+fn create_frontend_components(project_dir: &Path) {
+    fs::create_dir_all(project_dir.join("src/components/base")).unwrap();
+    fs::create_dir_all(project_dir.join("src/utils")).unwrap();
+    fs::create_dir_all(project_dir.join("src/pages")).unwrap();
+
+    fs::write(
+        project_dir.join("src/main.ts"),
+        "import { createApp } from 'vue';\nimport App from './App.vue';\n\ncreateApp(App).mount('#app');\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("src/App.vue"),
+        r#"<template>
+  <div>
+    <UserCard :user="user" />
+    <base-button @click="refresh">Reload</base-button>
+  </div>
+</template>
+
+<script setup lang="ts">
+import { ref } from 'vue';
+import { formatName } from './utils/format';
+
+const user = ref({ name: formatName('ada') });
+function refresh() {
+  user.value = { name: formatName('grace') };
+}
+</script>
+"#,
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("src/components/UserCard.vue"),
+        "<template><p>{{ user.name }}</p></template>\n<script>\nexport default { props: ['user'] };\n</script>\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("src/components/base/Button.vue"),
+        "<template><button><slot /></button></template>\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("src/components/Unused.vue"),
+        "<template><p>unused</p></template>\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("src/utils/format.ts"),
+        "export function formatName(name: string): string {\n  return name.toUpperCase();\n}\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("src/Counter.svelte"),
+        r#"<script lang="ts">
+  import Badge from './Badge.svelte';
+  let count = 0;
+</script>
+
+<Badge value={count} />
+"#,
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("src/Badge.svelte"),
+        "<script>\n  export let value;\n</script>\n<span>{value}</span>\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("src/pages/index.astro"),
+        "---\nimport Layout from '../Layout.astro';\nimport { formatName } from '../utils/format';\nconst title = formatName('home');\n---\n<Layout title={title}><h1>{title}</h1></Layout>\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("src/Layout.astro"),
+        "---\nconst { title } = Astro.props;\n---\n<html><body><slot /></body></html>\n",
+    )
+    .unwrap();
+}
+
+/// Import tracing reads the `<script setup>` block of a Vue component and turns components
+/// used in its template into dependencies
+#[test]
+fn test_e2e_vue_trace_imports_from_scripts_and_templates() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path();
+    create_frontend_components(project_dir);
+
+    let output_file = temp_dir.path().join("vue_imports.paths");
+    let mut cmd = Command::cargo_bin("context-creator").unwrap();
+    cmd.current_dir(project_dir)
+        .arg("--include")
+        .arg("src/main.ts")
+        .arg("--trace-imports")
+        .arg("--style")
+        .arg("paths")
+        .arg("--output-file")
+        .arg(&output_file);
+
+    cmd.assert().success();
+    let content = fs::read_to_string(&output_file).unwrap();
+    let paths = content.lines().collect::<Vec<_>>();
+
+    assert!(paths.contains(&"src/main.ts"));
+    assert!(paths.contains(&"src/App.vue"));
+    assert!(paths.contains(&"src/utils/format.ts"));
+    assert!(paths.contains(&"src/components/UserCard.vue"));
+    assert!(paths.contains(&"src/components/base/Button.vue"));
+    assert!(!paths.contains(&"src/components/Unused.vue"));
+}
+
+/// Import tracing reads Svelte `<script>` blocks and Astro frontmatter
+#[test]
+fn test_e2e_svelte_and_astro_trace_imports() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path();
+    create_frontend_components(project_dir);
+
+    let output_file = temp_dir.path().join("svelte_astro_imports.paths");
+    let mut cmd = Command::cargo_bin("context-creator").unwrap();
+    cmd.current_dir(project_dir)
+        .arg("--include")
+        .arg("src/Counter.svelte")
+        .arg("--include")
+        .arg("src/pages/index.astro")
+        .arg("--trace-imports")
+        .arg("--style")
+        .arg("paths")
+        .arg("--output-file")
+        .arg(&output_file);
+
+    cmd.assert().success();
+    let content = fs::read_to_string(&output_file).unwrap();
+    let paths = content.lines().collect::<Vec<_>>();
+
+    assert!(paths.contains(&"src/Badge.svelte"));
+    assert!(paths.contains(&"src/Layout.astro"));
+    assert!(paths.contains(&"src/utils/format.ts"));
+}
+
+/// Callers of a TypeScript function include the components calling it from their scripts
+#[test]
+fn test_e2e_include_callers_from_component_scripts() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path();
+    create_frontend_components(project_dir);
+
+    let output_file = temp_dir.path().join("component_callers.paths");
+    let mut cmd = Command::cargo_bin("context-creator").unwrap();
+    cmd.current_dir(project_dir)
+        .arg("--include")
+        .arg("src/utils/format.ts")
+        .arg("--include-callers")
+        .arg("--style")
+        .arg("paths")
+        .arg("--output-file")
+        .arg(&output_file);
+
+    cmd.assert().success();
+    let content = fs::read_to_string(&output_file).unwrap();
+    let paths = content.lines().collect::<Vec<_>>();
+
+    assert!(paths.contains(&"src/utils/format.ts"));
+    assert!(paths.contains(&"src/App.vue"));
+    assert!(paths.contains(&"src/pages/index.astro"));
+    assert!(!paths.contains(&"src/Counter.svelte"));
+}
+
+/// Create a service whose handlers, models, and frontend are generated from or driven by
+/// Protobuf, SQL, GraphQL, and OpenAPI schemas
+fn create_schema_project(project_dir: &Path) {
+    fs::create_dir_all(project_dir.join("proto/users/v1")).unwrap();
+    fs::create_dir_all(project_dir.join("db/migrations")).unwrap();
+    fs::create_dir_all(project_dir.join("server")).unwrap();
+    fs::create_dir_all(project_dir.join("models")).unwrap();
+    fs::create_dir_all(project_dir.join("web/graphql")).unwrap();
+    fs::create_dir_all(project_dir.join("api")).unwrap();
+
+    fs::write(
+        project_dir.join("proto/users/v1/user.proto"),
+        r#"syntax = "proto3";
+package users.v1;
+
+import "users/v1/common.proto";
+import "google/protobuf/timestamp.proto";
+
+message User {
+  string id = 1;
+  Metadata metadata = 2;
+}
+
+service UserService {
+  rpc GetUser(GetUserRequest) returns (User);
+}
+
+message GetUserRequest { string id = 1; }
+"#,
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("proto/users/v1/common.proto"),
+        "syntax = \"proto3\";\npackage users.v1;\n\nmessage Metadata { string trace_id = 1; }\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("server/handler.go"),
+        r#"package server
+
+import pb "example.com/gen/users/v1"
+
+type Handler struct {
+	pb.UnimplementedUserServiceServer
+}
+
+func (h *Handler) GetUser(ctx context.Context, req *pb.GetUserRequest) (*pb.User, error) {
+	return &pb.User{Id: req.Id}, nil
+}
+"#,
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("server/health.go"),
+        "package server\n\nfunc Healthy() bool {\n\treturn true\n}\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("db/migrations/001_create_users.sql"),
+        "CREATE TABLE IF NOT EXISTS public.users (\n    id UUID PRIMARY KEY,\n    email TEXT NOT NULL\n);\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("models/user.py"),
+        "from sqlalchemy import Column, String\n\n\nclass Account(Base):\n    __tablename__ = \"users\"\n    email = Column(String)\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("web/graphql/viewer.graphql"),
+        "#import \"./fragments.graphql\"\n\nquery GetViewer {\n  viewer {\n    ...ViewerFields\n  }\n}\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("web/graphql/fragments.graphql"),
+        "fragment ViewerFields on Viewer {\n  id\n  name\n}\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("web/Profile.tsx"),
+        "import { useGetViewerQuery } from './gen/graphql';\n\nexport function Profile() {\n  const { data } = useGetViewerQuery();\n  return data;\n}\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("api/openapi.yaml"),
+        r#"openapi: 3.0.0
+paths:
+  /pets:
+    get:
+      operationId: listPets
+      responses:
+        '200':
+          content:
+            application/json:
+              schema:
+                $ref: "./pet.yaml#/Pet"
+"#,
+    )
+    .unwrap();
+    fs::write(project_dir.join("api/pet.yaml"), "Pet:\n  type: object\n").unwrap();
+    fs::write(
+        project_dir.join("web/pets.ts"),
+        "export async function loadPets(api: any) {\n  return api.listPets();\n}\n",
+    )
+    .unwrap();
+}
+
+/// Import tracing links handlers and models to the schemas whose generated names and tables
+/// they use, and follows imports between schemas
+#[test]
+fn test_e2e_trace_imports_links_code_to_schemas() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path();
+    create_schema_project(project_dir);
+
+    let output_file = temp_dir.path().join("schema_imports.paths");
+    let mut cmd = Command::cargo_bin("context-creator").unwrap();
+    cmd.current_dir(project_dir)
+        .arg("--include")
+        .arg("server/handler.go")
+        .arg("--include")
+        .arg("models/user.py")
+        .arg("--include")
+        .arg("web/Profile.tsx")
+        .arg("--include")
+        .arg("web/pets.ts")
+        .arg("--trace-imports")
+        .arg("--style")
+        .arg("paths")
+        .arg("--output-file")
+        .arg(&output_file);
+
+    cmd.assert().success();
+    let content = fs::read_to_string(&output_file).unwrap();
+    let paths = content.lines().collect::<Vec<_>>();
+
+    assert!(paths.contains(&"proto/users/v1/user.proto"));
+    assert!(paths.contains(&"proto/users/v1/common.proto"));
+    assert!(paths.contains(&"db/migrations/001_create_users.sql"));
+    assert!(paths.contains(&"web/graphql/viewer.graphql"));
+    assert!(paths.contains(&"web/graphql/fragments.graphql"));
+    assert!(paths.contains(&"api/openapi.yaml"));
+    assert!(paths.contains(&"api/pet.yaml"));
+    assert!(!paths.contains(&"server/health.go"));
+}
+
+/// Callers of a Protobuf schema or a migration are the handlers and models using them
+#[test]
+fn test_e2e_include_callers_of_schemas() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path();
+    create_schema_project(project_dir);
+
+    let output_file = temp_dir.path().join("schema_callers.paths");
+    let mut cmd = Command::cargo_bin("context-creator").unwrap();
+    cmd.current_dir(project_dir)
+        .arg("--include")
+        .arg("proto/users/v1/user.proto")
+        .arg("--include")
+        .arg("db/migrations/001_create_users.sql")
+        .arg("--include-callers")
+        .arg("--style")
+        .arg("paths")
+        .arg("--output-file")
+        .arg(&output_file);
+
+    cmd.assert().success();
+    let content = fs::read_to_string(&output_file).unwrap();
+    let paths = content.lines().collect::<Vec<_>>();
+
+    assert!(paths.contains(&"server/handler.go"));
+    assert!(paths.contains(&"models/user.py"));
+    assert!(!paths.contains(&"server/health.go"));
+    assert!(!paths.contains(&"web/Profile.tsx"));
+}
+
+/// Create a project deployed by a GitHub Actions workflow that builds a Docker image and runs
+/// scripts, with a Makefile and `package.json` scripts running more of them
+fn create_deploy_pipeline(project_dir: &Path) {
+    fs::create_dir_all(project_dir.join(".github/workflows")).unwrap();
+    fs::create_dir_all(project_dir.join(".github/actions/setup")).unwrap();
+    fs::create_dir_all(project_dir.join("docker")).unwrap();
+    fs::create_dir_all(project_dir.join("scripts")).unwrap();
+    fs::create_dir_all(project_dir.join("src")).unwrap();
+
+    fs::write(
+        project_dir.join(".github/workflows/deploy.yml"),
+        r#"name: deploy
+on:
+  push:
+    branches:
+      - main
+jobs:
+  deploy:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/checkout@v4
+      - uses: ./.github/actions/setup
+      - name: Build image
+        uses: docker/build-push-action@v5
+        with:
+          file: ./docker/Dockerfile
+      - name: Release
+        run: |
+          ./scripts/deploy.sh production
+"#,
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join(".github/actions/setup/action.yml"),
+        "runs:\n  using: composite\n  steps:\n    - run: ./scripts/setup.sh\n      shell: bash\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("docker/Dockerfile"),
+        "FROM node:20\nWORKDIR /app\nCOPY package*.json ./\nCOPY src/ ./src/\nRUN npm ci && ./scripts/build.sh\nENTRYPOINT [\"./docker/entrypoint.sh\"]\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("docker/entrypoint.sh"),
+        "exec node src/server.js\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("package.json"),
+        "{\n  \"name\": \"app\",\n  \"scripts\": {\n    \"build\": \"./scripts/build.sh\"\n  }\n}\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("Makefile"),
+        ".PHONY: test\ntest:\n\t./scripts/test.sh\n",
+    )
+    .unwrap();
+    for script in ["deploy", "setup", "build", "test"] {
+        fs::write(
+            project_dir.join(format!("scripts/{script}.sh")),
+            format!("#!/bin/sh\necho {script}\n"),
+        )
+        .unwrap();
+    }
+    fs::write(project_dir.join("src/server.js"), "console.log('up');\n").unwrap();
+}
+
+/// Import tracing from a workflow brings the local action, the Dockerfile, and the scripts
+/// they run
+#[test]
+fn test_e2e_trace_imports_from_deploy_workflow() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path();
+    create_deploy_pipeline(project_dir);
+
+    let output_file = temp_dir.path().join("workflow_imports.paths");
+    let mut cmd = Command::cargo_bin("context-creator").unwrap();
+    cmd.current_dir(project_dir)
+        .arg("--include")
+        .arg(".github/workflows/deploy.yml")
+        .arg("--trace-imports")
+        .arg("--style")
+        .arg("paths")
+        .arg("--output-file")
+        .arg(&output_file);
+
+    cmd.assert().success();
+    let content = fs::read_to_string(&output_file).unwrap();
+    let paths = content.lines().collect::<Vec<_>>();
+
+    assert!(paths.contains(&".github/workflows/deploy.yml"));
+    assert!(paths.contains(&".github/actions/setup/action.yml"));
+    assert!(paths.contains(&"scripts/setup.sh"));
+    assert!(paths.contains(&"docker/Dockerfile"));
+    assert!(paths.contains(&"docker/entrypoint.sh"));
+    assert!(paths.contains(&"package.json"));
+    assert!(paths.contains(&"scripts/build.sh"));
+    assert!(paths.contains(&"scripts/deploy.sh"));
+    assert!(!paths.contains(&"scripts/test.sh"));
+}
+
+/// A workflow building with `docker build` and `make` brings the Dockerfile and Makefile they
+/// read, named bare or with `-f`
+#[test]
+fn test_e2e_trace_imports_from_workflow_to_dockerfile_and_makefile() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path();
+    fs::create_dir_all(project_dir.join(".github/workflows")).unwrap();
+    fs::create_dir_all(project_dir.join("worker")).unwrap();
+    fs::create_dir_all(project_dir.join("docs")).unwrap();
+    fs::write(
+        project_dir.join(".github/workflows/ci.yml"),
+        r#"name: ci
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: docker/build-push-action@v5
+        with:
+          file: Dockerfile
+      - run: docker build -t app .
+      - run: docker build -f worker/Dockerfile.worker -t worker worker
+      - run: make build
+      - run: make -C docs html
+"#,
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("Dockerfile"),
+        "FROM alpine:3
+",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("worker/Dockerfile.worker"),
+        "FROM alpine:3
+",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("Makefile"),
+        "build:
+	echo build
+",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("docs/Makefile"),
+        "html:
+	echo html
+",
+    )
+    .unwrap();
+
+    let output_file = temp_dir.path().join("build_files.paths");
+    let mut cmd = Command::cargo_bin("context-creator").unwrap();
+    cmd.current_dir(project_dir)
+        .arg("--include")
+        .arg(".github/workflows/ci.yml")
+        .arg("--trace-imports")
+        .arg("--style")
+        .arg("paths")
+        .arg("--output-file")
+        .arg(&output_file);
+
+    cmd.assert().success();
+    let content = fs::read_to_string(&output_file).unwrap();
+    let paths = content.lines().collect::<Vec<_>>();
+
+    assert!(paths.contains(&"Dockerfile"));
+    assert!(paths.contains(&"worker/Dockerfile.worker"));
+    assert!(paths.contains(&"Makefile"));
+    assert!(paths.contains(&"docs/Makefile"));
+}
+
+/// Callers of a script are the workflows, Makefiles, and `package.json` scripts running it
+#[test]
+fn test_e2e_include_callers_of_build_scripts() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path();
+    create_deploy_pipeline(project_dir);
+
+    let output_file = temp_dir.path().join("script_callers.paths");
+    let mut cmd = Command::cargo_bin("context-creator").unwrap();
+    cmd.current_dir(project_dir)
+        .arg("--include")
+        .arg("scripts/build.sh")
+        .arg("--include")
+        .arg("scripts/test.sh")
+        .arg("--include-callers")
+        .arg("--style")
+        .arg("paths")
+        .arg("--output-file")
+        .arg(&output_file);
+
+    cmd.assert().success();
+    let content = fs::read_to_string(&output_file).unwrap();
+    let paths = content.lines().collect::<Vec<_>>();
+
+    assert!(paths.contains(&"docker/Dockerfile"));
+    assert!(paths.contains(&"package.json"));
+    assert!(paths.contains(&"Makefile"));
+    assert!(!paths.contains(&".github/workflows/deploy.yml"));
+}
+
+/// Workflows are hidden files, so they are only pulled in through the scripts they run
+#[test]
+fn test_e2e_workflows_are_included_only_as_callers() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path();
+    create_deploy_pipeline(project_dir);
+
+    let output_file = temp_dir.path().join("plain.paths");
+    let mut cmd = Command::cargo_bin("context-creator").unwrap();
+    cmd.current_dir(project_dir)
+        .arg("--style")
+        .arg("paths")
+        .arg("--output-file")
+        .arg(&output_file);
+    cmd.assert().success();
+    let content = fs::read_to_string(&output_file).unwrap();
+    assert!(content.contains("scripts/deploy.sh"));
+    assert!(!content.contains(".github/"));
+
+    let output_file = temp_dir.path().join("deploy_callers.paths");
+    let mut cmd = Command::cargo_bin("context-creator").unwrap();
+    cmd.current_dir(project_dir)
+        .arg("--include")
+        .arg("scripts/deploy.sh")
+        .arg("--include-callers")
+        .arg("--style")
+        .arg("paths")
+        .arg("--output-file")
+        .arg(&output_file);
+    cmd.assert().success();
+    let content = fs::read_to_string(&output_file).unwrap();
+    let paths = content.lines().collect::<Vec<_>>();
+    assert!(paths.contains(&".github/workflows/deploy.yml"));
+    assert!(!paths.contains(&".github/actions/setup/action.yml"));
+}
+
+/// Create a Terraform repository with production and staging environments built from local
+/// modules
+fn create_terraform_environments(project_dir: &Path) {
+    fs::create_dir_all(project_dir.join("envs/prod")).unwrap();
+    fs::create_dir_all(project_dir.join("envs/staging")).unwrap();
+    fs::create_dir_all(project_dir.join("modules/vpc")).unwrap();
+    fs::create_dir_all(project_dir.join("modules/app/templates")).unwrap();
+    fs::create_dir_all(project_dir.join("modules/cache")).unwrap();
+
+    fs::write(
+        project_dir.join("envs/prod/main.tf"),
+        r#"module "vpc" {
+  source = "../../modules/vpc"
+  cidr   = var.vpc_cidr
+}
+
+module "app" {
+  source  = "../../modules/app"
+  subnets = module.vpc.private_subnets
+  role    = aws_iam_role.app.arn
+}
+"#,
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("envs/prod/iam.tf"),
+        "resource \"aws_iam_role\" \"app\" {\n  name = \"app-${var.environment}\"\n}\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("envs/prod/variables.tf"),
+        "variable \"vpc_cidr\" {}\nvariable \"environment\" {}\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("envs/prod/terraform.tfvars"),
+        "vpc_cidr    = \"10.0.0.0/16\"\nenvironment = \"prod\"\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("envs/staging/main.tf"),
+        "module \"cache\" {\n  source = \"../../modules/cache\"\n}\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("modules/vpc/main.tf"),
+        "resource \"aws_vpc\" \"this\" {\n  cidr_block = var.cidr\n}\n\nresource \"aws_subnet\" \"private\" {\n  vpc_id = aws_vpc.this.id\n}\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("modules/vpc/variables.tf"),
+        "variable \"cidr\" {}\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("modules/vpc/outputs.tf"),
+        "output \"private_subnets\" {\n  value = aws_subnet.private[*].id\n}\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("modules/app/main.tf"),
+        "resource \"aws_instance\" \"app\" {\n  subnet_id = var.subnets[0]\n  user_data = templatefile(\"${path.module}/templates/init.sh.tpl\", { role = var.role })\n}\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("modules/app/variables.tf"),
+        "variable \"subnets\" {}\nvariable \"role\" {}\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("modules/app/templates/init.sh.tpl"),
+        "#!/bin/sh\necho ${role}\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("modules/cache/main.tf"),
+        "resource \"aws_elasticache_cluster\" \"this\" {}\n",
+    )
+    .unwrap();
+}
+
+/// Import tracing from an environment gathers the modules it calls, the variables and
+/// resources its files use from each other, and the templates the modules read
+#[test]
+fn test_e2e_terraform_trace_imports_from_environment() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path();
+    create_terraform_environments(project_dir);
+
+    let output_file = temp_dir.path().join("terraform_imports.paths");
+    let mut cmd = Command::cargo_bin("context-creator").unwrap();
+    cmd.current_dir(project_dir)
+        .arg("--include")
+        .arg("envs/prod/main.tf")
+        .arg("--trace-imports")
+        .arg("--style")
+        .arg("paths")
+        .arg("--output-file")
+        .arg(&output_file);
+
+    cmd.assert().success();
+    let content = fs::read_to_string(&output_file).unwrap();
+    let paths = content.lines().collect::<Vec<_>>();
+
+    assert!(paths.contains(&"envs/prod/iam.tf"));
+    assert!(paths.contains(&"envs/prod/variables.tf"));
+    assert!(paths.contains(&"envs/prod/terraform.tfvars"));
+    assert!(paths.contains(&"modules/vpc/main.tf"));
+    assert!(paths.contains(&"modules/vpc/outputs.tf"));
+    assert!(paths.contains(&"modules/vpc/variables.tf"));
+    assert!(paths.contains(&"modules/app/main.tf"));
+    assert!(paths.contains(&"modules/app/templates/init.sh.tpl"));
+    assert!(!paths.contains(&"modules/cache/main.tf"));
+    assert!(!paths.contains(&"envs/staging/main.tf"));
+}
+
+/// Callers of a module's outputs are the environments using the module
+#[test]
+fn test_e2e_terraform_include_callers_of_module() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path();
+    create_terraform_environments(project_dir);
+
+    let output_file = temp_dir.path().join("terraform_callers.paths");
+    let mut cmd = Command::cargo_bin("context-creator").unwrap();
+    cmd.current_dir(project_dir)
+        .arg("--include")
+        .arg("modules/vpc/outputs.tf")
+        .arg("--include-callers")
+        .arg("--style")
+        .arg("paths")
+        .arg("--output-file")
+        .arg(&output_file);
+
+    cmd.assert().success();
+    let content = fs::read_to_string(&output_file).unwrap();
+    let paths = content.lines().collect::<Vec<_>>();
+
+    assert!(paths.contains(&"envs/prod/main.tf"));
+    assert!(!paths.contains(&"envs/staging/main.tf"));
+}
+
+/// Create a Rust service whose handler saves users through a storage module
+fn create_user_service(project_dir: &Path) {
+    fs::create_dir_all(project_dir.join("src")).unwrap();
+    fs::write(
+        project_dir.join("Cargo.toml"),
+        "[package]\nname = \"user_service\"\nversion = \"0.1.0\"\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("src/main.rs"),
+        r#"mod api;
+mod db;
+mod models;
+
+fn main() {
+    api::handle("ada".to_string());
+}
+"#,
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("src/api.rs"),
+        r#"use crate::db::save;
+use crate::models::User;
+
+pub fn handle(name: String) -> bool {
+    let user = User::new(name);
+    save(&user)
+}
+"#,
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("src/db.rs"),
+        r#"use crate::models::User;
+
+pub fn save(user: &User) -> bool {
+    !user.name.is_empty()
+}
+"#,
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("src/models.rs"),
+        r#"pub struct User {
+    pub name: String,
+}
+
+impl User {
+    pub fn new(name: String) -> Self {
+        Self { name }
+    }
+}
+"#,
+    )
+    .unwrap();
+}
+
+/// Verbose runs explain which symbol caused each expanded file to be included
+#[test]
+fn test_e2e_verbose_expansion_names_the_symbols_including_each_file() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path();
+    create_user_service(project_dir);
+
+    let output_file = temp_dir.path().join("explained.paths");
+    let mut cmd = Command::cargo_bin("context-creator").unwrap();
+    cmd.current_dir(project_dir)
+        .arg("--include")
+        .arg("src/api.rs")
+        .arg("--trace-imports")
+        .arg("--style")
+        .arg("paths")
+        .arg("--output-file")
+        .arg(&output_file)
+        .arg("-v");
+
+    cmd.assert()
+        .success()
+        .stderr(predicate::str::contains(
+            "Included src/db.rs: function `handle` in api.rs calls function `save` in db.rs (line 6)",
+        ))
+        .stderr(predicate::str::contains(
+            "Included src/models.rs: function `handle` in api.rs calls method `new` in models.rs (line 5)",
+        ));
+
+    let content = fs::read_to_string(&output_file).unwrap();
+    let paths = content.lines().collect::<Vec<_>>();
+    assert!(paths.contains(&"src/db.rs"));
+    assert!(paths.contains(&"src/models.rs"));
+}
+
+/// Callers are explained by the symbols they use in the selected files
+#[test]
+fn test_e2e_verbose_callers_name_the_calling_symbol() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path();
+    create_user_service(project_dir);
+
+    let output_file = temp_dir.path().join("callers_explained.paths");
+    let mut cmd = Command::cargo_bin("context-creator").unwrap();
+    cmd.current_dir(project_dir)
+        .arg("--include")
+        .arg("src/db.rs")
+        .arg("--include-callers")
+        .arg("--style")
+        .arg("paths")
+        .arg("--output-file")
+        .arg(&output_file)
+        .arg("-v");
+
+    cmd.assert().success().stderr(predicate::str::contains(
+        "Included src/api.rs: function `handle` in api.rs calls function `save` in db.rs (line 6)",
+    ));
+}
@@ -0,0 +1,242 @@
+#![cfg(test)]
+
+//! Tests for language packs loaded at runtime
+
+use context_creator::core::semantic::analyzer::SemanticContext;
+use context_creator::core::semantic::language_pack::{
+    register_language_pack, LanguagePack, LanguagePackManifest,
+};
+use context_creator::core::semantic::{get_analyzer_for_file, get_resolver_for_file};
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+/// Create a pack for a Lua-like DSL with `.wdgt` files, using the Lua grammar
+fn create_widget_pack(pack_dir: &Path) -> LanguagePackManifest {
+    fs::create_dir_all(pack_dir).unwrap();
+    let manifest = r#"
+name = "widget-test"
+extensions = ["wdgt"]
+
+[resolve]
+separator = "."
+paths = ["src/{module}.wdgt", "src/{module}/init.wdgt"]
+external = ["std"]
+"#;
+    fs::write(pack_dir.join("language.toml"), manifest).unwrap();
+    fs::write(
+        pack_dir.join("imports.scm"),
+        r#"(function_call
+  name: (identifier) @require_fn
+  arguments: (arguments . (string content: (string_content) @module_path) .)
+  (#eq? @require_fn "require"))
+"#,
+    )
+    .unwrap();
+    fs::write(
+        pack_dir.join("calls.scm"),
+        r#"(function_call name: (identifier) @fn_name)
+(function_call
+  name: (dot_index_expression table: (identifier) @module_name field: (identifier) @method_name))
+"#,
+    )
+    .unwrap();
+    fs::write(
+        pack_dir.join("definitions.scm"),
+        r#"(function_declaration
+  name: [(identifier) @fn_name (dot_index_expression field: (identifier) @fn_name)])
+(function_declaration "local" @private name: (identifier) @fn_name)
+"#,
+    )
+    .unwrap();
+
+    toml::from_str(manifest).unwrap()
+}
+
+#[test]
+fn test_language_pack_registers_analyzer_and_resolver() {
+    let temp_dir = TempDir::new().unwrap();
+    let pack_dir = temp_dir.path().join("packs/widget");
+    let manifest = create_widget_pack(&pack_dir);
+    register_language_pack(
+        LanguagePack::with_language(&pack_dir, manifest, tree_sitter_lua::language()).unwrap(),
+    );
+
+    let project = temp_dir.path().join("project");
+    fs::create_dir_all(project.join("src/app/ui")).unwrap();
+    let main_file = project.join("src/main.wdgt");
+    let content = "local util = require(\"app.util\")\nlocal io = require(\"std.io\")\n\nutil.greet(\"world\")\n\nlocal function helper() end\nfunction render() end\n";
+    fs::write(&main_file, content).unwrap();
+    fs::write(project.join("src/app/util.wdgt"), "return {}\n").unwrap();
+    fs::write(project.join("src/app/ui/init.wdgt"), "return {}\n").unwrap();
+
+    let analyzer = get_analyzer_for_file(&main_file)
+        .unwrap()
+        .expect("the pack should provide an analyzer");
+    assert_eq!(analyzer.language_name(), "widget-test");
+
+    let context = SemanticContext::new(main_file.clone(), project.clone(), 3);
+    let result = analyzer
+        .analyze_file(&main_file, content, &context)
+        .unwrap();
+
+    let modules: Vec<&str> = result.imports.iter().map(|i| i.module.as_str()).collect();
+    assert_eq!(modules, ["app.util", "std.io"]);
+    assert_eq!(result.imports[0].line, 1);
+
+    let greet = result
+        .function_calls
+        .iter()
+        .find(|call| call.name == "greet")
+        .expect("the qualified call should be found");
+    assert_eq!(greet.module.as_deref(), Some("util"));
+    assert_eq!(greet.line, 4);
+
+    let exported: Vec<(&str, bool)> = result
+        .exported_functions
+        .iter()
+        .map(|function| (function.name.as_str(), function.is_exported))
+        .collect();
+    assert!(exported.contains(&("helper", false)));
+    assert!(exported.contains(&("render", true)));
+
+    let resolver = get_resolver_for_file(&main_file)
+        .unwrap()
+        .expect("the pack should provide a resolver");
+    let util = resolver
+        .resolve_import("app.util", &main_file, &project)
+        .unwrap();
+    assert!(!util.is_external);
+    assert!(util.path.ends_with("src/app/util.wdgt"));
+
+    let ui = resolver
+        .resolve_import("app.ui", &main_file, &project)
+        .unwrap();
+    assert!(ui.path.ends_with("src/app/ui/init.wdgt"));
+
+    let io = resolver
+        .resolve_import("std.io", &main_file, &project)
+        .unwrap();
+    assert!(io.is_external);
+
+    assert!(resolver
+        .resolve_import("../../etc/passwd", &main_file, &project)
+        .map_or(true, |resolved| resolved.is_external));
+}
+
+#[test]
+fn test_language_pack_reports_missing_grammar() {
+    let temp_dir = TempDir::new().unwrap();
+    let pack_dir = temp_dir.path().join("widget");
+    create_widget_pack(&pack_dir);
+
+    let error = LanguagePack::load(&pack_dir).unwrap_err().to_string();
+    assert!(
+        error.contains("Failed to load grammar"),
+        "unexpected error: {error}"
+    );
+}
+
+#[test]
+fn test_language_pack_rejects_queries_not_matching_the_grammar() {
+    let temp_dir = TempDir::new().unwrap();
+    let pack_dir = temp_dir.path().join("widget");
+    let manifest = create_widget_pack(&pack_dir);
+    fs::write(pack_dir.join("types.scm"), "(no_such_node) @type_name\n").unwrap();
+
+    let error = LanguagePack::with_language(&pack_dir, manifest, tree_sitter_lua::language())
+        .unwrap_err()
+        .to_string();
+    assert!(
+        error.contains("type reference query"),
+        "unexpected error: {error}"
+    );
+}
+
+#[test]
+fn test_cli_fails_on_language_pack_without_grammar() {
+    use assert_cmd::Command;
+
+    let temp_dir = TempDir::new().unwrap();
+    let pack_dir = temp_dir.path().join("packs/widget");
+    create_widget_pack(&pack_dir);
+    fs::write(temp_dir.path().join("main.wdgt"), "return {}\n").unwrap();
+
+    let mut cmd = Command::cargo_bin("context-creator").unwrap();
+    cmd.current_dir(temp_dir.path())
+        .arg("--language-pack")
+        .arg(temp_dir.path().join("packs"))
+        .arg("--trace-imports")
+        .arg("--style")
+        .arg("paths");
+
+    let output = cmd.output().unwrap();
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Failed to load grammar"));
+}
+
+#[test]
+fn test_cli_fails_on_language_pack_directory_without_pack() {
+    use assert_cmd::Command;
+
+    let temp_dir = TempDir::new().unwrap();
+    fs::create_dir_all(temp_dir.path().join("packs/widget")).unwrap();
+    fs::write(temp_dir.path().join("main.py"), "print('hi')\n").unwrap();
+
+    let run = |pack_dir: &str, args: &[&str]| {
+        Command::cargo_bin("context-creator")
+            .unwrap()
+            .current_dir(temp_dir.path())
+            .arg("--language-pack")
+            .arg(temp_dir.path().join(pack_dir))
+            .args(args)
+            .args(["--style", "paths"])
+            .output()
+            .unwrap()
+    };
+
+    let output = run("pakcs", &["--trace-imports"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("does not exist"));
+
+    // Packs given on the command line load even when no semantic option is set
+    let output = run("packs", &[]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("No language pack in"));
+}
+
+#[test]
+fn test_cli_skips_broken_language_pack_in_home_directory() {
+    use assert_cmd::Command;
+
+    let temp_dir = TempDir::new().unwrap();
+    let home = temp_dir.path().join("home");
+    create_widget_pack(&home.join(".context-creator/languages/widget"));
+    let project = temp_dir.path().join("project");
+    fs::create_dir_all(&project).unwrap();
+    fs::write(project.join("main.py"), "print('hi')\n").unwrap();
+
+    // Plain runs don't load packs at all
+    let output = Command::cargo_bin("context-creator")
+        .unwrap()
+        .current_dir(&project)
+        .env("HOME", &home)
+        .args(["--style", "paths"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert!(!String::from_utf8_lossy(&output.stderr).contains("language pack"));
+
+    // Semantic runs skip the broken pack with a warning
+    let output = Command::cargo_bin("context-creator")
+        .unwrap()
+        .current_dir(&project)
+        .env("HOME", &home)
+        .args(["--trace-imports", "--style", "paths"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("main.py"));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Skipping language pack"));
+}
@@ -5,7 +5,12 @@ use std::path::Path;
 
 #[test]
 fn test_semantic_analyzers_are_registered_only_for_implemented_languages() {
-    for extension in ["rs", "py", "js", "jsx", "ts", "tsx", "go", "swift"] {
+    for extension in [
+        "rs", "py", "js", "jsx", "ts", "tsx", "go", "swift", "java", "c", "h", "cpp", "cc", "cxx",
+        "hpp", "cs", "rb", "php", "kt", "kts", "scala", "sc", "dart", "ex", "exs", "lua", "hs",
+        "r", "R", "jl", "elm", "vue", "svelte", "astro", "proto", "graphql", "gql", "sql", "tf",
+        "tfvars",
+    ] {
         let path = format!("sample.{extension}");
         assert!(
             get_analyzer_for_file(Path::new(&path)).unwrap().is_some(),
@@ -13,21 +18,55 @@ fn test_semantic_analyzers_are_registered_only_for_implemented_languages() {
         );
     }
 
-    for extension in [
-        "java", "cpp", "cc", "cxx", "hpp", "h", "c", "cs", "rb", "php", "kt", "kts", "scala", "sc",
-        "dart", "lua", "r", "R", "jl", "ex", "exs", "elm",
-    ] {
+    for extension in ["md", "json"] {
         let path = format!("sample.{extension}");
         assert!(
             get_analyzer_for_file(Path::new(&path)).unwrap().is_none(),
             "{extension} should not be advertised as semantically implemented"
         );
     }
+
+    for path in ["openapi.yaml", "api/swagger.json", "billing.openapi.yml"] {
+        assert!(
+            get_analyzer_for_file(Path::new(path)).unwrap().is_some(),
+            "{path} should be analyzed as an OpenAPI document"
+        );
+    }
+
+    for path in [
+        "Dockerfile",
+        "docker/api.Dockerfile",
+        "Makefile",
+        "build/rules.mk",
+        "web/package.json",
+        ".github/workflows/deploy.yml",
+        ".github/actions/setup/action.yaml",
+    ] {
+        assert!(
+            get_analyzer_for_file(Path::new(path)).unwrap().is_some(),
+            "{path} should be analyzed as a build file"
+        );
+        assert!(
+            get_resolver_for_file(Path::new(path)).unwrap().is_some(),
+            "{path} should resolve the files it runs"
+        );
+    }
+    for path in ["config/settings.yml", "tsconfig.json"] {
+        assert!(
+            get_analyzer_for_file(Path::new(path)).unwrap().is_none(),
+            "{path} should not be analyzed"
+        );
+    }
 }
 
 #[test]
 fn test_semantic_resolvers_are_registered_for_implemented_dependency_expansion() {
-    for extension in ["rs", "py", "js", "jsx", "ts", "tsx", "go", "swift"] {
+    for extension in [
+        "rs", "py", "js", "jsx", "ts", "tsx", "go", "swift", "java", "c", "h", "cpp", "cc", "cxx",
+        "hpp", "cs", "rb", "php", "kt", "kts", "scala", "sc", "dart", "ex", "exs", "lua", "hs",
+        "r", "R", "jl", "elm", "vue", "svelte", "astro", "proto", "graphql", "gql", "sql", "tf",
+        "tfvars",
+    ] {
         let path = format!("sample.{extension}");
         assert!(
             get_resolver_for_file(Path::new(&path)).unwrap().is_some(),
@@ -35,3 +74,364 @@ fn test_semantic_resolvers_are_registered_for_implemented_dependency_expansion()
         );
     }
 }
+
+#[test]
+fn test_c_family_extensions_have_one_owning_analyzer() {
+    use context_creator::core::semantic::analyzer::LanguageAnalyzer;
+    use context_creator::core::semantic::languages::{c::CAnalyzer, cpp::CppAnalyzer};
+
+    for extension in ["c", "h", "cpp", "cc", "cxx", "hpp", "hh", "hxx"] {
+        let path = format!("sample.{extension}");
+        let analyzer = get_analyzer_for_file(Path::new(&path)).unwrap().unwrap();
+        assert!(
+            analyzer.can_handle_extension(extension),
+            "the analyzer chosen for {extension} should claim it"
+        );
+        let owners = [
+            CAnalyzer::new().can_handle_extension(extension),
+            CppAnalyzer::new().can_handle_extension(extension),
+        ];
+        assert_eq!(
+            owners.iter().filter(|owned| **owned).count(),
+            1,
+            "{extension} should be claimed by one analyzer"
+        );
+    }
+
+    let header = get_analyzer_for_file(Path::new("sample.h"))
+        .unwrap()
+        .unwrap();
+    assert_eq!(header.language_name(), "Cpp");
+}
+
+#[test]
+fn test_single_file_component_scripts_keep_component_line_numbers() {
+    use context_creator::core::semantic::analyzer::SemanticContext;
+
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let root = temp_dir.path();
+    std::fs::create_dir_all(root.join("components")).unwrap();
+    std::fs::write(
+        root.join("components/UserCard.vue"),
+        "<template><p /></template>\n",
+    )
+    .unwrap();
+    let component = root.join("App.vue");
+    let content = r#"<template>
+  <UserCard />
+</template>
+
+<script setup lang="ts">
+import { formatName } from "./format";
+const name: string = formatName("ada");
+</script>
+"#;
+    std::fs::write(&component, content).unwrap();
+
+    let analyzer = get_analyzer_for_file(&component).unwrap().unwrap();
+    let context = SemanticContext::new(component.clone(), root.to_path_buf(), 3);
+    let result = analyzer
+        .analyze_file(&component, content, &context)
+        .unwrap();
+
+    let script_import = result
+        .imports
+        .iter()
+        .find(|import| import.module == "./format")
+        .expect("the script block import should be found");
+    assert_eq!(script_import.line, 6);
+
+    let template_import = result
+        .imports
+        .iter()
+        .find(|import| import.items == ["UserCard"])
+        .expect("the template component should be imported");
+    assert_eq!(template_import.module, "./components/UserCard.vue");
+    assert_eq!(template_import.line, 2);
+
+    let call = result
+        .function_calls
+        .iter()
+        .find(|call| call.name == "formatName")
+        .expect("calls in the script block should be found");
+    assert_eq!(call.line, 7);
+}
+
+#[test]
+fn test_schema_references_link_generated_names_and_tables() {
+    use context_creator::core::semantic::languages::schema::schema_references;
+
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let root = temp_dir.path();
+    std::fs::create_dir_all(root.join("proto")).unwrap();
+    std::fs::create_dir_all(root.join("migrations")).unwrap();
+    std::fs::write(
+        root.join("proto/orders.proto"),
+        "syntax = \"proto3\";\n// service Ignored {}\nmessage Order { string id = 1; }\nservice OrderService {\n  rpc CancelOrder(Order) returns (Order);\n}\n",
+    )
+    .unwrap();
+    std::fs::write(
+        root.join("migrations/001_init.sql"),
+        "CREATE TABLE IF NOT EXISTS shop.order_items (id INT);\n-- CREATE TABLE ignored (id INT);\n",
+    )
+    .unwrap();
+
+    let client = root.join("client.py");
+    let references = schema_references(
+        &client,
+        "stub = orders_pb2_grpc.OrderServiceStub(channel)\nstub.cancel_order(Order())\n",
+        root,
+    );
+    assert_eq!(references.len(), 1);
+    assert!(references[0].0.ends_with("proto/orders.proto"));
+    assert_eq!(references[0].1, ["CancelOrder", "OrderService"]);
+
+    let model = root.join("models.rb");
+    let references = schema_references(&model, "class OrderItem < ApplicationRecord\nend\n", root);
+    assert_eq!(references.len(), 1);
+    assert!(references[0].0.ends_with("migrations/001_init.sql"));
+    assert_eq!(references[0].1, ["order_items"]);
+
+    let query = root.join("report.go");
+    let references = schema_references(
+        &query,
+        "rows, err := db.Query(\"SELECT count(*) FROM shop.order_items\")\n",
+        root,
+    );
+    assert_eq!(references.len(), 1);
+
+    // A single word such as `Order` names too many unrelated things to link the schema
+    let unrelated = root.join("unrelated.ts");
+    assert!(schema_references(&unrelated, "const order: Order = load();\n", root).is_empty());
+}
+
+#[test]
+fn test_build_files_reference_the_files_their_commands_run() {
+    use context_creator::core::semantic::analyzer::SemanticContext;
+
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let root = temp_dir.path();
+    let analyze = |name: &str, content: &str| -> Vec<(String, usize)> {
+        let path = root.join(name);
+        let analyzer = get_analyzer_for_file(&path).unwrap().unwrap();
+        let context = SemanticContext::new(path.clone(), root.to_path_buf(), 3);
+        analyzer
+            .analyze_file(&path, content, &context)
+            .unwrap()
+            .imports
+            .into_iter()
+            .map(|import| (import.module, import.line))
+            .collect()
+    };
+
+    let dockerfile = analyze(
+        "Dockerfile",
+        "FROM python:3.12\nCOPY --from=builder /app/dist /app\nCOPY requirements.txt ./\nRUN pip install -r requirements.txt && \\\n    ./scripts/setup.sh --env=prod\nCMD [\"python\", \"app/main.py\"]\n",
+    );
+    assert_eq!(
+        dockerfile,
+        [
+            ("requirements.txt".to_string(), 3),
+            ("scripts/setup.sh".to_string(), 4),
+            ("app/main.py".to_string(), 6),
+        ]
+    );
+
+    let workflow = analyze(
+        ".github/workflows/ci.yml",
+        r#"jobs:
+  test:
+    steps:
+      - uses: actions/checkout@v4
+      - uses: ./.github/actions/setup
+      - run: |
+          make lint
+          ./scripts/test.sh
+      - working-directory: web
+        run: npm run build && node tools/bundle.js
+"#,
+    );
+    assert_eq!(
+        workflow,
+        [
+            (".github/actions/setup".to_string(), 5),
+            ("Makefile".to_string(), 7),
+            ("scripts/test.sh".to_string(), 8),
+            ("web/tools/bundle.js".to_string(), 10),
+        ]
+    );
+
+    let makefile = analyze(
+        "Makefile",
+        "include common.mk\nCC := gcc\n\nbuild: src/main.c | out\n\t@$(CC) -o out/app src/main.c\n\t-./scripts/sign.sh out/app\n",
+    );
+    let modules: Vec<&str> = makefile.iter().map(|(module, _)| module.as_str()).collect();
+    assert_eq!(
+        modules,
+        ["common.mk", "src/main.c", "out/app", "scripts/sign.sh"]
+    );
+
+    let docker_build = analyze(
+        ".github/workflows/image.yml",
+        "steps:\n  - run: docker build --build-arg ENV=ci -f docker/api.Dockerfile .\n  - run: cd web && docker build --no-cache .\n  - run: make -f build.mk release\n",
+    );
+    assert_eq!(
+        docker_build,
+        [
+            ("docker/api.Dockerfile".to_string(), 2),
+            ("web/Dockerfile".to_string(), 3),
+            ("build.mk".to_string(), 4),
+        ]
+    );
+
+    let package = analyze(
+        "package.json",
+        "{\n  \"scripts\": {\n    \"build\": \"tsc -p tsconfig.build.json\",\n    \"seed\": \"cd db && node seeds/run.js\"\n  }\n}\n",
+    );
+    assert_eq!(
+        package,
+        [
+            ("tsconfig.build.json".to_string(), 3),
+            ("db/seeds/run.js".to_string(), 4),
+        ]
+    );
+}
+
+#[test]
+fn test_terraform_references_resolve_to_declaring_files() {
+    use context_creator::core::semantic::analyzer::SemanticContext;
+
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let root = temp_dir.path().canonicalize().unwrap();
+    let env = root.join("envs/prod");
+    std::fs::create_dir_all(&env).unwrap();
+    std::fs::create_dir_all(root.join("modules/network")).unwrap();
+    std::fs::write(
+        env.join("variables.tf"),
+        "variable \"region\" {}\nvariable \"owner\" {}\n",
+    )
+    .unwrap();
+    std::fs::write(env.join("locals.tf"), "locals {\n  name = \"prod\"\n}\n").unwrap();
+    std::fs::write(
+        env.join("data.tf"),
+        "data \"aws_ami\" \"base\" { most_recent = true }\n",
+    )
+    .unwrap();
+    std::fs::write(
+        root.join("modules/network/main.tf"),
+        "resource \"aws_vpc\" \"this\" {}\n",
+    )
+    .unwrap();
+    std::fs::write(
+        root.join("modules/network/outputs.tf"),
+        "output \"vpc_id\" { value = aws_vpc.this.id }\n",
+    )
+    .unwrap();
+
+    let main_file = env.join("main.tf");
+    let content = r#"// var.commented_out
+module "network" { source = "../../modules/network" }
+
+module "dns" {
+  source = "cloudposse/route53/aws"
+}
+
+resource "aws_instance" "web" {
+  ami       = data.aws_ami.base.id
+  subnet_id = module.network.vpc_id
+  user_data = <<-EOT
+    echo ${local.name}
+  EOT
+  tags = { Name = "web-${var.region}" }
+}
+"#;
+    std::fs::write(&main_file, content).unwrap();
+
+    let analyzer = get_analyzer_for_file(&main_file).unwrap().unwrap();
+    let context = SemanticContext::new(main_file.clone(), root.clone(), 3);
+    let result = analyzer
+        .analyze_file(&main_file, content, &context)
+        .unwrap();
+
+    let imports: Vec<(&str, Vec<&str>, usize)> = result
+        .imports
+        .iter()
+        .map(|import| {
+            (
+                import.module.as_str(),
+                import.items.iter().map(String::as_str).collect(),
+                import.line,
+            )
+        })
+        .collect();
+    assert_eq!(
+        imports,
+        [
+            ("../../modules/network/main.tf", vec!["module.network"], 2),
+            (
+                "../../modules/network/outputs.tf",
+                vec!["module.network", "module.network.vpc_id"],
+                2
+            ),
+            ("./data.tf", vec!["data.aws_ami.base"], 9),
+            ("./locals.tf", vec!["local.name"], 12),
+            ("./variables.tf", vec!["var.region"], 14),
+            ("cloudposse/route53/aws", vec!["module.dns"], 4),
+        ]
+    );
+
+    let resolver = get_resolver_for_file(&main_file).unwrap().unwrap();
+    let outputs = resolver
+        .resolve_import("../../modules/network/outputs.tf", &main_file, &root)
+        .unwrap();
+    assert_eq!(outputs.path, root.join("modules/network/outputs.tf"));
+    assert!(
+        resolver
+            .resolve_import("cloudposse/route53/aws", &main_file, &root)
+            .unwrap()
+            .is_external
+    );
+}
+
+#[test]
+fn test_c_includes_follow_quoted_compile_commands_and_skip_outside_headers() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let root = temp_dir.path().join("firmware");
+    let system = temp_dir.path().join("sysroot/include");
+    std::fs::create_dir_all(root.join("app")).unwrap();
+    std::fs::create_dir_all(root.join("vendor sdk/include")).unwrap();
+    std::fs::create_dir_all(&system).unwrap();
+    std::fs::write(root.join("app/main.c"), "int main(void) { return 0; }\n").unwrap();
+    std::fs::write(
+        root.join("vendor sdk/include/sdk.h"),
+        "void sdk_init(void);\n",
+    )
+    .unwrap();
+    std::fs::write(system.join("board_support.h"), "void bsp_init(void);\n").unwrap();
+    std::fs::write(
+        root.join("compile_commands.json"),
+        serde_json::json!([{
+            "directory": ".",
+            "file": "app/main.c",
+            "command": format!(
+                "cc -I\"vendor sdk/include\" -I '{}' -c app/main.c",
+                system.display()
+            ),
+        }])
+        .to_string(),
+    )
+    .unwrap();
+
+    let main_file = root.join("app/main.c");
+    let resolver = get_resolver_for_file(&main_file).unwrap().unwrap();
+
+    let sdk = resolver.resolve_import("sdk.h", &main_file, &root).unwrap();
+    assert!(!sdk.is_external);
+    assert!(sdk.path.ends_with("vendor sdk/include/sdk.h"));
+
+    // Headers under an include directory outside the project are external, not errors
+    let board = resolver
+        .resolve_import("board_support.h", &main_file, &root)
+        .unwrap();
+    assert!(board.is_external);
+}
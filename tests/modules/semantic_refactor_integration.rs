@@ -163,6 +163,7 @@ fn test_new_modular_architecture_works_together() {
         trace_imports: true,
         include_types: true,
         include_functions: true,
+        include_dirs: Vec::new(),
     };
 
     let file_paths: Vec<_> = files.iter().map(|f| f.path.clone()).collect();
@@ -432,6 +433,7 @@ fn test_modules_work_together_seamlessly() {
                 trace_imports: true,
                 include_types: true,
                 include_functions: true,
+                include_dirs: Vec::new(),
             },
             &valid_files,
         )
@@ -7,6 +7,7 @@
 
 use context_creator::cli::Config;
 use context_creator::core::cache::FileCache;
+use context_creator::core::file_expander::InclusionKind;
 use context_creator::core::walker::{walk_directory, WalkOptions};
 use std::collections::HashMap;
 use std::fs;
@@ -900,3 +901,93 @@ export function fetchData(endpoint) {
     assert!(!file_names.contains(&"frontend.js".to_string()));
     assert!(!file_names.contains(&"api.js".to_string()));
 }
+
+#[test]
+fn test_expansion_explains_which_symbols_included_each_file() {
+    let temp_dir = TempDir::new().unwrap();
+    let root = temp_dir.path();
+    fs::create_dir_all(root.join(".git")).unwrap();
+
+    fs::write(
+        root.join("server.py"),
+        r#"from handlers import handle_request
+
+def serve(request):
+    return handle_request(request)
+"#,
+    )
+    .unwrap();
+    fs::write(
+        root.join("handlers.py"),
+        r#"from config import Settings
+
+def handle_request(request):
+    settings = Settings()
+    return settings.debug
+"#,
+    )
+    .unwrap();
+    fs::write(
+        root.join("config.py"),
+        r#"class Settings:
+    debug = False
+"#,
+    )
+    .unwrap();
+
+    let config = Config {
+        paths: Some(vec![root.to_path_buf()]),
+        include: Some(vec!["server.py".to_string()]),
+        trace_imports: true,
+        semantic_depth: 2,
+        ..Default::default()
+    };
+
+    let walk_options = WalkOptions::from_config(&config).unwrap();
+    let cache = Arc::new(FileCache::new());
+    let files_map: HashMap<PathBuf, _> = walk_directory(root, walk_options.clone())
+        .unwrap()
+        .into_iter()
+        .map(|file| (file.path.clone(), file))
+        .collect();
+    let (expanded_files, mut inclusions) =
+        context_creator::core::file_expander::expand_file_list_with_inclusions(
+            files_map,
+            &config,
+            &cache,
+            &walk_options,
+            None,
+        )
+        .unwrap();
+    context_creator::core::file_expander::explain_inclusions(
+        &mut inclusions,
+        &expanded_files,
+        &cache,
+    );
+
+    assert_eq!(inclusions.len(), 2);
+    let explanation = |name: &str| {
+        inclusions
+            .iter()
+            .find(|inclusion| inclusion.path.ends_with(name))
+            .unwrap_or_else(|| panic!("no explanation for {name}: {inclusions:?}"))
+    };
+
+    let handlers = explanation("handlers.py");
+    assert!(handlers.source.ends_with("server.py"));
+    assert_eq!(handlers.kind, InclusionKind::Import);
+    assert_eq!(handlers.symbol.as_deref(), Some("handle_request"));
+    assert_eq!(handlers.line, Some(1));
+    assert_eq!(handlers.links[0].from.name, "serve");
+    assert_eq!(handlers.links[0].to.name, "handle_request");
+    assert_eq!(
+        handlers.reason(),
+        "function `serve` in server.py calls function `handle_request` in handlers.py (line 4)"
+    );
+
+    let settings = explanation("config.py");
+    assert!(settings.source.ends_with("handlers.py"));
+    assert_eq!(settings.symbol.as_deref(), Some("Settings"));
+    assert_eq!(settings.links[0].from.name, "handle_request");
+    assert_eq!(settings.links[0].to.name, "Settings");
+}